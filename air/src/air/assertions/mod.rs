@@ -13,6 +13,9 @@ use math::FieldElement;
 
 use crate::errors::AssertionError;
 
+mod sequence;
+pub use sequence::PublicSequence;
+
 #[cfg(test)]
 mod tests;
 
@@ -21,6 +24,7 @@ mod tests;
 
 const MIN_STRIDE_LENGTH: usize = 2;
 const NO_STRIDE: usize = 0;
+const CONTIGUOUS_STRIDE: usize = 1;
 
 // ASSERTION
 // ================================================================================================
@@ -28,7 +32,7 @@ const NO_STRIDE: usize = 0;
 /// An assertion made against an execution trace.
 ///
 /// An assertion is always placed against a single column of an execution trace, but can cover
-/// multiple steps and multiple values. Specifically, there are three kinds of assertions:
+/// multiple steps and multiple values. Specifically, there are four kinds of assertions:
 ///
 /// 1. **Single** assertion - which requires that a value in a single cell of an execution trace
 ///    is equal to the specified value.
@@ -42,11 +46,16 @@ const NO_STRIDE: usize = 0;
 ///    lengths equal to powers of two. For example, we can specify that values in a column must
 ///    be equal to a sequence 1, 2, 3, 4 at steps 0, 8, 16, 24. That is, value at step 0 should be
 ///    equal to 1, value at step 8 should be equal to 2 etc.
+/// 4. **Contiguous** assertion - which requires that cells at consecutive steps of a single
+///    column are equal to the values from the provided list. The number of values can be
+///    arbitrary. For example, we can specify that values in a column must be equal to a sequence
+///    5, 7, 9 at steps 2, 3, 4. This is useful for binding a column to a public sequence of
+///    values (e.g., program code or a stream of public inputs, see [PublicSequence]).
 ///
 /// Note that single and periodic assertions are succinct. That is, a verifier can evaluate them
-/// very efficiently. However, sequence assertions have liner complexity in the number of
-/// asserted values. Though, unless many thousands of values are asserted, practical impact of
-/// this linear complexity should be negligible.
+/// very efficiently. However, sequence and contiguous assertions have liner complexity in the
+/// number of asserted values. Though, unless many thousands of values are asserted, practical
+/// impact of this linear complexity should be negligible.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Assertion<E: FieldElement> {
     pub(super) column: usize,
@@ -122,6 +131,32 @@ impl<E: FieldElement> Assertion<E> {
         }
     }
 
+    /// Returns a multi-value assertion against consecutive cells of a single column.
+    ///
+    /// The returned assertion requires that values in the specified `column` must be equal to
+    /// the provided `values` at steps `first_step`, `first_step + 1`, etc. until all values have
+    /// been consumed. Unlike for sequence assertions, the number of values does not need to be a
+    /// power of two. If only a single value is provided, a single-value assertion is returned.
+    ///
+    /// # Panics
+    /// Panics if `values` is empty.
+    pub fn contiguous(column: usize, first_step: usize, values: Vec<E>) -> Self {
+        assert!(
+            !values.is_empty(),
+            "invalid assertion for column {column}: number of asserted values must be greater than zero"
+        );
+        Assertion {
+            column,
+            first_step,
+            stride: if values.len() == 1 {
+                NO_STRIDE
+            } else {
+                CONTIGUOUS_STRIDE
+            },
+            values,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -139,7 +174,7 @@ impl<E: FieldElement> Assertion<E> {
 
     /// Returns the interval at which the assertion repeats in the execution trace.
     ///
-    /// For single value assertions, this will be 0.
+    /// For single value assertions, this will be 0; for contiguous assertions, this will be 1.
     pub fn stride(&self) -> usize {
         self.stride
     }
@@ -161,9 +196,14 @@ impl<E: FieldElement> Assertion<E> {
         self.stride != NO_STRIDE && self.values.len() == 1
     }
 
-    /// Returns true if this is a sequence assertion (many values, many steps).
+    /// Returns true if this is a sequence assertion (many values, many evenly spaced steps).
     pub fn is_sequence(&self) -> bool {
-        self.values.len() > 1
+        self.values.len() > 1 && self.stride != CONTIGUOUS_STRIDE
+    }
+
+    /// Returns true if this is a contiguous assertion (many values, many consecutive steps).
+    pub fn is_contiguous(&self) -> bool {
+        self.stride == CONTIGUOUS_STRIDE
     }
 
     // PUBLIC METHODS
@@ -179,6 +219,12 @@ impl<E: FieldElement> Assertion<E> {
         if self.first_step == other.first_step {
             return true;
         }
+        if self.is_contiguous() {
+            return self.steps().any(|step| other.covers_step(step));
+        }
+        if other.is_contiguous() {
+            return other.steps().any(|step| self.covers_step(step));
+        }
         if self.stride == other.stride {
             return false;
         }
@@ -223,6 +269,7 @@ impl<E: FieldElement> Assertion<E> {
     /// * For single assertion, `first_step` >= `trace_length`.
     /// * For periodic assertion, `stride` > `trace_length`.
     /// * For sequence assertion, `num_values` * `stride` != `trace_length`;
+    /// * For contiguous assertion, `first_step` + `num_values` > `trace_length`.
     pub fn validate_trace_length(&self, trace_length: usize) -> Result<(), AssertionError> {
         if !trace_length.is_power_of_two() {
             return Err(AssertionError::TraceLengthNotPowerOfTwo(trace_length));
@@ -238,6 +285,14 @@ impl<E: FieldElement> Assertion<E> {
             if self.stride > trace_length {
                 return Err(AssertionError::TraceLengthTooShort(self.stride, trace_length));
             }
+        } else if self.is_contiguous() {
+            let last_step = self.first_step + self.values.len() - 1;
+            if last_step >= trace_length {
+                return Err(AssertionError::TraceLengthTooShort(
+                    (last_step + 1).next_power_of_two(),
+                    trace_length,
+                ));
+            }
         } else {
             let expected_length = self.values.len() * self.stride;
             if expected_length != trace_length {
@@ -277,7 +332,8 @@ impl<E: FieldElement> Assertion<E> {
     ///
     /// * For single-value assertions, this will always be one.
     /// * For periodic assertions this will be equal to `trace_length` / `stride`.
-    /// * For sequence assertions this will be equal to the number of asserted values.
+    /// * For sequence and contiguous assertions this will be equal to the number of asserted
+    ///   values.
     ///
     /// # Panics
     /// Panics if the specified trace length is not valid for this assertion.
@@ -293,6 +349,31 @@ impl<E: FieldElement> Assertion<E> {
             self.values.len()
         }
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over the steps covered by this contiguous assertion.
+    fn steps(&self) -> core::ops::Range<usize> {
+        debug_assert!(self.is_contiguous(), "not a contiguous assertion");
+        self.first_step..self.first_step + self.values.len()
+    }
+
+    /// Returns true if this assertion is placed against the specified step (assuming the
+    /// execution trace is long enough to include the step).
+    fn covers_step(&self, step: usize) -> bool {
+        if step < self.first_step {
+            return false;
+        }
+        let offset = step - self.first_step;
+        if self.is_single() {
+            offset == 0
+        } else if self.is_periodic() {
+            offset % self.stride == 0
+        } else {
+            offset % self.stride == 0 && offset / self.stride < self.values.len()
+        }
+    }
 }
 
 // OTHER TRAIT IMPLEMENTATIONS
@@ -0,0 +1,139 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+
+use crypto::{ElementHasher, MerkleTree};
+use math::{FieldElement, ToElements};
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::Assertion;
+
+// PUBLIC SEQUENCE
+// ================================================================================================
+
+/// A sequence of public values against which a column of an execution trace can be asserted.
+///
+/// Public sequences are intended for binding a column of an execution trace to values known to
+/// the verifier - e.g., program code or a stream of public inputs. A sequence can be committed
+/// to via [commitment()](PublicSequence::commitment); this way, the verifier needs to know only
+/// the commitment in advance, and can make sure that the sequence received together with a
+/// proof matches the commitment via [verify_commitment()](PublicSequence::verify_commitment).
+///
+/// A sequence is placed against an execution trace via an [Assertion] built by
+/// [to_assertion()](PublicSequence::to_assertion). The framework then interpolates the
+/// sequence into a value polynomial, and builds a divisor which vanishes on the steps covered
+/// by the sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicSequence<E: FieldElement> {
+    values: Vec<E>,
+}
+
+impl<E: FieldElement> PublicSequence<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new public sequence consisting of the provided values.
+    ///
+    /// # Panics
+    /// Panics if `values` is empty.
+    pub fn new(values: Vec<E>) -> Self {
+        assert!(!values.is_empty(), "a public sequence must contain at least one value");
+        Self { values }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of values in this sequence.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if this sequence contains no values; this is never the case for a valid
+    /// sequence.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the values of this sequence.
+    pub fn values(&self) -> &[E] {
+        &self.values
+    }
+
+    // COMMITMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to this sequence.
+    ///
+    /// The commitment is computed by hashing each value into a leaf of a Merkle tree (padding
+    /// the leaves with default digests to the next power of two), and then merging the root of
+    /// the tree with the length of the sequence.
+    pub fn commitment<H>(&self) -> H::Digest
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        let num_leaves = self.values.len().next_power_of_two().max(2);
+        let mut leaves = self
+            .values
+            .iter()
+            .map(|value| H::hash_elements(core::slice::from_ref(value)))
+            .collect::<Vec<_>>();
+        leaves.resize(num_leaves, H::Digest::default());
+
+        let tree = MerkleTree::<H>::new(leaves).expect("failed to build Merkle tree");
+        H::merge_with_int(*tree.root(), self.values.len() as u64)
+    }
+
+    /// Returns true if the specified commitment is a commitment to this sequence.
+    pub fn verify_commitment<H>(&self, commitment: &H::Digest) -> bool
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        &self.commitment::<H>() == commitment
+    }
+
+    // ASSERTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an assertion which requires values in the specified `column` to be equal to the
+    /// values of this sequence at steps `first_step`, `first_step + 1`, etc.
+    pub fn to_assertion(&self, column: usize, first_step: usize) -> Assertion<E> {
+        Assertion::contiguous(column, first_step, self.values.clone())
+    }
+}
+
+// TRAIT IMPLEMENTATIONS
+// ================================================================================================
+
+impl<E: FieldElement> ToElements<E::BaseField> for PublicSequence<E> {
+    fn to_elements(&self) -> Vec<E::BaseField> {
+        E::slice_as_base_elements(&self.values).to_vec()
+    }
+}
+
+impl<E: FieldElement> Serializable for PublicSequence<E> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.values.len() as u32);
+        target.write_many(&self.values);
+    }
+}
+
+impl<E: FieldElement> Deserializable for PublicSequence<E> {
+    /// Reads a public sequence from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid sequence could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_values = source.read_u32()? as usize;
+        if num_values == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a public sequence must contain at least one value".to_string(),
+            ));
+        }
+        let values = source.read_many(num_values)?;
+        Ok(Self { values })
+    }
+}
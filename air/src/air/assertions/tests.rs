@@ -5,10 +5,12 @@
 
 use alloc::vec::Vec;
 
+use crypto::hashers::Blake3_256;
 use math::{fields::f128::BaseElement, FieldElement};
 use rand_utils::{rand_value, rand_vector};
+use utils::{Deserializable, Serializable};
 
-use super::{Assertion, AssertionError};
+use super::{Assertion, AssertionError, PublicSequence};
 
 // SINGLE ASSERTIONS
 // ================================================================================================
@@ -176,6 +178,76 @@ fn sequence_assertion_num_values_not_power_of_two() {
         Assertion::sequence(3, 2, 4, vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::ONE]);
 }
 
+// CONTIGUOUS ASSERTIONS
+// ================================================================================================
+
+#[test]
+fn contiguous_assertion() {
+    let values = rand_vector::<BaseElement>(3);
+    let a = Assertion::contiguous(2, 5, values.clone());
+    assert_eq!(2, a.column);
+    assert_eq!(5, a.first_step);
+    assert_eq!(values, a.values);
+    assert_eq!(1, a.stride);
+    assert!(a.is_contiguous());
+    assert!(!a.is_sequence());
+    assert_eq!(3, a.get_num_steps(8));
+    assert_eq!(3, a.get_num_steps(16));
+
+    let mut steps = Vec::new();
+    a.apply(8, |step, val| {
+        assert_eq!(values[step - 5], val);
+        steps.push(step);
+    });
+    assert_eq!(vec![5, 6, 7], steps);
+
+    assert_eq!(Ok(()), a.validate_trace_length(8));
+    assert_eq!(Err(AssertionError::TraceLengthTooShort(8, 4)), a.validate_trace_length(4));
+
+    // a contiguous assertion with a single value is a single-value assertion
+    let a = Assertion::contiguous(2, 5, vec![values[0]]);
+    assert_eq!(Assertion::single(2, 5, values[0]), a);
+}
+
+#[test]
+#[should_panic(
+    expected = "invalid assertion for column 3: number of asserted values must be greater than zero"
+)]
+fn contiguous_assertion_empty_values() {
+    Assertion::<BaseElement>::contiguous(3, 0, Vec::new());
+}
+
+// PUBLIC SEQUENCES
+// ================================================================================================
+
+#[test]
+fn public_sequence_commitment() {
+    let values = rand_vector::<BaseElement>(5);
+    let sequence = PublicSequence::new(values.clone());
+    assert_eq!(5, sequence.len());
+    assert_eq!(Assertion::contiguous(1, 2, values.clone()), sequence.to_assertion(1, 2));
+
+    let commitment = sequence.commitment::<Blake3_256<BaseElement>>();
+    assert!(sequence.verify_commitment::<Blake3_256<BaseElement>>(&commitment));
+
+    // changing any of the values changes the commitment
+    let mut other_values = values.clone();
+    other_values[4] += BaseElement::ONE;
+    let other = PublicSequence::new(other_values);
+    assert!(!other.verify_commitment::<Blake3_256<BaseElement>>(&commitment));
+
+    // padding the sequence with zeros changes the commitment
+    let mut other_values = values;
+    other_values.push(BaseElement::ZERO);
+    let other = PublicSequence::new(other_values);
+    assert!(!other.verify_commitment::<Blake3_256<BaseElement>>(&commitment));
+
+    // commitment is preserved through serialization
+    let bytes = sequence.to_bytes();
+    let sequence = PublicSequence::<BaseElement>::read_from_bytes(&bytes).unwrap();
+    assert!(sequence.verify_commitment::<Blake3_256<BaseElement>>(&commitment));
+}
+
 // OVERLAPPING ASSERTIONS
 // ================================================================================================
 
@@ -339,4 +411,39 @@ fn assertion_overlap() {
     let b = Assertion::periodic(0, 0, 16, BaseElement::ONE);
     assert!(!a.overlaps_with(&b));
     assert!(!b.overlaps_with(&a));
+
+    // ----- contiguous overlap -------------------------------------------------------------------
+
+    let values = vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::ONE];
+
+    let a = Assertion::contiguous(3, 5, values.clone());
+    let b = Assertion::single(3, 7, BaseElement::ONE);
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    let b = Assertion::periodic(3, 6, 8, BaseElement::ONE);
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    let b = Assertion::contiguous(3, 3, values.clone());
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    // different columns: no overlap
+    let b = Assertion::contiguous(1, 5, values.clone());
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    // disjoint steps: no overlap
+    let b = Assertion::single(3, 8, BaseElement::ONE);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    let b = Assertion::periodic(3, 0, 8, BaseElement::ONE);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    let b = Assertion::contiguous(3, 8, values);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
 }
@@ -63,8 +63,20 @@ where
         // polynomial; but for multi-value assertions, we need to interpolate the values
         // into a polynomial using inverse FFT
         let mut poly_offset = (0, F::BaseField::ONE);
+        let is_contiguous = assertion.is_contiguous();
         let mut poly = assertion.values;
-        if poly.len() > 1 {
+        if is_contiguous {
+            // contiguous steps do not form a subgroup of the trace domain, and thus, we cannot
+            // use FFT to interpolate the values; instead, we interpolate the values directly and
+            // pad the polynomial with zero coefficients to the next power of two so that it can
+            // be evaluated via FFT by the prover
+            let g = inv_g.inv();
+            let xs = (assertion.first_step..assertion.first_step + poly.len())
+                .map(|step| F::from(g.exp((step as u64).into())))
+                .collect::<Vec<_>>();
+            poly = polynom::interpolate(&xs, &poly, false);
+            poly.resize(poly.len().next_power_of_two(), F::ZERO);
+        } else if poly.len() > 1 {
             // get the twiddles from the map; if twiddles for this domain haven't been built
             // yet, build them and add them to the map
             let inv_twiddles = twiddle_map
@@ -99,15 +99,24 @@ where
     /// * $\alpha_i$ are random field elements. In the interactive version of the
     ///   protocol, these are provided by the verifier.
     pub fn evaluate_at(&self, state: &[E], x: E) -> E {
+        let numerator = self.evaluate_numerator_at(state, x);
+        let denominator = self.divisor.evaluate_at(x);
+
+        numerator / denominator
+    }
+
+    /// Evaluates all constraints in this group at the specified point `x` and returns their
+    /// random linear combination without dividing it by the divisor of this constraint group.
+    ///
+    /// This is useful when evaluating multiple constraint groups at the same point: divisors of
+    /// all groups can then be inverted at once via batch inversion.
+    pub fn evaluate_numerator_at(&self, state: &[E], x: E) -> E {
         let mut numerator = E::ZERO;
         for constraint in self.constraints().iter() {
             let trace_value = state[constraint.column()];
             let evaluation = constraint.evaluate_at(x, trace_value);
             numerator += evaluation * *constraint.cc();
         }
-
-        let denominator = self.divisor.evaluate_at(x);
-
-        numerator / denominator
+        numerator
     }
 }
@@ -163,10 +163,12 @@ where
     E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
 {
     // iterate over all assertions, which are sorted first by stride and then by first_step
-    // in ascending order
+    // in ascending order; the number of asserted steps is also a part of the key because
+    // contiguous assertions starting on the same step may cover a different number of steps
     let mut groups = BTreeMap::new();
     for (assertion, &cc) in assertions.into_iter().zip(composition_coefficients) {
-        let key = (assertion.stride(), assertion.first_step());
+        let num_steps = assertion.get_num_steps(context.trace_len());
+        let key = (assertion.stride(), assertion.first_step(), num_steps);
         let group = groups.entry(key).or_insert_with(|| {
             BoundaryConstraintGroup::new(ConstraintDivisor::from_assertion(
                 &assertion,
@@ -175,6 +175,42 @@ fn boundary_constraint_from_sequence_assertion() {
     );
 }
 
+#[test]
+fn boundary_constraint_from_contiguous_assertion() {
+    let mut test_prng = build_prng();
+    let (inv_g, mut twiddle_map, mut prng) = build_constraint_params(16);
+    let g = inv_g.inv();
+
+    // constraint should be built correctly for column 1, steps 3, 4, 5, 6, 7
+    let values = rand_vector::<BaseElement>(5);
+    let assertion = Assertion::contiguous(1, 3, values.clone());
+    let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
+        assertion,
+        inv_g,
+        &mut twiddle_map,
+        prng.draw().unwrap(),
+    );
+    assert_eq!(1, constraint.column());
+    assert_eq!(8, constraint.poly().len());
+    assert_eq!((0, BaseElement::ONE), constraint.poly_offset());
+    assert_eq!(&test_prng.draw::<BaseElement>().unwrap(), constraint.cc());
+    assert!(twiddle_map.is_empty());
+
+    // the value polynomial should evaluate to the asserted values at the asserted steps
+    for (i, &value) in values.iter().enumerate() {
+        let x = g.exp((3 + i) as u64);
+        assert_eq!(value, polynom::eval(constraint.poly(), x));
+    }
+
+    // contiguous value constraints should evaluate to trace_value - constraint_poly(x)
+    let x = rand_value::<BaseElement>();
+    let trace_value = rand_value::<BaseElement>();
+    assert_eq!(
+        trace_value - polynom::eval(constraint.poly(), x),
+        constraint.evaluate_at(x, trace_value)
+    );
+}
+
 // PREPARE ASSERTIONS
 // ================================================================================================
 
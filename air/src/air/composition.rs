@@ -0,0 +1,238 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::cmp;
+
+use math::{FieldElement, StarkField, ToElements};
+use utils::{ByteWriter, Serializable};
+
+use super::{Air, AirContext, Assertion, EvaluationFrame, TraceInfo};
+use crate::ProofOptions;
+
+// AIR COMPOSITION
+// ================================================================================================
+/// An AIR which joins two independent AIRs into a single computation, so that both can be proven
+/// with a single proof.
+///
+/// The execution trace of the composition consists of the columns of the first component
+/// followed by the columns of the second component; such a trace can be described via
+/// [TraceInfo::compose()]. Trace infos of the components are stored in the metadata of the
+/// composed trace info, and thus, each component is instantiated with its own trace info and its
+/// own public inputs (see [ComposedInputs]).
+///
+/// All column references of the second component are shifted automatically: its assertions are
+/// moved to the columns of the composed trace it occupies, and its transition constraints are
+/// evaluated against the part of the evaluation frame which holds its columns. Transition
+/// constraints, assertions, and periodic columns (together with their offsets) of the
+/// composition are those of the first component followed by those of the second component.
+///
+/// More than two AIRs can be joined by nesting compositions (e.g.,
+/// `AirComposition<AirComposition<A, B>, C>`).
+///
+/// Components must have traces of the same length, must use the same evaluation frame offsets,
+/// and cannot have auxiliary trace segments. If the components specify different numbers of
+/// transition exemptions, the composition uses the larger number, and the constraints of the
+/// other component retain their original divisors.
+pub struct AirComposition<A1, A2>
+where
+    A1: Air,
+    A2: Air<BaseField = A1::BaseField>,
+{
+    context: AirContext<A1::BaseField>,
+    first: A1,
+    second: A2,
+    num_first_periodic_columns: usize,
+}
+
+impl<A1, A2> AirComposition<A1, A2>
+where
+    A1: Air,
+    A2: Air<BaseField = A1::BaseField>,
+{
+    /// Returns the first component of this composition.
+    pub fn first(&self) -> &A1 {
+        &self.first
+    }
+
+    /// Returns the second component of this composition.
+    pub fn second(&self) -> &A2 {
+        &self.second
+    }
+}
+
+impl<A1, A2> Air for AirComposition<A1, A2>
+where
+    A1: Air,
+    A2: Air<BaseField = A1::BaseField>,
+{
+    type BaseField = A1::BaseField;
+    type PublicInputs = ComposedInputs<A1::PublicInputs, A2::PublicInputs>;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    /// Instantiates both components from the trace infos stored in the metadata of `trace_info`
+    /// and from their respective public inputs, and combines their contexts.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `trace_info` was not created via [TraceInfo::compose()].
+    /// * The components use different evaluation frame offsets.
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let (first_info, second_info) = trace_info.decompose();
+        let first = A1::new(first_info, pub_inputs.first, options.clone());
+        let second = A2::new(second_info, pub_inputs.second, options.clone());
+
+        let (first_context, second_context) = (first.context(), second.context());
+        assert_eq!(
+            first_context.frame_offsets, second_context.frame_offsets,
+            "components of an AIR composition must use the same evaluation frame offsets"
+        );
+
+        let mut degrees = first_context.main_transition_constraint_degrees.clone();
+        degrees.extend_from_slice(&second_context.main_transition_constraint_degrees);
+        let num_assertions = first_context.num_main_assertions + second_context.num_main_assertions;
+        let num_exemptions = cmp::max(
+            first_context.num_transition_exemptions,
+            second_context.num_transition_exemptions,
+        );
+        let mut context = AirContext::new(trace_info, degrees, num_assertions, options)
+            .with_frame_offsets(first_context.frame_offsets.clone())
+            .set_num_transition_exemptions(num_exemptions);
+
+        // carry over custom divisors of the components; constraints of a component with fewer
+        // transition exemptions than the composition keep their original default divisor
+        let num_first_constraints = first_context.num_main_transition_constraints();
+        for (offset, component) in [(0, first_context), (num_first_constraints, second_context)] {
+            for i in 0..component.num_main_transition_constraints() {
+                if component.transition_divisors.contains_key(&i)
+                    || component.num_transition_exemptions != num_exemptions
+                {
+                    context = context
+                        .with_transition_divisor(offset + i, component.get_transition_divisor(i));
+                }
+            }
+        }
+
+        let num_first_periodic_columns = first.get_periodic_column_values().len();
+        Self {
+            context,
+            first,
+            second,
+            num_first_periodic_columns,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let (first_frame, second_frame) =
+            split_frame(frame, self.first.trace_info().main_trace_width());
+        let (first_periodic_values, second_periodic_values) =
+            periodic_values.split_at(self.num_first_periodic_columns);
+        let (first_result, second_result) =
+            result.split_at_mut(self.first.context().num_main_transition_constraints());
+
+        self.first
+            .evaluate_transition(&first_frame, first_periodic_values, first_result);
+        self.second
+            .evaluate_transition(&second_frame, second_periodic_values, second_result);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let column_offset = self.first.trace_info().main_trace_width();
+        let mut result = self.first.get_assertions();
+        result.extend(self.second.get_assertions().into_iter().map(|mut assertion| {
+            assertion.column += column_offset;
+            assertion
+        }));
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut result = self.first.get_periodic_column_values();
+        result.extend(self.second.get_periodic_column_values());
+        result
+    }
+
+    fn get_periodic_column_offsets(&self) -> Vec<usize> {
+        let (first, second) = (
+            self.first.get_periodic_column_offsets(),
+            self.second.get_periodic_column_offsets(),
+        );
+        if first.is_empty() && second.is_empty() {
+            return Vec::new();
+        }
+
+        // an empty vector of offsets means that all cycles of a component start at step 0
+        let mut result = first;
+        result.resize(self.num_first_periodic_columns, 0);
+        if second.is_empty() {
+            result.resize(
+                self.num_first_periodic_columns + self.second.get_periodic_column_values().len(),
+                0,
+            );
+        } else {
+            result.extend(second);
+        }
+        result
+    }
+}
+
+// COMPOSED INPUTS
+// ================================================================================================
+/// Public inputs of an [AirComposition] consisting of the public inputs of its two components.
+pub struct ComposedInputs<P1, P2> {
+    pub first: P1,
+    pub second: P2,
+}
+
+impl<B, P1, P2> ToElements<B> for ComposedInputs<P1, P2>
+where
+    B: StarkField,
+    P1: ToElements<B>,
+    P2: ToElements<B>,
+{
+    fn to_elements(&self) -> Vec<B> {
+        let mut result = self.first.to_elements();
+        result.extend(self.second.to_elements());
+        result
+    }
+}
+
+impl<P1: Serializable, P2: Serializable> Serializable for ComposedInputs<P1, P2> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.first.write_into(target);
+        self.second.write_into(target);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Splits the provided evaluation frame into a frame holding the first `num_first_columns`
+/// columns and a frame holding the remaining columns.
+fn split_frame<E: FieldElement>(
+    frame: &EvaluationFrame<E>,
+    num_first_columns: usize,
+) -> (EvaluationFrame<E>, EvaluationFrame<E>) {
+    let (first_rows, second_rows) = (0..frame.num_rows())
+        .map(|i| {
+            let (first_row, second_row) = frame.row(i).split_at(num_first_columns);
+            (first_row.to_vec(), second_row.to_vec())
+        })
+        .unzip();
+    (
+        EvaluationFrame::from_rows_with_offsets(first_rows, frame.offsets().to_vec()),
+        EvaluationFrame::from_rows_with_offsets(second_rows, frame.offsets().to_vec()),
+    )
+}
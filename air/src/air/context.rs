@@ -3,12 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::cmp;
 
 use math::StarkField;
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{
+    air::{
+        transition::validate_frame_offsets, ConstraintDivisor, LookupArgument,
+        TransitionConstraintDegree,
+    },
+    ProofOptions, TraceInfo,
+};
 
 // AIR CONTEXT
 // ================================================================================================
@@ -26,6 +32,9 @@ pub struct AirContext<B: StarkField> {
     pub(super) trace_domain_generator: B,
     pub(super) lde_domain_generator: B,
     pub(super) num_transition_exemptions: usize,
+    pub(super) lookups: Vec<LookupArgument>,
+    pub(super) frame_offsets: Vec<usize>,
+    pub(super) transition_divisors: BTreeMap<usize, ConstraintDivisor<B>>,
 }
 
 impl<B: StarkField> AirContext<B> {
@@ -77,13 +86,15 @@ impl<B: StarkField> AirContext<B> {
     /// computed by [Air::evaluate_aux_transition()](crate::Air::evaluate_aux_transition) function
     /// are expected to be in the order defined by `aux_transition_constraint_degrees` list.
     ///
+    /// The lists of auxiliary transition constraint degrees and the number of auxiliary
+    /// assertions may be empty (or zero) only if all columns of the auxiliary trace segment are
+    /// constrained by other means, e.g., by lookup arguments (see
+    /// [with_lookups()](AirContext::with_lookups)) or by Lagrange kernel constraints.
+    ///
     /// # Panics
     /// Panics if
     /// * `main_transition_constraint_degrees` is an empty vector.
     /// * `num_main_assertions` is zero.
-    /// * `trace_info.is_multi_segment() == true` but:
-    ///   - `aux_transition_constraint_degrees` is an empty vector.
-    ///   - `num_aux_assertions` is zero.
     /// * `trace_info.is_multi_segment() == false` but:
     ///   - `aux_transition_constraint_degrees` is a non-empty vector.
     ///   - `num_aux_assertions` is greater than zero.
@@ -104,16 +115,7 @@ impl<B: StarkField> AirContext<B> {
         );
         assert!(num_main_assertions > 0, "at least one assertion must be specified");
 
-        if trace_info.is_multi_segment() {
-            assert!(
-                !aux_transition_constraint_degrees.is_empty(),
-                "at least one transition constraint degree must be specified for the auxiliary trace segment"
-                );
-            assert!(
-                num_aux_assertions > 0,
-                "at least one assertion must be specified against the auxiliary trace segment"
-            );
-        } else {
+        if !trace_info.is_multi_segment() {
             assert!(
                 aux_transition_constraint_degrees.is_empty(),
                 "auxiliary transition constraint degrees specified for a single-segment trace"
@@ -170,6 +172,9 @@ impl<B: StarkField> AirContext<B> {
             trace_domain_generator: B::get_root_of_unity(trace_length.ilog2()),
             lde_domain_generator: B::get_root_of_unity(lde_domain_size.ilog2()),
             num_transition_exemptions: 1,
+            lookups: Vec::new(),
+            frame_offsets: vec![0, 1],
+            transition_divisors: BTreeMap::new(),
         }
     }
 
@@ -230,6 +235,27 @@ impl<B: StarkField> AirContext<B> {
         self.aux_transition_constraint_degrees.len()
     }
 
+    /// Returns the lookup arguments defined for a computation.
+    pub fn lookups(&self) -> &[LookupArgument] {
+        &self.lookups
+    }
+
+    /// Returns the number of lookup arguments defined for a computation.
+    pub fn num_lookups(&self) -> usize {
+        self.lookups.len()
+    }
+
+    /// Returns the index of the auxiliary column which contains the LogUp accumulator of the
+    /// first lookup argument.
+    ///
+    /// Accumulator columns of lookup arguments are placed at the end of the auxiliary trace
+    /// segment, right before the Lagrange kernel column (if any).
+    pub fn lookup_aux_column_offset(&self) -> usize {
+        self.trace_info.aux_segment_width()
+            - self.num_lookups()
+            - usize::from(self.has_lagrange_kernel_aux_column())
+    }
+
     /// Returns the index of the auxiliary column which implements the Lagrange kernel, if any
     pub fn lagrange_kernel_aux_column_idx(&self) -> Option<usize> {
         self.lagrange_kernel_aux_column_idx
@@ -259,6 +285,68 @@ impl<B: StarkField> AirContext<B> {
         self.num_transition_exemptions
     }
 
+    /// Returns the divisor of the transition constraint at the specified index.
+    ///
+    /// Transition constraints are indexed in the order in which their degrees were specified,
+    /// with constraints of the main trace segment followed by constraints of the auxiliary trace
+    /// segment. Unless a custom divisor was set for the constraint via
+    /// [with_transition_divisor()](AirContext::with_transition_divisor), this is the default
+    /// divisor which specifies that the constraint must hold on all steps except for the last
+    /// [num_transition_exemptions()](AirContext::num_transition_exemptions) steps.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of transition constraints.
+    pub fn get_transition_divisor(&self, index: usize) -> ConstraintDivisor<B> {
+        assert!(
+            index < self.num_transition_constraints(),
+            "transition constraint index must be smaller than {}, but was {}",
+            self.num_transition_constraints(),
+            index
+        );
+        match self.transition_divisors.get(&index) {
+            Some(divisor) => divisor.clone(),
+            None => {
+                ConstraintDivisor::from_transition(self.trace_len(), self.num_transition_exemptions)
+            },
+        }
+    }
+
+    /// Returns custom divisors of transition constraints keyed by constraint index.
+    ///
+    /// Transition constraints which are not present in the returned map use the default divisor.
+    pub fn custom_transition_divisors(&self) -> &BTreeMap<usize, ConstraintDivisor<B>> {
+        &self.transition_divisors
+    }
+
+    /// Returns offsets of execution trace rows in the transition constraint evaluation frame
+    /// relative to the current row.
+    ///
+    /// By default, this is `[0, 1]` (i.e., transition constraints are evaluated over the current
+    /// and the next rows), but the frame can be widened via
+    /// [with_frame_offsets()](AirContext::with_frame_offsets).
+    pub fn frame_offsets(&self) -> &[usize] {
+        &self.frame_offsets
+    }
+
+    /// Returns the largest row offset in the transition constraint evaluation frame.
+    pub fn max_frame_offset(&self) -> usize {
+        *self.frame_offsets.last().expect("frame offsets must not be empty")
+    }
+
+    /// Returns the number of rows at the end of each trace segment which are filled with random
+    /// values when generating zero-knowledge proofs.
+    ///
+    /// Each trace polynomial is opened at every row offset of the out-of-domain evaluation frame;
+    /// thus, every offset beyond the default two requires an additional blinding row on top of
+    /// [ProofOptions::num_blinding_rows()]. When zero-knowledge is not enabled, this returns zero.
+    pub fn num_blinding_rows(&self) -> usize {
+        if self.options.is_zk() {
+            self.options.num_blinding_rows() + self.frame_offsets.len() - 2
+        } else {
+            0
+        }
+    }
+
     /// Returns the number of columns needed to store the constraint composition polynomial.
     ///
     /// This is the maximum of:
@@ -292,43 +380,237 @@ impl<B: StarkField> AirContext<B> {
     /// numerator is `trace_len - 1` for all transition constraints (i.e. the base degree is 1).
     /// Hence, no matter what the degree of the divisor is for each, the degree of the fraction will
     /// be at most `trace_len - 1`.
+    ///
+    /// When zero-knowledge is enabled in the proof options, the returned number also includes
+    /// the randomizer column (see
+    /// [num_composition_randomizer_columns()](AirContext::num_composition_randomizer_columns)).
     pub fn num_constraint_composition_columns(&self) -> usize {
-        let mut highest_constraint_degree = 0_usize;
-        for degree in self
+        let trace_length = self.trace_len();
+        let transition_divisior_degree = trace_length - self.num_transition_exemptions();
+
+        // determine the highest degree of a transition constraint after its divisor is divided
+        // out; for constraints with custom divisors, we use the degree of the custom divisor
+        let mut highest_quotient_degree = 0_usize;
+        for (i, degree) in self
             .main_transition_constraint_degrees
             .iter()
             .chain(self.aux_transition_constraint_degrees.iter())
+            .enumerate()
         {
-            let eval_degree = degree.get_evaluation_degree(self.trace_len());
-            if eval_degree > highest_constraint_degree {
-                highest_constraint_degree = eval_degree
-            }
+            let eval_degree = degree.get_evaluation_degree(trace_length);
+            let divisor_degree = match self.transition_divisors.get(&i) {
+                Some(divisor) => divisor.degree(),
+                None => transition_divisior_degree,
+            };
+            highest_quotient_degree =
+                cmp::max(highest_quotient_degree, eval_degree.saturating_sub(divisor_degree));
         }
-        let trace_length = self.trace_len();
-        let transition_divisior_degree = trace_length - self.num_transition_exemptions();
 
-        // we use the identity: ceil(a/b) = (a + b - 1)/b
-        let num_constraint_col =
-            (highest_constraint_degree - transition_divisior_degree + trace_length - 1)
-                / trace_length;
+        let num_constraint_col = highest_quotient_degree.div_ceil(trace_length);
 
-        cmp::max(num_constraint_col, 1)
+        cmp::max(num_constraint_col, 1) + self.num_composition_randomizer_columns()
+    }
+
+    /// Returns the number of columns of the constraint composition polynomial which contain
+    /// random polynomials rather than constraint evaluations.
+    ///
+    /// When zero-knowledge is enabled in the proof options, the prover appends a single column
+    /// with a random polynomial of degree `trace_length - 1` to the composition polynomial. This
+    /// column is committed to and opened together with the other composition columns, and thus,
+    /// masks the DEEP composition polynomial; however, it is excluded from the out-of-domain
+    /// consistency check. When zero-knowledge is not enabled, this returns zero.
+    pub fn num_composition_randomizer_columns(&self) -> usize {
+        usize::from(self.options.is_zk())
     }
 
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Adds the specified lookup arguments to this context.
+    ///
+    /// For each lookup argument, a transition constraint is appended to the list of auxiliary
+    /// transition constraints, and two assertions are added to the auxiliary assertions. These
+    /// constraints are evaluated by the framework, and thus, are not expected to be evaluated by
+    /// [Air::evaluate_aux_transition()](crate::Air::evaluate_aux_transition) or returned from
+    /// [Air::get_aux_assertions()](crate::Air::get_aux_assertions).
+    ///
+    /// The LogUp accumulator columns of the lookup arguments must be the last columns of the
+    /// auxiliary trace segment (but precede the Lagrange kernel column, if any), and the last
+    /// [LookupArgument::NUM_RAND_ELEMENTS] random elements of the auxiliary trace segment are used
+    /// to build them. Both of these must be accounted for in the [TraceInfo] of the computation.
+    ///
+    /// This should be called before
+    /// [set_num_transition_exemptions()](AirContext::set_num_transition_exemptions).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The execution trace does not have enough auxiliary columns to hold the accumulators of
+    ///   the specified lookup arguments.
+    /// * The number of random elements for the auxiliary trace segment is smaller than
+    ///   [LookupArgument::NUM_RAND_ELEMENTS].
+    /// * Any of the lookup arguments references a column outside of the main trace segment.
+    /// * Blowup factor specified by the options of this context is too small to accommodate
+    ///   degrees of lookup constraints.
+    pub fn with_lookups(mut self, lookups: Vec<LookupArgument>) -> Self {
+        let num_reserved_columns =
+            self.num_lookups() + lookups.len() + usize::from(self.has_lagrange_kernel_aux_column());
+        assert!(
+            self.trace_info.aux_segment_width() >= num_reserved_columns,
+            "auxiliary trace segment must have at least {} columns, but had {}",
+            num_reserved_columns,
+            self.trace_info.aux_segment_width()
+        );
+        assert!(
+            self.trace_info.get_num_aux_segment_rand_elements()
+                >= LookupArgument::NUM_RAND_ELEMENTS,
+            "lookup arguments require at least {} random elements, but only {} were specified",
+            LookupArgument::NUM_RAND_ELEMENTS,
+            self.trace_info.get_num_aux_segment_rand_elements()
+        );
+
+        for lookup in lookups.iter() {
+            assert!(
+                lookup.max_column_index() < self.trace_info.main_trace_width(),
+                "lookup argument references column {}, but main trace segment has only {} columns",
+                lookup.max_column_index(),
+                self.trace_info.main_trace_width()
+            );
+
+            let degree = lookup.transition_constraint_degree();
+            self.ce_blowup_factor = cmp::max(self.ce_blowup_factor, degree.min_blowup_factor());
+            self.aux_transition_constraint_degrees.push(degree);
+        }
+        assert!(
+            self.options.blowup_factor() >= self.ce_blowup_factor,
+            "blowup factor too small; expected at least {}, but was {}",
+            self.ce_blowup_factor,
+            self.options.blowup_factor()
+        );
+
+        self.num_aux_assertions += 2 * lookups.len();
+        self.lookups.extend(lookups);
+        self
+    }
+
+    /// Sets row offsets of the transition constraint evaluation frame for this context.
+    ///
+    /// Each offset specifies the position of a row in the evaluation frame relative to the
+    /// current row; e.g., offsets `[0, 1, 7]` make rows `i`, `i + 1`, and `i + 7` available to
+    /// transition constraints evaluated at step `i`. The prover opens the trace at all of the
+    /// specified offsets when computing the out-of-domain evaluation frame.
+    ///
+    /// Since constraints evaluated at the last rows of the trace would reference rows past the
+    /// end of the trace, the number of transition exemptions is raised to the largest offset if
+    /// it is currently smaller than that.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `offsets` does not start with `[0, 1]`.
+    /// * `offsets` are not strictly increasing.
+    /// * The largest offset cannot be accommodated by the number of transition exemptions (see
+    ///   [set_num_transition_exemptions()](AirContext::set_num_transition_exemptions)).
+    pub fn with_frame_offsets(mut self, offsets: Vec<usize>) -> Self {
+        validate_frame_offsets(&offsets);
+        assert!(
+            offsets.len() <= u8::MAX as usize,
+            "evaluation frame cannot have more than {} rows, but had {}",
+            u8::MAX,
+            offsets.len()
+        );
+
+        self.frame_offsets = offsets;
+        if self.num_transition_exemptions < self.max_frame_offset() {
+            let num_exemptions = self.max_frame_offset();
+            self = self.set_num_transition_exemptions(num_exemptions);
+        }
+        self
+    }
+
+    /// Sets a custom divisor for the transition constraint at the specified index.
+    ///
+    /// By default, all transition constraints must hold on all steps of the execution trace
+    /// except for the last [num_transition_exemptions()](AirContext::num_transition_exemptions)
+    /// steps. A custom divisor can be used to enforce a constraint on a different set of steps:
+    /// e.g., a divisor built via [ConstraintDivisor::from_periodic_transition()] enforces the
+    /// constraint only on every n-th step, and a divisor built via
+    /// [ConstraintDivisor::from_transition_exemptions()] enforces the constraint on all steps
+    /// except for an arbitrary set of steps.
+    ///
+    /// Transition constraints are indexed in the order in which their degrees were specified,
+    /// with constraints of the main trace segment followed by constraints of the auxiliary trace
+    /// segment. Constraints of lookup arguments always use the default divisor.
+    ///
+    /// Since a divisor of a smaller degree increases the degree of the resulting rational
+    /// function, the constraint evaluation domain is expanded if needed.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `index` does not refer to a transition constraint defined by the AIR (i.e., it is
+    ///   greater than or equal to the number of transition constraints less the number of lookup
+    ///   arguments).
+    /// * The numerator of the divisor consists of more than one term, or the degree of this term
+    ///   is not a power of two which divides the trace length.
+    /// * Blowup factor specified by the options of this context is too small to accommodate the
+    ///   degree of the constraint divided by the specified divisor.
+    pub fn with_transition_divisor(mut self, index: usize, divisor: ConstraintDivisor<B>) -> Self {
+        let num_constraints = self.num_transition_constraints() - self.num_lookups();
+        assert!(
+            index < num_constraints,
+            "transition constraint index must be smaller than {num_constraints}, but was {index}"
+        );
+        assert_eq!(divisor.numerator().len(), 1, "divisor numerator must consist of a single term");
+        let numerator_degree = divisor.numerator()[0].0;
+        assert!(
+            numerator_degree.is_power_of_two() && numerator_degree <= self.trace_len(),
+            "divisor numerator degree must be a power of two not greater than {}, but was {}",
+            self.trace_len(),
+            numerator_degree
+        );
+
+        // make sure the constraint evaluation domain is large enough to accommodate the degree of
+        // the constraint divided by the divisor
+        let degree = self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .nth(index)
+            .expect("transition constraint index out of bounds");
+        let quotient_degree =
+            degree.get_evaluation_degree(self.trace_len()).saturating_sub(divisor.degree());
+        let min_ce_domain_size =
+            cmp::max(quotient_degree, self.trace_len() + 1).next_power_of_two();
+        self.ce_blowup_factor =
+            cmp::max(self.ce_blowup_factor, min_ce_domain_size / self.trace_len());
+        assert!(
+            self.options.blowup_factor() >= self.ce_blowup_factor,
+            "blowup factor too small; expected at least {}, but was {}",
+            self.ce_blowup_factor,
+            self.options.blowup_factor()
+        );
+
+        self.transition_divisors.insert(index, divisor);
+        self
+    }
+
     /// Sets the number of transition exemptions for this context.
     ///
     /// # Panics
     /// Panics if:
     /// * The number of exemptions is zero.
+    /// * The number of exemptions is smaller than the largest row offset of the evaluation frame
+    ///   (see [with_frame_offsets()](AirContext::with_frame_offsets)).
     /// * The number of exemptions exceeds half of the trace length.
     /// * Given the combination of transition constraints degrees and the blowup factor in this
     ///   context, the number of exemptions is too larger for a valid computation of the constraint
     ///   composition polynomial.
     pub fn set_num_transition_exemptions(mut self, n: usize) -> Self {
         assert!(n > 0, "number of transition exemptions must be greater than zero");
+        assert!(
+            n >= self.max_frame_offset(),
+            "number of transition exemptions must be at least {}, but was {}",
+            self.max_frame_offset(),
+            n
+        );
         // exemptions which are for more than half the trace plus one are probably a mistake
         assert!(
             n <= self.trace_len() / 2 + 1,
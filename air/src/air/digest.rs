@@ -0,0 +1,117 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{hashers::Blake3_256, Digest, Hasher};
+use math::StarkField;
+use utils::{ByteWriter, Serializable};
+
+use super::Air;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Domain separator prepended to the structure of an AIR before it is hashed.
+const AIR_DIGEST_DOMAIN: &[u8] = b"WINTERFELL_AIR_DIGEST_V1";
+
+// AIR DIGEST
+// ================================================================================================
+
+/// Returns a digest of the structure of the computation described by the specified AIR instance.
+///
+/// The digest binds:
+/// * Layout of the execution trace (i.e., widths of the trace segments and the number of random
+///   elements needed to build the auxiliary segment) and the modulus of the base field.
+/// * Degrees of main and auxiliary transition constraints, the number of transition exemptions,
+///   custom transition constraint divisors, and offsets of the rows of the evaluation frame.
+/// * Number of main and auxiliary assertions, lookup arguments, and the index of the Lagrange
+///   kernel column (if any).
+/// * Lengths of periodic columns.
+/// * Proof options.
+///
+/// Values of periodic columns, assertions, and public inputs are not bound by the digest, as
+/// these may differ between instances of the same computation. The length of the execution trace
+/// is bound by the proof context separately.
+///
+/// The digest is computed using BLAKE3 regardless of the hash function used to generate proofs.
+/// The prover includes the digest into the proof context, and thus, into the seed of the public
+/// coin; a verifier recomputes the digest from its own AIR instance to detect proofs generated
+/// for a structurally different AIR before performing any other checks.
+pub fn get_air_digest<A: Air>(air: &A) -> [u8; 32] {
+    let context = air.context();
+    let trace_info = air.trace_info();
+
+    let mut bytes = Vec::new();
+    bytes.write_bytes(AIR_DIGEST_DOMAIN);
+
+    // trace layout and base field
+    bytes.write_usize(trace_info.main_trace_width());
+    bytes.write_usize(trace_info.aux_segment_width());
+    bytes.write_usize(trace_info.get_num_aux_segment_rand_elements());
+    let modulus_bytes = A::BaseField::get_modulus_le_bytes();
+    bytes.write_usize(modulus_bytes.len());
+    bytes.write_bytes(&modulus_bytes);
+
+    // transition constraints
+    bytes.write_usize(context.main_transition_constraint_degrees.len());
+    bytes.write_many(&context.main_transition_constraint_degrees);
+    bytes.write_usize(context.aux_transition_constraint_degrees.len());
+    bytes.write_many(&context.aux_transition_constraint_degrees);
+    bytes.write_usize(context.num_transition_exemptions);
+    bytes.write_usize(context.transition_divisors.len());
+    for (&index, divisor) in context.transition_divisors.iter() {
+        bytes.write_usize(index);
+        bytes.write_usize(divisor.numerator().len());
+        for (degree, value) in divisor.numerator() {
+            bytes.write_usize(*degree);
+            bytes.write(value);
+        }
+        bytes.write_usize(divisor.exemptions().len());
+        bytes.write_many(divisor.exemptions());
+    }
+    bytes.write_usize(context.frame_offsets.len());
+    for &offset in context.frame_offsets.iter() {
+        bytes.write_usize(offset);
+    }
+
+    // assertions, lookup arguments, and the Lagrange kernel column
+    bytes.write_usize(context.num_main_assertions);
+    bytes.write_usize(context.num_aux_assertions);
+    bytes.write_usize(context.lookups.len());
+    for lookup in context.lookups.iter() {
+        bytes.write_usize(lookup.table_columns().len());
+        for &column in lookup.table_columns() {
+            bytes.write_usize(column);
+        }
+        bytes.write_usize(lookup.multiplicity_column());
+        bytes.write_usize(lookup.witness_columns().len());
+        for columns in lookup.witness_columns() {
+            bytes.write_usize(columns.len());
+            for &column in columns {
+                bytes.write_usize(column);
+            }
+        }
+    }
+    match context.lagrange_kernel_aux_column_idx {
+        Some(idx) => {
+            bytes.write_bool(true);
+            bytes.write_usize(idx);
+        },
+        None => bytes.write_bool(false),
+    }
+
+    // periodic columns
+    let periodic_columns = air.get_periodic_column_values();
+    bytes.write_usize(periodic_columns.len());
+    for column in periodic_columns.iter() {
+        bytes.write_usize(column.len());
+    }
+
+    // proof options
+    air.options().write_into(&mut bytes);
+
+    Blake3_256::<A::BaseField>::hash(&bytes).as_bytes()
+}
@@ -23,7 +23,10 @@ use crate::air::Assertion;
 /// numerator: `[(a, 1), (b, 2)]`, exemptions: `[3]`.
 ///
 /// A divisor cannot be instantiated directly, and instead must be created either for an
-/// [Assertion] or for a transition constraint.
+/// [Assertion] or for a transition constraint. In addition to the default transition constraint
+/// divisor, custom divisors for transition constraints can be created via
+/// [from_periodic_transition()](ConstraintDivisor::from_periodic_transition) and
+/// [from_transition_exemptions()](ConstraintDivisor::from_transition_exemptions).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConstraintDivisor<B: StarkField> {
     pub(super) numerator: Vec<(usize, B)>,
@@ -63,6 +66,74 @@ impl<B: StarkField> ConstraintDivisor<B> {
         Self::new(vec![(constraint_enforcement_domain_size, B::ONE)], exemptions)
     }
 
+    /// Builds a divisor for transition constraints which must hold only on every `period`-th step
+    /// of the execution trace, starting with `first_step`.
+    ///
+    /// The divisor polynomial is defined as:
+    ///
+    /// $$
+    /// z(x) = x^k - g^{a \cdot k}
+    /// $$
+    ///
+    /// where $g$ is the generator of the trace domain, $k = n / period$ is the number of steps on
+    /// which the constraints must hold, and $a$ is the `first_step`. For example, a divisor with
+    /// `first_step = 0` and `period = 8` specifies that constraints must hold on steps 0, 8, 16,
+    /// etc.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `period` is not a power of two, or is greater than `trace_length`.
+    /// * `first_step` is greater than or equal to `period`.
+    pub fn from_periodic_transition(trace_length: usize, first_step: usize, period: usize) -> Self {
+        assert!(period.is_power_of_two(), "period must be a power of two, but was {period}");
+        assert!(
+            period <= trace_length,
+            "period cannot exceed trace length of {trace_length}, but was {period}"
+        );
+        assert!(
+            first_step < period,
+            "first step must be smaller than period of {period}, but was {first_step}"
+        );
+
+        let num_steps = trace_length / period;
+        let offset = get_trace_domain_value_at::<B>(trace_length, num_steps * first_step);
+        Self::new(vec![(num_steps, offset)], vec![])
+    }
+
+    /// Builds a divisor for transition constraints which must hold on all steps of the execution
+    /// trace except for the specified `exempt_steps`.
+    ///
+    /// The divisor polynomial is defined as:
+    ///
+    /// $$
+    /// z(x) = \frac{x^n - 1}{ \prod_{s \in S} (x - g^s)}
+    /// $$
+    ///
+    /// where $n$ is the length of the execution trace, $g$ is the generator of the trace domain,
+    /// and $S$ is the set of exempt steps. Unlike the default transition constraint divisor,
+    /// the exempt steps do not need to be at the end of the execution trace.
+    ///
+    /// # Panics
+    /// Panics if any of the exempt steps is outside of the execution trace or is listed more
+    /// than once.
+    pub fn from_transition_exemptions(trace_length: usize, exempt_steps: &[usize]) -> Self {
+        let mut steps = exempt_steps.to_vec();
+        steps.sort_unstable();
+        for (i, &step) in steps.iter().enumerate() {
+            assert!(
+                step < trace_length,
+                "exempt step must be smaller than trace length of {trace_length}, but was {step}"
+            );
+            assert!(i == 0 || steps[i - 1] != step, "exempt step {step} is listed more than once");
+        }
+
+        let exemptions = steps
+            .into_iter()
+            .map(|step| get_trace_domain_value_at::<B>(trace_length, step))
+            .collect();
+        Self::new(vec![(trace_length, B::ONE)], exemptions)
+    }
+
     /// Builds a divisor for a boundary constraint described by the assertion.
     ///
     /// For boundary constraints, the divisor polynomial is defined as:
@@ -83,6 +154,10 @@ impl<B: StarkField> ConstraintDivisor<B> {
     ///   deviate from a power of two, and $k$ is the number of asserted steps. This is equivalent to
     ///   $(x - g^a) \cdot (x - g^{a + j}) \cdot (x - g^{a + 2 \cdot j}) ... (x - g^{a + (k  - 1) \cdot j})$,
     ///   where $j$ is the length of interval between asserted steps (e.g. 8).
+    /// * For an assertion against a contiguous range of steps, it is
+    ///   $(x - g^a) \cdot (x - g^{a + 1}) ... (x - g^{a + k - 1})$, where $a$ is the first asserted
+    ///   step. Unlike for other assertions, the numerator of such divisor consists of $k$ terms,
+    ///   and thus, the divisor is not succinct.
     ///
     /// # Panics
     /// Panics of the specified `trace_length` is inconsistent with the specified `assertion`.
@@ -91,7 +166,12 @@ impl<B: StarkField> ConstraintDivisor<B> {
         E: FieldElement<BaseField = B>,
     {
         let num_steps = assertion.get_num_steps(trace_length);
-        if assertion.first_step == 0 {
+        if assertion.is_contiguous() {
+            let numerator = (assertion.first_step..assertion.first_step + num_steps)
+                .map(|step| (1, get_trace_domain_value_at::<B>(trace_length, step)))
+                .collect();
+            Self::new(numerator, vec![])
+        } else if assertion.first_step == 0 {
             Self::new(vec![(num_steps, B::ONE)], vec![])
         } else {
             let trace_offset = num_steps * assertion.first_step;
@@ -120,6 +200,17 @@ impl<B: StarkField> ConstraintDivisor<B> {
         numerator_degree - denominator_degree
     }
 
+    /// Returns true if the divisor polynomial evaluates to zero at the provided `x` coordinate.
+    ///
+    /// For divisors of constraints, this means that the constraints are enforced at `x`.
+    pub fn vanishes_at(&self, x: B) -> bool {
+        let numerator_vanishes = self
+            .numerator
+            .iter()
+            .any(|&(degree, constant)| x.exp((degree as u64).into()) == constant);
+        numerator_vanishes && !self.exemptions.contains(&x)
+    }
+
     // EVALUATORS
     // --------------------------------------------------------------------------------------------
     /// Evaluates the divisor polynomial at the provided `x` coordinate.
@@ -226,6 +317,78 @@ mod tests {
         assert_eq!(expected, div.evaluate_at(BaseElement::new(4)));
     }
 
+    #[test]
+    fn periodic_transition_divisor() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // constraints enforced on steps 3, 7, 11, 15
+        let divisor = ConstraintDivisor::from_periodic_transition(n, 3, 4);
+        assert_eq!(4, divisor.degree());
+        assert_eq!(ConstraintDivisor::new(vec![(4, g.exp(12))], vec![]), divisor);
+
+        for i in 0..n {
+            let x = g.exp(i as u128);
+            assert_eq!(i % 4 == 3, divisor.vanishes_at(x));
+            assert_eq!(i % 4 == 3, divisor.evaluate_at(x) == BaseElement::ZERO);
+        }
+    }
+
+    #[test]
+    fn transition_exemptions_divisor() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // constraints enforced on all steps except for steps 0, 5, and 15
+        let divisor = ConstraintDivisor::from_transition_exemptions(n, &[15, 0, 5]);
+        assert_eq!(13, divisor.degree());
+        assert_eq!(
+            ConstraintDivisor::<BaseElement>::from_transition(n, 1),
+            ConstraintDivisor::from_transition_exemptions(n, &[15])
+        );
+
+        // z(x) = prod(x - g^i) for all i not in the set of exempt steps
+        let poly =
+            (0..n).filter(|i| ![0, 5, 15].contains(i)).fold(vec![BaseElement::ONE], |p, i| {
+                polynom::mul(&p, &[-g.exp(i as u128), BaseElement::ONE])
+            });
+
+        for i in 0..n {
+            let x = g.exp(i as u128);
+            assert_eq!(![0, 5, 15].contains(&i), divisor.vanishes_at(x));
+        }
+
+        // evaluate outside of the trace domain to avoid division by zero at exempt steps
+        let x = BaseElement::new(7);
+        assert_eq!(polynom::eval(&poly, x), divisor.evaluate_at(x));
+    }
+
+    #[test]
+    #[should_panic(expected = "exempt step 5 is listed more than once")]
+    fn transition_exemptions_divisor_duplicate_steps() {
+        ConstraintDivisor::<BaseElement>::from_transition_exemptions(16, &[5, 1, 5]);
+    }
+
+    #[test]
+    fn contiguous_assertion_divisor() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // assertion against steps 3, 4, 5
+        let assertion = Assertion::contiguous(0, 3, vec![BaseElement::ONE; 3]);
+        let divisor = ConstraintDivisor::from_assertion(&assertion, n);
+        assert_eq!(3, divisor.degree());
+        assert_eq!(
+            ConstraintDivisor::new(vec![(1, g.exp(3)), (1, g.exp(4)), (1, g.exp(5))], vec![]),
+            divisor
+        );
+
+        for i in 0..n {
+            let x = g.exp(i as u128);
+            assert_eq!((3..6).contains(&i), divisor.evaluate_at(x) == BaseElement::ZERO);
+        }
+    }
+
     #[test]
     fn constraint_divisor_equivalence() {
         let n = 8_usize;
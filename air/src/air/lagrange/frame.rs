@@ -37,7 +37,14 @@ impl<E: FieldElement> LagrangeKernelEvaluationFrame<E> {
 
     /// Constructs the frame from the Lagrange kernel column trace polynomial coefficients for an
     /// evaluation point.
-    pub fn from_lagrange_kernel_column_poly(lagrange_kernel_col_poly: &[E], z: E) -> Self {
+    ///
+    /// The evaluation point may be drawn from an extension of the field of the polynomial
+    /// coefficients.
+    pub fn from_lagrange_kernel_column_poly<F>(lagrange_kernel_col_poly: &[F], z: E) -> Self
+    where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: From<F>,
+    {
         let log_trace_len = lagrange_kernel_col_poly.len().ilog2();
         let g = E::from(E::BaseField::get_root_of_unity(log_trace_len));
 
@@ -0,0 +1,190 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{ExtensionOf, FieldElement};
+
+use super::TransitionConstraintDegree;
+
+// LOOKUP ARGUMENT
+// ================================================================================================
+/// Describes a LogUp lookup argument between a table and a set of witnesses in the main trace
+/// segment.
+///
+/// A lookup argument asserts that every row of each witness column set is equal to some row of
+/// the table column set. Rows of column sets are compressed into single values using a random
+/// element $\beta$ as $\sum_k \beta^k \cdot c_k$, and the argument is enforced via the LogUp
+/// identity:
+///
+/// $$
+/// \sum_i \frac{m_i}{\alpha - t_i} = \sum_j \sum_i \frac{1}{\alpha - w_{j, i}}
+/// $$
+///
+/// where $t_i$ and $w_{j, i}$ are compressed table and witness rows, $m_i$ is the value in the
+/// multiplicity column (i.e., the number of times row $i$ of the table is looked up), and
+/// $\alpha$ is a random element.
+///
+/// The sums are accumulated in a single column of the auxiliary trace segment which is built by
+/// the prover, and the corresponding transition constraint and assertions are added to the AIR
+/// automatically (see [AirContext::with_lookups()](crate::AirContext::with_lookups)). The
+/// accumulator column starts and ends with zero, and only the rows to which transition
+/// constraints apply (i.e., all rows except the last `num_transition_exemptions` rows) take part
+/// in the lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupArgument {
+    table_columns: Vec<usize>,
+    multiplicity_column: usize,
+    witness_columns: Vec<Vec<usize>>,
+}
+
+impl LookupArgument {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Number of random elements required by lookup arguments; these are the last two random
+    /// elements drawn for the auxiliary trace segment.
+    pub const NUM_RAND_ELEMENTS: usize = 2;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new lookup argument for the specified table, multiplicity, and witness columns
+    /// of the main trace segment.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `table_columns` is empty.
+    /// * `witness_columns` is empty.
+    /// * The number of columns in any of the witness column sets is different from the number of
+    ///   table columns.
+    pub fn new(
+        table_columns: Vec<usize>,
+        multiplicity_column: usize,
+        witness_columns: Vec<Vec<usize>>,
+    ) -> Self {
+        assert!(!table_columns.is_empty(), "a lookup table must consist of at least one column");
+        assert!(
+            !witness_columns.is_empty(),
+            "at least one set of witness columns must be specified"
+        );
+        for (i, columns) in witness_columns.iter().enumerate() {
+            assert_eq!(
+                columns.len(),
+                table_columns.len(),
+                "witness column set {i} must consist of {} columns, but had {}",
+                table_columns.len(),
+                columns.len()
+            );
+        }
+
+        Self {
+            table_columns,
+            multiplicity_column,
+            witness_columns,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns indexes of the main trace columns which contain the lookup table.
+    pub fn table_columns(&self) -> &[usize] {
+        &self.table_columns
+    }
+
+    /// Returns the index of the main trace column which contains lookup multiplicities.
+    pub fn multiplicity_column(&self) -> usize {
+        self.multiplicity_column
+    }
+
+    /// Returns indexes of the main trace columns for each set of looked up values.
+    pub fn witness_columns(&self) -> &[Vec<usize>] {
+        &self.witness_columns
+    }
+
+    /// Returns the largest main trace column index referenced by this lookup argument.
+    pub fn max_column_index(&self) -> usize {
+        self.table_columns
+            .iter()
+            .chain(self.witness_columns.iter().flatten())
+            .fold(self.multiplicity_column, |acc, &c| acc.max(c))
+    }
+
+    /// Returns the degree of the transition constraint enforcing this lookup argument.
+    ///
+    /// After clearing denominators, the constraint multiplies the accumulator delta by the
+    /// compressed table row and all compressed witness rows, and thus, has degree equal to the
+    /// number of witness column sets plus 2.
+    pub fn transition_constraint_degree(&self) -> TransitionConstraintDegree {
+        TransitionConstraintDegree::new(self.witness_columns.len() + 2)
+    }
+
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraint of this lookup argument.
+    ///
+    /// The constraint enforces that `next - current` is equal to the contribution of `row` of the
+    /// main trace segment to the LogUp sum; it is evaluated as:
+    ///
+    /// $$
+    /// (next - current) \cdot d_t \cdot \prod_j d_j - m \cdot \prod_j d_j +
+    /// d_t \cdot \sum_j \prod_{k \neq j} d_k
+    /// $$
+    ///
+    /// where $d_t = \alpha - t$ and $d_j = \alpha - w_j$ are the denominators of the table and
+    /// witness terms.
+    pub fn evaluate_transition<F, E>(&self, row: &[F], current: E, next: E, alpha: E, beta: E) -> E
+    where
+        F: FieldElement,
+        E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    {
+        let table_denominator = alpha - compress(row, &self.table_columns, beta);
+
+        // compute the product of all witness denominators together with the sum of products of
+        // all witness denominators but one
+        let mut witness_product = E::ONE;
+        let mut witness_sum = E::ZERO;
+        for columns in self.witness_columns.iter() {
+            let denominator = alpha - compress(row, columns, beta);
+            witness_sum = witness_sum * denominator + witness_product;
+            witness_product *= denominator;
+        }
+
+        let multiplicity = E::from(row[self.multiplicity_column]);
+        (next - current) * table_denominator * witness_product - multiplicity * witness_product
+            + table_denominator * witness_sum
+    }
+
+    /// Returns denominators of all terms of the LogUp sum for the specified `row` of the main
+    /// trace segment.
+    ///
+    /// The first element of the returned vector is the denominator of the table term, and it is
+    /// followed by denominators of terms for each set of witness columns.
+    pub fn get_denominators<F, E>(&self, row: &[F], alpha: E, beta: E) -> Vec<E>
+    where
+        F: FieldElement,
+        E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    {
+        core::iter::once(&self.table_columns)
+            .chain(self.witness_columns.iter())
+            .map(|columns| alpha - compress(row, columns, beta))
+            .collect()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Compresses values in the specified `columns` of the `row` into a single value as
+/// $\sum_k \beta^k \cdot row[columns_k]$.
+fn compress<F, E>(row: &[F], columns: &[usize], beta: E) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    columns.iter().rev().fold(E::ZERO, |acc, &c| acc * beta + E::from(row[c]))
+}
@@ -6,7 +6,10 @@
 use alloc::{collections::BTreeMap, vec::Vec};
 
 use crypto::{RandomCoin, RandomCoinError};
-use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
+use math::{
+    fft::{self, EvaluationDomain},
+    ExtensibleField, ExtensionOf, FieldElement, StarkField,
+};
 
 use crate::ProofOptions;
 
@@ -20,13 +23,16 @@ mod context;
 pub use context::AirContext;
 
 mod assertions;
-pub use assertions::Assertion;
+pub use assertions::{Assertion, PublicSequence};
 
 mod boundary;
 pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup, BoundaryConstraints};
 
 mod transition;
-pub use transition::{EvaluationFrame, TransitionConstraintDegree, TransitionConstraints};
+pub use transition::{EvaluationFrame, Expr, TransitionConstraintDegree, TransitionConstraints};
+
+mod lookup;
+pub use lookup::LookupArgument;
 
 mod lagrange;
 pub use lagrange::{
@@ -42,6 +48,15 @@ pub use coefficients::{
 
 mod divisor;
 pub use divisor::ConstraintDivisor;
+
+mod digest;
+pub use digest::get_air_digest;
+
+mod composition;
+pub use composition::{AirComposition, ComposedInputs};
+
+mod public_inputs;
+pub use public_inputs::PublicInputs;
 use utils::{Deserializable, Serializable};
 
 #[cfg(test)]
@@ -84,7 +99,9 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// [Air::evaluate_transition()] function which takes the following parameters:
 ///
 /// - [EvaluationFrame] which contains vectors with current and next states of the
-///   computation.
+///   computation. Constraints which need to access more distant rows can declare additional row
+///   offsets via [AirContext::with_frame_offsets()]; in such a case, the frame also contains
+///   states at these offsets (e.g., rows `i`, `i + 1`, and `i + 7` for offsets `[0, 1, 7]`).
 /// - A list of periodic values. When periodic columns are defined for a computation,
 ///   this will contain values of periodic columns at the current step of the computation.
 ///   Otherwise, this will be an empty list.
@@ -156,7 +173,10 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// To define such columns for your computation, you can override
 /// [Air::get_periodic_column_values()] method. The values of the periodic columns at a given
 /// step of the computation will be supplied to the [Air::evaluate_transition()] method via the
-/// `periodic_values` parameter.
+/// `periodic_values` parameter. Cycles of periodic columns start at step 0 by default; a cycle
+/// starting at a different step can be described by overriding
+/// [Air::get_periodic_column_offsets()] method. Since both methods are invoked on an instantiated
+/// AIR, cycle lengths, values, and offsets may depend on public inputs of the computation.
 ///
 /// ### Randomized AIR
 /// Randomized AIR is a powerful extension of AIR which enables, among other things, multiset and
@@ -184,6 +204,16 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// * Override [Air::get_aux_assertions()] method. This method is similar to the
 ///   [Air::get_assertions()] method, but it should return assertions against columns of the
 ///   auxiliary trace segment.
+///
+/// ### Lookup arguments
+/// Lookup arguments are a common application of Randomized AIR, and Winterfell provides built-in
+/// support for LogUp-based lookups. To assert that rows of some sets of main trace columns are
+/// contained in a table defined by another set of main trace columns, describe the lookup via
+/// [LookupArgument] and add it to the [AirContext] using [AirContext::with_lookups()]. For each
+/// lookup argument, the prover builds an accumulator column in the auxiliary trace segment, and
+/// the corresponding transition constraints and assertions are generated automatically; thus,
+/// these do not need to be handled in [Air::evaluate_aux_transition()] and
+/// [Air::get_aux_assertions()] methods.
 pub trait Air: Send + Sync {
     /// Base field for the computation described by this AIR. STARK protocol for this computation
     /// may be executed in the base field, or in an extension of the base fields as specified
@@ -191,8 +221,9 @@ pub trait Air: Send + Sync {
     type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3>;
 
     /// A type defining shape of public inputs for the computation described by this protocol.
-    /// This could be any type as long as it can be serialized into a sequence of field elements.
-    type PublicInputs: ToElements<Self::BaseField> + Send;
+    /// This could be any type as long as it can be converted into a sequence of field elements
+    /// and serialized into a sequence of bytes (see [PublicInputs] trait).
+    type PublicInputs: PublicInputs<Self::BaseField>;
 
     /// An GKR proof object. If not needed, set to `()`.
     type GkrProof: Serializable + Deserializable + Send;
@@ -245,7 +276,9 @@ pub trait Air: Send + Sync {
     /// The evaluations should be written into the `results` slice in the same order as the order
     /// of auxiliary transition constraint degree descriptors used to instantiate [AirContext] for
     /// this AIR. Thus, the length of the `result` slice will equal to the number of auxiliary
-    /// transition constraints defined for this computation.
+    /// transition constraints defined for this computation, excluding the constraints of lookup
+    /// arguments (these are evaluated separately by
+    /// [evaluate_lookup_transition()](Air::evaluate_lookup_transition)).
     ///
     /// The default implementation of this function panics. It must be overridden for AIRs
     /// describing computations which require multiple trace segments.
@@ -335,6 +368,62 @@ pub trait Air: Send + Sync {
         Ok(rand_elements)
     }
 
+    /// Evaluates transition constraints of all lookup arguments defined for this computation
+    /// over the specified evaluation frames for the main and auxiliary trace segments.
+    ///
+    /// The evaluations are written into the `result` slice in the order in which lookup arguments
+    /// were added to the [AirContext] of this AIR; the length of the slice must be equal to the
+    /// number of lookup arguments.
+    fn evaluate_lookup_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        aux_rand_elements: &[E],
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        if self.context().num_lookups() == 0 {
+            return;
+        }
+
+        let num_rand_elements = aux_rand_elements.len();
+        let alpha = aux_rand_elements[num_rand_elements - 2];
+        let beta = aux_rand_elements[num_rand_elements - 1];
+
+        let offset = self.context().lookup_aux_column_offset();
+        for (i, (lookup, result)) in self.context().lookups().iter().zip(result).enumerate() {
+            *result = lookup.evaluate_transition(
+                main_frame.current(),
+                aux_frame.current()[offset + i],
+                aux_frame.next()[offset + i],
+                alpha,
+                beta,
+            );
+        }
+    }
+
+    /// Returns assertions against LogUp accumulator columns of all lookup arguments defined for
+    /// this computation.
+    ///
+    /// For each lookup argument, the accumulator column must be zero in the first row and in the
+    /// first row exempt from transition constraints.
+    fn get_lookup_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+    ) -> Vec<Assertion<E>> {
+        let offset = self.context().lookup_aux_column_offset();
+        let last_step = self.trace_length() - self.context().num_transition_exemptions();
+        (offset..offset + self.context().num_lookups())
+            .flat_map(|column| {
+                [
+                    Assertion::single(column, 0, E::ZERO),
+                    Assertion::single(column, last_step, E::ZERO),
+                ]
+            })
+            .collect()
+    }
+
     /// Returns a new [`LagrangeKernelConstraints`] if a Lagrange kernel auxiliary column is present
     /// in the trace, or `None` otherwise.
     fn get_lagrange_kernel_constraints<E: FieldElement<BaseField = Self::BaseField>>(
@@ -364,18 +453,46 @@ pub trait Air: Send + Sync {
         Vec::new()
     }
 
+    /// Returns the steps at which cycles of the periodic columns start.
+    ///
+    /// A periodic column with values `v` and offset `k` takes value `v[(i - k) mod n]` at step
+    /// `i` of the computation, where `n` is the length of the column's cycle. Thus, the first
+    /// value of the cycle is placed at step `k` (and at steps `k + n`, `k + 2n` etc.).
+    ///
+    /// The default implementation of this method returns an empty vector, which means that all
+    /// cycles start at step 0. Otherwise, the returned vector must contain an offset for every
+    /// column returned from the [get_periodic_column_values()](Air::get_periodic_column_values)
+    /// method. Similarly to periodic values, offsets may depend on public inputs.
+    fn get_periodic_column_offsets(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
     /// Returns polynomial for all periodic columns.
     ///
     /// These polynomials are interpolated from the values returned from the
-    /// [get_periodic_column_values()](Air::get_periodic_column_values) method.
+    /// [get_periodic_column_values()](Air::get_periodic_column_values) method, shifted by the
+    /// offsets returned from the [get_periodic_column_offsets()](Air::get_periodic_column_offsets)
+    /// method.
     fn get_periodic_column_polys(&self) -> Vec<Vec<Self::BaseField>> {
+        let columns = self.get_periodic_column_values();
+        let mut offsets = self.get_periodic_column_offsets();
+        if offsets.is_empty() {
+            offsets.resize(columns.len(), 0);
+        }
+        assert_eq!(
+            offsets.len(),
+            columns.len(),
+            "number of periodic column offsets must be equal to the number of periodic columns"
+        );
+
         // cache inverse twiddles for each cycle length so that we don't have to re-build them
         // for columns with identical cycle lengths
         let mut twiddle_map = BTreeMap::new();
         // iterate over all periodic columns and convert column values into polynomials
-        self.get_periodic_column_values()
+        columns
             .into_iter()
-            .map(|mut column| {
+            .zip(offsets)
+            .map(|(mut column, offset)| {
                 let cycle_length = column.len();
                 assert!(
                     cycle_length >= MIN_CYCLE_LENGTH,
@@ -391,6 +508,10 @@ pub trait Air: Send + Sync {
                     cycle_length
                 );
 
+                // align the cycle with its offset so that the value at position i of the column
+                // is the value of the periodic column at step i
+                column.rotate_right(offset % cycle_length);
+
                 // get twiddles for interpolation and interpolate values into a polynomial
                 let inv_twiddles = twiddle_map
                     .entry(cycle_length)
@@ -414,8 +535,9 @@ pub trait Air: Send + Sync {
         TransitionConstraints::new(self.context(), composition_coefficients)
     }
 
-    /// Convert assertions returned from [get_assertions()](Air::get_assertions) and
-    /// [get_aux_assertions()](Air::get_aux_assertions) methods into boundary constraints.
+    /// Convert assertions returned from [get_assertions()](Air::get_assertions),
+    /// [get_aux_assertions()](Air::get_aux_assertions), and
+    /// [get_lookup_assertions()](Air::get_lookup_assertions) methods into boundary constraints.
     ///
     /// This function also assigns composition coefficients to each constraint, and groups the
     /// constraints by their divisors. The coefficients will be used to compute random linear
@@ -429,7 +551,11 @@ pub trait Air: Send + Sync {
             self.context(),
             self.get_assertions(),
             aux_rand_elements
-                .map(|aux_rand_elements| self.get_aux_assertions(aux_rand_elements))
+                .map(|aux_rand_elements| {
+                    let mut assertions = self.get_aux_assertions(aux_rand_elements);
+                    assertions.extend(self.get_lookup_assertions());
+                    assertions
+                })
                 .unwrap_or_default(),
             composition_coefficients,
         )
@@ -522,6 +648,15 @@ pub trait Air: Send + Sync {
         self.context().options.domain_offset()
     }
 
+    /// Returns the low-degree extension domain for an instance of the computation described by
+    /// this AIR.
+    ///
+    /// The returned domain does not cache twiddles or offset powers; these can be built via
+    /// the corresponding [EvaluationDomain] methods if needed.
+    fn lde_domain(&self) -> EvaluationDomain<Self::BaseField> {
+        EvaluationDomain::new(self.lde_domain_size(), self.domain_offset())
+    }
+
     // LINEAR COMBINATION COEFFICIENTS
     // --------------------------------------------------------------------------------------------
 
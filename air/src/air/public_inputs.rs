@@ -0,0 +1,108 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{StarkField, ToElements};
+use utils::Serializable;
+
+// PUBLIC INPUTS
+// ================================================================================================
+/// Public inputs of a computation.
+///
+/// Public inputs can be of arbitrary structure (e.g., vectors or nested structs) as long as they
+/// can be converted into a sequence of field elements via [ToElements] trait, and can be
+/// serialized into a sequence of bytes via [Serializable] trait. This trait is implemented
+/// automatically for all types which satisfy these requirements.
+///
+/// Both the prover and the verifier absorb public inputs into the protocol transcript via the
+/// [PublicInputs::to_transcript_elements()] method, and thus, a proof verifies only against
+/// public inputs which produce exactly the same transcript elements as the public inputs used by
+/// the prover.
+pub trait PublicInputs<B: StarkField>: ToElements<B> + Serializable + Send {
+    /// Returns field elements via which these public inputs are absorbed into the protocol
+    /// transcript.
+    ///
+    /// The elements consist of:
+    /// * The number of elements returned from [ToElements::to_elements()], followed by these
+    ///   elements.
+    /// * The number of bytes in serialized public inputs, followed by the serialized bytes broken
+    ///   into chunks which are slightly smaller than the number of bytes needed to encode a field
+    ///   element (each chunk is converted into a single field element).
+    ///
+    /// Serialized public inputs are included to make sure that all data contained in the public
+    /// inputs is bound to the transcript even if the field element representation of the inputs
+    /// omits some of it; length prefixes make sure that the encoding is unambiguous.
+    fn to_transcript_elements(&self) -> Vec<B> {
+        let elements = self.to_elements();
+        let bytes = self.to_bytes();
+
+        let mut result = Vec::with_capacity(elements.len() + bytes.len() / B::ELEMENT_BYTES + 3);
+        result.push(B::from(elements.len() as u32));
+        result.extend(elements);
+        result.push(B::from(bytes.len() as u32));
+        for chunk in bytes.chunks(B::ELEMENT_BYTES - 1) {
+            result.push(B::from_bytes_with_padding(chunk));
+        }
+        result
+    }
+}
+
+impl<B, T> PublicInputs<B> for T
+where
+    B: StarkField,
+    T: ToElements<B> + Serializable + Send,
+{
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use math::{fields::f64::BaseElement, FieldElement, StarkField, ToElements};
+    use utils::{ByteWriter, Serializable};
+
+    use super::PublicInputs;
+
+    /// Public inputs which leave out their second value from the field element representation.
+    struct MockInputs(u8, u8);
+
+    impl ToElements<BaseElement> for MockInputs {
+        fn to_elements(&self) -> Vec<BaseElement> {
+            vec![BaseElement::from(self.0)]
+        }
+    }
+
+    impl Serializable for MockInputs {
+        fn write_into<W: ByteWriter>(&self, target: &mut W) {
+            target.write_u8(self.0);
+            target.write_u8(self.1);
+        }
+    }
+
+    #[test]
+    fn public_inputs_to_transcript_elements() {
+        let inputs = MockInputs(3, 5);
+        let expected = vec![
+            BaseElement::ONE,
+            BaseElement::from(3_u32),
+            BaseElement::from(2_u32),
+            BaseElement::from_bytes_with_padding(&[3, 5]),
+        ];
+        assert_eq!(expected, inputs.to_transcript_elements());
+
+        // values omitted from the field element representation are still bound to the transcript
+        let other = MockInputs(3, 6);
+        assert_eq!(inputs.to_elements(), other.to_elements());
+        assert_ne!(inputs.to_transcript_elements(), other.to_transcript_elements());
+
+        // public inputs without any data still produce length prefixes
+        let expected = vec![BaseElement::ZERO, BaseElement::ZERO];
+        assert_eq!(expected, PublicInputs::<BaseElement>::to_transcript_elements(&()));
+    }
+}
@@ -9,8 +9,8 @@ use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
 use math::{fields::f64::BaseElement, get_power_series, polynom, FieldElement, StarkField};
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirComposition, AirContext, Assertion, ComposedInputs, ConstraintDivisor, EvaluationFrame,
+    LookupArgument, ProofOptions, TraceInfo, TransitionConstraintDegree, TransitionConstraints,
 };
 use crate::FieldExtension;
 
@@ -52,6 +52,36 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
     assert_eq!(0, column_polys.len());
 }
 
+#[test]
+fn get_periodic_column_polys_with_offsets() {
+    let col1 = vec![BaseElement::ONE, BaseElement::ZERO];
+    let col2: Vec<BaseElement> = (1..5_u32).map(BaseElement::from).collect();
+    let trace_length = 16;
+    let mut air = MockAir::with_periodic_columns(vec![col1.clone(), col2.clone()], trace_length);
+    air.periodic_offsets = vec![0, 5];
+    let column_polys = air.get_periodic_column_polys();
+    assert_eq!(build_periodic_column_poly(&col1), column_polys[0]);
+
+    // the cycle of the second column starts at step 5 (i.e., at step 1 of a 4-step cycle)
+    let g = BaseElement::get_root_of_unity(trace_length.ilog2());
+    for step in 0..trace_length {
+        let x = g.exp((step * trace_length / col2.len()) as u64);
+        let expected = col2[(step + col2.len() - 1) % col2.len()];
+        assert_eq!(expected, polynom::eval(&column_polys[1], x));
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "number of periodic column offsets must be equal to the number of periodic columns"
+)]
+fn get_periodic_column_polys_wrong_number_of_offsets() {
+    let col1 = vec![BaseElement::ONE, BaseElement::ZERO];
+    let mut air = MockAir::with_periodic_columns(vec![col1], 16);
+    air.periodic_offsets = vec![1, 2];
+    air.get_periodic_column_polys();
+}
+
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
@@ -188,6 +218,184 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&5], constraint.cc().clone());
 }
 
+// LOOKUP ARGUMENTS
+// ================================================================================================
+
+#[test]
+fn air_context_with_lookups() {
+    let lookup = LookupArgument::new(vec![0], 1, vec![vec![2], vec![3]]);
+    assert_eq!(TransitionConstraintDegree::new(4), lookup.transition_constraint_degree());
+    assert_eq!(3, lookup.max_column_index());
+
+    let context =
+        build_multi_segment_context::<BaseElement>(2, 3).with_lookups(vec![lookup.clone()]);
+    assert_eq!(&[lookup], context.lookups());
+    assert_eq!(2, context.num_aux_transition_constraints());
+    assert_eq!(4, context.num_assertions());
+    assert_eq!(1, context.lookup_aux_column_offset());
+    assert_eq!(4, context.ce_domain_size() / context.trace_len());
+}
+
+#[test]
+#[should_panic(expected = "lookup arguments require at least 2 random elements, but only 1 were")]
+fn air_context_with_lookups_not_enough_rand_elements() {
+    let lookup = LookupArgument::new(vec![0], 1, vec![vec![2]]);
+    build_multi_segment_context::<BaseElement>(2, 1).with_lookups(vec![lookup]);
+}
+
+#[test]
+#[should_panic(expected = "lookup argument references column 4, but main trace segment has only 4")]
+fn air_context_with_lookups_column_out_of_bounds() {
+    let lookup = LookupArgument::new(vec![0], 1, vec![vec![4]]);
+    build_multi_segment_context::<BaseElement>(2, 2).with_lookups(vec![lookup]);
+}
+
+// FRAME OFFSETS
+// ================================================================================================
+
+#[test]
+fn air_context_with_frame_offsets() {
+    let context = build_multi_segment_context::<BaseElement>(2, 1);
+    assert_eq!(&[0, 1], context.frame_offsets());
+    assert_eq!(1, context.num_transition_exemptions());
+
+    // the number of transition exemptions is raised to the largest offset
+    let context = context.with_frame_offsets(vec![0, 1, 7]);
+    assert_eq!(&[0, 1, 7], context.frame_offsets());
+    assert_eq!(7, context.max_frame_offset());
+    assert_eq!(7, context.num_transition_exemptions());
+    assert_eq!(0, context.num_blinding_rows());
+
+    let frame = EvaluationFrame::<BaseElement>::with_offsets(4, context.frame_offsets().to_vec());
+    assert_eq!(3, frame.num_rows());
+    assert_eq!(&[0, 1, 7], frame.offsets());
+    assert_eq!(4, frame.row(2).len());
+}
+
+#[test]
+#[should_panic(expected = "frame offsets must start with [0, 1], but were [0, 2]")]
+fn air_context_with_frame_offsets_invalid_start() {
+    build_multi_segment_context::<BaseElement>(2, 1).with_frame_offsets(vec![0, 2]);
+}
+
+#[test]
+#[should_panic(expected = "frame offsets must be strictly increasing, but were [0, 1, 3, 3]")]
+fn air_context_with_frame_offsets_not_increasing() {
+    build_multi_segment_context::<BaseElement>(2, 1).with_frame_offsets(vec![0, 1, 3, 3]);
+}
+
+#[test]
+#[should_panic(expected = "number of transition exemptions must be at least 3, but was 2")]
+fn air_context_with_frame_offsets_too_few_exemptions() {
+    build_multi_segment_context::<BaseElement>(2, 1)
+        .with_frame_offsets(vec![0, 1, 3])
+        .set_num_transition_exemptions(2);
+}
+
+// EVALUATION FRAME
+// ================================================================================================
+
+#[test]
+fn evaluation_frame_row_arrays() {
+    let current: Vec<BaseElement> = (1..4_u32).map(BaseElement::from).collect();
+    let next: Vec<BaseElement> = (4..7_u32).map(BaseElement::from).collect();
+    let frame = EvaluationFrame::from_rows(current.clone(), next.clone());
+    assert_eq!(3, frame.num_columns());
+    assert_eq!(current.as_slice(), frame.current_array::<3>());
+    assert_eq!(next.as_slice(), frame.next_array::<3>());
+    assert_eq!(next.as_slice(), frame.row_array::<3>(1));
+}
+
+#[test]
+#[should_panic(expected = "expected a frame with 4 columns, but the frame has 3 columns")]
+fn evaluation_frame_row_arrays_wrong_width() {
+    let frame = EvaluationFrame::<BaseElement>::new(3);
+    frame.current_array::<4>();
+}
+
+// CUSTOM TRANSITION DIVISORS
+// ================================================================================================
+
+#[test]
+fn air_context_with_transition_divisor() {
+    let context = build_multi_segment_context::<BaseElement>(2, 1);
+    let default_divisor = ConstraintDivisor::from_transition(16, 1);
+    assert_eq!(default_divisor, context.get_transition_divisor(0));
+    assert_eq!(default_divisor, context.get_transition_divisor(1));
+    assert_eq!(1, context.num_constraint_composition_columns());
+
+    // enforce the degree 2 main constraint on every 8th step only; this reduces the degree of the
+    // divisor from 15 to 2, and thus, the degree of the quotient increases from 15 to 28
+    let divisor = ConstraintDivisor::from_periodic_transition(16, 3, 8);
+    let context = context.with_transition_divisor(0, divisor.clone());
+    assert_eq!(divisor, context.get_transition_divisor(0));
+    assert_eq!(default_divisor, context.get_transition_divisor(1));
+    assert_eq!(1, context.custom_transition_divisors().len());
+    assert_eq!(2, context.num_constraint_composition_columns());
+    assert_eq!(32, context.ce_domain_size());
+}
+
+#[test]
+#[should_panic(expected = "transition constraint index must be smaller than 2, but was 2")]
+fn air_context_with_transition_divisor_invalid_index() {
+    build_multi_segment_context::<BaseElement>(2, 1)
+        .with_transition_divisor(2, ConstraintDivisor::from_periodic_transition(16, 0, 8));
+}
+
+#[test]
+fn transition_constraints_with_custom_divisors() {
+    let divisor = ConstraintDivisor::from_transition_exemptions(16, &[0, 15]);
+    let context = build_multi_segment_context::<BaseElement>(2, 1)
+        .with_transition_divisor(1, divisor.clone());
+
+    let coefficients = [BaseElement::new(3), BaseElement::new(5)];
+    let constraints = TransitionConstraints::new(&context, &coefficients);
+    assert_eq!(2, constraints.num_divisors());
+    assert_eq!(&ConstraintDivisor::from_transition(16, 1), constraints.divisor());
+    assert_eq!(&[0], constraints.main_constraint_divisors());
+    assert_eq!(&[1], constraints.aux_constraint_divisors());
+
+    // each constraint evaluation must be divided by the evaluation of its own divisor
+    let x = BaseElement::new(11);
+    let main_evaluations = [BaseElement::new(7)];
+    let aux_evaluations = [BaseElement::new(13)];
+    let expected = coefficients[0] * main_evaluations[0] / constraints.divisor().evaluate_at(x)
+        + coefficients[1] * aux_evaluations[0] / divisor.evaluate_at(x);
+    assert_eq!(
+        expected,
+        constraints.combine_evaluations(&main_evaluations, &aux_evaluations, x)
+    );
+}
+
+// AIR COMPOSITION
+// ================================================================================================
+
+#[test]
+fn air_composition() {
+    let first_info = TraceInfo::with_meta(4, 16, vec![1]);
+    let second_info = TraceInfo::with_meta(3, 16, vec![2]);
+    let trace_info = TraceInfo::compose(&first_info, &second_info);
+    assert_eq!(7, trace_info.main_trace_width());
+    assert_eq!((first_info.clone(), second_info.clone()), trace_info.decompose());
+
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let pub_inputs = ComposedInputs { first: (), second: () };
+    let air = AirComposition::<MockAir, MockAir>::new(trace_info, pub_inputs, options);
+    assert_eq!(&first_info, air.first().trace_info());
+    assert_eq!(&second_info, air.second().trace_info());
+    assert_eq!(2, air.context().num_main_transition_constraints());
+    assert_eq!(3, air.context().num_assertions());
+    assert!(air.context().custom_transition_divisors().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "trace info does not describe a composition of two execution traces")]
+fn air_composition_invalid_trace_info() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let pub_inputs = ComposedInputs { first: (), second: () };
+    AirComposition::<MockAir, MockAir>::new(TraceInfo::new(7, 16), pub_inputs, options);
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -195,6 +403,7 @@ struct MockAir {
     context: AirContext<BaseElement>,
     assertions: Vec<Assertion<BaseElement>>,
     periodic_columns: Vec<Vec<BaseElement>>,
+    periodic_offsets: Vec<usize>,
 }
 
 impl MockAir {
@@ -230,11 +439,12 @@ impl Air for MockAir {
 
     fn new(trace_info: TraceInfo, _pub_inputs: (), _options: ProofOptions) -> Self {
         let num_assertions = trace_info.meta()[0] as usize;
-        let context = build_context(trace_info.length(), trace_info.width(), num_assertions);
+        let context = build_context(trace_info, num_assertions);
         MockAir {
             context,
             assertions: Vec::new(),
             periodic_columns: Vec::new(),
+            periodic_offsets: Vec::new(),
         }
     }
 
@@ -246,6 +456,10 @@ impl Air for MockAir {
         self.periodic_columns.clone()
     }
 
+    fn get_periodic_column_offsets(&self) -> Vec<usize> {
+        self.periodic_offsets.clone()
+    }
+
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
         self.assertions.clone()
     }
@@ -262,17 +476,29 @@ impl Air for MockAir {
 // UTILITY FUNCTIONS
 // ================================================================================================
 
-pub fn build_context<B: StarkField>(
-    trace_length: usize,
-    trace_width: usize,
-    num_assertions: usize,
-) -> AirContext<B> {
+pub fn build_context<B: StarkField>(trace_info: TraceInfo, num_assertions: usize) -> AirContext<B> {
     let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
     let t_degrees = vec![TransitionConstraintDegree::new(2)];
-    let trace_info = TraceInfo::new(trace_width, trace_length);
     AirContext::new(trace_info, t_degrees, num_assertions, options)
 }
 
+pub fn build_multi_segment_context<B: StarkField>(
+    aux_width: usize,
+    num_aux_rands: usize,
+) -> AirContext<B> {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let trace_info = TraceInfo::new_multi_segment(4, aux_width, num_aux_rands, 16, vec![]);
+    AirContext::new_multi_segment(
+        trace_info,
+        vec![TransitionConstraintDegree::new(2)],
+        vec![TransitionConstraintDegree::new(1)],
+        1,
+        1,
+        None,
+        options,
+    )
+}
+
 pub fn build_prng() -> DefaultRandomCoin<Blake3_256<BaseElement>> {
     RandomCoin::new(&[BaseElement::ZERO; 32])
 }
@@ -6,7 +6,11 @@
 use alloc::{string::ToString, vec::Vec};
 
 use math::{StarkField, ToElements};
-use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
 
 // CONSTANTS
 // ================================================================================================
@@ -21,6 +25,7 @@ use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serial
 /// segment and one auxiliary segment. Metadata is just a vector of bytes and can store any values
 /// up to 64KB in size.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct TraceInfo {
     main_segment_width: usize,
     aux_segment_width: usize,
@@ -32,8 +37,9 @@ pub struct TraceInfo {
 impl TraceInfo {
     /// Smallest allowed execution trace length; currently set at 8.
     pub const MIN_TRACE_LENGTH: usize = 8;
-    /// Maximum number of columns in an execution trace (across all segments); currently set at 255.
-    pub const MAX_TRACE_WIDTH: usize = 255;
+    /// Maximum number of columns in an execution trace (across all segments); currently set at
+    /// 65535.
+    pub const MAX_TRACE_WIDTH: usize = 65535;
     /// Maximum number of bytes in trace metadata; currently set at 65535.
     pub const MAX_META_LENGTH: usize = 65535;
     /// Maximum number of random elements in the auxiliary trace segment; currently set to 255.
@@ -48,7 +54,7 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
         Self::with_meta(width, length, vec![])
@@ -60,7 +66,7 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     /// * Length of `meta` is greater than 65535;
     pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
@@ -76,7 +82,7 @@ impl TraceInfo {
     /// # Panics
     /// Panics if:
     /// * The width of the first trace segment is zero.
-    /// * Total width of all trace segments is greater than 255.
+    /// * Total width of all trace segments is greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     /// * A zero entry in auxiliary segment width array is followed by a non-zero entry.
     /// * Number of random elements for the auxiliary trace segment of non-zero width is set to
@@ -141,12 +147,41 @@ impl TraceInfo {
         }
     }
 
+    /// Creates a new [TraceInfo] describing an execution trace of an
+    /// [AirComposition](crate::AirComposition) from the trace infos of its two components.
+    ///
+    /// The main segment of the resulting trace consists of the columns of the first component
+    /// followed by the columns of the second component. Trace infos of the components are stored
+    /// in the metadata of the resulting trace info, and thus, are bound to the proof.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Lengths of the component traces are not the same.
+    /// * Any of the component traces has an auxiliary segment.
+    /// * Total width of the component traces is greater than 65535.
+    /// * Serialized trace infos of the components are longer than 65535 bytes.
+    pub fn compose(first: &TraceInfo, second: &TraceInfo) -> Self {
+        assert_eq!(
+            first.length(),
+            second.length(),
+            "components of an AIR composition must have traces of the same length"
+        );
+        assert!(
+            !first.is_multi_segment() && !second.is_multi_segment(),
+            "components of an AIR composition cannot have auxiliary trace segments"
+        );
+
+        let mut meta = first.to_bytes();
+        second.write_into(&mut meta);
+        Self::with_meta(first.main_trace_width() + second.main_trace_width(), first.length(), meta)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns the total number of columns in an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 65535.
     pub fn width(&self) -> usize {
         self.main_segment_width + self.aux_segment_width
     }
@@ -170,7 +205,7 @@ impl TraceInfo {
 
     /// Returns the number of columns in the main segment of an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 65535.
     pub fn main_trace_width(&self) -> usize {
         self.main_segment_width
     }
@@ -208,22 +243,51 @@ impl TraceInfo {
     pub fn get_num_aux_segment_rand_elements(&self) -> usize {
         self.num_aux_segment_rands
     }
+
+    // COMPOSITION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns trace infos of the two components of an execution trace described by this trace
+    /// info, which must have been created via [TraceInfo::compose()].
+    ///
+    /// # Panics
+    /// Panics if this trace info does not describe a valid composition of two execution traces.
+    pub(crate) fn decompose(&self) -> (TraceInfo, TraceInfo) {
+        let mut reader = SliceReader::new(&self.trace_meta);
+        let components = TraceInfo::read_from(&mut reader)
+            .and_then(|first| TraceInfo::read_from(&mut reader).map(|second| (first, second)));
+        let (first, second) = match components {
+            Ok(components) if !reader.has_more_bytes() => components,
+            _ => panic!("trace info does not describe a composition of two execution traces"),
+        };
+        assert!(
+            first.length() == self.length()
+                && second.length() == self.length()
+                && first.main_trace_width() + second.main_trace_width() == self.main_trace_width()
+                && !first.is_multi_segment()
+                && !second.is_multi_segment()
+                && !self.is_multi_segment(),
+            "trace info is inconsistent with the trace infos of its components"
+        );
+        (first, second)
+    }
 }
 
 impl<E: StarkField> ToElements<E> for TraceInfo {
     fn to_elements(&self) -> Vec<E> {
         let mut result = Vec::new();
 
-        // main segment width, number of auxiliary segments, and parameters of the first auxiliary
-        // segment (if present) go into the first field element; we assume that each parameter can
-        // be encoded in 8 bits (which is enforced by the constructor)
-        let mut buf = self.main_segment_width as u32;
-        buf = (buf << 8) | self.num_aux_segments() as u32;
+        // main segment width and number of auxiliary segments go into the first field element,
+        // and parameters of the auxiliary segment (if present) go into the second field element;
+        // we assume that segment widths can be encoded in 16 bits and all other parameters in 8
+        // bits (which is enforced by the constructor). thus, each element encodes at most 24 bits,
+        // and fits into fields with small moduli (e.g., 31-bit fields).
+        let buf = ((self.main_segment_width as u32) << 8) | self.num_aux_segments() as u32;
+        result.push(E::from(buf));
         if self.num_aux_segments() == 1 {
-            buf = (buf << 8) | self.aux_segment_width as u32;
-            buf = (buf << 8) | self.num_aux_segment_rands as u32;
+            let buf = ((self.aux_segment_width as u32) << 8) | self.num_aux_segment_rands as u32;
+            result.push(E::from(buf));
         }
-        result.push(E::from(buf));
 
         // We assume here that the trace length is never greater than 2^32.
         result.push(E::from(self.trace_length as u32));
@@ -244,14 +308,9 @@ impl<E: StarkField> ToElements<E> for TraceInfo {
 impl Serializable for TraceInfo {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        // store segments
-        target.write_u8(self.main_segment_width as u8);
-
-        debug_assert!(
-            self.aux_segment_width <= u8::MAX as usize,
-            "aux segment width does not fit into u8 value"
-        );
-        target.write_u8(self.aux_segment_width as u8);
+        // store segments; segment widths are guaranteed to fit into u16 values by the constructor
+        target.write_u16(self.main_segment_width as u16);
+        target.write_u16(self.aux_segment_width as u16);
         debug_assert!(
             self.num_aux_segment_rands <= u8::MAX as usize,
             "aux segment random element count does not fit into u8 value"
@@ -274,7 +333,7 @@ impl Deserializable for TraceInfo {
     /// Returns an error of a valid [`TraceInfo`] struct could not be read from the specified
     /// `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let main_segment_width = source.read_u8()? as usize;
+        let main_segment_width = source.read_u16()? as usize;
         if main_segment_width == 0 {
             return Err(DeserializationError::InvalidValue(
                 "main trace segment width must be greater than zero".to_string(),
@@ -282,10 +341,10 @@ impl Deserializable for TraceInfo {
         }
 
         // read auxiliary trace segment width
-        let aux_segment_width = source.read_u8()? as usize;
+        let aux_segment_width = source.read_u16()? as usize;
 
         let full_trace_width = main_segment_width + aux_segment_width;
-        if full_trace_width >= TraceInfo::MAX_TRACE_WIDTH {
+        if full_trace_width > TraceInfo::MAX_TRACE_WIDTH {
             return Err(DeserializationError::InvalidValue(format!(
                 "full trace width cannot be greater than {}, but was {}",
                 TraceInfo::MAX_TRACE_WIDTH,
@@ -342,6 +401,7 @@ impl Deserializable for TraceInfo {
 #[cfg(test)]
 mod tests {
     use math::{fields::f64::BaseElement, FieldElement};
+    use utils::{Deserializable, Serializable};
 
     use super::{ToElements, TraceInfo};
 
@@ -370,15 +430,20 @@ mod tests {
         let trace_meta = vec![1_u8, 2, 3, 4];
 
         let expected = {
-            let first_ele =
-                u32::from_le_bytes([aux_rands as u8, aux_width, num_aux_segments, main_width]);
+            let first_ele = u32::from_le_bytes([num_aux_segments, main_width, 0, 0]);
+            let second_ele = u32::from_le_bytes([aux_rands as u8, aux_width, 0, 0]);
 
             // `trace_meta` is 4 bytes, so fits into a single element
             let mut meta_bytes = trace_meta.clone();
             meta_bytes.resize(BaseElement::ELEMENT_BYTES, 0);
             let meta_ele = BaseElement::try_from(meta_bytes.as_slice()).unwrap();
 
-            vec![BaseElement::from(first_ele), BaseElement::from(trace_length), meta_ele]
+            vec![
+                BaseElement::from(first_ele),
+                BaseElement::from(second_ele),
+                BaseElement::from(trace_length),
+                meta_ele,
+            ]
         };
 
         let info = TraceInfo::new_multi_segment(
@@ -390,5 +455,36 @@ mod tests {
         );
 
         assert_eq!(expected, info.to_elements());
+
+        // --- test trace with segments wider than 255 columns ----------------
+        let main_width = 300_u32;
+        let aux_width = 700_u32;
+        let aux_rands = 4_u32;
+
+        let expected = vec![
+            BaseElement::from((main_width << 8) | num_aux_segments as u32),
+            BaseElement::from((aux_width << 8) | aux_rands),
+            BaseElement::from(trace_length),
+        ];
+
+        let info = TraceInfo::new_multi_segment(
+            main_width as usize,
+            aux_width as usize,
+            aux_rands as usize,
+            trace_length as usize,
+            vec![],
+        );
+        assert_eq!(expected, info.to_elements());
+    }
+
+    #[test]
+    fn trace_info_serialization() {
+        let info = TraceInfo::new_multi_segment(1000, 2000, 16, 1024, vec![1, 2, 3]);
+        let bytes = info.to_bytes();
+        assert_eq!(info, TraceInfo::read_from_bytes(&bytes).unwrap());
+
+        let info = TraceInfo::new(TraceInfo::MAX_TRACE_WIDTH, 8);
+        let bytes = info.to_bytes();
+        assert_eq!(info, TraceInfo::read_from_bytes(&bytes).unwrap());
     }
 }
@@ -6,6 +6,8 @@
 use alloc::vec::Vec;
 use core::cmp;
 
+use utils::{ByteWriter, Serializable};
+
 use super::{super::super::ProofOptions, MIN_CYCLE_LENGTH};
 
 // TRANSITION CONSTRAINT DEGREE
@@ -114,3 +116,14 @@ impl TransitionConstraintDegree {
         cmp::max(degree_bound.next_power_of_two(), ProofOptions::MIN_BLOWUP_FACTOR)
     }
 }
+
+impl Serializable for TransitionConstraintDegree {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.base);
+        target.write_usize(self.cycles.len());
+        for &cycle_length in self.cycles.iter() {
+            target.write_usize(cycle_length);
+        }
+    }
+}
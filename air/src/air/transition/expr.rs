@@ -0,0 +1,528 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use math::StarkField;
+
+use super::{EvaluationFrame, ExtensionOf, FieldElement, TransitionConstraintDegree};
+
+// CONSTRAINT EXPRESSION
+// ================================================================================================
+
+/// A symbolic expression describing a transition constraint.
+///
+/// Expressions are built from references to trace columns, periodic columns, random elements,
+/// and constants, combined via the usual arithmetic operators. For example, a constraint which
+/// requires the value in column 0 at the next step to be equal to the product of values in
+/// columns 0 and 1 at the current step, scaled by a periodic column, can be written as
+/// `Expr::col(0).next() - Expr::col(0) * Expr::col(1) * Expr::periodic(0)`. When printed, this
+/// expression reads as `main'[0] - main[0] * main[1] * periodic[0]`.
+///
+/// Given lengths of periodic columns and length of the execution trace, the framework derives
+/// the degree of an expression via [degree()](Expr::degree), and thus, there is no need to
+/// describe constraint degrees manually. The derived degree assumes that no terms of the
+/// expression cancel out; for expressions where this is not the case (e.g., `a * b - a * b`),
+/// the derived degree will be higher than the actual degree of the constraint.
+///
+/// Expressions are evaluated against an evaluation frame via [evaluate()](Expr::evaluate) for
+/// the main trace segment, and via [evaluate_aux()](Expr::evaluate_aux) for the auxiliary trace
+/// segment. Thus, an [Air](crate::Air) can keep a list of expressions and evaluate them in its
+/// [evaluate_transition()](crate::Air::evaluate_transition) and
+/// [evaluate_aux_transition()](crate::Air::evaluate_aux_transition) methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr<B: StarkField> {
+    /// A constant value.
+    Const(B),
+    /// A value in the specified column of the main trace segment, located in the specified row of
+    /// the evaluation frame.
+    Main { column: usize, row: usize },
+    /// A value in the specified column of the auxiliary trace segment, located in the specified
+    /// row of the evaluation frame.
+    Aux { column: usize, row: usize },
+    /// A value of the periodic column at the specified index.
+    Periodic(usize),
+    /// A random element at the specified index drawn for the auxiliary trace segment.
+    Rand(usize),
+    /// A sum of two expressions.
+    Add(Box<Expr<B>>, Box<Expr<B>>),
+    /// A difference of two expressions.
+    Sub(Box<Expr<B>>, Box<Expr<B>>),
+    /// A product of two expressions.
+    Mul(Box<Expr<B>>, Box<Expr<B>>),
+    /// A negation of an expression.
+    Neg(Box<Expr<B>>),
+    /// An expression raised to the specified power.
+    Exp(Box<Expr<B>>, u32),
+}
+
+impl<B: StarkField> Expr<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an expression referring to the specified column of the main trace segment in the
+    /// current row.
+    pub fn col(column: usize) -> Self {
+        Self::Main { column, row: 0 }
+    }
+
+    /// Returns an expression referring to the specified column of the auxiliary trace segment in
+    /// the current row.
+    pub fn aux(column: usize) -> Self {
+        Self::Aux { column, row: 0 }
+    }
+
+    /// Returns an expression referring to the periodic column at the specified index.
+    pub fn periodic(index: usize) -> Self {
+        Self::Periodic(index)
+    }
+
+    /// Returns an expression referring to the random element at the specified index drawn for
+    /// the auxiliary trace segment.
+    pub fn rand(index: usize) -> Self {
+        Self::Rand(index)
+    }
+
+    /// Returns an expression for the specified constant value.
+    pub fn constant(value: B) -> Self {
+        Self::Const(value)
+    }
+
+    /// Returns this column reference moved to the next row of the evaluation frame.
+    ///
+    /// # Panics
+    /// Panics if this expression is not a reference to a trace column.
+    pub fn next(self) -> Self {
+        self.at_row(1)
+    }
+
+    /// Returns this column reference moved to the specified row of the evaluation frame.
+    ///
+    /// The row is located `offsets()[row]` steps after the current row in the execution trace
+    /// (see [EvaluationFrame::row()]).
+    ///
+    /// # Panics
+    /// Panics if this expression is not a reference to a trace column.
+    pub fn at_row(self, row: usize) -> Self {
+        match self {
+            Self::Main { column, .. } => Self::Main { column, row },
+            Self::Aux { column, .. } => Self::Aux { column, row },
+            _ => panic!("only references to trace columns can be moved to another row"),
+        }
+    }
+
+    /// Returns this expression raised to the specified power.
+    pub fn exp(self, power: u32) -> Self {
+        Self::Exp(Box::new(self), power)
+    }
+
+    // DEGREE
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the degree of this expression for an execution trace of the specified length.
+    ///
+    /// `periodic_column_lengths` must contain the cycle length of each periodic column (i.e., the
+    /// number of values returned for the column from
+    /// [get_periodic_column_values()](crate::Air::get_periodic_column_values)). The degree of a
+    /// sum or a difference is the larger of the degrees of its operands, and the degree of a
+    /// product is the combination of the degrees of its operands.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * This expression does not refer to any trace columns.
+    /// * This expression refers to a periodic column not described in `periodic_column_lengths`.
+    pub fn degree(
+        &self,
+        periodic_column_lengths: &[usize],
+        trace_length: usize,
+    ) -> TransitionConstraintDegree {
+        let (base, cycles) = self.degree_parts(periodic_column_lengths, trace_length);
+        assert!(base > 0, "constraint expression must refer to at least one trace column");
+        TransitionConstraintDegree::with_cycles(base, cycles)
+    }
+
+    // EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates this expression against the provided main trace segment frame and periodic
+    /// column values.
+    ///
+    /// # Panics
+    /// Panics if this expression refers to auxiliary trace columns or random elements.
+    pub fn evaluate<E>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E]) -> E
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        self.eval::<E, E>(frame, None, periodic_values, &[])
+    }
+
+    /// Evaluates this expression against the provided main and auxiliary trace segment frames,
+    /// periodic column values, and random elements drawn for the auxiliary trace segment.
+    pub fn evaluate_aux<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &[E],
+    ) -> E
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        self.eval(main_frame, Some(aux_frame), periodic_values, aux_rand_elements)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the base degree and the cycle lengths of periodic columns for this expression.
+    fn degree_parts(
+        &self,
+        periodic_column_lengths: &[usize],
+        trace_length: usize,
+    ) -> (usize, Vec<usize>) {
+        match self {
+            Self::Const(_) | Self::Rand(_) => (0, Vec::new()),
+            Self::Main { .. } | Self::Aux { .. } => (1, Vec::new()),
+            Self::Periodic(index) => {
+                let cycle_length = *periodic_column_lengths.get(*index).unwrap_or_else(|| {
+                    panic!("length of periodic column {index} has not been provided")
+                });
+                (0, vec![cycle_length])
+            },
+            Self::Add(lhs, rhs) | Self::Sub(lhs, rhs) => {
+                let lhs = lhs.degree_parts(periodic_column_lengths, trace_length);
+                let rhs = rhs.degree_parts(periodic_column_lengths, trace_length);
+                // pick the operand of the larger evaluation degree; on ties, pick the operand
+                // which requires the larger blowup factor
+                let lhs_key = (evaluation_degree(&lhs, trace_length), lhs.0 + lhs.1.len());
+                let rhs_key = (evaluation_degree(&rhs, trace_length), rhs.0 + rhs.1.len());
+                if lhs_key >= rhs_key {
+                    lhs
+                } else {
+                    rhs
+                }
+            },
+            Self::Mul(lhs, rhs) => {
+                let (lhs_base, mut cycles) =
+                    lhs.degree_parts(periodic_column_lengths, trace_length);
+                let (rhs_base, rhs_cycles) =
+                    rhs.degree_parts(periodic_column_lengths, trace_length);
+                cycles.extend(rhs_cycles);
+                (lhs_base + rhs_base, cycles)
+            },
+            Self::Neg(expr) => expr.degree_parts(periodic_column_lengths, trace_length),
+            Self::Exp(expr, power) => {
+                let power = *power as usize;
+                let (base, cycles) = expr.degree_parts(periodic_column_lengths, trace_length);
+                let cycles = cycles.iter().copied().cycle().take(cycles.len() * power).collect();
+                (base * power, cycles)
+            },
+        }
+    }
+
+    /// Recursively evaluates this expression.
+    fn eval<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: Option<&EvaluationFrame<E>>,
+        periodic_values: &[F],
+        aux_rand_elements: &[E],
+    ) -> E
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        match self {
+            Self::Const(value) => E::from(*value),
+            Self::Main { column, row } => E::from(main_frame.row(*row)[*column]),
+            Self::Aux { column, row } => {
+                let aux_frame = aux_frame
+                    .expect("auxiliary trace columns cannot be referred to in main constraints");
+                aux_frame.row(*row)[*column]
+            },
+            Self::Periodic(index) => E::from(periodic_values[*index]),
+            Self::Rand(index) => {
+                assert!(
+                    aux_frame.is_some(),
+                    "random elements cannot be referred to in main constraints"
+                );
+                aux_rand_elements[*index]
+            },
+            Self::Add(lhs, rhs) => {
+                lhs.eval(main_frame, aux_frame, periodic_values, aux_rand_elements)
+                    + rhs.eval(main_frame, aux_frame, periodic_values, aux_rand_elements)
+            },
+            Self::Sub(lhs, rhs) => {
+                lhs.eval(main_frame, aux_frame, periodic_values, aux_rand_elements)
+                    - rhs.eval(main_frame, aux_frame, periodic_values, aux_rand_elements)
+            },
+            Self::Mul(lhs, rhs) => {
+                lhs.eval(main_frame, aux_frame, periodic_values, aux_rand_elements)
+                    * rhs.eval(main_frame, aux_frame, periodic_values, aux_rand_elements)
+            },
+            Self::Neg(expr) => {
+                -expr.eval(main_frame, aux_frame, periodic_values, aux_rand_elements)
+            },
+            Self::Exp(expr, power) => {
+                let value = expr.eval(main_frame, aux_frame, periodic_values, aux_rand_elements);
+                exp(value, *power)
+            },
+        }
+    }
+
+    /// Returns the binding strength of the top-level operation of this expression; this is used
+    /// to decide where parentheses are needed when printing expressions.
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add(..) | Self::Sub(..) => 1,
+            Self::Mul(..) => 2,
+            Self::Neg(_) => 3,
+            Self::Exp(..) => 4,
+            _ => 5,
+        }
+    }
+
+    /// Writes the specified sub-expression into the formatter, wrapping it in parentheses if its
+    /// precedence is lower than `min_precedence`.
+    fn fmt_operand(f: &mut fmt::Formatter<'_>, expr: &Expr<B>, min_precedence: u8) -> fmt::Result {
+        if expr.precedence() < min_precedence {
+            write!(f, "({expr})")
+        } else {
+            write!(f, "{expr}")
+        }
+    }
+}
+
+// OPERATORS
+// ================================================================================================
+
+impl<B: StarkField> Add for Expr<B> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::Add(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<B: StarkField> Add<B> for Expr<B> {
+    type Output = Self;
+
+    fn add(self, rhs: B) -> Self {
+        self + Self::Const(rhs)
+    }
+}
+
+impl<B: StarkField> Sub for Expr<B> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::Sub(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<B: StarkField> Sub<B> for Expr<B> {
+    type Output = Self;
+
+    fn sub(self, rhs: B) -> Self {
+        self - Self::Const(rhs)
+    }
+}
+
+impl<B: StarkField> Mul for Expr<B> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::Mul(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<B: StarkField> Mul<B> for Expr<B> {
+    type Output = Self;
+
+    fn mul(self, rhs: B) -> Self {
+        self * Self::Const(rhs)
+    }
+}
+
+impl<B: StarkField> Neg for Expr<B> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::Neg(Box::new(self))
+    }
+}
+
+// PRETTY PRINTING
+// ================================================================================================
+
+/// Prints an expression in a human-readable form.
+///
+/// Values in the current row of the main and auxiliary trace segments are printed as `main[i]`
+/// and `aux[i]` respectively; values in the next row are printed as `main'[i]` and `aux'[i]`, and
+/// values in other rows of the evaluation frame are printed as `main@r[i]` and `aux@r[i]`, where
+/// `r` is the index of the row in the frame.
+impl<B: StarkField> fmt::Display for Expr<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Const(value) => write!(f, "{value}"),
+            Self::Main { column, row } => fmt_column(f, "main", *column, *row),
+            Self::Aux { column, row } => fmt_column(f, "aux", *column, *row),
+            Self::Periodic(index) => write!(f, "periodic[{index}]"),
+            Self::Rand(index) => write!(f, "rand[{index}]"),
+            Self::Add(lhs, rhs) => {
+                Self::fmt_operand(f, lhs, 1)?;
+                f.write_str(" + ")?;
+                Self::fmt_operand(f, rhs, 1)
+            },
+            Self::Sub(lhs, rhs) => {
+                Self::fmt_operand(f, lhs, 1)?;
+                f.write_str(" - ")?;
+                Self::fmt_operand(f, rhs, 2)
+            },
+            Self::Mul(lhs, rhs) => {
+                Self::fmt_operand(f, lhs, 2)?;
+                f.write_str(" * ")?;
+                Self::fmt_operand(f, rhs, 2)
+            },
+            Self::Neg(expr) => {
+                f.write_str("-")?;
+                Self::fmt_operand(f, expr, 4)
+            },
+            Self::Exp(expr, power) => {
+                Self::fmt_operand(f, expr, 5)?;
+                write!(f, "^{power}")
+            },
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes the evaluation degree of an expression described by its base degree and cycle
+/// lengths of periodic columns; see [TransitionConstraintDegree::get_evaluation_degree()].
+fn evaluation_degree((base, cycles): &(usize, Vec<usize>), trace_length: usize) -> usize {
+    let mut result = base * (trace_length - 1);
+    for cycle_length in cycles.iter() {
+        result += (trace_length / cycle_length) * (cycle_length - 1);
+    }
+    result
+}
+
+/// Raises `value` to the specified `power` using square-and-multiply.
+fn exp<E: FieldElement>(value: E, power: u32) -> E {
+    let mut result = E::ONE;
+    for i in (0..u32::BITS - power.leading_zeros()).rev() {
+        result = result.square();
+        if (power >> i) & 1 == 1 {
+            result *= value;
+        }
+    }
+    result
+}
+
+/// Writes a reference to a trace column into the formatter.
+fn fmt_column(f: &mut fmt::Formatter<'_>, segment: &str, column: usize, row: usize) -> fmt::Result {
+    match row {
+        0 => write!(f, "{segment}[{column}]"),
+        1 => write!(f, "{segment}'[{column}]"),
+        _ => write!(f, "{segment}@{row}[{column}]"),
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use math::fields::f64::BaseElement;
+
+    use super::*;
+
+    #[test]
+    fn expr_degree() {
+        let trace_length = 64;
+
+        // a * b' - c
+        let expr: Expr<BaseElement> = Expr::col(0) * Expr::col(1).next() - Expr::col(2);
+        assert_eq!(TransitionConstraintDegree::new(2), expr.degree(&[], trace_length));
+
+        // (a - 1)^3 * p0
+        let expr = (Expr::col(0) - BaseElement::ONE).exp(3) * Expr::periodic(0);
+        assert_eq!(
+            TransitionConstraintDegree::with_cycles(3, vec![8]),
+            expr.degree(&[8], trace_length)
+        );
+
+        // a * p0^2 + b * c: the first term has a higher evaluation degree
+        let expr: Expr<BaseElement> =
+            Expr::col(0) * Expr::periodic(0).exp(2) + Expr::col(1) * Expr::col(2);
+        assert_eq!(
+            TransitionConstraintDegree::with_cycles(1, vec![32, 32]),
+            expr.degree(&[32], trace_length)
+        );
+
+        // random elements and constants do not contribute to the degree
+        let expr = Expr::aux(0).next() - Expr::aux(0) * Expr::rand(1) + BaseElement::new(5);
+        assert_eq!(TransitionConstraintDegree::new(1), expr.degree(&[], trace_length));
+    }
+
+    #[test]
+    #[should_panic(expected = "constraint expression must refer to at least one trace column")]
+    fn expr_degree_without_trace_columns() {
+        let expr: Expr<BaseElement> = Expr::periodic(0) * BaseElement::new(3);
+        expr.degree(&[8], 64);
+    }
+
+    #[test]
+    fn expr_evaluate() {
+        let frame = EvaluationFrame::from_rows(
+            vec![BaseElement::new(2), BaseElement::new(3)],
+            vec![BaseElement::new(5), BaseElement::new(7)],
+        );
+        let periodic_values = [BaseElement::new(11)];
+
+        // a' - a * b * p0 = 5 - 2 * 3 * 11
+        let expr = Expr::col(0).next() - Expr::col(0) * Expr::col(1) * Expr::periodic(0);
+        let expected = BaseElement::new(5) - BaseElement::new(66);
+        assert_eq!(expected, expr.evaluate(&frame, &periodic_values));
+
+        // -(b' + 1)^3 = -512
+        let expr = -(Expr::col(1).next() + BaseElement::ONE).exp(3);
+        assert_eq!(-BaseElement::new(512), expr.evaluate(&frame, &periodic_values));
+
+        // z' - z * (a + r0) = 13 - 17 * (2 + 19)
+        let aux_frame =
+            EvaluationFrame::from_rows(vec![BaseElement::new(17)], vec![BaseElement::new(13)]);
+        let rand_elements = [BaseElement::new(19)];
+        let expr = Expr::aux(0).next() - Expr::aux(0) * (Expr::col(0) + Expr::rand(0));
+        let expected = BaseElement::new(13) - BaseElement::new(17 * 21);
+        assert_eq!(
+            expected,
+            expr.evaluate_aux(&frame, &aux_frame, &periodic_values, &rand_elements)
+        );
+    }
+
+    #[test]
+    fn expr_display() {
+        let expr: Expr<BaseElement> =
+            Expr::col(0).next() - (Expr::col(1) + Expr::col(2)) * Expr::periodic(0);
+        assert_eq!("main'[0] - (main[1] + main[2]) * periodic[0]", expr.to_string());
+
+        let expr: Expr<BaseElement> = Expr::col(0) - (Expr::col(1) - Expr::col(2));
+        assert_eq!("main[0] - (main[1] - main[2])", expr.to_string());
+
+        let expr: Expr<BaseElement> =
+            -(Expr::aux(0).at_row(2) * Expr::rand(1)).exp(2) + BaseElement::new(3);
+        assert_eq!("-(aux@2[0] * rand[1])^2 + 3", expr.to_string());
+    }
+}
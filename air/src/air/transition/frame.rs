@@ -7,18 +7,30 @@ use alloc::vec::Vec;
 
 use super::FieldElement;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Row offsets of the default evaluation frame consisting of the current and the next rows.
+const DEFAULT_OFFSETS: [usize; 2] = [0, 1];
+
 // EVALUATION FRAME
 // ================================================================================================
 
 /// A set of execution trace rows required for evaluation of transition constraints.
 ///
-/// In the current implementation, an evaluation frame always contains two consecutive rows of the
-/// execution trace. It is passed in as one of the parameters into
+/// By default, an evaluation frame contains two consecutive rows of the execution trace: the
+/// current row and the next row. However, an AIR can declare a wider frame via
+/// [AirContext::with_frame_offsets()](crate::AirContext::with_frame_offsets); in such a case, the
+/// frame contains one row for each of the declared offsets (e.g., rows `i`, `i + 1`, and `i + 7`
+/// for offsets `[0, 1, 7]`). The first two offsets are always `0` and `1`, and thus, the first two
+/// rows of a frame are always the current and the next rows.
+///
+/// An evaluation frame is passed in as one of the parameters into
 /// [Air::evaluate_transition()](crate::Air::evaluate_transition) function.
 #[derive(Debug, Clone)]
 pub struct EvaluationFrame<E: FieldElement> {
-    current: Vec<E>,
-    next: Vec<E>,
+    rows: Vec<Vec<E>>,
+    offsets: Vec<usize>,
 }
 
 impl<E: FieldElement> EvaluationFrame<E> {
@@ -27,13 +39,28 @@ impl<E: FieldElement> EvaluationFrame<E> {
 
     /// Returns a new evaluation frame instantiated with the specified number of columns.
     ///
+    /// The frame consists of two rows: the current row and the next row.
+    ///
     /// # Panics
     /// Panics if `num_columns` is zero.
     pub fn new(num_columns: usize) -> Self {
+        Self::with_offsets(num_columns, DEFAULT_OFFSETS.to_vec())
+    }
+
+    /// Returns a new evaluation frame instantiated with the specified number of columns and one
+    /// row for each of the specified row offsets.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_columns` is zero.
+    /// * `offsets` are not valid frame offsets (see
+    ///   [AirContext::with_frame_offsets()](crate::AirContext::with_frame_offsets)).
+    pub fn with_offsets(num_columns: usize, offsets: Vec<usize>) -> Self {
         assert!(num_columns > 0, "number of columns must be greater than zero");
+        validate_frame_offsets(&offsets);
         EvaluationFrame {
-            current: vec![E::ZERO; num_columns],
-            next: vec![E::ZERO; num_columns],
+            rows: vec![vec![E::ZERO; num_columns]; offsets.len()],
+            offsets,
         }
     }
 
@@ -44,9 +71,31 @@ impl<E: FieldElement> EvaluationFrame<E> {
     /// * Lengths of the provided rows are zero.
     /// * Lengths of the provided rows are not the same.
     pub fn from_rows(current: Vec<E>, next: Vec<E>) -> Self {
-        assert!(!current.is_empty(), "a row must contain at least one value");
-        assert_eq!(current.len(), next.len(), "number of values in the rows must be the same");
-        Self { current, next }
+        Self::from_rows_with_offsets(vec![current, next], DEFAULT_OFFSETS.to_vec())
+    }
+
+    /// Returns a new evaluation frame instantiated from the provided rows located at the
+    /// specified row offsets.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Lengths of the provided rows are zero.
+    /// * Lengths of the provided rows are not the same.
+    /// * The number of rows is not the same as the number of offsets.
+    /// * `offsets` are not valid frame offsets (see
+    ///   [AirContext::with_frame_offsets()](crate::AirContext::with_frame_offsets)).
+    pub fn from_rows_with_offsets(rows: Vec<Vec<E>>, offsets: Vec<usize>) -> Self {
+        validate_frame_offsets(&offsets);
+        assert_eq!(
+            rows.len(),
+            offsets.len(),
+            "number of rows must be the same as number of offsets"
+        );
+        assert!(!rows[0].is_empty(), "a row must contain at least one value");
+        for row in rows.iter() {
+            assert_eq!(rows[0].len(), row.len(), "number of values in the rows must be the same");
+        }
+        Self { rows, offsets }
     }
 
     // ROW ACCESSORS
@@ -55,13 +104,75 @@ impl<E: FieldElement> EvaluationFrame<E> {
     /// Returns a reference to the current row.
     #[inline(always)]
     pub fn current(&self) -> &[E] {
-        &self.current
+        &self.rows[0]
     }
 
     /// Returns a reference to the next row.
     #[inline(always)]
     pub fn next(&self) -> &[E] {
-        &self.next
+        &self.rows[1]
+    }
+
+    /// Returns a reference to the row at the specified position in this frame.
+    ///
+    /// The row is located `offsets()[idx]` steps after the current row in the execution trace.
+    ///
+    /// # Panics
+    /// Panics if `idx` is greater than or equal to the number of rows in this frame.
+    #[inline(always)]
+    pub fn row(&self, idx: usize) -> &[E] {
+        &self.rows[idx]
+    }
+
+    /// Returns a reference to the current row as an array of `N` values.
+    ///
+    /// This is intended for AIRs with trace width known at compile time: accessing columns of
+    /// a fixed-size array does not require bounds checks.
+    ///
+    /// # Panics
+    /// Panics if the number of columns in this frame is not `N`.
+    #[inline(always)]
+    pub fn current_array<const N: usize>(&self) -> &[E; N] {
+        self.row_array(0)
+    }
+
+    /// Returns a reference to the next row as an array of `N` values.
+    ///
+    /// # Panics
+    /// Panics if the number of columns in this frame is not `N`.
+    #[inline(always)]
+    pub fn next_array<const N: usize>(&self) -> &[E; N] {
+        self.row_array(1)
+    }
+
+    /// Returns a reference to the row at the specified position in this frame as an array of
+    /// `N` values.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `idx` is greater than or equal to the number of rows in this frame.
+    /// * The number of columns in this frame is not `N`.
+    #[inline(always)]
+    pub fn row_array<const N: usize>(&self, idx: usize) -> &[E; N] {
+        let row = self.rows[idx].as_slice();
+        row.try_into().unwrap_or_else(|_| {
+            panic!("expected a frame with {N} columns, but the frame has {} columns", row.len())
+        })
+    }
+
+    /// Returns the number of columns in this frame.
+    pub fn num_columns(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    /// Returns the number of rows in this frame.
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the offsets of rows in this frame relative to the current row.
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
     }
 
     // DATA MUTATORS
@@ -70,12 +181,36 @@ impl<E: FieldElement> EvaluationFrame<E> {
     /// Returns a mutable reference to the current row.
     #[inline(always)]
     pub fn current_mut(&mut self) -> &mut [E] {
-        &mut self.current
+        &mut self.rows[0]
     }
 
     /// Returns a mutable reference to the next row.
     #[inline(always)]
     pub fn next_mut(&mut self) -> &mut [E] {
-        &mut self.next
+        &mut self.rows[1]
+    }
+
+    /// Returns a mutable reference to the row at the specified position in this frame.
+    ///
+    /// # Panics
+    /// Panics if `idx` is greater than or equal to the number of rows in this frame.
+    #[inline(always)]
+    pub fn row_mut(&mut self, idx: usize) -> &mut [E] {
+        &mut self.rows[idx]
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure the provided frame offsets start with `[0, 1]` and are strictly increasing.
+pub(crate) fn validate_frame_offsets(offsets: &[usize]) {
+    assert!(
+        offsets.starts_with(&DEFAULT_OFFSETS),
+        "frame offsets must start with [0, 1], but were {offsets:?}"
+    );
+    assert!(
+        offsets.windows(2).all(|w| w[0] < w[1]),
+        "frame offsets must be strictly increasing, but were {offsets:?}"
+    );
+}
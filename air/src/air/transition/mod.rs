@@ -5,14 +5,20 @@
 
 use alloc::vec::Vec;
 
+use math::batch_inversion;
+
 use super::{AirContext, ConstraintDivisor, ExtensionOf, FieldElement};
 
 mod frame;
+pub(crate) use frame::validate_frame_offsets;
 pub use frame::EvaluationFrame;
 
 mod degree;
 pub use degree::TransitionConstraintDegree;
 
+mod expr;
+pub use expr::Expr;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -28,13 +34,17 @@ const MIN_CYCLE_LENGTH: usize = 2;
 ///   trace segments (if any).
 /// - Groupings of random composition constraint coefficients separately for the main trace segment
 ///   and for auxiliary tace segment.
-/// - Divisor of transition constraints for a computation.
+/// - Divisors of transition constraints for a computation. The first divisor is the default
+///   divisor; any custom divisors specified via [AirContext::with_transition_divisor()] follow
+///   it. For each constraint, the index of its divisor in this list is recorded.
 pub struct TransitionConstraints<E: FieldElement> {
     main_constraint_coef: Vec<E>,
     main_constraint_degrees: Vec<TransitionConstraintDegree>,
+    main_constraint_divisors: Vec<usize>,
     aux_constraint_coef: Vec<E>,
     aux_constraint_degrees: Vec<TransitionConstraintDegree>,
-    divisor: ConstraintDivisor<E::BaseField>,
+    aux_constraint_divisors: Vec<usize>,
+    divisors: Vec<ConstraintDivisor<E::BaseField>>,
 }
 
 impl<E: FieldElement> TransitionConstraints<E> {
@@ -53,23 +63,43 @@ impl<E: FieldElement> TransitionConstraints<E> {
             "number of transition constraints must match the number of composition coefficient tuples"
         );
 
-        // build constraint divisor; the same divisor applies to all transition constraints
-        let divisor = ConstraintDivisor::from_transition(
+        // build the default constraint divisor; unless a custom divisor was specified for a
+        // constraint, this divisor applies to the constraint
+        let mut divisors = vec![ConstraintDivisor::from_transition(
             context.trace_len(),
             context.num_transition_exemptions(),
-        );
+        )];
+
+        // assign a divisor index to each constraint; identical custom divisors are de-duplicated
+        // so that each distinct divisor is evaluated only once
+        let constraint_divisors = (0..context.num_transition_constraints())
+            .map(|i| match context.custom_transition_divisors().get(&i) {
+                Some(divisor) => match divisors.iter().position(|d| d == divisor) {
+                    Some(idx) => idx,
+                    None => {
+                        divisors.push(divisor.clone());
+                        divisors.len() - 1
+                    },
+                },
+                None => 0,
+            })
+            .collect::<Vec<_>>();
 
         let main_constraint_degrees = context.main_transition_constraint_degrees.clone();
         let aux_constraint_degrees = context.aux_transition_constraint_degrees.clone();
+        let (main_constraint_divisors, aux_constraint_divisors) =
+            constraint_divisors.split_at(main_constraint_degrees.len());
 
         let (main_constraint_coef, aux_constraint_coef) =
             composition_coefficients.split_at(context.main_transition_constraint_degrees.len());
         Self {
             main_constraint_coef: main_constraint_coef.to_vec(),
             main_constraint_degrees,
+            main_constraint_divisors: main_constraint_divisors.to_vec(),
             aux_constraint_coef: aux_constraint_coef.to_vec(),
             aux_constraint_degrees,
-            divisor,
+            aux_constraint_divisors: aux_constraint_divisors.to_vec(),
+            divisors,
         }
     }
 
@@ -118,9 +148,10 @@ impl<E: FieldElement> TransitionConstraints<E> {
         self.aux_constraint_coef.clone()
     }
 
-    /// Returns a divisor for transition constraints.
+    /// Returns the default divisor for transition constraints.
     ///
-    /// All transition constraints have the same divisor which has the form:
+    /// Unless a custom divisor was specified for a constraint, the constraint has the divisor of
+    /// the form:
     /// $$
     /// z(x) = \frac{x^n - 1}{x - g^{n - 1}}
     /// $$
@@ -130,7 +161,32 @@ impl<E: FieldElement> TransitionConstraints<E> {
     /// This divisor specifies that transition constraints must hold on all steps of the
     /// execution trace except for the last one.
     pub fn divisor(&self) -> &ConstraintDivisor<E::BaseField> {
-        &self.divisor
+        &self.divisors[0]
+    }
+
+    /// Returns a list of all distinct divisors of transition constraints.
+    ///
+    /// The first divisor in the list is always the default divisor (see
+    /// [divisor()](TransitionConstraints::divisor)).
+    pub fn divisors(&self) -> &[ConstraintDivisor<E::BaseField>] {
+        &self.divisors
+    }
+
+    /// Returns the number of distinct divisors of transition constraints.
+    pub fn num_divisors(&self) -> usize {
+        self.divisors.len()
+    }
+
+    /// Returns indexes into the [divisors()](TransitionConstraints::divisors) list for
+    /// constraints applied against the main trace segment of a computation.
+    pub fn main_constraint_divisors(&self) -> &[usize] {
+        &self.main_constraint_divisors
+    }
+
+    /// Returns indexes into the [divisors()](TransitionConstraints::divisors) list for
+    /// constraints applied against the auxiliary trace segment of a computation.
+    pub fn aux_constraint_divisors(&self) -> &[usize] {
+        &self.aux_constraint_divisors
     }
 
     // CONSTRAINT COMPOSITION
@@ -146,30 +202,43 @@ impl<E: FieldElement> TransitionConstraints<E> {
     ///
     /// Thus, this function computes a linear combination of $C(x)$ evaluations.
     ///
-    /// Since, the divisor polynomial is the same for all transition constraints (see
-    /// [ConstraintDivisor::from_transition]), we can divide the linear combination by the
-    /// divisor rather than dividing each individual $C(x)$ evaluation. This requires executing only
-    /// one division at the end.
+    /// Constraints which share the same divisor are combined first, and then each combination is
+    /// divided by the evaluation of the shared divisor. Thus, when all constraints use the default
+    /// divisor (see [ConstraintDivisor::from_transition]), this requires executing only one
+    /// division at the end.
     pub fn combine_evaluations<F>(&self, main_evaluations: &[F], aux_evaluations: &[E], x: F) -> E
     where
         F: FieldElement<BaseField = E::BaseField>,
         E: ExtensionOf<F>,
     {
+        let mut numerators = vec![E::ZERO; self.divisors.len()];
+
         // merge constraint evaluations for the main trace segment
-        let mut result = main_evaluations
+        for ((&const_eval, &coef), &divisor_idx) in main_evaluations
             .iter()
             .zip(self.main_constraint_coef.iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef.mul_base(const_eval));
-
-        if !self.aux_constraint_coef.is_empty() {
-            result += aux_evaluations
-                .iter()
-                .zip(self.aux_constraint_coef.iter())
-                .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef * const_eval);
-        };
-        // divide out the evaluation of divisor at x and return the result
-        let z = E::from(self.divisor.evaluate_at(x));
-
-        result / z
+            .zip(self.main_constraint_divisors.iter())
+        {
+            numerators[divisor_idx] += coef.mul_base(const_eval);
+        }
+
+        // merge constraint evaluations for the auxiliary trace segment
+        for ((&const_eval, &coef), &divisor_idx) in aux_evaluations
+            .iter()
+            .zip(self.aux_constraint_coef.iter())
+            .zip(self.aux_constraint_divisors.iter())
+        {
+            numerators[divisor_idx] += coef * const_eval;
+        }
+
+        // divide out the evaluations of divisors at x and return the sum of the results
+        if self.divisors.len() == 1 {
+            return numerators[0] / E::from(self.divisors[0].evaluate_at(x));
+        }
+        let denominators = self.divisors.iter().map(|d| d.evaluate_at(x)).collect::<Vec<_>>();
+        numerators
+            .into_iter()
+            .zip(batch_inversion(&denominators))
+            .fold(E::ZERO, |acc, (numerator, z_inv)| acc + numerator.mul_base(z_inv))
     }
 }
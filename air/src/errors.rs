@@ -42,3 +42,37 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+// SECURITY TARGET ERROR
+// ================================================================================================
+/// Represents an error returned when proof options for a target security level cannot be
+/// selected via [ProofOptions::with_security_target()](crate::ProofOptions::with_security_target).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityTargetError {
+    /// This error occurs when the maximum constraint degree is zero, or when evaluating
+    /// constraints of this degree requires a blowup factor greater than 128.
+    UnsupportedConstraintDegree(usize),
+    /// This error occurs when the target security level cannot be achieved by any supported
+    /// combination of proof options (e.g., because it exceeds the collision resistance of the
+    /// hash function).
+    UnachievableSecurityLevel(u32),
+    /// This error occurs when all proof options which achieve the target security level result
+    /// in proofs larger than the specified maximum; the smallest estimated proof size is included.
+    ProofSizeExceeded(usize),
+}
+
+impl fmt::Display for SecurityTargetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedConstraintDegree(degree) => {
+                write!(f, "constraint degree {degree} is not supported")
+            },
+            Self::UnachievableSecurityLevel(bits) => {
+                write!(f, "security level of {bits} bits cannot be achieved")
+            },
+            Self::ProofSizeExceeded(size) => {
+                write!(f, "smallest proof achieving the security level would take {size} bytes")
+            },
+        }
+    }
+}
@@ -5,10 +5,15 @@
 
 use alloc::vec::Vec;
 
+use crypto::Hasher;
 use fri::FriOptions;
 use math::{StarkField, ToElements};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
+use crate::{proof::Context, SecurityTargetError, TraceInfo};
+
 // CONSTANTS
 // ================================================================================================
 
@@ -22,9 +27,20 @@ const MAX_BLOWUP_FACTOR: usize = 128;
 const MAX_GRINDING_FACTOR: u32 = 32;
 
 const FRI_MIN_FOLDING_FACTOR: usize = 2;
-const FRI_MAX_FOLDING_FACTOR: usize = 16;
+const FRI_MAX_FOLDING_FACTOR: usize = 64;
 const FRI_MAX_REMAINDER_DEGREE: usize = 255;
 
+// flags encoding protocol modes of proof options in serialized form.
+const ZK_FLAG: u8 = 0b001;
+const PROVEN_SECURITY_FLAG: u8 = 0b010;
+const OOD_FIELD_EXTENSION_FLAG: u8 = 0b100;
+
+// FRI parameters and candidate grinding factors used when selecting options for a target security
+// level.
+const SECURITY_TARGET_FRI_FOLDING_FACTOR: usize = 8;
+const SECURITY_TARGET_FRI_REMAINDER_MAX_DEGREE: usize = 31;
+const SECURITY_TARGET_GRINDING_FACTORS: [u32; 5] = [0, 8, 16, 20, 24];
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -43,6 +59,7 @@ const FRI_MAX_REMAINDER_DEGREE: usize = 255;
 /// as much as 50%.
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum FieldExtension {
     /// Composition polynomial is constructed in the base field.
     None = 1,
@@ -52,6 +69,35 @@ pub enum FieldExtension {
     Cubic = 3,
 }
 
+/// Defines how much memory the prover may use for the low-degree extensions of trace segments.
+///
+/// The budget does not affect the generated proof; it is used only by provers which can trade
+/// proof generation time for memory (e.g., by extending trace segments in chunks of columns and
+/// spilling the extended chunks to disk). Such provers are expected to keep the extended trace
+/// data they hold in memory at any one time within the budget. Provers which keep the entire
+/// trace LDE in memory ignore the budget.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum MemoryBudget {
+    /// The prover may keep entire trace segment LDEs in memory.
+    #[default]
+    Unlimited,
+    /// The prover should use at most the specified number of bytes for the trace segment LDE
+    /// data it holds in memory.
+    Bytes(usize),
+}
+
+/// Defines which aspect of the protocol [ProofOptions::with_security_target()] should optimize for
+/// when selecting proof options.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SpeedPreference {
+    /// Minimize proof generation time; proofs may be larger.
+    FastProving,
+    /// Balance proof generation time against proof size.
+    Balanced,
+    /// Minimize proof size; proof generation may be slower.
+    SmallProofs,
+}
+
 /// STARK protocol parameters.
 ///
 /// These parameters have a direct impact on proof soundness, proof generation time, and proof
@@ -70,11 +116,38 @@ pub enum FieldExtension {
 ///    generation time. More precisely, conjectured proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
 ///
+/// Proofs can also be made zero-knowledge via [ProofOptions::with_zk()]. In this mode, the
+/// prover blinds the last [num_blinding_rows()](ProofOptions::num_blinding_rows) rows of every
+/// trace segment with random values, masks the constraint composition polynomial with a random
+/// polynomial, and salts FRI layer commitments. This makes proof generation somewhat slower and
+/// proofs somewhat larger.
+///
+/// Security of proofs is estimated using conjectured soundness bounds by default. Via
+/// [ProofOptions::with_proven_security()], options can instead declare that they were selected
+/// using provable soundness bounds (i.e., FRI soundness in the list-decoding regime), which
+/// require substantially more queries for the same security level. This declaration is a part of
+/// the proof: it is serialized and absorbed into the protocol transcript, and verifiers can
+/// require it (see [Proof::declared_security_level()](crate::proof::Proof::declared_security_level)).
+///
+/// By default, the out-of-domain point and the coefficients of the DEEP composition polynomial
+/// are drawn from the same field as the one in which the composition polynomial is built (see
+/// [FieldExtension]). When the composition polynomial is built in the base field, these values
+/// can be drawn from an extension of the base field instead via
+/// [ProofOptions::with_ood_field_extension()]. In this case, constraints are evaluated and
+/// committed to in the base field, while the DEEP composition polynomial and FRI layers are built
+/// in the extension field. This is intended for small base fields, for which security of
+/// out-of-domain sampling over the base field itself is inadequate.
+///
+/// Memory used by the prover can be limited via [ProofOptions::with_memory_budget()]. The memory
+/// budget is not a part of the proof: it is neither serialized nor absorbed into the protocol
+/// transcript, and it is ignored when proof options are compared for equality.
+///
 /// Another important parameter in defining STARK security level, which is not a part of [ProofOptions]
 /// is the hash function used in the protocol. The soundness of a STARK proof is limited by the
 /// collision resistance of the hash function used by the protocol. For example, if a hash function
 /// with 128-bit collision resistance is used, soundness of a STARK proof cannot exceed 128 bits.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ProofOptions {
     num_queries: u8,
     blowup_factor: u8,
@@ -82,6 +155,13 @@ pub struct ProofOptions {
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_remainder_max_degree: u8,
+    is_zk: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    proven_security: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    ood_field_extension: Option<FieldExtension>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    memory_budget: MemoryBudget,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -106,7 +186,7 @@ impl ProofOptions {
     /// - `num_queries` is zero or greater than 255.
     /// - `blowup_factor` is smaller than 2, greater than 128, or is not a power of two.
     /// - `grinding_factor` is greater than 32.
-    /// - `fri_folding_factor` is not 2, 4, 8, or 16.
+    /// - `fri_folding_factor` is not 2, 4, 8, 16, 32, or 64.
     /// - `fri_remainder_max_degree` is greater than 255 or is not a power of two minus 1.
     #[rustfmt::skip]
     pub const fn new(
@@ -129,7 +209,7 @@ impl ProofOptions {
 
         assert!(fri_folding_factor.is_power_of_two(), "FRI folding factor must be a power of 2");
         assert!(fri_folding_factor >= FRI_MIN_FOLDING_FACTOR, "FRI folding factor cannot be smaller than 2");
-        assert!(fri_folding_factor <= FRI_MAX_FOLDING_FACTOR, "FRI folding factor cannot be greater than 16");
+        assert!(fri_folding_factor <= FRI_MAX_FOLDING_FACTOR, "FRI folding factor cannot be greater than 64");
 
         assert!(
             (fri_remainder_max_degree + 1).is_power_of_two(),
@@ -147,9 +227,66 @@ impl ProofOptions {
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
             fri_remainder_max_degree: fri_remainder_max_degree as u8,
+            is_zk: false,
+            proven_security: false,
+            ood_field_extension: None,
+            memory_budget: MemoryBudget::Unlimited,
         }
     }
 
+    /// Returns these options updated to generate zero-knowledge proofs.
+    ///
+    /// In zero-knowledge mode, the last [num_blinding_rows()](ProofOptions::num_blinding_rows)
+    /// rows of every trace segment are overwritten with random values by the prover. Thus, AIRs
+    /// used with these options must not place assertions against these rows, and must exempt
+    /// them from transition constraints (i.e., the number of transition exemptions must be
+    /// greater than the number of blinding rows).
+    pub fn with_zk(mut self) -> Self {
+        self.is_zk = true;
+        self
+    }
+
+    /// Returns these options updated to declare that their security is accounted for using
+    /// provable, rather than conjectured, soundness bounds.
+    ///
+    /// The declaration does not change how proofs are generated or verified, but it is bound to
+    /// the proof: verifiers evaluate the security level of such proofs using provable bounds, and
+    /// may reject proofs which do not make this declaration.
+    pub fn with_proven_security(mut self) -> Self {
+        self.proven_security = true;
+        self
+    }
+
+    /// Returns these options updated to draw the out-of-domain point and the coefficients of the
+    /// DEEP composition polynomial from the specified field extension.
+    ///
+    /// The DEEP composition polynomial and the FRI layers are then built in this extension as
+    /// well, while constraints are evaluated and committed to in the field specified by
+    /// [field_extension()](ProofOptions::field_extension).
+    ///
+    /// # Panics
+    /// Panics if `field_extension` is different from the field extension of these options, and
+    /// the composition polynomial of these options is not built in the base field.
+    pub fn with_ood_field_extension(mut self, field_extension: FieldExtension) -> Self {
+        assert!(
+            is_valid_ood_field_extension(self.field_extension, field_extension),
+            "out-of-domain field extension must be the same as the composition field extension \
+            unless the composition polynomial is built in the base field"
+        );
+        self.ood_field_extension =
+            (field_extension != self.field_extension).then_some(field_extension);
+        self
+    }
+
+    /// Returns these options updated with the specified memory budget for the prover.
+    ///
+    /// The budget affects only how the proof is generated, and thus, proofs generated with
+    /// different memory budgets are identical.
+    pub fn with_memory_budget(mut self, budget: MemoryBudget) -> Self {
+        self.memory_budget = budget;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -192,6 +329,49 @@ impl ProofOptions {
         self.field_extension
     }
 
+    /// Returns the field extension from which the out-of-domain point and the coefficients of the
+    /// DEEP composition polynomial are drawn.
+    ///
+    /// Unless set via [with_ood_field_extension()](ProofOptions::with_ood_field_extension), this
+    /// is the same as [field_extension()](ProofOptions::field_extension).
+    pub const fn ood_field_extension(&self) -> FieldExtension {
+        match self.ood_field_extension {
+            Some(field_extension) => field_extension,
+            None => self.field_extension,
+        }
+    }
+
+    /// Returns true if these options specify generation of zero-knowledge proofs.
+    pub const fn is_zk(&self) -> bool {
+        self.is_zk
+    }
+
+    /// Returns true if the security of these options is accounted for using provable soundness
+    /// bounds, and false if conjectured soundness bounds are used.
+    pub const fn uses_proven_security(&self) -> bool {
+        self.proven_security
+    }
+
+    /// Returns the memory budget for trace segment LDEs which the prover should stay within.
+    pub const fn memory_budget(&self) -> MemoryBudget {
+        self.memory_budget
+    }
+
+    /// Returns the number of rows at the end of each trace segment which are filled with random
+    /// values when generating zero-knowledge proofs.
+    ///
+    /// Each trace polynomial is opened at `num_queries` points of the LDE domain and at the two
+    /// points of the out-of-domain evaluation frame; thus, this many random rows are sufficient
+    /// to make the opened values independent of the witness. When zero-knowledge is not enabled,
+    /// this returns zero.
+    pub const fn num_blinding_rows(&self) -> usize {
+        if self.is_zk {
+            self.num_queries() + 2
+        } else {
+            0
+        }
+    }
+
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
@@ -201,17 +381,67 @@ impl ProofOptions {
     }
 
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
+    ///
+    /// Grinding factor of the returned FRI options is always zero: query seed grinding for
+    /// STARK proofs is performed by the STARK prover after the FRI commit phase and is
+    /// controlled by the [grinding_factor()](ProofOptions::grinding_factor()) of these options.
+    ///
+    /// If these options specify zero-knowledge proofs, FRI layer commitments are salted.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let remainder_max_degree = self.fri_remainder_max_degree as usize;
-        FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+        let options = FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree);
+        if self.is_zk {
+            options.with_salted_commitments()
+        } else {
+            options
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the protocol mode flags of these options packed into a byte.
+    fn flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.is_zk {
+            flags |= ZK_FLAG;
+        }
+        if self.proven_security {
+            flags |= PROVEN_SECURITY_FLAG;
+        }
+        if self.ood_field_extension.is_some() {
+            flags |= OOD_FIELD_EXTENSION_FLAG;
+        }
+        flags
     }
 }
 
+impl PartialEq for ProofOptions {
+    /// Returns true if both options define the same protocol parameters; memory budgets of the
+    /// options are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.num_queries == other.num_queries
+            && self.blowup_factor == other.blowup_factor
+            && self.grinding_factor == other.grinding_factor
+            && self.field_extension == other.field_extension
+            && self.fri_folding_factor == other.fri_folding_factor
+            && self.fri_remainder_max_degree == other.fri_remainder_max_degree
+            && self.is_zk == other.is_zk
+            && self.proven_security == other.proven_security
+            && self.ood_field_extension == other.ood_field_extension
+    }
+}
+
+impl Eq for ProofOptions {}
+
 impl<E: StarkField> ToElements<E> for ProofOptions {
     fn to_elements(&self) -> Vec<E> {
-        // encode field extension and FRI parameters into a single field element
-        let mut buf = self.field_extension as u32;
+        // encode flags, out-of-domain field extension (if different from the composition field
+        // extension), field extension, and FRI parameters into a single field element
+        let ood_field_extension = self.ood_field_extension.map_or(0, |ext| ext as u32);
+        let mut buf = (ood_field_extension << 4) | self.flags() as u32;
+        buf = (buf << 8) | self.field_extension as u32;
         buf = (buf << 8) | self.fri_folding_factor as u32;
         buf = (buf << 8) | self.fri_remainder_max_degree as u32;
 
@@ -233,6 +463,10 @@ impl Serializable for ProofOptions {
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_remainder_max_degree);
+        target.write_u8(self.flags());
+        if let Some(ood_field_extension) = self.ood_field_extension {
+            target.write(ood_field_extension);
+        }
     }
 }
 
@@ -242,14 +476,138 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
+        let options = ProofOptions::new(
             source.read_u8()? as usize,
             source.read_u8()? as usize,
             source.read_u8()? as u32,
             FieldExtension::read_from(source)?,
             source.read_u8()? as usize,
             source.read_u8()? as usize,
-        ))
+        );
+        let flags = source.read_u8()?;
+        if flags & !(ZK_FLAG | PROVEN_SECURITY_FLAG | OOD_FIELD_EXTENSION_FLAG) != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid proof options flags {flags:#010b}"
+            )));
+        }
+
+        let options = if flags & ZK_FLAG != 0 {
+            options.with_zk()
+        } else {
+            options
+        };
+        let options = if flags & PROVEN_SECURITY_FLAG != 0 {
+            options.with_proven_security()
+        } else {
+            options
+        };
+        if flags & OOD_FIELD_EXTENSION_FLAG == 0 {
+            return Ok(options);
+        }
+
+        let ood_field_extension = FieldExtension::read_from(source)?;
+        if ood_field_extension == options.field_extension
+            || !is_valid_ood_field_extension(options.field_extension, ood_field_extension)
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "out-of-domain field extension {ood_field_extension:?} is not valid for \
+                composition field extension {:?}",
+                options.field_extension
+            )));
+        }
+        Ok(options.with_ood_field_extension(ood_field_extension))
+    }
+}
+
+// SECURITY TARGET SELECTION
+// ================================================================================================
+
+impl ProofOptions {
+    /// Returns proof options which achieve at least `bits` of conjectured security for proving
+    /// computations with the specified trace shape over the base field `B` using hash function
+    /// `H`.
+    ///
+    /// The blowup factor, number of queries, grinding factor, and field extension are selected
+    /// automatically:
+    /// - The blowup factor is at least the one required to evaluate constraints of
+    ///   `max_constraint_degree`.
+    /// - For each candidate combination of the blowup factor, grinding factor, and field
+    ///   extension, the smallest number of queries which achieves the target security level is
+    ///   used; security levels are computed in the same way as
+    ///   [Proof::security_level()](crate::proof::Proof::security_level).
+    /// - Among the candidates whose estimated proof size does not exceed `max_proof_size` (if
+    ///   specified), the one best matching `speed_preference` is returned. Proof generation time
+    ///   is estimated from the size of the trace LDE and the number of hashes needed for
+    ///   grinding; proof size is estimated without accounting for Merkle authentication path
+    ///   nodes shared between queries, and thus, is an upper bound on the actual size (up to a
+    ///   few bytes of metadata).
+    ///
+    /// FRI layers of the returned options are folded by a factor of 8 down to a remainder of
+    /// degree 31.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `max_constraint_degree` is zero or requires a blowup factor greater than 128.
+    /// * The target security level cannot be achieved with any supported proof options.
+    /// * All options achieving the target security level result in proofs larger than
+    ///   `max_proof_size`.
+    pub fn with_security_target<B: StarkField, H: Hasher>(
+        bits: u32,
+        max_proof_size: Option<usize>,
+        speed_preference: SpeedPreference,
+        trace_info: &TraceInfo,
+        max_constraint_degree: usize,
+    ) -> Result<Self, SecurityTargetError> {
+        select_options::<B, H>(
+            bits,
+            false,
+            max_proof_size,
+            speed_preference,
+            trace_info,
+            max_constraint_degree,
+        )
+    }
+
+    /// Returns proof options which achieve at least `bits` of proven security for proving
+    /// computations with the specified trace shape over the base field `B` using hash function
+    /// `H`.
+    ///
+    /// This is the same as [with_security_target()](ProofOptions::with_security_target), except
+    /// that security levels are computed using provable soundness bounds, and the returned
+    /// options declare this via [with_proven_security()](ProofOptions::with_proven_security).
+    ///
+    /// # Errors
+    /// Returns an error in the same cases as
+    /// [with_security_target()](ProofOptions::with_security_target).
+    pub fn with_proven_security_target<B: StarkField, H: Hasher>(
+        bits: u32,
+        max_proof_size: Option<usize>,
+        speed_preference: SpeedPreference,
+        trace_info: &TraceInfo,
+        max_constraint_degree: usize,
+    ) -> Result<Self, SecurityTargetError> {
+        select_options::<B, H>(
+            bits,
+            true,
+            max_proof_size,
+            speed_preference,
+            trace_info,
+            max_constraint_degree,
+        )
+    }
+}
+
+// MEMORY BUDGET IMPLEMENTATION
+// ================================================================================================
+
+impl MemoryBudget {
+    /// Returns the maximum number of bytes specified by this budget, or `None` if the budget is
+    /// unlimited.
+    pub const fn max_bytes(&self) -> Option<usize> {
+        match self {
+            Self::Unlimited => None,
+            Self::Bytes(num_bytes) => Some(*num_bytes),
+        }
     }
 }
 
@@ -301,14 +659,209 @@ impl Deserializable for FieldExtension {
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the out-of-domain point can be drawn from `ood_field_extension` when the
+/// composition polynomial is built in `field_extension`.
+///
+/// The out-of-domain field must be an extension of the composition field; for the supported
+/// extensions, this is the case only if the fields are the same, or if the composition field is
+/// the base field.
+fn is_valid_ood_field_extension(
+    field_extension: FieldExtension,
+    ood_field_extension: FieldExtension,
+) -> bool {
+    field_extension == ood_field_extension || field_extension.is_none()
+}
+
+/// Returns proof options which achieve at least `bits` of proven (if `proven_security` is true) or
+/// conjectured security; see [ProofOptions::with_security_target()] for details.
+fn select_options<B: StarkField, H: Hasher>(
+    bits: u32,
+    proven_security: bool,
+    max_proof_size: Option<usize>,
+    speed_preference: SpeedPreference,
+    trace_info: &TraceInfo,
+    max_constraint_degree: usize,
+) -> Result<ProofOptions, SecurityTargetError> {
+    if max_constraint_degree == 0 {
+        return Err(SecurityTargetError::UnsupportedConstraintDegree(max_constraint_degree));
+    }
+    let min_blowup_factor = (max_constraint_degree - 1).next_power_of_two().max(MIN_BLOWUP_FACTOR);
+    if min_blowup_factor > MAX_BLOWUP_FACTOR {
+        return Err(SecurityTargetError::UnsupportedConstraintDegree(max_constraint_degree));
+    }
+    let num_composition_columns = min_blowup_factor;
+
+    let mut best: Option<((u128, u128), ProofOptions)> = None;
+    let mut smallest_size = None;
+    for field_extension in [FieldExtension::None, FieldExtension::Quadratic, FieldExtension::Cubic]
+    {
+        let mut blowup_factor = min_blowup_factor;
+        while blowup_factor <= MAX_BLOWUP_FACTOR {
+            for grinding_factor in SECURITY_TARGET_GRINDING_FACTORS {
+                let options = match find_min_num_queries::<B, H>(
+                    bits,
+                    trace_info,
+                    blowup_factor,
+                    grinding_factor,
+                    field_extension,
+                    proven_security,
+                ) {
+                    Some(options) => options,
+                    None => continue,
+                };
+
+                let size =
+                    estimate_proof_size::<B, H>(&options, trace_info, num_composition_columns);
+                smallest_size = Some(smallest_size.map_or(size, |s: usize| s.min(size)));
+                if max_proof_size.is_some_and(|max_size| size > max_size) {
+                    continue;
+                }
+
+                let cost = estimate_proving_cost(&options, trace_info, num_composition_columns);
+                let size = size as u128;
+                let score = match speed_preference {
+                    SpeedPreference::FastProving => (cost, size),
+                    SpeedPreference::Balanced => (cost * size, size),
+                    SpeedPreference::SmallProofs => (size, cost),
+                };
+                if best.as_ref().map_or(true, |(best_score, _)| score < *best_score) {
+                    best = Some((score, options));
+                }
+            }
+            blowup_factor *= 2;
+        }
+    }
+
+    match (best, smallest_size) {
+        (Some((_, options)), _) => Ok(options),
+        (None, Some(size)) => Err(SecurityTargetError::ProofSizeExceeded(size)),
+        (None, None) => Err(SecurityTargetError::UnachievableSecurityLevel(bits)),
+    }
+}
+
+/// Returns options with the smallest number of queries which achieve `bits` of proven (if
+/// `proven_security` is true) or conjectured security for the specified parameters, or None if no
+/// number of queries achieves it.
+fn find_min_num_queries<B: StarkField, H: Hasher>(
+    bits: u32,
+    trace_info: &TraceInfo,
+    blowup_factor: usize,
+    grinding_factor: u32,
+    field_extension: FieldExtension,
+    proven_security: bool,
+) -> Option<ProofOptions> {
+    let build_options = |num_queries| {
+        let options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            SECURITY_TARGET_FRI_FOLDING_FACTOR,
+            SECURITY_TARGET_FRI_REMAINDER_MAX_DEGREE,
+        );
+        if proven_security {
+            options.with_proven_security()
+        } else {
+            options
+        }
+    };
+    let security_level = |options: &ProofOptions| {
+        // the AIR digest does not affect the security level
+        Context::new::<B>(trace_info.clone(), options.clone(), [0; 32])
+            .declared_security_level::<H>()
+    };
+
+    // security grows with the number of queries; if even the maximum number of queries is not
+    // enough, the target is limited by the field or the hash function
+    if security_level(&build_options(MAX_NUM_QUERIES)) < bits {
+        return None;
+    }
+
+    // start from the number of queries implied by the query security bound and search upwards;
+    // the bound may be too optimistic since grinding counts only above a certain security level
+    let bits_per_query = blowup_factor.ilog2();
+    let min_num_queries = (bits + 1).saturating_sub(grinding_factor).div_ceil(bits_per_query);
+    (min_num_queries.max(1) as usize..=MAX_NUM_QUERIES)
+        .map(build_options)
+        .find(|options| security_level(options) >= bits)
+}
+
+/// Returns an estimate of the size (in bytes) of a proof generated with the specified options.
+///
+/// Merkle authentication paths are counted as if no nodes were shared between queries.
+fn estimate_proof_size<B: StarkField, H: Hasher>(
+    options: &ProofOptions,
+    trace_info: &TraceInfo,
+    num_composition_columns: usize,
+) -> usize {
+    let element_bytes = B::ELEMENT_BYTES;
+    let extension_bytes = element_bytes * options.field_extension().degree() as usize;
+    let digest_bytes = H::Digest::default().to_bytes().len();
+    let lde_domain_size = trace_info.length() * options.blowup_factor();
+    let lde_depth = lde_domain_size.ilog2() as usize;
+
+    // values and authentication paths of trace and constraint queries
+    let mut query_bytes = trace_info.main_trace_width() * element_bytes
+        + trace_info.aux_segment_width() * extension_bytes
+        + num_composition_columns * extension_bytes
+        + (trace_info.num_segments() + 1) * lde_depth * digest_bytes;
+
+    // values and authentication paths of FRI layer queries
+    let fri_options = options.to_fri_options();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+    let mut domain_size = lde_domain_size;
+    for layer_idx in 0..num_fri_layers {
+        let folding_factor = fri_options.folding_factor_at(layer_idx);
+        domain_size /= folding_factor;
+        query_bytes +=
+            folding_factor * extension_bytes + domain_size.ilog2() as usize * digest_bytes;
+    }
+
+    // commitments, out-of-domain evaluations, and the FRI remainder
+    let num_commitments = trace_info.num_segments() + 1 + num_fri_layers;
+    let ood_elements = 2 * trace_info.width() + num_composition_columns;
+    let remainder_elements = options.fri_remainder_max_degree as usize + 1;
+    let fixed_bytes =
+        num_commitments * digest_bytes + (ood_elements + remainder_elements) * extension_bytes;
+
+    fixed_bytes + options.num_queries() * query_bytes
+}
+
+/// Returns an estimate of the work needed to generate a proof with the specified options, in
+/// units of field element evaluations and hashes.
+fn estimate_proving_cost(
+    options: &ProofOptions,
+    trace_info: &TraceInfo,
+    num_composition_columns: usize,
+) -> u128 {
+    let extension_degree = options.field_extension().degree() as usize;
+    let lde_domain_size = trace_info.length() * options.blowup_factor();
+    let num_lde_elements = trace_info.main_trace_width()
+        + (trace_info.aux_segment_width() + num_composition_columns) * extension_degree;
+
+    (lde_domain_size * num_lde_elements) as u128 + (1u128 << options.grinding_factor())
+}
+
 // TESTS
 // ================================================================================================
 
 #[cfg(test)]
 mod tests {
+    use crypto::hashers::Blake3_256;
     use math::fields::f64::BaseElement;
 
-    use super::{FieldExtension, ProofOptions, ToElements};
+    use utils::{Deserializable, Serializable};
+
+    use super::{
+        estimate_proof_size, FieldExtension, MemoryBudget, ProofOptions, SpeedPreference,
+        ToElements, OOD_FIELD_EXTENSION_FLAG,
+    };
+    use crate::{proof::Context, SecurityTargetError, TraceInfo};
+
+    type Hasher = Blake3_256<BaseElement>;
 
     #[test]
     fn proof_options_to_elements() {
@@ -342,4 +895,242 @@ mod tests {
         );
         assert_eq!(expected, options.to_elements());
     }
+
+    #[test]
+    fn proof_options_zk() {
+        let options = ProofOptions::new(30, 8, 0, FieldExtension::None, 8, 127);
+        assert!(!options.is_zk());
+        assert_eq!(0, options.num_blinding_rows());
+        assert!(!options.to_fri_options().salted_commitments());
+
+        let zk_options = options.clone().with_zk();
+        assert!(zk_options.is_zk());
+        assert_eq!(32, zk_options.num_blinding_rows());
+        assert!(zk_options.to_fri_options().salted_commitments());
+        assert_ne!(
+            ToElements::<BaseElement>::to_elements(&options),
+            ToElements::<BaseElement>::to_elements(&zk_options)
+        );
+
+        let bytes = zk_options.to_bytes();
+        assert_eq!(zk_options, ProofOptions::read_from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn proof_options_proven_security() {
+        let options = ProofOptions::new(30, 8, 0, FieldExtension::None, 8, 127);
+        assert!(!options.uses_proven_security());
+
+        let proven_options = options.clone().with_proven_security();
+        assert!(proven_options.uses_proven_security());
+        assert_ne!(options, proven_options);
+        assert_ne!(
+            ToElements::<BaseElement>::to_elements(&options),
+            ToElements::<BaseElement>::to_elements(&proven_options)
+        );
+
+        let zk_proven_options = proven_options.clone().with_zk();
+        for options in [options, proven_options, zk_proven_options] {
+            let bytes = options.to_bytes();
+            assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        }
+
+        // unknown flags are rejected
+        let mut bytes = ProofOptions::new(30, 8, 0, FieldExtension::None, 8, 127).to_bytes();
+        *bytes.last_mut().unwrap() = 0b1000;
+        assert!(ProofOptions::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn proof_options_ood_field_extension() {
+        let options = ProofOptions::new(30, 8, 0, FieldExtension::None, 8, 127);
+        assert_eq!(FieldExtension::None, options.ood_field_extension());
+
+        let ood_options = options.clone().with_ood_field_extension(FieldExtension::Quadratic);
+        assert_eq!(FieldExtension::None, ood_options.field_extension());
+        assert_eq!(FieldExtension::Quadratic, ood_options.ood_field_extension());
+        assert_ne!(options, ood_options);
+        assert_ne!(
+            ToElements::<BaseElement>::to_elements(&options),
+            ToElements::<BaseElement>::to_elements(&ood_options)
+        );
+
+        // setting the out-of-domain field extension to the composition field extension is a no-op
+        assert_eq!(options, options.clone().with_ood_field_extension(FieldExtension::None));
+
+        for options in [options, ood_options.clone(), ood_options.with_zk()] {
+            let bytes = options.to_bytes();
+            assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        }
+
+        // the out-of-domain field extension must differ from the composition field extension
+        let mut bytes = ProofOptions::new(30, 8, 0, FieldExtension::None, 8, 127).to_bytes();
+        *bytes.last_mut().unwrap() = OOD_FIELD_EXTENSION_FLAG;
+        bytes.push(FieldExtension::None as u8);
+        assert!(ProofOptions::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn proof_options_invalid_ood_field_extension() {
+        ProofOptions::new(30, 8, 0, FieldExtension::Quadratic, 8, 127)
+            .with_ood_field_extension(FieldExtension::Cubic);
+    }
+
+    #[test]
+    fn proof_options_memory_budget() {
+        let options = ProofOptions::new(30, 8, 0, FieldExtension::None, 8, 127);
+        assert_eq!(MemoryBudget::Unlimited, options.memory_budget());
+        assert_eq!(None, options.memory_budget().max_bytes());
+
+        let budget = MemoryBudget::Bytes(1 << 30);
+        let limited_options = options.clone().with_memory_budget(budget);
+        assert_eq!(budget, limited_options.memory_budget());
+        assert_eq!(Some(1 << 30), limited_options.memory_budget().max_bytes());
+
+        // memory budget does not affect the proof
+        assert_eq!(options, limited_options);
+        assert_eq!(
+            ToElements::<BaseElement>::to_elements(&options),
+            ToElements::<BaseElement>::to_elements(&limited_options)
+        );
+        assert_eq!(options.to_bytes(), limited_options.to_bytes());
+    }
+
+    #[test]
+    fn proof_options_security_target() {
+        let trace_info = TraceInfo::new(8, 1 << 16);
+        let security_level = |options: &ProofOptions| {
+            Context::new::<BaseElement>(trace_info.clone(), options.clone(), [0; 32])
+                .security_level::<Hasher>(true)
+        };
+
+        for bits in [80, 100, 120] {
+            for preference in [
+                SpeedPreference::FastProving,
+                SpeedPreference::Balanced,
+                SpeedPreference::SmallProofs,
+            ] {
+                let options = ProofOptions::with_security_target::<BaseElement, Hasher>(
+                    bits,
+                    None,
+                    preference,
+                    &trace_info,
+                    3,
+                )
+                .unwrap();
+                assert!(security_level(&options) >= bits);
+
+                // the number of queries is the smallest one achieving the target
+                let fri_options = options.to_fri_options();
+                let fewer_queries = ProofOptions::new(
+                    options.num_queries() - 1,
+                    options.blowup_factor(),
+                    options.grinding_factor(),
+                    options.field_extension(),
+                    fri_options.folding_factor(),
+                    fri_options.remainder_max_degree(),
+                );
+                assert!(security_level(&fewer_queries) < bits);
+            }
+        }
+
+        // the 64-bit field requires an extension for 100 bits of security
+        let options = ProofOptions::with_security_target::<BaseElement, Hasher>(
+            100,
+            None,
+            SpeedPreference::Balanced,
+            &trace_info,
+            3,
+        )
+        .unwrap();
+        assert_eq!(FieldExtension::Quadratic, options.field_extension());
+    }
+
+    #[test]
+    fn proof_options_proven_security_target() {
+        let trace_info = TraceInfo::new(8, 1 << 16);
+        let context = |options: &ProofOptions| {
+            Context::new::<BaseElement>(trace_info.clone(), options.clone(), [0; 32])
+        };
+
+        let conjectured = ProofOptions::with_security_target::<BaseElement, Hasher>(
+            96,
+            None,
+            SpeedPreference::Balanced,
+            &trace_info,
+            3,
+        )
+        .unwrap();
+        let proven = ProofOptions::with_proven_security_target::<BaseElement, Hasher>(
+            96,
+            None,
+            SpeedPreference::Balanced,
+            &trace_info,
+            3,
+        )
+        .unwrap();
+        assert!(!conjectured.uses_proven_security());
+        assert!(proven.uses_proven_security());
+        assert!(context(&conjectured).declared_security_level::<Hasher>() >= 96);
+        assert!(context(&proven).declared_security_level::<Hasher>() >= 96);
+        assert!(context(&proven).security_level::<Hasher>(false) >= 96);
+
+        // proven security requires stricter parameters than conjectured security
+        assert!(context(&conjectured).security_level::<Hasher>(false) < 96);
+        assert!(
+            proven.num_queries() * proven.blowup_factor().ilog2() as usize
+                > conjectured.num_queries() * conjectured.blowup_factor().ilog2() as usize
+        );
+    }
+
+    #[test]
+    fn proof_options_security_target_preferences() {
+        let trace_info = TraceInfo::new(8, 1 << 16);
+        let select = |preference, max_proof_size| {
+            ProofOptions::with_security_target::<BaseElement, Hasher>(
+                100,
+                max_proof_size,
+                preference,
+                &trace_info,
+                3,
+            )
+        };
+        let estimate_size = |options: &ProofOptions| {
+            estimate_proof_size::<BaseElement, Hasher>(options, &trace_info, 2)
+        };
+
+        // faster proving comes at the cost of larger proofs
+        let fast = select(SpeedPreference::FastProving, None).unwrap();
+        let small = select(SpeedPreference::SmallProofs, None).unwrap();
+        assert!(fast.blowup_factor() < small.blowup_factor());
+        assert!(estimate_size(&fast) > estimate_size(&small));
+
+        // a size limit excludes options which result in larger proofs
+        let limited = select(SpeedPreference::FastProving, Some(estimate_size(&small))).unwrap();
+        assert!(estimate_size(&limited) <= estimate_size(&small));
+        assert_eq!(
+            Err(SecurityTargetError::ProofSizeExceeded(estimate_size(&small))),
+            select(SpeedPreference::FastProving, Some(estimate_size(&small) - 1))
+        );
+    }
+
+    #[test]
+    fn proof_options_security_target_errors() {
+        let trace_info = TraceInfo::new(8, 1 << 16);
+        let select = |bits, max_constraint_degree| {
+            ProofOptions::with_security_target::<BaseElement, Hasher>(
+                bits,
+                None,
+                SpeedPreference::Balanced,
+                &trace_info,
+                max_constraint_degree,
+            )
+        };
+
+        // security is limited by the collision resistance of the hash function
+        assert_eq!(Err(SecurityTargetError::UnachievableSecurityLevel(129)), select(129, 3));
+        assert_eq!(Err(SecurityTargetError::UnsupportedConstraintDegree(0)), select(100, 0));
+        assert_eq!(Err(SecurityTargetError::UnsupportedConstraintDegree(130)), select(100, 130));
+    }
 }
@@ -6,6 +6,8 @@
 use alloc::vec::Vec;
 
 use crypto::Hasher;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
@@ -23,6 +25,7 @@ use utils::{
 /// Internally, the commitments are stored as a sequence of bytes. Thus, to retrieve the
 /// commitments, [parse()](Commitments::parse) function should be used.
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Commitments(Vec<u8>);
 
 impl Commitments {
@@ -0,0 +1,335 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{format, string::ToString, vec::Vec};
+
+use utils::DeserializationError;
+
+use super::Proof;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Set when field elements in the query sections of a compressed proof are bit-packed.
+const PACKED_ELEMENTS: u8 = 0b01;
+
+/// Set when a compressed proof (after bit-packing) is compressed using zstd.
+const ZSTD: u8 = 0b10;
+
+/// Maximum number of bytes a zstd-compressed proof can be decompressed into; this is well above
+/// the size of proofs generated with any practical set of parameters, and prevents small inputs
+/// from being decompressed into arbitrarily large buffers.
+#[cfg(feature = "zstd")]
+pub(super) const MAX_DECOMPRESSED_SIZE: usize = 1 << 25;
+
+// COMPRESSED PROOF ENCODING
+// ================================================================================================
+
+impl Proof {
+    /// Serializes this proof into a vector of bytes using a compressed encoding.
+    ///
+    /// The compressed encoding starts with a single byte describing the applied compression,
+    /// followed by the proof serialized as described in [to_bytes()](Proof::to_bytes()) with the
+    /// following changes:
+    /// - Field elements in query values of trace segments and the constraint composition
+    ///   polynomial, as well as in FRI layers, the FRI remainder, and FRI DEEP values are
+    ///   bit-packed: each base field element takes up as many bits as there are in the field
+    ///   modulus (e.g., 62 bits for the 62-bit field), rather than a whole number of bytes.
+    /// - When the `zstd` feature is enabled, the resulting bytes are additionally compressed using
+    ///   zstd, unless this does not reduce their size.
+    ///
+    /// Field elements are packed only if all of them can be represented using the number of bits
+    /// in the field modulus (which is always the case for elements in canonical form); otherwise,
+    /// the elements are left as is.
+    ///
+    /// The gains depend on the field and on the proof: elements of fields with a modulus which
+    /// uses all bits of its bytes (e.g., the 64-bit field) cannot be packed, and digests of Merkle
+    /// authentication paths are incompressible. In the worst case, the compressed proof is one
+    /// byte larger than the proof serialized via [to_bytes()](Proof::to_bytes()).
+    pub fn to_bytes_compressed(&self) -> Vec<u8> {
+        let mut flags = 0;
+        let mut bytes = match self.pack_elements() {
+            Ok(proof) => {
+                flags |= PACKED_ELEMENTS;
+                proof.to_bytes()
+            },
+            Err(_) => self.to_bytes(),
+        };
+
+        #[cfg(feature = "zstd")]
+        {
+            let compressed = zstd::bulk::compress(&bytes, zstd::DEFAULT_COMPRESSION_LEVEL)
+                .expect("failed to compress proof");
+            if compressed.len() < bytes.len() {
+                flags |= ZSTD;
+                bytes = compressed;
+            }
+        }
+
+        bytes.insert(0, flags);
+        bytes
+    }
+
+    /// Returns a STARK proof read from bytes produced by
+    /// [to_bytes_compressed()](Proof::to_bytes_compressed()).
+    ///
+    /// # Errors
+    /// Returns an error if a valid STARK proof could not be read from the specified `source`, if
+    /// the proof was compressed using zstd but the `zstd` feature is not enabled, or if the
+    /// decompressed proof would be larger than 32 MiB.
+    pub fn from_bytes_compressed(source: &[u8]) -> Result<Self, DeserializationError> {
+        let (&flags, bytes) = source.split_first().ok_or(DeserializationError::UnexpectedEOF)?;
+        if flags & !(PACKED_ELEMENTS | ZSTD) != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid proof compression flags {flags:#010b}"
+            )));
+        }
+
+        let proof = if flags & ZSTD != 0 {
+            Proof::from_bytes(&decompress_zstd(bytes)?)?
+        } else {
+            Proof::from_bytes(bytes)?
+        };
+
+        if flags & PACKED_ELEMENTS != 0 {
+            proof.unpack_elements()
+        } else {
+            Ok(proof)
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a copy of this proof with all field elements in the query sections bit-packed.
+    fn pack_elements(&self) -> Result<Self, DeserializationError> {
+        let packer = ElementPacker::new(self)?;
+        self.clone().map_values(|values| packer.pack(&values))
+    }
+
+    /// Reverses [pack_elements()](Proof::pack_elements()).
+    fn unpack_elements(self) -> Result<Self, DeserializationError> {
+        let packer = ElementPacker::new(&self)?;
+        self.map_values(|values| packer.unpack(&values))
+    }
+
+    /// Applies `f` to the bytes of all field elements in the query sections of this proof.
+    fn map_values<F>(self, mut f: F) -> Result<Self, DeserializationError>
+    where
+        F: FnMut(Vec<u8>) -> Result<Vec<u8>, DeserializationError>,
+    {
+        let trace_queries = self
+            .trace_queries
+            .into_iter()
+            .map(|queries| queries.map_values(&mut f))
+            .collect::<Result<_, _>>()?;
+        let constraint_queries = self.constraint_queries.map_values(&mut f)?;
+        let fri_proof = self.fri_proof.map_values(&mut f)?;
+
+        Ok(Proof {
+            trace_queries,
+            constraint_queries,
+            fri_proof,
+            ..self
+        })
+    }
+}
+
+// ELEMENT PACKER
+// ================================================================================================
+
+/// Packs serialized base field elements into the number of bits needed to represent the field
+/// modulus.
+///
+/// Serialized elements are expected to be encoded as little-endian integers; extension field
+/// elements are handled as sequences of base field elements.
+struct ElementPacker {
+    element_bytes: usize,
+    element_bits: usize,
+}
+
+impl ElementPacker {
+    /// Returns a packer for elements of the base field of the specified proof.
+    ///
+    /// # Errors
+    /// Returns an error if the field modulus specified in the proof context is zero.
+    fn new(proof: &Proof) -> Result<Self, DeserializationError> {
+        let element_bits = proof.context.num_modulus_bits() as usize;
+        if element_bits == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "field modulus must not be zero".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            element_bytes: proof.context.field_modulus_bytes().len(),
+            element_bits,
+        })
+    }
+
+    /// Returns the specified serialized elements packed into a sequence of bits.
+    ///
+    /// # Errors
+    /// Returns an error if the values do not consist of a whole number of elements, or if any of
+    /// the elements cannot be represented using the number of bits in the field modulus.
+    fn pack(&self, values: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+        if values.len() % self.element_bytes != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "values do not consist of a whole number of field elements".to_string(),
+            ));
+        }
+
+        let num_elements = values.len() / self.element_bytes;
+        let mut writer = BitWriter::new((num_elements * self.element_bits).div_ceil(8));
+        for element in values.chunks(self.element_bytes) {
+            let mut remaining_bits = self.element_bits;
+            for &byte in element {
+                let num_bits = remaining_bits.min(8);
+                if (byte as u16) >> num_bits != 0 {
+                    return Err(DeserializationError::InvalidValue(
+                        "field element does not fit into the number of bits in the modulus"
+                            .to_string(),
+                    ));
+                }
+                writer.write(byte, num_bits);
+                remaining_bits -= num_bits;
+            }
+        }
+
+        Ok(writer.finish())
+    }
+
+    /// Returns serialized elements unpacked from the specified sequence of bits.
+    ///
+    /// # Errors
+    /// Returns an error if the packed bits do not encode a whole number of elements.
+    fn unpack(&self, packed: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+        let num_elements = packed.len() * 8 / self.element_bits;
+        if (num_elements * self.element_bits).div_ceil(8) != packed.len() {
+            return Err(DeserializationError::InvalidValue(
+                "packed values do not consist of a whole number of field elements".to_string(),
+            ));
+        }
+
+        let mut reader = BitReader::new(packed);
+        let mut values = Vec::with_capacity(num_elements * self.element_bytes);
+        for _ in 0..num_elements {
+            let mut remaining_bits = self.element_bits;
+            for _ in 0..self.element_bytes {
+                let num_bits = remaining_bits.min(8);
+                values.push(reader.read(num_bits));
+                remaining_bits -= num_bits;
+            }
+        }
+
+        if reader.read(packed.len() * 8 - num_elements * self.element_bits) != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "padding of packed values must be zero".to_string(),
+            ));
+        }
+
+        Ok(values)
+    }
+}
+
+/// Writes values of up to 8 bits into a vector of bytes, starting with the least significant bits.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u16,
+    num_buffered_bits: usize,
+}
+
+impl BitWriter {
+    fn new(capacity: usize) -> Self {
+        Self {
+            bytes: Vec::with_capacity(capacity),
+            buffer: 0,
+            num_buffered_bits: 0,
+        }
+    }
+
+    fn write(&mut self, value: u8, num_bits: usize) {
+        self.buffer |= (value as u16) << self.num_buffered_bits;
+        self.num_buffered_bits += num_bits;
+        if self.num_buffered_bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.num_buffered_bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.num_buffered_bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Reads values of up to 8 bits written by a [BitWriter].
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    buffer: u16,
+    num_buffered_bits: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            buffer: 0,
+            num_buffered_bits: 0,
+        }
+    }
+
+    /// Reads the next `num_bits` bits; the caller must ensure that enough bits are available.
+    fn read(&mut self, num_bits: usize) -> u8 {
+        if self.num_buffered_bits < num_bits {
+            self.buffer |= (self.bytes[self.pos] as u16) << self.num_buffered_bits;
+            self.pos += 1;
+            self.num_buffered_bits += 8;
+        }
+        let value = self.buffer & ((1 << num_bits) - 1);
+        self.buffer >>= num_bits;
+        self.num_buffered_bits -= num_bits;
+        value as u8
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[cfg(feature = "zstd")]
+fn decompress_zstd(bytes: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    use std::io::Read;
+
+    let invalid_data =
+        |err| DeserializationError::InvalidValue(format!("invalid zstd data: {err}"));
+
+    // read at most one byte past the limit so that oversized proofs are detected without
+    // decompressing them in full
+    let decoder = zstd::stream::read::Decoder::new(bytes).map_err(invalid_data)?;
+    let mut result = Vec::new();
+    decoder
+        .take(MAX_DECOMPRESSED_SIZE as u64 + 1)
+        .read_to_end(&mut result)
+        .map_err(invalid_data)?;
+    if result.len() > MAX_DECOMPRESSED_SIZE {
+        return Err(DeserializationError::InvalidValue(format!(
+            "decompressed proof exceeds {MAX_DECOMPRESSED_SIZE} bytes"
+        )));
+    }
+
+    Ok(result)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress_zstd(_bytes: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    Err(DeserializationError::InvalidValue(
+        "proof is compressed using zstd, but the zstd feature is not enabled".to_string(),
+    ))
+}
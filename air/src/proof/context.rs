@@ -5,31 +5,42 @@
 
 use alloc::{string::ToString, vec::Vec};
 
+use crypto::Hasher;
 use math::{StarkField, ToElements};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
-use crate::{ProofOptions, TraceInfo};
+use super::{get_conjectured_security, get_proven_security};
+use crate::{get_air_digest, Air, ProofOptions, TraceInfo};
 
 // PROOF CONTEXT
 // ================================================================================================
 /// Basic metadata about a specific execution of a computation.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Context {
     trace_info: TraceInfo,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
+    air_digest: [u8; 32],
 }
 
 impl Context {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new context for a computation described by the specified field, trace info, and
-    /// proof options.
+    /// Creates a new context for a computation described by the specified field, trace info,
+    /// proof options, and digest of the structure of the AIR (see
+    /// [get_air_digest()](crate::get_air_digest)).
     ///
     /// # Panics
     /// Panics if either trace length or the LDE domain size implied by the trace length and the
     /// blowup factor is greater then [u32::MAX].
-    pub fn new<B: StarkField>(trace_info: TraceInfo, options: ProofOptions) -> Self {
+    pub fn new<B: StarkField>(
+        trace_info: TraceInfo,
+        options: ProofOptions,
+        air_digest: [u8; 32],
+    ) -> Self {
         // TODO: return errors instead of panicking?
 
         let trace_length = trace_info.length();
@@ -42,9 +53,19 @@ impl Context {
             trace_info,
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
+            air_digest,
         }
     }
 
+    /// Returns a new context for the computation described by the specified AIR instance.
+    pub fn from_air<A: Air>(air: &A) -> Self {
+        Self::new::<A::BaseField>(
+            air.trace_info().clone(),
+            air.options().clone(),
+            get_air_digest(air),
+        )
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -80,10 +101,46 @@ impl Context {
         0
     }
 
+    /// Returns the digest of the structure of the AIR for which proofs in this context are
+    /// generated.
+    pub fn air_digest(&self) -> &[u8; 32] {
+        &self.air_digest
+    }
+
     /// Returns proof options which were used to a proof in this context.
     pub fn options(&self) -> &ProofOptions {
         &self.options
     }
+
+    /// Returns security level (in bits) of proofs generated for this context using hash
+    /// function `H`.
+    ///
+    /// When `conjectured` is true, conjectured security level is returned; otherwise, provable
+    /// security level is returned.
+    pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
+        if conjectured {
+            get_conjectured_security(
+                &self.options,
+                self.num_modulus_bits(),
+                self.trace_info.length(),
+                H::COLLISION_RESISTANCE,
+            )
+        } else {
+            get_proven_security(
+                &self.options,
+                self.num_modulus_bits(),
+                self.trace_info.length(),
+                H::COLLISION_RESISTANCE,
+            )
+        }
+    }
+
+    /// Returns security level (in bits) of proofs generated for this context using hash
+    /// function `H`, computed using the soundness bounds declared by the proof options of this
+    /// context.
+    pub fn declared_security_level<H: Hasher>(&self) -> u32 {
+        self.security_level::<H>(!self.options.uses_proven_security())
+    }
 }
 
 impl<E: StarkField> ToElements<E> for Context {
@@ -96,6 +153,7 @@ impl<E: StarkField> ToElements<E> for Context {
     /// - grinding factor [1 element].
     /// - blowup factor [1 element].
     /// - number of queries [1 element].
+    /// - AIR digest [32 bytes packed into elements of `ELEMENT_BYTES - 1` bytes].
     fn to_elements(&self) -> Vec<E> {
         // convert trace layout
         let mut result = self.trace_info.to_elements();
@@ -109,6 +167,11 @@ impl<E: StarkField> ToElements<E> for Context {
         // convert proof options to elements
         result.append(&mut self.options.to_elements());
 
+        // convert AIR digest into elements
+        for chunk in self.air_digest.chunks(E::ELEMENT_BYTES - 1) {
+            result.push(E::from_bytes_with_padding(chunk));
+        }
+
         result
     }
 }
@@ -124,6 +187,7 @@ impl Serializable for Context {
         target.write_u8(self.field_modulus_bytes.len() as u8);
         target.write_bytes(&self.field_modulus_bytes);
         self.options.write_into(target);
+        target.write_bytes(&self.air_digest);
     }
 }
 
@@ -148,7 +212,15 @@ impl Deserializable for Context {
         // read options
         let options = ProofOptions::read_from(source)?;
 
-        Ok(Context { trace_info, field_modulus_bytes, options })
+        // read AIR digest
+        let air_digest = source.read_array()?;
+
+        Ok(Context {
+            trace_info,
+            field_modulus_bytes,
+            options,
+            air_digest,
+        })
     }
 }
 
@@ -157,7 +229,7 @@ impl Deserializable for Context {
 
 #[cfg(test)]
 mod tests {
-    use math::fields::f64::BaseElement;
+    use math::{fields::f64::BaseElement, StarkField};
 
     use super::{Context, ProofOptions, ToElements, TraceInfo};
     use crate::FieldExtension;
@@ -175,6 +247,7 @@ mod tests {
         let aux_width = 9;
         let aux_rands = 12;
         let trace_length = 4096;
+        let air_digest: [u8; 32] = core::array::from_fn(|i| i as u8);
 
         let ext_fri = u32::from_le_bytes([
             fri_remainder_max_degree,
@@ -202,6 +275,9 @@ mod tests {
                 BaseElement::from(num_queries as u32),
             ]);
 
+            // the AIR digest is packed into elements of 7 bytes each
+            expected.extend(air_digest.chunks(7).map(BaseElement::from_bytes_with_padding));
+
             expected
         };
 
@@ -215,7 +291,7 @@ mod tests {
         );
         let trace_info =
             TraceInfo::new_multi_segment(main_width, aux_width, aux_rands, trace_length, vec![]);
-        let context = Context::new::<BaseElement>(trace_info, options);
+        let context = Context::new::<BaseElement>(trace_info, options, air_digest);
         assert_eq!(expected, context.to_elements());
     }
 }
@@ -0,0 +1,161 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::Proof;
+
+// VERIFICATION COST
+// ================================================================================================
+
+/// Estimate of the work needed to verify a STARK proof.
+///
+/// The estimate is computed via [Proof::verification_cost()] from the metadata of a proof alone,
+/// and thus, is available before any verification work is done. This makes it possible to predict
+/// the cost of verifying a proof in constrained environments (e.g., gas consumption of an on-chain
+/// verifier), and to reject proofs which would exceed a given budget.
+///
+/// All counts are upper bounds derived from the number of queries specified in the proof options:
+/// - Hashes: leaf hashes and internal node hashes needed to verify Merkle authentication paths
+///   for the trace segments, the constraint composition polynomial, and all FRI layers. Merkle
+///   paths are counted as if no nodes were shared between paths of different queries. Hashes
+///   computed by the public coin are not included.
+/// - Field multiplications: extension field multiplications needed to compute the DEEP
+///   composition polynomial at the queried positions, to fold FRI layers, and to evaluate the FRI
+///   remainder. Batched inversions are counted as three multiplications per inverted element.
+///   Evaluation of AIR constraints at the out-of-domain point is not included, as its cost
+///   depends on the AIR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationCost {
+    num_leaf_hashes: usize,
+    num_node_hashes: usize,
+    num_hashed_elements: usize,
+    num_field_mults: usize,
+    merkle_path_lengths: Vec<usize>,
+}
+
+impl VerificationCost {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an estimate of the work needed to verify the specified proof.
+    pub(super) fn new(proof: &Proof) -> Self {
+        let options = proof.options();
+        let trace_info = proof.trace_info();
+        let num_queries = options.num_queries();
+        let lde_domain_size = proof.lde_domain_size();
+        let lde_domain_depth = lde_domain_size.ilog2() as usize;
+
+        let extension_degree = options.field_extension().degree() as usize;
+        let element_size = proof.context.field_modulus_bytes().len() * extension_degree;
+
+        // the number of rows in the evaluation frame and the number of composition polynomial
+        // columns are not a part of the proof context, and thus, are inferred from the OOD frame
+        let num_frame_rows = proof.ood_frame.num_trace_rows();
+        let num_composition_columns = proof.ood_frame.num_evaluation_bytes() / element_size;
+
+        // trace segments and the constraint composition polynomial are committed to via Merkle
+        // trees with a leaf for every row of the LDE domain
+        let mut merkle_path_lengths = vec![lde_domain_depth; trace_info.num_segments() + 1];
+        let mut num_hashed_elements = num_queries
+            * (trace_info.main_trace_width()
+                + (trace_info.aux_segment_width() + num_composition_columns) * extension_degree);
+
+        // DEEP composition: each queried trace value is combined with its OOD value for every row
+        // of the frame; denominators are computed for every row of the frame and inverted in a
+        // batch together with the denominators of the constraint composition columns
+        let num_trace_columns = trace_info.width();
+        let mut num_field_mults = num_queries
+            * (num_trace_columns * num_frame_rows
+                + num_composition_columns
+                + 3 * (num_frame_rows + 1)
+                + lde_domain_depth);
+
+        // FRI layers: a layer folded by a factor of N is committed to via a Merkle tree with a
+        // leaf for every N evaluations; folding interpolates a polynomial of degree N - 1 and
+        // evaluates it at the layer's random point
+        let fri_options = options.to_fri_options();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let mut layer_size = lde_domain_size;
+        for layer_idx in 0..num_fri_layers {
+            let folding_factor = fri_options.folding_factor_at(layer_idx);
+            layer_size /= folding_factor;
+            merkle_path_lengths.push(layer_size.ilog2() as usize);
+            num_hashed_elements += num_queries * folding_factor * extension_degree;
+            num_field_mults += num_queries * (folding_factor * folding_factor + folding_factor);
+        }
+
+        // remainder: hashed once and evaluated at every query position via Horner's method
+        let remainder_size = fri_options.remainder_max_degree() + 1;
+        num_hashed_elements += remainder_size * extension_degree;
+        num_field_mults += num_queries * remainder_size;
+
+        let num_leaf_hashes = num_queries * merkle_path_lengths.len() + 1;
+        let num_node_hashes = num_queries * merkle_path_lengths.iter().sum::<usize>();
+
+        Self {
+            num_leaf_hashes,
+            num_node_hashes,
+            num_hashed_elements,
+            num_field_mults,
+            merkle_path_lengths,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the total number of hash function invocations needed to verify Merkle
+    /// authentication paths and the FRI remainder commitment.
+    pub fn num_hashes(&self) -> usize {
+        self.num_leaf_hashes + self.num_node_hashes
+    }
+
+    /// Returns the number of hashes of queried leaves, including the hash of the FRI remainder.
+    pub fn num_leaf_hashes(&self) -> usize {
+        self.num_leaf_hashes
+    }
+
+    /// Returns the number of 2-to-1 hashes of internal Merkle tree nodes.
+    pub fn num_node_hashes(&self) -> usize {
+        self.num_node_hashes
+    }
+
+    /// Returns the number of base field elements hashed into leaves.
+    pub fn num_hashed_elements(&self) -> usize {
+        self.num_hashed_elements
+    }
+
+    /// Returns the number of extension field multiplications.
+    pub fn num_field_mults(&self) -> usize {
+        self.num_field_mults
+    }
+
+    /// Returns the length of a Merkle authentication path for every commitment opened by the
+    /// verifier.
+    ///
+    /// Paths are listed for trace segments first, followed by the constraint composition
+    /// polynomial, and then by FRI layers.
+    pub fn merkle_path_lengths(&self) -> &[usize] {
+        &self.merkle_path_lengths
+    }
+}
+
+impl fmt::Display for VerificationCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hashes: {} ({} leaves, {} nodes), hashed elements: {}, field multiplications: {}, \
+            Merkle path lengths: {:?}",
+            self.num_hashes(),
+            self.num_leaf_hashes,
+            self.num_node_hashes,
+            self.num_hashed_elements,
+            self.num_field_mults,
+            self.merkle_path_lengths,
+        )
+    }
+}
@@ -5,13 +5,21 @@
 
 //! Contains STARK proof struct and associated components.
 
-use alloc::vec::Vec;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::cmp;
 
 use crypto::Hasher;
 use fri::FriProof;
 use math::FieldElement;
-use utils::{ByteReader, Deserializable, DeserializationError, Serializable, SliceReader};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use utils::{
+    encoding, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
 
 use crate::{ProofOptions, TraceInfo};
 
@@ -30,6 +38,11 @@ pub use ood_frame::{OodFrame, TraceOodFrame};
 mod table;
 pub use table::Table;
 
+mod cost;
+pub use cost::VerificationCost;
+
+mod compression;
+
 #[cfg(test)]
 mod tests;
 
@@ -37,6 +50,11 @@ mod tests;
 // ================================================================================================
 
 const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+
+/// Marks the start of the format version header of a serialized proof. Proofs serialized by
+/// earlier releases do not have a version header and start with the (non-zero) width of the main
+/// trace segment instead; such proofs are rejected, as no decoder is provided for their encoding.
+const VERSION_HEADER_MARKER: u16 = 0;
 const MAX_PROXIMITY_PARAMETER: u64 = 1000;
 
 // PROOF
@@ -53,15 +71,19 @@ const MAX_PROXIMITY_PARAMETER: u64 = 1000;
 ///
 /// A proof can be serialized into a sequence of bytes using [to_bytes()](Proof::to_bytes) function,
 /// and deserialized from a sequence of bytes using [from_bytes()](Proof::from_bytes) function.
+/// To verify a proof without buffering all of it in memory, the proof can be serialized via
+/// [to_stream_bytes()](Proof::to_stream_bytes) function.
 ///
 /// To estimate soundness of a proof (in bits), [security_level()](Proof::security_level) function
-/// can be used.
+/// can be used. To estimate the work needed to verify a proof,
+/// [verification_cost()](Proof::verification_cost) function can be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Proof {
     /// Basic metadata about the execution of the computation described by this proof.
     pub context: Context,
-    /// Number of unique queries made by the verifier. This will be different from the
-    /// context.options.num_queries if the same position in the domain was queried more than once.
+    /// Number of unique queries made by the verifier. Query positions are drawn without
+    /// replacement, and thus, this is equal to context.options.num_queries.
     pub num_unique_queries: u8,
     /// Commitments made by the prover during the commit phase of the protocol.
     pub commitments: Commitments,
@@ -82,6 +104,18 @@ pub struct Proof {
 }
 
 impl Proof {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Version of the format in which proofs are serialized via [to_bytes()](Proof::to_bytes()).
+    ///
+    /// The version is written at the start of every serialized proof. Only proofs serialized
+    /// using this version can be read; version 2 added the AIR digest to the proof context.
+    pub const FORMAT_VERSION: u8 = 2;
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
     /// Returns STARK protocol parameters used to generate this proof.
     pub fn options(&self) -> &ProofOptions {
         self.context.options()
@@ -106,21 +140,28 @@ impl Proof {
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
     /// security level.
     pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
-        if conjectured {
-            get_conjectured_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.trace_info().length(),
-                H::COLLISION_RESISTANCE,
-            )
-        } else {
-            get_proven_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.trace_info().length(),
-                H::COLLISION_RESISTANCE,
-            )
-        }
+        self.context.security_level::<H>(conjectured)
+    }
+
+    /// Returns security level of this proof (in bits) computed using the soundness bounds declared
+    /// by its options.
+    ///
+    /// That is, provable security level is returned if the proof options declare proven security
+    /// via [ProofOptions::with_proven_security()], and conjectured security level otherwise.
+    pub fn declared_security_level<H: Hasher>(&self) -> u32 {
+        self.context.declared_security_level::<H>()
+    }
+
+    // VERIFICATION COST
+    // --------------------------------------------------------------------------------------------
+    /// Returns an estimate of the number of hashes, field operations, and Merkle path lengths
+    /// needed to verify this proof.
+    ///
+    /// The estimate is derived from the proof metadata only, and thus, can be used to reject
+    /// proofs whose parameters exceed a verification budget before any verification work is done.
+    /// See [VerificationCost] for details on what is included in the estimate.
+    pub fn verification_cost(&self) -> VerificationCost {
+        VerificationCost::new(self)
     }
 
     // SERIALIZATION / DESERIALIZATION
@@ -133,12 +174,86 @@ impl Proof {
 
     /// Returns a STARK proof read from the specified `source`.
     ///
+    /// Only proofs serialized using [Proof::FORMAT_VERSION] are supported.
+    ///
     /// # Errors
-    /// Returns an error of a valid STARK proof could not be read from the specified `source`.
+    /// Returns an error of a valid STARK proof could not be read from the specified `source`, or
+    /// [DeserializationError::UnsupportedVersion] if the proof was serialized using a format
+    /// version which is not supported.
     pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
         Deserializable::read_from_bytes(source)
     }
 
+    /// Serializes this proof into a lowercase hex string (without a `0x` prefix).
+    ///
+    /// The string encodes the bytes returned by [to_bytes()](Proof::to_bytes()).
+    pub fn to_hex(&self) -> String {
+        encoding::to_hex(&self.to_bytes())
+    }
+
+    /// Returns a STARK proof read from the specified hex string; the string may optionally start
+    /// with a `0x` prefix.
+    ///
+    /// # Errors
+    /// Returns an error if the string is not a valid hex encoding, or if a valid STARK proof
+    /// could not be read from the decoded bytes.
+    pub fn from_hex(source: &str) -> Result<Self, DeserializationError> {
+        Self::from_bytes(&encoding::from_hex(source)?)
+    }
+
+    /// Serializes this proof into a base64 string using the standard alphabet and padding.
+    ///
+    /// The string encodes the bytes returned by [to_bytes()](Proof::to_bytes()).
+    pub fn to_base64(&self) -> String {
+        encoding::to_base64(&self.to_bytes())
+    }
+
+    /// Returns a STARK proof read from the specified base64 string; padding is optional.
+    ///
+    /// # Errors
+    /// Returns an error if the string is not a valid base64 encoding, or if a valid STARK proof
+    /// could not be read from the decoded bytes.
+    pub fn from_base64(source: &str) -> Result<Self, DeserializationError> {
+        Self::from_bytes(&encoding::from_base64(source)?)
+    }
+
+    /// Serializes this proof into a stream of bytes in which proof sections are ordered in the
+    /// same way as they are consumed by the verifier, and writes the stream into `target`.
+    ///
+    /// The stream consists of the following sections:
+    /// 1. Header: proof context, number of unique queries, commitments, GKR proof (if any),
+    ///    FRI remainder, FRI DEEP values, number of FRI partitions, and proof-of-work nonces.
+    /// 2. Out-of-domain evaluation frame.
+    /// 3. Trace queries for all trace segments followed by constraint queries.
+    /// 4. FRI layers, one after another.
+    ///
+    /// This allows a verifier to process the proof as it arrives, without buffering the entire
+    /// proof in memory. The header is small; the size of the remaining sections is dominated by
+    /// query values and their authentication paths.
+    ///
+    /// Unlike [to_bytes()](Proof::to_bytes()), the stream does not start with a format version
+    /// header; thus, streams are not intended for long-term storage of proofs.
+    pub fn write_stream_into<W: ByteWriter>(&self, target: &mut W) {
+        self.context.write_into(target);
+        target.write_u8(self.num_unique_queries);
+        self.commitments.write_into(target);
+        self.gkr_proof.write_into(target);
+        self.fri_proof.write_header_into(target);
+        target.write_u64(self.pow_nonce);
+        self.ood_frame.write_into(target);
+        target.write_many(&self.trace_queries);
+        self.constraint_queries.write_into(target);
+        self.fri_proof.write_layers_into(target);
+    }
+
+    /// Serializes this proof into a vector of bytes ordered as described in
+    /// [write_stream_into()](Proof::write_stream_into()).
+    pub fn to_stream_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.write_stream_into(&mut result);
+        result
+    }
+
     /// Creates a dummy `Proof` for use in tests.
     pub fn new_dummy() -> Self {
         use crypto::{hashers::Blake3_192 as DummyHasher, BatchMerkleProof};
@@ -150,6 +265,7 @@ impl Proof {
             context: Context::new::<DummyField>(
                 TraceInfo::new(1, 8),
                 ProofOptions::new(1, 2, 2, FieldExtension::None, 8, 1),
+                [0; 32],
             ),
             num_unique_queries: 0,
             commitments: Commitments::default(),
@@ -174,7 +290,9 @@ impl Proof {
 // ================================================================================================
 
 impl Serializable for Proof {
-    fn write_into<W: utils::ByteWriter>(&self, target: &mut W) {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(VERSION_HEADER_MARKER);
+        target.write_u8(Self::FORMAT_VERSION);
         self.context.write_into(target);
         target.write_u8(self.num_unique_queries);
         self.commitments.write_into(target);
@@ -189,6 +307,15 @@ impl Serializable for Proof {
 
 impl Deserializable for Proof {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        if source.read_u16()? != VERSION_HEADER_MARKER {
+            return Err(DeserializationError::InvalidValue(
+                "proof does not start with a format version header".to_string(),
+            ));
+        }
+        let version = source.read_u8()?;
+        if version != Self::FORMAT_VERSION {
+            return Err(DeserializationError::UnsupportedVersion(version));
+        }
         let context = Context::read_from(source)?;
         let num_unique_queries = source.read_u8()?;
         let commitments = Commitments::read_from(source)?;
@@ -223,9 +350,16 @@ fn get_conjectured_security(
     trace_domain_size: usize,
     collision_resistance: u32,
 ) -> u32 {
-    // compute max security we can get for a given field size
-    let field_size = base_field_bits * options.field_extension().degree();
-    let field_security = field_size - (trace_domain_size * options.blowup_factor()).ilog2();
+    // compute max security we can get for given field sizes; out-of-domain sampling is limited
+    // by the size of the field from which the out-of-domain point is drawn relative to the size
+    // of the LDE domain, while constraint composition is limited by the size of the field from
+    // which composition coefficients are drawn
+    let ood_field_size = base_field_bits * options.ood_field_extension().degree();
+    let composition_field_size = base_field_bits * options.field_extension().degree();
+    let field_security = cmp::min(
+        ood_field_size - (trace_domain_size * options.blowup_factor()).ilog2(),
+        composition_field_size,
+    );
 
     // compute security we get by executing multiple query rounds
     let security_per_query = options.blowup_factor().ilog2();
@@ -281,7 +415,8 @@ fn proven_security_protocol_for_m(
     trace_domain_size: usize,
     m: usize,
 ) -> u64 {
-    let extension_field_bits = (base_field_bits * options.field_extension().degree()) as f64;
+    let extension_field_bits = (base_field_bits * options.ood_field_extension().degree()) as f64;
+    let composition_field_bits = (base_field_bits * options.field_extension().degree()) as f64;
     let num_fri_queries = options.num_queries() as f64;
     let m = m as f64;
     let rho = 1.0 / options.blowup_factor() as f64;
@@ -332,8 +467,9 @@ fn proven_security_protocol_for_m(
     let l_plus = (2.0 * m_plus + 1.0) / (2.0 * sqrt(rho_plus));
 
     // ALI related soundness error. Note that C here is equal to 1 because of our use of
-    // linear batching.
-    let ali_err_bits = -log2(l_plus) + extension_field_bits;
+    // linear batching, and that constraint composition coefficients are drawn from the
+    // composition field (which may be smaller than the out-of-domain field).
+    let ali_err_bits = -log2(l_plus) + composition_field_bits;
 
     // DEEP related soundness error. Note that this uses that the denominator |F| - |D ∪ H|
     // can be approximated by |F| for all practical domain sizes. We also use the blow-up factor
@@ -410,7 +546,10 @@ mod prove_security_tests {
     use math::{fields::f64::BaseElement, StarkField};
 
     use super::ProofOptions;
-    use crate::{proof::get_proven_security, FieldExtension};
+    use crate::{
+        proof::{get_conjectured_security, get_proven_security},
+        FieldExtension,
+    };
 
     #[test]
     fn get_96_bits_security() {
@@ -657,4 +796,35 @@ mod prove_security_tests {
 
         assert!(security_1 < security_2);
     }
+
+    #[test]
+    fn ood_field_extension() {
+        let base_field_bits = BaseElement::MODULUS_BITS;
+        let collision_resistance = 128;
+        let trace_length = 2_usize.pow(20);
+
+        let options = ProofOptions::new(80, 8, 20, FieldExtension::None, 8, 127);
+        let ood_options = options.clone().with_ood_field_extension(FieldExtension::Quadratic);
+        let ext_options = ProofOptions::new(80, 8, 20, FieldExtension::Quadratic, 8, 127);
+
+        // drawing the out-of-domain point from an extension field increases security, but not
+        // beyond the security of building the composition polynomial in the extension field
+        let security = |options: &ProofOptions| {
+            (
+                get_conjectured_security(
+                    options,
+                    base_field_bits,
+                    trace_length,
+                    collision_resistance,
+                ),
+                get_proven_security(options, base_field_bits, trace_length, collision_resistance),
+            )
+        };
+        let (conjectured_1, proven_1) = security(&options);
+        let (conjectured_2, proven_2) = security(&ood_options);
+        let (conjectured_3, proven_3) = security(&ext_options);
+
+        assert!(conjectured_1 < conjectured_2 && conjectured_2 <= conjectured_3);
+        assert!(proven_1 <= proven_2 && proven_2 <= proven_3);
+    }
 }
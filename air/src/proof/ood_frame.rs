@@ -7,6 +7,8 @@ use alloc::vec::Vec;
 
 use crypto::ElementHasher;
 use math::FieldElement;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
@@ -19,8 +21,8 @@ use crate::{EvaluationFrame, LagrangeKernelEvaluationFrame};
 /// Trace and constraint polynomial evaluations at an out-of-domain point.
 ///
 /// This struct contains the following evaluations:
-/// * Evaluations of all trace polynomials at *z*.
-/// * Evaluations of all trace polynomials at *z * g*.
+/// * Evaluations of all trace polynomials at *z * g^k* for each row offset *k* of the transition
+///   constraint evaluation frame; by default, these are *z* and *z * g*.
 /// * Evaluations of Lagrange kernel trace polynomial (if any) at *z*, *z * g*, *z * g^2*, ...,
 ///   *z * g^(2^(v-1))*, where `v == log(trace_len)`
 /// * Evaluations of constraint composition column polynomials at *z*.
@@ -30,6 +32,7 @@ use crate::{EvaluationFrame, LagrangeKernelEvaluationFrame};
 /// Internally, the evaluations are stored as a sequence of bytes. Thus, to retrieve the
 /// evaluations, [parse()](OodFrame::parse) function should be used.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct OodFrame {
     trace_states: Vec<u8>,
     lagrange_kernel_trace_states: Vec<u8>,
@@ -43,8 +46,8 @@ impl OodFrame {
     /// Updates the trace state portion of this out-of-domain frame, and returns the hash of the
     /// trace states.
     ///
-    /// The out-of-domain frame is stored as one vector of interleaved values, one from the current
-    /// row and the other from the next row. Given the input frame
+    /// The out-of-domain frame is stored as one vector of interleaved values, one from each row of
+    /// the frame. Given the input frame consisting of the current and the next rows
     ///
     ///    +-------+-------+-------+-------+-------+-------+-------+-------+
     ///    |   a1  |   a2  |  ...  |  an   |  c1   |  c2   |  ...  |  cm   |
@@ -56,7 +59,8 @@ impl OodFrame {
     ///
     /// [a1, b1, a2, b2, ..., an, bn, c1, d1, c2, d2, ..., cm, dm]
     ///
-    /// into `Self::trace_states` (as byte values).
+    /// into `Self::trace_states` (as byte values). Frames with more than two rows are stored in
+    /// the same way, with values from all rows of the frame interleaved for each column.
     ///
     /// # Panics
     /// Panics if evaluation frame has already been set.
@@ -67,11 +71,11 @@ impl OodFrame {
     {
         assert!(self.trace_states.is_empty(), "trace sates have already been set");
 
-        // save the evaluations with the current and next evaluations interleaved for each polynomial
+        // save the evaluations with evaluations from all rows interleaved for each polynomial
         let (main_and_aux_trace_states, lagrange_trace_states) = trace_ood_frame.to_trace_states();
 
-        // there are 2 frames: current and next
-        let frame_size: u8 = 2;
+        // the number of rows in the frame is guaranteed to fit into a u8 by the AIR context
+        let frame_size = trace_ood_frame.num_rows() as u8;
         self.trace_states.write_u8(frame_size);
         self.trace_states.write_many(&main_and_aux_trace_states);
 
@@ -101,18 +105,35 @@ impl OodFrame {
         self.evaluations.write_many(evaluations);
     }
 
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in the trace evaluation frame, or zero if the trace states have
+    /// not been set.
+    pub(crate) fn num_trace_rows(&self) -> usize {
+        self.trace_states.first().map_or(0, |&frame_size| frame_size as usize)
+    }
+
+    /// Returns the number of bytes used to encode constraint evaluations.
+    pub(crate) fn num_evaluation_bytes(&self) -> usize {
+        self.evaluations.len()
+    }
+
     // PARSER
     // --------------------------------------------------------------------------------------------
     /// Returns an out-of-domain trace frame and a vector of out-of-domain constraint evaluations
     /// contained in `self`.
     ///
+    /// `frame_offsets` specifies row offsets of the evaluation frame declared by the AIR (see
+    /// [AirContext::frame_offsets()](crate::AirContext::frame_offsets)).
+    ///
     /// # Panics
     /// Panics if either `main_trace_width` or `num_evaluations` are equal to zero.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * Valid [`crate::EvaluationFrame`]s for the specified `main_trace_width` and
-    ///   `aux_trace_width` could not be parsed from the internal bytes.
+    /// * Valid [`crate::EvaluationFrame`]s for the specified `main_trace_width`,
+    ///   `aux_trace_width`, and `frame_offsets` could not be parsed from the internal bytes.
     /// * A vector of evaluations specified by `num_evaluations` could not be parsed from the
     ///   internal bytes.
     /// * Any unconsumed bytes remained after the parsing was complete.
@@ -121,6 +142,7 @@ impl OodFrame {
         main_trace_width: usize,
         aux_trace_width: usize,
         num_evaluations: usize,
+        frame_offsets: &[usize],
     ) -> Result<(TraceOodFrame<E>, Vec<E>), DeserializationError> {
         assert!(main_trace_width > 0, "trace width cannot be zero");
         assert!(num_evaluations > 0, "number of evaluations cannot be zero");
@@ -141,24 +163,31 @@ impl OodFrame {
 
         // parse main and auxiliary trace evaluation frames. This does the reverse operation done in
         // `set_trace_states()`.
-        let (current_row, next_row) = {
+        let rows = {
             let mut reader = SliceReader::new(&self.trace_states);
             let frame_size = reader.read_u8()? as usize;
+            if frame_size != frame_offsets.len() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "expected evaluation frame with {} rows, but was {}",
+                    frame_offsets.len(),
+                    frame_size
+                )));
+            }
             let trace = reader.read_many((main_trace_width + aux_trace_width) * frame_size)?;
 
             if reader.has_more_bytes() {
                 return Err(DeserializationError::UnconsumedBytes);
             }
 
-            let mut current_row = Vec::with_capacity(main_trace_width);
-            let mut next_row = Vec::with_capacity(main_trace_width);
-
-            for col in trace.chunks_exact(2) {
-                current_row.push(col[0]);
-                next_row.push(col[1]);
+            let trace_width = main_trace_width + aux_trace_width;
+            let mut rows = vec![Vec::with_capacity(trace_width); frame_size];
+            for col in trace.chunks_exact(frame_size) {
+                for (row, &value) in rows.iter_mut().zip(col) {
+                    row.push(value);
+                }
             }
 
-            (current_row, next_row)
+            rows
         };
 
         // parse the constraint evaluations
@@ -169,7 +198,12 @@ impl OodFrame {
         }
 
         Ok((
-            TraceOodFrame::new(current_row, next_row, main_trace_width, lagrange_kernel_frame),
+            TraceOodFrame::new(
+                rows,
+                frame_offsets.to_vec(),
+                main_trace_width,
+                lagrange_kernel_frame,
+            ),
             evaluations,
         ))
     }
@@ -229,65 +263,85 @@ impl Deserializable for OodFrame {
 // OOD FRAME TRACE STATES
 // ================================================================================================
 
-/// Stores the trace evaluations at `z` and `gz`, where `z` is a random Field element in
-/// `current_row` and `next_row`, respectively. If the Air contains a Lagrange kernel auxiliary
-/// column, then that column interpolated polynomial will be evaluated at `z`, `gz`, `g^2 z`, ...
-/// `g^(2^(v-1)) z`, where `v == log(trace_len)`, and stored in `lagrange_kernel_frame`.
+/// Stores the trace evaluations at `z * g^k` for each row offset `k` of the transition constraint
+/// evaluation frame, where `z` is a random Field element; by default, the offsets are `0` and `1`,
+/// and thus, the frame consists of evaluations at `z` and `gz`. If the Air contains a Lagrange
+/// kernel auxiliary column, then that column interpolated polynomial will be evaluated at `z`,
+/// `gz`, `g^2 z`, ... `g^(2^(v-1)) z`, where `v == log(trace_len)`, and stored in
+/// `lagrange_kernel_frame`.
 pub struct TraceOodFrame<E: FieldElement> {
-    current_row: Vec<E>,
-    next_row: Vec<E>,
+    rows: Vec<Vec<E>>,
+    offsets: Vec<usize>,
     main_trace_width: usize,
     lagrange_kernel_frame: Option<LagrangeKernelEvaluationFrame<E>>,
 }
 
 impl<E: FieldElement> TraceOodFrame<E> {
-    /// Creates a new [`TraceOodFrame`] from current, next and optionally Lagrange kernel frames.
+    /// Creates a new [`TraceOodFrame`] from rows located at the specified row offsets and
+    /// optionally a Lagrange kernel frame.
+    ///
+    /// # Panics
+    /// Panics if the number of rows is not the same as the number of offsets, or if the rows are
+    /// of different lengths.
     pub fn new(
-        current_row: Vec<E>,
-        next_row: Vec<E>,
+        rows: Vec<Vec<E>>,
+        offsets: Vec<usize>,
         main_trace_width: usize,
         lagrange_kernel_frame: Option<LagrangeKernelEvaluationFrame<E>>,
     ) -> Self {
-        assert_eq!(current_row.len(), next_row.len());
+        assert_eq!(rows.len(), offsets.len());
+        assert!(rows.iter().all(|row| row.len() == rows[0].len()));
 
         Self {
-            current_row,
-            next_row,
+            rows,
+            offsets,
             main_trace_width,
             lagrange_kernel_frame,
         }
     }
 
-    /// Returns the number of columns for the current and next frames.
+    /// Returns the number of columns in each row of the frame.
     pub fn num_columns(&self) -> usize {
-        self.current_row.len()
+        self.rows[0].len()
+    }
+
+    /// Returns the number of rows in the frame.
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns row offsets of the frame relative to the current row.
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
     }
 
     /// Returns the current row, consisting of both main and auxiliary columns.
     pub fn current_row(&self) -> &[E] {
-        &self.current_row
+        &self.rows[0]
     }
 
     /// Returns the next frame, consisting of both main and auxiliary columns.
     pub fn next_row(&self) -> &[E] {
-        &self.next_row
+        &self.rows[1]
+    }
+
+    /// Returns the row at the specified position in the frame, consisting of both main and
+    /// auxiliary columns.
+    pub fn row(&self, idx: usize) -> &[E] {
+        &self.rows[idx]
     }
 
     /// Returns the evaluation frame for the main trace
     pub fn main_frame(&self) -> EvaluationFrame<E> {
-        let current = self.current_row[0..self.main_trace_width].to_vec();
-        let next = self.next_row[0..self.main_trace_width].to_vec();
-
-        EvaluationFrame::from_rows(current, next)
+        let rows = self.rows.iter().map(|row| row[..self.main_trace_width].to_vec()).collect();
+        EvaluationFrame::from_rows_with_offsets(rows, self.offsets.clone())
     }
 
     /// Returns the evaluation frame for the auxiliary trace
     pub fn aux_frame(&self) -> Option<EvaluationFrame<E>> {
         if self.has_aux_frame() {
-            let current = self.current_row[self.main_trace_width..].to_vec();
-            let next = self.next_row[self.main_trace_width..].to_vec();
-
-            Some(EvaluationFrame::from_rows(current, next))
+            let rows = self.rows.iter().map(|row| row[self.main_trace_width..].to_vec()).collect();
+            Some(EvaluationFrame::from_rows_with_offsets(rows, self.offsets.clone()))
         } else {
             None
         }
@@ -309,16 +363,18 @@ impl<E: FieldElement> TraceOodFrame<E> {
 
     /// Returns true if an auxiliary frame is present
     fn has_aux_frame(&self) -> bool {
-        self.current_row.len() > self.main_trace_width
+        self.num_columns() > self.main_trace_width
     }
 
     /// Returns the main/aux frame and Lagrange kernel frame as element vectors. Specifically, the
     /// main and auxiliary frames are interleaved, as described in [`OodFrame::set_trace_states`].
     fn to_trace_states(&self) -> (Vec<E>, Vec<E>) {
-        let mut main_and_aux_frame_states = Vec::new();
-        for col in 0..self.current_row.len() {
-            main_and_aux_frame_states.push(self.current_row[col]);
-            main_and_aux_frame_states.push(self.next_row[col]);
+        let mut main_and_aux_frame_states =
+            Vec::with_capacity(self.num_columns() * self.num_rows());
+        for col in 0..self.num_columns() {
+            for row in self.rows.iter() {
+                main_and_aux_frame_states.push(row[col]);
+            }
         }
 
         let lagrange_frame_states = match self.lagrange_kernel_frame {
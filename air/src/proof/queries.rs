@@ -7,6 +7,8 @@ use alloc::vec::Vec;
 
 use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::FieldElement;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
@@ -30,6 +32,7 @@ use super::Table;
 /// retrieve query values and the corresponding Merkle authentication paths,
 /// [parse()](Queries::parse) function should be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Queries {
     paths: Vec<u8>,
     values: Vec<u8>,
@@ -128,6 +131,17 @@ impl Queries {
 
         Ok((merkle_proof, query_values))
     }
+
+    // TRANSFORMATIONS
+    // --------------------------------------------------------------------------------------------
+    /// Applies `f` to the bytes of query values and returns the resulting queries; Merkle
+    /// authentication paths are left unchanged.
+    pub(crate) fn map_values<F>(self, f: F) -> Result<Self, DeserializationError>
+    where
+        F: FnOnce(Vec<u8>) -> Result<Vec<u8>, DeserializationError>,
+    {
+        Ok(Queries { values: f(self.values)?, ..self })
+    }
 }
 
 // SERIALIZATION
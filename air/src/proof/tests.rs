@@ -3,9 +3,165 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Proof;
+use alloc::vec::Vec;
+
+use crypto::{hashers::Blake3_256, BatchMerkleProof};
+use math::{
+    fields::{f62, f64::BaseElement},
+    FieldElement,
+};
+use rand_utils::rand_vector;
+use utils::DeserializationError;
+
+use super::{Context, OodFrame, Proof, Queries, TraceOodFrame};
+use crate::{FieldExtension, ProofOptions, TraceInfo};
 
 #[test]
 pub fn starkproof_new_dummy_doesnt_panic() {
     let _ = Proof::new_dummy();
 }
+
+#[test]
+fn verification_cost() {
+    // 4 columns, 2^10 rows, 32 queries, blowup factor 8, folding factor 4, remainder degree 31
+    let mut proof = Proof::new_dummy();
+    proof.context = Context::new::<BaseElement>(
+        TraceInfo::new(4, 1024),
+        ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
+        [0; 32],
+    );
+
+    // two-row evaluation frame and a single composition column
+    let mut ood_frame = OodFrame::default();
+    let rows = vec![vec![BaseElement::ONE; 4]; 2];
+    let trace_frame = TraceOodFrame::new(rows, vec![0, 1], 4, None);
+    ood_frame.set_trace_states::<_, Blake3_256<BaseElement>>(&trace_frame);
+    ood_frame.set_constraint_evaluations(&[BaseElement::ONE]);
+    proof.ood_frame = ood_frame;
+
+    let cost = proof.verification_cost();
+
+    // the LDE domain has 2^13 elements; FRI layers are folded down to 2^7 elements
+    assert_eq!(&[13, 13, 11, 9, 7], cost.merkle_path_lengths());
+    assert_eq!(32 * 5 + 1, cost.num_leaf_hashes());
+    assert_eq!(32 * 53, cost.num_node_hashes());
+    assert_eq!(cost.num_leaf_hashes() + cost.num_node_hashes(), cost.num_hashes());
+
+    // trace and constraint rows, 3 FRI layers with 4 elements per leaf, and the remainder
+    assert_eq!(32 * 5 + 3 * 32 * 4 + 32, cost.num_hashed_elements());
+
+    // DEEP composition, FRI folding, and remainder evaluation
+    assert_eq!(32 * 31 + 3 * 32 * 20 + 32 * 32, cost.num_field_mults());
+}
+
+#[test]
+fn proof_serialization_versions() {
+    // the dummy proof does not contain queries for its main trace segment
+    let mut proof = Proof::new_dummy();
+    proof.trace_queries.push(proof.constraint_queries.clone());
+    let bytes = proof.to_bytes();
+    assert_eq!(Proof::FORMAT_VERSION, bytes[2]);
+    assert_eq!(proof, Proof::from_bytes(&bytes).unwrap());
+
+    // proofs serialized without the version header cannot be read
+    assert!(matches!(
+        Proof::from_bytes(&bytes[3..]),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+
+    // proofs serialized using an earlier or an unknown format version cannot be read
+    for version in [1, Proof::FORMAT_VERSION + 1] {
+        let mut bytes = bytes.clone();
+        bytes[2] = version;
+        assert_eq!(
+            Err(DeserializationError::UnsupportedVersion(version)),
+            Proof::from_bytes(&bytes)
+        );
+    }
+}
+
+#[test]
+fn proof_compression() {
+    // 64 queries of 8 elements in a 62-bit field, i.e., 2 bits per element can be packed away
+    let mut proof = Proof::new_dummy();
+    proof.context = Context::new::<f62::BaseElement>(
+        TraceInfo::new(8, 1024),
+        ProofOptions::new(64, 8, 0, FieldExtension::None, 4, 31),
+        [0; 32],
+    );
+    let values = (0..64).map(|_| rand_vector::<f62::BaseElement>(8)).collect();
+    let queries = build_queries::<f62::BaseElement>(values);
+    proof.trace_queries = vec![queries.clone()];
+    proof.constraint_queries = queries;
+
+    let compressed = proof.to_bytes_compressed();
+    assert!(compressed.len() < proof.to_bytes().len());
+    assert_eq!(proof, Proof::from_bytes_compressed(&compressed).unwrap());
+
+    // values which do not fit into 62 bits are not packed, but can still be compressed
+    let values = vec![vec![BaseElement::new(u64::MAX >> 1); 8]];
+    proof.trace_queries = vec![build_queries::<BaseElement>(values)];
+    let compressed = proof.to_bytes_compressed();
+    assert_eq!(proof, Proof::from_bytes_compressed(&compressed).unwrap());
+
+    // unknown compression flags are rejected
+    let mut compressed = compressed;
+    compressed[0] = 0b100;
+    assert!(matches!(
+        Proof::from_bytes_compressed(&compressed),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn proof_decompression_limit() {
+    use super::compression::MAX_DECOMPRESSED_SIZE;
+
+    // a few kilobytes of zstd data which decompress into more than the limit are rejected
+    let zeros = vec![0; MAX_DECOMPRESSED_SIZE + 1];
+    let mut compressed = zstd::bulk::compress(&zeros, zstd::DEFAULT_COMPRESSION_LEVEL).unwrap();
+    assert!(compressed.len() < 4096);
+    compressed.insert(0, 0b10);
+    let expected = format!("decompressed proof exceeds {MAX_DECOMPRESSED_SIZE} bytes");
+    assert_eq!(
+        Err(DeserializationError::InvalidValue(expected)),
+        Proof::from_bytes_compressed(&compressed)
+    );
+
+    // data which decompress into exactly the limit pass the size check
+    let mut compressed =
+        zstd::bulk::compress(&zeros[1..], zstd::DEFAULT_COMPRESSION_LEVEL).unwrap();
+    compressed.insert(0, 0b10);
+    assert!(!matches!(
+        Proof::from_bytes_compressed(&compressed),
+        Err(DeserializationError::InvalidValue(msg)) if msg.contains("exceeds")
+    ));
+}
+
+#[test]
+fn proof_text_encoding() {
+    let mut proof = Proof::new_dummy();
+    proof.trace_queries.push(proof.constraint_queries.clone());
+
+    let hex = proof.to_hex();
+    assert_eq!(proof, Proof::from_hex(&hex).unwrap());
+    assert_eq!(proof, Proof::from_hex(&format!("0x{hex}")).unwrap());
+    assert_eq!(proof, Proof::from_base64(&proof.to_base64()).unwrap());
+
+    // malformed encodings are rejected before the proof is parsed
+    assert!(matches!(Proof::from_hex(&hex[1..]), Err(DeserializationError::InvalidValue(_))));
+    assert!(matches!(Proof::from_base64("AAA*"), Err(DeserializationError::InvalidValue(_))));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_queries<E: FieldElement>(values: Vec<Vec<E>>) -> Queries {
+    let merkle_proof = BatchMerkleProof::<Blake3_256<E::BaseField>> {
+        leaves: Vec::new(),
+        nodes: Vec::new(),
+        depth: 0,
+    };
+    Queries::new(merkle_proof, values)
+}
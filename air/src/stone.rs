@@ -0,0 +1,311 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Conversion of proof parameters between Winterfell and the StarkWare Stone (ethSTARK) prover.
+//!
+//! Only protocol parameters can be converted: proofs themselves cannot be translated between the
+//! two stacks. Stone proofs are generated over the 252-bit STARK field (which is not implemented
+//! in Winterfell), and the two provers derive verifier challenges using different Fiat-Shamir
+//! transcripts and commit to different sets of values (e.g., Stone does not commit to the first
+//! FRI layer). Thus, a proof generated by one stack can never be checked by the verifier of the
+//! other, even if it were re-encoded.
+//!
+//! What can be done is to instantiate both stacks with the same FRI and query parameters (e.g., to
+//! compare proof sizes or security levels of equivalent configurations). For this purpose, the
+//! structs in this module mirror the `stark` section of Stone's proof parameters file; when the
+//! `serde` feature is enabled, they can be deserialized from it directly.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{FieldExtension, ProofOptions};
+
+// CONSTANTS
+// ================================================================================================
+
+const MAX_NUM_QUERIES: usize = 255;
+const MAX_LOG_N_COSETS: u32 = 7;
+const MAX_PROOF_OF_WORK_BITS: u32 = 32;
+const MAX_FRI_STEP: u32 = 6;
+const MAX_LAST_LAYER_DEGREE_BOUND: usize = 256;
+
+// STONE PARAMETERS
+// ================================================================================================
+
+/// STARK protocol parameters in the layout of the `stark` section of the Stone prover's proof
+/// parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StoneProofParameters {
+    /// FRI parameters.
+    pub fri: StoneFriParameters,
+    /// Base 2 logarithm of the blowup factor.
+    pub log_n_cosets: u32,
+}
+
+/// FRI parameters in the layout of the `fri` section of the Stone prover's proof parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StoneFriParameters {
+    /// Base 2 logarithms of folding factors applied at each FRI step. The first step must be 0
+    /// since Stone does not commit to the first FRI layer.
+    pub fri_step_list: Vec<u32>,
+    /// Degree bound of the polynomial sent in the last FRI layer.
+    pub last_layer_degree_bound: usize,
+    /// Number of queries.
+    pub n_queries: usize,
+    /// Number of proof-of-work bits required from the query seed.
+    pub proof_of_work_bits: u32,
+}
+
+// STONE PARAMETERS ERROR
+// ================================================================================================
+
+/// Represents an error returned when proof parameters cannot be converted between Winterfell and
+/// Stone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoneParametersError {
+    /// This error occurs when proof options use features which Stone does not support (i.e., a
+    /// field extension or zero-knowledge mode).
+    UnsupportedOptions,
+    /// This error occurs when the number of queries is zero or is greater than 255.
+    UnsupportedNumQueries(usize),
+    /// This error occurs when the blowup factor is smaller than 2 or greater than 128.
+    UnsupportedBlowupFactor(u32),
+    /// This error occurs when the number of proof-of-work bits is greater than 32.
+    UnsupportedGrindingFactor(u32),
+    /// This error occurs when FRI steps cannot be expressed as folding by a single factor
+    /// between 2 and 64 (after the initial step of 0).
+    UnsupportedFriSteps(Vec<u32>),
+    /// This error occurs when the degree bound of the last FRI layer is not a power of two or is
+    /// greater than 256.
+    UnsupportedLastLayerDegreeBound(usize),
+    /// This error occurs when the trace length is not a power of two, or when it is inconsistent
+    /// with the FRI steps and the degree bound of the last FRI layer.
+    InvalidTraceLength(usize),
+}
+
+impl fmt::Display for StoneParametersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedOptions => {
+                write!(f, "field extensions and zero-knowledge proofs are not supported by Stone")
+            },
+            Self::UnsupportedNumQueries(value) => {
+                write!(f, "number of queries must be between 1 and 255, but was {value}")
+            },
+            Self::UnsupportedBlowupFactor(log_n_cosets) => {
+                write!(f, "log_n_cosets must be between 1 and 7, but was {log_n_cosets}")
+            },
+            Self::UnsupportedGrindingFactor(value) => {
+                write!(f, "number of proof-of-work bits cannot be greater than 32, but was {value}")
+            },
+            Self::UnsupportedFriSteps(steps) => {
+                write!(f, "FRI steps {steps:?} must be 0 followed by equal steps between 1 and 6")
+            },
+            Self::UnsupportedLastLayerDegreeBound(value) => {
+                write!(
+                    f,
+                    "last layer degree bound must be a power of two up to 256, but was {value}"
+                )
+            },
+            Self::InvalidTraceLength(value) => {
+                write!(f, "trace length {value} is inconsistent with FRI parameters")
+            },
+        }
+    }
+}
+
+// CONVERSIONS
+// ================================================================================================
+
+impl ProofOptions {
+    /// Returns proof options equivalent to the specified Stone proof parameters for proving
+    /// computations with traces of `trace_length` rows.
+    ///
+    /// The returned options fold FRI layers by the same factor and down to the same degree
+    /// bound as Stone would for the same trace length.
+    ///
+    /// # Errors
+    /// Returns an error if the parameters cannot be expressed using Winterfell proof options
+    /// (e.g., if FRI layers are folded by different factors), or if they are inconsistent with
+    /// `trace_length`.
+    pub fn from_stone_parameters(
+        params: &StoneProofParameters,
+        trace_length: usize,
+    ) -> Result<Self, StoneParametersError> {
+        let fri = &params.fri;
+        if fri.n_queries == 0 || fri.n_queries > MAX_NUM_QUERIES {
+            return Err(StoneParametersError::UnsupportedNumQueries(fri.n_queries));
+        }
+        if params.log_n_cosets == 0 || params.log_n_cosets > MAX_LOG_N_COSETS {
+            return Err(StoneParametersError::UnsupportedBlowupFactor(params.log_n_cosets));
+        }
+        if fri.proof_of_work_bits > MAX_PROOF_OF_WORK_BITS {
+            return Err(StoneParametersError::UnsupportedGrindingFactor(fri.proof_of_work_bits));
+        }
+
+        // the first step must be 0, and all other steps must fold by the same factor
+        let fri_step = match fri.fri_step_list.as_slice() {
+            [0, step, rest @ ..]
+                if (1..=MAX_FRI_STEP).contains(step) && rest.iter().all(|s| s == step) =>
+            {
+                *step
+            },
+            _ => return Err(StoneParametersError::UnsupportedFriSteps(fri.fri_step_list.clone())),
+        };
+
+        let bound = fri.last_layer_degree_bound;
+        if !bound.is_power_of_two() || bound > MAX_LAST_LAYER_DEGREE_BOUND {
+            return Err(StoneParametersError::UnsupportedLastLayerDegreeBound(bound));
+        }
+
+        // Stone requires FRI steps to reduce the trace length exactly to the last layer bound
+        let num_steps = fri.fri_step_list.len() as u32 - 1;
+        if !trace_length.is_power_of_two()
+            || trace_length.ilog2() != bound.ilog2() + num_steps * fri_step
+        {
+            return Err(StoneParametersError::InvalidTraceLength(trace_length));
+        }
+
+        Ok(ProofOptions::new(
+            fri.n_queries,
+            1 << params.log_n_cosets,
+            fri.proof_of_work_bits,
+            FieldExtension::None,
+            1 << fri_step,
+            bound - 1,
+        ))
+    }
+
+    /// Returns Stone proof parameters equivalent to these options for proving computations with
+    /// traces of `trace_length` rows.
+    ///
+    /// The degree bound of the last FRI layer in the returned parameters is the degree bound of
+    /// the FRI remainder which Winterfell produces for `trace_length`; this may be smaller than
+    /// the maximum remainder degree of these options plus one. When the last FRI layer would be
+    /// folded below a constant, the last FRI step is shortened.
+    ///
+    /// # Errors
+    /// Returns an error if these options use a field extension (either for the composition
+    /// polynomial or for out-of-domain sampling) or zero-knowledge mode, neither of which are
+    /// supported by Stone, or if `trace_length` is not a power of two.
+    pub fn to_stone_parameters(
+        &self,
+        trace_length: usize,
+    ) -> Result<StoneProofParameters, StoneParametersError> {
+        if !self.ood_field_extension().is_none() || self.is_zk() {
+            return Err(StoneParametersError::UnsupportedOptions);
+        }
+        if !trace_length.is_power_of_two() {
+            return Err(StoneParametersError::InvalidTraceLength(trace_length));
+        }
+
+        let fri_options = self.to_fri_options();
+        let fri_step = fri_options.folding_factor().ilog2();
+        let num_layers = fri_options.num_fri_layers(trace_length * self.blowup_factor());
+
+        let mut remaining_bits = trace_length.ilog2();
+        let mut fri_step_list = vec![0];
+        for _ in 0..num_layers {
+            let step = fri_step.min(remaining_bits);
+            fri_step_list.push(step);
+            remaining_bits -= step;
+        }
+
+        Ok(StoneProofParameters {
+            fri: StoneFriParameters {
+                fri_step_list,
+                last_layer_degree_bound: 1 << remaining_bits,
+                n_queries: self.num_queries(),
+                proof_of_work_bits: self.grinding_factor(),
+            },
+            log_n_cosets: self.blowup_factor().ilog2(),
+        })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{StoneFriParameters, StoneParametersError, StoneProofParameters};
+    use crate::{FieldExtension, ProofOptions};
+
+    fn build_params(
+        fri_step_list: Vec<u32>,
+        last_layer_degree_bound: usize,
+    ) -> StoneProofParameters {
+        StoneProofParameters {
+            fri: StoneFriParameters {
+                fri_step_list,
+                last_layer_degree_bound,
+                n_queries: 18,
+                proof_of_work_bits: 24,
+            },
+            log_n_cosets: 4,
+        }
+    }
+
+    #[test]
+    fn stone_parameters_round_trip() {
+        // 2^20 rows folded by 8 three times down to a degree bound of 2^11
+        let params = build_params(vec![0, 3, 3, 3], 2048);
+        assert!(matches!(
+            ProofOptions::from_stone_parameters(&params, 1 << 20),
+            Err(StoneParametersError::UnsupportedLastLayerDegreeBound(2048))
+        ));
+
+        // 2^20 rows folded by 16 four times down to a degree bound of 2^4
+        let params = build_params(vec![0, 4, 4, 4, 4], 16);
+        let options = ProofOptions::from_stone_parameters(&params, 1 << 20).unwrap();
+        assert_eq!(ProofOptions::new(18, 16, 24, FieldExtension::None, 16, 15), options);
+        assert_eq!(params, options.to_stone_parameters(1 << 20).unwrap());
+        assert_eq!(4, options.to_fri_options().num_fri_layers((1 << 20) * 16));
+    }
+
+    #[test]
+    fn stone_parameters_uneven_folding() {
+        // folding 2^20 rows by 16 down to at most 128 coefficients overshoots to 16 coefficients
+        let options = ProofOptions::new(18, 16, 24, FieldExtension::None, 16, 127);
+        let params = options.to_stone_parameters(1 << 20).unwrap();
+        assert_eq!(build_params(vec![0, 4, 4, 4, 4], 16), params);
+
+        // the last step is shortened when folding 2^6 rows by 16 down to a constant
+        let options = ProofOptions::new(18, 16, 24, FieldExtension::None, 16, 0);
+        let params = options.to_stone_parameters(1 << 6).unwrap();
+        assert_eq!(build_params(vec![0, 4, 2], 1), params);
+    }
+
+    #[test]
+    fn stone_parameters_unsupported() {
+        let params = build_params(vec![0, 4, 4, 3], 64);
+        assert_eq!(
+            Err(StoneParametersError::UnsupportedFriSteps(vec![0, 4, 4, 3])),
+            ProofOptions::from_stone_parameters(&params, 1 << 17)
+        );
+
+        let params = build_params(vec![2, 4], 64);
+        assert!(ProofOptions::from_stone_parameters(&params, 1 << 12).is_err());
+
+        let params = build_params(vec![0, 4, 4], 64);
+        assert_eq!(
+            Err(StoneParametersError::InvalidTraceLength(1 << 15)),
+            ProofOptions::from_stone_parameters(&params, 1 << 15)
+        );
+
+        let options = ProofOptions::new(18, 16, 24, FieldExtension::Quadratic, 16, 15);
+        assert_eq!(
+            Err(StoneParametersError::UnsupportedOptions),
+            options.to_stone_parameters(1 << 20)
+        );
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use utils::{ByteReader, DeserializationError};
+
+use crate::Hasher;
+
+// VECTOR COMMITMENT TRAIT
+// ================================================================================================
+
+/// A vector commitment (VC) scheme.
+///
+/// This is a cryptographic primitive allowing one to commit, using a commitment string `com`, to
+/// a vector of values (v_0, ..., v_{n-1}) such that one can later reveal the value at the i-th
+/// position.
+///
+/// This is achieved by providing the value `v_i` together with a proof `proof_i` such that
+/// anyone possessing `com` can be convinced, with high confidence, that the claim is true.
+///
+/// Values committed to by implementations of this trait are digests of hash function `H`, and
+/// multiple positions can be opened with a single batch proof. [MerkleTree] is the
+/// default implementation of this trait.
+///
+/// [MerkleTree]: crate::MerkleTree
+pub trait VectorCommitment<H: Hasher>: Sized {
+    /// Batch opening proof of a vector commitment.
+    type BatchProof: Clone;
+
+    /// Error returned by the scheme.
+    type Error: Debug;
+
+    /// Creates a commitment to a vector of values (v_0, ..., v_{n-1}).
+    ///
+    /// # Errors
+    /// Returns an error if the commitment cannot be created for the specified `items` (e.g., the
+    /// number of items is not supported by the scheme).
+    fn commit(items: Vec<H::Digest>) -> Result<Self, Self::Error>;
+
+    /// Returns the commitment string to the committed values.
+    fn commitment(&self) -> H::Digest;
+
+    /// Returns the length of the vector committed to.
+    fn domain_len(&self) -> usize;
+
+    /// Opens the values at the specified `indexes` and returns a single proof for all of them.
+    ///
+    /// # Errors
+    /// Returns an error if the values at the specified `indexes` cannot be opened (e.g., any of
+    /// the indexes is out of bounds).
+    fn open_batch(&self, indexes: &[usize]) -> Result<Self::BatchProof, Self::Error>;
+
+    /// Verifies that the specified `items` are the values at the specified `indexes` of a vector
+    /// committed to by the specified `commitment`.
+    ///
+    /// # Errors
+    /// Returns an error if the `proof` is not valid for the specified `items` and `indexes`.
+    fn verify_batch(
+        commitment: &H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::BatchProof,
+    ) -> Result<(), Self::Error>;
+
+    /// Converts the specified batch `proof` into a vector of bytes.
+    ///
+    /// The opened values are not included in the serialized proof as they are expected to be
+    /// provided separately when the proof is parsed.
+    fn serialize_batch_proof(proof: &Self::BatchProof) -> Vec<u8>;
+
+    /// Reads a batch proof for the specified opened `items` from the `source`; `domain_len`
+    /// specifies the length of the vector the proof was generated for.
+    ///
+    /// # Errors
+    /// Returns an error if a valid batch proof could not be read from the `source`.
+    fn deserialize_batch_proof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+    ) -> Result<Self::BatchProof, DeserializationError>;
+}
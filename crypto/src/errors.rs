@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::string::String;
 use core::fmt;
 
 // MERKLE TREE ERROR
@@ -25,6 +26,10 @@ pub enum MerkleTreeError {
     TooManyLeafIndexes(usize, usize),
     /// Merkle proof is not valid for the specified position(s).
     InvalidProof,
+    /// Arity of a Merkle tree was not a power of two greater than one.
+    InvalidArity(usize),
+    /// Persistent storage backing a Merkle tree could not be accessed or was malformed.
+    StorageError(String),
 }
 
 impl fmt::Display for MerkleTreeError {
@@ -57,6 +62,15 @@ impl fmt::Display for MerkleTreeError {
             Self::InvalidProof => {
                 write!(f, "Merkle proof is invalid")
             },
+            Self::InvalidArity(arity) => {
+                write!(
+                    f,
+                    "Merkle tree arity must be a power of two greater than one, but was {arity}"
+                )
+            },
+            Self::StorageError(err) => {
+                write!(f, "failed to access Merkle tree storage: {err}")
+            },
         }
     }
 }
@@ -0,0 +1,544 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::ops::Range;
+
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+use super::{Digest, ElementHasher, Hasher};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The state is split into two halves (x and y) of 6 elements each; Anemoi S-Boxes are applied
+/// to pairs (x_i, y_i).
+const NUM_COLUMNS: usize = STATE_WIDTH / 2;
+
+/// The rate portion of the state is located in elements 4 through 11.
+const RATE_RANGE: Range<usize> = 4..12;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 4..8;
+const INPUT2_RANGE: Range<usize> = 8..12;
+
+/// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+const CAPACITY_RANGE: Range<usize> = 0..4;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 4, 5, 6, and 7 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 4..8;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of rounds is set to 10 as recommended in <https://eprint.iacr.org/2022/840.pdf>
+/// for 128-bit security level with 6 columns and S-Box degree 7.
+const NUM_ROUNDS: usize = 10;
+
+/// Generator of the multiplicative group of the field used in the S-Box.
+const G: BaseElement = BaseElement::new(7);
+
+/// Inverse of the generator of the multiplicative group of the field used in the S-Box.
+const G_INV: BaseElement = BaseElement::new(2635249152773512046);
+
+/// Inverse of the S-Box power 7 modulo p - 1; used to compute 7-th roots in the S-Box.
+const INV_ALPHA: u64 = 10540996611094048183;
+
+/// S-Box power.
+///
+/// The constant is defined for tests only because the exponentiation in the code is unrolled for
+/// efficiency reasons.
+#[cfg(test)]
+const ALPHA: u64 = 7;
+
+/// First row of the circulant MDS matrix used in the linear layer.
+const MDS_ROW: [u64; NUM_COLUMNS] = [1, 1, 2, 3, 7, 3];
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Anemoi hash function with 256-bit output.
+///
+/// The hash function is implemented according to the Anemoi
+/// [specifications](https://eprint.iacr.org/2022/840.pdf) with the following exceptions:
+/// * When hashing a sequence of elements, we do not pad the sequence with Fp(1) followed by Fp(0)
+///   elements. Instead, we initialize the first capacity element to the number of elements to be
+///   hashed, and pad the sequence with Fp(0) elements only. This ensures consistency of hash
+///   outputs between different hashing methods (see section below). However, it also means that
+///   our instantiation of Anemoi cannot be used in a stream mode as the number of elements to be
+///   hashed must be known upfront.
+/// * We use the sponge construction with the same layout of the state as
+///   [Rp64_256](crate::hashers::Rp64_256): the first 4 elements of the state are used for
+///   capacity and the remaining 8 elements for rate. The output of the hash function comes from
+///   the first four elements of the rate portion of the state (elements 4, 5, 6, and 7).
+///
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements (6 columns).
+/// * Capacity size: 4 field elements.
+/// * Number of rounds: 10.
+/// * S-Box: open Flystel with power 7 and quadratic functions g * x^2 and g * x^2 + g^-1, where
+///   g = 7 is the generator of the multiplicative group of the field.
+/// * Linear layer: 6x6 circulant MDS matrix with the first row [1, 1, 2, 3, 7, 3].
+///
+/// Round constants were generated from the digits of pi in the same way as in the Anemoi
+/// specifications.
+///
+/// The above parameters target 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits).
+///
+/// Anemoi requires fewer rounds than Rescue Prime, and its S-Box can be verified using only low
+/// degree constraints. Thus, it is cheaper to express as an arithmetic circuit, which makes it
+/// well-suited for recursive proof verification.
+///
+/// ## Hash output consistency
+/// Functions [hash_elements()](Anemoi64_256::hash_elements), [merge()](Anemoi64_256::merge), and
+/// [merge_with_int()](Anemoi64_256::merge_with_int) are internally consistent. That is, computing
+/// a hash for the same set of elements using these functions will always produce the same
+/// result. For example, merging two digests using [merge()](Anemoi64_256::merge) will produce the
+/// same result as hashing 8 elements which make up these digests using
+/// [hash_elements()](Anemoi64_256::hash_elements) function.
+///
+/// However, [hash()](Anemoi64_256::hash) function is not consistent with functions mentioned
+/// above. For example, if we take two field elements, serialize them to bytes and hash them using
+/// [hash()](Anemoi64_256::hash), the result will differ from the result obtained by hashing these
+/// elements directly using [hash_elements()](Anemoi64_256::hash_elements) function. The reason
+/// for this difference is that [hash()](Anemoi64_256::hash) function needs to be able to handle
+/// arbitrary binary strings, which may or may not encode valid field elements - and thus,
+/// deserialization procedure used by this function is different from the procedure used to
+/// deserialize valid field elements.
+pub struct Anemoi64_256();
+
+impl Hasher for Anemoi64_256 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % 7 == 0 {
+            bytes.len() / 7
+        } else {
+            bytes.len() / 7 + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(num_elements as u64);
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state. we use 7-byte chunks because
+        // every 7-byte chunk is guaranteed to map to some field element.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for chunk in bytes.chunks(7) {
+            if i < num_elements - 1 {
+                buf[..7].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state; if the rate is filled up, apply the Anemoi permutation and start absorbing
+            // again from zero index.
+            state[RATE_RANGE.start + i] += BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Anemoi permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements), and set the first capacity element to 8 (the number of elements to
+        // be hashed).
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+        state[CAPACITY_RANGE.start] = BaseElement::new(RATE_WIDTH as u64);
+
+        // apply the Anemoi permutation and return the first four elements of the state
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::hash_elements(Self::Digest::digests_as_elements(values))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and set the first capacity element to 5 (the number of elements to be hashed).
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 5 and 6, and set the first capacity element
+        //   to 6.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 1);
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 2);
+        }
+
+        // apply the Anemoi permutation and return the first four elements of the state
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Anemoi64_256 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // initialize state to all zeros, except for the last element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(elements.len() as u64);
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the Anemoi permutation and start absorbing again; repeat until all
+        // elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Anemoi permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Anemoi64_256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 10.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 4 through 11 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 4, 5, 6, and 7.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Round constants added to the x half of the state at the beginning of every Anemoi round.
+    pub const ARK1: [[BaseElement; NUM_COLUMNS]; NUM_ROUNDS] = ARK1;
+
+    /// Round constants added to the y half of the state at the beginning of every Anemoi round.
+    pub const ARK2: [[BaseElement; NUM_COLUMNS]; NUM_ROUNDS] = ARK2;
+
+    // ANEMOI PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Anemoi permutation to the provided state.
+    ///
+    /// The first 6 elements of the state are treated as the x half and the remaining 6 elements
+    /// as the y half of the state. After all rounds are applied, the linear layer is applied one
+    /// more time.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        for i in 0..NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+        Self::apply_linear_layer(state);
+    }
+
+    /// Anemoi round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::add_constants(state, round);
+        Self::apply_linear_layer(state);
+        Self::apply_sbox(state);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[inline(always)]
+    fn add_constants(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        let (x, y) = state.split_at_mut(NUM_COLUMNS);
+        x.iter_mut().zip(ARK1[round]).for_each(|(s, k)| *s += k);
+        y.iter_mut().zip(ARK2[round]).for_each(|(s, k)| *s += k);
+    }
+
+    /// Multiplies the x half of the state by the MDS matrix, multiplies the y half of the state
+    /// rotated by one element by the MDS matrix, and then mixes the two halves using the
+    /// pseudo-Hadamard transform.
+    #[inline(always)]
+    fn apply_linear_layer(state: &mut [BaseElement; STATE_WIDTH]) {
+        let (x, y) = state.split_at_mut(NUM_COLUMNS);
+        x.copy_from_slice(&mds_multiply(x));
+        y.rotate_left(1);
+        y.copy_from_slice(&mds_multiply(y));
+
+        for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+            *y += *x;
+            *x += *y;
+        }
+    }
+
+    /// Applies the open Flystel S-Box to every column (x_i, y_i) of the state.
+    #[inline(always)]
+    fn apply_sbox(state: &mut [BaseElement; STATE_WIDTH]) {
+        let (x, y) = state.split_at_mut(NUM_COLUMNS);
+        for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+            *x -= G * y.square();
+            *y -= x.exp(INV_ALPHA);
+            *x += G * y.square() + G_INV;
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Multiplies the specified vector by the circulant MDS matrix defined by [MDS_ROW].
+#[inline(always)]
+fn mds_multiply(v: &[BaseElement]) -> [BaseElement; NUM_COLUMNS] {
+    let mut result = [BaseElement::ZERO; NUM_COLUMNS];
+    for (i, r) in result.iter_mut().enumerate() {
+        for (j, &value) in v.iter().enumerate() {
+            *r += BaseElement::new(MDS_ROW[(j + NUM_COLUMNS - i) % NUM_COLUMNS]) * value;
+        }
+    }
+    result
+}
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Anemoi round constants for the x half of the state; computed as
+/// C_r^i = g * (pi_0^r)^2 + (pi_0^r + pi_1^i)^7, where pi_0 = 1415926535 and pi_1 = 8979323846.
+const ARK1: [[BaseElement; NUM_COLUMNS]; NUM_ROUNDS] = [
+    [
+        BaseElement::new(135),
+        BaseElement::new(12616817002499211280),
+        BaseElement::new(10322855620882634095),
+        BaseElement::new(11515394017209471433),
+        BaseElement::new(3746999555209320802),
+        BaseElement::new(143073978418608185),
+    ],
+    [
+        BaseElement::new(4589240930789969862),
+        BaseElement::new(16618325948943106831),
+        BaseElement::new(18200144109916626466),
+        BaseElement::new(10404409773164030300),
+        BaseElement::new(3786652616941072450),
+        BaseElement::new(2325333986555565815),
+    ],
+    [
+        BaseElement::new(1056806635938363454),
+        BaseElement::new(12032802661514536002),
+        BaseElement::new(10002665097632248002),
+        BaseElement::new(6412252127398148956),
+        BaseElement::new(2062742140415357773),
+        BaseElement::new(13811752644010672623),
+    ],
+    [
+        BaseElement::new(2033339464122141606),
+        BaseElement::new(16651387481995968952),
+        BaseElement::new(4985598636326750382),
+        BaseElement::new(16478276872291823677),
+        BaseElement::new(14384006055097878095),
+        BaseElement::new(8319774533786875103),
+    ],
+    [
+        BaseElement::new(17998547505995654386),
+        BaseElement::new(6416007608347979543),
+        BaseElement::new(3759814826981602401),
+        BaseElement::new(13568539573461218236),
+        BaseElement::new(6268711879508969561),
+        BaseElement::new(17709508224681198724),
+    ],
+    [
+        BaseElement::new(6248231951858815273),
+        BaseElement::new(10075988513384689706),
+        BaseElement::new(8639551545635388466),
+        BaseElement::new(6349442195028952653),
+        BaseElement::new(2284691932698573383),
+        BaseElement::new(80095633336970286),
+    ],
+    [
+        BaseElement::new(6373743027521678262),
+        BaseElement::new(15640457269987185203),
+        BaseElement::new(7059777190151104864),
+        BaseElement::new(16234292380744713453),
+        BaseElement::new(16536908235571724708),
+        BaseElement::new(14007655168542425928),
+    ],
+    [
+        BaseElement::new(9496862436573416816),
+        BaseElement::new(8021320825921566955),
+        BaseElement::new(10011109434848134153),
+        BaseElement::new(15157571183736724529),
+        BaseElement::new(16756427443317826452),
+        BaseElement::new(14621961295244070287),
+    ],
+    [
+        BaseElement::new(4953972297419589317),
+        BaseElement::new(15709647264193197289),
+        BaseElement::new(1063287896505862446),
+        BaseElement::new(1284682883009552102),
+        BaseElement::new(14014691689355300239),
+        BaseElement::new(3512172991145861125),
+    ],
+    [
+        BaseElement::new(12577582626570549900),
+        BaseElement::new(2955264990711345269),
+        BaseElement::new(8561611934416731227),
+        BaseElement::new(5067004547994152727),
+        BaseElement::new(13706913035289842841),
+        BaseElement::new(18071271233329781753),
+    ],
+];
+
+/// Anemoi round constants for the y half of the state; computed as
+/// D_r^i = g * (pi_1^i)^2 + (pi_0^r + pi_1^i)^7 + g^-1, where pi_0 = 1415926535 and
+/// pi_1 = 8979323846.
+const ARK2: [[BaseElement; NUM_COLUMNS]; NUM_ROUNDS] = [
+    [
+        BaseElement::new(2635249152773512181),
+        BaseElement::new(7800797122830231380),
+        BaseElement::new(9653037688956642187),
+        BaseElement::new(3310491136445819131),
+        BaseElement::new(12456830745844116195),
+        BaseElement::new(12867022051863415039),
+    ],
+    [
+        BaseElement::new(11637298485358322661),
+        BaseElement::new(16215114471068967684),
+        BaseElement::new(3496390510370890990),
+        BaseElement::new(6612315294195218751),
+        BaseElement::new(16909292209370708596),
+        BaseElement::new(1015346392380629101),
+    ],
+    [
+        BaseElement::new(9725267137655892445),
+        BaseElement::new(13249994130789573047),
+        BaseElement::new(15366058514650273039),
+        BaseElement::new(4240560595578513599),
+        BaseElement::new(16805784679994170111),
+        BaseElement::new(14122167996984912101),
+    ],
+    [
+        BaseElement::new(15778423628933702095),
+        BaseElement::new(4498458544950453174),
+        BaseElement::new(15425615716438806917),
+        BaseElement::new(936464934151635497),
+        BaseElement::new(15756928188356137610),
+        BaseElement::new(13706813549855146079),
+    ],
+    [
+        BaseElement::new(15842802120403555692),
+        BaseElement::new(15255737259727973224),
+        BaseElement::new(16745746426104584074),
+        BaseElement::new(572642154331955194),
+        BaseElement::new(10187548531778154214),
+        BaseElement::new(7195717690345810517),
+    ],
+    [
+        BaseElement::new(1174110789228001021),
+        BaseElement::new(15997342387725967829),
+        BaseElement::new(260363298305070260),
+        BaseElement::new(8881913068275558374),
+        BaseElement::new(3285152807929042478),
+        BaseElement::new(5094673391377450842),
+    ],
+    [
+        BaseElement::new(14790259923157830657),
+        BaseElement::new(16605705133180845652),
+        BaseElement::new(12171227001087753305),
+        BaseElement::new(13810657242843701500),
+        BaseElement::new(12581263099654576129),
+        BaseElement::new(14066126915435288810),
+    ],
+    [
+        BaseElement::new(331279510332425244),
+        BaseElement::new(9851212936652667758),
+        BaseElement::new(15987203493322222948),
+        BaseElement::new(13598580293373152930),
+        BaseElement::new(13665426554938118227),
+        BaseElement::new(15545077289674373523),
+    ],
+    [
+        BaseElement::new(16113664442313208359),
+        BaseElement::new(971326307229740064),
+        BaseElement::new(8917912956699977534),
+        BaseElement::new(1604222994366006796),
+        BaseElement::new(12802221802695618307),
+        BaseElement::new(6313819987296190654),
+    ],
+    [
+        BaseElement::new(7461308516502810908),
+        BaseElement::new(8834465917615698652),
+        BaseElement::new(140270739649488281),
+        BaseElement::new(7557322473803833708),
+        BaseElement::new(14665220963083387196),
+        BaseElement::new(4596951974518753248),
+    ],
+];
@@ -0,0 +1,145 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use rand_utils::{rand_array, rand_value};
+
+use super::{
+    Anemoi64_256, BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, StarkField,
+    ALPHA, G, G_INV, INV_ALPHA, NUM_COLUMNS, STATE_WIDTH,
+};
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    // the S-Box must be invertible; compute the closed Flystel on the output of the open Flystel
+    // and make sure the original state is recovered
+    let mut actual = state;
+    Anemoi64_256::apply_sbox(&mut actual);
+    for i in 0..NUM_COLUMNS {
+        let (mut x, mut y) = (actual[i], actual[i + NUM_COLUMNS]);
+        x -= G * y.square() + G_INV;
+        y += x.exp(INV_ALPHA);
+        x += G * y.square();
+        assert_eq!(state[i], x);
+        assert_eq!(state[i + NUM_COLUMNS], y);
+    }
+
+    // the 7-th root computed in the S-Box must be consistent with the S-Box power
+    let value: BaseElement = rand_value();
+    assert_eq!(value, value.exp(INV_ALPHA).exp(ALPHA));
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+        BaseElement::new(8),
+        BaseElement::new(9),
+        BaseElement::new(10),
+        BaseElement::new(11),
+    ];
+
+    Anemoi64_256::apply_permutation(&mut state);
+
+    // expected values are obtained by executing an independent Python implementation of the
+    // permutation with the same round constants and MDS matrix
+    let expected = vec![
+        BaseElement::new(7724522154561592633),
+        BaseElement::new(14572109535602352126),
+        BaseElement::new(12916694200165362686),
+        BaseElement::new(6238291660000480331),
+        BaseElement::new(379336945447698621),
+        BaseElement::new(9347249976874144407),
+        BaseElement::new(9197167150621423103),
+        BaseElement::new(1060371705346816547),
+        BaseElement::new(9694850766622131308),
+        BaseElement::new(3996401080147682952),
+        BaseElement::new(5010418990824134502),
+        BaseElement::new(16263597193007456589),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Anemoi64_256::merge(&digests);
+    let h_result = Anemoi64_256::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Anemoi64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Anemoi64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Anemoi64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Anemoi64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Anemoi64_256::hash(&[1_u8, 2, 3]);
+    let r2 = Anemoi64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Anemoi64_256::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = Anemoi64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Anemoi64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Anemoi64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Anemoi64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Anemoi64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Anemoi64_256::hash_elements(&e1);
+    let r2 = Anemoi64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
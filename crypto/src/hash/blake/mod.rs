@@ -8,7 +8,7 @@ use core::{fmt::Debug, marker::PhantomData};
 use math::{FieldElement, StarkField};
 use utils::ByteWriter;
 
-use super::{ByteDigest, ElementHasher, Hasher};
+use super::{ByteDigest, ElementHasher, HashFunction, Hasher};
 
 #[cfg(test)]
 mod tests;
@@ -25,6 +25,7 @@ impl<B: StarkField> Hasher for Blake3_256<B> {
     type Digest = ByteDigest<32>;
 
     const COLLISION_RESISTANCE: u32 = 128;
+    const HASH_FUNCTION: Option<HashFunction> = Some(HashFunction::Blake3_256);
 
     fn hash(bytes: &[u8]) -> Self::Digest {
         ByteDigest(*blake3::hash(bytes).as_bytes())
@@ -73,6 +74,7 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
     type Digest = ByteDigest<24>;
 
     const COLLISION_RESISTANCE: u32 = 96;
+    const HASH_FUNCTION: Option<HashFunction> = Some(HashFunction::Blake3_192);
 
     fn hash(bytes: &[u8]) -> Self::Digest {
         let result = blake3::hash(bytes);
@@ -115,6 +117,60 @@ impl<B: StarkField> ElementHasher for Blake3_192<B> {
     }
 }
 
+// BLAKE3 160-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for BLAKE3 hash function with 160-bit
+/// output.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blake3_160<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Blake3_160<B> {
+    type Digest = ByteDigest<20>;
+
+    const COLLISION_RESISTANCE: u32 = 80;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        let result = blake3::hash(bytes);
+        ByteDigest(result.as_bytes()[..20].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let result = blake3::hash(ByteDigest::digests_as_bytes(values));
+        ByteDigest(result.as_bytes()[..20].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 28];
+        data[..20].copy_from_slice(&seed.0);
+        data[20..].copy_from_slice(&value.to_le_bytes());
+
+        let result = blake3::hash(&data);
+        ByteDigest(result.as_bytes()[..20].try_into().unwrap())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Blake3_160<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            let result = blake3::hash(bytes);
+            ByteDigest(result.as_bytes()[..20].try_into().unwrap())
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = BlakeHasher::new();
+            hasher.write_many(elements);
+            let result = hasher.finalize();
+            ByteDigest(result[..20].try_into().unwrap())
+        }
+    }
+}
+
 // BLAKE HASHER
 // ================================================================================================
 
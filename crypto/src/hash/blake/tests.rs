@@ -3,10 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::vec::Vec;
+
 use math::{fields::f62::BaseElement, FieldElement};
 use rand_utils::rand_array;
 
-use super::{Blake3_256, ElementHasher, Hasher};
+use super::{Blake3_160, Blake3_192, Blake3_256, ElementHasher, Hasher};
+use crate::{DefaultRandomCoin, MerkleTree, RandomCoin};
 
 #[test]
 fn hash_padding() {
@@ -29,3 +32,36 @@ fn hash_elements_padding() {
     let r2 = Blake3_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn hash_truncation() {
+    let bytes = [1_u8, 2, 3];
+    let r256 = Blake3_256::<BaseElement>::hash(&bytes);
+
+    // shorter digests are prefixes of the 256-bit digest
+    assert_eq!(r256.0[..24], Blake3_192::<BaseElement>::hash(&bytes).0);
+    assert_eq!(r256.0[..20], Blake3_160::<BaseElement>::hash(&bytes).0);
+
+    // merging with an integer should result in different digests for different integers
+    let seed = Blake3_160::<BaseElement>::hash(&bytes);
+    assert_ne!(
+        Blake3_160::<BaseElement>::merge_with_int(seed, 1),
+        Blake3_160::<BaseElement>::merge_with_int(seed, 2)
+    );
+}
+
+#[test]
+fn merkle_tree_and_random_coin_160() {
+    type Blake3 = Blake3_160<BaseElement>;
+
+    let leaves = (0..8u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3>::new(leaves).unwrap();
+    let proof = tree.prove_batch(&[1, 6]).unwrap();
+    assert!(MerkleTree::<Blake3>::verify_batch(tree.root(), &[1, 6], &proof).is_ok());
+
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[BaseElement::ONE]);
+    coin.reseed(*tree.root());
+    let value1: BaseElement = coin.draw().unwrap();
+    let value2: BaseElement = coin.draw().unwrap();
+    assert_ne!(value1, value2);
+}
@@ -0,0 +1,157 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+use math::{
+    fields::{f128, f31, f62, f64, m31},
+    StarkField,
+};
+
+use super::{
+    Blake3_192, Blake3_256, ElementHasher, Hasher, Poseidon64_256, Rp64_256, RpJive64_256, Sha3_256,
+};
+
+// HASH FUNCTION
+// ================================================================================================
+
+/// Defines a set of hash functions which can be selected at runtime.
+///
+/// Hash functions are usually specified via generic parameters, and thus, need to be known at
+/// compile time. This enum, together with [HashFunctionField], makes it possible to pick one of
+/// the supported hash functions based on runtime data (e.g., a configuration file or a request),
+/// while the code for each hash function is still generated via static dispatch.
+///
+/// Some hash functions are defined only over specific base fields; these are noted below.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HashFunction {
+    /// BLAKE3 hash function with 192 bit output.
+    Blake3_192 = 0,
+    /// BLAKE3 hash function with 256 bit output.
+    Blake3_256 = 1,
+    /// SHA3 hash function with 256 bit output.
+    Sha3_256 = 2,
+    /// Rescue Prime hash function with 256 bit output. It only works in `f64` field.
+    Rp64_256 = 3,
+    /// Rescue Prime hash function with 256 bit output using the Jive compression mode in Merkle
+    /// trees. It only works in `f64` field.
+    RpJive64_256 = 4,
+    /// Poseidon hash function with 256 bit output. It only works in `f64` field.
+    Poseidon64_256 = 5,
+}
+
+impl HashFunction {
+    /// Returns collision resistance of this hash function measured in bits.
+    ///
+    /// When this hash function is used in the STARK protocol, proof security cannot exceed this
+    /// value.
+    pub fn collision_resistance(&self) -> u32 {
+        match self {
+            Self::Blake3_192 => Blake3_192::<f64::BaseElement>::COLLISION_RESISTANCE,
+            Self::Blake3_256 => Blake3_256::<f64::BaseElement>::COLLISION_RESISTANCE,
+            Self::Sha3_256 => Sha3_256::<f64::BaseElement>::COLLISION_RESISTANCE,
+            Self::Rp64_256 => Rp64_256::COLLISION_RESISTANCE,
+            Self::RpJive64_256 => RpJive64_256::COLLISION_RESISTANCE,
+            Self::Poseidon64_256 => Poseidon64_256::COLLISION_RESISTANCE,
+        }
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Blake3_192 => write!(f, "blake3_192"),
+            Self::Blake3_256 => write!(f, "blake3_256"),
+            Self::Sha3_256 => write!(f, "sha3_256"),
+            Self::Rp64_256 => write!(f, "rp64_256"),
+            Self::RpJive64_256 => write!(f, "rp_jive64_256"),
+            Self::Poseidon64_256 => write!(f, "poseidon64_256"),
+        }
+    }
+}
+
+// HASH FUNCTION DISPATCH
+// ================================================================================================
+
+/// Defines an operation which is generic over a hash function with elements in field `B`.
+///
+/// Implementations of this trait are passed to [HashFunctionField::dispatch()], which invokes
+/// [visit()](HashFunctionVisitor::visit) instantiated with the hash function selected at
+/// runtime.
+pub trait HashFunctionVisitor<B: StarkField> {
+    /// Result of the operation.
+    type Output;
+
+    /// Executes the operation using hash function `H`.
+    fn visit<H: ElementHasher<BaseField = B>>(self) -> Self::Output;
+}
+
+/// Defines a base field over which hash functions listed in [HashFunction] can be selected at
+/// runtime.
+pub trait HashFunctionField: StarkField {
+    /// Executes the operation defined by `visitor` using the hasher specified by `hash_fn`.
+    ///
+    /// Returns `None` if the specified hash function is not defined over this field.
+    fn dispatch<V: HashFunctionVisitor<Self>>(
+        hash_fn: HashFunction,
+        visitor: V,
+    ) -> Option<V::Output>;
+}
+
+impl HashFunctionField for f64::BaseElement {
+    fn dispatch<V: HashFunctionVisitor<Self>>(
+        hash_fn: HashFunction,
+        visitor: V,
+    ) -> Option<V::Output> {
+        let result = match hash_fn {
+            HashFunction::Blake3_192 => visitor.visit::<Blake3_192<Self>>(),
+            HashFunction::Blake3_256 => visitor.visit::<Blake3_256<Self>>(),
+            HashFunction::Sha3_256 => visitor.visit::<Sha3_256<Self>>(),
+            HashFunction::Rp64_256 => visitor.visit::<Rp64_256>(),
+            HashFunction::RpJive64_256 => visitor.visit::<RpJive64_256>(),
+            HashFunction::Poseidon64_256 => visitor.visit::<Poseidon64_256>(),
+        };
+        Some(result)
+    }
+}
+
+/// Implements [HashFunctionField] for fields over which only byte-oriented hash functions are
+/// defined.
+macro_rules! impl_byte_hash_function_field {
+    ($($field:ty),*) => {
+        $(
+            impl HashFunctionField for $field {
+                fn dispatch<V: HashFunctionVisitor<Self>>(
+                    hash_fn: HashFunction,
+                    visitor: V,
+                ) -> Option<V::Output> {
+                    match hash_fn {
+                        HashFunction::Blake3_192 => {
+                            Some(visitor.visit::<Blake3_192<Self>>())
+                        },
+                        HashFunction::Blake3_256 => {
+                            Some(visitor.visit::<Blake3_256<Self>>())
+                        },
+                        HashFunction::Sha3_256 => {
+                            Some(visitor.visit::<Sha3_256<Self>>())
+                        },
+                        HashFunction::Rp64_256
+                        | HashFunction::RpJive64_256
+                        | HashFunction::Poseidon64_256 => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_hash_function_field!(
+    f128::BaseElement,
+    f128::montgomery::BaseElement,
+    f62::BaseElement,
+    f31::BaseElement,
+    m31::BaseElement
+);
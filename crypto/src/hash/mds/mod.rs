@@ -5,6 +5,8 @@
 
 //! An implementation of matrix-vector products for several MDS matrices.
 
+#[allow(clippy::empty_line_after_doc_comments)]
 pub mod mds_f64_8x8;
 
+#[allow(clippy::empty_line_after_doc_comments)]
 pub mod mds_f64_12x12;
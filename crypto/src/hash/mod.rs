@@ -3,22 +3,32 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::vec::Vec;
 use core::{fmt::Debug, slice};
 
 use math::{FieldElement, StarkField};
 use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
 
 mod blake;
-pub use blake::{Blake3_192, Blake3_256};
+pub use blake::{Blake3_160, Blake3_192, Blake3_256};
 
 mod sha;
-pub use sha::Sha3_256;
+pub use sha::{Keccak256, Sha3_256};
 
 mod mds;
 
 mod rescue;
 pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
 
+mod poseidon;
+pub use poseidon::Poseidon64_256;
+
+mod anemoi;
+pub use anemoi::Anemoi64_256;
+
+mod function;
+pub use function::{HashFunction, HashFunctionField, HashFunctionVisitor};
+
 // HASHER TRAITS
 // ================================================================================================
 
@@ -27,6 +37,7 @@ pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
 /// This trait defines hash procedures for the following inputs:
 /// * A sequence of bytes.
 /// * Two digests - this is intended for use in Merkle tree constructions.
+/// * Many digests - this is intended for use in Merkle trees with arity greater than two.
 /// * A digests and a u64 value - this intended for use in PRNG or PoW contexts.
 pub trait Hasher {
     /// Specifies a digest type returned by this hasher.
@@ -35,6 +46,10 @@ pub trait Hasher {
     /// Collision resistance of the hash function measured in bits.
     const COLLISION_RESISTANCE: u32;
 
+    /// Identifier of this hash function among the hash functions which can be selected at
+    /// runtime, or None if this hash function cannot be selected at runtime.
+    const HASH_FUNCTION: Option<HashFunction> = None;
+
     /// Returns a hash of the provided sequence of bytes.
     fn hash(bytes: &[u8]) -> Self::Digest;
 
@@ -42,6 +57,16 @@ pub trait Hasher {
     /// Merkle trees.
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest;
 
+    /// Returns a hash of an arbitrary number of digests. This method is intended for use in
+    /// construction of Merkle trees with arity greater than two.
+    ///
+    /// The default implementation hashes a concatenation of byte representations of the digests;
+    /// hash functions which can absorb digests natively should override it.
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        let bytes: Vec<u8> = values.iter().flat_map(|value| value.as_bytes()).collect();
+        Self::hash(&bytes)
+    }
+
     /// Returns hash(`seed` || `value`). This method is intended for use in PRNG and PoW contexts.
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest;
 }
@@ -0,0 +1,179 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use rand_utils::{rand_array, rand_value};
+
+use super::{
+    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Poseidon64_256, StarkField,
+    ALPHA, STATE_WIDTH,
+};
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected.iter_mut().for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    Poseidon64_256::apply_sbox(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+        BaseElement::new(8),
+        BaseElement::new(9),
+        BaseElement::new(10),
+        BaseElement::new(11),
+    ];
+
+    Poseidon64_256::apply_permutation(&mut state);
+
+    // expected values are obtained by executing an independent Python implementation of the
+    // permutation with the same round constants and MDS matrix
+    let expected = vec![
+        BaseElement::new(4641002866549900661),
+        BaseElement::new(13020144589501907268),
+        BaseElement::new(9291923113014312323),
+        BaseElement::new(2052087091727655297),
+        BaseElement::new(8475168048298067631),
+        BaseElement::new(14012581593613805083),
+        BaseElement::new(3416906160983159606),
+        BaseElement::new(10332525608450155989),
+        BaseElement::new(2098120237155716416),
+        BaseElement::new(17923667669790388350),
+        BaseElement::new(8429766778227274511),
+        BaseElement::new(15580399571007166126),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Poseidon64_256::merge(&digests);
+    let h_result = Poseidon64_256::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Poseidon64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Poseidon64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Poseidon64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Poseidon64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Poseidon64_256::hash(&[1_u8, 2, 3]);
+    let r2 = Poseidon64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Poseidon64_256::hash_elements(&e1);
+    let r2 = Poseidon64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn mds_has_no_inactive_subspace_trails() {
+    // column i of the matrix contains the first element of the state after applying the MDS
+    // matrix k times (for k in 0..12) to the i-th unit vector; if the matrix has full rank, no
+    // non-zero state difference leaves the input of the partial round S-Box unchanged for 12
+    // consecutive rounds
+    let mut matrix = [[BaseElement::ZERO; STATE_WIDTH]; STATE_WIDTH];
+    for i in 0..STATE_WIDTH {
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[i] = BaseElement::ONE;
+        for row in matrix.iter_mut() {
+            row[i] = state[0];
+            Poseidon64_256::apply_mds(&mut state);
+        }
+    }
+    assert_eq!(STATE_WIDTH, rank(matrix));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the rank of the provided matrix computed via Gaussian elimination.
+fn rank(mut matrix: [[BaseElement; STATE_WIDTH]; STATE_WIDTH]) -> usize {
+    let mut rank = 0;
+    for col in 0..STATE_WIDTH {
+        let Some(pivot) = (rank..STATE_WIDTH).find(|&i| matrix[i][col] != BaseElement::ZERO) else {
+            continue;
+        };
+        matrix.swap(rank, pivot);
+        let inv = matrix[rank][col].inv();
+        let pivot_row = matrix[rank].map(|v| v * inv);
+        for (i, row) in matrix.iter_mut().enumerate() {
+            if i != rank {
+                let factor = row[col];
+                row.iter_mut().zip(pivot_row).for_each(|(v, p)| *v -= factor * p);
+            }
+        }
+        matrix[rank] = pivot_row;
+        rank += 1;
+    }
+    rank
+}
@@ -7,7 +7,10 @@ use core::ops::Range;
 
 use math::{fields::f64::BaseElement, FieldElement, StarkField};
 
-use super::{super::mds::mds_f64_12x12::mds_multiply, exp_acc, Digest, ElementHasher, Hasher};
+use super::{
+    super::{mds::mds_f64_12x12::mds_multiply, HashFunction},
+    exp_acc, Digest, ElementHasher, Hasher,
+};
 
 mod digest;
 pub use digest::ElementDigest;
@@ -119,6 +122,7 @@ impl Hasher for Rp64_256 {
     type Digest = ElementDigest;
 
     const COLLISION_RESISTANCE: u32 = 128;
+    const HASH_FUNCTION: Option<HashFunction> = Some(HashFunction::Rp64_256);
 
     fn hash(bytes: &[u8]) -> Self::Digest {
         // compute the number of elements required to represent the string; we will be processing
@@ -191,6 +195,10 @@ impl Hasher for Rp64_256 {
         ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::hash_elements(Self::Digest::digests_as_elements(values))
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         // initialize the state as follows:
         // - seed is copied into the first 4 elements of the rate portion of the state.
@@ -7,7 +7,10 @@ use core::ops::Range;
 
 use math::{fields::f64::BaseElement, FieldElement, StarkField};
 
-use super::{super::mds::mds_f64_8x8::mds_multiply, exp_acc, Digest, ElementHasher, Hasher};
+use super::{
+    super::{mds::mds_f64_8x8::mds_multiply, HashFunction},
+    exp_acc, Digest, ElementHasher, Hasher,
+};
 
 mod digest;
 pub use digest::ElementDigest;
@@ -115,6 +118,7 @@ impl Hasher for RpJive64_256 {
     type Digest = ElementDigest;
 
     const COLLISION_RESISTANCE: u32 = 128;
+    const HASH_FUNCTION: Option<HashFunction> = Some(HashFunction::RpJive64_256);
 
     fn hash(bytes: &[u8]) -> Self::Digest {
         // compute the number of elements required to represent the string; we will be processing
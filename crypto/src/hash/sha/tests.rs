@@ -0,0 +1,56 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::{fields::f62::BaseElement, FieldElement};
+use rand_utils::rand_array;
+
+use super::{ElementHasher, Hasher, Keccak256, Sha3_256};
+
+#[test]
+fn sha3_hash() {
+    // SHA3-256 hash of an empty string, as specified in FIPS 202 test vectors
+    let expected = [
+        0xa7, 0xff, 0xc6, 0xf8, 0xbf, 0x1e, 0xd7, 0x66, 0x51, 0xc1, 0x47, 0x56, 0xa0, 0x61, 0xd6,
+        0x62, 0xf5, 0x80, 0xff, 0x4d, 0xe4, 0x3b, 0x49, 0xfa, 0x82, 0xd8, 0x0a, 0x4b, 0x80, 0xf8,
+        0x43, 0x4a,
+    ];
+    assert_eq!(expected, Sha3_256::<BaseElement>::hash(&[]).0);
+}
+
+#[test]
+fn sha3_hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let r1 = Sha3_256::hash_elements(&e1);
+    let r2 = Sha3_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn keccak_hash() {
+    // Keccak-256 hash of an empty string, as computed by the EVM
+    let expected = [
+        0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03,
+        0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85,
+        0xa4, 0x70,
+    ];
+    assert_eq!(expected, Keccak256::<BaseElement>::hash(&[]).0);
+
+    // Keccak-256 and SHA3-256 differ only in padding, but that is enough to make outputs differ
+    assert_ne!(Keccak256::<BaseElement>::hash(&[]).0, Sha3_256::<BaseElement>::hash(&[]).0);
+}
+
+#[test]
+fn keccak_hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let r1 = Keccak256::hash_elements(&e1);
+    let r2 = Keccak256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
@@ -11,31 +11,56 @@
 //! * **Merkle trees** - which are used as a commitment scheme in the STARK protocol. The
 //!   [MerkleTree] implementation supports concurrent tree construction as well as compact
 //!   aggregation of Merkle paths implemented using a variation of the
-//!   [Octopus](https://eprint.iacr.org/2017/933) algorithm.
+//!   [Octopus](https://eprint.iacr.org/2017/933) algorithm. Merkle trees implement the
+//!   [VectorCommitment] trait, which allows protocols to be defined over arbitrary vector
+//!   commitment schemes.
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
-//!   elements form a seed.
+//!   elements form a seed. The [Transcript] trait extends random coins with labeled absorb
+//!   and squeeze operations for domain-separated Fiat-Shamir transcripts, while [FieldPrng]
+//!   expands a digest seed into a reproducible stream of field elements.
+//! * **VRF** - keys and evaluation of a verifiable random function built from an algebraic hash
+//!   function ([VrfSecretKey]), such that correctness of the evaluation can be proven in an AIR.
 
 #![no_std]
 
 #[macro_use]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod hash;
-pub use hash::{Digest, ElementHasher, Hasher};
+pub use hash::{
+    Digest, ElementHasher, HashFunction, HashFunctionField, HashFunctionVisitor, Hasher,
+};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
-    pub use super::hash::{Blake3_192, Blake3_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256};
+    pub use super::hash::{
+        Anemoi64_256, Blake3_160, Blake3_192, Blake3_256, Keccak256, Poseidon64_256, Rp62_248,
+        Rp64_256, RpJive64_256, Sha3_256,
+    };
 }
 
+mod commitment;
+pub use commitment::VectorCommitment;
+
 mod merkle;
 #[cfg(feature = "concurrent")]
 pub use merkle::concurrent;
-pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
+#[cfg(feature = "mmap")]
+pub use merkle::MmapMerkleTree;
+pub use merkle::{
+    build_merkle_nodes, BatchMerkleProof, KaryBatchMerkleProof, KaryMerkleTree, MerkleTree,
+    MerkleTreeBuilder, SaltedBatchMerkleProof, SaltedMerkleTree,
+};
 
 mod random;
-pub use random::{DefaultRandomCoin, RandomCoin};
+pub use random::{DefaultRandomCoin, FieldPrng, RandomCoin, Transcript, TranscriptLabel};
+
+mod vrf;
+pub use vrf::{VrfOutput, VrfPublicKey, VrfSecretKey};
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
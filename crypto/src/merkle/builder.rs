@@ -0,0 +1,141 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use super::MerkleTree;
+use crate::{errors::MerkleTreeError, hash::Hasher};
+
+// MERKLE TREE BUILDER
+// ================================================================================================
+
+/// An incremental builder of a [MerkleTree].
+///
+/// Leaves are supplied to the builder one at a time via [push_leaf()](MerkleTreeBuilder::push_leaf)
+/// method, and internal nodes of the tree are computed as soon as all leaves of the sub-tree they
+/// are the roots of have been pushed. Thus, once the last leaf is pushed, all internal nodes of
+/// the tree are already computed, and [build()](MerkleTreeBuilder::build) method simply returns
+/// the resulting tree.
+///
+/// This allows the caller to hash values into leaves on the fly (e.g., while evaluating a
+/// polynomial over a large domain) without materializing a separate vector of leaf digests
+/// before the tree is built. The resulting tree is the same as the one built via
+/// [MerkleTree::new()] from the same leaves.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{MerkleTree, MerkleTreeBuilder, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256::<BaseElement>;
+///
+/// let mut builder = MerkleTreeBuilder::<Blake3>::new(8).unwrap();
+/// for i in 0..8u8 {
+///     builder.push_leaf(Blake3::hash(&[i])).unwrap();
+/// }
+/// let tree = builder.build().unwrap();
+///
+/// let leaves = (0..8u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+/// assert_eq!(MerkleTree::<Blake3>::new(leaves).unwrap().root(), tree.root());
+/// ```
+#[derive(Debug)]
+pub struct MerkleTreeBuilder<H: Hasher> {
+    nodes: Vec<H::Digest>,
+    leaves: Vec<H::Digest>,
+}
+
+impl<H: Hasher> MerkleTreeBuilder<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new builder for a Merkle tree with the specified number of leaves.
+    ///
+    /// Memory for all leaves and internal nodes of the tree is allocated up-front.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `num_leaves` is smaller than two.
+    /// * `num_leaves` is not a power of two.
+    pub fn new(num_leaves: usize) -> Result<Self, MerkleTreeError> {
+        if num_leaves < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, num_leaves));
+        }
+        if !num_leaves.is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(num_leaves));
+        }
+
+        Ok(MerkleTreeBuilder {
+            nodes: vec![H::Digest::default(); num_leaves],
+            leaves: Vec::with_capacity(num_leaves),
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of leaves in the tree being built.
+    pub fn num_leaves(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of leaves pushed into this builder so far.
+    pub fn num_pushed_leaves(&self) -> usize {
+        self.leaves.len()
+    }
+
+    // BUILDING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified leaf to the tree being built, and computes all internal nodes of the
+    /// tree which can be computed once this leaf is known.
+    ///
+    /// # Errors
+    /// Returns an error if all leaves of the tree have already been pushed.
+    pub fn push_leaf(&mut self, leaf: H::Digest) -> Result<(), MerkleTreeError> {
+        let n = self.nodes.len();
+        let index = self.leaves.len();
+        if index >= n {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(n, index));
+        }
+        self.leaves.push(leaf);
+
+        // a leaf with an odd index completes a pair of leaves; thus, we can compute their parent,
+        // and then move up the tree for as long as the computed node is a right child
+        if index & 1 == 1 {
+            let mut node_index = (index + n) >> 1;
+            self.nodes[node_index] = H::merge(&[self.leaves[index - 1], leaf]);
+            while node_index > 1 && node_index & 1 == 1 {
+                let parent = node_index >> 1;
+                self.nodes[parent] =
+                    H::merge(&[self.nodes[node_index - 1], self.nodes[node_index]]);
+                node_index = parent;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the Merkle tree built from the leaves pushed into this builder.
+    ///
+    /// # Errors
+    /// Returns an error if not all leaves of the tree have been pushed into this builder.
+    pub fn build(self) -> Result<MerkleTree<H>, MerkleTreeError> {
+        if self.leaves.len() != self.nodes.len() {
+            return Err(MerkleTreeError::TooFewLeaves(self.nodes.len(), self.leaves.len()));
+        }
+        MerkleTree::from_raw_parts(self.nodes, self.leaves)
+    }
+}
+
+impl<H: Hasher> Extend<H::Digest> for MerkleTreeBuilder<H> {
+    /// Pushes all leaves from the specified iterator into this builder.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields more leaves than the tree can hold.
+    fn extend<T: IntoIterator<Item = H::Digest>>(&mut self, leaves: T) {
+        for leaf in leaves {
+            self.push_leaf(leaf).expect("too many leaves");
+        }
+    }
+}
@@ -80,7 +80,10 @@ mod tests {
     use math::fields::f128::BaseElement;
     use proptest::{collection::vec, prelude::*};
 
-    use crate::hash::{ByteDigest, Sha3_256};
+    use crate::{
+        hash::{ByteDigest, Sha3_256},
+        KaryMerkleTree,
+    };
 
     proptest! {
         #[test]
@@ -90,5 +93,13 @@ mod tests {
             let concurrent = super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
             assert_eq!(concurrent, sequential);
         }
+
+        #[test]
+        fn build_kary_merkle_tree_concurrent(ref data in vec(any::<[u8; 32]>(), 2048..2049).no_shrink()) {
+            let leaves = ByteDigest::bytes_as_digests(data).to_vec();
+            let sequential = super::super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
+            let concurrent = KaryMerkleTree::<Sha3_256<BaseElement>, 2>::new(leaves).unwrap();
+            assert_eq!(*concurrent.root(), sequential[1]);
+        }
     }
 }
@@ -0,0 +1,434 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::ToString,
+    vec::Vec,
+};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+use utils::{iter_mut, ByteReader, ByteWriter, DeserializationError};
+
+use super::{map_indexes, proofs::MAX_PATHS};
+use crate::{errors::MerkleTreeError, hash::Hasher, VectorCommitment};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Minimum number of nodes at a level of a tree to be computed by a single thread.
+#[cfg(feature = "concurrent")]
+const MIN_CONCURRENT_NODES: usize = 64;
+
+// K-ARY MERKLE TREE
+// ================================================================================================
+
+/// A Merkle tree in which every internal node has `ARITY` children.
+///
+/// Compared to a binary [MerkleTree](super::MerkleTree), a tree of higher arity has fewer levels,
+/// and thus, authentication paths in such a tree span fewer levels and require fewer hash calls
+/// to verify (though each level of a path contains `ARITY - 1` siblings). This is beneficial for
+/// hash functions which can absorb many digests in a single permutation (e.g., algebraic hash
+/// functions with a wide state) as a node is computed by hashing all of its children together
+/// using [Hasher::merge_many()].
+///
+/// `ARITY` must be a power of two greater than one; currently, arities 2, 4, and 8 are expected
+/// to be used in practice. The number of leaves must be a power of two, but it does not need to be
+/// a power of `ARITY`: if the number of nodes at some level of the tree is smaller than `ARITY`,
+/// the root is computed by hashing all of these nodes together.
+///
+/// A tree with arity 2 is equivalent to a binary [MerkleTree](super::MerkleTree) (i.e., both trees
+/// have the same root for the same set of leaves).
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{KaryMerkleTree, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256::<BaseElement>;
+///
+/// // build a tree of arity 4
+/// let leaves = (0..16u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+/// let tree = KaryMerkleTree::<Blake3, 4>::new(leaves.clone()).unwrap();
+/// assert_eq!(2, tree.depth());
+///
+/// // generate and verify a batch proof
+/// let proof = tree.prove_batch(&[1, 9]).unwrap();
+/// assert_eq!(vec![leaves[1], leaves[9]], proof.leaves);
+/// assert!(KaryMerkleTree::<Blake3, 4>::verify_batch(tree.root(), &[1, 9], &proof).is_ok());
+/// assert!(KaryMerkleTree::<Blake3, 4>::verify_batch(tree.root(), &[1, 8], &proof).is_err());
+/// ```
+#[derive(Debug)]
+pub struct KaryMerkleTree<H: Hasher, const ARITY: usize> {
+    leaves: Vec<H::Digest>,
+    /// Internal nodes of the tree grouped by level; the first level contains parents of the
+    /// leaves, and the last level contains only the root.
+    levels: Vec<Vec<H::Digest>>,
+}
+
+impl<H: Hasher, const ARITY: usize> KaryMerkleTree<H, ARITY> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns new Merkle tree of arity `ARITY` built from the provided leaves using hash function
+    /// specified by the `H` generic parameter.
+    ///
+    /// When `concurrent` feature is enabled, the tree is built using multiple threads.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `ARITY` is not a power of two greater than one.
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    pub fn new(leaves: Vec<H::Digest>) -> Result<Self, MerkleTreeError> {
+        if ARITY < 2 || !ARITY.is_power_of_two() {
+            return Err(MerkleTreeError::InvalidArity(ARITY));
+        }
+        if leaves.len() < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
+        }
+        if !leaves.len().is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        }
+
+        // when concurrent feature is enabled, nodes at each level are computed in multiple
+        // threads; the resulting tree is the same regardless of the number of threads
+        let mut levels: Vec<Vec<H::Digest>> = Vec::new();
+        let mut current = &leaves;
+        while current.len() > 1 {
+            let group_size = ARITY.min(current.len());
+            let mut next = vec![H::Digest::default(); current.len() / group_size];
+            iter_mut!(next, MIN_CONCURRENT_NODES).enumerate().for_each(|(i, node)| {
+                *node = merge_nodes::<H>(&current[i * group_size..(i + 1) * group_size]);
+            });
+            levels.push(next);
+            current = levels.last().expect("no levels");
+        }
+
+        Ok(KaryMerkleTree { leaves, levels })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Returns depth of the tree.
+    ///
+    /// The depth of a tree is the number of levels of internal nodes. Thus, a tree of arity 4
+    /// with 4 leaves has depth 1, and a tree of arity 4 with 8 or 16 leaves has depth 2.
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns leaf nodes of the tree.
+    pub fn leaves(&self) -> &[H::Digest] {
+        &self.leaves
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    pub fn prove_batch(
+        &self,
+        indexes: &[usize],
+    ) -> Result<KaryBatchMerkleProof<H>, MerkleTreeError> {
+        if indexes.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        if indexes.len() > MAX_PATHS {
+            return Err(MerkleTreeError::TooManyLeafIndexes(MAX_PATHS, indexes.len()));
+        }
+
+        let index_map = map_indexes(indexes, self.leaves.len().ilog2() as usize)?;
+        let leaves = indexes.iter().map(|&index| self.leaves[index]).collect();
+
+        // for every level of the tree, add siblings of the known nodes to the proof; the nodes
+        // are added in the order in which they are consumed by the verifier
+        let mut nodes = Vec::new();
+        let mut known: BTreeSet<usize> = index_map.into_keys().collect();
+        let mut current = &self.leaves;
+        for level in self.levels.iter() {
+            let group_size = ARITY.min(current.len());
+            let parents: BTreeSet<usize> = known.iter().map(|&index| index / group_size).collect();
+            for &parent in parents.iter() {
+                let first_index = parent * group_size;
+                for (i, &node) in current[first_index..first_index + group_size].iter().enumerate()
+                {
+                    if !known.contains(&(first_index + i)) {
+                        nodes.push(node);
+                    }
+                }
+            }
+            known = parents;
+            current = level;
+        }
+
+        Ok(KaryBatchMerkleProof {
+            leaves,
+            nodes,
+            num_leaves: self.leaves.len(),
+        })
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the batch proof contains Merkle paths for the of the specified `indexes`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree from which the batch proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * Any of the paths in the batch proof does not resolve to the specified `root`.
+    pub fn verify_batch(
+        root: &H::Digest,
+        indexes: &[usize],
+        proof: &KaryBatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if *root != proof.get_root::<ARITY>(indexes)? {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher, const ARITY: usize> VectorCommitment<H> for KaryMerkleTree<H, ARITY> {
+    type BatchProof = KaryBatchMerkleProof<H>;
+    type Error = MerkleTreeError;
+
+    fn commit(items: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        KaryMerkleTree::new(items)
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    fn open_batch(&self, indexes: &[usize]) -> Result<Self::BatchProof, Self::Error> {
+        self.prove_batch(indexes)
+    }
+
+    fn verify_batch(
+        commitment: &H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::BatchProof,
+    ) -> Result<(), Self::Error> {
+        // leaves of the batch proof are ordered in the same way as the indexes they were opened
+        // at; thus, they must match the opened items exactly
+        if proof.leaves != items {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        KaryMerkleTree::<H, ARITY>::verify_batch(commitment, indexes, proof)
+    }
+
+    fn serialize_batch_proof(proof: &Self::BatchProof) -> Vec<u8> {
+        proof.serialize_nodes()
+    }
+
+    fn deserialize_batch_proof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+    ) -> Result<Self::BatchProof, DeserializationError> {
+        KaryBatchMerkleProof::deserialize(source, items, domain_len)
+    }
+}
+
+// K-ARY BATCH MERKLE PROOF
+// ================================================================================================
+
+/// Multiple Merkle paths in a [KaryMerkleTree] aggregated into a single proof.
+///
+/// Internal nodes which can be computed from the leaves being proven (or from other nodes in the
+/// proof) are not included in the proof. The remaining nodes are listed level by level starting
+/// from the level of the leaves; within a level, the nodes are sorted by their index.
+///
+/// The arity of the tree is not recorded in the proof, and thus, it must be known to the verifier.
+///
+/// Currently, at most 255 paths can be aggregated into a single proof. This limitation is
+/// imposed primarily for serialization purposes.
+#[derive(Debug, PartialEq, Eq)]
+pub struct KaryBatchMerkleProof<H: Hasher> {
+    /// The leaves being proven, in the order of the indexes they were opened at
+    pub leaves: Vec<H::Digest>,
+    /// Internal nodes required to compute the root of the tree from the leaves
+    pub nodes: Vec<H::Digest>,
+    /// Number of leaves in the tree from which the proof was generated
+    pub num_leaves: usize,
+}
+
+// Clone is implemented manually because deriving it would require `H` to implement Clone.
+impl<H: Hasher> Clone for KaryBatchMerkleProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            leaves: self.leaves.clone(),
+            nodes: self.nodes.clone(),
+            num_leaves: self.num_leaves,
+        }
+    }
+}
+
+impl<H: Hasher> KaryBatchMerkleProof<H> {
+    /// Computes a node to which all Merkle paths aggregated in this proof resolve in a tree of
+    /// arity `ARITY`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Number of provided indexes is not the same as the number of leaves in this proof.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree from which this proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * The number of nodes in this proof is not consistent with the specified `indexes`.
+    pub fn get_root<const ARITY: usize>(
+        &self,
+        indexes: &[usize],
+    ) -> Result<H::Digest, MerkleTreeError> {
+        if ARITY < 2 || !ARITY.is_power_of_two() {
+            return Err(MerkleTreeError::InvalidArity(ARITY));
+        }
+        if indexes.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        if indexes.len() > MAX_PATHS {
+            return Err(MerkleTreeError::TooManyLeafIndexes(MAX_PATHS, indexes.len()));
+        }
+        if indexes.len() != self.leaves.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        if self.num_leaves < 2 || !self.num_leaves.is_power_of_two() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        let index_map = map_indexes(indexes, self.num_leaves.ilog2() as usize)?;
+        let mut known: BTreeMap<usize, H::Digest> =
+            index_map.into_iter().map(|(index, i)| (index, self.leaves[i])).collect();
+
+        // compute the nodes at every level of the tree from the known nodes at the level below,
+        // consuming the missing siblings from the proof
+        let mut proof_nodes = self.nodes.iter();
+        let mut level_size = self.num_leaves;
+        while level_size > 1 {
+            let group_size = ARITY.min(level_size);
+            let parents: BTreeSet<usize> = known.keys().map(|&index| index / group_size).collect();
+
+            let mut next = BTreeMap::new();
+            let mut children = Vec::with_capacity(group_size);
+            for parent in parents {
+                children.clear();
+                for index in parent * group_size..(parent + 1) * group_size {
+                    let node = match known.get(&index) {
+                        Some(node) => *node,
+                        None => *proof_nodes.next().ok_or(MerkleTreeError::InvalidProof)?,
+                    };
+                    children.push(node);
+                }
+                next.insert(parent, merge_nodes::<H>(&children));
+            }
+
+            known = next;
+            level_size /= group_size;
+        }
+
+        // all nodes in the proof must have been consumed
+        if proof_nodes.next().is_some() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        Ok(known[&0])
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Converts all internal proof nodes into a vector of bytes.
+    ///
+    /// # Panics
+    /// Panics if the proof contains more than 65535 internal nodes.
+    pub fn serialize_nodes(&self) -> Vec<u8> {
+        assert!(self.nodes.len() <= u16::MAX as usize, "too many nodes");
+        let mut result = Vec::new();
+        result.write_u16(self.nodes.len() as u16);
+        result.write_many(&self.nodes);
+        result
+    }
+
+    /// Parses internal nodes from the provided `node_bytes`, and constructs a batch Merkle proof
+    /// from these nodes, provided `leaves`, and provided number of leaves in the tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No leaves were provided (i.e., `leaves` is an empty slice).
+    /// * Number of provided leaves is greater than 255.
+    /// * `num_leaves` is smaller than two or is not a power of two.
+    /// * `node_bytes` could not be deserialized into a valid set of internal nodes.
+    pub fn deserialize<R: ByteReader>(
+        node_bytes: &mut R,
+        leaves: Vec<H::Digest>,
+        num_leaves: usize,
+    ) -> Result<Self, DeserializationError> {
+        if num_leaves < 2 || !num_leaves.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of leaves must be a power of two greater than one, but was {num_leaves}"
+            )));
+        }
+        if leaves.is_empty() {
+            return Err(DeserializationError::InvalidValue(
+                "at lease one leaf must be provided".to_string(),
+            ));
+        }
+        if leaves.len() > MAX_PATHS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of leaves cannot exceed {}, but {} were provided",
+                MAX_PATHS,
+                leaves.len()
+            )));
+        }
+
+        let num_nodes = node_bytes.read_u16()? as usize;
+        let nodes = node_bytes.read_many(num_nodes)?;
+
+        Ok(KaryBatchMerkleProof { leaves, nodes, num_leaves })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Hashes the provided child nodes into their parent node.
+///
+/// Two nodes are merged using [Hasher::merge()] so that a tree of arity 2 is the same as a binary
+/// Merkle tree; for all other numbers of nodes, [Hasher::merge_many()] is used.
+fn merge_nodes<H: Hasher>(nodes: &[H::Digest]) -> H::Digest {
+    match nodes {
+        [left, right] => H::merge(&[*left, *right]),
+        _ => H::merge_many(nodes),
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use memmap2::Mmap;
+use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
+
+use super::{build_batch_proof, BatchMerkleProof, MerkleTree};
+use crate::{errors::MerkleTreeError, hash::Hasher, VectorCommitment};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Size of the header of a persisted Merkle tree: number of leaves and digest size, both encoded
+/// as 8-byte little-endian integers.
+const HEADER_SIZE: usize = 16;
+
+/// Counter used to generate unique names for temporary files backing committed trees.
+static NEXT_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+
+// MERKLE TREE PERSISTENCE
+// ================================================================================================
+
+impl<H: Hasher> MerkleTree<H> {
+    /// Writes this tree into a file at the specified `path`.
+    ///
+    /// The file contains a header with the number of leaves and the size of a serialized digest,
+    /// followed by all internal nodes of the tree and then by all leaves of the tree. The file
+    /// can be loaded back via [MerkleTree::open_mmap()].
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be created or written to.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), MerkleTreeError> {
+        let file = File::create(path).map_err(storage_error)?;
+        let mut writer = BufWriter::new(file);
+
+        let header = [self.leaves.len() as u64, digest_size::<H>() as u64];
+        for value in header {
+            writer.write_all(&value.to_le_bytes()).map_err(storage_error)?;
+        }
+        for digest in self.nodes.iter().chain(self.leaves.iter()) {
+            writer.write_all(&digest.to_bytes()).map_err(storage_error)?;
+        }
+
+        writer.flush().map_err(storage_error)
+    }
+
+    /// Loads a tree previously written via [MerkleTree::write_to()] from the file at the
+    /// specified `path` by memory-mapping the file.
+    ///
+    /// Nodes of the returned tree are read from the mapped file on demand, and thus, the tree
+    /// does not need to fit into memory. The file may also be mapped by multiple processes at the
+    /// same time.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The file could not be opened or mapped into memory.
+    /// * The file does not contain a valid Merkle tree for the hash function `H`.
+    pub fn open_mmap<P: AsRef<Path>>(path: P) -> Result<MmapMerkleTree<H>, MerkleTreeError> {
+        MmapMerkleTree::open(path.as_ref(), None)
+    }
+}
+
+// MEMORY-MAPPED MERKLE TREE
+// ================================================================================================
+
+/// A Merkle tree backed by a memory-mapped file.
+///
+/// The tree can be loaded via [MerkleTree::open_mmap()] from a file written by
+/// [MerkleTree::write_to()]. Nodes of the tree are deserialized from the mapped file whenever
+/// they are accessed; the root of the tree is read once when the tree is loaded.
+///
+/// The tree implements [VectorCommitment], and thus, can be used in place of a [MerkleTree]
+/// wherever the commitment scheme is specified via a type parameter (e.g., to keep FRI layer
+/// commitments out of memory between the commit and the query phases of the protocol). In this
+/// case, a newly committed tree is written into a temporary file which is removed when the tree
+/// is dropped.
+#[derive(Debug)]
+pub struct MmapMerkleTree<H: Hasher> {
+    mmap: Mmap,
+    num_leaves: usize,
+    digest_size: usize,
+    root: H::Digest,
+    // declared after `mmap` so that the file is unmapped before it is removed
+    _temp_file: Option<TempFile>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> MmapMerkleTree<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Maps the file at the specified `path` into memory and validates its header; if
+    /// `temp_path` is provided, the file at this path is removed when the tree is dropped.
+    fn open(path: &Path, temp_path: Option<PathBuf>) -> Result<Self, MerkleTreeError> {
+        let file = File::open(path).map_err(storage_error)?;
+        // SAFETY: the mapping is read-only; modifying the file while it is mapped is not
+        // supported.
+        let mmap = unsafe { Mmap::map(&file) }.map_err(storage_error)?;
+
+        if mmap.len() < HEADER_SIZE {
+            return Err(MerkleTreeError::StorageError("file header is missing".to_string()));
+        }
+        let num_leaves = read_u64(&mmap[..8]) as usize;
+        let file_digest_size = read_u64(&mmap[8..HEADER_SIZE]) as usize;
+
+        if num_leaves < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, num_leaves));
+        }
+        if !num_leaves.is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(num_leaves));
+        }
+        if file_digest_size != digest_size::<H>() {
+            return Err(MerkleTreeError::StorageError(format!(
+                "expected digests of {} bytes, but file contains digests of {file_digest_size} bytes",
+                digest_size::<H>()
+            )));
+        }
+        let expected_len = num_leaves
+            .checked_mul(2 * file_digest_size)
+            .and_then(|len| len.checked_add(HEADER_SIZE));
+        if expected_len != Some(mmap.len()) {
+            return Err(MerkleTreeError::StorageError(format!(
+                "file of {} bytes does not contain a tree with {num_leaves} leaves",
+                mmap.len()
+            )));
+        }
+
+        let mut tree = MmapMerkleTree {
+            mmap,
+            num_leaves,
+            digest_size: file_digest_size,
+            root: H::Digest::default(),
+            _temp_file: temp_path.map(TempFile),
+            _hasher: PhantomData,
+        };
+        tree.root = tree.try_read_digest(1).map_err(storage_error)?;
+
+        Ok(tree)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        &self.root
+    }
+
+    /// Returns depth of the tree.
+    ///
+    /// The depth of a tree is zero-based. Thus, a tree with two leaves has depth 1, a tree with
+    /// four leaves has depth 2 etc.
+    pub fn depth(&self) -> usize {
+        self.num_leaves.ilog2() as usize
+    }
+
+    /// Returns the number of leaves in the tree.
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Returns the leaf at the specified `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of leaves in the tree, or if the
+    /// leaf could not be read from the mapped file.
+    pub fn leaf(&self, index: usize) -> H::Digest {
+        assert!(index < self.num_leaves, "leaf index {index} is out of bounds");
+        self.read_digest(self.num_leaves + index)
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a Merkle path to a leaf at the specified `index`.
+    ///
+    /// The leaf itself will be the first element in the path.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree.
+    pub fn prove(&self, index: usize) -> Result<Vec<H::Digest>, MerkleTreeError> {
+        if index >= self.num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(self.num_leaves, index));
+        }
+
+        let mut proof = vec![self.leaf(index), self.leaf(index ^ 1)];
+
+        let mut index = (index + self.num_leaves) >> 1;
+        while index > 1 {
+            proof.push(self.read_digest(index ^ 1));
+            index >>= 1;
+        }
+
+        Ok(proof)
+    }
+
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single proof.
+    ///
+    /// The resulting proof is the same as the one produced by [MerkleTree::prove_batch()] for the
+    /// tree this tree was loaded from.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    pub fn prove_batch(&self, indexes: &[usize]) -> Result<BatchMerkleProof<H>, MerkleTreeError> {
+        build_batch_proof(indexes, self.depth(), |i| self.leaf(i), |i| self.read_digest(i))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads the digest at the specified position of the tree; positions [1, n) contain internal
+    /// nodes and positions [n, 2n) contain leaves.
+    fn read_digest(&self, position: usize) -> H::Digest {
+        self.try_read_digest(position).expect("failed to read digest from mapped file")
+    }
+
+    fn try_read_digest(&self, position: usize) -> Result<H::Digest, DeserializationError> {
+        let offset = HEADER_SIZE + position * self.digest_size;
+        H::Digest::read_from_bytes(&self.mmap[offset..offset + self.digest_size])
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher> VectorCommitment<H> for MmapMerkleTree<H> {
+    type BatchProof = BatchMerkleProof<H>;
+    type Error = MerkleTreeError;
+
+    fn commit(items: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        let tree = MerkleTree::<H>::new(items)?;
+        let file_id = NEXT_FILE_ID.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir()
+            .join(format!("winter-merkle-tree-{}-{file_id}.bin", process::id()));
+        tree.write_to(&path)?;
+        drop(tree);
+        MmapMerkleTree::open(&path, Some(path.clone()))
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.num_leaves
+    }
+
+    fn open_batch(&self, indexes: &[usize]) -> Result<Self::BatchProof, Self::Error> {
+        self.prove_batch(indexes)
+    }
+
+    fn verify_batch(
+        commitment: &H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::BatchProof,
+    ) -> Result<(), Self::Error> {
+        <MerkleTree<H> as VectorCommitment<H>>::verify_batch(commitment, indexes, items, proof)
+    }
+
+    fn serialize_batch_proof(proof: &Self::BatchProof) -> Vec<u8> {
+        proof.serialize_nodes()
+    }
+
+    fn deserialize_batch_proof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+    ) -> Result<Self::BatchProof, DeserializationError> {
+        <MerkleTree<H> as VectorCommitment<H>>::deserialize_batch_proof(source, items, domain_len)
+    }
+}
+
+// TEMPORARY FILE
+// ================================================================================================
+
+/// Path to a temporary file which is removed when this struct is dropped.
+#[derive(Debug)]
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of bytes in a serialized digest of the hash function `H`.
+fn digest_size<H: Hasher>() -> usize {
+    H::Digest::default().to_bytes().len()
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().expect("slice must contain 8 bytes"))
+}
+
+fn storage_error<E: ToString>(err: E) -> MerkleTreeError {
+    MerkleTreeError::StorageError(err.to_string())
+}
@@ -9,11 +9,27 @@ use alloc::{
 };
 use core::slice;
 
-use crate::{errors::MerkleTreeError, hash::Hasher};
+use utils::{ByteReader, DeserializationError};
+
+use crate::{errors::MerkleTreeError, hash::Hasher, VectorCommitment};
 
 mod proofs;
 pub use proofs::BatchMerkleProof;
 
+mod builder;
+pub use builder::MerkleTreeBuilder;
+
+mod kary;
+pub use kary::{KaryBatchMerkleProof, KaryMerkleTree};
+
+mod salted;
+pub use salted::{SaltedBatchMerkleProof, SaltedMerkleTree};
+
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapMerkleTree;
+
 #[cfg(feature = "concurrent")]
 pub mod concurrent;
 
@@ -176,6 +192,68 @@ impl<H: Hasher> MerkleTree<H> {
         &self.leaves
     }
 
+    // UPDATE METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Replaces the leaf at the specified `index` with `leaf` and returns the new root of the
+    /// tree.
+    ///
+    /// Only the nodes on the path from the leaf to the root are recomputed, and thus, the update
+    /// requires O(log n) hash function invocations.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree.
+    pub fn update_leaf(
+        &mut self,
+        index: usize,
+        leaf: H::Digest,
+    ) -> Result<H::Digest, MerkleTreeError> {
+        if index >= self.leaves.len() {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(self.leaves.len(), index));
+        }
+        self.leaves[index] = leaf;
+
+        // recompute the parent of the updated leaf, and then move up the tree to the root
+        let pair = index & !1;
+        let mut node_index = (index + self.nodes.len()) >> 1;
+        self.nodes[node_index] = H::merge(&[self.leaves[pair], self.leaves[pair + 1]]);
+        while node_index > 1 {
+            let sibling = node_index & !1;
+            node_index >>= 1;
+            self.nodes[node_index] = H::merge(&[self.nodes[sibling], self.nodes[sibling + 1]]);
+        }
+
+        Ok(*self.root())
+    }
+
+    /// Replaces the leaf at the specified `index` with `leaf`, and returns Merkle paths to this
+    /// leaf before and after the update (in this order).
+    ///
+    /// Both paths are in the same format as the paths returned from [MerkleTree::prove()]. The
+    /// old path resolves to the root of the tree before the update, and the new path resolves to
+    /// the root of the tree after the update.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree.
+    #[allow(clippy::type_complexity)]
+    pub fn update_leaf_with_paths(
+        &mut self,
+        index: usize,
+        leaf: H::Digest,
+    ) -> Result<(Vec<H::Digest>, Vec<H::Digest>), MerkleTreeError> {
+        let old_path = self.prove(index)?;
+        self.update_leaf(index, leaf)?;
+
+        // siblings on the path are not affected by the update; thus, only the leaf itself
+        // differs between the two paths
+        let mut new_path = old_path.clone();
+        new_path[0] = leaf;
+
+        Ok((old_path, new_path))
+    }
+
     // PROVING METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -204,6 +282,12 @@ impl<H: Hasher> MerkleTree<H> {
 
     /// Computes Merkle paths for the provided indexes and compresses the paths into a single proof.
     ///
+    /// Internal nodes shared by multiple paths, as well as nodes which can be computed from other
+    /// nodes in the proof, are included in the proof only once (or not at all). Thus, for a large
+    /// number of indexes, the proof is much smaller than the concatenation of individual paths.
+    /// The proof can be verified via [MerkleTree::verify_batch()] or expanded back into
+    /// individual paths via [BatchMerkleProof::into_paths()].
+    ///
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
@@ -212,60 +296,7 @@ impl<H: Hasher> MerkleTree<H> {
     ///   tree.
     /// * List of indexes contains duplicates.
     pub fn prove_batch(&self, indexes: &[usize]) -> Result<BatchMerkleProof<H>, MerkleTreeError> {
-        if indexes.is_empty() {
-            return Err(MerkleTreeError::TooFewLeafIndexes);
-        }
-        if indexes.len() > proofs::MAX_PATHS {
-            return Err(MerkleTreeError::TooManyLeafIndexes(proofs::MAX_PATHS, indexes.len()));
-        }
-
-        let index_map = map_indexes(indexes, self.depth())?;
-        let indexes = normalize_indexes(indexes);
-        let mut leaves = vec![H::Digest::default(); index_map.len()];
-        let mut nodes: Vec<Vec<H::Digest>> = Vec::with_capacity(indexes.len());
-
-        // populate the proof with leaf node values
-        let n = self.leaves.len();
-        let mut next_indexes: Vec<usize> = Vec::new();
-        for index in indexes {
-            let missing: Vec<H::Digest> = (index..index + 2)
-                .flat_map(|i| {
-                    let v = self.leaves[i];
-                    if let Some(idx) = index_map.get(&i) {
-                        leaves[*idx] = v;
-                        None
-                    } else {
-                        Some(v)
-                    }
-                })
-                .collect();
-            nodes.push(missing);
-
-            next_indexes.push((index + n) >> 1);
-        }
-
-        // add required internal nodes to the proof, skipping redundancies
-        for _ in 1..self.depth() {
-            let indexes = next_indexes.clone();
-            next_indexes.truncate(0);
-
-            let mut i = 0;
-            while i < indexes.len() {
-                let sibling_index = indexes[i] ^ 1;
-                if i + 1 < indexes.len() && indexes[i + 1] == sibling_index {
-                    i += 1;
-                } else {
-                    nodes[i].push(self.nodes[sibling_index]);
-                }
-
-                // add parent index to the set of next indexes
-                next_indexes.push(sibling_index >> 1);
-
-                i += 1;
-            }
-        }
-
-        Ok(BatchMerkleProof { leaves, nodes, depth: self.depth() as u8 })
+        build_batch_proof(indexes, self.depth(), |i| self.leaves[i], |i| self.nodes[i])
     }
 
     // VERIFICATION METHODS
@@ -322,6 +353,61 @@ impl<H: Hasher> MerkleTree<H> {
     }
 }
 
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher> VectorCommitment<H> for MerkleTree<H> {
+    type BatchProof = BatchMerkleProof<H>;
+    type Error = MerkleTreeError;
+
+    fn commit(items: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        MerkleTree::new(items)
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    fn open_batch(&self, indexes: &[usize]) -> Result<Self::BatchProof, Self::Error> {
+        self.prove_batch(indexes)
+    }
+
+    fn verify_batch(
+        commitment: &H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::BatchProof,
+    ) -> Result<(), Self::Error> {
+        // leaves of the batch proof are ordered in the same way as the indexes they were opened
+        // at; thus, they must match the opened items exactly
+        if proof.leaves != items {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        MerkleTree::verify_batch(commitment, indexes, proof)
+    }
+
+    fn serialize_batch_proof(proof: &Self::BatchProof) -> Vec<u8> {
+        proof.serialize_nodes()
+    }
+
+    fn deserialize_batch_proof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+    ) -> Result<Self::BatchProof, DeserializationError> {
+        if !domain_len.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of leaves must be a power of two, but was {domain_len}"
+            )));
+        }
+        BatchMerkleProof::deserialize(source, items, domain_len.ilog2() as u8)
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -358,6 +444,71 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     nodes
 }
 
+/// Computes Merkle paths for the provided indexes in a tree of the specified depth and
+/// compresses the paths into a single proof; leaves and internal nodes of the tree are read via
+/// the `leaf` and `node` closures respectively.
+fn build_batch_proof<H: Hasher>(
+    indexes: &[usize],
+    depth: usize,
+    leaf: impl Fn(usize) -> H::Digest,
+    node: impl Fn(usize) -> H::Digest,
+) -> Result<BatchMerkleProof<H>, MerkleTreeError> {
+    if indexes.is_empty() {
+        return Err(MerkleTreeError::TooFewLeafIndexes);
+    }
+    if indexes.len() > proofs::MAX_PATHS {
+        return Err(MerkleTreeError::TooManyLeafIndexes(proofs::MAX_PATHS, indexes.len()));
+    }
+
+    let index_map = map_indexes(indexes, depth)?;
+    let indexes = normalize_indexes(indexes);
+    let mut leaves = vec![H::Digest::default(); index_map.len()];
+    let mut nodes: Vec<Vec<H::Digest>> = Vec::with_capacity(indexes.len());
+
+    // populate the proof with leaf node values
+    let n = 1 << depth;
+    let mut next_indexes: Vec<usize> = Vec::new();
+    for index in indexes {
+        let missing: Vec<H::Digest> = (index..index + 2)
+            .flat_map(|i| {
+                let v = leaf(i);
+                if let Some(idx) = index_map.get(&i) {
+                    leaves[*idx] = v;
+                    None
+                } else {
+                    Some(v)
+                }
+            })
+            .collect();
+        nodes.push(missing);
+
+        next_indexes.push((index + n) >> 1);
+    }
+
+    // add required internal nodes to the proof, skipping redundancies
+    for _ in 1..depth {
+        let indexes = next_indexes.clone();
+        next_indexes.truncate(0);
+
+        let mut i = 0;
+        while i < indexes.len() {
+            let sibling_index = indexes[i] ^ 1;
+            if i + 1 < indexes.len() && indexes[i + 1] == sibling_index {
+                i += 1;
+            } else {
+                nodes[i].push(node(sibling_index));
+            }
+
+            // add parent index to the set of next indexes
+            next_indexes.push(sibling_index >> 1);
+
+            i += 1;
+        }
+    }
+
+    Ok(BatchMerkleProof { leaves, nodes, depth: depth as u8 })
+}
+
 fn map_indexes(
     indexes: &[usize],
     tree_depth: usize,
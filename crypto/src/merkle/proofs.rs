@@ -26,7 +26,7 @@ pub(super) const MAX_PATHS: usize = 255;
 ///
 /// Currently, at most 255 paths can be aggregated into a single proof. This limitation is
 /// imposed primarily for serialization purposes.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct BatchMerkleProof<H: Hasher> {
     /// The leaves being proven
     pub leaves: Vec<H::Digest>,
@@ -36,6 +36,17 @@ pub struct BatchMerkleProof<H: Hasher> {
     pub depth: u8,
 }
 
+// Clone is implemented manually because deriving it would require `H` to implement Clone.
+impl<H: Hasher> Clone for BatchMerkleProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            leaves: self.leaves.clone(),
+            nodes: self.nodes.clone(),
+            depth: self.depth,
+        }
+    }
+}
+
 impl<H: Hasher> BatchMerkleProof<H> {
     /// Constructs a batch Merkle proof from individual Merkle authentication paths.
     ///
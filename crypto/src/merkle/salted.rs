@@ -0,0 +1,266 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use rand_core::RngCore;
+#[cfg(feature = "std")]
+use rand_core::SeedableRng;
+use utils::{ByteReader, ByteWriter, DeserializationError};
+
+use super::{BatchMerkleProof, MerkleTree};
+#[cfg(feature = "std")]
+use crate::VectorCommitment;
+use crate::{errors::MerkleTreeError, hash::Hasher};
+
+// SALTED MERKLE TREE
+// ================================================================================================
+
+/// A Merkle tree in which every leaf is salted with a pseudo-random value.
+///
+/// A leaf of the tree is computed as hash(`value` || `salt`), where `value` is the digest being
+/// committed to, and `salt` is derived from the output of the pseudo-random generator `P`. Thus,
+/// as long as the salts remain secret, the root of the tree and the authentication paths for the
+/// opened values do not reveal any information about the values which were not opened. This
+/// makes the tree suitable for hiding commitments (e.g., in zero-knowledge variants of STARK and
+/// FRI protocols).
+///
+/// When a value is opened, its salt is revealed alongside the authentication path in a
+/// [SaltedBatchMerkleProof].
+///
+/// The tree implements [VectorCommitment] (when the crate is compiled with `std` feature), and
+/// thus, can be used in place of a [MerkleTree] wherever the commitment scheme is specified via
+/// a type parameter. In this case, the generator `P` is seeded from the operating system's source
+/// of randomness every time a commitment is created.
+#[derive(Debug)]
+pub struct SaltedMerkleTree<H: Hasher, P> {
+    tree: MerkleTree<H>,
+    salts: Vec<H::Digest>,
+    _prng: PhantomData<P>,
+}
+
+impl<H: Hasher, P: RngCore> SaltedMerkleTree<H, P> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new salted Merkle tree committing to the provided `values`; salts for the leaves
+    /// of the tree are drawn from the specified `prng`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two values were provided.
+    /// * Number of values is not a power of two.
+    pub fn new(values: Vec<H::Digest>, prng: &mut P) -> Result<Self, MerkleTreeError> {
+        let salts = (0..values.len())
+            .map(|_| {
+                let mut salt_bytes = [0u8; 32];
+                prng.fill_bytes(&mut salt_bytes);
+                H::hash(&salt_bytes)
+            })
+            .collect::<Vec<_>>();
+
+        let leaves = values
+            .iter()
+            .zip(salts.iter())
+            .map(|(&value, &salt)| H::merge(&[value, salt]))
+            .collect();
+        let tree = MerkleTree::new(leaves)?;
+
+        Ok(SaltedMerkleTree { tree, salts, _prng: PhantomData })
+    }
+}
+
+impl<H: Hasher, P> SaltedMerkleTree<H, P> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        self.tree.root()
+    }
+
+    /// Returns depth of the tree.
+    pub fn depth(&self) -> usize {
+        self.tree.depth()
+    }
+
+    /// Returns salted leaf nodes of the tree.
+    pub fn leaves(&self) -> &[H::Digest] {
+        self.tree.leaves()
+    }
+
+    /// Returns salts of all leaves of the tree.
+    ///
+    /// The salts must be kept secret for the tree to be hiding; only the salts of the opened
+    /// values should be revealed.
+    pub fn salts(&self) -> &[H::Digest] {
+        &self.salts
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single
+    /// proof; salts of the leaves at the specified indexes are included in the proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    pub fn prove_batch(
+        &self,
+        indexes: &[usize],
+    ) -> Result<SaltedBatchMerkleProof<H>, MerkleTreeError> {
+        let proof = self.tree.prove_batch(indexes)?;
+        let salts = indexes.iter().map(|&index| self.salts[index]).collect();
+        Ok(SaltedBatchMerkleProof { salts, proof })
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the batch proof opens the specified `values` at the specified `indexes`
+    /// of a tree with the specified `root`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of `values` is not the same as the number of `indexes`.
+    /// * The number of salts in the proof is not the same as the number of `indexes`.
+    /// * Any of the salted `values` does not match the corresponding leaf in the proof.
+    /// * Any of the paths in the batch proof does not resolve to the specified `root`.
+    pub fn verify_batch(
+        root: &H::Digest,
+        indexes: &[usize],
+        values: &[H::Digest],
+        proof: &SaltedBatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if values.len() != indexes.len() || proof.salts.len() != indexes.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        let leaves_match = values
+            .iter()
+            .zip(proof.salts.iter())
+            .zip(proof.proof.leaves.iter())
+            .all(|((&value, &salt), leaf)| H::merge(&[value, salt]) == *leaf);
+        if !leaves_match || proof.proof.leaves.len() != values.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        MerkleTree::verify_batch(root, indexes, &proof.proof)
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+#[cfg(feature = "std")]
+impl<H: Hasher, P: RngCore + SeedableRng> VectorCommitment<H> for SaltedMerkleTree<H, P> {
+    type BatchProof = SaltedBatchMerkleProof<H>;
+    type Error = MerkleTreeError;
+
+    fn commit(items: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        SaltedMerkleTree::new(items, &mut P::from_entropy())
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.salts.len()
+    }
+
+    fn open_batch(&self, indexes: &[usize]) -> Result<Self::BatchProof, Self::Error> {
+        self.prove_batch(indexes)
+    }
+
+    fn verify_batch(
+        commitment: &H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::BatchProof,
+    ) -> Result<(), Self::Error> {
+        SaltedMerkleTree::<H, P>::verify_batch(commitment, indexes, items, proof)
+    }
+
+    fn serialize_batch_proof(proof: &Self::BatchProof) -> Vec<u8> {
+        proof.serialize()
+    }
+
+    fn deserialize_batch_proof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+    ) -> Result<Self::BatchProof, DeserializationError> {
+        SaltedBatchMerkleProof::deserialize(source, &items, domain_len)
+    }
+}
+
+// SALTED BATCH MERKLE PROOF
+// ================================================================================================
+
+/// Multiple Merkle paths in a [SaltedMerkleTree] aggregated into a single proof together with
+/// salts of the opened leaves.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SaltedBatchMerkleProof<H: Hasher> {
+    /// Salts of the opened leaves, in the order of the indexes they were opened at
+    pub salts: Vec<H::Digest>,
+    /// Batch Merkle proof for the salted leaves
+    pub proof: BatchMerkleProof<H>,
+}
+
+// Clone is implemented manually because deriving it would require `H` to implement Clone.
+impl<H: Hasher> Clone for SaltedBatchMerkleProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            salts: self.salts.clone(),
+            proof: self.proof.clone(),
+        }
+    }
+}
+
+impl<H: Hasher> SaltedBatchMerkleProof<H> {
+    /// Converts this proof into a vector of bytes.
+    ///
+    /// Neither the opened values nor the salted leaves are included in the serialized proof.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        result.write_many(&self.salts);
+        result.extend_from_slice(&self.proof.serialize_nodes());
+        result
+    }
+
+    /// Parses salts and internal nodes from the provided `source`, and constructs a salted batch
+    /// Merkle proof for the opened `values` in a tree with `num_leaves` leaves.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `num_leaves` is not a power of two.
+    /// * `source` could not be deserialized into a valid salted batch Merkle proof.
+    pub fn deserialize<R: ByteReader>(
+        source: &mut R,
+        values: &[H::Digest],
+        num_leaves: usize,
+    ) -> Result<Self, DeserializationError> {
+        if !num_leaves.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of leaves must be a power of two, but was {num_leaves}"
+            )));
+        }
+
+        let salts: Vec<H::Digest> = source.read_many(values.len())?;
+        let leaves = values
+            .iter()
+            .zip(salts.iter())
+            .map(|(&value, &salt)| H::merge(&[value, salt]))
+            .collect();
+        let proof = BatchMerkleProof::deserialize(source, leaves, num_leaves.ilog2() as u8)?;
+
+        Ok(SaltedBatchMerkleProof { salts, proof })
+    }
+}
@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use core::sync::atomic::{AtomicUsize, Ordering};
+
 use math::fields::f128::BaseElement;
 use proptest::prelude::*;
 
@@ -137,6 +139,30 @@ fn verify() {
     assert!(MerkleTree::<Blake3_256>::verify(*tree.root(), 6, &proof).is_ok());
 }
 
+#[test]
+fn update_leaf() {
+    let mut leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let mut tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    let old_root = *tree.root();
+
+    // updating a leaf should result in the same tree as building it from the updated leaves
+    let new_root = tree.update_leaf(5, leaves[0]).unwrap();
+    leaves[5] = leaves[0];
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_eq!(*expected.root(), new_root);
+    assert_eq!(expected.nodes, tree.nodes);
+    assert_eq!(leaves, tree.leaves());
+
+    // old path should resolve to the old root and new path should resolve to the new root
+    let (old_path, new_path) = tree.update_leaf_with_paths(2, leaves[7]).unwrap();
+    assert!(MerkleTree::<Blake3_256>::verify(new_root, 2, &old_path).is_ok());
+    assert!(MerkleTree::<Blake3_256>::verify(*tree.root(), 2, &new_path).is_ok());
+    assert_eq!(new_path, tree.prove(2).unwrap());
+    assert_ne!(old_root, *tree.root());
+
+    assert_eq!(Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)), tree.update_leaf(8, leaves[0]));
+}
+
 #[test]
 fn prove_batch() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
@@ -187,6 +213,28 @@ fn prove_batch() {
     assert_eq!(3, proof.depth);
 }
 
+#[test]
+fn prove_batch_shared_paths() {
+    let leaves = (0..1024u16).map(|i| Blake3_256::hash(&i.to_le_bytes())).collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    // every sub-tree of 8 leaves contains exactly one of the queried leaves; thus, only the 3
+    // lowest nodes of each path need to be included in the proof
+    let indexes = (0..128).map(|i| i * 8 + i % 8).collect::<Vec<_>>();
+    let proof = tree.prove_batch(&indexes).unwrap();
+    let num_path_nodes = indexes.iter().map(|&i| tree.prove(i).unwrap().len() - 1).sum::<usize>();
+    let num_proof_nodes = proof.nodes.iter().map(|nodes| nodes.len()).sum::<usize>();
+    assert_eq!(128 * 10, num_path_nodes);
+    assert_eq!(128 * 3, num_proof_nodes);
+    assert!(MerkleTree::verify_batch(tree.root(), &indexes, &proof).is_ok());
+
+    // the compressed proof can be expanded back into individual paths
+    let paths = proof.into_paths(&indexes).unwrap();
+    for (&index, path) in indexes.iter().zip(paths) {
+        assert_eq!(tree.prove(index).unwrap(), path);
+    }
+}
+
 #[test]
 fn verify_batch() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
@@ -212,6 +260,301 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn vector_commitment() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = <MerkleTree<Blake3_256> as VectorCommitment<_>>::commit(leaves.clone()).unwrap();
+    assert_eq!(*tree.root(), tree.commitment());
+    assert_eq!(8, tree.domain_len());
+
+    let indexes = [1, 3, 6];
+    let items = [leaves[1], leaves[3], leaves[6]];
+    let proof = tree.open_batch(&indexes).unwrap();
+    let commitment = tree.commitment();
+    assert!(<MerkleTree<Blake3_256> as VectorCommitment<_>>::verify_batch(
+        &commitment,
+        &indexes,
+        &items,
+        &proof
+    )
+    .is_ok());
+
+    // opened items which do not match the proof should be rejected
+    let wrong_items = [leaves[1], leaves[3], leaves[7]];
+    assert!(<MerkleTree<Blake3_256> as VectorCommitment<_>>::verify_batch(
+        &commitment,
+        &indexes,
+        &wrong_items,
+        &proof
+    )
+    .is_err());
+
+    // a proof should survive a serialization round trip
+    let proof_bytes = MerkleTree::<Blake3_256>::serialize_batch_proof(&proof);
+    let mut reader = utils::SliceReader::new(&proof_bytes);
+    let parsed_proof =
+        MerkleTree::<Blake3_256>::deserialize_batch_proof(&mut reader, items.to_vec(), 8).unwrap();
+    assert_eq!(proof, parsed_proof);
+}
+
+#[test]
+fn build_tree_incrementally() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let mut builder = MerkleTreeBuilder::<Blake3_256>::new(8).unwrap();
+    for (i, &leaf) in leaves.iter().enumerate() {
+        assert_eq!(i, builder.num_pushed_leaves());
+        builder.push_leaf(leaf).unwrap();
+    }
+    assert_eq!(Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)), builder.push_leaf(leaves[0]));
+
+    let tree = builder.build().unwrap();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_eq!(expected.root(), tree.root());
+    assert_eq!(expected.nodes, tree.nodes);
+    assert_eq!(leaves, tree.leaves());
+
+    // a tree cannot be built until all leaves have been pushed
+    let mut builder = MerkleTreeBuilder::<Blake3_256>::new(8).unwrap();
+    builder.extend(leaves[..5].iter().copied());
+    assert_eq!(MerkleTreeError::TooFewLeaves(8, 5), builder.build().unwrap_err());
+
+    assert_eq!(
+        MerkleTreeError::NumberOfLeavesNotPowerOfTwo(6),
+        MerkleTreeBuilder::<Blake3_256>::new(6).unwrap_err()
+    );
+}
+
+#[test]
+fn salted_tree() {
+    let values = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree =
+        SaltedMerkleTree::<Blake3_256, TestPrng>::new(values.clone(), &mut TestPrng(1)).unwrap();
+    assert_eq!(8, tree.salts().len());
+    for ((&value, &salt), &leaf) in values.iter().zip(tree.salts()).zip(tree.leaves()) {
+        assert_eq!(hash_2x1(value, salt), leaf);
+    }
+
+    // different salts should result in a different root
+    let other_tree =
+        SaltedMerkleTree::<Blake3_256, TestPrng>::new(values.clone(), &mut TestPrng(2)).unwrap();
+    assert_ne!(tree.root(), other_tree.root());
+
+    let indexes = [1, 6];
+    let opened_values = [values[1], values[6]];
+    let proof = tree.prove_batch(&indexes).unwrap();
+    assert_eq!(vec![tree.salts()[1], tree.salts()[6]], proof.salts);
+    assert!(SaltedMerkleTree::<Blake3_256, TestPrng>::verify_batch(
+        tree.root(),
+        &indexes,
+        &opened_values,
+        &proof
+    )
+    .is_ok());
+
+    // wrong values or wrong salts should be rejected
+    assert!(SaltedMerkleTree::<Blake3_256, TestPrng>::verify_batch(
+        tree.root(),
+        &indexes,
+        &[values[1], values[7]],
+        &proof
+    )
+    .is_err());
+    let mut bad_proof = proof.clone();
+    bad_proof.salts[0] = bad_proof.salts[1];
+    assert!(SaltedMerkleTree::<Blake3_256, TestPrng>::verify_batch(
+        tree.root(),
+        &indexes,
+        &opened_values,
+        &bad_proof
+    )
+    .is_err());
+
+    // a proof should survive a serialization round trip
+    let proof_bytes = proof.serialize();
+    let mut reader = utils::SliceReader::new(&proof_bytes);
+    let parsed_proof =
+        SaltedBatchMerkleProof::<Blake3_256>::deserialize(&mut reader, &opened_values, 8).unwrap();
+    assert_eq!(proof, parsed_proof);
+}
+
+#[test]
+fn kary_new_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+
+    // a tree of arity 2 is the same as a binary Merkle tree
+    let tree = KaryMerkleTree::<Blake3_256, 2>::new(leaves.clone()).unwrap();
+    assert_eq!(MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap().root(), tree.root());
+    assert_eq!(3, tree.depth());
+
+    // the root of a tree of arity 4 over 8 leaves hashes two nodes together
+    let tree = KaryMerkleTree::<Blake3_256, 4>::new(leaves.clone()).unwrap();
+    let node0 = Blake3_256::merge_many(&leaves[..4]);
+    let node1 = Blake3_256::merge_many(&leaves[4..]);
+    assert_eq!(hash_2x1(node0, node1), *tree.root());
+    assert_eq!(2, tree.depth());
+
+    // a tree of arity 8 over 8 leaves consists of the root only
+    let tree = KaryMerkleTree::<Blake3_256, 8>::new(leaves.clone()).unwrap();
+    assert_eq!(Blake3_256::merge_many(&leaves), *tree.root());
+    assert_eq!(1, tree.depth());
+    assert_eq!(leaves, tree.leaves());
+
+    assert_eq!(
+        Err(MerkleTreeError::InvalidArity(3)),
+        KaryMerkleTree::<Blake3_256, 3>::new(leaves).map(|tree| *tree.root())
+    );
+}
+
+#[test]
+fn kary_prove_verify_batch() {
+    let leaves = (0..64u8).map(|i| Blake3_256::hash(&[i])).collect::<Vec<_>>();
+    let indexes = [1, 9, 10, 33, 63];
+    let items = indexes.iter().map(|&i| leaves[i]).collect::<Vec<_>>();
+
+    let tree4 = KaryMerkleTree::<Blake3_256, 4>::new(leaves.clone()).unwrap();
+    let proof4 = tree4.prove_batch(&indexes).unwrap();
+    assert_eq!(items, proof4.leaves);
+    assert!(KaryMerkleTree::<Blake3_256, 4>::verify_batch(tree4.root(), &indexes, &proof4).is_ok());
+
+    let tree8 = KaryMerkleTree::<Blake3_256, 8>::new(leaves.clone()).unwrap();
+    let proof8 = tree8.prove_batch(&indexes).unwrap();
+    assert!(KaryMerkleTree::<Blake3_256, 8>::verify_batch(tree8.root(), &indexes, &proof8).is_ok());
+
+    // trees of higher arity have fewer levels, and verifying a batch proof against them takes
+    // fewer hash calls than against a binary tree with the same leaves
+    let binary_tree = MerkleTree::<CountingHasher>::new(leaves.clone()).unwrap();
+    let binary_proof = binary_tree.prove_batch(&indexes).unwrap();
+    assert!(tree4.depth() < binary_tree.depth());
+    assert!(tree8.depth() < tree4.depth());
+
+    let counting_tree4 = KaryMerkleTree::<CountingHasher, 4>::new(leaves.clone()).unwrap();
+    let counting_proof4 = counting_tree4.prove_batch(&indexes).unwrap();
+    let counting_tree8 = KaryMerkleTree::<CountingHasher, 8>::new(leaves).unwrap();
+    let counting_proof8 = counting_tree8.prove_batch(&indexes).unwrap();
+
+    let binary_merges = count_merges(|| {
+        MerkleTree::verify_batch(binary_tree.root(), &indexes, &binary_proof).unwrap()
+    });
+    let merges4 = count_merges(|| {
+        KaryMerkleTree::<_, 4>::verify_batch(counting_tree4.root(), &indexes, &counting_proof4)
+            .unwrap()
+    });
+    let merges8 = count_merges(|| {
+        KaryMerkleTree::<_, 8>::verify_batch(counting_tree8.root(), &indexes, &counting_proof8)
+            .unwrap()
+    });
+    assert!(merges4 < binary_merges);
+    assert!(merges8 < merges4);
+
+    // a proof should be rejected for wrong indexes, a wrong arity, or a modified node
+    assert!(KaryMerkleTree::<Blake3_256, 4>::verify_batch(
+        tree4.root(),
+        &[1, 9, 11, 33, 63],
+        &proof4
+    )
+    .is_err());
+    assert!(KaryMerkleTree::<Blake3_256, 8>::verify_batch(tree4.root(), &indexes, &proof4).is_err());
+    let mut bad_proof = proof4.clone();
+    bad_proof.nodes[0] = Blake3_256::hash(&[255]);
+    assert!(
+        KaryMerkleTree::<Blake3_256, 4>::verify_batch(tree4.root(), &indexes, &bad_proof).is_err()
+    );
+
+    // duplicate and out-of-bounds indexes should be rejected
+    assert_eq!(Err(MerkleTreeError::DuplicateLeafIndex), tree4.prove_batch(&[1, 1]));
+    assert_eq!(Err(MerkleTreeError::LeafIndexOutOfBounds(64, 64)), tree4.prove_batch(&[64]));
+
+    // a proof should survive a serialization round trip
+    let proof_bytes = KaryMerkleTree::<Blake3_256, 4>::serialize_batch_proof(&proof4);
+    let mut reader = utils::SliceReader::new(&proof_bytes);
+    let parsed_proof =
+        KaryMerkleTree::<Blake3_256, 4>::deserialize_batch_proof(&mut reader, items.clone(), 64)
+            .unwrap();
+    assert_eq!(proof4, parsed_proof);
+    assert!(<KaryMerkleTree<Blake3_256, 4> as VectorCommitment<_>>::verify_batch(
+        &tree4.commitment(),
+        &indexes,
+        &items,
+        &parsed_proof
+    )
+    .is_ok());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mmap_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    let path = std::env::temp_dir().join(format!("winter-mmap-tree-{}.bin", std::process::id()));
+    tree.write_to(&path).unwrap();
+    let mmap_tree = MerkleTree::<Blake3_256>::open_mmap(&path).unwrap();
+
+    assert_eq!(tree.root(), mmap_tree.root());
+    assert_eq!(tree.depth(), mmap_tree.depth());
+    assert_eq!(leaves[5], mmap_tree.leaf(5));
+    assert_eq!(tree.prove(3).unwrap(), mmap_tree.prove(3).unwrap());
+    assert_eq!(
+        tree.prove_batch(&[1, 2, 6]).unwrap(),
+        mmap_tree.prove_batch(&[1, 2, 6]).unwrap()
+    );
+    drop(mmap_tree);
+
+    // a tree for a hash function with a different digest size cannot be loaded from the file
+    type Blake3_192 = crate::hash::Blake3_192<BaseElement>;
+    assert!(matches!(
+        MerkleTree::<Blake3_192>::open_mmap(&path),
+        Err(MerkleTreeError::StorageError(_))
+    ));
+
+    // a truncated file should be rejected
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    assert!(matches!(
+        MerkleTree::<Blake3_256>::open_mmap(&path),
+        Err(MerkleTreeError::StorageError(_))
+    ));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mmap_tree_commitment() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    // committing via the vector commitment interface should produce the same root and proofs
+    let commitment = MmapMerkleTree::<Blake3_256>::commit(leaves.clone()).unwrap();
+    assert_eq!(*tree.root(), commitment.commitment());
+    assert_eq!(8, commitment.domain_len());
+
+    let indexes = [0, 3, 7];
+    let proof = commitment.open_batch(&indexes).unwrap();
+    assert_eq!(tree.prove_batch(&indexes).unwrap(), proof);
+    let items = indexes.iter().map(|&i| leaves[i]).collect::<Vec<_>>();
+    assert!(MmapMerkleTree::<Blake3_256>::verify_batch(
+        &commitment.commitment(),
+        &indexes,
+        &items,
+        &proof
+    )
+    .is_ok());
+
+    // the temporary file backing the commitment should be removed when the tree is dropped
+    let num_temp_files = || {
+        let prefix = format!("winter-merkle-tree-{}-", std::process::id());
+        std::fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .filter(|entry| {
+                entry.as_ref().unwrap().file_name().to_string_lossy().starts_with(&prefix)
+            })
+            .count()
+    };
+    assert_eq!(1, num_temp_files());
+    drop(commitment);
+    assert_eq!(0, num_temp_files());
+}
+
 #[test]
 fn verify_into_paths() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
@@ -299,6 +642,32 @@ fn hash_2x1(v1: Digest256, v2: Digest256) -> Digest256 {
     Blake3_256::merge(&[v1, v2])
 }
 
+/// A simple deterministic pseudo-random generator used for salting Merkle tree leaves in tests.
+struct TestPrng(u64);
+
+impl rand_core::RngCore for TestPrng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // xorshift64
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
 pub fn random_blake3_merkle_tree(
     leave_count: usize,
 ) -> impl Strategy<Value = MerkleTree<Blake3_256>> {
@@ -307,3 +676,39 @@ pub fn random_blake3_merkle_tree(
         MerkleTree::<Blake3_256>::new(leaves).unwrap()
     })
 }
+
+/// Blake3 hasher which counts the number of times digests are merged.
+struct CountingHasher;
+
+static NUM_MERGES: AtomicUsize = AtomicUsize::new(0);
+
+impl Hasher for CountingHasher {
+    type Digest = Digest256;
+
+    const COLLISION_RESISTANCE: u32 = Blake3_256::COLLISION_RESISTANCE;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        Blake3_256::hash(bytes)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        NUM_MERGES.fetch_add(1, Ordering::Relaxed);
+        Blake3_256::merge(values)
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        NUM_MERGES.fetch_add(1, Ordering::Relaxed);
+        Blake3_256::merge_many(values)
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        Blake3_256::merge_with_int(seed, value)
+    }
+}
+
+/// Returns the number of digest merges performed by [CountingHasher] while executing `f`.
+fn count_merges(f: impl FnOnce()) -> usize {
+    let start = NUM_MERGES.load(Ordering::Relaxed);
+    f();
+    NUM_MERGES.load(Ordering::Relaxed) - start
+}
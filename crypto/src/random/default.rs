@@ -3,11 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
+use alloc::{collections::BTreeSet, vec::Vec};
 
 use math::{FieldElement, StarkField};
 
-use crate::{errors::RandomCoinError, Digest, ElementHasher, RandomCoin};
+use super::prng::draw_element;
+use crate::{
+    errors::RandomCoinError, Digest, ElementHasher, RandomCoin, Transcript, TranscriptLabel,
+};
 
 // DEFAULT RANDOM COIN IMPLEMENTATION
 // ================================================================================================
@@ -24,7 +27,8 @@ use crate::{errors::RandomCoinError, Digest, ElementHasher, RandomCoin};
 ///   time, the `seed` is set to a hash of the provided bytes, and the `counter` is set to 0.
 /// - To draw the next element, we increment the `counter` and compute hash(`seed` || `counter`).
 ///   If the resulting value is a valid field element, we return the result; otherwise we try
-///   again until a valid element is found or the number of allowed tries is exceeded.
+///   again until a valid element is found or the number of allowed tries is exceeded. This is
+///   the same procedure as the one used by [FieldPrng](super::FieldPrng).
 /// - We can also re-seed the coin with a new value. During the reseeding procedure, the
 ///   seed is set to hash(`old_seed` || `new_seed`), and the counter is reset to 0.
 ///
@@ -139,27 +143,25 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     /// Returns an error if a valid field element could not be generated after 1000 calls to the
     /// PRNG.
     fn draw<E: FieldElement>(&mut self) -> Result<E, RandomCoinError> {
-        for _ in 0..1000 {
-            // get the next pseudo-random value and take the first ELEMENT_BYTES from it
-            let value = self.next();
-            let bytes = &value.as_bytes()[..E::ELEMENT_BYTES];
-
-            // check if the bytes can be converted into a valid field element; if they can,
-            // return; otherwise try again
-            if let Some(element) = E::from_random_bytes(bytes) {
-                return Ok(element);
-            }
-        }
-
-        Err(RandomCoinError::FailedToDrawFieldElement(1000))
+        draw_element::<E, H>(&self.seed, &mut self.counter)
     }
 
     /// Returns a vector of integers selected from the range [0, domain_size) after reseeding
     /// the PRNG with the specified `nonce` by setting the new seed to hash(`seed` || `nonce`).
     ///
+    /// Values are drawn without replacement: a value which has been drawn already is skipped, and
+    /// another value is drawn in its place. Thus, all returned values are distinct, and they are
+    /// listed in the order in which they were first drawn.
+    ///
+    /// The number of calls to the PRNG is bounded by 1000 plus 8 times the expected number of
+    /// calls needed to draw `num_values` distinct values (i.e., the sum of
+    /// `domain_size / (domain_size - i)` for `i` in `0..num_values`); the probability of not
+    /// drawing enough distinct values within this bound is negligible even when almost all values
+    /// of the domain are requested.
+    ///
     /// # Errors
-    /// Returns an error if the specified number of integers could not be generated after 1000
-    /// calls to the PRNG.
+    /// Returns an error if the specified number of integers could not be generated within the
+    /// above number of calls to the PRNG.
     ///
     /// # Panics
     /// Panics if:
@@ -183,6 +185,10 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     ///
     /// assert_eq!(num_values, values.len());
     ///
+    /// // all values are distinct
+    /// let unique_values: HashSet<_> = values.iter().collect();
+    /// assert_eq!(num_values, unique_values.len());
+    ///
     /// for value in values {
     ///     assert!(value < domain_size);
     /// }
@@ -200,12 +206,24 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         self.seed = H::merge_with_int(self.seed, nonce);
         self.counter = 0;
 
-        // determine how many bits are needed to represent valid values in the domain
+        // determine how many bits are needed to represent valid values in the domain; since the
+        // domain size is a power of two, keeping only these bits of a uniformly random integer
+        // results in a uniformly random value in the domain (i.e., there is no modulo bias)
         let v_mask = (domain_size - 1) as u64;
 
-        // draw values from PRNG until we get as many unique values as specified by num_queries
-        let mut values = Vec::new();
-        for _ in 0..1000 {
+        // determine the maximum number of draws; the expected number of draws needed to get
+        // num_values distinct values grows as the number of values which have not been drawn yet
+        // shrinks (i.e., as in the coupon collector's problem)
+        let expected_draws: usize =
+            (0..num_values).map(|i| domain_size.div_ceil(domain_size - i)).sum();
+        let max_draws = 1000 + 8 * expected_draws;
+
+        // draw values from PRNG until we get as many unique values as specified by num_values;
+        // values which have been drawn already are skipped, and thus, the returned values are
+        // in the order in which they were first drawn
+        let mut values = Vec::with_capacity(num_values);
+        let mut drawn_values = BTreeSet::new();
+        for _ in 0..max_draws {
             // get the next pseudo-random value and read the first 8 bytes from it
             let bytes: [u8; 8] = self.next().as_bytes()[..8].try_into().unwrap();
 
@@ -213,16 +231,104 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
             // into the specified domain
             let value = (u64::from_le_bytes(bytes) & v_mask) as usize;
 
-            values.push(value);
+            if drawn_values.insert(value) {
+                values.push(value);
+            }
             if values.len() == num_values {
                 break;
             }
         }
 
         if values.len() < num_values {
-            return Err(RandomCoinError::FailedToDrawIntegers(num_values, values.len(), 1000));
+            return Err(RandomCoinError::FailedToDrawIntegers(num_values, values.len(), max_draws));
         }
 
         Ok(values)
     }
 }
+
+// TRANSCRIPT IMPLEMENTATION
+// ================================================================================================
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> Transcript for DefaultRandomCoin<H> {
+    /// Absorbs the specified `data` by reseeding the coin with hash(`data` || `label`), thus
+    /// the same data absorbed under different labels results in different coin states.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{
+    /// #     DefaultRandomCoin, Hasher, RandomCoin, Transcript, TranscriptLabel,
+    /// #     hashers::Blake3_256,
+    /// # };
+    /// # use math::fields::f128::BaseElement;
+    /// let seed = &[BaseElement::new(1), BaseElement::new(2)];
+    /// let data = Blake3_256::<BaseElement>::hash(&[1, 2, 3]);
+    ///
+    /// let mut coin1 = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(seed);
+    /// let mut coin2 = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(seed);
+    /// coin1.absorb(TranscriptLabel::TraceCommitment, data);
+    /// coin2.absorb(TranscriptLabel::ConstraintCommitment, data);
+    ///
+    /// let e1 = coin1.draw::<BaseElement>().unwrap();
+    /// let e2 = coin2.draw::<BaseElement>().unwrap();
+    /// assert_ne!(e1, e2);
+    /// ```
+    fn absorb(&mut self, label: TranscriptLabel, data: H::Digest) {
+        self.reseed(H::merge_with_int(data, label.tag()));
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use math::fields::f128::BaseElement;
+
+    use super::{DefaultRandomCoin, RandomCoin};
+    use crate::hashers::Blake3_256;
+
+    type Coin = DefaultRandomCoin<Blake3_256<BaseElement>>;
+
+    #[test]
+    fn draw_integers_without_replacement() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+
+        // almost all values of a small domain can be drawn, and all drawn values are distinct
+        let values = Coin::new(&seed).draw_integers(15, 16, 0).unwrap();
+        let mut unique_values = values.clone();
+        unique_values.sort_unstable();
+        unique_values.dedup();
+        assert_eq!(15, unique_values.len());
+
+        // the order of the values is deterministic
+        assert_eq!(values, Coin::new(&seed).draw_integers(15, 16, 0).unwrap());
+        assert_ne!(values, Coin::new(&seed).draw_integers(15, 16, 1).unwrap());
+
+        // drawing all but one value of a larger domain needs more than 1000 draws on average
+        for nonce in 0..16 {
+            let mut values = Coin::new(&seed).draw_integers(255, 256, nonce).unwrap();
+            values.sort_unstable();
+            values.dedup();
+            assert_eq!(255, values.len());
+        }
+    }
+
+    #[test]
+    fn draw_integers_without_bias() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let domain_size = 8;
+        let num_draws = 8000;
+
+        // count how many times each value of the domain is drawn as the first value; each count
+        // is expected to be 1000 with standard deviation of ~30
+        let mut counts = [0usize; 8];
+        for nonce in 0..num_draws {
+            let values = Coin::new(&seed).draw_integers(1, domain_size, nonce).unwrap();
+            counts[values[0]] += 1;
+        }
+        for count in counts {
+            assert!(count.abs_diff(num_draws as usize / domain_size) < 150);
+        }
+    }
+}
@@ -12,6 +12,12 @@ use crate::{errors::RandomCoinError, ElementHasher, Hasher};
 mod default;
 pub use default::DefaultRandomCoin;
 
+mod prng;
+pub use prng::FieldPrng;
+
+mod transcript;
+pub use transcript::{Transcript, TranscriptLabel};
+
 // RANDOM COIN TRAIT
 // ================================================================================================
 
@@ -49,12 +55,16 @@ pub trait RandomCoin: Sync {
     /// PRNG.
     fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError>;
 
-    /// Returns a vector of integers selected from the range [0, domain_size) after it reseeds
-    /// the coin with a nonce.
+    /// Returns a vector of `num_values` distinct integers selected uniformly at random from the
+    /// range [0, domain_size) after it reseeds the coin with a nonce.
+    ///
+    /// Values are drawn without replacement so that each of them contributes to the soundness of
+    /// the protocol; the order of the returned values must be a deterministic function of the
+    /// state of the coin and the nonce.
     ///
     /// # Errors
-    /// Returns an error if the specified number of integers could not be generated after 1000
-    /// calls to the PRNG.
+    /// Returns an error if the specified number of integers could not be generated within a
+    /// bounded number of calls to the PRNG.
     ///
     /// # Panics
     /// Panics if:
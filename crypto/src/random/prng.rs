@@ -0,0 +1,121 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use math::FieldElement;
+
+use crate::{errors::RandomCoinError, Digest, Hasher};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of PRNG outputs tried when drawing a single field element.
+const MAX_DRAW_ATTEMPTS: usize = 1000;
+
+// FIELD PRNG
+// ================================================================================================
+
+/// Deterministic pseudo-random generator of field elements.
+///
+/// The generator expands a digest seed into a reproducible stream of elements in the field `E`.
+/// The i-th output of the underlying PRNG is computed as hash(`seed` || i), and the first
+/// `E::ELEMENT_BYTES` bytes of the output are interpreted as a field element; outputs which do
+/// not encode a valid field element are rejected.
+///
+/// The same seed always results in the same stream of elements. This makes the generator useful
+/// for deriving values such as trace blinding factors and salts from a secret seed, as well as
+/// for generating test vectors. It is also the element sampler used by [DefaultRandomCoin](
+/// super::DefaultRandomCoin).
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{
+/// #     DefaultRandomCoin, ElementHasher, FieldPrng, RandomCoin, hashers::Blake3_256,
+/// # };
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256<BaseElement>;
+///
+/// let seed = Blake3::hash_elements(&[BaseElement::new(1), BaseElement::new(2)]);
+/// let mut prng1 = FieldPrng::<BaseElement, Blake3>::new(seed);
+/// let mut prng2 = FieldPrng::<BaseElement, Blake3>::new(seed);
+/// assert_eq!(prng1.draw_many(8).unwrap(), prng2.draw_many(8).unwrap());
+///
+/// // the random coin draws the same elements from the same seed
+/// let mut prng = FieldPrng::<BaseElement, Blake3>::new(seed);
+/// let mut coin = DefaultRandomCoin::<Blake3>::new(&[BaseElement::new(1), BaseElement::new(2)]);
+/// assert_eq!(prng.draw().unwrap(), coin.draw::<BaseElement>().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct FieldPrng<E: FieldElement, H: Hasher> {
+    seed: H::Digest,
+    counter: u64,
+    _element: PhantomData<E>,
+}
+
+impl<E: FieldElement, H: Hasher> FieldPrng<E, H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new generator instantiated with the specified `seed`.
+    pub fn new(seed: H::Digest) -> Self {
+        Self { seed, counter: 0, _element: PhantomData }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the seed of this generator.
+    pub fn seed(&self) -> &H::Digest {
+        &self.seed
+    }
+
+    // DRAW METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the next pseudo-random field element.
+    ///
+    /// # Errors
+    /// Returns an error if a valid field element could not be generated after 1000 calls to the
+    /// PRNG.
+    pub fn draw(&mut self) -> Result<E, RandomCoinError> {
+        draw_element::<E, H>(&self.seed, &mut self.counter)
+    }
+
+    /// Returns the next `num_elements` pseudo-random field elements.
+    ///
+    /// # Errors
+    /// Returns an error if any of the elements could not be generated after 1000 calls to the
+    /// PRNG.
+    pub fn draw_many(&mut self, num_elements: usize) -> Result<Vec<E>, RandomCoinError> {
+        (0..num_elements).map(|_| self.draw()).collect()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws the next field element from the PRNG defined by the specified `seed` and `counter`
+/// using rejection sampling; the counter is incremented for every PRNG output consumed.
+pub(super) fn draw_element<E: FieldElement, H: Hasher>(
+    seed: &H::Digest,
+    counter: &mut u64,
+) -> Result<E, RandomCoinError> {
+    for _ in 0..MAX_DRAW_ATTEMPTS {
+        // get the next pseudo-random value and take the first ELEMENT_BYTES from it
+        *counter += 1;
+        let value = H::merge_with_int(*seed, *counter);
+        let bytes = &value.as_bytes()[..E::ELEMENT_BYTES];
+
+        // check if the bytes can be converted into a valid field element; if they can,
+        // return; otherwise try again
+        if let Some(element) = E::from_random_bytes(bytes) {
+            return Ok(element);
+        }
+    }
+
+    Err(RandomCoinError::FailedToDrawFieldElement(MAX_DRAW_ATTEMPTS))
+}
@@ -0,0 +1,101 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::FieldElement;
+
+use super::RandomCoin;
+use crate::{errors::RandomCoinError, Hasher};
+
+// TRANSCRIPT LABELS
+// ================================================================================================
+
+/// Domain-separation labels for the phases of the STARK, FRI, and sum-check protocols.
+///
+/// Every value absorbed into (or squeezed out of) a [Transcript] is tagged with the label of the
+/// protocol phase it belongs to. This guarantees that data sent by the prover in one phase cannot
+/// be re-interpreted as data belonging to a different phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TranscriptLabel {
+    /// Commitment to a segment of the execution trace.
+    TraceCommitment = 1,
+    /// Commitment to the evaluations of the constraint composition polynomial.
+    ConstraintCommitment = 2,
+    /// Out-of-domain evaluation frame of the trace polynomials.
+    OodTraceFrame = 3,
+    /// Out-of-domain evaluations of the constraint composition polynomial columns.
+    OodConstraintEvaluations = 4,
+    /// Commitment to a FRI (or STIR) layer.
+    FriLayer = 5,
+    /// DEEP values of a FRI (or STIR) layer.
+    FriDeepValues = 6,
+    /// Seed from which query positions are drawn.
+    QuerySeed = 7,
+    /// Round polynomial of the sum-check protocol.
+    SumCheckRound = 8,
+}
+
+impl TranscriptLabel {
+    /// Returns an integer tag uniquely identifying this label.
+    pub const fn tag(&self) -> u64 {
+        *self as u64
+    }
+}
+
+// TRANSCRIPT TRAIT
+// ================================================================================================
+
+/// Fiat-Shamir transcript with labeled absorb and squeeze operations.
+///
+/// A transcript extends a [RandomCoin] with operations which explicitly specify the protocol
+/// phase (via a [TranscriptLabel]) for which data is absorbed into or randomness is squeezed out
+/// of the transcript. Both the prover and the verifier drive the transcript through the same
+/// sequence of labeled operations, and thus, transcripts which bind labels into their state
+/// (e.g., Merlin-style transcripts) can be used in place of the [DefaultRandomCoin](
+/// super::DefaultRandomCoin).
+///
+/// Squeeze operations are provided with default implementations which ignore the label and draw
+/// values directly from the underlying random coin.
+pub trait Transcript: RandomCoin {
+    /// Absorbs the specified `data` into the transcript under the specified `label`.
+    fn absorb(&mut self, label: TranscriptLabel, data: <Self::Hasher as Hasher>::Digest);
+
+    /// Returns the next pseudo-random field element squeezed out of the transcript under the
+    /// specified `label`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid field element could not be generated.
+    fn squeeze<E: FieldElement<BaseField = Self::BaseField>>(
+        &mut self,
+        label: TranscriptLabel,
+    ) -> Result<E, RandomCoinError> {
+        let _ = label;
+        self.draw()
+    }
+
+    /// Returns a vector of `num_values` distinct integers selected from the range [0, domain_size)
+    /// squeezed out of the transcript under the specified `label` after the transcript absorbs the
+    /// `nonce`.
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of integers could not be generated.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `domain_size` is not a power of two.
+    /// - `num_values` is greater than or equal to `domain_size`.
+    fn squeeze_integers(
+        &mut self,
+        label: TranscriptLabel,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        let _ = label;
+        self.draw_integers(num_values, domain_size, nonce)
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+use math::FieldElement;
+use rand_core::RngCore;
+
+use crate::{ElementHasher, FieldPrng};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Value merged with the secret key to derive the public key.
+const PUBLIC_KEY_DOMAIN: u64 = 0;
+
+// SECRET KEY
+// ================================================================================================
+
+/// Secret key of a verifiable random function (VRF) instantiated with the hash function `H`.
+///
+/// The function is defined by the following relations, where `sk` is the secret key and `x` is
+/// the input:
+///
+/// * Public key: `pk = H::merge_with_int(sk, 0)`.
+/// * Output: `y = H::merge([sk, H::hash_elements(x)])`.
+///
+/// When instantiated with [Rp64_256](crate::hashers::Rp64_256), computing either `pk` or `y`
+/// requires a single application of the Rescue permutation, and thus, the relation between `pk`,
+/// `x`, and `y` can be cheaply proven in an AIR; such a proof serves as the VRF proof. For the
+/// proof not to leak the secret key, the STARK used to prove the relation must be zero-knowledge.
+pub struct VrfSecretKey<H: ElementHasher> {
+    key: H::Digest,
+}
+
+impl<H: ElementHasher> VrfSecretKey<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new secret key derived from 32 bytes drawn from the specified `rng`.
+    pub fn generate<R: RngCore>(rng: &mut R) -> Self {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        Self { key: H::hash(&seed) }
+    }
+
+    /// Returns a secret key consisting of the specified digest.
+    pub fn from_digest(key: H::Digest) -> Self {
+        Self { key }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key corresponding to this secret key.
+    pub fn public_key(&self) -> VrfPublicKey<H> {
+        VrfPublicKey {
+            key: H::merge_with_int(self.key, PUBLIC_KEY_DOMAIN),
+        }
+    }
+
+    /// Returns the digest this secret key consists of.
+    pub fn as_digest(&self) -> &H::Digest {
+        &self.key
+    }
+
+    // EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the VRF at the specified `input`.
+    pub fn evaluate<E>(&self, input: &[E]) -> VrfOutput<H>
+    where
+        E: FieldElement<BaseField = H::BaseField>,
+    {
+        VrfOutput {
+            value: H::merge(&[self.key, H::hash_elements(input)]),
+        }
+    }
+}
+
+impl<H: ElementHasher> fmt::Debug for VrfSecretKey<H> {
+    /// Formats the secret key without revealing its value.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VrfSecretKey(..)")
+    }
+}
+
+// PUBLIC KEY
+// ================================================================================================
+
+/// Public key of a verifiable random function instantiated with the hash function `H`.
+pub struct VrfPublicKey<H: ElementHasher> {
+    key: H::Digest,
+}
+
+impl<H: ElementHasher> VrfPublicKey<H> {
+    /// Returns a public key consisting of the specified digest.
+    pub fn from_digest(key: H::Digest) -> Self {
+        Self { key }
+    }
+
+    /// Returns the digest this public key consists of.
+    pub fn as_digest(&self) -> &H::Digest {
+        &self.key
+    }
+}
+
+// Clone, Debug, and PartialEq are implemented manually because deriving them would require `H`
+// to implement these traits.
+impl<H: ElementHasher> Clone for VrfPublicKey<H> {
+    fn clone(&self) -> Self {
+        Self { key: self.key }
+    }
+}
+
+impl<H: ElementHasher> fmt::Debug for VrfPublicKey<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VrfPublicKey").field(&self.key).finish()
+    }
+}
+
+impl<H: ElementHasher> PartialEq for VrfPublicKey<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<H: ElementHasher> Eq for VrfPublicKey<H> {}
+
+// VRF OUTPUT
+// ================================================================================================
+
+/// Output of a verifiable random function instantiated with the hash function `H`.
+pub struct VrfOutput<H: ElementHasher> {
+    value: H::Digest,
+}
+
+impl<H: ElementHasher> VrfOutput<H> {
+    /// Returns the digest this output consists of.
+    pub fn as_digest(&self) -> &H::Digest {
+        &self.value
+    }
+
+    /// Returns a pseudo-random generator of field elements seeded with this output.
+    pub fn to_prng<E>(&self) -> FieldPrng<E, H>
+    where
+        E: FieldElement<BaseField = H::BaseField>,
+    {
+        FieldPrng::new(self.value)
+    }
+}
+
+// Clone, Debug, and PartialEq are implemented manually because deriving them would require `H`
+// to implement these traits.
+impl<H: ElementHasher> Clone for VrfOutput<H> {
+    fn clone(&self) -> Self {
+        Self { value: self.value }
+    }
+}
+
+impl<H: ElementHasher> fmt::Debug for VrfOutput<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VrfOutput").field(&self.value).finish()
+    }
+}
+
+impl<H: ElementHasher> PartialEq for VrfOutput<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<H: ElementHasher> Eq for VrfOutput<H> {}
@@ -0,0 +1,68 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::{fields::f64::BaseElement, FieldElement};
+use rand_core::{impls, RngCore};
+
+use super::{VrfPublicKey, VrfSecretKey};
+use crate::{hashers::Rp64_256, ElementHasher, Hasher};
+
+#[test]
+fn vrf_keys_and_evaluation() {
+    let mut rng = TestPrng(7);
+    let sk = VrfSecretKey::<Rp64_256>::generate(&mut rng);
+    let pk = sk.public_key();
+
+    // the public key and the outputs are computed with a single permutation each
+    assert_eq!(Rp64_256::merge_with_int(*sk.as_digest(), 0), *pk.as_digest());
+    let input = [BaseElement::new(1), BaseElement::new(2), BaseElement::new(3)];
+    let output = sk.evaluate(&input);
+    let input_digest = Rp64_256::hash_elements(&input);
+    assert_eq!(Rp64_256::merge(&[*sk.as_digest(), input_digest]), *output.as_digest());
+
+    // evaluation is deterministic, but different inputs result in different outputs
+    assert_eq!(output, sk.evaluate(&input));
+    assert_ne!(output, sk.evaluate(&input[..2]));
+
+    // different keys result in different public keys and outputs
+    let sk2 = VrfSecretKey::<Rp64_256>::generate(&mut rng);
+    assert_ne!(pk, sk2.public_key());
+    assert_ne!(output, sk2.evaluate(&input));
+    assert_eq!(pk, VrfPublicKey::from_digest(*sk.public_key().as_digest()));
+
+    // outputs can be expanded into field elements
+    let elements = output.to_prng::<BaseElement>().draw_many(4).unwrap();
+    assert_eq!(elements, output.to_prng::<BaseElement>().draw_many(4).unwrap());
+    assert!(elements.iter().any(|&e| e != BaseElement::ZERO));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// A simple deterministic pseudo-random generator used for generating keys in tests.
+struct TestPrng(u64);
+
+impl RngCore for TestPrng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // xorshift64
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
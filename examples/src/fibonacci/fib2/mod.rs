@@ -17,10 +17,10 @@ use super::utils::compute_fib_term;
 use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
 
 mod air;
-use air::FibAir;
+pub(crate) use air::FibAir;
 
 mod prover;
-use prover::FibProver;
+pub(crate) use prover::FibProver;
 
 #[cfg(test)]
 mod tests;
@@ -4,9 +4,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use winterfell::{
-    matrix::ColMatrix, AuxRandElements, ConstraintCompositionCoefficients,
-    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
-    TraceTable,
+    matrix::ColMatrix, AuxRandElements, CompositionPoly, ConstraintCompositionCoefficients,
+    DefaultConstraintCommitment, DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace,
+    TraceInfo, TracePolyTable, TraceTable,
 };
 
 use super::{
@@ -60,6 +60,8 @@ where
     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
         DefaultConstraintEvaluator<'a, Self::Air, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
         let last_step = trace.length() - 1;
@@ -87,4 +89,12 @@ where
     ) -> Self::ConstraintEvaluator<'a, E> {
         DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
     }
+
+    fn new_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E> {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
 }
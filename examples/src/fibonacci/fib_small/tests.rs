@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use winterfell::{FieldExtension, ProofOptions};
+
 use super::{super::utils::build_proof_options, Rp64_256};
 
 #[test]
@@ -17,6 +19,13 @@ fn fib_small_test_basic_proof_verification_extension() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn fib_small_test_basic_proof_verification_cubic_extension() {
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::Cubic, 4, 7);
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
 #[test]
 fn fib_small_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Rp64_256>::new(128, build_proof_options(false)));
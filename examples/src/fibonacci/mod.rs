@@ -9,4 +9,4 @@ pub mod fib_small;
 pub mod mulfib2;
 pub mod mulfib8;
 
-mod utils;
+pub(crate) mod utils;
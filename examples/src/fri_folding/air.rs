@@ -0,0 +1,328 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{
+    math::ToElements,
+    recursion::{domain_point, fold_position, QueryWitness},
+    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+use super::{BaseElement, FieldElement, ProofOptions, TRACE_WIDTH};
+use crate::utils::{are_equal, is_binary, not, EvaluationResult};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Minimum length of an execution trace supported by the prover.
+const MIN_TRACE_LENGTH: usize = 8;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Values against which the queries of a single inner proof are checked.
+///
+/// In a complete recursive verifier, layer values would be authenticated against the FRI layer
+/// commitments of the inner proof, and the folding coefficients and positions would be derived
+/// from the transcript of the inner proof, both inside the outer computation.
+#[derive(Clone)]
+pub struct FriQueries {
+    /// Size of the LDE domain of the inner proof.
+    pub domain_size: usize,
+    /// Queried positions of the inner proof.
+    pub positions: Vec<usize>,
+    /// Evaluations of the DEEP composition polynomial of the inner proof at the queried positions.
+    pub evaluations: Vec<BaseElement>,
+    /// For each query, the pair of values opened at each of the FRI layers.
+    pub layer_values: Vec<Vec<[BaseElement; 2]>>,
+    /// Values α used to fold each of the FRI layers.
+    pub alphas: Vec<BaseElement>,
+    /// The constant to which the last FRI layer is folded.
+    pub remainder: BaseElement,
+}
+
+impl FriQueries {
+    /// Returns the values checked for the inner proof from which the specified `witness` was
+    /// extracted.
+    ///
+    /// # Panics
+    /// Panics if FRI layers of the inner proof are not folded by a factor of 2 down to a constant.
+    pub fn from_witness(witness: QueryWitness<BaseElement>) -> Self {
+        assert_eq!(1, witness.remainder.len(), "remainder of the inner proof must be a constant");
+        let layer_values = witness
+            .layer_values
+            .iter()
+            .map(|layers| {
+                layers
+                    .iter()
+                    .map(|values| {
+                        values
+                            .as_slice()
+                            .try_into()
+                            .expect("layers must be folded by a factor of 2")
+                    })
+                    .collect()
+            })
+            .collect();
+        let num_layers = witness.layer_values[0].len();
+
+        FriQueries {
+            domain_size: witness.domain_size,
+            positions: witness.challenges.query_positions,
+            evaluations: witness.deep_evaluations,
+            layer_values,
+            alphas: witness.challenges.fri_alphas[..num_layers].to_vec(),
+            remainder: witness.remainder[0],
+        }
+    }
+
+    /// Returns the number of FRI layers folded by the inner proof.
+    pub fn num_layers(&self) -> usize {
+        self.alphas.len()
+    }
+}
+
+/// Values against which the queries of all inner proofs are checked.
+#[derive(Clone)]
+pub struct PublicInputs {
+    /// Offset of the LDE domain shared by all inner proofs.
+    pub domain_offset: BaseElement,
+    /// Values checked for each of the inner proofs.
+    pub proofs: Vec<FriQueries>,
+}
+
+impl PublicInputs {
+    /// Returns the number of trace rows used to check a single query; this is enough to hold all
+    /// layers of the inner proof with the largest number of FRI layers followed by the remainder.
+    pub fn block_length(&self) -> usize {
+        let num_layers = self.proofs.iter().map(FriQueries::num_layers).max().unwrap_or(0);
+        (num_layers + 1).next_power_of_two()
+    }
+
+    /// Returns the number of blocks in the execution trace; this is the total number of queries
+    /// across all inner proofs plus at least one padding block, rounded up to the next power of
+    /// two.
+    ///
+    /// All rows of padding blocks are zeros; since there is always at least one such block, the
+    /// layer flag and α columns are never periodic, and thus, the degrees of the transition
+    /// constraints do not depend on the inner proofs.
+    pub fn num_blocks(&self) -> usize {
+        let num_queries: usize = self.proofs.iter().map(|proof| proof.positions.len()).sum();
+        (num_queries + 1)
+            .next_power_of_two()
+            .max(MIN_TRACE_LENGTH / self.block_length())
+    }
+
+    /// Returns the inner proof and the index of the query checked by the specified block, or
+    /// None if the block is a padding block.
+    pub fn block_query(&self, block: usize) -> Option<(&FriQueries, usize)> {
+        let mut idx = block;
+        for proof in self.proofs.iter() {
+            if idx < proof.positions.len() {
+                return Some((proof, idx));
+            }
+            idx -= proof.positions.len();
+        }
+        None
+    }
+
+    /// Returns the row of the specified block which describes the FRI layer at the specified
+    /// depth; all rows following the remainder row, as well as all rows of padding blocks, are
+    /// zeros.
+    pub fn block_row(&self, block: usize, depth: usize) -> [BaseElement; TRACE_WIDTH] {
+        let Some((proof, query)) = self.block_query(block) else {
+            return [BaseElement::ZERO; TRACE_WIDTH];
+        };
+        let num_layers = proof.num_layers();
+        let offset = self.domain_offset;
+
+        if depth < num_layers {
+            let domain_size = proof.domain_size >> depth;
+            let position = proof.positions[query] % domain_size;
+            let (folded_position, bit) = fold_position(position, domain_size, 2);
+            let [v0, v1] = proof.layer_values[query][depth];
+            [
+                domain_point(offset, domain_size, folded_position),
+                v0,
+                v1,
+                BaseElement::from(bit as u64),
+                if bit == 0 { v0 } else { v1 },
+                BaseElement::ONE,
+                proof.alphas[depth],
+            ]
+        } else if depth == num_layers {
+            // the point of the remainder row is fixed by the point of the last layer
+            let p = self.block_row(block, depth - 1)[0];
+            let remainder = proof.remainder;
+            [
+                p.square() / offset,
+                remainder,
+                remainder,
+                BaseElement::ZERO,
+                remainder,
+                BaseElement::ZERO,
+                BaseElement::ZERO,
+            ]
+        } else {
+            [BaseElement::ZERO; TRACE_WIDTH]
+        }
+    }
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = vec![self.domain_offset];
+        for proof in self.proofs.iter() {
+            result.push(BaseElement::from(proof.domain_size as u64));
+            result.extend(proof.positions.iter().map(|&p| BaseElement::from(p as u64)));
+            result.extend_from_slice(&proof.evaluations);
+            result.extend(proof.layer_values.iter().flatten().flatten());
+            result.extend_from_slice(&proof.alphas);
+            result.push(proof.remainder);
+        }
+        result
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.domain_offset);
+        target.write_usize(self.proofs.len());
+        for proof in self.proofs.iter() {
+            target.write_usize(proof.domain_size);
+            target.write_usize(proof.positions.len());
+            for &position in proof.positions.iter() {
+                target.write_usize(position);
+            }
+            target.write_many(&proof.evaluations);
+            for values in proof.layer_values.iter() {
+                for pair in values {
+                    target.write(*pair);
+                }
+            }
+            target.write_usize(proof.alphas.len());
+            target.write_many(&proof.alphas);
+            target.write(proof.remainder);
+        }
+    }
+}
+
+// FRI FOLDING AIR
+// ================================================================================================
+
+/// Checks the FRI queries of one or more inner proofs folded by a factor of 2 down to a
+/// constant.
+///
+/// Each query is checked by a block of rows; row r of the block describes the r-th FRI layer
+/// using the following columns:
+/// - p: x coordinate of the folded position in the domain of the layer.
+/// - v0, v1: values of the layer polynomial at p and -p.
+/// - b: index of the queried value among v0 and v1.
+/// - f: the queried value.
+/// - m: set to 1 if the row describes a FRI layer, and to 0 otherwise.
+/// - α: the value used to fold the layer.
+///
+/// The row following the last layer holds the remainder in v0, v1, and f. Since inner proofs
+/// may fold different numbers of layers, m and α are fixed by assertions rather than by periodic
+/// columns.
+pub struct FriFoldingAir {
+    context: AirContext<BaseElement>,
+    pub_inputs: PublicInputs,
+}
+
+impl Air for FriFoldingAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let block_length = pub_inputs.block_length();
+        let degrees = vec![
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+        ];
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert_eq!(block_length * pub_inputs.num_blocks(), trace_info.length());
+        let num_assertions = 5 * block_length + 2;
+        FriFoldingAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let (p, v0, v1, b, f) = (current[0], current[1], current[2], current[3], current[4]);
+        let layer_flag = current[5];
+        let alpha = current[6];
+
+        // the queried value of the next layer is the value of the current layer folded with
+        // alpha; this is the relation computed by recursion::fold_evaluations() for N = 2
+        let two_p = p + p;
+        result.agg_constraint(
+            0,
+            layer_flag,
+            are_equal(two_p * next[4], p * (v0 + v1) + alpha * (v0 - v1)),
+        );
+
+        // the folded position in the next layer is derived from the folded position in the
+        // current layer and the index of the next queried value: p^2 = offset * p' * (-1)^b'
+        let offset = E::from(self.pub_inputs.domain_offset);
+        result.agg_constraint(
+            1,
+            layer_flag,
+            are_equal(p * p, offset * next[0] * (not(next[3]) - next[3])),
+        );
+
+        // the queried value is v0 when b = 0, and v1 when b = 1
+        result[2] = are_equal(f, v0 + b * (v1 - v0));
+        result[3] = is_binary(b);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let inputs = &self.pub_inputs;
+        let stride = inputs.block_length();
+        let num_blocks = inputs.num_blocks();
+
+        let mut result = Vec::with_capacity(5 * stride + 2);
+        for row in 0..stride {
+            let states: Vec<_> = (0..num_blocks).map(|i| inputs.block_row(i, row)).collect();
+            let column = |idx: usize| states.iter().map(|state| state[idx]).collect::<Vec<_>>();
+
+            // the first row of each block starts at the queried position of the first layer and
+            // holds the evaluation of the DEEP composition polynomial at this position
+            if row == 0 {
+                result.push(Assertion::sequence(0, row, stride, column(0)));
+                result.push(Assertion::sequence(4, row, stride, column(4)));
+            }
+
+            // in each layer, the queried value is selected by the position of the query, and the
+            // opened values and folding coefficients are the values of the inner proof; the
+            // layer following the last one holds the remainder
+            for idx in [1, 2, 3, 5, 6] {
+                result.push(Assertion::sequence(idx, row, stride, column(idx)));
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Checks FRI folding at the queried positions of a Fibonacci proof inside another proof.
+//!
+//! This is a partial recursion example: it demonstrates how the FRI folding computations exposed
+//! via `winterfell::recursion` can be re-executed inside an AIR, but the resulting proof does not
+//! attest to the validity of the inner proof. The inner proof attests to the computation of a
+//! Fibonacci sequence (see [fib2](crate::fibonacci::fib2)), and the outer proof attests that:
+//! - The evaluations of the DEEP composition polynomial of the inner proof at the queried
+//!   positions are consistent with the values opened at the first FRI layer.
+//! - The values opened at each FRI layer fold into the values opened at the next layer, and
+//!   the values of the last layer fold into the remainder.
+//!
+//! The DEEP composition polynomial evaluations are not derived from the trace and constraint
+//! openings of the inner proof, and Merkle authentication paths of the opened values and the
+//! public coin of the inner proof are not arithmetized either. Instead, the inner proof is
+//! checked by the regular verifier, and the values derived from it are passed to the outer proof
+//! as public inputs.
+
+use core::marker::PhantomData;
+use std::time::Instant;
+
+use tracing::{field, info_span};
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement},
+    recursion::extract_query_witness,
+    FieldExtension, Proof, ProofOptions, Prover, Trace, VerifierError,
+};
+
+use crate::{
+    fibonacci::{
+        fib2::{FibAir, FibProver},
+        utils::compute_fib_term,
+    },
+    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256,
+};
+
+mod air;
+pub use air::{FriFoldingAir, FriQueries, PublicInputs};
+
+mod prover;
+pub use prover::FriFoldingProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+pub(crate) const TRACE_WIDTH: usize = 7;
+
+// FRI FOLDING EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    sequence_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(FriFoldingExample::<Blake3_192>::new(sequence_length, options)))
+        },
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(FriFoldingExample::<Blake3_256>::new(sequence_length, options)))
+        },
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(FriFoldingExample::<Sha3_256>::new(sequence_length, options)))
+        },
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct FriFoldingExample<H: ElementHasher> {
+    options: ProofOptions,
+    pub_inputs: PublicInputs,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FriFoldingExample<H> {
+    pub fn new(sequence_length: usize, options: ProofOptions) -> Self {
+        assert!(sequence_length.is_power_of_two(), "sequence length must be a power of 2");
+
+        // generate and verify the inner proof; FRI layers of the inner proof are folded by a
+        // factor of 2 down to a constant, so that all layers are checked by the same constraints
+        let now = Instant::now();
+        let inner_options = ProofOptions::new(28, 8, 0, FieldExtension::None, 2, 0);
+        let prover = FibProver::<Blake3_256>::new(inner_options.clone());
+        let inner_proof = prover.prove(prover.build_trace(sequence_length)).unwrap();
+        let result = compute_fib_term(sequence_length);
+        winterfell::verify::<FibAir, Blake3_256, DefaultRandomCoin<Blake3_256>>(
+            inner_proof.clone(),
+            result,
+            &winterfell::AcceptableOptions::OptionSet(vec![inner_options]),
+        )
+        .expect("failed to verify the inner proof");
+        println!(
+            "Generated and verified inner proof for Fibonacci sequence up to {}th term in {} ms",
+            sequence_length,
+            now.elapsed().as_millis()
+        );
+
+        FriFoldingExample {
+            options,
+            pub_inputs: build_pub_inputs(inner_proof, result),
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for FriFoldingExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> Proof {
+        println!(
+            "Generating proof for checking {} FRI queries of the inner proof",
+            self.pub_inputs.proofs[0].positions.len()
+        );
+
+        // create a prover
+        let prover = FriFoldingProver::<H>::new(self.options.clone(), self.pub_inputs.clone());
+
+        // generate execution trace
+        let trace =
+            info_span!("generate_execution_trace", num_cols = TRACE_WIDTH, steps = field::Empty)
+                .in_scope(|| {
+                    let trace = prover.build_trace();
+                    tracing::Span::current().record("steps", trace.length());
+                    trace
+                });
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: Proof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<FriFoldingAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.pub_inputs.clone(),
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: Proof) -> Result<(), VerifierError> {
+        let mut pub_inputs = self.pub_inputs.clone();
+        pub_inputs.proofs[0].evaluations[0] += BaseElement::ONE;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<FriFoldingAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Extracts the values checked by the outer proof from the specified inner proof.
+///
+/// Query positions and folding coefficients are derived by replaying the transcript of the inner
+/// proof, and evaluations of the DEEP composition polynomial are computed from the opened trace
+/// and constraint evaluations of the inner proof.
+fn build_pub_inputs(proof: Proof, result: BaseElement) -> PublicInputs {
+    let witness = extract_query_witness::<FibAir, BaseElement, Blake3_256, DefaultRandomCoin<_>>(
+        proof, result,
+    )
+    .unwrap();
+
+    PublicInputs {
+        domain_offset: witness.domain_offset,
+        proofs: vec![FriQueries::from_witness(witness)],
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{
+    recursion::{domain_point, fold_evaluations, fold_position},
+    FieldExtension, ProofOptions,
+};
+
+use super::Blake3_256;
+
+#[test]
+fn fri_folding_test_basic_proof_verification() {
+    let fri_folding =
+        Box::new(super::FriFoldingExample::<Blake3_256>::new(64, build_options(false)));
+    crate::tests::test_basic_proof_verification(fri_folding);
+}
+
+#[test]
+fn fri_folding_test_basic_proof_verification_extension() {
+    let fri_folding =
+        Box::new(super::FriFoldingExample::<Blake3_256>::new(64, build_options(true)));
+    crate::tests::test_basic_proof_verification(fri_folding);
+}
+
+#[test]
+fn fri_folding_test_basic_proof_verification_fail() {
+    let fri_folding =
+        Box::new(super::FriFoldingExample::<Blake3_256>::new(64, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(fri_folding);
+}
+
+#[test]
+fn fri_folding_test_folding_consistency() {
+    let fri_folding = super::FriFoldingExample::<Blake3_256>::new(64, build_options(false));
+    let offset = fri_folding.pub_inputs.domain_offset;
+    let inputs = &fri_folding.pub_inputs.proofs[0];
+
+    // the values opened at each FRI layer of the inner proof fold into the values opened at the
+    // next layer, and the values of the last layer fold into the remainder
+    for (&position, values) in inputs.positions.iter().zip(&inputs.layer_values) {
+        let mut domain_size = inputs.domain_size;
+        let mut position = position;
+        for (depth, (pair, &alpha)) in values.iter().zip(&inputs.alphas).enumerate() {
+            let (folded_position, _) = fold_position(position, domain_size, 2);
+            let x = domain_point(offset, domain_size, folded_position);
+            let folded = fold_evaluations(pair, x, alpha);
+            domain_size /= 2;
+            match values.get(depth + 1) {
+                Some(next) => {
+                    let (_, idx) = fold_position(folded_position, domain_size, 2);
+                    assert_eq!(next[idx], folded);
+                },
+                None => assert_eq!(inputs.remainder, folded),
+            }
+            position = folded_position;
+        }
+    }
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
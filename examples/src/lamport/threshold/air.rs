@@ -5,7 +5,7 @@
 
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements},
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
     TransitionConstraintDegree,
 };
 
@@ -40,6 +40,15 @@ impl ToElements<BaseElement> for PublicInputs {
     }
 }
 
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.pub_key_root);
+        target.write_usize(self.num_pub_keys);
+        target.write_usize(self.num_signatures);
+        target.write(self.message);
+    }
+}
+
 pub struct LamportThresholdAir {
     context: AirContext<BaseElement>,
     pub_key_root: [BaseElement; 2],
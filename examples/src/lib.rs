@@ -11,6 +11,7 @@ use winterfell::{
 };
 
 pub mod fibonacci;
+pub mod fri_folding;
 #[cfg(feature = "std")]
 pub mod lamport;
 #[cfg(feature = "std")]
@@ -164,6 +165,12 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1048575")]
         num_steps: usize,
     },
+    /// Check FRI folding of a Fibonacci proof inside another proof (partial recursion)
+    FriFolding {
+        /// Length of Fibonacci sequence of the inner proof; must be a power of two
+        #[structopt(short = "n", default_value = "1024")]
+        sequence_length: usize,
+    },
     /// Compute a hash chain using Rescue hash function
     Rescue {
         /// Length of the hash chain; must be a power of two
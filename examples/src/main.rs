@@ -5,7 +5,7 @@
 
 use std::time::Instant;
 
-use examples::{fibonacci, rescue, vdf, ExampleOptions, ExampleType};
+use examples::{fibonacci, fri_folding, rescue, vdf, ExampleOptions, ExampleType};
 #[cfg(feature = "std")]
 use examples::{lamport, merkle, rescue_raps};
 use structopt::StructOpt;
@@ -67,6 +67,9 @@ fn main() {
         },
         ExampleType::Vdf { num_steps } => vdf::regular::get_example(&options, num_steps),
         ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(&options, num_steps),
+        ExampleType::FriFolding { sequence_length } => {
+            fri_folding::get_example(&options, sequence_length)
+        },
         ExampleType::Rescue { chain_length } => rescue::get_example(&options, chain_length),
         #[cfg(feature = "std")]
         ExampleType::RescueRaps { chain_length } => {
@@ -5,8 +5,8 @@
 
 use core_utils::flatten_slice_elements;
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable,
+    TraceInfo, TransitionConstraintDegree,
 };
 
 use super::{
@@ -51,6 +51,12 @@ impl ToElements<BaseElement> for PublicInputs {
     }
 }
 
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.result);
+    }
+}
+
 pub struct RescueRapsAir {
     context: AirContext<BaseElement>,
     result: [[BaseElement; 2]; 2],
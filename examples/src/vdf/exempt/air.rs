@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable,
+    TraceInfo, TransitionConstraintDegree,
 };
 
 use super::{BaseElement, FieldElement, ProofOptions, ALPHA, FORTY_TWO, TRACE_WIDTH};
@@ -25,6 +25,13 @@ impl ToElements<BaseElement> for VdfInputs {
     }
 }
 
+impl Serializable for VdfInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.seed);
+        target.write(self.result);
+    }
+}
+
 // VDF AIR
 // ================================================================================================
 
@@ -4,7 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
-use math::{fields::f128::BaseElement, get_power_series, polynom, StarkField};
+use math::{
+    fields::f128::{montgomery, BaseElement},
+    get_power_series, polynom, StarkField,
+};
 use rand_utils::{rand_value, rand_vector};
 use utils::group_slice_elements;
 use winter_fri::folding;
@@ -31,6 +34,13 @@ pub fn apply_drp(c: &mut Criterion) {
         group.bench_function(BenchmarkId::new("base field", size), |b| {
             b.iter(|| folding::apply_drp(&ys, BaseElement::GENERATOR, alpha))
         });
+
+        // the same projection over the field in Montgomery representation
+        let ys = ys.iter().map(|y| y.map(montgomery::BaseElement::from)).collect::<Vec<_>>();
+        let alpha = montgomery::BaseElement::from(alpha);
+        group.bench_function(BenchmarkId::new("base field (montgomery)", size), |b| {
+            b.iter(|| folding::apply_drp(&ys, montgomery::BaseElement::GENERATOR, alpha))
+        });
     }
 }
 
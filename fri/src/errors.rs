@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::string::String;
 use core::fmt;
 
 use crypto::RandomCoinError;
@@ -16,24 +17,42 @@ pub enum VerifierError {
     /// Attempt to draw a random value from a public coin failed.
     RandomCoinError(RandomCoinError),
     /// Folding factor specified for the protocol is not supported. Currently, supported folding
-    /// factors are: 4, 8, and 16.
+    /// factors are: 2, 4, 8, 16, 32, and 64.
     UnsupportedFoldingFactor(usize),
     /// Number of query positions does not match the number of provided evaluations.
     NumPositionEvaluationMismatch(usize, usize),
-    /// Evaluations at queried positions did not match layer commitment made by the prover.
-    LayerCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at one of the layers.
-    InvalidLayerFolding(usize),
+    /// Evaluations at queried positions did not match the commitment made by the prover for the
+    /// layer at the specified index.
+    LayerCommitmentMismatch(usize),
+    /// Degree-respecting projection was not performed correctly at the layer with the specified
+    /// index; the second value is the first query position (in the evaluation domain of the layer)
+    /// at which an inconsistency was detected.
+    InvalidLayerFolding(usize, usize),
     /// FRI remainder did not match the commitment.
     RemainderCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at the last layer.
-    InvalidRemainderFolding,
+    /// Degree-respecting projection was not performed correctly at the last layer; the value is
+    /// the first query position (in the evaluation domain of the remainder) at which evaluations
+    /// of the remainder polynomial did not match the folded evaluations.
+    InvalidRemainderFolding(usize),
     /// FRI remainder expected degree is greater than number of remainder values.
     RemainderDegreeNotValid,
     /// FRI remainder degree is greater than the polynomial degree expected for the last layer.
     RemainderDegreeMismatch(usize),
     /// Polynomial degree at one of the FRI layers could not be divided evenly by the folding factor.
     DegreeTruncation(usize, usize, usize),
+    /// Query seed proof-of-work verification failed.
+    QuerySeedProofOfWorkVerificationFailed,
+    /// Number of codewords in a batched FRI query does not match the number of batching
+    /// coefficients.
+    NumCodewordsMismatch(usize, usize),
+    /// Number of DEEP values sent by the prover does not match the number of values implied by
+    /// the folding schedule.
+    NumDeepValuesMismatch(usize, usize),
+    /// Number of layer commitments does not match the number of layers implied by the protocol
+    /// parameters.
+    NumLayerCommitmentsMismatch(usize, usize),
+    /// Proof could not be parsed into its components.
+    ProofDeserializationError(String),
 }
 
 impl fmt::Display for VerifierError {
@@ -49,17 +68,17 @@ impl fmt::Display for VerifierError {
             Self::NumPositionEvaluationMismatch(num_positions, num_evaluations) => write!(f,
                 "the number of query positions must be the same as the number of polynomial evaluations, but {num_positions} and {num_evaluations} were provided"
             ),
-            Self::LayerCommitmentMismatch => {
-                write!(f, "FRI queries did not match layer commitment made by the prover")
+            Self::LayerCommitmentMismatch(layer) => {
+                write!(f, "FRI queries did not match the commitment made by the prover for layer {layer}")
             }
-            Self::InvalidLayerFolding(layer) => {
-                write!(f, "degree-respecting projection is not consistent at layer {layer}")
+            Self::InvalidLayerFolding(layer, position) => {
+                write!(f, "degree-respecting projection is not consistent at layer {layer} for query position {position}")
             }
             Self::RemainderCommitmentMismatch => {
                 write!(f, "FRI remainder did not match the commitment")
             }
-            Self::InvalidRemainderFolding => {
-                write!(f, "degree-respecting projection is inconsistent at the last FRI layer")
+            Self::InvalidRemainderFolding(position) => {
+                write!(f, "degree-respecting projection is inconsistent at the last FRI layer for query position {position}")
             }
             Self::RemainderDegreeNotValid => {
                 write!(f, "FRI remainder expected degree is greater than number of remainder values")
@@ -70,6 +89,79 @@ impl fmt::Display for VerifierError {
             Self::DegreeTruncation(degree, folding, layer) => {
                 write!(f, "degree reduction from {degree} by {folding} at layer {layer} results in degree truncation")
             }
+            Self::QuerySeedProofOfWorkVerificationFailed => {
+                write!(f, "FRI query seed proof-of-work verification failed")
+            }
+            Self::NumCodewordsMismatch(expected, actual) => {
+                write!(f, "expected evaluations of {expected} codewords, but {actual} were provided")
+            }
+            Self::NumDeepValuesMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI DEEP values, but {actual} were provided")
+            }
+            Self::NumLayerCommitmentsMismatch(expected, actual) => {
+                write!(f, "expected {expected} layer commitments, but {actual} were provided")
+            }
+            Self::ProofDeserializationError(msg) => {
+                write!(f, "proof deserialization failed: {msg}")
+            }
+        }
+    }
+}
+
+// OPTIONS ERROR
+// ================================================================================================
+
+/// Defines errors which can occur when FRI protocol parameters are validated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FriOptionsError {
+    /// Blowup factor is not a power of two.
+    BlowupFactorNotPowerOfTwo(usize),
+    /// Folding schedule does not contain any folding factors.
+    EmptyFoldingSchedule,
+    /// Folding factor is not supported. Currently, supported folding factors are: 2, 4, 8, 16,
+    /// 32, and 64.
+    UnsupportedFoldingFactor(usize),
+    /// Number of remainder coefficients (max remainder degree + 1) is not a power of two.
+    RemainderSizeNotPowerOfTwo(usize),
+    /// Number of remainder coefficients (max remainder degree + 1) is smaller than a folding
+    /// factor.
+    RemainderSizeTooSmall(usize, usize),
+    /// Grinding factor is greater than the maximum supported grinding factor.
+    GrindingFactorTooLarge(u32, u32),
+    /// Offset of the evaluation domain is zero.
+    ZeroDomainOffset,
+    /// Concurrency threshold is zero.
+    ZeroConcurrencyThreshold,
+}
+
+impl fmt::Display for FriOptionsError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BlowupFactorNotPowerOfTwo(value) => {
+                write!(f, "blowup factor must be a power of two, but was {value}")
+            }
+            Self::EmptyFoldingSchedule => {
+                write!(f, "folding schedule cannot be empty")
+            }
+            Self::UnsupportedFoldingFactor(value) => {
+                write!(f, "folding factor {value} is not supported")
+            }
+            Self::RemainderSizeNotPowerOfTwo(value) => {
+                write!(f, "number of remainder coefficients must be a power of two, but was {value}")
+            }
+            Self::RemainderSizeTooSmall(size, folding) => {
+                write!(f, "number of remainder coefficients must be at least {folding}, but was {size}")
+            }
+            Self::GrindingFactorTooLarge(value, max) => {
+                write!(f, "grinding factor cannot be greater than {max}, but was {value}")
+            }
+            Self::ZeroDomainOffset => {
+                write!(f, "domain offset cannot be zero")
+            }
+            Self::ZeroConcurrencyThreshold => {
+                write!(f, "concurrency threshold must be greater than zero")
+            }
         }
     }
 }
@@ -11,8 +11,9 @@
 use alloc::vec::Vec;
 
 use math::{
+    batch_inversion,
     fft::{get_inv_twiddles, serial_fft},
-    get_power_series_with_offset, polynom, FieldElement, StarkField,
+    get_power_series, get_power_series_with_offset, mul_slices, polynom, FieldElement, StarkField,
 };
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -84,6 +85,25 @@ use utils::{iter_mut, uninit_vector};
 /// assert_eq!(folded_evaluations, drp_evaluations);
 /// ```
 pub fn apply_drp<B, E, const N: usize>(values: &[[E; N]], domain_offset: B, alpha: E) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    apply_drp_with_min_batch_size(values, domain_offset, alpha, 1)
+}
+
+/// Applies degree-respecting projection to evaluations of a polynomial.
+///
+/// This is the same as [apply_drp()], but when `concurrent` feature is enabled, each thread
+/// processes at least `min_batch_size` values. Thus, fewer than `min_batch_size` values are
+/// projected in a single thread.
+#[cfg_attr(not(feature = "concurrent"), allow(unused_variables))]
+pub fn apply_drp_with_min_batch_size<B, E, const N: usize>(
+    values: &[[E; N]],
+    domain_offset: B,
+    alpha: E,
+    min_batch_size: usize,
+) -> Vec<E>
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
@@ -94,29 +114,148 @@ where
     let len_offset = E::inv((N as u32).into());
 
     let mut result = unsafe { uninit_vector(values.len()) };
-    iter_mut!(result)
-        .zip(values)
-        .zip(inv_offsets)
-        .for_each(|((result, values), domain_offset)| {
-            // interpolate the values into a polynomial; this is similar to interpolation with
-            // offset implemented in math::fft module
-            let mut poly = *values;
-            serial_fft(&mut poly, &inv_twiddles);
-
-            let mut offset = len_offset;
-            let domain_offset = E::from(domain_offset);
-            for coeff in poly.iter_mut() {
-                *coeff *= offset;
-                offset *= domain_offset;
-            }
-
-            // evaluate the polynomial at alpha, and save the result
+    iter_mut!(result, min_batch_size).zip(values).zip(inv_offsets).for_each(
+        |((result, values), domain_offset)| {
+            // interpolate the values into a polynomial, evaluate the polynomial at alpha, and
+            // save the result
+            let poly = interpolate_row(values, &inv_twiddles, len_offset, domain_offset);
             *result = polynom::eval(&poly, alpha)
-        });
+        },
+    );
 
     result
 }
 
+// DEEP DEGREE-RESPECTING PROJECTION
+// ================================================================================================
+/// Returns evaluations of the polynomials into which a polynomial is decomposed during
+/// degree-respecting projection at the out-of-domain point `z`.
+///
+/// Specifically, let `values` contain transposed evaluations of polynomial *f*(x) decomposed as
+/// *f*(x) = f_0(x^N) + x * f_1(x^N) + ... + x^(N - 1) * f_{N-1}(x^N). This function returns
+/// a vector [f_0(z), f_1(z), ..., f_{N-1}(z)]. These values are sent by the prover in DEEP-FRI
+/// before the folding coefficient α is drawn; they are then used in [apply_deep_drp()].
+///
+/// Evaluations of f_i polynomials at `z` are computed via barycentric interpolation over the
+/// folded domain.
+///
+/// # Panics
+/// Panics if `z` is in the folded domain.
+pub fn get_deep_values<B, E, const N: usize>(values: &[[E; N]], domain_offset: B, z: E) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let folded_domain_size = values.len();
+    let inv_offsets = get_inv_offsets(folded_domain_size, domain_offset, N);
+    let inv_twiddles = get_inv_twiddles::<B>(N);
+    let len_offset = E::inv((N as u32).into());
+
+    // the folded domain consists of points y_i = c * g^i, where c = domain_offset^N and g is
+    // the generator of the folded domain; we compute Lagrange weights for all these points at
+    // z as (u^m - 1) / m * g^i / (u - g^i), where u = z / c
+    let g = B::get_root_of_unity(folded_domain_size.ilog2());
+    let u = z * E::from(domain_offset.exp_vartime((N as u32).into())).inv();
+    let domain = get_power_series(g, folded_domain_size);
+    let denominators = domain.iter().map(|&x| u - E::from(x)).collect::<Vec<_>>();
+    assert!(
+        !denominators.contains(&E::ZERO),
+        "out-of-domain point must not be in the folded domain"
+    );
+    let inv_denominators = batch_inversion(&denominators);
+    let scale = (u.exp_vartime((folded_domain_size as u64).into()) - E::ONE)
+        * E::inv((folded_domain_size as u32).into());
+
+    // the coefficients of each row polynomial are evaluations of f_i polynomials at the point
+    // of the folded domain corresponding to the row
+    let mut result = vec![E::ZERO; N];
+    for (((row, &inv_offset), &x), &inv_denominator) in values
+        .iter()
+        .zip(inv_offsets.iter())
+        .zip(domain.iter())
+        .zip(inv_denominators.iter())
+    {
+        let poly = interpolate_row(row, &inv_twiddles, len_offset, inv_offset);
+        let weight = inv_denominator * E::from(x);
+        for (r, &coeff) in result.iter_mut().zip(poly.iter()) {
+            *r += coeff * weight;
+        }
+    }
+    result.iter_mut().for_each(|r| *r *= scale);
+
+    result
+}
+
+/// Applies DEEP degree-respecting projection to evaluations of a polynomial.
+///
+/// This is similar to [apply_drp()], but in addition to computing the folded polynomial
+/// *f'*(x) = f_0(x) + α * f_1(x) + ... + α^(N - 1) * f_{N-1}(x), the evaluations of *f'* are
+/// shifted by the out-of-domain point `z`. That is, the function returns evaluations of
+/// (*f'*(x) - *f'*(z)) / (x - z) over the folded domain, where *f'*(z) is computed from
+/// `deep_values` (as returned by [get_deep_values()]).
+///
+/// When `concurrent` feature is enabled, each thread processes at least `min_batch_size` values.
+///
+/// # Panics
+/// Panics if `z` is in the folded domain.
+pub fn apply_deep_drp<B, E, const N: usize>(
+    values: &[[E; N]],
+    domain_offset: B,
+    alpha: E,
+    z: E,
+    deep_values: &[E],
+    min_batch_size: usize,
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let mut result = apply_drp_with_min_batch_size(values, domain_offset, alpha, min_batch_size);
+    apply_deep_shift(&mut result, domain_offset, N, alpha, z, deep_values, min_batch_size);
+    result
+}
+
+/// Shifts evaluations of a folded polynomial *f'* by the out-of-domain point `z` in place; that
+/// is, replaces each evaluation *f'*(y) with (*f'*(y) - *f'*(z)) / (y - z).
+///
+/// `domain_offset` and `folding_factor` describe the domain of the layer from which `values`
+/// were folded, and *f'*(z) is computed from `deep_values` and `alpha`.
+///
+/// # Panics
+/// Panics if `z` is in the folded domain.
+#[cfg_attr(not(feature = "concurrent"), allow(unused_variables))]
+pub(crate) fn apply_deep_shift<B, E>(
+    values: &mut [E],
+    domain_offset: B,
+    folding_factor: usize,
+    alpha: E,
+    z: E,
+    deep_values: &[E],
+    min_batch_size: usize,
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    // compute denominators (y_i - z) for all points y_i = c * g^i of the folded domain, where
+    // c = domain_offset^N and g is the generator of the folded domain
+    let folded_domain_size = values.len();
+    let g = B::get_root_of_unity(folded_domain_size.ilog2());
+    let c = domain_offset.exp_vartime((folding_factor as u32).into());
+    let denominators = get_power_series_with_offset(g, c, folded_domain_size)
+        .into_iter()
+        .map(|y| E::from(y) - z)
+        .collect::<Vec<_>>();
+    assert!(
+        !denominators.contains(&E::ZERO),
+        "out-of-domain point must not be in the folded domain"
+    );
+    let inv_denominators = batch_inversion(&denominators);
+
+    let folded_z = polynom::eval(deep_values, alpha);
+    iter_mut!(values, min_batch_size).for_each(|value| *value -= folded_z);
+    mul_slices(values, &inv_denominators);
+}
+
 // POSITION FOLDING
 // ================================================================================================
 /// Maps positions in the source domain, to positions in the folded domain.
@@ -178,6 +317,31 @@ pub fn fold_positions(
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Interpolates evaluations of a polynomial in a single row of transposed evaluations into
+/// coefficients of the row polynomial; this is similar to interpolation with offset implemented
+/// in math::fft module.
+fn interpolate_row<B, E, const N: usize>(
+    values: &[E; N],
+    inv_twiddles: &[B],
+    len_offset: E,
+    inv_offset: B,
+) -> [E; N]
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let mut poly = *values;
+    serial_fft(&mut poly, inv_twiddles);
+
+    let mut offset = len_offset;
+    let inv_offset = E::from(inv_offset);
+    for coeff in poly.iter_mut() {
+        *coeff *= offset;
+        offset *= inv_offset;
+    }
+    poly
+}
+
 fn get_inv_offsets<B>(domain_size: usize, domain_offset: B, folding_factor: usize) -> Vec<B>
 where
     B: StarkField,
@@ -29,6 +29,8 @@
 //! When the crate is compiled with `concurrent` feature enabled, proof generation will be
 //! performed in multiple threads (usually, as many threads as there are logical cores on the
 //! machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment variable.
+//! FRI layers with fewer evaluations than the prover's concurrency threshold are built in a single
+//! thread.
 //!
 //! # Proof verification
 //! FRI proofs are verified by a [FriVerifier] as follows:
@@ -42,7 +44,9 @@
 //! 3. Finally, the query phase of the FRI protocol should be executed via
 //!    [verify()](FriVerifier::verify()) function. Note that query values at the first FRI layer
 //!    are provided to the [verify()](FriVerifier::verify()) function directly. The values at
-//!    remaining layers, the verifier reads from the specified verifier channel.
+//!    remaining layers, the verifier reads from the specified verifier channel. The remainder
+//!    polynomial is sent by the prover in coefficient form; thus, the verifier checks it only at
+//!    the positions queried in the last FRI layer, without interpolating the remainder.
 //!
 //! # Protocol parameters
 //! The current implementation supports executing FRI protocol with dynamically configurable
@@ -51,8 +55,10 @@
 //! * Base STARK field,
 //! * Extension field,
 //! * Domain blowup factor,
-//! * Hash function (used for Merkle tree commitments),
-//! * Folding factor (used for degree reduction for each FRI layer),
+//! * Hash function (used for hashing of layer evaluations),
+//! * Vector commitment scheme (see [VectorCommitment](crypto::VectorCommitment); Merkle trees
+//!   are used by default),
+//! * Folding schedule (folding factors used for degree reduction at each FRI layer),
 //! * Maximum size of the last FRI layer.
 //!
 //! # References
@@ -68,8 +74,14 @@ extern crate alloc;
 
 pub mod folding;
 
+pub mod stir;
+
 mod prover;
-pub use prover::{DefaultProverChannel, FriProver, ProverChannel};
+pub use prover::{
+    CpuDrpBackend, DefaultProverChannel, DrpBackend, FriLayerStorage, FriObserver, FriProver,
+    FriProverBuilder, InMemoryLayerStorage, LayerHandle, ProverChannel,
+    DEFAULT_CONCURRENCY_THRESHOLD,
+};
 
 mod verifier;
 pub use verifier::{DefaultVerifierChannel, FriVerifier, VerifierChannel};
@@ -78,9 +90,9 @@ mod options;
 pub use options::FriOptions;
 
 mod proof;
-pub use proof::FriProof;
+pub use proof::{FriProof, FriProofLayer};
 
 mod errors;
-pub use errors::VerifierError;
+pub use errors::{FriOptionsError, VerifierError};
 
 pub mod utils;
@@ -3,46 +3,132 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::vec::Vec;
+
 use math::StarkField;
 
+// CONSTANTS
+// ================================================================================================
+
+pub(crate) const MAX_GRINDING_FACTOR: u32 = 32;
+
+pub(crate) const MIN_FOLDING_FACTOR: usize = 2;
+pub(crate) const MAX_FOLDING_FACTOR: usize = 64;
+
 // FRI OPTIONS
 // ================================================================================================
 
 /// FRI protocol config options for proof generation and verification.
 #[derive(Clone, PartialEq, Eq)]
 pub struct FriOptions {
-    folding_factor: usize,
+    folding_schedule: Vec<usize>,
     remainder_max_degree: usize,
     blowup_factor: usize,
+    grinding_factor: u32,
+    salted_commitments: bool,
+    deep_fri: bool,
 }
 
 impl FriOptions {
     /// Returns a new [FriOptions] struct instantiated with the specified parameters.
     ///
+    /// The same `folding_factor` is used to reduce the degree of the polynomial at every FRI
+    /// layer.
+    ///
     /// # Panics
     /// Panics if:
     /// - `blowup_factor` is not a power of two.
-    /// - `folding_factor` is not 2, 4, 8, or 16.
+    /// - `folding_factor` is not 2, 4, 8, 16, 32, or 64.
     pub fn new(blowup_factor: usize, folding_factor: usize, remainder_max_degree: usize) -> Self {
+        Self::with_folding_schedule(blowup_factor, vec![folding_factor], remainder_max_degree)
+    }
+
+    /// Returns a new [FriOptions] struct instantiated with a per-layer folding schedule.
+    ///
+    /// The i-th entry of `folding_schedule` specifies the folding factor applied at the i-th FRI
+    /// layer. If the evaluation domain requires more layers than there are entries in the
+    /// schedule, the last entry is used for all of the remaining layers. For example, a schedule
+    /// of `[16, 8, 8, 4]` folds the first (and largest) layer by 16, the next two layers by 8,
+    /// and all subsequent layers by 4.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `blowup_factor` is not a power of two.
+    /// - `folding_schedule` is empty.
+    /// - Any of the folding factors in `folding_schedule` is not 2, 4, 8, 16, 32, or 64.
+    pub fn with_folding_schedule(
+        blowup_factor: usize,
+        folding_schedule: Vec<usize>,
+        remainder_max_degree: usize,
+    ) -> Self {
         // TODO: change panics to errors
         assert!(
             blowup_factor.is_power_of_two(),
             "blowup factor must be a power of two, but was {blowup_factor}"
         );
-        assert!(
-            folding_factor == 2
-                || folding_factor == 4
-                || folding_factor == 8
-                || folding_factor == 16,
-            "folding factor {folding_factor} is not supported"
-        );
+        assert!(!folding_schedule.is_empty(), "folding schedule cannot be empty");
+        for &folding_factor in folding_schedule.iter() {
+            assert!(
+                folding_factor.is_power_of_two()
+                    && (MIN_FOLDING_FACTOR..=MAX_FOLDING_FACTOR).contains(&folding_factor),
+                "folding factor {folding_factor} is not supported"
+            );
+        }
         FriOptions {
-            folding_factor,
+            folding_schedule,
             remainder_max_degree,
             blowup_factor,
+            grinding_factor: 0,
+            salted_commitments: false,
+            deep_fri: false,
         }
     }
 
+    /// Returns these options updated to use the specified query seed grinding factor.
+    ///
+    /// When grinding factor is greater than zero, after committing to all FRI layers the prover
+    /// must find a nonce which, when hashed with the current state of the channel, results in a
+    /// value with at least `grinding_factor` leading zeros. The verifier checks the nonce before
+    /// the query phase, and query positions are drawn using this nonce.
+    ///
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 32.
+    pub fn with_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        assert!(
+            grinding_factor <= MAX_GRINDING_FACTOR,
+            "grinding factor cannot be greater than {MAX_GRINDING_FACTOR}"
+        );
+        self.grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Returns these options updated to use salted FRI layer commitments.
+    ///
+    /// When salted commitments are used, the hash of every row of transposed layer evaluations is
+    /// combined with a random salt before it is committed to. Salts for all queried rows are
+    /// included in the proof. Without salts, layer commitments are deterministic functions of the
+    /// evaluations, and thus, they leak information about the evaluations; salted commitments
+    /// are required to make the overall STARK zero-knowledge.
+    ///
+    /// A FRI prover instantiated with these options must be supplied with a secret salt seed via
+    /// [FriProver::with_salt_seed()](crate::FriProver::with_salt_seed()).
+    pub fn with_salted_commitments(mut self) -> Self {
+        self.salted_commitments = true;
+        self
+    }
+
+    /// Returns these options updated to use DEEP-FRI folding.
+    ///
+    /// With DEEP-FRI folding, after committing to a layer the prover draws an out-of-domain point
+    /// *z* and sends evaluations at *z* of the polynomials into which the layer polynomial is
+    /// decomposed during folding. The folded layer *f'* is then replaced with the quotient
+    /// (*f'*(x) - *f'*(z)) / (x - z), and the verifier checks the quotient at every queried
+    /// position. This improves soundness of the protocol for a given number of queries.
+    pub fn with_deep_fri(mut self) -> Self {
+        self.deep_fri = true;
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
@@ -53,22 +139,62 @@ impl FriOptions {
         B::GENERATOR
     }
 
-    /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
+    /// Returns the factor by which the degree of a polynomial is reduced at the first FRI layer.
     ///
-    /// In combination with `remainder_max_degree_plus_1` this property defines how many FRI layers are
-    /// needed for an evaluation domain of a given size.
+    /// When these options were instantiated with a single folding factor, the same factor is
+    /// used at every FRI layer.
     pub fn folding_factor(&self) -> usize {
-        self.folding_factor
+        self.folding_schedule[0]
+    }
+
+    /// Returns the factor by which the degree of a polynomial is reduced at the FRI layer with
+    /// the specified index.
+    ///
+    /// Layers beyond the end of the folding schedule are folded by the last factor in the
+    /// schedule.
+    pub fn folding_factor_at(&self, layer_idx: usize) -> usize {
+        let last_idx = self.folding_schedule.len() - 1;
+        self.folding_schedule[core::cmp::min(layer_idx, last_idx)]
+    }
+
+    /// Returns the per-layer folding schedule for these options.
+    ///
+    /// In combination with `remainder_max_degree` this property defines how many FRI layers are
+    /// needed for an evaluation domain of a given size.
+    pub fn folding_schedule(&self) -> &[usize] {
+        &self.folding_schedule
     }
 
     /// Returns maximum allowed remainder polynomial degree.
     ///
-    /// In combination with `folding_factor` this property defines how many FRI layers are needed
-    /// for an evaluation domain of a given size.
+    /// In combination with `folding_schedule` this property defines how many FRI layers are needed
+    /// for an evaluation domain of a given size. This is also the bound enforced by the verifier:
+    /// a remainder polynomial with more than `remainder_max_degree + 1` coefficients is rejected,
+    /// even if its degree is consistent with the degree implied by folding the input polynomial.
+    /// Thus, a smaller value results in more FRI layers and a shorter remainder in the proof.
     pub fn remainder_max_degree(&self) -> usize {
         self.remainder_max_degree
     }
 
+    /// Returns query seed grinding factor for the FRI protocol.
+    ///
+    /// Grinding applies Proof-of-Work to the query position seed; thus, each bit of grinding
+    /// increases conjectured security of the FRI protocol by one bit. Grinding factor of zero
+    /// means that no grinding is performed.
+    pub fn grinding_factor(&self) -> u32 {
+        self.grinding_factor
+    }
+
+    /// Returns true if FRI layer commitments are salted.
+    pub fn salted_commitments(&self) -> bool {
+        self.salted_commitments
+    }
+
+    /// Returns true if FRI layers are folded using DEEP-FRI folding.
+    pub fn deep_fri(&self) -> bool {
+        self.deep_fri
+    }
+
     /// Returns a blowup factor of the evaluation domain.
     ///
     /// Specifically, if the polynomial for which the FRI protocol is executed is of degree `d`
@@ -80,13 +206,13 @@ impl FriOptions {
 
     /// Computes and return the number of FRI layers required for a domain of the specified size.
     ///
-    /// The number of layers for a given domain size is defined by the `folding_factor` and
+    /// The number of layers for a given domain size is defined by the `folding_schedule` and
     /// `remainder_max_degree` and `blowup_factor` settings.
     pub fn num_fri_layers(&self, mut domain_size: usize) -> usize {
         let mut result = 0;
         let max_remainder_size = (self.remainder_max_degree + 1) * self.blowup_factor;
         while domain_size > max_remainder_size {
-            domain_size /= self.folding_factor;
+            domain_size /= self.folding_factor_at(result);
             result += 1;
         }
         result
@@ -5,12 +5,16 @@
 
 use alloc::{string::ToString, vec::Vec};
 
-use crypto::{BatchMerkleProof, ElementHasher, Hasher};
+use crypto::{ElementHasher, Hasher, VectorCommitment};
 use math::FieldElement;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
+use crate::{utils::apply_salts, FriOptions};
+
 // FRI PROOF
 // ================================================================================================
 
@@ -22,24 +26,34 @@ use utils::{
 /// [FriVerifier](crate::FriVerifier) via [VerifierChannel](crate::VerifierChannel) interface.
 ///
 /// A proof consists of zero or more layers and a remainder polynomial. Each layer contains a set of
-/// polynomial evaluations at positions queried by the verifier as well as Merkle authentication
-/// paths for these evaluations (the Merkle paths are compressed into a batch Merkle proof). The
-/// remainder polynomial is given by its list of coefficients i.e. field elements.
+/// polynomial evaluations at positions queried by the verifier as well as a batch opening proof
+/// for these evaluations against the layer commitment (e.g., a batch Merkle proof). When salted
+/// layer commitments are used, each layer also contains salts for the queried evaluations. The
+/// remainder polynomial is given by its list of coefficients i.e. field elements. When DEEP-FRI
+/// folding is used, the proof also contains evaluations of the decomposed layer polynomials at
+/// out-of-domain points for all layers (excluding the remainder layer).
 ///
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
-/// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
-/// and [parse_remainder()](FriProof::parse_remainder()) methods can be used.
+/// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers()),
+/// [parse_remainder()](FriProof::parse_remainder()), and
+/// [parse_deep_values()](FriProof::parse_deep_values()) methods can be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FriProof {
     layers: Vec<FriProofLayer>,
     remainder: Vec<u8>,
+    deep_values: Vec<u8>,
     num_partitions: u8, // stored as power of 2
+    pow_nonce: u64,
 }
 
 impl FriProof {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new FRI proof from the provided layers and remainder polynomial.
+    /// Creates a new FRI proof from the provided layers, remainder polynomial, DEEP values, and
+    /// query seed proof-of-work nonce.
+    ///
+    /// `deep_values` must be empty if the proof was generated without DEEP-FRI folding.
     ///
     /// # Panics
     /// Panics if:
@@ -48,7 +62,9 @@ impl FriProof {
     pub(crate) fn new<E: FieldElement>(
         layers: Vec<FriProofLayer>,
         remainder: Vec<E>,
+        deep_values: Vec<E>,
         num_partitions: usize,
+        pow_nonce: u64,
     ) -> Self {
         assert!(!remainder.is_empty(), "number of remainder elements must be greater than zero");
         assert!(
@@ -65,10 +81,15 @@ impl FriProof {
         let mut remainder_bytes = Vec::with_capacity(E::ELEMENT_BYTES * remainder.len());
         remainder_bytes.write_many(&remainder);
 
+        let mut deep_value_bytes = Vec::with_capacity(E::ELEMENT_BYTES * deep_values.len());
+        deep_value_bytes.write_many(&deep_values);
+
         FriProof {
             layers,
             remainder: remainder_bytes,
+            deep_values: deep_value_bytes,
             num_partitions: num_partitions.trailing_zeros() as u8,
+            pow_nonce,
         }
     }
 
@@ -77,7 +98,9 @@ impl FriProof {
         Self {
             layers: Vec::new(),
             remainder: Vec::new(),
+            deep_values: Vec::new(),
             num_partitions: 0,
+            pow_nonce: 0,
         }
     }
 
@@ -102,57 +125,75 @@ impl FriProof {
         2usize.pow(self.num_partitions as u32)
     }
 
+    /// Returns the query seed proof-of-work nonce of this proof.
+    ///
+    /// If the proof was generated with grinding factor of zero, the nonce is zero.
+    pub fn pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
+        // +1 for number of layers, +1 for remainder length, +2 for DEEP values length, +1 for
+        // number of partitions, +8 for the proof-of-work nonce
         self.layers
             .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+            .fold(self.remainder.len() + self.deep_values.len() + 13, |acc, layer| {
+                acc + layer.size()
+            })
     }
 
     // PARSING
     // --------------------------------------------------------------------------------------------
 
-    /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
-    /// authentication paths for each query (grouped into batch Merkle proofs).
+    /// Decomposes this proof into vectors of query values for each layer, salts for the queried
+    /// values, and corresponding batch opening proofs of the vector commitment scheme `V`.
+    ///
+    /// The folding factor used at each layer is determined by the folding schedule of the
+    /// specified `options`. If the `options` do not specify salted layer commitments, vectors of
+    /// salts for all layers are empty.
     ///
     /// # Panics
-    /// Panics if:
-    /// * `domain_size` is not a power of two.
-    /// * `folding_factor` is smaller than two or is not a power of two.
+    /// Panics if `domain_size` is not a power of two.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
+    /// * This proof is not consistent with the specified `domain_size` and `options`.
     /// * Any of the layers could not be parsed successfully.
     #[allow(clippy::type_complexity)]
-    pub fn parse_layers<H, E>(
+    pub fn parse_layers<H, E, V>(
         self,
         mut domain_size: usize,
-        folding_factor: usize,
-    ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>), DeserializationError>
+        options: &FriOptions,
+    ) -> Result<(Vec<Vec<E>>, Vec<Vec<H::Digest>>, Vec<V::BatchProof>), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
         assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
-        assert!(folding_factor.is_power_of_two(), "folding factor must be a power of two");
-        assert!(folding_factor > 1, "folding factor must be greater than 1");
 
         let mut layer_proofs = Vec::new();
         let mut layer_queries = Vec::new();
+        let mut layer_salts = Vec::new();
 
         // parse all layers
         for (i, layer) in self.layers.into_iter().enumerate() {
+            let folding_factor = options.folding_factor_at(i);
             domain_size /= folding_factor;
-            let (qv, mp) = layer.parse(domain_size, folding_factor).map_err(|err| {
-                DeserializationError::InvalidValue(format!("failed to parse FRI layer {i}: {err}"))
-            })?;
+            let (qv, salts, mp) = layer
+                .parse::<H, E, V>(domain_size, folding_factor, options.salted_commitments())
+                .map_err(|err| {
+                    DeserializationError::InvalidValue(format!(
+                        "failed to parse FRI layer {i}: {err}"
+                    ))
+                })?;
             layer_proofs.push(mp);
             layer_queries.push(qv);
+            layer_salts.push(salts);
         }
 
-        Ok((layer_queries, layer_proofs))
+        Ok((layer_queries, layer_salts, layer_proofs))
     }
 
     /// Returns a vector of remainder values (last FRI layer) parsed from this proof.
@@ -179,26 +220,134 @@ impl FriProof {
         }
         Ok(remainder)
     }
-}
 
-// SERIALIZATION / DESERIALIZATION
-// ------------------------------------------------------------------------------------------------
-
-impl Serializable for FriProof {
-    /// Serializes `self` and writes the resulting bytes into the `target` writer.
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        // write layers
-        target.write_u8(self.layers.len() as u8);
-        for layer in self.layers.iter() {
-            layer.write_into(target);
+    /// Returns a vector of DEEP values parsed from this proof.
+    ///
+    /// The values for all layers are returned in a single vector in the order in which the layers
+    /// were built; if the proof was generated without DEEP-FRI folding, the vector is empty.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of DEEP value bytes is not a multiple of the size of `E`.
+    /// * Any of the DEEP values could not be parsed correctly.
+    pub fn parse_deep_values<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        if self.deep_values.len() % E::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of DEEP value bytes must be a multiple of {}, but was {}",
+                E::ELEMENT_BYTES,
+                self.deep_values.len()
+            )));
         }
+        let num_elements = self.deep_values.len() / E::ELEMENT_BYTES;
+        let mut reader = SliceReader::new(&self.deep_values);
+        reader.read_many(num_elements).map_err(|err| {
+            DeserializationError::InvalidValue(format!("failed to parse FRI DEEP values: {err}"))
+        })
+    }
+
+    // STREAMING
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes the layers of this proof and writes the resulting bytes into the `target`
+    /// writer.
+    ///
+    /// Unlike regular serialization, the number of layers is not written; when a proof is
+    /// streamed, the number of layers is implied by the FRI options and the size of the
+    /// evaluation domain. The layers can be read back one by one via
+    /// [FriProofLayer::read_from()].
+    pub fn write_layers_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_many(&self.layers);
+    }
 
+    /// Serializes the remainder, DEEP values, number of partitions, and the query seed
+    /// proof-of-work nonce of this proof and writes the resulting bytes into the `target` writer.
+    ///
+    /// These values are small and are needed by the verifier before any of the layers; thus, when
+    /// a proof is streamed, they are written ahead of the layers.
+    pub fn write_header_into<W: ByteWriter>(&self, target: &mut W) {
         // write remainder
         target.write_u16(self.remainder.len() as u16);
         target.write_bytes(&self.remainder);
 
+        // write DEEP values
+        target.write_u16(self.deep_values.len() as u16);
+        target.write_bytes(&self.deep_values);
+
         // write number of partitions
         target.write_u8(self.num_partitions);
+
+        // write proof-of-work nonce
+        target.write_u64(self.pow_nonce);
+    }
+
+    /// Reads the values written by [write_header_into()](FriProof::write_header_into()) from the
+    /// specified `source` and returns a proof without any layers.
+    ///
+    /// # Errors
+    /// Returns an error if valid values could not be read from the source.
+    pub fn read_header_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // read remainder
+        let num_remainder_bytes = source.read_u16()? as usize;
+        let remainder = source.read_vec(num_remainder_bytes)?;
+
+        // read DEEP values
+        let num_deep_value_bytes = source.read_u16()? as usize;
+        let deep_values = source.read_vec(num_deep_value_bytes)?;
+
+        // read number of partitions
+        let num_partitions = source.read_u8()?;
+
+        // read proof-of-work nonce
+        let pow_nonce = source.read_u64()?;
+
+        Ok(FriProof {
+            layers: Vec::new(),
+            remainder,
+            deep_values,
+            num_partitions,
+            pow_nonce,
+        })
+    }
+
+    // TRANSFORMATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies `f` to the bytes of all field elements contained in this proof and returns the
+    /// resulting proof.
+    ///
+    /// The function is applied to the query values of each layer, the remainder, and the DEEP
+    /// values; salts and batch opening proofs are left unchanged. This can be used to re-encode
+    /// field elements of a proof (e.g., to pack them more tightly) without parsing the proof.
+    ///
+    /// # Errors
+    /// Returns an error if `f` returns an error for any of the values.
+    pub fn map_values<F>(self, mut f: F) -> Result<Self, DeserializationError>
+    where
+        F: FnMut(Vec<u8>) -> Result<Vec<u8>, DeserializationError>,
+    {
+        let mut layers = Vec::with_capacity(self.layers.len());
+        for layer in self.layers {
+            layers.push(FriProofLayer { values: f(layer.values)?, ..layer });
+        }
+
+        Ok(FriProof {
+            layers,
+            remainder: f(self.remainder)?,
+            deep_values: f(self.deep_values)?,
+            ..self
+        })
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for FriProof {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.layers.len() as u8);
+        self.write_layers_into(target);
+        self.write_header_into(target);
     }
 }
 
@@ -212,51 +361,65 @@ impl Deserializable for FriProof {
         let num_layers = source.read_u8()? as usize;
         let layers = source.read_many(num_layers)?;
 
-        // read remainder
-        let num_remainder_bytes = source.read_u16()? as usize;
-        let remainder = source.read_vec(num_remainder_bytes)?;
-
-        // read number of partitions
-        let num_partitions = source.read_u8()?;
+        // read remainder, DEEP values, number of partitions, and proof-of-work nonce
+        let proof = FriProof::read_header_from(source)?;
 
-        Ok(FriProof { layers, remainder, num_partitions })
+        Ok(FriProof { layers, ..proof })
     }
 }
 
 // FRI PROOF LAYER
 // ================================================================================================
 
+/// A single layer of a [FriProof].
+///
+/// A layer contains polynomial evaluations at positions queried by the verifier, salts for these
+/// evaluations (if layer commitments are salted), and a batch opening proof for the evaluations
+/// against the layer commitment. All values are stored as vectors of bytes and can be parsed via
+/// [parse()](FriProofLayer::parse()).
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FriProofLayer {
     values: Vec<u8>,
+    salts: Vec<u8>,
     paths: Vec<u8>,
 }
 
 impl FriProofLayer {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new proof layer from the specified query values and the corresponding Merkle
-    /// paths aggregated into a single batch Merkle proof.
+    /// Creates a new proof layer from the specified query values, salts for these values, and the
+    /// corresponding batch opening proof of the vector commitment scheme `V`.
+    ///
+    /// `salts` must be empty if layer commitments are not salted.
     ///
     /// # Panics
-    /// Panics if `query_values` is an empty slice.
-    pub(crate) fn new<H: Hasher, E: FieldElement, const N: usize>(
+    /// Panics if:
+    /// * `query_values` is an empty slice.
+    /// * `salts` is not empty and the number of salts is not equal to the number of query values.
+    pub(crate) fn new<H: Hasher, E: FieldElement, V: VectorCommitment<H>, const N: usize>(
         query_values: Vec<[E; N]>,
-        merkle_proof: BatchMerkleProof<H>,
+        salts: Vec<H::Digest>,
+        batch_proof: V::BatchProof,
     ) -> Self {
         assert!(!query_values.is_empty(), "query values cannot be empty");
+        assert!(
+            salts.is_empty() || salts.len() == query_values.len(),
+            "number of salts must be equal to the number of query values"
+        );
 
         // TODO: add debug check that values actually hash into the leaf nodes of the batch proof
 
         let mut value_bytes = Vec::with_capacity(E::ELEMENT_BYTES * N * query_values.len());
         value_bytes.write_many(&query_values);
 
-        // concatenate all query values and all internal Merkle proof nodes into vectors of bytes;
-        // we care about internal nodes only because leaf nodes can be reconstructed from hashes
-        // of query values
+        // concatenate all query values and the batch opening proof into vectors of bytes; the
+        // opened items are not serialized as a part of the batch proof because they can be
+        // reconstructed from hashes of query values
         FriProofLayer {
             values: value_bytes,
-            paths: merkle_proof.serialize_nodes(),
+            salts: salts.to_bytes(),
+            paths: V::serialize_batch_proof(&batch_proof),
         }
     }
 
@@ -265,28 +428,35 @@ impl FriProofLayer {
 
     /// Returns the size of this proof layer in bytes.
     pub fn size(&self) -> usize {
-        // +4 for length of values, +4 for length of paths
-        self.values.len() + 4 + self.paths.len() + 4
+        // +4 for length of values, +4 for length of salts, +4 for length of paths
+        self.values.len() + 4 + self.salts.len() + 4 + self.paths.len() + 4
     }
 
     // PARSING
     // --------------------------------------------------------------------------------------------
-    /// Decomposes this layer into a combination of query values and corresponding Merkle
-    /// authentication paths (grouped together into a single batch Merkle proof).
+    /// Decomposes this layer into a combination of query values, salts for these values, and
+    /// corresponding batch opening proof of the vector commitment scheme `V`.
+    ///
+    /// If `salted` is false, the returned vector of salts is empty.
     ///
     /// # Errors
     /// Returns an error if:
     /// * This layer does not contain at least one query.
-    /// * Parsing of any of the query values or the corresponding Merkle paths fails.
+    /// * Parsing of any of the query values, salts, or the corresponding batch opening proof
+    ///   fails.
+    /// * The number of salts is inconsistent with the number of queries and `salted` flag.
     /// * Not all bytes have been consumed while parsing this layer.
-    pub fn parse<H, E>(
+    #[allow(clippy::type_complexity)]
+    pub fn parse<H, E, V>(
         self,
         domain_size: usize,
         folding_factor: usize,
-    ) -> Result<(Vec<E>, BatchMerkleProof<H>), DeserializationError>
+        salted: bool,
+    ) -> Result<(Vec<E>, Vec<H::Digest>, V::BatchProof), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
         // make sure the number of value bytes can be parsed into a whole number of queries
         let num_query_bytes = E::ELEMENT_BYTES * folding_factor;
@@ -307,7 +477,7 @@ impl FriProofLayer {
         let mut query_values = Vec::with_capacity(num_queries * folding_factor);
 
         // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
+        // and also hash them to get the items opened by the batch proof
         let mut reader = SliceReader::new(&self.values);
         for query_hash in hashed_queries.iter_mut() {
             let mut qe = reader.read_many(folding_factor)?;
@@ -318,15 +488,29 @@ impl FriProofLayer {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        // build batch Merkle proof
+        // read salts and combine them with hashed queries
+        let mut reader = SliceReader::new(&self.salts);
+        let salts: Vec<H::Digest> = Vec::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        let expected_num_salts = if salted { num_queries } else { 0 };
+        if salts.len() != expected_num_salts {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {expected_num_salts} salts, but {} were provided",
+                salts.len()
+            )));
+        }
+        apply_salts::<H>(&mut hashed_queries, &salts);
+
+        // build batch opening proof
         let mut reader = SliceReader::new(&self.paths);
-        let tree_depth = domain_size.ilog2() as u8;
-        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
+        let batch_proof = V::deserialize_batch_proof(&mut reader, hashed_queries, domain_size)?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        Ok((query_values, merkle_proof))
+        Ok((query_values, salts, batch_proof))
     }
 }
 
@@ -340,6 +524,10 @@ impl Serializable for FriProofLayer {
         target.write_u32(self.values.len() as u32);
         target.write_bytes(&self.values);
 
+        // write salt bytes
+        target.write_u32(self.salts.len() as u32);
+        target.write_bytes(&self.salts);
+
         // write path bytes
         target.write_u32(self.paths.len() as u32);
         target.write_bytes(&self.paths);
@@ -361,10 +549,14 @@ impl Deserializable for FriProofLayer {
         }
         let values = source.read_vec(num_value_bytes as usize)?;
 
+        // read salts
+        let num_salt_bytes = source.read_u32()?;
+        let salts = source.read_vec(num_salt_bytes as usize)?;
+
         // read paths
         let num_paths_bytes = source.read_u32()?;
         let paths = source.read_vec(num_paths_bytes as usize)?;
 
-        Ok(FriProofLayer { values, paths })
+        Ok(FriProofLayer { values, salts, paths })
     }
 }
@@ -0,0 +1,66 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{FieldElement, StarkField};
+
+use crate::folding::apply_drp_with_min_batch_size;
+
+// DRP BACKEND TRAIT
+// ================================================================================================
+
+/// Defines an interface for executing degree-respecting projection (DRP) of FRI layers.
+///
+/// For large domains, folding of layer evaluations dominates the commit phase of the FRI
+/// protocol. Implementations of this trait can offload the folding to a different device (e.g.,
+/// a GPU). The [FriProver](crate::FriProver) hands over transposed evaluations of every layer to
+/// the backend, and if the backend cannot fold the evaluations (e.g., because no device is
+/// available), the prover falls back to folding them on the CPU.
+///
+/// The default implementation ([CpuDrpBackend]) folds all layers on the CPU.
+pub trait DrpBackend<B: StarkField, E: FieldElement<BaseField = B>> {
+    /// Applies degree-respecting projection to transposed evaluations of a polynomial over a
+    /// domain shifted by `domain_offset`, and returns evaluations of the folded polynomial (see
+    /// [apply_drp()](crate::folding::apply_drp())).
+    ///
+    /// `min_batch_size` specifies the minimum number of rows folded by a single thread when
+    /// folding is executed on the CPU; other backends may ignore this parameter.
+    ///
+    /// Returns `None` if the backend cannot fold the specified `values`; in such a case, the
+    /// prover folds them on the CPU instead.
+    fn apply_drp<const N: usize>(
+        &self,
+        values: &[[E; N]],
+        domain_offset: B,
+        alpha: E,
+        min_batch_size: usize,
+    ) -> Option<Vec<E>>;
+}
+
+// CPU BACKEND
+// ================================================================================================
+
+/// Provides a default implementation of the [DrpBackend] trait which folds FRI layers on the CPU.
+///
+/// When `concurrent` feature is enabled, folding is performed using multiple threads.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuDrpBackend;
+
+impl<B, E> DrpBackend<B, E> for CpuDrpBackend
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    fn apply_drp<const N: usize>(
+        &self,
+        values: &[[E; N]],
+        domain_offset: B,
+        alpha: E,
+        min_batch_size: usize,
+    ) -> Option<Vec<E>> {
+        Some(apply_drp_with_min_batch_size(values, domain_offset, alpha, min_batch_size))
+    }
+}
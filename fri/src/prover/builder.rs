@@ -0,0 +1,170 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::ElementHasher;
+use math::{FieldElement, StarkField};
+
+use super::{FriProver, ProverChannel, DEFAULT_CONCURRENCY_THRESHOLD};
+use crate::{
+    options::{MAX_FOLDING_FACTOR, MAX_GRINDING_FACTOR, MIN_FOLDING_FACTOR},
+    FriOptions, FriOptionsError,
+};
+
+// FRI PROVER BUILDER
+// ================================================================================================
+
+/// A builder for [FriProver] which validates FRI protocol parameters at construction time.
+///
+/// Unlike [FriOptions] constructors, which panic on invalid parameters, the builder checks that
+/// all parameters are mutually consistent in [build()](FriProverBuilder::build()) and returns a
+/// [FriOptionsError] if they are not. Specifically, the builder checks that:
+/// * Blowup factor is a power of two.
+/// * Folding schedule is not empty, and all folding factors are 2, 4, 8, 16, 32, or 64.
+/// * Number of remainder coefficients (i.e., max remainder degree + 1) is a power of two, and is
+///   not smaller than any of the folding factors.
+/// * Grinding factor is not greater than 32.
+/// * Domain offset is not zero.
+/// * Concurrency threshold is not zero.
+#[derive(Debug, Clone)]
+pub struct FriProverBuilder {
+    blowup_factor: usize,
+    folding_schedule: Vec<usize>,
+    remainder_max_degree: usize,
+    grinding_factor: u32,
+    salt_seed: Option<[u8; 32]>,
+    deep_fri: bool,
+    concurrency_threshold: usize,
+}
+
+impl FriProverBuilder {
+    /// Returns a new builder for a FRI prover which uses the same `folding_factor` at every FRI
+    /// layer.
+    pub fn new(blowup_factor: usize, folding_factor: usize, remainder_max_degree: usize) -> Self {
+        FriProverBuilder {
+            blowup_factor,
+            folding_schedule: vec![folding_factor],
+            remainder_max_degree,
+            grinding_factor: 0,
+            salt_seed: None,
+            deep_fri: false,
+            concurrency_threshold: DEFAULT_CONCURRENCY_THRESHOLD,
+        }
+    }
+
+    /// Returns this builder updated to use the specified per-layer folding schedule (see
+    /// [FriOptions::with_folding_schedule()]).
+    pub fn with_folding_schedule(mut self, folding_schedule: Vec<usize>) -> Self {
+        self.folding_schedule = folding_schedule;
+        self
+    }
+
+    /// Returns this builder updated to use the specified query seed grinding factor (see
+    /// [FriOptions::with_grinding_factor()]).
+    pub fn with_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        self.grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Returns this builder updated to use salted FRI layer commitments with salts derived from
+    /// the specified secret `seed` (see [FriOptions::with_salted_commitments()]).
+    pub fn with_salted_commitments(mut self, seed: [u8; 32]) -> Self {
+        self.salt_seed = Some(seed);
+        self
+    }
+
+    /// Returns this builder updated to use DEEP-FRI folding (see [FriOptions::with_deep_fri()]).
+    pub fn with_deep_fri(mut self) -> Self {
+        self.deep_fri = true;
+        self
+    }
+
+    /// Returns this builder updated to use the specified concurrency threshold (see
+    /// [FriProver::with_concurrency_threshold()]).
+    pub fn with_concurrency_threshold(mut self, concurrency_threshold: usize) -> Self {
+        self.concurrency_threshold = concurrency_threshold;
+        self
+    }
+
+    /// Validates parameters of this builder and returns the resulting [FriOptions].
+    ///
+    /// # Errors
+    /// Returns an error if any of the parameters are invalid or inconsistent with each other.
+    pub fn build_options(&self) -> Result<FriOptions, FriOptionsError> {
+        if !self.blowup_factor.is_power_of_two() {
+            return Err(FriOptionsError::BlowupFactorNotPowerOfTwo(self.blowup_factor));
+        }
+        if self.folding_schedule.is_empty() {
+            return Err(FriOptionsError::EmptyFoldingSchedule);
+        }
+
+        let remainder_size = self.remainder_max_degree + 1;
+        if !remainder_size.is_power_of_two() {
+            return Err(FriOptionsError::RemainderSizeNotPowerOfTwo(remainder_size));
+        }
+        for &folding_factor in self.folding_schedule.iter() {
+            if !folding_factor.is_power_of_two()
+                || !(MIN_FOLDING_FACTOR..=MAX_FOLDING_FACTOR).contains(&folding_factor)
+            {
+                return Err(FriOptionsError::UnsupportedFoldingFactor(folding_factor));
+            }
+            if remainder_size < folding_factor {
+                return Err(FriOptionsError::RemainderSizeTooSmall(remainder_size, folding_factor));
+            }
+        }
+
+        if self.grinding_factor > MAX_GRINDING_FACTOR {
+            return Err(FriOptionsError::GrindingFactorTooLarge(
+                self.grinding_factor,
+                MAX_GRINDING_FACTOR,
+            ));
+        }
+        if self.concurrency_threshold == 0 {
+            return Err(FriOptionsError::ZeroConcurrencyThreshold);
+        }
+
+        let mut options = FriOptions::with_folding_schedule(
+            self.blowup_factor,
+            self.folding_schedule.clone(),
+            self.remainder_max_degree,
+        )
+        .with_grinding_factor(self.grinding_factor);
+        if self.salt_seed.is_some() {
+            options = options.with_salted_commitments();
+        }
+        if self.deep_fri {
+            options = options.with_deep_fri();
+        }
+
+        Ok(options)
+    }
+
+    /// Validates parameters of this builder and returns a new [FriProver] instantiated with them.
+    ///
+    /// The returned prover commits to FRI layers using Merkle trees, and keeps evaluations of all
+    /// FRI layers in memory.
+    ///
+    /// # Errors
+    /// Returns an error if any of the parameters are invalid or inconsistent with each other.
+    pub fn build<B, E, C, H>(self) -> Result<FriProver<B, E, C, H>, FriOptionsError>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+        C: ProverChannel<E, Hasher = H>,
+        H: ElementHasher<BaseField = B>,
+    {
+        let options = self.build_options()?;
+        if options.domain_offset::<B>() == B::ZERO {
+            return Err(FriOptionsError::ZeroDomainOffset);
+        }
+
+        let prover = FriProver::with_concurrency_threshold(options, self.concurrency_threshold);
+        Ok(match self.salt_seed {
+            Some(seed) => prover.with_salt_seed(seed),
+            None => prover,
+        })
+    }
+}
@@ -6,8 +6,12 @@
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
-use crypto::{ElementHasher, Hasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, RandomCoin, Transcript, TranscriptLabel};
 use math::FieldElement;
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+use crate::stir::StirProverChannel;
 
 // PROVER CHANNEL TRAIT
 // ================================================================================================
@@ -46,6 +50,28 @@ pub trait ProverChannel<E: FieldElement> {
     /// prover, in the non-interactive version, the α is pseudo-randomly generated based on the
     /// values the prover previously wrote into the channel.
     fn draw_fri_alpha(&mut self) -> E;
+
+    /// Sends evaluations of polynomials obtained by decomposing the current FRI layer polynomial
+    /// at an out-of-domain point to the verifier.
+    ///
+    /// This method is invoked only when FRI is executed with DEEP folding enabled (see
+    /// [FriOptions::with_deep_fri()](crate::FriOptions::with_deep_fri())). In such a case, the
+    /// prover draws an out-of-domain point via [draw_fri_alpha()](ProverChannel::draw_fri_alpha)
+    /// after each layer commitment, sends the `values` via this method, and only then draws α
+    /// used for folding the layer.
+    fn commit_fri_deep_values(&mut self, values: &[E]);
+
+    /// Determines a nonce, which when hashed with the current state of the channel results in a
+    /// value with at least `grinding_factor` leading zeros, and returns this nonce.
+    ///
+    /// The prover calls this method after all FRI layer commitments (including the remainder
+    /// commitment) have been written into the channel. The nonce is then used to draw query
+    /// positions for the query phase of the protocol.
+    ///
+    /// To keep proof generation deterministic, implementations should return the smallest nonce
+    /// satisfying the proof-of-work condition; the default channel does so regardless of the
+    /// number of threads used for the search.
+    fn grind_fri_query_seed(&mut self, grinding_factor: u32) -> u64;
 }
 
 // DEFAULT PROVER CHANNEL IMPLEMENTATION
@@ -59,7 +85,7 @@ pub struct DefaultProverChannel<E, H, R>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
 {
     public_coin: R,
     commitments: Vec<H::Digest>,
@@ -72,7 +98,7 @@ impl<E, H, R> DefaultProverChannel<E, H, R>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
 {
     /// Returns a new prover channel instantiated from the specified parameters.
     ///
@@ -100,7 +126,9 @@ where
     /// layer should be queried.
     ///
     /// The positions are pseudo-randomly generated based on the values the prover has written
-    /// into this channel and a PoW nonce.
+    /// into this channel and a PoW nonce. When FRI is executed with a non-zero grinding factor,
+    /// the nonce must be the one found by the prover during the commit phase (see
+    /// [FriProver::pow_nonce()](crate::FriProver::pow_nonce())).
     ///
     /// # Panics
     /// Panics if the specified number of unique positions could not be drawn from the specified
@@ -108,7 +136,7 @@ where
     /// construction of the channel.
     pub fn draw_query_positions(&mut self, nonce: u64) -> Vec<usize> {
         self.public_coin
-            .draw_integers(self.num_queries, self.domain_size, nonce)
+            .squeeze_integers(TranscriptLabel::QuerySeed, self.num_queries, self.domain_size, nonce)
             .expect("failed to draw query position")
     }
 
@@ -122,16 +150,55 @@ impl<E, H, R> ProverChannel<E> for DefaultProverChannel<E, H, R>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
 {
     type Hasher = H;
 
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.push(layer_root);
-        self.public_coin.reseed(layer_root);
+        self.public_coin.absorb(TranscriptLabel::FriLayer, layer_root);
     }
 
     fn draw_fri_alpha(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw FRI alpha")
+        self.public_coin
+            .squeeze(TranscriptLabel::FriLayer)
+            .expect("failed to draw FRI alpha")
+    }
+
+    fn commit_fri_deep_values(&mut self, values: &[E]) {
+        self.public_coin
+            .absorb(TranscriptLabel::FriDeepValues, H::hash_elements(values));
+    }
+
+    fn grind_fri_query_seed(&mut self, grinding_factor: u32) -> u64 {
+        #[cfg(not(feature = "concurrent"))]
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+
+        #[cfg(feature = "concurrent")]
+        let nonce = (1..u64::MAX)
+            .into_par_iter()
+            .find_first(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+
+        nonce
+    }
+}
+
+impl<E, H, R> StirProverChannel<E> for DefaultProverChannel<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
+{
+    fn draw_stir_query_positions(
+        &mut self,
+        num_positions: usize,
+        domain_size: usize,
+    ) -> Vec<usize> {
+        self.public_coin
+            .squeeze_integers(TranscriptLabel::QuerySeed, num_positions, domain_size, 0)
+            .expect("failed to draw STIR query positions")
     }
 }
@@ -6,23 +6,45 @@
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
-use crypto::{ElementHasher, Hasher, MerkleTree};
-use math::{fft, FieldElement, StarkField};
+use crypto::{ElementHasher, Hasher, MerkleTree, VectorCommitment};
+use math::{
+    fft::{EvaluationDomain, FftDomain},
+    FieldElement, StarkField,
+};
 use utils::{flatten_vector_elements, group_slice_elements, transpose_slice};
 
 use crate::{
-    folding::{apply_drp, fold_positions},
+    folding::{apply_deep_shift, apply_drp_with_min_batch_size, fold_positions, get_deep_values},
     proof::{FriProof, FriProofLayer},
-    utils::hash_values,
+    utils::{apply_salts, get_batching_coefficients, hash_values},
     FriOptions,
 };
 
 mod channel;
 pub use channel::{DefaultProverChannel, ProverChannel};
 
+mod backend;
+pub use backend::{CpuDrpBackend, DrpBackend};
+
+mod builder;
+pub use builder::FriProverBuilder;
+
+mod observer;
+pub use observer::FriObserver;
+
+mod storage;
+pub use storage::{FriLayerStorage, InMemoryLayerStorage};
+
 #[cfg(test)]
 mod tests;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Default number of FRI layer evaluations below which a layer is hashed and folded in a single
+/// thread.
+pub const DEFAULT_CONCURRENCY_THRESHOLD: usize = 4096;
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -40,26 +62,42 @@ mod tests;
 ///   base field `B`, but it can also be an extension of the base field in cases when the base
 ///   field is too small to provide desired security level for the FRI protocol.
 /// * `C` specifies the type used to simulate prover-verifier interaction.
-/// * `H` specifies the hash function used to build layer Merkle trees. The same hash function
+/// * `H` specifies the hash function used to hash layer evaluations. The same hash function
 ///   must be used in the prover channel to generate pseudo random values.
+/// * `V` specifies the vector commitment scheme used to commit to hashed layer evaluations. This
+///   defaults to [MerkleTree]; Merkle trees of higher arity can be selected by setting `V` to
+///   [KaryMerkleTree](crypto::KaryMerkleTree) (e.g., `KaryMerkleTree<H, 4>`).
+/// * `S` specifies the storage used to keep evaluations of FRI layers between the commit phase
+///   and the query phase. This defaults to [InMemoryLayerStorage].
+/// * `O` specifies the observer notified about progress of proof generation (see
+///   [FriObserver]). This defaults to `()`, which ignores all notifications.
+/// * `D` specifies the backend used to apply degree-respecting projection to layer evaluations
+///   (see [DrpBackend]). This defaults to [CpuDrpBackend].
 ///
 /// Proof generation is performed in two phases: commit phase and query phase.
 ///
+/// The commit phase can be executed either in a single call via
+/// [build_layers()](FriProver::build_layers()), or layer-by-layer via
+/// [commit_layer()](FriProver::commit_layer()) followed by
+/// [finalize()](FriProver::finalize()). The latter allows callers to interleave other protocol
+/// steps (e.g., auxiliary commitments) between FRI layers.
+///
 /// # Commit phase
 /// During the commit phase, which is executed via [build_layers()](FriProver::build_layers())
 /// function, the prover repeatedly applies a degree-respecting projection (DRP) to `evaluations`
 /// (see [folding](crate::folding)). With every application of the DRP, the degree of the function
-/// *f* (and size of the domain over which it is evaluated) is reduced by the `folding_factor`
-/// until the remaining evaluations correspond to a polynomial, called remainder polynomial, with
-/// a number of coefficients less than or equal to `remainder_max_degree_plus_1`.
+/// *f* (and size of the domain over which it is evaluated) is reduced by the folding factor of
+/// the current layer (as specified by the folding schedule in [FriOptions]) until the remaining
+/// evaluations correspond to a polynomial, called remainder polynomial, with a number of
+/// coefficients less than or equal to `remainder_max_degree_plus_1`.
 ///
 /// At each layer of reduction, the prover commits to the current set of evaluations. This is done
-/// by building a Merkle tree from the evaluations and sending the root of the tree to the verifier
-/// (via [ProverChannel]). The Merkle tree is build in such a way that all evaluations needed to
-/// compute a single value in the next FRI layer are grouped into the same leaf (the number of
-/// evaluations needed to compute a single element in the next FRI layer is equal to the
-/// `folding_factor`). This allows us to decommit all these values using a single Merkle
-/// authentication path.
+/// by building a vector commitment (e.g., a Merkle tree) from the evaluations and sending the
+/// commitment string (e.g., the root of the tree) to the verifier (via [ProverChannel]). The
+/// commitment is built in such a way that all evaluations needed to compute a single value in the
+/// next FRI layer are grouped into the same committed item (the number of evaluations needed to
+/// compute a single element in the next FRI layer is equal to the `folding_factor`). This allows
+/// us to decommit all these values using a single opening.
 ///
 /// After committing to the set of evaluations at the current layer, the prover draws a random
 /// field element α from the channel, and uses it to build the next FRI layer. In the interactive
@@ -67,14 +105,17 @@ mod tests;
 /// sends it to the prover. In the non-interactive version, α is pseudo-randomly generated based
 /// on the values the prover has written into the channel up to that point.
 ///
-/// The prover keeps all FRI layers (consisting of evaluations and corresponding Merkle trees) in
-/// its internal state.
+/// The prover keeps vector commitments of all FRI layers in its internal state, while the
+/// evaluations of each layer are handed over to a [FriLayerStorage]. By default, the evaluations
+/// are kept in memory (see [InMemoryLayerStorage]); to reduce memory consumption for large
+/// domains, a different storage can be supplied via
+/// [new_with_storage()](FriProver::new_with_storage()).
 ///
 /// # Query phase
 /// In the query phase, which is executed via [build_proof()](FriProver::build_proof()) function,
 /// the prover receives a set of positions in the domain *D* from the verifier. The prover then
-/// decommits evaluations corresponding to these positions across all FRI layers (except for the
-/// remainder layer) and builds a [FriProof] from these evaluations. The remainder polynomial
+/// reads evaluations corresponding to these positions across all FRI layers (except for the
+/// remainder layer) from the layer storage and builds a [FriProof] from these evaluations. The remainder polynomial
 /// is included in the proof in its entirety.
 ///
 /// In the interactive version of the protocol, the verifier draws the position uniformly at
@@ -87,29 +128,89 @@ mod tests;
 ///
 /// After the proof is generated, the prover deletes all internally stored FRI layers.
 ///
+/// # Concurrency
+/// When the crate is compiled with `concurrent` feature enabled, hashing of layer evaluations and
+/// degree-respecting projection are performed using multiple threads. To avoid the overhead of
+/// distributing small amounts of work across threads, each thread processes at least
+/// `concurrency_threshold` evaluations. Thus, layers with fewer evaluations than the threshold are
+/// hashed and folded in a single thread. The threshold can be set via
+/// [with_concurrency_threshold()](FriProver::with_concurrency_threshold()) constructor, and
+/// defaults to [DEFAULT_CONCURRENCY_THRESHOLD] otherwise. Construction of layer commitments is
+/// delegated to the vector commitment scheme (e.g., [MerkleTree] builds large trees using
+/// multiple threads).
+///
 /// Calling [build_layers()](FriProver::build_layers()) when the internal state is dirty, or
 /// calling [build_proof()](FriProver::build_proof()) on a clean state will result in a panic.
-pub struct FriProver<B, E, C, H>
-where
+pub struct FriProver<
+    B,
+    E,
+    C,
+    H,
+    V = MerkleTree<H>,
+    S = InMemoryLayerStorage<E>,
+    O = (),
+    D = CpuDrpBackend,
+> where
     B: StarkField,
     E: FieldElement<BaseField = B>,
     C: ProverChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+    S: FriLayerStorage<E>,
+    O: FriObserver<E, H>,
+    D: DrpBackend<B, E>,
 {
     options: FriOptions,
-    layers: Vec<FriLayer<B, E, H>>,
+    concurrency_threshold: usize,
+    layers: Vec<FriLayer<B, H, V>>,
+    storage: S,
     remainder_poly: FriRemainder<E>,
+    batching_coefficients: Vec<E>,
+    pow_nonce: u64,
+    salt_seed: Option<H::Digest>,
+    deep_values: Vec<E>,
+    layer_commitments: Vec<H::Digest>,
+    layer_alphas: Vec<E>,
+    observer: O,
+    drp_backend: D,
     _channel: PhantomData<C>,
 }
 
-struct FriLayer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
-    tree: MerkleTree<H>,
-    evaluations: Vec<E>,
+struct FriLayer<B: StarkField, H: Hasher, V: VectorCommitment<H>> {
+    commitment: V,
+    salt_seed: Option<H::Digest>,
     _base_field: PhantomData<B>,
+    _hasher: PhantomData<H>,
 }
 
 struct FriRemainder<E: FieldElement>(Vec<E>);
 
+/// Describes a FRI layer committed to via [FriProver::commit_layer()] method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayerHandle {
+    index: usize,
+    domain_size: usize,
+    folding_factor: usize,
+}
+
+impl LayerHandle {
+    /// Returns the index of this layer; the first committed layer has index 0.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the size of the domain over which evaluations of this layer were committed to.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns the factor by which evaluations of this layer were folded to get evaluations of
+    /// the next layer.
+    pub fn folding_factor(&self) -> usize {
+        self.folding_factor
+    }
+}
+
 // PROVER IMPLEMENTATION
 // ================================================================================================
 
@@ -120,26 +221,175 @@ where
     C: ProverChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = B>,
 {
-    // CONSTRUCTOR
+    // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new FRI prover instantiated with the provided `options`.
+    ///
+    /// The returned prover commits to FRI layers using Merkle trees, and keeps evaluations of all
+    /// FRI layers in memory.
     pub fn new(options: FriOptions) -> Self {
+        Self::with_concurrency_threshold(options, DEFAULT_CONCURRENCY_THRESHOLD)
+    }
+
+    /// Returns a new FRI prover instantiated with the provided `options` and concurrency
+    /// threshold.
+    ///
+    /// When `concurrent` feature is enabled, FRI layers with fewer than `concurrency_threshold`
+    /// evaluations are hashed and folded in a single thread; otherwise, the threshold has no
+    /// effect.
+    ///
+    /// # Panics
+    /// Panics if `concurrency_threshold` is zero.
+    pub fn with_concurrency_threshold(options: FriOptions, concurrency_threshold: usize) -> Self {
+        assert!(concurrency_threshold > 0, "concurrency threshold must be greater than zero");
         FriProver {
             options,
+            concurrency_threshold,
             layers: Vec::new(),
+            storage: InMemoryLayerStorage::new(),
             remainder_poly: FriRemainder(vec![]),
+            batching_coefficients: Vec::new(),
+            pow_nonce: 0,
+            salt_seed: None,
+            deep_values: Vec::new(),
+            layer_commitments: Vec::new(),
+            layer_alphas: Vec::new(),
+            observer: (),
+            drp_backend: CpuDrpBackend,
             _channel: PhantomData,
         }
     }
+}
+
+impl<B, E, C, H, V, S> FriProver<B, E, C, H, V, S>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+    S: FriLayerStorage<E>,
+{
+    /// Returns a new FRI prover instantiated with the provided `options` which keeps evaluations
+    /// of FRI layers in the provided `storage`.
+    ///
+    /// During the commit phase, only commitments to FRI layers are retained by the prover, while
+    /// layer evaluations are handed over to the `storage`. During the query phase, evaluations at
+    /// the queried positions are read back from the `storage`.
+    ///
+    /// # Panics
+    /// Panics if the `storage` is not empty.
+    pub fn new_with_storage(options: FriOptions, storage: S) -> Self {
+        assert_eq!(storage.num_layers(), 0, "FRI layer storage must be empty");
+        FriProver {
+            options,
+            concurrency_threshold: DEFAULT_CONCURRENCY_THRESHOLD,
+            layers: Vec::new(),
+            storage,
+            remainder_poly: FriRemainder(vec![]),
+            batching_coefficients: Vec::new(),
+            pow_nonce: 0,
+            salt_seed: None,
+            deep_values: Vec::new(),
+            layer_commitments: Vec::new(),
+            layer_alphas: Vec::new(),
+            observer: (),
+            drp_backend: CpuDrpBackend,
+            _channel: PhantomData,
+        }
+    }
+}
+
+impl<B, E, C, H, V, S, O, D> FriProver<B, E, C, H, V, S, O, D>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+    S: FriLayerStorage<E>,
+    O: FriObserver<E, H>,
+    D: DrpBackend<B, E>,
+{
+    /// Returns this prover updated to notify the specified `observer` about progress of proof
+    /// generation.
+    ///
+    /// The observer is notified after each FRI layer is committed to, after each α is drawn, and
+    /// after a proof is built (see [FriObserver]).
+    pub fn with_observer<P: FriObserver<E, H>>(
+        self,
+        observer: P,
+    ) -> FriProver<B, E, C, H, V, S, P, D> {
+        FriProver {
+            options: self.options,
+            concurrency_threshold: self.concurrency_threshold,
+            layers: self.layers,
+            storage: self.storage,
+            remainder_poly: self.remainder_poly,
+            batching_coefficients: self.batching_coefficients,
+            pow_nonce: self.pow_nonce,
+            salt_seed: self.salt_seed,
+            deep_values: self.deep_values,
+            layer_commitments: self.layer_commitments,
+            layer_alphas: self.layer_alphas,
+            observer,
+            drp_backend: self.drp_backend,
+            _channel: PhantomData,
+        }
+    }
+
+    /// Returns this prover updated to apply degree-respecting projection to layer evaluations
+    /// using the specified `backend`.
+    ///
+    /// If the backend cannot fold evaluations of a given layer, the prover falls back to folding
+    /// them on the CPU (see [DrpBackend]).
+    pub fn with_drp_backend<P: DrpBackend<B, E>>(
+        self,
+        backend: P,
+    ) -> FriProver<B, E, C, H, V, S, O, P> {
+        FriProver {
+            options: self.options,
+            concurrency_threshold: self.concurrency_threshold,
+            layers: self.layers,
+            storage: self.storage,
+            remainder_poly: self.remainder_poly,
+            batching_coefficients: self.batching_coefficients,
+            pow_nonce: self.pow_nonce,
+            salt_seed: self.salt_seed,
+            deep_values: self.deep_values,
+            layer_commitments: self.layer_commitments,
+            layer_alphas: self.layer_alphas,
+            observer: self.observer,
+            drp_backend: backend,
+            _channel: PhantomData,
+        }
+    }
+
+    /// Returns this prover updated to derive salts for FRI layer commitments from the specified
+    /// secret `seed`.
+    ///
+    /// The seed is used only when the options of this prover specify salted layer commitments
+    /// (see [FriOptions::with_salted_commitments()]). For the salts to hide layer evaluations,
+    /// the seed must be sampled uniformly at random and must not be revealed to the verifier.
+    pub fn with_salt_seed(mut self, seed: [u8; 32]) -> Self {
+        self.salt_seed = Some(H::hash(&seed));
+        self
+    }
 
     // ACCESSORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns folding factor for this prover.
+    /// Returns folding factor used at the first FRI layer by this prover.
     pub fn folding_factor(&self) -> usize {
         self.options.folding_factor()
     }
 
+    /// Returns the number of layer evaluations below which this prover builds a FRI layer in a
+    /// single thread.
+    pub fn concurrency_threshold(&self) -> usize {
+        self.concurrency_threshold
+    }
+
     /// Returns offset of the domain over which FRI protocol is executed by this prover.
     pub fn domain_offset(&self) -> B {
         self.options.domain_offset()
@@ -151,10 +401,58 @@ where
         self.layers.len()
     }
 
-    /// Clears a vector of internally stored layers.
+    /// Returns FRI layer commitments written into the channel during the commit phase.
+    ///
+    /// The commitments are listed in the order in which they were written into the channel; if
+    /// the commit phase has been completed, the last commitment is the commitment to the
+    /// remainder polynomial. The commitments are cleared when the proof is built.
+    pub fn layer_commitments(&self) -> &[H::Digest] {
+        &self.layer_commitments
+    }
+
+    /// Returns random values α drawn from the channel during the commit phase and used to fold
+    /// FRI layers.
+    ///
+    /// The i-th value was used to fold evaluations of the i-th FRI layer. The values are cleared
+    /// when the proof is built.
+    pub fn layer_alphas(&self) -> &[E] {
+        &self.layer_alphas
+    }
+
+    /// Returns coefficients used to combine codewords during the last execution of the
+    /// [build_layers_batched()](FriProver::build_layers_batched()) method.
+    ///
+    /// If the layers were built via [build_layers()](FriProver::build_layers()) method, an empty
+    /// slice is returned.
+    pub fn batching_coefficients(&self) -> &[E] {
+        &self.batching_coefficients
+    }
+
+    /// Returns the observer notified by this prover about progress of proof generation.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Returns the query seed proof-of-work nonce found during the last execution of the
+    /// [build_layers()](FriProver::build_layers()) method.
+    ///
+    /// If the grinding factor specified in the options of this prover is zero, the nonce is
+    /// always zero.
+    pub fn pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
+    /// Clears a vector of internally stored layers and removes all layer evaluations from the
+    /// layer storage.
     pub fn reset(&mut self) {
         self.layers.clear();
+        self.storage.clear();
         self.remainder_poly.0.clear();
+        self.batching_coefficients.clear();
+        self.pow_nonce = 0;
+        self.deep_values.clear();
+        self.layer_commitments.clear();
+        self.layer_alphas.clear();
     }
 
     // COMMIT PHASE
@@ -166,9 +464,14 @@ where
     /// application of the DRP the degree of the function (and size of the domain) is reduced by
     /// `folding_factor` until the remaining evaluations can be represented by a remainder polynomial
     /// with at most `remainder_max_degree_plus_1` number of coefficients.
-    /// At each layer of reduction the current evaluations are committed to using a Merkle tree,
-    /// and the root of this tree is written into the channel. After this the prover draws a random
-    /// field element α from the channel, and uses it in the next application of the DRP.
+    /// At each layer of reduction the current evaluations are committed to using the vector
+    /// commitment scheme `V`, and the commitment string is written into the channel. After this
+    /// the prover draws a random field element α from the channel, and uses it in the next
+    /// application of the DRP.
+    ///
+    /// If the options of this prover specify a non-zero grinding factor, after all layers are
+    /// committed to, the prover also searches for a proof-of-work nonce for the query seed. This
+    /// nonce can be retrieved via [pow_nonce()](FriProver::pow_nonce()) method.
     ///
     /// # Panics
     /// Panics if the prover state is dirty (the vector of layers is not empty).
@@ -178,53 +481,249 @@ where
             "a prior proof generation request has not been completed yet"
         );
 
-        // reduce the degree by folding_factor at each iteration until the remaining polynomial
-        // has small enough degree
+        // reduce the degree by the folding factor of each layer at each iteration until the
+        // remaining polynomial has small enough degree
         for _ in 0..self.options.num_fri_layers(evaluations.len()) {
-            match self.folding_factor() {
-                2 => self.build_layer::<2>(channel, &mut evaluations),
-                4 => self.build_layer::<4>(channel, &mut evaluations),
-                8 => self.build_layer::<8>(channel, &mut evaluations),
-                16 => self.build_layer::<16>(channel, &mut evaluations),
-                _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
-            }
+            self.commit_layer(channel, &mut evaluations);
         }
 
+        self.finalize(channel, evaluations);
+    }
+
+    /// Executes a single step of the commit phase of the FRI protocol.
+    ///
+    /// The `evaluations` are committed to using the vector commitment scheme `V`, and the
+    /// commitment string is written into the channel. Then, a random field element α is drawn from
+    /// the channel and is used to fold the `evaluations` in place by the folding factor of the
+    /// current layer. Thus, after this method returns, `evaluations` contain evaluations of the
+    /// next FRI layer.
+    ///
+    /// Layers committed to via this method must be completed by calling
+    /// [finalize()](FriProver::finalize()) with evaluations of the last layer. Calling this
+    /// method repeatedly followed by [finalize()](FriProver::finalize()) is equivalent to
+    /// calling [build_layers()](FriProver::build_layers()), but allows the caller to interact
+    /// with the channel between FRI layers.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The commit phase has already been finalized and the proof has not been built yet.
+    /// * `evaluations` are small enough to be represented by the remainder polynomial, and thus,
+    ///   no more FRI layers are needed.
+    /// * Salted layer commitments are specified in the options of this prover, but the salt seed
+    ///   has not been set via [with_salt_seed()](FriProver::with_salt_seed()).
+    pub fn commit_layer(&mut self, channel: &mut C, evaluations: &mut Vec<E>) -> LayerHandle {
+        assert!(
+            self.remainder_poly.0.is_empty(),
+            "a prior proof generation request has not been completed yet"
+        );
+        assert!(
+            !self.options.salted_commitments() || self.salt_seed.is_some(),
+            "salted FRI layer commitments require a salt seed"
+        );
+        assert!(
+            self.options.num_fri_layers(evaluations.len()) > 0,
+            "evaluations over a domain of size {} do not require another FRI layer",
+            evaluations.len()
+        );
+
+        let folding_factor = self.options.folding_factor_at(self.layers.len());
+        match folding_factor {
+            2 => self.build_layer::<2>(channel, evaluations),
+            4 => self.build_layer::<4>(channel, evaluations),
+            8 => self.build_layer::<8>(channel, evaluations),
+            16 => self.build_layer::<16>(channel, evaluations),
+            32 => self.build_layer::<32>(channel, evaluations),
+            64 => self.build_layer::<64>(channel, evaluations),
+            _ => unimplemented!("folding factor {} is not supported", folding_factor),
+        }
+    }
+
+    /// Completes the commit phase of the FRI protocol.
+    ///
+    /// The `evaluations` of the last FRI layer are interpolated into the remainder polynomial, and
+    /// a commitment to the remainder is written into the channel. If the options of this prover
+    /// specify a non-zero grinding factor, the prover then also searches for a proof-of-work
+    /// nonce for the query seed.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The commit phase has already been finalized and the proof has not been built yet.
+    /// * `evaluations` are too large to be represented by the remainder polynomial (i.e., more
+    ///   FRI layers need to be committed to via [commit_layer()](FriProver::commit_layer())).
+    pub fn finalize(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+        assert!(
+            self.remainder_poly.0.is_empty(),
+            "a prior proof generation request has not been completed yet"
+        );
+        assert_eq!(
+            self.options.num_fri_layers(evaluations.len()),
+            0,
+            "evaluations over a domain of size {} require more FRI layers",
+            evaluations.len()
+        );
+
         self.set_remainder(channel, &mut evaluations);
+
+        // apply proof-of-work to the query seed
+        let grinding_factor = self.options.grinding_factor();
+        if grinding_factor > 0 {
+            self.pow_nonce = channel.grind_fri_query_seed(grinding_factor);
+        }
+    }
+
+    /// Executes the commit phase of the FRI protocol for a random linear combination of the
+    /// provided `codewords`.
+    ///
+    /// First, a random value γ is drawn from the channel, and the codewords are combined into a
+    /// single codeword as f_0 + γ * f_1 + γ^2 * f_2 + ..., where f_i is the i-th codeword. Then,
+    /// the commit phase is executed for the combined codeword in the same way as described in
+    /// [build_layers()](FriProver::build_layers()).
+    ///
+    /// The coefficients used to combine the codewords are recorded by the prover and can be
+    /// retrieved via [batching_coefficients()](FriProver::batching_coefficients()) method. The
+    /// verifier can reproduce them via
+    /// [FriVerifier::new_batched()](crate::FriVerifier::new_batched()).
+    ///
+    /// For the protocol to be sound, the prover must commit to all `codewords` (e.g., by writing
+    /// commitments to the codewords into the channel) before calling this
+    /// method.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The prover state is dirty (the vector of layers is not empty).
+    /// * `codewords` is empty or the codewords are not all of the same length.
+    pub fn build_layers_batched(&mut self, channel: &mut C, codewords: Vec<Vec<E>>) {
+        assert!(!codewords.is_empty(), "at least one codeword must be provided");
+        let codeword_len = codewords[0].len();
+        assert!(
+            codewords.iter().all(|codeword| codeword.len() == codeword_len),
+            "all codewords must have the same length"
+        );
+
+        // draw a random value from the channel and use its powers to combine the codewords
+        let gamma = channel.draw_fri_alpha();
+        let batching_coefficients = get_batching_coefficients(gamma, codewords.len());
+
+        let mut codewords = codewords.into_iter();
+        let mut evaluations = codewords.next().expect("no codewords");
+        for (codeword, &coefficient) in codewords.zip(batching_coefficients.iter().skip(1)) {
+            evaluations.iter_mut().zip(codeword).for_each(|(acc, value)| {
+                *acc += value * coefficient;
+            });
+        }
+
+        self.build_layers(channel, evaluations);
+        self.batching_coefficients = batching_coefficients;
     }
 
     /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
     /// alpha from the channel and use it to perform degree-respecting projection.
-    fn build_layer<const N: usize>(&mut self, channel: &mut C, evaluations: &mut Vec<E>) {
+    fn build_layer<const N: usize>(
+        &mut self,
+        channel: &mut C,
+        evaluations: &mut Vec<E>,
+    ) -> LayerHandle {
+        let handle = LayerHandle {
+            index: self.layers.len(),
+            domain_size: evaluations.len(),
+            folding_factor: N,
+        };
+
         // commit to the evaluations at the current layer; we do this by first transposing the
-        // evaluations into a matrix of N columns, and then building a Merkle tree from the
-        // rows of this matrix; we do this so that we could de-commit to N values with a single
-        // Merkle authentication path.
+        // evaluations into a matrix of N columns, and then building a vector commitment from the
+        // hashed rows of this matrix; we do this so that we could de-commit to N values with a
+        // single opening.
+        //
+        // when running in multiple threads, each thread processes at least as many rows as
+        // needed to cover the concurrency threshold; thus, small layers are hashed in one thread.
+        let min_batch_size = (self.concurrency_threshold / N).max(1);
         let transposed_evaluations = transpose_slice(evaluations);
-        let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
-        let evaluation_tree =
-            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
-        channel.commit_fri_layer(*evaluation_tree.root());
+        let mut hashed_evaluations =
+            hash_values::<H, E, N>(&transposed_evaluations, min_batch_size);
+
+        // when layer commitments are salted, combine the hash of each row with a salt derived
+        // from the secret salt seed of this layer
+        let salt_seed = if self.options.salted_commitments() {
+            let prover_seed = self.salt_seed.expect("salt seed has not been set");
+            let layer_seed = H::merge_with_int(prover_seed, self.layers.len() as u64);
+            let rows = (0..hashed_evaluations.len()).collect::<Vec<_>>();
+            apply_salts::<H>(&mut hashed_evaluations, &get_salts::<H>(layer_seed, &rows));
+            Some(layer_seed)
+        } else {
+            None
+        };
+
+        let evaluation_commitment =
+            V::commit(hashed_evaluations).expect("failed to construct FRI layer commitment");
+        let commitment = evaluation_commitment.commitment();
+        channel.commit_fri_layer(commitment);
+        self.layer_commitments.push(commitment);
+        self.observer.on_layer_committed(handle, &commitment);
+
+        // in DEEP-FRI, we first draw an out-of-domain point z, and send evaluations of the
+        // decomposed layer polynomial at z to the verifier
+        let domain_offset = self.domain_offset();
+        let deep_point = if self.options.deep_fri() {
+            let z = channel.draw_fri_alpha();
+            let deep_values = get_deep_values(&transposed_evaluations, domain_offset, z);
+            channel.commit_fri_deep_values(&deep_values);
+            Some((z, deep_values))
+        } else {
+            None
+        };
 
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
-        // projection to reduce the degree of evaluations by N
+        // projection to reduce the degree of evaluations by N; if the DRP backend cannot fold
+        // the evaluations, fall back to folding them on the CPU
         let alpha = channel.draw_fri_alpha();
-        *evaluations = apply_drp(&transposed_evaluations, self.domain_offset(), alpha);
+        self.layer_alphas.push(alpha);
+        self.observer.on_alpha_drawn(handle, alpha);
+        let mut folded_evaluations = self
+            .drp_backend
+            .apply_drp(&transposed_evaluations, domain_offset, alpha, min_batch_size)
+            .unwrap_or_else(|| {
+                apply_drp_with_min_batch_size(
+                    &transposed_evaluations,
+                    domain_offset,
+                    alpha,
+                    min_batch_size,
+                )
+            });
+
+        // in DEEP-FRI, shift the folded evaluations by the out-of-domain point z
+        if let Some((z, deep_values)) = deep_point {
+            apply_deep_shift(
+                &mut folded_evaluations,
+                domain_offset,
+                N,
+                alpha,
+                z,
+                &deep_values,
+                min_batch_size,
+            );
+            self.deep_values.extend_from_slice(&deep_values);
+        }
+        *evaluations = folded_evaluations;
+        self.storage.store_layer(flatten_vector_elements(transposed_evaluations));
         self.layers.push(FriLayer {
-            tree: evaluation_tree,
-            evaluations: flatten_vector_elements(transposed_evaluations),
+            commitment: evaluation_commitment,
+            salt_seed,
             _base_field: PhantomData,
+            _hasher: PhantomData,
         });
+
+        handle
     }
 
     /// Creates remainder polynomial in coefficient form from a vector of `evaluations` over a domain.
     fn set_remainder(&mut self, channel: &mut C, evaluations: &mut [E]) {
-        let inv_twiddles = fft::get_inv_twiddles(evaluations.len());
-        fft::interpolate_poly_with_offset(evaluations, &inv_twiddles, self.options.domain_offset());
+        let domain = EvaluationDomain::new(evaluations.len(), self.options.domain_offset());
+        domain.interpolate(evaluations);
         let remainder_poly_size = evaluations.len() / self.options.blowup_factor();
         let remainder_poly = evaluations[..remainder_poly_size].to_vec();
         let commitment = <H as ElementHasher>::hash_elements(&remainder_poly);
         channel.commit_fri_layer(commitment);
+        self.layer_commitments.push(commitment);
         self.remainder_poly = FriRemainder(remainder_poly);
     }
 
@@ -233,10 +732,16 @@ where
     /// Executes query phase of FRI protocol.
     ///
     /// For each of the provided `positions`, corresponding evaluations from each of the layers
-    /// (excluding the remainder layer) are recorded into the proof together with Merkle
-    /// authentication paths from the root of layer commitment trees. For the remainder, we send
+    /// (excluding the remainder layer) are recorded into the proof together with a batch opening
+    /// proof against the layer commitment. If layer commitments are salted, salts for the queried
+    /// evaluations are recorded into the proof as well. For the remainder, we send
     /// the whole remainder polynomial resulting from interpolating the remainder layer.
     ///
+    /// Positions which fold to the same position in a given layer are queried only once, and
+    /// openings for all queries in a layer are aggregated into a single batch proof (e.g., for
+    /// Merkle trees, authentication paths share their common nodes). Thus, repeated positions do
+    /// not increase the size of the proof.
+    ///
     /// # Panics
     /// Panics is the prover state is clean (no FRI layers have been build yet).
     pub fn build_proof(&mut self, positions: &[usize]) -> FriProof {
@@ -246,20 +751,25 @@ where
 
         if !self.layers.is_empty() {
             let mut positions = positions.to_vec();
-            let mut domain_size = self.layers[0].evaluations.len();
-            let folding_factor = self.options.folding_factor();
+            let mut domain_size =
+                self.layers[0].commitment.domain_len() * self.options.folding_factor_at(0);
 
             // for all FRI layers, except the last one, record tree root, determine a set of query
             // positions, and query the layer at these positions.
             for i in 0..self.layers.len() {
+                let folding_factor = self.options.folding_factor_at(i);
                 positions = fold_positions(&positions, domain_size, folding_factor);
 
                 // sort of a static dispatch for folding_factor parameter
+                let layer = &self.layers[i];
+                let storage = &mut self.storage;
                 let proof_layer = match folding_factor {
-                    2 => query_layer::<B, E, H, 2>(&self.layers[i], &positions),
-                    4 => query_layer::<B, E, H, 4>(&self.layers[i], &positions),
-                    8 => query_layer::<B, E, H, 8>(&self.layers[i], &positions),
-                    16 => query_layer::<B, E, H, 16>(&self.layers[i], &positions),
+                    2 => query_layer::<B, E, H, V, S, 2>(layer, storage, i, &positions),
+                    4 => query_layer::<B, E, H, V, S, 4>(layer, storage, i, &positions),
+                    8 => query_layer::<B, E, H, V, S, 8>(layer, storage, i, &positions),
+                    16 => query_layer::<B, E, H, V, S, 16>(layer, storage, i, &positions),
+                    32 => query_layer::<B, E, H, V, S, 32>(layer, storage, i, &positions),
+                    64 => query_layer::<B, E, H, V, S, 64>(layer, storage, i, &positions),
                     _ => unimplemented!("folding factor {} is not supported", folding_factor),
                 };
 
@@ -270,11 +780,15 @@ where
 
         // use the remaining polynomial values directly as proof
         let remainder = self.remainder_poly.0.clone();
+        let deep_values = self.deep_values.clone();
+        let pow_nonce = self.pow_nonce;
 
         // clear layers so that another proof can be generated
         self.reset();
 
-        FriProof::new(layers, remainder, 1)
+        let proof = FriProof::new(layers, remainder, deep_values, 1, pow_nonce);
+        self.observer.on_proof_built(&proof);
+        proof
     }
 }
 
@@ -283,24 +797,42 @@ where
 
 /// Builds a single proof layer by querying the evaluations of the passed in FRI layer at the
 /// specified positions.
-fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N: usize>(
-    layer: &FriLayer<B, E, H>,
+fn query_layer<B, E, H, V, S, const N: usize>(
+    layer: &FriLayer<B, H, V>,
+    storage: &mut S,
+    layer_idx: usize,
     positions: &[usize],
-) -> FriProofLayer {
-    // build Merkle authentication paths for all query positions
+) -> FriProofLayer
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: Hasher,
+    V: VectorCommitment<H>,
+    S: FriLayerStorage<E>,
+{
+    // build a batch opening proof for all query positions
     let proof = layer
-        .tree
-        .prove_batch(positions)
-        .expect("failed to generate a Merkle proof for FRI layer queries");
-
-    // build a list of polynomial evaluations at each position; since evaluations in FRI layers
-    // are stored in transposed form, a position refers to N evaluations which are committed
-    // in a single leaf
-    let evaluations: &[[E; N]] = group_slice_elements(&layer.evaluations);
-    let mut queried_values: Vec<[E; N]> = Vec::with_capacity(positions.len());
-    for &position in positions.iter() {
-        queried_values.push(evaluations[position]);
-    }
+        .commitment
+        .open_batch(positions)
+        .expect("failed to generate a batch opening proof for FRI layer queries");
+
+    // read polynomial evaluations at each position from the layer storage; since evaluations in
+    // FRI layers are stored in transposed form, a position refers to N evaluations which are
+    // committed in a single leaf
+    let evaluations = storage.read_rows(layer_idx, positions, N);
+    let queried_values: Vec<[E; N]> = group_slice_elements(&evaluations).to_vec();
+
+    // re-derive salts for the queried rows, if the layer commitment is salted
+    let salts = match layer.salt_seed {
+        Some(layer_seed) => get_salts::<H>(layer_seed, positions),
+        None => Vec::new(),
+    };
+
+    FriProofLayer::new::<H, E, V, N>(queried_values, salts, proof)
+}
 
-    FriProofLayer::new(queried_values, proof)
+/// Returns salts for the specified rows of a FRI layer derived from the secret salt seed of the
+/// layer.
+fn get_salts<H: Hasher>(layer_seed: H::Digest, rows: &[usize]) -> Vec<H::Digest> {
+    rows.iter().map(|&row| H::merge_with_int(layer_seed, row as u64)).collect()
 }
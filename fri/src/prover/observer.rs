@@ -0,0 +1,38 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crypto::Hasher;
+use math::FieldElement;
+
+use super::LayerHandle;
+use crate::FriProof;
+
+// FRI OBSERVER TRAIT
+// ================================================================================================
+
+/// Defines an interface for observing progress of a [FriProver](crate::FriProver).
+///
+/// The prover invokes methods of this trait at specific points of proof generation. This can be
+/// used to collect telemetry (e.g., per-layer timings, layer sizes, or layer commitments) without
+/// modifying the prover. All methods have default implementations which do nothing, and thus,
+/// implementors need to override only the methods they are interested in.
+///
+/// An observer can be attached to a prover via
+/// [FriProver::with_observer()](crate::FriProver::with_observer()). By default, the prover uses
+/// the unit type `()` as its observer, which ignores all events.
+pub trait FriObserver<E: FieldElement, H: Hasher> {
+    /// Invoked after the prover has committed to evaluations of a FRI layer and has written the
+    /// `commitment` into the channel.
+    fn on_layer_committed(&mut self, _layer: LayerHandle, _commitment: &H::Digest) {}
+
+    /// Invoked after the prover has drawn the random value `alpha` used to fold evaluations of
+    /// the specified layer.
+    fn on_alpha_drawn(&mut self, _layer: LayerHandle, _alpha: E) {}
+
+    /// Invoked after the prover has built a FRI proof in the query phase.
+    fn on_proof_built(&mut self, _proof: &FriProof) {}
+}
+
+impl<E: FieldElement, H: Hasher> FriObserver<E, H> for () {}
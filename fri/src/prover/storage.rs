@@ -0,0 +1,91 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::FieldElement;
+
+// FRI LAYER STORAGE TRAIT
+// ================================================================================================
+
+/// Defines an interface for storing evaluations of FRI layers between the commit phase and the
+/// query phase of the FRI protocol.
+///
+/// During the commit phase, a [FriProver](crate::FriProver) hands over evaluations of every FRI
+/// layer to the storage, and retains only the Merkle tree built from these evaluations. During
+/// the query phase, the prover reads back only the evaluations at the queried positions.
+///
+/// Evaluations of a layer are stored in transposed form. That is, a layer which was folded by a
+/// factor of `N` is stored as a sequence of rows, where each row consists of `N` evaluations
+/// committed to in a single leaf of the layer's Merkle tree.
+///
+/// The default implementation ([InMemoryLayerStorage]) keeps all layers in memory. Since layer
+/// evaluations dominate memory consumption of the prover for large domains, implementations of
+/// this trait can spill the evaluations to a different backing store (e.g., disk or
+/// memory-mapped files) instead.
+pub trait FriLayerStorage<E: FieldElement> {
+    /// Saves evaluations of the next FRI layer into this storage.
+    ///
+    /// Layers are stored in the order in which they are built; thus, the first call to this
+    /// method stores evaluations for the layer with index 0, the second call stores evaluations
+    /// for the layer with index 1 etc.
+    fn store_layer(&mut self, evaluations: Vec<E>);
+
+    /// Returns the number of layers saved in this storage.
+    fn num_layers(&self) -> usize;
+
+    /// Returns evaluations from the specified `rows` of the layer at the specified index.
+    ///
+    /// Each row consists of `row_width` consecutive evaluations; evaluations from all requested
+    /// rows are returned in a single vector in the order in which the rows were specified.
+    ///
+    /// # Panics
+    /// Panics if the specified layer or any of the rows does not exist in this storage, or if
+    /// the evaluations could not be read from the underlying backing store.
+    fn read_rows(&mut self, layer_idx: usize, rows: &[usize], row_width: usize) -> Vec<E>;
+
+    /// Removes all layers from this storage.
+    fn clear(&mut self);
+}
+
+// IN-MEMORY LAYER STORAGE
+// ================================================================================================
+
+/// Provides a default implementation of the [FriLayerStorage] trait which keeps evaluations of all
+/// FRI layers in memory.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryLayerStorage<E: FieldElement> {
+    layers: Vec<Vec<E>>,
+}
+
+impl<E: FieldElement> InMemoryLayerStorage<E> {
+    /// Returns a new empty in-memory layer storage.
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+}
+
+impl<E: FieldElement> FriLayerStorage<E> for InMemoryLayerStorage<E> {
+    fn store_layer(&mut self, evaluations: Vec<E>) {
+        self.layers.push(evaluations);
+    }
+
+    fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    fn read_rows(&mut self, layer_idx: usize, rows: &[usize], row_width: usize) -> Vec<E> {
+        let layer = &self.layers[layer_idx];
+        let mut result = Vec::with_capacity(rows.len() * row_width);
+        for &row in rows {
+            result.extend_from_slice(&layer[row * row_width..(row + 1) * row_width]);
+        }
+        result
+    }
+
+    fn clear(&mut self) {
+        self.layers.clear();
+    }
+}
@@ -4,15 +4,29 @@
 // LICENSE file in the root directory of this source tree.
 
 use alloc::vec::Vec;
+use core::cell::Cell;
 
-use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, RandomCoin};
-use math::{fft, fields::f128::BaseElement, FieldElement};
-use utils::{Deserializable, Serializable, SliceReader};
+use crypto::{
+    hashers::Blake3_256, DefaultRandomCoin, ElementHasher, Hasher, KaryMerkleTree, MerkleTree,
+    MerkleTreeBuilder, RandomCoin, Transcript, TranscriptLabel, VectorCommitment,
+};
+use math::{
+    fft::{self, EvaluationDomain},
+    fields::f128::BaseElement,
+    FieldElement,
+};
+use utils::{
+    transpose_slice, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
 
-use super::{DefaultProverChannel, FriProver};
+use super::{
+    DefaultProverChannel, DrpBackend, FriLayerStorage, FriObserver, FriProver, FriProverBuilder,
+    InMemoryLayerStorage, LayerHandle,
+};
 use crate::{
-    verifier::{DefaultVerifierChannel, FriVerifier},
-    FriOptions, FriProof, VerifierError,
+    verifier::{eval_horner, DefaultVerifierChannel, FriVerifier, VerifierChannel},
+    FriOptions, FriOptionsError, FriProof, VerifierError,
 };
 
 type Blake3 = Blake3_256<BaseElement>;
@@ -38,6 +52,697 @@ fn fri_folding_4() {
     fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
 }
 
+#[test]
+fn fri_folding_32() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 5;
+    let max_remainder_degree = 7;
+    fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
+}
+
+#[test]
+fn fri_folding_64() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 6;
+    let max_remainder_degree = 255;
+    fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
+}
+
+#[test]
+fn fri_folding_schedule() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let max_remainder_degree = 7;
+    let options =
+        FriOptions::with_folding_schedule(1 << lde_blowup_e, vec![16, 8, 4], max_remainder_degree);
+    assert_eq!(3, options.num_fri_layers(1 << (trace_length_e + lde_blowup_e)));
+    fri_prove_verify_with_options(trace_length_e, options)
+}
+
+#[test]
+fn fri_concurrency_threshold() {
+    let trace_length = 1 << 12;
+    let options = FriOptions::new(8, 4, 255);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+
+    // layers built in a single thread and in multiple threads should result in the same proof
+    let build_proof = |concurrency_threshold: usize| {
+        let mut channel = build_prover_channel(trace_length, &options);
+        let mut prover =
+            FriProver::with_concurrency_threshold(options.clone(), concurrency_threshold);
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions(0);
+        prover.build_proof(&positions)
+    };
+    assert_eq!(build_proof(1), build_proof(usize::MAX));
+}
+
+#[test]
+fn fri_layer_storage() {
+    let trace_length = 1 << 12;
+    let options = FriOptions::new(8, 4, 255);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+
+    // build a proof with evaluations kept in memory
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let expected = prover.build_proof(&positions);
+
+    // build a proof with evaluations spilled into a byte-backed storage
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::<_, _, _, _, MerkleTree<Blake3>, _>::new_with_storage(
+        options.clone(),
+        ByteLayerStorage::default(),
+    );
+    prover.build_layers(&mut channel, evaluations);
+    assert_eq!(options.num_fri_layers(trace_length * 8), prover.storage.num_layers());
+    let positions = channel.draw_query_positions(0);
+    let actual = prover.build_proof(&positions);
+
+    assert_eq!(expected, actual);
+    assert_eq!(0, prover.storage.num_layers());
+}
+
+#[test]
+fn fri_commit_layer() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::with_folding_schedule(lde_blowup, vec![8, 4, 2], 7);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // build a proof with all layers committed to in a single call
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let expected = prover.build_proof(&positions);
+
+    // build a proof by committing to one layer at a time
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    let mut layer_evaluations = evaluations;
+    let handles = (0..options.num_fri_layers(domain_size))
+        .map(|_| prover.commit_layer(&mut channel, &mut layer_evaluations))
+        .collect::<Vec<_>>();
+    prover.finalize(&mut channel, layer_evaluations);
+    let positions = channel.draw_query_positions(0);
+    let actual = prover.build_proof(&positions);
+
+    assert_eq!(expected, actual);
+    assert_eq!(vec![8, 4, 2, 2], handles.iter().map(|h| h.folding_factor()).collect::<Vec<_>>());
+    assert_eq!(
+        vec![8192, 1024, 256, 128],
+        handles.iter().map(|h| h.domain_size()).collect::<Vec<_>>()
+    );
+    assert_eq!(3, handles.last().unwrap().index());
+}
+
+#[test]
+fn fri_query_deduplication() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+
+    // all positions fold into the same position in the first layer
+    let folded_domain_size = domain_size / 4;
+    let positions = [3, 3 + folded_domain_size, 3 + 2 * folded_domain_size];
+    let proof = prover.build_proof(&positions);
+    let proof_size = proof.size();
+
+    let commitments = channel.layer_commitments().to_vec();
+    let (layer_queries, _, _) = proof
+        .clone()
+        .parse_layers::<Blake3, BaseElement, MerkleTree<Blake3>>(domain_size, &options)
+        .unwrap();
+    for queries in layer_queries {
+        assert_eq!(4, queries.len());
+    }
+
+    // a proof for a single position should be of the same size
+    prover.build_layers(&mut build_prover_channel(trace_length, &options), evaluations.clone());
+    assert_eq!(proof_size, prover.build_proof(&positions[..1]).size());
+
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn fri_remainder_commitment_mismatch() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 255);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    // replace the remainder commitment with a commitment to a different remainder
+    let mut commitments = channel.layer_commitments().to_vec();
+    *commitments.last_mut().unwrap() = Blake3::hash_elements(&[BaseElement::ONE]);
+
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::RemainderCommitmentMismatch), result);
+}
+
+#[test]
+fn fri_remainder_degree_bound_without_layers() {
+    // a polynomial of degree 64 is evaluated over a domain of 64 * 8 = 512 points; with the
+    // remainder degree bound of 63, no FRI layers are needed for this domain, and thus, the
+    // degree implied by folding is greater than the declared bound
+    let options = FriOptions::new(8, 4, 63);
+    let max_poly_degree = 64;
+    let domain_size = 512;
+    assert_eq!(0, options.num_fri_layers(domain_size));
+
+    // the remainder is consistent with the queried evaluations and with its commitment, but
+    // contains 65 coefficients
+    let remainder = (0..65).map(|i| BaseElement::new(i + 1)).collect::<Vec<_>>();
+    let domain = EvaluationDomain::new(domain_size, options.domain_offset());
+    let positions = [1, 7, 100, 311];
+    let evaluations = positions
+        .iter()
+        .map(|&p| eval_horner::<BaseElement>(&remainder, domain.get_x_at(p)))
+        .collect::<Vec<_>>();
+    let mut channel = RemainderChannel {
+        commitment: Some(Blake3::hash_elements(&remainder)),
+        remainder,
+    };
+
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = FriVerifier::<_, _, Blake3, _>::new(
+        &mut channel,
+        &mut coin,
+        options.clone(),
+        max_poly_degree,
+    )
+    .unwrap();
+    let result = verifier.verify(&mut channel, &evaluations, &positions);
+    assert_eq!(Err(VerifierError::RemainderDegreeMismatch(63)), result);
+}
+
+#[test]
+fn fri_layer_errors() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 255);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+    let domain_size = trace_length * lde_blowup;
+
+    // replace the commitment to the second layer; the error identifies the layer
+    let mut invalid_commitments = commitments.clone();
+    invalid_commitments[1] = Blake3::hash_elements(&[BaseElement::ONE]);
+    let result = verify_proof(
+        proof.clone(),
+        invalid_commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::LayerCommitmentMismatch(1)), result);
+
+    // modify the evaluation at one of the queried positions; the error identifies the layer and
+    // the position
+    let mut invalid_evaluations = evaluations;
+    invalid_evaluations[positions[3]] += BaseElement::ONE;
+    let result = verify_proof(
+        proof,
+        commitments,
+        &invalid_evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::InvalidLayerFolding(0, positions[3])), result);
+}
+
+#[test]
+fn fri_grinding() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 255).with_grinding_factor(8);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(prover.pow_nonce());
+    let proof = prover.build_proof(&positions);
+    assert_ne!(0, proof.pow_nonce());
+
+    // make sure the proof can be verified
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure the proof fails for a grinding factor which was not satisfied by the prover
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options.with_grinding_factor(32),
+    );
+    assert_eq!(Err(VerifierError::QuerySeedProofOfWorkVerificationFailed), result);
+}
+
+#[test]
+fn fri_salted_commitments() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let salted_options = options.clone().with_salted_commitments();
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // build unsalted layer commitments
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let unsalted_commitments = channel.layer_commitments().to_vec();
+
+    // build salted layer commitments; these should differ from unsalted commitments (except for
+    // the remainder commitment)
+    let mut channel = build_prover_channel(trace_length, &salted_options);
+    let mut prover = FriProver::new(salted_options.clone()).with_salt_seed([7; 32]);
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+    let num_layers = commitments.len() - 1;
+    for (salted, unsalted) in commitments.iter().zip(unsalted_commitments.iter()).take(num_layers) {
+        assert_ne!(salted, unsalted);
+    }
+
+    // the proof should be valid against salted options only
+    assert!(DefaultVerifierChannel::<BaseElement, Blake3>::new(
+        proof.clone(),
+        commitments.clone(),
+        domain_size,
+        &options,
+    )
+    .is_err());
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &salted_options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn fri_deep_folding() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::with_folding_schedule(lde_blowup, vec![8, 4], 7);
+    let deep_options = options.clone().with_deep_fri();
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &deep_options);
+    let mut prover = FriProver::new(deep_options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    // the proof should contain DEEP values for all layers but the remainder layer
+    let num_layers = deep_options.num_fri_layers(domain_size);
+    let deep_values = proof.parse_deep_values::<BaseElement>().unwrap();
+    let expected_num_values: usize =
+        (0..num_layers).map(|depth| deep_options.folding_factor_at(depth)).sum();
+    assert_eq!(expected_num_values, deep_values.len());
+
+    // the proof should be valid against DEEP options only
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::NumDeepValuesMismatch(0, expected_num_values)), result);
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &deep_options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn fri_observer() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let options = FriOptions::with_folding_schedule(lde_blowup, vec![8, 4], 7);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone()).with_observer(RecordingObserver::default());
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    // the observer should be notified about every layer except for the remainder layer
+    let observer = prover.observer();
+    let commitments = channel.layer_commitments();
+    let num_layers = commitments.len() - 1;
+    assert_eq!(num_layers, observer.layers.len());
+    assert_eq!(num_layers, observer.alphas.len());
+    for (i, (layer, commitment)) in observer.layers.iter().enumerate() {
+        assert_eq!(i, layer.index());
+        assert_eq!(options.folding_factor_at(i), layer.folding_factor());
+        assert_eq!(&commitments[i], commitment);
+        assert_eq!(*layer, observer.alphas[i].0);
+    }
+    assert_eq!(Some(proof.size()), observer.proof_size);
+}
+
+#[test]
+fn fri_layer_commitments_and_alphas() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+
+    // layer commitments should be the same as the ones written into the channel, and alphas
+    // should be reproducible from the commitments
+    assert_eq!(channel.layer_commitments(), prover.layer_commitments());
+    assert_eq!(prover.num_layers(), prover.layer_alphas().len());
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    for (commitment, &alpha) in prover.layer_commitments().iter().zip(prover.layer_alphas()) {
+        coin.absorb(TranscriptLabel::FriLayer, *commitment);
+        assert_eq!(alpha, coin.draw::<BaseElement>().unwrap());
+    }
+
+    // building the proof should clear commitments and alphas
+    let positions = channel.draw_query_positions(0);
+    prover.build_proof(&positions);
+    assert!(prover.layer_commitments().is_empty());
+    assert!(prover.layer_alphas().is_empty());
+}
+
+#[test]
+fn fri_drp_backend() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let options = FriOptions::with_folding_schedule(lde_blowup, vec![8, 4], 7);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // build a proof using the default CPU backend
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let expected_proof = prover.build_proof(&positions);
+
+    // build a proof using a backend which cannot fold any layer; the prover should fall back to
+    // the CPU for all layers and build the same proof
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone())
+        .with_drp_backend(UnavailableDrpBackend { num_calls: Cell::new(0) });
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    assert_eq!(expected_proof, proof);
+    assert_eq!(channel.layer_commitments().len() - 1, prover.drp_backend.num_calls.get());
+}
+
+#[test]
+fn fri_prover_builder() {
+    type Channel = DefaultProverChannel<BaseElement, Blake3, DefaultRandomCoin<Blake3>>;
+
+    // invalid parameters should be reported as errors
+    let build = |builder: FriProverBuilder| {
+        builder.build::<BaseElement, BaseElement, Channel, Blake3>().err()
+    };
+    assert_eq!(
+        Some(FriOptionsError::BlowupFactorNotPowerOfTwo(6)),
+        build(FriProverBuilder::new(6, 4, 31))
+    );
+    assert_eq!(
+        Some(FriOptionsError::EmptyFoldingSchedule),
+        build(FriProverBuilder::new(8, 4, 31).with_folding_schedule(vec![]))
+    );
+    assert_eq!(
+        Some(FriOptionsError::UnsupportedFoldingFactor(128)),
+        build(FriProverBuilder::new(8, 128, 255))
+    );
+    assert_eq!(
+        Some(FriOptionsError::RemainderSizeNotPowerOfTwo(30)),
+        build(FriProverBuilder::new(8, 4, 29))
+    );
+    assert_eq!(
+        Some(FriOptionsError::RemainderSizeTooSmall(8, 16)),
+        build(FriProverBuilder::new(8, 4, 7).with_folding_schedule(vec![16, 4]))
+    );
+    assert_eq!(
+        Some(FriOptionsError::GrindingFactorTooLarge(33, 32)),
+        build(FriProverBuilder::new(8, 4, 31).with_grinding_factor(33))
+    );
+    assert_eq!(
+        Some(FriOptionsError::ZeroConcurrencyThreshold),
+        build(FriProverBuilder::new(8, 4, 31).with_concurrency_threshold(0))
+    );
+
+    // a prover built from valid parameters should generate valid proofs
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let builder = FriProverBuilder::new(lde_blowup, 4, 31).with_salted_commitments([3; 32]);
+    let options = builder.build_options().unwrap();
+    let mut prover = builder.build::<BaseElement, BaseElement, Channel, Blake3>().unwrap();
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    let result = verify_proof(
+        proof,
+        channel.layer_commitments().to_vec(),
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn fri_batched() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+
+    // build three codewords of the same degree
+    let codewords = (0..3)
+        .map(|i| {
+            let mut p = (0..trace_length as u128)
+                .map(|j| BaseElement::new(j * (i + 1) + i))
+                .collect::<Vec<_>>();
+            p.resize(domain_size, BaseElement::ZERO);
+            fft::evaluate_poly(&mut p, &fft::get_twiddles::<BaseElement>(domain_size));
+            p
+        })
+        .collect::<Vec<_>>();
+
+    // generate the proof for a random linear combination of the codewords
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers_batched(&mut channel, codewords.clone());
+    let batching_coefficients = prover.batching_coefficients().to_vec();
+    assert_eq!(3, batching_coefficients.len());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    // verify the proof against query values of individual codewords
+    let commitments = channel.layer_commitments().to_vec();
+    let mut channel = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+        proof,
+        commitments,
+        domain_size,
+        &options,
+    )
+    .unwrap();
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier =
+        FriVerifier::new_batched(&mut channel, &mut coin, options, trace_length - 1, 3).unwrap();
+    assert_eq!(batching_coefficients, verifier.batching_coefficients());
+
+    let queried_evaluations = codewords
+        .iter()
+        .map(|codeword| positions.iter().map(|&p| codeword[p]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let result = verifier.verify_batched(&mut channel, &queried_evaluations[..2], &positions);
+    assert_eq!(Err(VerifierError::NumCodewordsMismatch(3, 2)), result);
+    let result = verifier.verify_batched(&mut channel, &queried_evaluations, &positions);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn fri_vector_commitment() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // generate the proof using a commitment scheme other than Merkle trees
+    let mut prover = FriProver::<_, _, _, _, HashChainCommitment<Blake3>, _>::new_with_storage(
+        options.clone(),
+        InMemoryLayerStorage::new(),
+    );
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    // verify the proof using the same commitment scheme
+    let commitments = channel.layer_commitments().to_vec();
+    let mut channel =
+        DefaultVerifierChannel::<BaseElement, Blake3, HashChainCommitment<Blake3>>::new(
+            proof,
+            commitments,
+            domain_size,
+            &options,
+        )
+        .unwrap();
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = FriVerifier::new(&mut channel, &mut coin, options, trace_length - 1).unwrap();
+    let queried_evaluations = positions.iter().map(|&p| evaluations[p]).collect::<Vec<_>>();
+    let result = verifier.verify(&mut channel, &queried_evaluations, &positions);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn fri_layer_commitment_with_tree_builder() {
+    let trace_length = 1 << 10;
+    let options = FriOptions::new(8, 4, 31);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+
+    let mut prover = FriProver::new(options);
+    prover.build_layers(&mut channel, evaluations.clone());
+
+    // the first layer commitment can be reproduced by hashing rows one at a time
+    let rows = transpose_slice::<_, 4>(&evaluations);
+    let mut builder = MerkleTreeBuilder::<Blake3>::new(rows.len()).unwrap();
+    for row in rows.iter() {
+        builder.push_leaf(Blake3::hash_elements(row)).unwrap();
+    }
+    let tree = builder.build().unwrap();
+    assert_eq!(*tree.root(), prover.layer_commitments()[0]);
+}
+
+#[test]
+fn fri_kary_merkle_tree() {
+    fri_prove_verify_with_commitment::<KaryMerkleTree<Blake3, 4>>();
+    fri_prove_verify_with_commitment::<KaryMerkleTree<Blake3, 8>>();
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn fri_mmap_merkle_tree() {
+    fri_prove_verify_with_commitment::<crypto::MmapMerkleTree<Blake3>>();
+}
+
+fn fri_prove_verify_with_commitment<V: VectorCommitment<Blake3>>() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::<_, _, _, _, V, _>::new_with_storage(
+        options.clone(),
+        InMemoryLayerStorage::new(),
+    );
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    let commitments = channel.layer_commitments().to_vec();
+    let mut channel = DefaultVerifierChannel::<BaseElement, Blake3, V>::new(
+        proof,
+        commitments,
+        domain_size,
+        &options,
+    )
+    .unwrap();
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = FriVerifier::new(&mut channel, &mut coin, options, trace_length - 1).unwrap();
+    let queried_evaluations = positions.iter().map(|&p| evaluations[p]).collect::<Vec<_>>();
+    let result = verifier.verify(&mut channel, &queried_evaluations, &positions);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
 // TEST UTILS
 // ================================================================================================
 
@@ -80,7 +785,7 @@ pub fn verify_proof(
         proof,
         commitments,
         domain_size,
-        options.folding_factor(),
+        options,
     )
     .unwrap();
     let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
@@ -95,11 +800,17 @@ fn fri_prove_verify(
     folding_factor_e: usize,
     max_remainder_degree: usize,
 ) {
-    let trace_length = 1 << trace_length_e;
     let lde_blowup = 1 << lde_blowup_e;
     let folding_factor = 1 << folding_factor_e;
 
     let options = FriOptions::new(lde_blowup, folding_factor, max_remainder_degree);
+    fri_prove_verify_with_options(trace_length_e, options)
+}
+
+fn fri_prove_verify_with_options(trace_length_e: usize, options: FriOptions) {
+    let trace_length = 1 << trace_length_e;
+    let lde_blowup = options.blowup_factor();
+
     let mut channel = build_prover_channel(trace_length, &options);
     let evaluations = build_evaluations(trace_length, lde_blowup);
 
@@ -135,3 +846,195 @@ fn fri_prove_verify(
     );
     assert!(result.is_err());
 }
+
+/// Observer which records all notifications received from a FRI prover.
+#[derive(Default)]
+struct RecordingObserver {
+    layers: Vec<(LayerHandle, <Blake3 as Hasher>::Digest)>,
+    alphas: Vec<(LayerHandle, BaseElement)>,
+    proof_size: Option<usize>,
+}
+
+impl FriObserver<BaseElement, Blake3> for RecordingObserver {
+    fn on_layer_committed(&mut self, layer: LayerHandle, commitment: &<Blake3 as Hasher>::Digest) {
+        self.layers.push((layer, *commitment));
+    }
+
+    fn on_alpha_drawn(&mut self, layer: LayerHandle, alpha: BaseElement) {
+        self.alphas.push((layer, alpha));
+    }
+
+    fn on_proof_built(&mut self, proof: &FriProof) {
+        self.proof_size = Some(proof.size());
+    }
+}
+
+/// Verifier channel for FRI proofs without any FRI layers which sends an arbitrary remainder.
+struct RemainderChannel {
+    commitment: Option<<Blake3 as Hasher>::Digest>,
+    remainder: Vec<BaseElement>,
+}
+
+impl VerifierChannel<BaseElement> for RemainderChannel {
+    type Hasher = Blake3;
+    type VectorCommitment = MerkleTree<Blake3>;
+
+    fn read_fri_num_partitions(&self) -> usize {
+        1
+    }
+
+    fn read_fri_pow_nonce(&self) -> u64 {
+        0
+    }
+
+    fn read_fri_layer_commitments(&mut self) -> Vec<<Blake3 as Hasher>::Digest> {
+        self.commitment.take().into_iter().collect()
+    }
+
+    fn read_fri_deep_values(&mut self) -> Vec<BaseElement> {
+        Vec::new()
+    }
+
+    fn take_next_fri_layer_queries(&mut self) -> Vec<BaseElement> {
+        unreachable!("the channel does not contain any FRI layers")
+    }
+
+    fn take_next_fri_layer_salts(&mut self) -> Vec<<Blake3 as Hasher>::Digest> {
+        unreachable!("the channel does not contain any FRI layers")
+    }
+
+    fn take_next_fri_layer_proof(
+        &mut self,
+    ) -> <MerkleTree<Blake3> as VectorCommitment<Blake3>>::BatchProof {
+        unreachable!("the channel does not contain any FRI layers")
+    }
+
+    fn take_fri_remainder(&mut self) -> Vec<BaseElement> {
+        core::mem::take(&mut self.remainder)
+    }
+}
+
+/// DRP backend which emulates a device which is not available.
+struct UnavailableDrpBackend {
+    num_calls: Cell<usize>,
+}
+
+impl DrpBackend<BaseElement, BaseElement> for UnavailableDrpBackend {
+    fn apply_drp<const N: usize>(
+        &self,
+        _values: &[[BaseElement; N]],
+        _domain_offset: BaseElement,
+        _alpha: BaseElement,
+        _min_batch_size: usize,
+    ) -> Option<Vec<BaseElement>> {
+        self.num_calls.set(self.num_calls.get() + 1);
+        None
+    }
+}
+
+/// Layer storage which serializes layer evaluations into bytes to emulate external storage.
+#[derive(Default)]
+struct ByteLayerStorage {
+    layers: Vec<Vec<u8>>,
+}
+
+impl FriLayerStorage<BaseElement> for ByteLayerStorage {
+    fn store_layer(&mut self, evaluations: Vec<BaseElement>) {
+        let mut bytes = Vec::new();
+        bytes.write_many(&evaluations);
+        self.layers.push(bytes);
+    }
+
+    fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    fn read_rows(
+        &mut self,
+        layer_idx: usize,
+        rows: &[usize],
+        row_width: usize,
+    ) -> Vec<BaseElement> {
+        let row_bytes = row_width * BaseElement::ELEMENT_BYTES;
+        let mut result = Vec::with_capacity(rows.len() * row_width);
+        for &row in rows {
+            let bytes = &self.layers[layer_idx][row * row_bytes..(row + 1) * row_bytes];
+            let mut reader = SliceReader::new(bytes);
+            result.extend(reader.read_many::<BaseElement>(row_width).unwrap());
+        }
+        result
+    }
+
+    fn clear(&mut self) {
+        self.layers.clear();
+    }
+}
+
+/// Vector commitment which commits to items by sequentially hashing them together; a batch proof
+/// consists of all committed items.
+struct HashChainCommitment<H: Hasher> {
+    items: Vec<H::Digest>,
+    commitment: H::Digest,
+}
+
+impl<H: Hasher> HashChainCommitment<H> {
+    fn hash_chain(items: &[H::Digest]) -> H::Digest {
+        items.iter().fold(H::Digest::default(), |acc, &item| H::merge(&[acc, item]))
+    }
+}
+
+impl<H: Hasher> VectorCommitment<H> for HashChainCommitment<H> {
+    type BatchProof = Vec<H::Digest>;
+    type Error = ();
+
+    fn commit(items: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        let commitment = Self::hash_chain(&items);
+        Ok(Self { items, commitment })
+    }
+
+    fn commitment(&self) -> H::Digest {
+        self.commitment
+    }
+
+    fn domain_len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn open_batch(&self, indexes: &[usize]) -> Result<Self::BatchProof, Self::Error> {
+        if indexes.iter().any(|&index| index >= self.items.len()) {
+            return Err(());
+        }
+        Ok(self.items.clone())
+    }
+
+    fn verify_batch(
+        commitment: &H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::BatchProof,
+    ) -> Result<(), Self::Error> {
+        if Self::hash_chain(proof) != *commitment || indexes.len() != items.len() {
+            return Err(());
+        }
+        for (&index, item) in indexes.iter().zip(items) {
+            if proof.get(index) != Some(item) {
+                return Err(());
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize_batch_proof(proof: &Self::BatchProof) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.write_many(proof);
+        bytes
+    }
+
+    fn deserialize_batch_proof<R: ByteReader>(
+        source: &mut R,
+        _items: Vec<H::Digest>,
+        domain_len: usize,
+    ) -> Result<Self::BatchProof, DeserializationError> {
+        source.read_many(domain_len)
+    }
+}
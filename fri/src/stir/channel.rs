@@ -0,0 +1,31 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::FieldElement;
+
+use crate::ProverChannel;
+
+// STIR PROVER CHANNEL TRAIT
+// ================================================================================================
+
+/// Defines an interface for a channel over which a STIR prover communicates with a verifier.
+///
+/// STIR is executed over the same channel abstraction as FRI: the prover sends commitments to
+/// the folded functions via [commit_fri_layer()](ProverChannel::commit_fri_layer), draws folding
+/// randomness and out-of-domain points via [draw_fri_alpha()](ProverChannel::draw_fri_alpha),
+/// and sends out-of-domain evaluations via
+/// [commit_fri_deep_values()](ProverChannel::commit_fri_deep_values). In addition, unlike FRI
+/// where all queries are drawn at the end of the protocol, STIR draws a fresh set of query
+/// positions in every round.
+pub trait StirProverChannel<E: FieldElement>: ProverChannel<E> {
+    /// Returns `num_positions` pseudo-random positions drawn from a domain of the specified size.
+    ///
+    /// The positions are generated based on the values the prover has written into the channel
+    /// up to this point; the returned positions may contain duplicates.
+    fn draw_stir_query_positions(&mut self, num_positions: usize, domain_size: usize)
+        -> Vec<usize>;
+}
@@ -0,0 +1,45 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains an implementation of the STIR low-degree test.
+//!
+//! STIR (Shift To Improve Rate) is an alternative to FRI which, for the same security level,
+//! requires substantially fewer queries, and thus, produces smaller proofs. Similarly to FRI, in
+//! every round the prover folds the polynomial by a folding factor *k*. However, instead of
+//! evaluating the folded polynomial over a domain *k* times smaller than the current one, the
+//! prover evaluates it over a domain only 2 times smaller; thus, the rate of the code improves
+//! from round to round, and fewer queries are needed in later rounds.
+//!
+//! To tie the folded function committed to in a round to the function committed to at the start
+//! of the round, the verifier queries the latter at a set of rows (the *shift* queries), and folds
+//! each row to get evaluations of the folded polynomial at the corresponding points. Together
+//! with an out-of-domain evaluation sent by the prover, these evaluations are removed from the
+//! committed function via a quotient, and the degree of the quotient is corrected back to the
+//! degree bound of the round.
+//!
+//! STIR is executed over the same [ProverChannel](crate::ProverChannel) abstraction as FRI, with
+//! the addition of drawing query positions in every round (see [StirProverChannel]). A proof is
+//! generated via [StirProver::prove()] and verified via [StirVerifier::verify()].
+//!
+//! # References
+//! * [STIR: Reed-Solomon Proximity Testing with Fewer Queries](https://eprint.iacr.org/2024/390)
+
+mod channel;
+pub use channel::StirProverChannel;
+
+mod options;
+pub use options::StirOptions;
+
+mod proof;
+pub use proof::StirProof;
+
+mod prover;
+pub use prover::StirProver;
+
+mod verifier;
+pub use verifier::StirVerifier;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,121 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::StarkField;
+
+use crate::options::{MAX_FOLDING_FACTOR, MIN_FOLDING_FACTOR};
+
+// STIR OPTIONS
+// ================================================================================================
+
+/// STIR protocol config options for proof generation and verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StirOptions {
+    blowup_factor: usize,
+    folding_factor: usize,
+    remainder_max_degree: usize,
+    num_queries: usize,
+}
+
+impl StirOptions {
+    /// Returns a new [StirOptions] struct instantiated with the specified parameters.
+    ///
+    /// `num_queries` specifies the number of queries made against the initial function; the
+    /// number of queries made in subsequent rounds decreases as the rate of the code improves
+    /// from round to round (see [num_queries_at()](StirOptions::num_queries_at)).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `blowup_factor` is not a power of two or is smaller than 2.
+    /// - `folding_factor` is not 2, 4, 8, 16, 32, or 64.
+    /// - `num_queries` is zero.
+    pub fn new(
+        blowup_factor: usize,
+        folding_factor: usize,
+        remainder_max_degree: usize,
+        num_queries: usize,
+    ) -> Self {
+        assert!(
+            blowup_factor.is_power_of_two() && blowup_factor >= 2,
+            "blowup factor must be a power of two greater than one, but was {blowup_factor}"
+        );
+        assert!(
+            folding_factor.is_power_of_two()
+                && (MIN_FOLDING_FACTOR..=MAX_FOLDING_FACTOR).contains(&folding_factor),
+            "folding factor {folding_factor} is not supported"
+        );
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        StirOptions {
+            blowup_factor,
+            folding_factor,
+            remainder_max_degree,
+            num_queries,
+        }
+    }
+
+    /// Returns the offset by which all evaluation domains of the protocol are shifted.
+    ///
+    /// Currently, the offset is hard-coded to be the primitive element in the field specified by
+    /// type parameter `B`.
+    pub fn domain_offset<B: StarkField>(&self) -> B {
+        B::GENERATOR
+    }
+
+    /// Returns a blowup factor of the initial evaluation domain.
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+
+    /// Returns the factor by which the degree of a polynomial is reduced in every STIR round.
+    ///
+    /// Unlike FRI, the evaluation domain is only halved in every round; thus, for folding factors
+    /// greater than 2, the rate of the code improves from round to round.
+    pub fn folding_factor(&self) -> usize {
+        self.folding_factor
+    }
+
+    /// Returns maximum allowed degree of the final polynomial sent by the prover in the clear.
+    pub fn remainder_max_degree(&self) -> usize {
+        self.remainder_max_degree
+    }
+
+    /// Returns the number of queries made against the initial function.
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    /// Computes and returns the number of STIR rounds required for an initial domain of the
+    /// specified size.
+    ///
+    /// A round is executed for as long as the degree bound of the folded polynomial exceeds
+    /// `remainder_max_degree`; the polynomial resulting from the last fold is sent in the clear.
+    pub fn num_rounds(&self, domain_size: usize) -> usize {
+        let mut degree_bound = domain_size / self.blowup_factor;
+        let mut result = 0;
+        while degree_bound / self.folding_factor > self.remainder_max_degree + 1 {
+            degree_bound /= self.folding_factor;
+            result += 1;
+        }
+        result
+    }
+
+    /// Returns the number of queries made in the specified round for an initial domain of the
+    /// specified size.
+    ///
+    /// The round with index equal to [num_rounds()](StirOptions::num_rounds) refers to the final
+    /// check against the polynomial sent in the clear. The number of queries is scaled so that
+    /// every round provides the same security as the initial round given the improved rate of the
+    /// code in that round, and is capped to be smaller than the number of rows in the round's
+    /// evaluation domain.
+    pub fn num_queries_at(&self, round: usize, domain_size: usize) -> usize {
+        let log_blowup = self.blowup_factor.ilog2() as usize;
+        let log_folding = self.folding_factor.ilog2() as usize;
+        let log_inv_rate = log_blowup + round * (log_folding - 1);
+        let num_queries = (self.num_queries * log_blowup).div_ceil(log_inv_rate);
+
+        let num_rows = (domain_size >> round) / self.folding_factor;
+        num_queries.clamp(1, num_rows.saturating_sub(1).max(1))
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{ElementHasher, VectorCommitment};
+use math::FieldElement;
+use utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+use super::StirOptions;
+use crate::proof::FriProofLayer;
+
+// STIR PROOF
+// ================================================================================================
+
+/// A proof generated by a [StirProver](super::StirProver).
+///
+/// A STIR proof consists of one query layer per round of the protocol plus a query layer for the
+/// final check, an out-of-domain evaluation for every round, and the final polynomial given by
+/// its list of coefficients. Each query layer contains the rows of the function committed to at
+/// the start of the round which were opened at the positions queried by the verifier, together
+/// with a batch opening proof for these rows.
+///
+/// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
+/// they can be returned to the user. To do this, [parse_layers()](StirProof::parse_layers()),
+/// [parse_ood_values()](StirProof::parse_ood_values()), and
+/// [parse_final_poly()](StirProof::parse_final_poly()) methods can be used.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StirProof {
+    layers: Vec<FriProofLayer>,
+    ood_values: Vec<u8>,
+    final_poly: Vec<u8>,
+}
+
+impl StirProof {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new STIR proof from the provided query layers, out-of-domain evaluations, and
+    /// coefficients of the final polynomial.
+    ///
+    /// # Panics
+    /// Panics if `final_poly` is empty.
+    pub(crate) fn new<E: FieldElement>(
+        layers: Vec<FriProofLayer>,
+        ood_values: Vec<E>,
+        final_poly: Vec<E>,
+    ) -> Self {
+        assert!(!final_poly.is_empty(), "final polynomial cannot be empty");
+
+        let mut ood_value_bytes = Vec::with_capacity(E::ELEMENT_BYTES * ood_values.len());
+        ood_value_bytes.write_many(&ood_values);
+
+        let mut final_poly_bytes = Vec::with_capacity(E::ELEMENT_BYTES * final_poly.len());
+        final_poly_bytes.write_many(&final_poly);
+
+        StirProof {
+            layers,
+            ood_values: ood_value_bytes,
+            final_poly: final_poly_bytes,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of query layers in this proof.
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns the size of this proof in bytes.
+    pub fn size(&self) -> usize {
+        // +1 for number of layers, +2 for out-of-domain values length, +2 for final polynomial
+        // length
+        self.layers
+            .iter()
+            .fold(self.ood_values.len() + self.final_poly.len() + 5, |acc, layer| {
+                acc + layer.size()
+            })
+    }
+
+    // PARSING
+    // --------------------------------------------------------------------------------------------
+
+    /// Decomposes this proof into vectors of query values for each layer and corresponding batch
+    /// opening proofs of the vector commitment scheme `V`.
+    ///
+    /// # Panics
+    /// Panics if `domain_size` is not a power of two.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This proof is not consistent with the specified `domain_size` and `options`.
+    /// * Any of the layers could not be parsed successfully.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_layers<H, E, V>(
+        self,
+        domain_size: usize,
+        options: &StirOptions,
+    ) -> Result<(Vec<Vec<E>>, Vec<V::BatchProof>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
+
+        let num_layers = options.num_rounds(domain_size) + 1;
+        if self.layers.len() != num_layers {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {num_layers} STIR layers, but {} were provided",
+                self.layers.len()
+            )));
+        }
+
+        let folding_factor = options.folding_factor();
+        let mut layer_proofs = Vec::new();
+        let mut layer_queries = Vec::new();
+
+        // parse all layers; the evaluation domain is halved in every round
+        for (i, layer) in self.layers.into_iter().enumerate() {
+            let num_rows = (domain_size >> i) / folding_factor;
+            let (qv, _, mp) =
+                layer.parse::<H, E, V>(num_rows, folding_factor, false).map_err(|err| {
+                    DeserializationError::InvalidValue(format!(
+                        "failed to parse STIR layer {i}: {err}"
+                    ))
+                })?;
+            layer_proofs.push(mp);
+            layer_queries.push(qv);
+        }
+
+        Ok((layer_queries, layer_proofs))
+    }
+
+    /// Returns a vector of out-of-domain evaluations (one per round) parsed from this proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of out-of-domain value bytes is not a multiple of the size of `E`.
+    /// * Any of the values could not be parsed correctly.
+    pub fn parse_ood_values<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        parse_elements(&self.ood_values, "out-of-domain values")
+    }
+
+    /// Returns coefficients of the final polynomial parsed from this proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of final polynomial bytes is not a multiple of the size of `E`.
+    /// * Any of the coefficients could not be parsed correctly.
+    pub fn parse_final_poly<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        parse_elements(&self.final_poly, "final polynomial")
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for StirProof {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // write layers
+        target.write_u8(self.layers.len() as u8);
+        for layer in self.layers.iter() {
+            layer.write_into(target);
+        }
+
+        // write out-of-domain values
+        target.write_u16(self.ood_values.len() as u16);
+        target.write_bytes(&self.ood_values);
+
+        // write final polynomial
+        target.write_u16(self.final_poly.len() as u16);
+        target.write_bytes(&self.final_poly);
+    }
+}
+
+impl Deserializable for StirProof {
+    /// Reads a STIR proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the source.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // read layers
+        let num_layers = source.read_u8()? as usize;
+        let layers = source.read_many(num_layers)?;
+
+        // read out-of-domain values
+        let num_ood_value_bytes = source.read_u16()? as usize;
+        let ood_values = source.read_vec(num_ood_value_bytes)?;
+
+        // read final polynomial
+        let num_final_poly_bytes = source.read_u16()? as usize;
+        let final_poly = source.read_vec(num_final_poly_bytes)?;
+
+        Ok(StirProof { layers, ood_values, final_poly })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses the specified bytes into a vector of field elements.
+fn parse_elements<E: FieldElement>(
+    bytes: &[u8],
+    name: &str,
+) -> Result<Vec<E>, DeserializationError> {
+    if bytes.len() % E::ELEMENT_BYTES != 0 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of STIR {name} bytes must be a multiple of {}, but was {}",
+            E::ELEMENT_BYTES,
+            bytes.len()
+        )));
+    }
+    let num_elements = bytes.len() / E::ELEMENT_BYTES;
+    let mut reader = SliceReader::new(bytes);
+    reader.read_many(num_elements).map_err(|err| {
+        DeserializationError::InvalidValue(format!("failed to parse STIR {name}: {err}"))
+    })
+}
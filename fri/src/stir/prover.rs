@@ -0,0 +1,320 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crypto::{ElementHasher, Hasher, MerkleTree, VectorCommitment};
+use math::{fft, get_power_series, polynom, FieldElement, StarkField};
+use utils::transpose_slice;
+
+use super::{StirOptions, StirProof, StirProverChannel};
+use crate::{proof::FriProofLayer, utils::hash_values, DEFAULT_CONCURRENCY_THRESHOLD};
+
+// STIR PROVER
+// ================================================================================================
+
+/// Implements the prover component of the STIR protocol.
+///
+/// Given evaluations of a function *f* over an evaluation domain, the prover generates a proof
+/// that *f* is a polynomial of degree less than `domain_size / blowup_factor`. The proof is
+/// generated in a single call to [prove()](StirProver::prove()), during which the prover writes
+/// commitments to the initial function, to the function folded in every round, and to the final
+/// polynomial into the specified channel. These commitments must be sent to the verifier
+/// together with the returned [StirProof].
+///
+/// The prover is parametrized by the following types:
+///
+/// * `B` specifies the base field of the STARK protocol.
+/// * `E` specifies the field in which STIR is executed. Can be the same as `B` field, but it can
+///   also be an extension of `B` field in cases when the base field is too small to provide
+///   desired security level for the protocol.
+/// * `C` specifies the type used to simulate prover-verifier interaction.
+/// * `H` specifies the hash function used to build commitments to the folded functions.
+/// * `V` specifies the vector commitment scheme used to commit to the folded functions.
+pub struct StirProver<B, E, C, H, V = MerkleTree<H>>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: StirProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+{
+    options: StirOptions,
+    _field: PhantomData<E>,
+    _channel: PhantomData<C>,
+    _vector_commitment: PhantomData<V>,
+}
+
+/// Evaluations of a function committed to at the start of a STIR round.
+struct StirLayer<E: FieldElement, H: Hasher, V: VectorCommitment<H>, const N: usize> {
+    commitment: V,
+    evaluations: Vec<[E; N]>,
+    _hasher: PhantomData<H>,
+}
+
+// PROVER IMPLEMENTATION
+// ================================================================================================
+
+impl<B, E, C, H> StirProver<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: StirProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new STIR prover instantiated with the provided `options`.
+    pub fn new(options: StirOptions) -> Self {
+        StirProver {
+            options,
+            _field: PhantomData,
+            _channel: PhantomData,
+            _vector_commitment: PhantomData,
+        }
+    }
+}
+
+impl<B, E, C, H, V> StirProver<B, E, C, H, V>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: StirProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+{
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns folding factor for this prover.
+    pub fn folding_factor(&self) -> usize {
+        self.options.folding_factor()
+    }
+
+    /// Returns offset of the domain over which STIR protocol is executed by this prover.
+    pub fn domain_offset(&self) -> B {
+        self.options.domain_offset()
+    }
+
+    // PROOF GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the STIR protocol for the specified `evaluations` and returns the resulting
+    /// proof.
+    ///
+    /// The evaluations must be in natural order over the evaluation domain shifted by the domain
+    /// offset. Commitments made by the prover are written into the specified `channel`; there are
+    /// `num_rounds + 2` of them: the commitment to the initial function, commitments to the
+    /// functions folded in every round, and the hash of the final polynomial.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of `evaluations` is not a power of two.
+    /// * The degree bound implied by the number of `evaluations` and the blowup factor is smaller
+    ///   than the folding factor.
+    /// * The evaluation domain of the last round contains fewer than two rows.
+    pub fn prove(&self, channel: &mut C, evaluations: Vec<E>) -> StirProof {
+        match self.folding_factor() {
+            2 => self.prove_with::<2>(channel, evaluations),
+            4 => self.prove_with::<4>(channel, evaluations),
+            8 => self.prove_with::<8>(channel, evaluations),
+            16 => self.prove_with::<16>(channel, evaluations),
+            32 => self.prove_with::<32>(channel, evaluations),
+            64 => self.prove_with::<64>(channel, evaluations),
+            _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
+        }
+    }
+
+    fn prove_with<const N: usize>(&self, channel: &mut C, evaluations: Vec<E>) -> StirProof {
+        let domain_size = evaluations.len();
+        assert!(
+            domain_size.is_power_of_two(),
+            "number of evaluations must be a power of two, but was {domain_size}"
+        );
+        let degree_bound = domain_size / self.options.blowup_factor();
+        assert!(
+            degree_bound >= N,
+            "degree bound {degree_bound} must be at least the folding factor {N}"
+        );
+        let num_rounds = self.options.num_rounds(domain_size);
+        assert!(
+            (domain_size >> num_rounds) / N >= 2,
+            "evaluation domain of the last STIR round must contain at least two rows"
+        );
+        let domain_offset = self.domain_offset();
+
+        // interpolate the evaluations into a polynomial; coefficients beyond the degree bound
+        // are discarded since they are zero for a function of the claimed degree
+        let mut coefficients = evaluations.clone();
+        let inv_twiddles = fft::get_inv_twiddles::<B>(domain_size);
+        fft::interpolate_poly_with_offset(&mut coefficients, &inv_twiddles, domain_offset);
+        coefficients.truncate(degree_bound);
+
+        // commit to the initial function
+        let mut layer = build_layer::<E, H, V, N>(evaluations);
+        channel.commit_fri_layer(layer.commitment.commitment());
+
+        let mut layers = Vec::with_capacity(num_rounds + 1);
+        let mut ood_values = Vec::with_capacity(num_rounds);
+        for round in 0..num_rounds {
+            let round_domain_size = domain_size >> round;
+
+            // fold the polynomial by the folding factor and commit to its evaluations over a
+            // domain which is half the size of the current domain
+            let alpha = channel.draw_fri_alpha();
+            let folded = fold_coefficients::<E, N>(&coefficients, alpha);
+            let next_domain_size = round_domain_size / 2;
+            let twiddles = fft::get_twiddles::<B>(folded.len());
+            let next_evaluations = fft::evaluate_poly_with_offset(
+                &folded,
+                &twiddles,
+                domain_offset,
+                next_domain_size / folded.len(),
+            );
+            let next_layer = build_layer::<E, H, V, N>(next_evaluations);
+            channel.commit_fri_layer(next_layer.commitment.commitment());
+
+            // send evaluation of the folded polynomial at an out-of-domain point
+            let z = channel.draw_fri_alpha();
+            let ood_value = polynom::eval(&folded, z);
+            channel.commit_fri_deep_values(&[ood_value]);
+            ood_values.push(ood_value);
+
+            // open the function committed to at the start of the round at the queried rows; the
+            // verifier folds these rows to get evaluations of the folded polynomial at the
+            // shift points
+            let num_rows = round_domain_size / N;
+            let positions =
+                draw_positions(channel, self.options.num_queries_at(round, domain_size), num_rows);
+            let comb_alpha = channel.draw_fri_alpha();
+            layers.push(query_layer::<E, H, V, N>(&layer, &positions));
+
+            // compute the quotient of the folded polynomial by the vanishing polynomial of the
+            // out-of-domain point and the shift points, and correct its degree to match the
+            // degree bound of the next round
+            let next_degree_bound = folded.len();
+            let domain_generator = B::get_root_of_unity(round_domain_size.ilog2());
+            let mut points = Vec::with_capacity(positions.len() + 1);
+            points.push(z);
+            points.extend(positions.iter().map(|&position| {
+                let x = domain_offset * domain_generator.exp_vartime((position as u64).into());
+                E::from(x.exp_vartime((N as u64).into()))
+            }));
+            let quotient = divide_by_roots(folded, &points);
+            coefficients = correct_degree(quotient, comb_alpha, &points, next_degree_bound);
+
+            layer = next_layer;
+        }
+
+        // fold the polynomial one last time and send the result in the clear
+        let alpha = channel.draw_fri_alpha();
+        let final_poly = fold_coefficients::<E, N>(&coefficients, alpha);
+        channel.commit_fri_layer(H::hash_elements(&final_poly));
+
+        let num_rows = (domain_size >> num_rounds) / N;
+        let positions =
+            draw_positions(channel, self.options.num_queries_at(num_rounds, domain_size), num_rows);
+        layers.push(query_layer::<E, H, V, N>(&layer, &positions));
+
+        StirProof::new(layers, ood_values, final_poly)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Commits to the specified evaluations by hashing each row of `N` transposed evaluations into
+/// a single leaf of the vector commitment.
+fn build_layer<E, H, V, const N: usize>(evaluations: Vec<E>) -> StirLayer<E, H, V, N>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    let transposed_evaluations = transpose_slice::<_, N>(&evaluations);
+    let min_batch_size = (DEFAULT_CONCURRENCY_THRESHOLD / N).max(1);
+    let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations, min_batch_size);
+    let commitment =
+        V::commit(hashed_evaluations).expect("failed to construct STIR layer commitment");
+    StirLayer {
+        commitment,
+        evaluations: transposed_evaluations,
+        _hasher: PhantomData,
+    }
+}
+
+/// Opens rows of the specified layer at the specified positions.
+fn query_layer<E, H, V, const N: usize>(
+    layer: &StirLayer<E, H, V, N>,
+    positions: &[usize],
+) -> FriProofLayer
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    let proof = layer
+        .commitment
+        .open_batch(positions)
+        .expect("failed to generate a batch opening proof for STIR layer queries");
+    let queried_values = positions.iter().map(|&pos| layer.evaluations[pos]).collect();
+    FriProofLayer::new::<H, E, V, N>(queried_values, Vec::new(), proof)
+}
+
+/// Draws the specified number of positions from the channel, and returns them sorted and with
+/// duplicates removed.
+fn draw_positions<E, C>(channel: &mut C, num_positions: usize, domain_size: usize) -> Vec<usize>
+where
+    E: FieldElement,
+    C: StirProverChannel<E>,
+{
+    let mut positions = channel.draw_stir_query_positions(num_positions, domain_size);
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+/// Folds a polynomial given by its coefficients by a factor of `N` using the specified `alpha`.
+///
+/// Writing the polynomial as f(x) = sum_{t < N} x^t * f_t(x^N), the result is the polynomial
+/// sum_{t < N} alpha^t * f_t(x).
+fn fold_coefficients<E: FieldElement, const N: usize>(coefficients: &[E], alpha: E) -> Vec<E> {
+    coefficients.chunks(N).map(|chunk| polynom::eval(chunk, alpha)).collect()
+}
+
+/// Divides the specified polynomial by (x - a) for every a in `roots` and returns the quotient;
+/// remainders of the divisions are discarded.
+fn divide_by_roots<E: FieldElement>(mut poly: Vec<E>, roots: &[E]) -> Vec<E> {
+    for &root in roots {
+        if poly.len() <= 1 {
+            return Vec::new();
+        }
+        let mut carry = E::ZERO;
+        for coeff in poly.iter_mut().rev() {
+            *coeff += root * carry;
+            core::mem::swap(coeff, &mut carry);
+        }
+        poly.pop();
+    }
+    poly
+}
+
+/// Multiplies the quotient polynomial by sum_{l <= |points|} (comb_alpha * x)^l; this raises the
+/// degree bound of the quotient back to the degree bound of the folded polynomial.
+///
+/// If the quotient is zero (i.e., there were at least as many points as coefficients in the
+/// folded polynomial), a zero polynomial with `degree_bound` coefficients is returned.
+fn correct_degree<E: FieldElement>(
+    quotient: Vec<E>,
+    comb_alpha: E,
+    points: &[E],
+    degree_bound: usize,
+) -> Vec<E> {
+    if quotient.is_empty() {
+        return vec![E::ZERO; degree_bound];
+    }
+    polynom::mul(&quotient, &get_power_series(comb_alpha, points.len() + 1))
+}
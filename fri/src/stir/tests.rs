@@ -0,0 +1,138 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, RandomCoin};
+use math::{fft, fields::f128::BaseElement, FieldElement};
+use utils::{Deserializable, Serializable, SliceReader};
+
+use super::{StirOptions, StirProof, StirProver, StirVerifier};
+use crate::{DefaultProverChannel, VerifierError};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// PROVE/VERIFY TEST
+// ================================================================================================
+
+#[test]
+fn stir_folding_2() {
+    let result = stir_prove_verify(12, 3, 1, 7, 32);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn stir_folding_4() {
+    let result = stir_prove_verify(12, 3, 2, 7, 32);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn stir_folding_16() {
+    let result = stir_prove_verify(12, 2, 4, 3, 32);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn stir_high_degree_polynomial() {
+    // the evaluations are of a polynomial of degree 2^11 - 1, but the verifier expects a
+    // polynomial of degree 2^10 - 1
+    let options = StirOptions::new(8, 4, 7, 32);
+    let evaluations = build_evaluations(1 << 11, 4);
+    let (proof, commitments) = build_proof(&options, evaluations);
+    let result = verify_proof(&options, proof, &commitments, (1 << 10) - 1);
+    assert!(result.is_err());
+}
+
+#[test]
+fn stir_commitments_mismatch() {
+    let options = StirOptions::new(8, 4, 7, 32);
+    let evaluations = build_evaluations(1 << 10, 8);
+    let (proof, mut commitments) = build_proof(&options, evaluations);
+
+    commitments.pop();
+    let result = verify_proof(&options, proof.clone(), &commitments, (1 << 10) - 1);
+    assert_eq!(
+        Err(VerifierError::NumLayerCommitmentsMismatch(
+            commitments.len() + 1,
+            commitments.len()
+        )),
+        result
+    );
+
+    let last_commitment = commitments[commitments.len() - 1];
+    commitments.insert(1, last_commitment);
+    let result = verify_proof(&options, proof, &commitments, (1 << 10) - 1);
+    assert!(result.is_err());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn stir_prove_verify(
+    trace_length_e: usize,
+    lde_blowup_e: usize,
+    folding_factor_e: usize,
+    max_remainder_degree: usize,
+    num_queries: usize,
+) -> Result<(), VerifierError> {
+    let trace_length = 1 << trace_length_e;
+    let lde_blowup = 1 << lde_blowup_e;
+    let options =
+        StirOptions::new(lde_blowup, 1 << folding_factor_e, max_remainder_degree, num_queries);
+
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let (proof, commitments) = build_proof(&options, evaluations);
+    assert_eq!(options.num_rounds(trace_length * lde_blowup) + 1, proof.num_layers());
+
+    verify_proof(&options, proof, &commitments, trace_length - 1)
+}
+
+fn build_evaluations(trace_length: usize, lde_blowup: usize) -> Vec<BaseElement> {
+    let mut p = (0..trace_length as u128).map(BaseElement::new).collect::<Vec<_>>();
+    let domain_size = trace_length * lde_blowup;
+    p.resize(domain_size, BaseElement::ZERO);
+
+    let twiddles = fft::get_twiddles::<BaseElement>(domain_size);
+
+    fft::evaluate_poly(&mut p, &twiddles);
+    p
+}
+
+fn build_proof(
+    options: &StirOptions,
+    evaluations: Vec<BaseElement>,
+) -> (StirProof, Vec<<Blake3 as Hasher>::Digest>) {
+    let mut channel = DefaultProverChannel::<BaseElement, Blake3, DefaultRandomCoin<Blake3>>::new(
+        evaluations.len(),
+        options.num_queries(),
+    );
+    let prover = StirProver::new(options.clone());
+    let proof = prover.prove(&mut channel, evaluations);
+    (proof, channel.layer_commitments().to_vec())
+}
+
+fn verify_proof(
+    options: &StirOptions,
+    proof: StirProof,
+    commitments: &[<Blake3 as Hasher>::Digest],
+    max_degree: usize,
+) -> Result<(), VerifierError> {
+    // test proof serialization / deserialization
+    let mut proof_bytes = Vec::new();
+    proof.write_into(&mut proof_bytes);
+    assert_eq!(proof.size(), proof_bytes.len());
+
+    let mut reader = SliceReader::new(&proof_bytes);
+    let proof = StirProof::read_from(&mut reader).unwrap();
+
+    // verify the proof
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = StirVerifier::<BaseElement, Blake3, DefaultRandomCoin<Blake3>>::new(
+        options.clone(),
+        max_degree,
+    );
+    verifier.verify(proof, commitments, &mut coin)
+}
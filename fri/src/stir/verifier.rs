@@ -0,0 +1,348 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+use core::marker::PhantomData;
+
+use crypto::{ElementHasher, MerkleTree, Transcript, TranscriptLabel, VectorCommitment};
+use math::{polynom, FieldElement, StarkField};
+use utils::group_slice_elements;
+
+use super::{StirOptions, StirProof};
+use crate::VerifierError;
+
+// STIR VERIFIER
+// ================================================================================================
+
+/// Implements the verifier component of the STIR protocol.
+///
+/// Given a [StirProof] and commitments made by a [StirProver](super::StirProver), a STIR
+/// verifier determines whether the committed function is a polynomial of degree less than
+/// (`max_poly_degree` + 1) rounded up to the next power of two.
+///
+/// The verifier is parametrized by the following types:
+///
+/// * `E` specifies the field in which STIR is executed.
+/// * `H` specifies the hash function used by the prover to commit to the folded functions.
+/// * `R` specifies the public coin used to replay the prover's transcript.
+/// * `V` specifies the vector commitment scheme used by the prover.
+///
+/// In every round, the verifier opens the function committed to at the start of the round at a
+/// set of rows, and folds each row to get evaluations of the folded polynomial at the shift
+/// points. Together with the out-of-domain evaluation sent by the prover, these evaluations
+/// define the function committed to in the next round; the verifier computes values of this
+/// function at the next set of queried rows from the opened values of the folded polynomial.
+/// Finally, the verifier checks the folded values at the last set of queried rows against the
+/// final polynomial sent in the clear.
+pub struct StirVerifier<E, H, R, V = MerkleTree<H>>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    options: StirOptions,
+    max_poly_degree: usize,
+    domain_size: usize,
+    _field: PhantomData<E>,
+    _public_coin: PhantomData<R>,
+    _vector_commitment: PhantomData<V>,
+}
+
+/// Describes the function committed to in a STIR round in terms of the folded polynomial
+/// committed to in the same round.
+struct RoundState<E: FieldElement> {
+    points: Vec<E>,
+    answers_poly: Vec<E>,
+    comb_alpha: E,
+}
+
+impl<E, H, R> StirVerifier<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new STIR verifier for polynomials of degree at most `max_poly_degree`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The degree bound implied by `max_poly_degree` is smaller than the folding factor.
+    /// * The evaluation domain of the last round contains fewer than two rows.
+    pub fn new(options: StirOptions, max_poly_degree: usize) -> Self {
+        let degree_bound = (max_poly_degree + 1).next_power_of_two();
+        assert!(
+            degree_bound >= options.folding_factor(),
+            "degree bound {degree_bound} must be at least the folding factor {}",
+            options.folding_factor()
+        );
+        let domain_size = degree_bound * options.blowup_factor();
+        let num_rounds = options.num_rounds(domain_size);
+        assert!(
+            (domain_size >> num_rounds) / options.folding_factor() >= 2,
+            "evaluation domain of the last STIR round must contain at least two rows"
+        );
+
+        StirVerifier {
+            options,
+            max_poly_degree,
+            domain_size,
+            _field: PhantomData,
+            _public_coin: PhantomData,
+            _vector_commitment: PhantomData,
+        }
+    }
+}
+
+impl<E, H, R, V> StirVerifier<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns maximum degree of a polynomial accepted by this verifier.
+    pub fn max_poly_degree(&self) -> usize {
+        self.max_poly_degree
+    }
+
+    /// Returns size of the domain over which the initial function is evaluated.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns protocol configuration options for this verifier.
+    pub fn options(&self) -> &StirOptions {
+        &self.options
+    }
+
+    // VERIFICATION PROCEDURE
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the STIR verification protocol against the specified `proof`.
+    ///
+    /// `commitments` must contain all commitments written by the prover into its channel in the
+    /// order in which they were written, and `public_coin` must be in the same state as the
+    /// public coin of the prover's channel was when the prover started the protocol.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of `commitments` is not consistent with the number of STIR rounds.
+    /// * The proof could not be parsed.
+    /// * Any of the opened rows does not match the commitment of its round.
+    /// * Values of the folded polynomials are inconsistent across rounds.
+    /// * The final polynomial does not match its commitment, exceeds the expected degree, or is
+    ///   inconsistent with the values folded in the last round.
+    pub fn verify(
+        &self,
+        proof: StirProof,
+        commitments: &[H::Digest],
+        public_coin: &mut R,
+    ) -> Result<(), VerifierError> {
+        match self.options.folding_factor() {
+            2 => self.verify_generic::<2>(proof, commitments, public_coin),
+            4 => self.verify_generic::<4>(proof, commitments, public_coin),
+            8 => self.verify_generic::<8>(proof, commitments, public_coin),
+            16 => self.verify_generic::<16>(proof, commitments, public_coin),
+            32 => self.verify_generic::<32>(proof, commitments, public_coin),
+            64 => self.verify_generic::<64>(proof, commitments, public_coin),
+            folding_factor => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+        }
+    }
+
+    fn verify_generic<const N: usize>(
+        &self,
+        proof: StirProof,
+        commitments: &[H::Digest],
+        public_coin: &mut R,
+    ) -> Result<(), VerifierError> {
+        let num_rounds = self.options.num_rounds(self.domain_size);
+        if commitments.len() != num_rounds + 2 {
+            return Err(VerifierError::NumLayerCommitmentsMismatch(
+                num_rounds + 2,
+                commitments.len(),
+            ));
+        }
+
+        // parse the proof
+        let ood_values = proof
+            .parse_ood_values::<E>()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        if ood_values.len() != num_rounds {
+            return Err(VerifierError::NumDeepValuesMismatch(num_rounds, ood_values.len()));
+        }
+        let final_poly = proof
+            .parse_final_poly::<E>()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let degree_bound = self.domain_size / self.options.blowup_factor();
+        let max_final_poly_len = (degree_bound / N.pow(num_rounds as u32)).div_ceil(N);
+        if final_poly.is_empty() || final_poly.len() > max_final_poly_len {
+            return Err(VerifierError::RemainderDegreeMismatch(max_final_poly_len - 1));
+        }
+        let (layer_queries, layer_proofs) = proof
+            .parse_layers::<H, E, V>(self.domain_size, &self.options)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+        let domain_offset = self.options.domain_offset::<E::BaseField>();
+        let mut prev_round: Option<RoundState<E>> = None;
+
+        public_coin.absorb(TranscriptLabel::FriLayer, commitments[0]);
+        for (round, (queries, layer_proof)) in layer_queries.iter().zip(layer_proofs).enumerate() {
+            let is_final = round == num_rounds;
+            let round_domain_size = self.domain_size >> round;
+            let num_rows = round_domain_size / N;
+
+            // replay the prover's transcript for this round
+            let alpha: E = public_coin
+                .squeeze(TranscriptLabel::FriLayer)
+                .map_err(VerifierError::RandomCoinError)?;
+            public_coin.absorb(TranscriptLabel::FriLayer, commitments[round + 1]);
+            let ood_point = if is_final {
+                if H::hash_elements(&final_poly) != commitments[round + 1] {
+                    return Err(VerifierError::RemainderCommitmentMismatch);
+                }
+                None
+            } else {
+                let z: E = public_coin
+                    .squeeze(TranscriptLabel::FriLayer)
+                    .map_err(VerifierError::RandomCoinError)?;
+                public_coin
+                    .absorb(TranscriptLabel::FriDeepValues, H::hash_elements(&[ood_values[round]]));
+                Some(z)
+            };
+            let num_queries = self.options.num_queries_at(round, self.domain_size);
+            let mut positions = public_coin
+                .squeeze_integers(TranscriptLabel::QuerySeed, num_queries, num_rows, 0)
+                .map_err(VerifierError::RandomCoinError)?;
+            positions.sort_unstable();
+            positions.dedup();
+            let comb_alpha: Option<E> = if is_final {
+                None
+            } else {
+                Some(
+                    public_coin
+                        .squeeze(TranscriptLabel::FriLayer)
+                        .map_err(VerifierError::RandomCoinError)?,
+                )
+            };
+
+            // authenticate the opened rows against the commitment made at the start of the round
+            let rows = group_slice_elements::<E, N>(queries);
+            if rows.len() != positions.len() {
+                return Err(VerifierError::NumPositionEvaluationMismatch(
+                    positions.len(),
+                    rows.len(),
+                ));
+            }
+            let hashed_rows =
+                rows.iter().map(|row| H::hash_elements(row.as_slice())).collect::<Vec<_>>();
+            V::verify_batch(&commitments[round], &positions, &hashed_rows, &layer_proof)
+                .map_err(|_| VerifierError::LayerCommitmentMismatch(round))?;
+
+            // build a set of x coordinates for each row
+            let domain_generator = E::BaseField::get_root_of_unity(round_domain_size.ilog2());
+            let folding_roots = (0..N)
+                .map(|i| domain_generator.exp_vartime(((num_rows * i) as u64).into()))
+                .collect::<Vec<_>>();
+            let row_offsets = positions
+                .iter()
+                .map(|&i| domain_generator.exp_vartime((i as u64).into()) * domain_offset)
+                .collect::<Vec<_>>();
+            let xs = row_offsets
+                .iter()
+                .map(|&xe| core::array::from_fn(|j| E::from(xe * folding_roots[j])))
+                .collect::<Vec<[E; N]>>();
+
+            // compute values of the function committed to at the start of the round; for rounds
+            // after the first one, the opened values are values of the folded polynomial from
+            // which the function is derived
+            let ys = match &prev_round {
+                None => rows.to_vec(),
+                Some(state) => {
+                    let mut ys = Vec::with_capacity(rows.len());
+                    for ((row, x_row), &position) in
+                        rows.iter().zip(xs.iter()).zip(positions.iter())
+                    {
+                        let mut y_row = [E::ZERO; N];
+                        for ((y, &value), &x) in y_row.iter_mut().zip(row).zip(x_row) {
+                            *y = state
+                                .evaluate(x, value)
+                                .ok_or(VerifierError::InvalidLayerFolding(round - 1, position))?;
+                        }
+                        ys.push(y_row);
+                    }
+                    ys
+                },
+            };
+
+            // fold the rows to get values of the folded polynomial at the shift points
+            let row_polys = polynom::interpolate_batch(&xs, &ys);
+            let folded_values =
+                row_polys.iter().map(|p| polynom::eval(p, alpha)).collect::<Vec<_>>();
+            let shift_points = row_offsets
+                .iter()
+                .map(|&xe| E::from(xe.exp_vartime((N as u64).into())))
+                .collect::<Vec<_>>();
+
+            match (ood_point, comb_alpha) {
+                (Some(z), Some(comb_alpha)) => {
+                    if let Some(idx) = shift_points.iter().position(|&point| point == z) {
+                        return Err(VerifierError::InvalidLayerFolding(round, positions[idx]));
+                    }
+                    let mut points = vec![z];
+                    points.extend_from_slice(&shift_points);
+                    let mut answers = vec![ood_values[round]];
+                    answers.extend_from_slice(&folded_values);
+                    prev_round = Some(RoundState::new(points, answers, comb_alpha));
+                },
+                _ => {
+                    // check the folded values against the final polynomial
+                    for ((&y, &value), &position) in
+                        shift_points.iter().zip(folded_values.iter()).zip(positions.iter())
+                    {
+                        if polynom::eval(&final_poly, y) != value {
+                            return Err(VerifierError::InvalidRemainderFolding(position));
+                        }
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ROUND STATE
+// ================================================================================================
+
+impl<E: FieldElement> RoundState<E> {
+    /// Returns a new round state for the specified quotient points and answers at these points.
+    fn new(points: Vec<E>, answers: Vec<E>, comb_alpha: E) -> Self {
+        let answers_poly = polynom::interpolate(&points, &answers, false);
+        RoundState { points, answers_poly, comb_alpha }
+    }
+
+    /// Computes value at `x` of the degree-corrected quotient function given the value of the
+    /// folded polynomial at `x`.
+    ///
+    /// Returns None if `x` is one of the quotient points.
+    fn evaluate(&self, x: E, folded_value: E) -> Option<E> {
+        let vanishing_value = self.points.iter().fold(E::ONE, |acc, &p| acc * (x - p));
+        if vanishing_value == E::ZERO {
+            return None;
+        }
+        let quotient = (folded_value - polynom::eval(&self.answers_poly, x)) / vanishing_value;
+
+        let comb_x = self.comb_alpha * x;
+        let (correction, _) = (0..=self.points.len())
+            .fold((E::ZERO, E::ONE), |(acc, power), _| (acc + power, power * comb_x));
+        Some(quotient * correction)
+    }
+}
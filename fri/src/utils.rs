@@ -5,7 +5,7 @@
 
 use alloc::vec::Vec;
 
-use crypto::ElementHasher;
+use crypto::{ElementHasher, Hasher};
 use math::FieldElement;
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -38,15 +38,56 @@ pub fn map_positions_to_indexes(
     result
 }
 
+/// Returns coefficients used to combine `num_codewords` codewords into a single codeword for
+/// batched FRI.
+///
+/// The coefficients are computed as successive powers of the random value `gamma`, starting with
+/// gamma^0 = 1.
+pub fn get_batching_coefficients<E: FieldElement>(gamma: E, num_codewords: usize) -> Vec<E> {
+    let mut result = Vec::with_capacity(num_codewords);
+    let mut coefficient = E::ONE;
+    for _ in 0..num_codewords {
+        result.push(coefficient);
+        coefficient *= gamma;
+    }
+    result
+}
+
 /// Hashes each of the arrays in the provided slice and returns a vector of resulting hashes.
-pub fn hash_values<H, E, const N: usize>(values: &[[E; N]]) -> Vec<H::Digest>
+///
+/// When `concurrent` feature is enabled, hashing is distributed across multiple threads such that
+/// each thread hashes at least `min_batch_size` arrays. Thus, slices with fewer than
+/// `min_batch_size` arrays are hashed in a single thread.
+#[cfg_attr(not(feature = "concurrent"), allow(unused_variables))]
+pub fn hash_values<H, E, const N: usize>(values: &[[E; N]], min_batch_size: usize) -> Vec<H::Digest>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
 {
     let mut result: Vec<H::Digest> = unsafe { uninit_vector(values.len()) };
-    iter_mut!(result, 1024).zip(values).for_each(|(r, v)| {
+    iter_mut!(result, min_batch_size).zip(values).for_each(|(r, v)| {
         *r = H::hash_elements(v);
     });
     result
 }
+
+/// Combines hashes of rows committed to in a salted FRI layer with the corresponding salts.
+///
+/// Each hash h_i is replaced with the result of merging h_i with salt s_i. If `salts` is empty,
+/// the hashes are left unchanged.
+///
+/// # Panics
+/// Panics if `salts` is not empty and the number of salts is not equal to the number of hashes.
+pub fn apply_salts<H: Hasher>(hashes: &mut [H::Digest], salts: &[H::Digest]) {
+    if salts.is_empty() {
+        return;
+    }
+    assert_eq!(
+        hashes.len(),
+        salts.len(),
+        "number of salts must be equal to the number of hashes"
+    );
+    for (hash, &salt) in hashes.iter_mut().zip(salts) {
+        *hash = H::merge(&[*hash, salt]);
+    }
+}
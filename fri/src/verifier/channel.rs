@@ -5,11 +5,11 @@
 
 use alloc::vec::Vec;
 
-use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
+use crypto::{ElementHasher, Hasher, MerkleTree, VectorCommitment};
 use math::FieldElement;
 use utils::{group_slice_elements, DeserializationError};
 
-use crate::{FriProof, VerifierError};
+use crate::{utils::apply_salts, FriOptions, FriProof, VerifierError};
 
 // VERIFIER CHANNEL TRAIT
 // ================================================================================================
@@ -26,12 +26,19 @@ pub trait VerifierChannel<E: FieldElement> {
     /// Hash function used by the prover to commit to polynomial evaluations.
     type Hasher: ElementHasher<BaseField = E::BaseField>;
 
+    /// Vector commitment scheme used by the prover to commit to hashed polynomial evaluations.
+    type VectorCommitment: VectorCommitment<Self::Hasher>;
+
     // REQUIRED METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Returns the number of partitions used during proof generation.
     fn read_fri_num_partitions(&self) -> usize;
 
+    /// Returns the query seed proof-of-work nonce found by the prover after committing to all
+    /// FRI layers.
+    fn read_fri_pow_nonce(&self) -> u64;
+
     /// Reads and removes from the channel all FRI layer commitments sent by the prover.
     ///
     /// In the interactive version of the protocol, the prover sends layer commitments to the
@@ -43,6 +50,14 @@ pub trait VerifierChannel<E: FieldElement> {
         &mut self,
     ) -> Vec<<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest>;
 
+    /// Reads and removes from the channel DEEP values sent by the prover for all FRI layers
+    /// (excluding the remainder layer).
+    ///
+    /// Values for all layers are returned in a single vector in the order in which the layers
+    /// were committed to; the number of values for each layer is equal to the folding factor
+    /// used at that layer. If FRI is executed without DEEP folding, an empty vector is returned.
+    fn read_fri_deep_values(&mut self) -> Vec<E>;
+
     /// Reads and removes from the channel evaluations of the polynomial at the queried positions
     /// for the next FRI layer.
     ///
@@ -50,20 +65,29 @@ pub trait VerifierChannel<E: FieldElement> {
     /// the verifier during the query phase of the FRI protocol.
     ///
     /// It is expected that layer queries and layer proofs at the same FRI layer are consistent.
-    /// That is, query values hash into the leaf nodes of corresponding Merkle authentication
-    /// paths.
+    /// That is, query values hash into the items opened by the corresponding batch proof.
     fn take_next_fri_layer_queries(&mut self) -> Vec<E>;
 
-    /// Reads and removes from the channel Merkle authentication paths for queried evaluations for
+    /// Reads and removes from the channel salts for the queried evaluations for the next FRI
+    /// layer.
+    ///
+    /// If FRI layer commitments are not salted, an empty vector is returned. Otherwise, the
+    /// number of returned salts is expected to be equal to the number of layer queries.
+    fn take_next_fri_layer_salts(
+        &mut self,
+    ) -> Vec<<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest>;
+
+    /// Reads and removes from the channel the batch opening proof for queried evaluations for
     /// the next FRI layer.
     ///
-    /// In the interactive version of the protocol, these authentication paths are sent from the
-    /// prover to the verifier during the query phase of the FRI protocol.
+    /// In the interactive version of the protocol, these proofs are sent from the prover to the
+    /// verifier during the query phase of the FRI protocol.
     ///
     /// It is expected that layer proofs and layer queries at the same FRI layer are consistent.
-    /// That is, query values hash into the leaf nodes of corresponding Merkle authentication
-    /// paths.
-    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<Self::Hasher>;
+    /// That is, query values hash into the items opened by the corresponding batch proof.
+    fn take_next_fri_layer_proof(
+        &mut self,
+    ) -> <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::BatchProof;
 
     /// Reads and removes the remainder polynomial from the channel.
     fn take_fri_remainder(&mut self) -> Vec<E>;
@@ -71,31 +95,75 @@ pub trait VerifierChannel<E: FieldElement> {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Prepares the channel for reading queries of the next FRI layer; `layer_idx` is the index
+    /// of the next layer.
+    ///
+    /// This is invoked before any data for the layer is taken from the channel. Channels which
+    /// receive the proof incrementally (e.g., from a byte stream) can use this to read and parse
+    /// the layer only when it is needed. The default implementation does nothing.
+    ///
+    /// # Errors
+    /// Returns an error if data for the next layer could not be read.
+    fn read_next_fri_layer(&mut self, _layer_idx: usize) -> Result<(), VerifierError> {
+        Ok(())
+    }
+
     /// Returns FRI query values at the specified positions from the current FRI layer and advances
     /// layer pointer by one.
     ///
-    /// This also checks if the values are valid against the provided FRI layer commitment.
+    /// This also checks if the values are valid against the provided FRI layer commitment;
+    /// `layer_idx` is the index of the current layer and is used only for error reporting.
     ///
     /// # Errors
     /// Returns an error if query values did not match layer commitment.
     fn read_layer_queries<const N: usize>(
         &mut self,
+        layer_idx: usize,
         positions: &[usize],
         commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
     ) -> Result<Vec<[E; N]>, VerifierError> {
+        self.read_next_fri_layer(layer_idx)?;
         let layer_proof = self.take_next_fri_layer_proof();
-        MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
-            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+        let layer_queries = self.take_next_fri_layer_queries();
+        let layer_queries: Vec<[E; N]> = group_slice_elements(&layer_queries).to_vec();
+        let layer_salts = self.take_next_fri_layer_salts();
+        if !layer_salts.is_empty() && layer_salts.len() != layer_queries.len() {
+            return Err(VerifierError::LayerCommitmentMismatch(layer_idx));
+        }
 
-        // TODO: make sure layer queries hash into leaves of layer proof
+        // hash the query values (combined with salts, if any) to get the items opened by the
+        // layer proof, and make sure these items are consistent with the layer commitment
+        let mut items = layer_queries
+            .iter()
+            .map(|query| <Self::Hasher as ElementHasher>::hash_elements(query))
+            .collect::<Vec<_>>();
+        apply_salts::<Self::Hasher>(&mut items, &layer_salts);
+        <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::verify_batch(
+            commitment,
+            positions,
+            &items,
+            &layer_proof,
+        )
+        .map_err(|_| VerifierError::LayerCommitmentMismatch(layer_idx))?;
 
-        let layer_queries = self.take_next_fri_layer_queries();
-        Ok(group_slice_elements(&layer_queries).to_vec())
+        Ok(layer_queries)
     }
 
     /// Returns FRI remainder polynomial read from this channel.
-    fn read_remainder(&mut self) -> Result<Vec<E>, VerifierError> {
+    ///
+    /// This also checks whether the remainder polynomial coefficients hash into the provided
+    /// remainder commitment.
+    ///
+    /// # Errors
+    /// Returns an error if the remainder polynomial coefficients did not match the commitment.
+    fn read_remainder(
+        &mut self,
+        commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
+    ) -> Result<Vec<E>, VerifierError> {
         let remainder = self.take_fri_remainder();
+        if <Self::Hasher as ElementHasher>::hash_elements(&remainder) != *commitment {
+            return Err(VerifierError::RemainderCommitmentMismatch);
+        }
 
         Ok(remainder)
     }
@@ -106,65 +174,92 @@ pub trait VerifierChannel<E: FieldElement> {
 
 /// Provides a default implementation of the [VerifierChannel] trait.
 ///
-/// Default verifier channel can be instantiated directly from a [FriProof] struct.
+/// Default verifier channel can be instantiated directly from a [FriProof] struct. Layer
+/// commitments are assumed to be built using the vector commitment scheme `V`, which defaults to
+/// [MerkleTree].
 ///
 /// Though this implementation is primarily intended for testing purposes, it can be used in
 /// production use cases as well.
-pub struct DefaultVerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+pub struct DefaultVerifierChannel<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> = MerkleTree<H>,
+> {
     layer_commitments: Vec<H::Digest>,
-    layer_proofs: Vec<BatchMerkleProof<H>>,
+    layer_proofs: Vec<V::BatchProof>,
     layer_queries: Vec<Vec<E>>,
+    layer_salts: Vec<Vec<H::Digest>>,
     remainder: Vec<E>,
+    deep_values: Vec<E>,
     num_partitions: usize,
+    pow_nonce: u64,
 }
 
-impl<E, H> DefaultVerifierChannel<E, H>
+impl<E, H, V> DefaultVerifierChannel<E, H, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     /// Builds a new verifier channel from the specified [FriProof].
     ///
+    /// The layers of the proof are parsed using the folding schedule of the specified `options`.
+    ///
     /// # Errors
     /// Returns an error if the specified `proof` could not be parsed correctly.
     pub fn new(
         proof: FriProof,
         layer_commitments: Vec<H::Digest>,
         domain_size: usize,
-        folding_factor: usize,
+        options: &FriOptions,
     ) -> Result<Self, DeserializationError> {
         let num_partitions = proof.num_partitions();
+        let pow_nonce = proof.pow_nonce();
 
         let remainder = proof.parse_remainder()?;
-        let (layer_queries, layer_proofs) =
-            proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+        let deep_values = proof.parse_deep_values()?;
+        let (layer_queries, layer_salts, layer_proofs) =
+            proof.parse_layers::<H, E, V>(domain_size, options)?;
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
             layer_proofs,
             layer_queries,
+            layer_salts,
             remainder,
+            deep_values,
             num_partitions,
+            pow_nonce,
         })
     }
 }
 
-impl<E, H> VerifierChannel<E> for DefaultVerifierChannel<E, H>
+impl<E, H, V> VerifierChannel<E> for DefaultVerifierChannel<E, H, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     type Hasher = H;
+    type VectorCommitment = V;
 
     fn read_fri_num_partitions(&self) -> usize {
         self.num_partitions
     }
 
+    fn read_fri_pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
     fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
         self.layer_commitments.drain(..).collect()
     }
 
-    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
+    fn read_fri_deep_values(&mut self) -> Vec<E> {
+        self.deep_values.drain(..).collect()
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> V::BatchProof {
         self.layer_proofs.remove(0)
     }
 
@@ -172,6 +267,10 @@ where
         self.layer_queries.remove(0)
     }
 
+    fn take_next_fri_layer_salts(&mut self) -> Vec<H::Digest> {
+        self.layer_salts.remove(0)
+    }
+
     fn take_fri_remainder(&mut self) -> Vec<E> {
         self.remainder.clone()
     }
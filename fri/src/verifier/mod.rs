@@ -6,12 +6,19 @@
 //! Contains an implementation of FRI verifier and associated components.
 
 use alloc::vec::Vec;
-use core::{marker::PhantomData, mem};
+use core::marker::PhantomData;
 
-use crypto::{ElementHasher, RandomCoin};
-use math::{polynom, FieldElement, StarkField};
+use crypto::{ElementHasher, Transcript, TranscriptLabel};
+use math::{
+    fft::{EvaluationDomain, FftDomain},
+    polynom, FieldElement,
+};
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
+use crate::{
+    folding::fold_positions,
+    utils::{get_batching_coefficients, map_positions_to_indexes},
+    FriOptions, VerifierError,
+};
 
 mod channel;
 pub use channel::{DefaultVerifierChannel, VerifierChannel};
@@ -43,32 +50,36 @@ pub use channel::{DefaultVerifierChannel, VerifierChannel};
 /// from the prover (via [VerifierChannel]). After each received commitment, the verifier
 /// draws a random value α from the entire field, and sends it to the prover. In the
 /// non-interactive version of the protocol, α values are derived pseudo-randomly from FRI
-/// layer commitments.
+/// layer commitments. When DEEP-FRI folding is used, before drawing α for a layer the verifier
+/// also draws an out-of-domain point *z*, and receives evaluations of the decomposed layer
+/// polynomial at *z* from the prover.
 ///
 /// # Query phase
 /// During the query phase, which is executed via [verify()](FriVerifier::verify()) function,
 /// the verifier sends a set of positions in the domain *D* to the prover, and the prover responds
-/// with polynomial evaluations at these positions (together with corresponding Merkle paths)
-/// across all FRI layers. The verifier then checks that:
-/// * The Merkle paths are valid against the layer commitments the verifier received during
+/// with polynomial evaluations at these positions (together with corresponding batch opening
+/// proofs) across all FRI layers. The verifier then checks that:
+/// * The opening proofs are valid against the layer commitments the verifier received during
 ///   the commit phase.
 /// * The evaluations are consistent across FRI layers (i.e., the degree-respecting projection
 ///   was applied correctly).
 /// * The degree of the polynomial implied by evaluations at the last FRI layer (the remainder)
 ///   is smaller than the degree resulting from reducing degree *d* by `folding_factor` at each
-///   FRI layer.
+///   FRI layer, and is not greater than the remainder degree bound declared in [FriOptions].
 pub struct FriVerifier<E, C, H, R>
 where
     E: FieldElement,
     C: VerifierChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
 {
     max_poly_degree: usize,
-    domain_size: usize,
-    domain_generator: E::BaseField,
+    domain: EvaluationDomain<E::BaseField>,
     layer_commitments: Vec<H::Digest>,
     layer_alphas: Vec<E>,
+    layer_deep_points: Vec<E>,
+    layer_deep_values: Vec<Vec<E>>,
+    batching_coefficients: Vec<E>,
     options: FriOptions,
     num_partitions: usize,
     _channel: PhantomData<C>,
@@ -80,7 +91,7 @@ where
     E: FieldElement,
     C: VerifierChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
 {
     /// Returns a new instance of FRI verifier created from the specified parameters.
     ///
@@ -101,47 +112,137 @@ where
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
+    /// * The number of DEEP values read from the channel is inconsistent with the folding
+    ///   schedule specified in the `options` parameter.
+    /// * The query seed proof-of-work nonce read from the channel does not satisfy the grinding
+    ///   factor specified in the `options` parameter.
     pub fn new(
         channel: &mut C,
         public_coin: &mut R,
         options: FriOptions,
         max_poly_degree: usize,
+    ) -> Result<Self, VerifierError> {
+        Self::with_batching_coefficients(channel, public_coin, options, max_poly_degree, Vec::new())
+    }
+
+    /// Returns a new instance of FRI verifier for a proof generated for a random linear
+    /// combination of `num_codewords` codewords.
+    ///
+    /// This mirrors [FriProver::build_layers_batched()](crate::FriProver::build_layers_batched()):
+    /// before executing the commit phase of the FRI protocol (as described in
+    /// [new()](FriVerifier::new())), the verifier draws a random value γ from the `public_coin`
+    /// and uses its powers as coefficients for combining the codewords. Query values of the
+    /// codewords are then combined via [verify_batched()](FriVerifier::verify_batched()).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * An error was encountered while drawing a random γ value from the coin.
+    /// * Any of the conditions described in [new()](FriVerifier::new()) are not satisfied.
+    ///
+    /// # Panics
+    /// Panics if `num_codewords` is zero.
+    pub fn new_batched(
+        channel: &mut C,
+        public_coin: &mut R,
+        options: FriOptions,
+        max_poly_degree: usize,
+        num_codewords: usize,
+    ) -> Result<Self, VerifierError> {
+        assert!(num_codewords > 0, "number of codewords must be greater than zero");
+        let gamma = public_coin
+            .squeeze(TranscriptLabel::FriLayer)
+            .map_err(VerifierError::RandomCoinError)?;
+        let batching_coefficients = get_batching_coefficients(gamma, num_codewords);
+        Self::with_batching_coefficients(
+            channel,
+            public_coin,
+            options,
+            max_poly_degree,
+            batching_coefficients,
+        )
+    }
+
+    /// Executes the commit phase of the FRI protocol from the verifier's perspective.
+    fn with_batching_coefficients(
+        channel: &mut C,
+        public_coin: &mut R,
+        options: FriOptions,
+        max_poly_degree: usize,
+        batching_coefficients: Vec<E>,
     ) -> Result<Self, VerifierError> {
         // infer evaluation domain info
         let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
-        let domain_generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
+        let domain = EvaluationDomain::new(domain_size, options.domain_offset());
 
         let num_partitions = channel.read_fri_num_partitions();
 
-        // read layer commitments from the channel and use them to build a list of alphas
+        // read layer commitments from the channel and use them to build a list of alphas; for
+        // DEEP-FRI, also draw out-of-domain points and absorb DEEP values for all layers but the
+        // remainder layer
         let layer_commitments = channel.read_fri_layer_commitments();
+        let deep_values = channel.read_fri_deep_values();
+        let num_deep_values = if options.deep_fri() && !layer_commitments.is_empty() {
+            (0..layer_commitments.len() - 1)
+                .map(|depth| options.folding_factor_at(depth))
+                .sum()
+        } else {
+            0
+        };
+        if deep_values.len() != num_deep_values {
+            return Err(VerifierError::NumDeepValuesMismatch(num_deep_values, deep_values.len()));
+        }
+
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
+        let mut layer_deep_points = Vec::new();
+        let mut layer_deep_values = Vec::new();
+        let mut deep_values = deep_values.as_slice();
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
-            public_coin.reseed(*commitment);
-            let alpha = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+            let folding_factor = options.folding_factor_at(depth);
+            public_coin.absorb(TranscriptLabel::FriLayer, *commitment);
+            if options.deep_fri() && depth != layer_commitments.len() - 1 {
+                let z = public_coin
+                    .squeeze(TranscriptLabel::FriLayer)
+                    .map_err(VerifierError::RandomCoinError)?;
+                let (values, rest) = deep_values.split_at(folding_factor);
+                public_coin.absorb(TranscriptLabel::FriDeepValues, H::hash_elements(values));
+                layer_deep_points.push(z);
+                layer_deep_values.push(values.to_vec());
+                deep_values = rest;
+            }
+            let alpha = public_coin
+                .squeeze(TranscriptLabel::FriLayer)
+                .map_err(VerifierError::RandomCoinError)?;
             layer_alphas.push(alpha);
 
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
-            if depth != layer_commitments.len() - 1
-                && max_degree_plus_1 % options.folding_factor() != 0
-            {
+            if depth != layer_commitments.len() - 1 && max_degree_plus_1 % folding_factor != 0 {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
-                    options.folding_factor(),
+                    folding_factor,
                     depth,
                 ));
             }
-            max_degree_plus_1 /= options.folding_factor();
+            max_degree_plus_1 /= folding_factor;
+        }
+
+        // make sure the prover performed the required proof-of-work for the query seed
+        let grinding_factor = options.grinding_factor();
+        if grinding_factor > 0
+            && public_coin.check_leading_zeros(channel.read_fri_pow_nonce()) < grinding_factor
+        {
+            return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
         }
 
         Ok(FriVerifier {
             max_poly_degree,
-            domain_size,
-            domain_generator,
+            domain,
             layer_commitments,
             layer_alphas,
+            layer_deep_points,
+            layer_deep_values,
+            batching_coefficients,
             options,
             num_partitions,
             _channel: PhantomData,
@@ -163,7 +264,13 @@ where
     /// The domain size can be computed by rounding `max_poly_degree` to the next power of two
     /// and multiplying the result by the `blowup_factor` from the protocol options.
     pub fn domain_size(&self) -> usize {
-        self.domain_size
+        self.domain.size()
+    }
+
+    /// Returns the domain over which a polynomial commitment checked by this verifier has been
+    /// evaluated.
+    pub fn domain(&self) -> &EvaluationDomain<E::BaseField> {
+        &self.domain
     }
 
     /// Returns number of partitions used during FRI proof generation.
@@ -173,6 +280,22 @@ where
         self.num_partitions
     }
 
+    /// Returns coefficients used to combine codewords into a single codeword.
+    ///
+    /// If this verifier was not instantiated via [new_batched()](FriVerifier::new_batched()), an
+    /// empty slice is returned.
+    pub fn batching_coefficients(&self) -> &[E] {
+        &self.batching_coefficients
+    }
+
+    /// Returns the values α drawn from the public coin for folding each of the FRI layers.
+    ///
+    /// The values are listed in the order in which the layers were committed to; the value for
+    /// the last commitment (i.e., the commitment to the remainder) is not used for folding.
+    pub fn layer_alphas(&self) -> &[E] {
+        &self.layer_alphas
+    }
+
     /// Returns protocol configuration options for this verifier.
     pub fn options(&self) -> &FriOptions {
         &self.options
@@ -201,8 +324,10 @@ where
     ///   FRI layers.
     /// * The verifier detects an error in how the degree-respecting projection was applied
     ///   at any of the FRI layers.
+    /// * The remainder polynomial read from the channel does not match the remainder commitment.
     /// * The degree of the remainder at the last FRI layer is greater than the degree implied by
-    ///   `max_poly_degree` reduced by the folding factor at each FRI layer.
+    ///   `max_poly_degree` reduced by the folding factor at each FRI layer, or greater than the
+    ///   `remainder_max_degree` specified by the options for this verifier.
     pub fn verify(
         &self,
         channel: &mut C,
@@ -216,112 +341,191 @@ where
             ));
         }
 
-        // static dispatch for folding factor parameter
-        let folding_factor = self.options.folding_factor();
-        match folding_factor {
-            2 => self.verify_generic::<2>(channel, evaluations, positions),
-            4 => self.verify_generic::<4>(channel, evaluations, positions),
-            8 => self.verify_generic::<8>(channel, evaluations, positions),
-            16 => self.verify_generic::<16>(channel, evaluations, positions),
-            _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
-        }
-    }
-
-    /// This is the actual implementation of the verification procedure described above, but it
-    /// also takes folding factor as a generic parameter N.
-    fn verify_generic<const N: usize>(
-        &self,
-        channel: &mut C,
-        evaluations: &[E],
-        positions: &[usize],
-    ) -> Result<(), VerifierError> {
-        // pre-compute roots of unity used in computing x coordinates in the folded domain
-        let folding_roots = (0..N)
-            .map(|i| self.domain_generator.exp_vartime(((self.domain_size / N * i) as u64).into()))
-            .collect::<Vec<_>>();
-
         // 1 ----- verify the recursive components of the FRI proof -----------------------------------
-        let mut domain_generator = self.domain_generator;
-        let mut domain_size = self.domain_size;
+        let mut domain = self.domain.clone();
         let mut max_degree_plus_1 = self.max_poly_degree + 1;
         let mut positions = positions.to_vec();
         let mut evaluations = evaluations.to_vec();
 
-        for depth in 0..self.options.num_fri_layers(self.domain_size) {
-            // determine which evaluations were queried in the folded layer
-            let mut folded_positions =
-                fold_positions(&positions, domain_size, self.options.folding_factor());
-            // determine where these evaluations are in the commitment Merkle tree
-            let position_indexes = map_positions_to_indexes(
-                &folded_positions,
-                domain_size,
-                self.options.folding_factor(),
-                self.num_partitions,
-            );
-            // read query values from the specified indexes in the Merkle tree
-            let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
-            let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
-            }
-
-            // build a set of x coordinates for each row polynomial
-            #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp_vartime((i as u64).into()) * self.options.domain_offset();
-                folding_roots.iter()
-                    .map(|&r| E::from(xe * r))
-                    .collect::<Vec<_>>().try_into().unwrap()
-            })
-            .collect::<Vec<_>>();
-
-            // interpolate x and y values into row polynomials
-            let row_polys = polynom::interpolate_batch(&xs, &layer_values);
-
-            // calculate the pseudo-random value used for linear combination in layer folding
-            let alpha = self.layer_alphas[depth];
-
-            // check that when the polynomials are evaluated at alpha, the result is equal to
-            // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+        for depth in 0..self.options.num_fri_layers(self.domain.size()) {
+            // static dispatch for folding factor parameter
+            let folding_factor = self.options.folding_factor_at(depth);
+            let (folded_positions, folded_evaluations) = match folding_factor {
+                2 => self.verify_layer::<2>(channel, depth, &positions, &evaluations, &domain)?,
+                4 => self.verify_layer::<4>(channel, depth, &positions, &evaluations, &domain)?,
+                8 => self.verify_layer::<8>(channel, depth, &positions, &evaluations, &domain)?,
+                16 => self.verify_layer::<16>(channel, depth, &positions, &evaluations, &domain)?,
+                32 => self.verify_layer::<32>(channel, depth, &positions, &evaluations, &domain)?,
+                64 => self.verify_layer::<64>(channel, depth, &positions, &evaluations, &domain)?,
+                _ => return Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+            };
 
             // make sure next degree reduction does not result in degree truncation
-            if max_degree_plus_1 % N != 0 {
-                return Err(VerifierError::DegreeTruncation(max_degree_plus_1 - 1, N, depth));
+            if max_degree_plus_1 % folding_factor != 0 {
+                return Err(VerifierError::DegreeTruncation(
+                    max_degree_plus_1 - 1,
+                    folding_factor,
+                    depth,
+                ));
             }
 
             // update variables for the next iteration of the loop
-            domain_generator = domain_generator.exp_vartime((N as u32).into());
-            max_degree_plus_1 /= N;
-            domain_size /= N;
-            mem::swap(&mut positions, &mut folded_positions);
+            domain = EvaluationDomain::new(domain.size() / folding_factor, domain.offset());
+            max_degree_plus_1 /= folding_factor;
+            positions = folded_positions;
+            evaluations = folded_evaluations;
         }
 
         // 2 ----- verify the remainder polynomial of the FRI proof -------------------------------
 
-        // read the remainder polynomial from the channel and make sure it agrees with the evaluations
-        // from the previous layer.
-        let remainder_poly = channel.read_remainder()?;
-        if remainder_poly.len() > max_degree_plus_1 {
-            return Err(VerifierError::RemainderDegreeMismatch(max_degree_plus_1 - 1));
+        // read the remainder polynomial from the channel and make sure it matches the remainder
+        // commitment and agrees with the evaluations from the previous layer; since the remainder
+        // is sent in coefficient form, we only need to evaluate it at the queried positions.
+        // the remainder degree must satisfy both the degree implied by folding and the bound
+        // declared in the options.
+        let max_remainder_len =
+            core::cmp::min(max_degree_plus_1, self.options.remainder_max_degree() + 1);
+        let remainder_commitment = self
+            .layer_commitments
+            .last()
+            .ok_or(VerifierError::RemainderCommitmentMismatch)?;
+        let remainder_poly = channel.read_remainder(remainder_commitment)?;
+        if remainder_poly.len() > max_remainder_len {
+            return Err(VerifierError::RemainderDegreeMismatch(max_remainder_len - 1));
         }
-        let offset: E::BaseField = self.options().domain_offset();
-
         for (&position, evaluation) in positions.iter().zip(evaluations) {
-            let comp_eval = eval_horner::<E>(
-                &remainder_poly,
-                offset * domain_generator.exp_vartime((position as u64).into()),
-            );
+            let comp_eval = eval_horner::<E>(&remainder_poly, domain.get_x_at(position));
             if comp_eval != evaluation {
-                return Err(VerifierError::InvalidRemainderFolding);
+                return Err(VerifierError::InvalidRemainderFolding(position));
             }
         }
 
         Ok(())
     }
+
+    /// Executes the query phase of the FRI protocol for a random linear combination of codewords.
+    ///
+    /// The i-th element of `evaluations` must contain evaluations of the i-th codeword at the
+    /// specified `positions`. The evaluations are combined using the batching coefficients of
+    /// this verifier, and the combined evaluations are verified as described in
+    /// [verify()](FriVerifier::verify()).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of codewords in `evaluations` is not equal to the number of batching
+    ///   coefficients of this verifier.
+    /// * The number of evaluations of any of the codewords is not equal to the length of
+    ///   `positions`.
+    /// * Any of the conditions described in [verify()](FriVerifier::verify()) are not satisfied.
+    pub fn verify_batched(
+        &self,
+        channel: &mut C,
+        evaluations: &[Vec<E>],
+        positions: &[usize],
+    ) -> Result<(), VerifierError> {
+        if evaluations.len() != self.batching_coefficients.len() {
+            return Err(VerifierError::NumCodewordsMismatch(
+                self.batching_coefficients.len(),
+                evaluations.len(),
+            ));
+        }
+
+        let mut combined_evaluations = vec![E::ZERO; positions.len()];
+        for (codeword_evaluations, &coefficient) in
+            evaluations.iter().zip(self.batching_coefficients.iter())
+        {
+            if codeword_evaluations.len() != positions.len() {
+                return Err(VerifierError::NumPositionEvaluationMismatch(
+                    positions.len(),
+                    codeword_evaluations.len(),
+                ));
+            }
+            combined_evaluations
+                .iter_mut()
+                .zip(codeword_evaluations)
+                .for_each(|(acc, &value)| *acc += value * coefficient);
+        }
+
+        self.verify(channel, &combined_evaluations, positions)
+    }
+
+    /// Verifies a single FRI layer folded by a factor of N.
+    ///
+    /// Reads the query values for the layer at the specified `depth` from the `channel`, checks
+    /// them against the `evaluations` computed at the previous layer, and returns folded positions
+    /// together with evaluations of the folded polynomial at these positions.
+    fn verify_layer<const N: usize>(
+        &self,
+        channel: &mut C,
+        depth: usize,
+        positions: &[usize],
+        evaluations: &[E],
+        domain: &EvaluationDomain<E::BaseField>,
+    ) -> Result<(Vec<usize>, Vec<E>), VerifierError> {
+        let domain_size = domain.size();
+
+        // pre-compute roots of unity used in computing x coordinates in the folded domain
+        let folding_roots = (0..N)
+            .map(|i| domain.generator().exp_vartime(((domain_size / N * i) as u64).into()))
+            .collect::<Vec<_>>();
+
+        // determine which evaluations were queried in the folded layer
+        let folded_positions = fold_positions(positions, domain_size, N);
+        // determine where these evaluations are in the commitment Merkle tree
+        let position_indexes =
+            map_positions_to_indexes(&folded_positions, domain_size, N, self.num_partitions);
+        // read query values from the specified indexes in the Merkle tree
+        let layer_commitment = self.layer_commitments[depth];
+        let layer_values =
+            channel.read_layer_queries(depth, &position_indexes, &layer_commitment)?;
+        let query_values =
+            get_query_values::<E, N>(&layer_values, positions, &folded_positions, domain_size);
+        if let Some((&position, _)) = positions
+            .iter()
+            .zip(evaluations.iter().zip(query_values.iter()))
+            .find(|(_, (evaluation, query_value))| evaluation != query_value)
+        {
+            return Err(VerifierError::InvalidLayerFolding(depth, position));
+        }
+
+        // build a set of x coordinates for each row polynomial
+        #[rustfmt::skip]
+        let xs = folded_positions.iter().map(|&i| {
+            let xe = domain.get_x_at(i);
+            folding_roots.iter()
+                .map(|&r| E::from(xe * r))
+                .collect::<Vec<_>>().try_into().unwrap()
+        })
+        .collect::<Vec<_>>();
+
+        // interpolate x and y values into row polynomials
+        let row_polys = polynom::interpolate_batch(&xs, &layer_values);
+
+        // calculate the pseudo-random value used for linear combination in layer folding
+        let alpha = self.layer_alphas[depth];
+
+        // check that when the polynomials are evaluated at alpha, the result is equal to
+        // the corresponding column value
+        let mut folded_evaluations =
+            row_polys.iter().map(|p| polynom::eval(p, alpha)).collect::<Vec<_>>();
+
+        // for DEEP-FRI, the next layer contains evaluations of the folded polynomial shifted by
+        // the out-of-domain point z; that is, (f'(y) - f'(z)) / (y - z), where y = x^N
+        if self.options.deep_fri() {
+            let z = self.layer_deep_points[depth];
+            let folded_z = polynom::eval(&self.layer_deep_values[depth], alpha);
+            for (&i, value) in folded_positions.iter().zip(folded_evaluations.iter_mut()) {
+                let xe = domain.get_x_at(i);
+                let y = E::from(xe.exp_vartime((N as u64).into()));
+                if y == z {
+                    return Err(VerifierError::InvalidLayerFolding(depth, i));
+                }
+                *value = (*value - folded_z) / (y - z);
+            }
+        }
+
+        Ok((folded_positions, folded_evaluations))
+    }
 }
 
 // HELPER FUNCTIONS
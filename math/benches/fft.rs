@@ -8,8 +8,9 @@ use std::time::Duration;
 use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
 use rand_utils::rand_vector;
 use winter_math::{
-    fft,
-    fields::{f128, f62, f64, CubeExtension, QuadExtension},
+    circle::CircleDomain,
+    fft::{self, EvaluationDomain, FftDomain},
+    fields::{f128, f62, f64, m31, CubeExtension, QuadExtension},
     FieldElement, StarkField,
 };
 
@@ -88,6 +89,34 @@ where
     group.finish();
 }
 
+fn fft_extend(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fft_extend");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    let blowup_factor = 8;
+
+    // two-adic cosets in the 64-bit field vs. circle domains in the Mersenne-31 field
+    for &size in SIZES.iter() {
+        let source = EvaluationDomain::new(size / blowup_factor, f64::BaseElement::ONE);
+        let target = EvaluationDomain::new(size, f64::BaseElement::GENERATOR);
+        let evaluations: Vec<f64::BaseElement> = rand_vector(source.size());
+        group.bench_function(BenchmarkId::new("f64", size), |bench| {
+            bench.iter_with_large_drop(|| source.extend(&evaluations, &target));
+        });
+
+        let log_size = size.ilog2();
+        let source = CircleDomain::new(log_size - blowup_factor.ilog2());
+        let target = CircleDomain::new(log_size);
+        let evaluations: Vec<m31::BaseElement> = rand_vector(source.size());
+        group.bench_function(BenchmarkId::new("m31_circle", size), |bench| {
+            bench.iter_with_large_drop(|| source.extend(&evaluations, &target));
+        });
+    }
+
+    group.finish();
+}
+
 fn get_twiddles(c: &mut Criterion) {
     let mut group = c.benchmark_group("fft_get_twiddles");
     group.sample_size(10);
@@ -103,6 +132,10 @@ fn bench_fft(c: &mut Criterion) {
     fft_evaluate_poly::<f62::BaseElement, f62::BaseElement>(c, "f62");
     fft_evaluate_poly::<f64::BaseElement, f64::BaseElement>(c, "f64");
     fft_evaluate_poly::<f128::BaseElement, f128::BaseElement>(c, "f128");
+    fft_evaluate_poly::<f128::montgomery::BaseElement, f128::montgomery::BaseElement>(
+        c,
+        "f128_mont",
+    );
 
     fft_evaluate_poly::<f62::BaseElement, QuadExtension<f62::BaseElement>>(c, "f62_quad");
     fft_evaluate_poly::<f64::BaseElement, QuadExtension<f64::BaseElement>>(c, "f64_quad");
@@ -113,7 +146,11 @@ fn bench_fft(c: &mut Criterion) {
     fft_interpolate_poly::<f62::BaseElement, f62::BaseElement>(c, "f62");
     fft_interpolate_poly::<f64::BaseElement, f64::BaseElement>(c, "f64");
     fft_interpolate_poly::<f128::BaseElement, f128::BaseElement>(c, "f128");
+    fft_interpolate_poly::<f128::montgomery::BaseElement, f128::montgomery::BaseElement>(
+        c,
+        "f128_mont",
+    );
 }
 
-criterion_group!(fft_group, bench_fft, get_twiddles);
+criterion_group!(fft_group, bench_fft, fft_extend, get_twiddles);
 criterion_main!(fft_group);
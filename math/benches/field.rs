@@ -13,7 +13,7 @@ use criterion::{
 use rand_utils::{rand_array, rand_value, rand_vector};
 use winter_math::{
     batch_inversion,
-    fields::{f128, f62, f64, CubeExtension, QuadExtension},
+    fields::{f128, f62, f64, m31, CubeExtension, QuadExtension},
     ExtensibleField, FieldElement, StarkField,
 };
 
@@ -260,6 +260,8 @@ fn bench_field_ops(c: &mut Criterion) {
     field_ops::<f62::BaseElement>(c, "f62");
     field_ops::<f64::BaseElement>(c, "f64");
     field_ops::<f128::BaseElement>(c, "f128");
+    field_ops::<f128::montgomery::BaseElement>(c, "f128_mont");
+    field_ops::<m31::BaseElement>(c, "m31");
 }
 
 // CRITERION BOILERPLATE
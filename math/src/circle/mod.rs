@@ -0,0 +1,385 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Circle-group evaluation domains and circle FFT over the Mersenne-31 field.
+//!
+//! The multiplicative group of the [Mersenne-31](crate::fields::m31) field has two-adicity of 1,
+//! and thus, this field does not contain multiplicative subgroups suitable for radix-2 FFT.
+//! However, the points of the circle curve x<sup>2</sup> + y<sup>2</sup> = 1 over this field
+//! form a cyclic group of order 2<sup>31</sup> under the group law
+//! (x<sub>0</sub>, y<sub>0</sub>) + (x<sub>1</sub>, y<sub>1</sub>) =
+//! (x<sub>0</sub>x<sub>1</sub> - y<sub>0</sub>y<sub>1</sub>,
+//! x<sub>0</sub>y<sub>1</sub> + y<sub>0</sub>x<sub>1</sub>). Cosets of subgroups of this group
+//! can be used as evaluation domains for polynomials, as described in
+//! [Circle STARKs](https://eprint.iacr.org/2024/278).
+//!
+//! Polynomials over circle domains are represented in the basis formed by products of y,
+//! x, π(x), π(π(x)), ..., where π(x) = 2x<sup>2</sup> - 1 is the x-coordinate of a point
+//! doubling map. The coefficient at index i is the coefficient of the basis element
+//! y<sup>b<sub>0</sub></sup> · x<sup>b<sub>1</sub></sup> · π(x)<sup>b<sub>2</sub></sup> · ...,
+//! where b<sub>j</sub> is the j-th bit of i. With this ordering, coefficients interpolated over
+//! a domain of size n can be padded with zeros to extend the polynomial onto a larger domain.
+
+use alloc::vec::Vec;
+use core::ops::{Add, Neg, Sub};
+
+use crate::{
+    batch_inversion,
+    fft::{self, FftDomain},
+    fields::m31::BaseElement,
+    FieldElement,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CIRCLE POINT
+// ================================================================================================
+
+/// A point on the circle curve x<sup>2</sup> + y<sup>2</sup> = 1 over the Mersenne-31 field.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CirclePoint {
+    x: BaseElement,
+    y: BaseElement,
+}
+
+impl CirclePoint {
+    /// Identity element of the circle group.
+    pub const IDENTITY: Self = Self {
+        x: BaseElement::new(1),
+        y: BaseElement::new(0),
+    };
+
+    /// Generator of the circle group; the order of this point is 2<sup>31</sup>.
+    pub const GENERATOR: Self = Self {
+        x: BaseElement::new(2),
+        y: BaseElement::new(1268011823),
+    };
+
+    /// Base-2 logarithm of the order of the circle group.
+    pub const LOG_ORDER: u32 = 31;
+
+    /// Returns a new point with the specified coordinates.
+    ///
+    /// # Panics
+    /// Panics if the point does not lie on the circle curve.
+    pub fn new(x: BaseElement, y: BaseElement) -> Self {
+        assert_eq!(
+            x.square() + y.square(),
+            BaseElement::ONE,
+            "point ({x}, {y}) is not on the circle"
+        );
+        Self { x, y }
+    }
+
+    /// Returns a generator of the subgroup of the circle group of size 2<sup>`log_order`</sup>.
+    ///
+    /// # Panics
+    /// Panics if `log_order` is greater than 31.
+    pub fn subgroup_generator(log_order: u32) -> Self {
+        assert!(
+            log_order <= Self::LOG_ORDER,
+            "circle subgroup of order 2^{log_order} does not exist"
+        );
+        (log_order..Self::LOG_ORDER).fold(Self::GENERATOR, |point, _| point.double())
+    }
+
+    /// Returns the x-coordinate of this point.
+    pub fn x(&self) -> BaseElement {
+        self.x
+    }
+
+    /// Returns the y-coordinate of this point.
+    pub fn y(&self) -> BaseElement {
+        self.y
+    }
+
+    /// Returns this point added to itself.
+    pub fn double(&self) -> Self {
+        // (x, y) + (x, y) = (x^2 - y^2, 2xy) = (2x^2 - 1, 2xy) since x^2 + y^2 = 1
+        Self {
+            x: self.x.square().double() - BaseElement::ONE,
+            y: (self.x * self.y).double(),
+        }
+    }
+
+    /// Returns the inverse of this point in the circle group; this is the same as (x, -y).
+    pub fn conjugate(&self) -> Self {
+        Self { x: self.x, y: -self.y }
+    }
+
+    /// Returns the point which is antipodal to this point; this is the same as (-x, -y).
+    pub fn antipode(&self) -> Self {
+        Self { x: -self.x, y: -self.y }
+    }
+
+    /// Returns this point added to itself `k` times.
+    pub fn mul_scalar(&self, k: u64) -> Self {
+        let mut result = Self::IDENTITY;
+        let mut base = *self;
+        let mut k = k;
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result + base;
+            }
+            base = base.double();
+            k >>= 1;
+        }
+        result
+    }
+}
+
+impl Add for CirclePoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x * rhs.x - self.y * rhs.y,
+            y: self.x * rhs.y + self.y * rhs.x,
+        }
+    }
+}
+
+impl Sub for CirclePoint {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs.conjugate()
+    }
+}
+
+impl Neg for CirclePoint {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.conjugate()
+    }
+}
+
+// CIRCLE DOMAIN
+// ================================================================================================
+
+/// A circle domain of size n = 2<sup>`log_size`</sup>.
+///
+/// The domain is the union of a half-coset Q = {q + k · g : k in 0..n/2} and its conjugate, where
+/// q is a point of order 2n and g is a generator of the subgroup of order n/2. Points of the
+/// domain are ordered such that the first n/2 points are the points of Q (in the order of k),
+/// and the last n/2 points are their conjugates (in the same order).
+///
+/// The twiddles needed to evaluate and interpolate polynomials over this domain are computed
+/// when the domain is instantiated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircleDomain {
+    log_size: u32,
+    initial_point: CirclePoint,
+    step: CirclePoint,
+    y_twiddles: Vec<BaseElement>,
+    inv_y_twiddles: Vec<BaseElement>,
+    x_twiddles: Vec<Vec<BaseElement>>,
+    inv_x_twiddles: Vec<Vec<BaseElement>>,
+}
+
+impl CircleDomain {
+    /// Returns a new circle domain of size 2<sup>`log_size`</sup>.
+    ///
+    /// # Panics
+    /// Panics if `log_size` is zero or greater than 30.
+    pub fn new(log_size: u32) -> Self {
+        assert!(log_size > 0, "circle domain size must be at least 2");
+        assert!(
+            log_size < CirclePoint::LOG_ORDER,
+            "circle domain size cannot be greater than 2^30, but was 2^{log_size}"
+        );
+
+        let initial_point = CirclePoint::subgroup_generator(log_size + 1);
+        let step = CirclePoint::subgroup_generator(log_size - 1);
+
+        // y-coordinates of the half-coset are used in the first layer of the FFT
+        let half_size = 1 << (log_size - 1);
+        let mut half_coset = Vec::with_capacity(half_size);
+        let mut point = initial_point;
+        for _ in 0..half_size {
+            half_coset.push(point);
+            point = point + step;
+        }
+        let y_twiddles = half_coset.iter().map(|p| p.y).collect::<Vec<_>>();
+
+        // x-coordinates are used in the remaining layers; for each subsequent layer, only the
+        // first half of the x-coordinates is needed (the second half contains their negations),
+        // and the coordinates for the next layer are obtained via the doubling map
+        let mut x_twiddles = Vec::with_capacity(log_size as usize - 1);
+        let mut xs = half_coset.iter().map(|p| p.x).collect::<Vec<_>>();
+        while xs.len() > 1 {
+            xs.truncate(xs.len() / 2);
+            x_twiddles.push(xs.clone());
+            xs.iter_mut().for_each(|x| *x = x.square().double() - BaseElement::ONE);
+        }
+
+        Self {
+            log_size,
+            initial_point,
+            step,
+            inv_y_twiddles: batch_inversion(&y_twiddles),
+            y_twiddles,
+            inv_x_twiddles: x_twiddles.iter().map(|xs| batch_inversion(xs)).collect(),
+            x_twiddles,
+        }
+    }
+
+    /// Returns base-2 logarithm of the size of this domain.
+    pub fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the point of this domain at the specified index.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the size of this domain.
+    pub fn get_point(&self, index: usize) -> CirclePoint {
+        let half_size = self.size() / 2;
+        assert!(index < self.size(), "point index {index} is out of bounds");
+        if index < half_size {
+            self.initial_point + self.step.mul_scalar(index as u64)
+        } else {
+            (self.initial_point + self.step.mul_scalar((index - half_size) as u64)).conjugate()
+        }
+    }
+
+    /// Returns all points of this domain.
+    pub fn points(&self) -> Vec<CirclePoint> {
+        let half_size = self.size() / 2;
+        let mut result = Vec::with_capacity(self.size());
+        let mut point = self.initial_point;
+        for _ in 0..half_size {
+            result.push(point);
+            point = point + self.step;
+        }
+        let conjugates = result.iter().map(|p| p.conjugate()).collect::<Vec<_>>();
+        result.extend(conjugates);
+        result
+    }
+}
+
+impl FftDomain<BaseElement> for CircleDomain {
+    fn size(&self) -> usize {
+        1 << self.log_size
+    }
+
+    fn interpolate<E: FieldElement<BaseField = BaseElement>>(&self, evaluations: &mut [E]) {
+        let n = self.size();
+        assert_eq!(
+            evaluations.len(),
+            n,
+            "number of evaluations must be equal to the domain size {n}, but was {}",
+            evaluations.len()
+        );
+
+        // split f(x, y) = f0(x) + y * f1(x); f0 and f1 are recovered (up to a factor of 2) from
+        // the values of f at conjugate points (x, y) and (x, -y)
+        let (lo, hi) = evaluations.split_at_mut(n / 2);
+        for ((a, b), &inv_y) in lo.iter_mut().zip(hi.iter_mut()).zip(&self.inv_y_twiddles) {
+            let (u, v) = (*a, *b);
+            *a = u + v;
+            *b = (u - v).mul_base(inv_y);
+        }
+
+        // split f(x) = g0(π(x)) + x * g1(π(x)); g0 and g1 are recovered (up to a factor of 2)
+        // from the values of f at x and -x
+        let mut block_size = n / 2;
+        for inv_xs in self.inv_x_twiddles.iter() {
+            for block in evaluations.chunks_mut(block_size) {
+                let (lo, hi) = block.split_at_mut(block_size / 2);
+                for ((a, b), &inv_x) in lo.iter_mut().zip(hi.iter_mut()).zip(inv_xs) {
+                    let (u, v) = (*a, *b);
+                    *a = u + v;
+                    *b = (u - v).mul_base(inv_x);
+                }
+            }
+            block_size /= 2;
+        }
+
+        // each of the log(n) layers above introduced a factor of 2
+        let inv_size = BaseElement::new(n as u32).inv();
+        evaluations.iter_mut().for_each(|c| *c = (*c).mul_base(inv_size));
+        fft::permute(evaluations);
+    }
+
+    fn evaluate<E: FieldElement<BaseField = BaseElement>>(&self, coefficients: &mut [E]) {
+        let n = self.size();
+        assert_eq!(
+            coefficients.len(),
+            n,
+            "number of coefficients must be equal to the domain size {n}, but was {}",
+            coefficients.len()
+        );
+
+        // this is the inverse of the interpolation procedure, with layers applied in reverse
+        fft::permute(coefficients);
+
+        let mut block_size = 2;
+        for xs in self.x_twiddles.iter().rev() {
+            for block in coefficients.chunks_mut(block_size) {
+                let (lo, hi) = block.split_at_mut(block_size / 2);
+                for ((a, b), &x) in lo.iter_mut().zip(hi.iter_mut()).zip(xs) {
+                    let (u, v) = (*a, (*b).mul_base(x));
+                    *a = u + v;
+                    *b = u - v;
+                }
+            }
+            block_size *= 2;
+        }
+
+        let (lo, hi) = coefficients.split_at_mut(n / 2);
+        for ((a, b), &y) in lo.iter_mut().zip(hi.iter_mut()).zip(&self.y_twiddles) {
+            let (u, v) = (*a, (*b).mul_base(y));
+            *a = u + v;
+            *b = u - v;
+        }
+    }
+}
+
+// POLYNOMIAL EVALUATION
+// ================================================================================================
+
+/// Evaluates a polynomial defined by its `coefficients` in the circle basis at the point
+/// (`x`, `y`).
+///
+/// The point does not need to belong to the base field, and thus, this function can be used to
+/// evaluate polynomials at points outside of circle domains (e.g., at out-of-domain points in
+/// an extension field). The point is assumed to lie on the circle curve.
+///
+/// # Panics
+/// Panics if the number of coefficients is not a power of two.
+pub fn eval_poly<E: FieldElement<BaseField = BaseElement>>(coefficients: &[E], x: E, y: E) -> E {
+    assert!(
+        coefficients.len().is_power_of_two(),
+        "number of coefficients must be a power of two, but was {}",
+        coefficients.len()
+    );
+
+    // the polynomial is folded one variable at a time starting with the most significant bit of
+    // coefficient indexes (i.e., the highest iterate of π(x)); the values of the variables are
+    // y, x, π(x), π(π(x)), ...
+    let num_vars = coefficients.len().trailing_zeros() as usize;
+    let mut vars = Vec::with_capacity(num_vars);
+    if num_vars > 0 {
+        vars.push(y);
+        let mut x = x;
+        for _ in 1..num_vars {
+            vars.push(x);
+            x = x.square().double() - E::ONE;
+        }
+    }
+
+    let mut values = coefficients.to_vec();
+    for &var in vars.iter().rev() {
+        let half = values.len() / 2;
+        let (lo, hi) = values.split_at_mut(half);
+        lo.iter_mut().zip(hi.iter()).for_each(|(a, &b)| *a += b * var);
+        values.truncate(half);
+    }
+    values[0]
+}
@@ -0,0 +1,130 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use rand_utils::{rand_value, rand_vector};
+
+use super::{eval_poly, CircleDomain, CirclePoint};
+use crate::{
+    fft::FftDomain,
+    fields::{m31::BaseElement, QuartExtension},
+    FieldElement, StarkField,
+};
+
+// CIRCLE POINT
+// ================================================================================================
+
+#[test]
+fn circle_generator() {
+    let g = CirclePoint::GENERATOR;
+    assert_eq!(CirclePoint::IDENTITY, g.mul_scalar(1 << 31));
+    assert_eq!(CirclePoint::new(-BaseElement::ONE, BaseElement::ZERO), g.mul_scalar(1 << 30));
+
+    for log_order in 0..=CirclePoint::LOG_ORDER {
+        let h = CirclePoint::subgroup_generator(log_order);
+        assert_eq!(CirclePoint::IDENTITY, h.mul_scalar(1 << log_order));
+        if log_order > 0 {
+            assert_ne!(CirclePoint::IDENTITY, h.mul_scalar(1 << (log_order - 1)));
+        }
+    }
+}
+
+#[test]
+fn circle_group_law() {
+    let g = CirclePoint::GENERATOR;
+    let a = g.mul_scalar(rand_value::<u64>());
+    let b = g.mul_scalar(rand_value::<u64>());
+
+    // results of group operations lie on the circle
+    let c = a + b;
+    assert_eq!(BaseElement::ONE, c.x().square() + c.y().square());
+
+    assert_eq!(a + a, a.double());
+    assert_eq!(CirclePoint::IDENTITY, a - a);
+    assert_eq!(CirclePoint::IDENTITY, a + (-a));
+    assert_eq!(a, c - b);
+    assert_eq!(a.antipode(), a + CirclePoint::subgroup_generator(1));
+    assert_eq!(a.mul_scalar(5), a.double().double() + a);
+}
+
+// CIRCLE DOMAIN
+// ================================================================================================
+
+#[test]
+fn circle_domain_points() {
+    for log_size in 1..8 {
+        let domain = CircleDomain::new(log_size);
+        let points = domain.points();
+        assert_eq!(domain.size(), points.len());
+
+        for (i, point) in points.iter().enumerate() {
+            assert_eq!(*point, domain.get_point(i));
+            assert_eq!(BaseElement::ONE, point.x().square() + point.y().square());
+        }
+
+        // all points are distinct
+        let mut xy = points.iter().map(|p| (p.x().as_int(), p.y().as_int())).collect::<Vec<_>>();
+        xy.sort();
+        xy.dedup();
+        assert_eq!(domain.size(), xy.len());
+    }
+}
+
+#[test]
+fn circle_fft_interpolate_evaluate() {
+    for log_size in 1..8 {
+        let domain = CircleDomain::new(log_size);
+        let evaluations: Vec<BaseElement> = rand_vector(domain.size());
+
+        let mut coefficients = evaluations.clone();
+        domain.interpolate(&mut coefficients);
+
+        // interpolated polynomial must evaluate to the original values at domain points
+        for (point, &value) in domain.points().iter().zip(evaluations.iter()) {
+            assert_eq!(value, eval_poly(&coefficients, point.x(), point.y()));
+        }
+
+        let mut result = coefficients.clone();
+        domain.evaluate(&mut result);
+        assert_eq!(evaluations, result);
+    }
+}
+
+#[test]
+fn circle_fft_in_quartic_extension() {
+    let domain = CircleDomain::new(6);
+    let evaluations: Vec<QuartExtension<BaseElement>> = rand_vector(domain.size());
+
+    let mut result = evaluations.clone();
+    domain.interpolate(&mut result);
+    domain.evaluate(&mut result);
+    assert_eq!(evaluations, result);
+}
+
+#[test]
+fn circle_fft_extend() {
+    let source = CircleDomain::new(4);
+    let target = CircleDomain::new(7);
+    let evaluations: Vec<BaseElement> = rand_vector(source.size());
+
+    let mut coefficients = evaluations.clone();
+    source.interpolate(&mut coefficients);
+
+    let extended = source.extend(&evaluations, &target);
+    assert_eq!(target.size(), extended.len());
+    for (point, &value) in target.points().iter().zip(extended.iter()) {
+        assert_eq!(value, eval_poly(&coefficients, point.x(), point.y()));
+    }
+
+    // extending onto the same domain is an identity
+    assert_eq!(evaluations, source.extend(&evaluations, &source));
+}
+
+#[test]
+#[should_panic]
+fn circle_domain_too_small() {
+    CircleDomain::new(0);
+}
@@ -87,7 +87,7 @@ where
 
     let domain_offset = E::inv(domain_offset.into());
     let inv_len = E::inv((values.len() as u32).into());
-    let batch_size = values.len() / rayon::current_num_threads().next_power_of_two();
+    let batch_size = values.len() / get_num_batches(values.len());
 
     values.par_chunks_mut(batch_size).enumerate().for_each(|(i, batch)| {
         let mut offset = domain_offset.exp(((i * batch_size) as u64).into()) * inv_len;
@@ -103,7 +103,7 @@ where
 
 pub fn permute<E: FieldElement>(v: &mut [E]) {
     let n = v.len();
-    let num_batches = rayon::current_num_threads().next_power_of_two();
+    let num_batches = get_num_batches(n);
     let batch_size = n / num_batches;
     rayon::scope(|s| {
         for batch_idx in 0..num_batches {
@@ -221,7 +221,7 @@ fn transpose_square_2<T>(matrix: &mut [T], size: usize) {
 // ================================================================================================
 
 fn clone_and_shift<E: FieldElement>(source: &[E], destination: &mut [E], offset: E::BaseField) {
-    let batch_size = source.len() / rayon::current_num_threads().next_power_of_two();
+    let batch_size = source.len() / get_num_batches(source.len());
     source
         .par_chunks(batch_size)
         .zip(destination.par_chunks_mut(batch_size))
@@ -234,3 +234,10 @@ fn clone_and_shift<E: FieldElement>(source: &[E], destination: &mut [E], offset:
             }
         });
 }
+
+/// Returns the number of batches into which work over `n` elements should be split; this is the
+/// number of threads rounded up to the next power of two, but never more than `n`. `n` is
+/// assumed to be a power of two.
+fn get_num_batches(n: usize) -> usize {
+    core::cmp::min(rayon::current_num_threads().next_power_of_two(), n)
+}
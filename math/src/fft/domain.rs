@@ -0,0 +1,305 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crate::{
+    field::{FieldElement, StarkField},
+    utils::{get_power_series, get_power_series_with_offset},
+};
+
+// FFT DOMAIN
+// ================================================================================================
+
+/// A domain over which polynomials can be evaluated and interpolated in O(`n` log `n`) time.
+///
+/// This trait abstracts over the structure of the domain, and thus, allows the same low-degree
+/// extension code to work with different kinds of domains. Currently, the trait is implemented
+/// for:
+/// * [EvaluationDomain] - a multiplicative coset of a two-adic subgroup of the base field.
+/// * [CircleDomain](crate::circle::CircleDomain) - a coset of a subgroup of the circle curve
+///   over the Mersenne-31 field.
+///
+/// The order of coefficients produced by [FftDomain::interpolate()] is specific to each domain;
+/// however, all implementations must guarantee that the coefficients of a polynomial
+/// interpolated over a domain of size `n` are also coefficients of the same polynomial over a
+/// larger domain of the same type when padded with zeros to the size of the larger domain. This
+/// property is what makes [FftDomain::extend()] work.
+pub trait FftDomain<B: StarkField> {
+    /// Returns the number of points in this domain.
+    fn size(&self) -> usize;
+
+    /// Interpolates evaluations of a polynomial over this domain into the coefficients of the
+    /// polynomial; the interpolation is done in place.
+    ///
+    /// # Panics
+    /// Panics if the length of `evaluations` is not equal to the size of this domain.
+    fn interpolate<E: FieldElement<BaseField = B>>(&self, evaluations: &mut [E]);
+
+    /// Evaluates a polynomial defined by its coefficients over this domain; the evaluation is
+    /// done in place.
+    ///
+    /// # Panics
+    /// Panics if the length of `coefficients` is not equal to the size of this domain.
+    fn evaluate<E: FieldElement<BaseField = B>>(&self, coefficients: &mut [E]);
+
+    /// Returns evaluations of a polynomial over the `target` domain given its evaluations over
+    /// this domain.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The length of `evaluations` is not equal to the size of this domain.
+    /// * The `target` domain is smaller than this domain.
+    fn extend<E: FieldElement<BaseField = B>>(&self, evaluations: &[E], target: &Self) -> Vec<E> {
+        assert!(
+            target.size() >= self.size(),
+            "target domain size ({}) cannot be smaller than source domain size ({})",
+            target.size(),
+            self.size()
+        );
+        let mut result = evaluations.to_vec();
+        self.interpolate(&mut result);
+        result.resize(target.size(), E::ZERO);
+        target.evaluate(&mut result);
+        result
+    }
+}
+
+// EVALUATION DOMAIN
+// ================================================================================================
+
+/// A multiplicative coset of a two-adic subgroup of a STARK field.
+///
+/// The domain is defined by its size (which must be a power of two) and its offset; the points
+/// of the domain are offset * g<sup>i</sup> for i in 0..size, where g is the generator of the
+/// subgroup of the specified size. A domain with offset equal to one is the subgroup itself.
+///
+/// The generator of the domain, its inverse, and the inverse of the offset are computed when
+/// the domain is instantiated. Twiddles, inverse twiddles, and powers of the offset require
+/// memory proportional to the size of the domain, and thus, are computed only when requested
+/// via [EvaluationDomain::with_twiddles()], [EvaluationDomain::with_inv_twiddles()], and
+/// [EvaluationDomain::with_offset_powers()] respectively. Evaluation and interpolation over a
+/// domain which does not cache these values builds them on every invocation.
+///
+/// Polynomial coefficients are in the natural order (i.e., from the lowest degree to the highest
+/// degree).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvaluationDomain<B: StarkField> {
+    size: usize,
+    generator: B,
+    inv_generator: B,
+    offset: B,
+    inv_offset: B,
+    twiddles: Vec<B>,
+    inv_twiddles: Vec<B>,
+    offset_powers: Vec<B>,
+    inv_offset_powers: Vec<B>,
+}
+
+impl<B: StarkField> EvaluationDomain<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new coset of the subgroup of the specified size shifted by the specified offset.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `size` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of size `size`.
+    /// * `offset` is zero.
+    pub fn new(size: usize, offset: B) -> Self {
+        assert!(size.is_power_of_two(), "domain size must be a power of 2, but was {size}");
+        assert!(
+            size.ilog2() <= B::TWO_ADICITY,
+            "multiplicative subgroup of size {size} does not exist in the specified base field"
+        );
+        assert_ne!(offset, B::ZERO, "domain offset cannot be zero");
+
+        // the subgroup of size one consists of the identity only
+        let generator = if size == 1 { B::ONE } else { B::get_root_of_unity(size.ilog2()) };
+        Self {
+            size,
+            generator,
+            inv_generator: generator.inv(),
+            offset,
+            inv_offset: offset.inv(),
+            twiddles: Vec::new(),
+            inv_twiddles: Vec::new(),
+            offset_powers: Vec::new(),
+            inv_offset_powers: Vec::new(),
+        }
+    }
+
+    /// Returns a new coset of a subgroup shifted by the specified offset using the provided
+    /// twiddles; the size of the subgroup is twice the number of twiddles.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of twiddles is zero or is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of the implied size.
+    /// * `offset` is zero.
+    pub fn from_twiddles(twiddles: Vec<B>, offset: B) -> Self {
+        assert!(!twiddles.is_empty(), "twiddles cannot be empty");
+        let mut domain = Self::new(twiddles.len() * 2, offset);
+        domain.twiddles = twiddles;
+        domain
+    }
+
+    /// Builds and caches twiddles needed to evaluate polynomials over this domain.
+    pub fn with_twiddles(mut self) -> Self {
+        if self.size > 1 && self.twiddles.is_empty() {
+            self.twiddles = super::get_twiddles(self.size);
+        }
+        self
+    }
+
+    /// Builds and caches inverse twiddles needed to interpolate polynomials over this domain.
+    pub fn with_inv_twiddles(mut self) -> Self {
+        if self.size > 1 && self.inv_twiddles.is_empty() {
+            self.inv_twiddles = super::get_inv_twiddles(self.size);
+        }
+        self
+    }
+
+    /// Builds and caches powers of the domain offset and of its inverse needed to shift
+    /// polynomials onto this domain. This has no effect if the domain offset is one.
+    pub fn with_offset_powers(mut self) -> Self {
+        if self.offset != B::ONE && self.offset_powers.is_empty() {
+            self.offset_powers = get_power_series(self.offset, self.size);
+            self.inv_offset_powers = get_power_series(self.inv_offset, self.size);
+        }
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the generator of the subgroup underlying this domain.
+    pub fn generator(&self) -> B {
+        self.generator
+    }
+
+    /// Returns the inverse of the generator of the subgroup underlying this domain.
+    pub fn inv_generator(&self) -> B {
+        self.inv_generator
+    }
+
+    /// Returns the offset of this domain.
+    pub fn offset(&self) -> B {
+        self.offset
+    }
+
+    /// Returns the inverse of the offset of this domain.
+    pub fn inv_offset(&self) -> B {
+        self.inv_offset
+    }
+
+    /// Returns twiddles needed to evaluate polynomials over this domain.
+    ///
+    /// # Panics
+    /// Panics if twiddles have not been built for this domain.
+    pub fn twiddles(&self) -> &[B] {
+        assert!(!self.twiddles.is_empty(), "twiddles have not been built for this domain");
+        &self.twiddles
+    }
+
+    /// Returns inverse twiddles needed to interpolate polynomials over this domain.
+    ///
+    /// # Panics
+    /// Panics if inverse twiddles have not been built for this domain.
+    pub fn inv_twiddles(&self) -> &[B] {
+        assert!(
+            !self.inv_twiddles.is_empty(),
+            "inverse twiddles have not been built for this domain"
+        );
+        &self.inv_twiddles
+    }
+
+    /// Returns the element of this domain at the specified index; this is computed as
+    /// offset * g<sup>index</sup>, where g is the generator of the underlying subgroup.
+    pub fn get_x_at(&self, index: usize) -> B {
+        self.offset * self.generator.exp_vartime((index as u64).into())
+    }
+
+    /// Returns all elements of this domain in the natural order.
+    pub fn elements(&self) -> Vec<B> {
+        get_power_series_with_offset(self.generator, self.offset, self.size)
+    }
+}
+
+impl<B: StarkField> FftDomain<B> for EvaluationDomain<B> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn interpolate<E: FieldElement<BaseField = B>>(&self, evaluations: &mut [E]) {
+        assert_eq!(
+            evaluations.len(),
+            self.size,
+            "number of evaluations must be equal to the domain size {}, but was {}",
+            self.size,
+            evaluations.len()
+        );
+        if self.size == 1 {
+            return;
+        }
+
+        if self.inv_twiddles.is_empty() {
+            super::interpolate_poly(evaluations, &super::get_inv_twiddles::<B>(self.size));
+        } else {
+            super::interpolate_poly(evaluations, &self.inv_twiddles);
+        }
+
+        // if p(offset * x) = q(x), then the coefficients of p are c_i * offset^(-i), where c_i
+        // are the coefficients of q
+        if self.offset != B::ONE {
+            if self.inv_offset_powers.is_empty() {
+                shift(evaluations, &get_power_series(self.inv_offset, self.size));
+            } else {
+                shift(evaluations, &self.inv_offset_powers);
+            }
+        }
+    }
+
+    fn evaluate<E: FieldElement<BaseField = B>>(&self, coefficients: &mut [E]) {
+        assert_eq!(
+            coefficients.len(),
+            self.size,
+            "number of coefficients must be equal to the domain size {}, but was {}",
+            self.size,
+            coefficients.len()
+        );
+        if self.size == 1 {
+            return;
+        }
+
+        // p(offset * x) = sum(c_i * offset^i * x^i), and thus, evaluating p over the coset is
+        // the same as evaluating the polynomial with scaled coefficients over the subgroup
+        if self.offset != B::ONE {
+            if self.offset_powers.is_empty() {
+                shift(coefficients, &get_power_series(self.offset, self.size));
+            } else {
+                shift(coefficients, &self.offset_powers);
+            }
+        }
+
+        if self.twiddles.is_empty() {
+            super::evaluate_poly(coefficients, &super::get_twiddles::<B>(self.size));
+        } else {
+            super::evaluate_poly(coefficients, &self.twiddles);
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Multiplies each coefficient of a polynomial by the corresponding power of the domain offset.
+fn shift<B: StarkField, E: FieldElement<BaseField = B>>(coefficients: &mut [E], powers: &[B]) {
+    coefficients
+        .iter_mut()
+        .zip(powers)
+        .for_each(|(c, &power)| *c = (*c).mul_base(power));
+}
@@ -136,10 +136,7 @@ impl<E: FieldElement> FftInputs<E> for [E] {
     }
 
     fn shift_by(&mut self, offset: E::BaseField) {
-        let offset = E::from(offset);
-        for d in self.iter_mut() {
-            *d *= offset;
-        }
+        E::mul_base_slice(self, offset);
     }
 }
 
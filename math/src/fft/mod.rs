@@ -12,6 +12,7 @@
 //! `n` is the domain size.
 
 use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::{
     fft::fft_inputs::FftInputs,
@@ -23,6 +24,12 @@ pub mod fft_inputs;
 pub mod real_u64;
 mod serial;
 
+mod domain;
+pub use domain::{EvaluationDomain, FftDomain};
+
+mod twiddle_cache;
+pub use twiddle_cache::TwiddleCache;
+
 #[cfg(feature = "concurrent")]
 mod concurrent;
 
@@ -31,7 +38,50 @@ mod tests;
 
 // CONSTANTS
 // ================================================================================================
-const MIN_CONCURRENT_SIZE: usize = 1024;
+
+/// Default minimum domain size at which FFT operations are executed in multiple threads.
+pub const DEFAULT_MIN_CONCURRENT_SIZE: usize = 1024;
+
+/// Smallest value which can be used as the minimum concurrent domain size; the concurrent FFT
+/// splits the domain into at least a 2 x 2 matrix and cannot handle smaller domains.
+const MIN_CONCURRENT_SIZE_LOWER_BOUND: usize = 4;
+
+static MIN_CONCURRENT_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_MIN_CONCURRENT_SIZE);
+
+// CONCURRENCY THRESHOLD
+// ================================================================================================
+
+/// Sets the minimum domain size at which FFT operations are executed in multiple threads.
+///
+/// Domains smaller than `size` are always processed in a single thread, as the overhead of
+/// splitting work between threads outweighs the benefits for small inputs. The optimal value
+/// depends on the field, the number of available cores, and the cache sizes of the target
+/// machine. The default is [DEFAULT_MIN_CONCURRENT_SIZE].
+///
+/// The threshold is global and affects all subsequent FFT operations. It has no effect unless
+/// the `concurrent` feature is enabled.
+///
+/// # Panics
+/// Panics if `size` is smaller than 4.
+pub fn set_min_concurrent_size(size: usize) {
+    assert!(
+        size >= MIN_CONCURRENT_SIZE_LOWER_BOUND,
+        "minimum concurrent size cannot be smaller than 4, but was {size}"
+    );
+    MIN_CONCURRENT_SIZE.store(size, Ordering::Relaxed);
+}
+
+/// Returns the minimum domain size at which FFT operations are executed in multiple threads.
+pub fn min_concurrent_size() -> usize {
+    MIN_CONCURRENT_SIZE.load(Ordering::Relaxed)
+}
+
+/// Returns true if an FFT operation over a domain of the specified size should be executed in
+/// multiple threads.
+#[inline(always)]
+fn use_concurrent(size: usize) -> bool {
+    cfg!(feature = "concurrent") && size >= min_concurrent_size()
+}
 
 // POLYNOMIAL EVALUATION
 // ================================================================================================
@@ -103,7 +153,7 @@ where
 
     // when `concurrent` feature is enabled, run the concurrent version of the function; unless
     // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
+    if use_concurrent(p.len()) {
         #[cfg(feature = "concurrent")]
         concurrent::evaluate_poly(p, twiddles);
     } else {
@@ -197,7 +247,7 @@ where
 
     // when `concurrent` feature is enabled, run the concurrent version of the function; unless
     // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
+    if use_concurrent(p.len()) {
         #[cfg(feature = "concurrent")]
         {
             result =
@@ -287,7 +337,7 @@ where
 
     // when `concurrent` feature is enabled, run the concurrent version of interpolate_poly;
     // unless the number of evaluations is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && evaluations.len() >= MIN_CONCURRENT_SIZE {
+    if use_concurrent(evaluations.len()) {
         #[cfg(feature = "concurrent")]
         concurrent::interpolate_poly(evaluations, inv_twiddles);
     } else {
@@ -379,7 +429,7 @@ pub fn interpolate_poly_with_offset<B, E>(
 
     // when `concurrent` feature is enabled, run the concurrent version of the function; unless
     // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && evaluations.len() >= MIN_CONCURRENT_SIZE {
+    if use_concurrent(evaluations.len()) {
         #[cfg(feature = "concurrent")]
         concurrent::interpolate_poly_with_offset(evaluations, inv_twiddles, domain_offset);
     } else {
@@ -584,8 +634,8 @@ pub fn permute_index(size: usize, index: usize) -> usize {
     index.reverse_bits().wrapping_shr(USIZE_BITS - bits)
 }
 
-fn permute<E: FieldElement>(v: &mut [E]) {
-    if cfg!(feature = "concurrent") && v.len() >= MIN_CONCURRENT_SIZE {
+pub(crate) fn permute<E: FieldElement>(v: &mut [E]) {
+    if use_concurrent(v.len()) {
         #[cfg(feature = "concurrent")]
         concurrent::permute(v);
     } else {
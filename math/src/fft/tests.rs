@@ -8,8 +8,8 @@ use alloc::vec::Vec;
 use rand_utils::rand_vector;
 
 use crate::{
-    fft::fft_inputs::FftInputs,
-    field::{f128::BaseElement, StarkField},
+    fft::{fft_inputs::FftInputs, FftDomain},
+    field::{f128::BaseElement, FieldElement, StarkField},
     polynom,
     utils::get_power_series,
 };
@@ -62,7 +62,7 @@ fn fft_in_place() {
 
 #[test]
 fn fft_get_twiddles() {
-    let n = super::MIN_CONCURRENT_SIZE * 2;
+    let n = super::DEFAULT_MIN_CONCURRENT_SIZE * 2;
     let g = BaseElement::get_root_of_unity(n.ilog2());
 
     let mut expected = get_power_series(g, n / 2);
@@ -72,6 +72,138 @@ fn fft_get_twiddles() {
     assert_eq!(expected, twiddles);
 }
 
+#[test]
+fn fft_twiddle_cache() {
+    let mut cache = super::TwiddleCache::<BaseElement>::new();
+    assert_eq!(None, cache.get_twiddles(64));
+    assert_eq!(None, cache.get_inv_twiddles(64));
+
+    assert_eq!(super::get_twiddles::<BaseElement>(64), cache.twiddles(64));
+    assert_eq!(super::get_inv_twiddles::<BaseElement>(64), cache.inv_twiddles(64));
+    assert_eq!(Some(super::get_twiddles::<BaseElement>(64).as_slice()), cache.get_twiddles(64));
+    assert_eq!(None, cache.get_twiddles(128));
+
+    cache.insert_twiddles(super::get_twiddles::<BaseElement>(128));
+    assert_eq!(
+        Some(super::get_twiddles::<BaseElement>(128).as_slice()),
+        cache.get_twiddles(128)
+    );
+
+    cache.clear();
+    assert_eq!(None, cache.get_twiddles(64));
+    assert_eq!(None, cache.get_inv_twiddles(64));
+}
+
+// EVALUATION DOMAINS
+// ================================================================================================
+
+#[test]
+fn fft_evaluation_domain_extend() {
+    let n = 16;
+    let source = super::EvaluationDomain::new(n, BaseElement::ONE);
+    let target = super::EvaluationDomain::new(n * 4, BaseElement::GENERATOR);
+    assert_eq!(n, source.size());
+    assert_eq!(n * 4, target.size());
+
+    let p: Vec<BaseElement> = rand_vector(n);
+    let mut evaluations = p.clone();
+    source.evaluate(&mut evaluations);
+    assert_eq!(polynom::eval_many(&p, &build_domain(n)), evaluations);
+
+    // extending evaluations must be the same as evaluating the polynomial over the target domain
+    let twiddles = super::get_twiddles::<BaseElement>(n);
+    let expected = super::evaluate_poly_with_offset(&p, &twiddles, BaseElement::GENERATOR, 4);
+    assert_eq!(expected, source.extend(&evaluations, &target));
+
+    let mut coefficients = expected;
+    target.interpolate(&mut coefficients);
+    assert_eq!(p, coefficients[..n]);
+    assert!(coefficients[n..].iter().all(|&c| c == BaseElement::ZERO));
+}
+
+#[test]
+fn fft_evaluation_domain_elements() {
+    let n = 32;
+    let offset = BaseElement::GENERATOR;
+    let domain = super::EvaluationDomain::new(n, offset);
+    assert_eq!(BaseElement::get_root_of_unity(n.ilog2()), domain.generator());
+    assert_eq!(BaseElement::ONE, domain.generator() * domain.inv_generator());
+    assert_eq!(BaseElement::ONE, domain.offset() * domain.inv_offset());
+
+    let expected = build_domain(n).into_iter().map(|x| x * offset).collect::<Vec<_>>();
+    assert_eq!(expected, domain.elements());
+    for (i, &x) in expected.iter().enumerate() {
+        assert_eq!(x, domain.get_x_at(i));
+    }
+}
+
+#[test]
+fn fft_evaluation_domain_cached() {
+    let n = 64;
+    let p: Vec<BaseElement> = rand_vector(n);
+    let domain = super::EvaluationDomain::new(n, BaseElement::GENERATOR);
+    let cached = domain.clone().with_twiddles().with_inv_twiddles().with_offset_powers();
+    assert_eq!(super::get_twiddles::<BaseElement>(n), cached.twiddles());
+    assert_eq!(super::get_inv_twiddles::<BaseElement>(n), cached.inv_twiddles());
+
+    // results must not depend on whether twiddles and offset powers are cached or not
+    let mut expected = p.clone();
+    domain.evaluate(&mut expected);
+    assert_eq!(polynom::eval_many(&p, &domain.elements()), expected);
+
+    let mut actual = p.clone();
+    cached.evaluate(&mut actual);
+    assert_eq!(expected, actual);
+
+    domain.interpolate(&mut expected);
+    cached.interpolate(&mut actual);
+    assert_eq!(p, expected);
+    assert_eq!(p, actual);
+}
+
+#[test]
+#[should_panic]
+fn fft_evaluation_domain_twiddles_not_built() {
+    super::EvaluationDomain::new(16, BaseElement::ONE).twiddles();
+}
+
+// CONCURRENCY THRESHOLD
+// ================================================================================================
+
+#[test]
+fn fft_min_concurrent_size() {
+    // results must not depend on whether small domains are processed concurrently or not
+    let n = 64;
+    let offset = BaseElement::GENERATOR;
+    let p: Vec<BaseElement> = rand_vector(n);
+    let twiddles = super::get_twiddles::<BaseElement>(n);
+    let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+
+    let mut expected = p.clone();
+    super::evaluate_poly(&mut expected, &twiddles);
+    let expected_shifted = super::evaluate_poly_with_offset(&p, &twiddles, offset, 4);
+
+    super::set_min_concurrent_size(4);
+    assert_eq!(4, super::min_concurrent_size());
+
+    let mut actual = p.clone();
+    super::evaluate_poly(&mut actual, &twiddles);
+    assert_eq!(expected, actual);
+    assert_eq!(expected_shifted, super::evaluate_poly_with_offset(&p, &twiddles, offset, 4));
+
+    super::interpolate_poly(&mut actual, &inv_twiddles);
+    assert_eq!(p, actual);
+
+    super::set_min_concurrent_size(super::DEFAULT_MIN_CONCURRENT_SIZE);
+    assert_eq!(super::DEFAULT_MIN_CONCURRENT_SIZE, super::min_concurrent_size());
+}
+
+#[test]
+#[should_panic]
+fn fft_min_concurrent_size_too_small() {
+    super::set_min_concurrent_size(2);
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -0,0 +1,104 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::field::StarkField;
+
+// TWIDDLE CACHE
+// ================================================================================================
+
+/// A cache of twiddles and inverse twiddles keyed by domain size.
+///
+/// Building twiddles for a domain of size `n` requires computing `n` / 2 powers of a root of
+/// unity. When many polynomials are evaluated or interpolated over domains of the same size
+/// (e.g., when computing low-degree extensions of all columns of an execution trace), this
+/// cache can be used to make sure the twiddles for each domain size are built only once.
+///
+/// # Examples
+/// ```
+/// # use winter_math::fft::*;
+/// # use winter_math::fields::f128::BaseElement;
+/// let mut cache = TwiddleCache::<BaseElement>::new();
+///
+/// // twiddles are built on first access and are re-used afterwards
+/// assert_eq!(get_twiddles::<BaseElement>(1024), cache.twiddles(1024));
+/// assert_eq!(get_inv_twiddles::<BaseElement>(1024), cache.inv_twiddles(1024));
+///
+/// assert_eq!(Some(get_twiddles::<BaseElement>(1024).as_slice()), cache.get_twiddles(1024));
+/// assert_eq!(None, cache.get_twiddles(2048));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TwiddleCache<B: StarkField> {
+    twiddles: BTreeMap<usize, Vec<B>>,
+    inv_twiddles: BTreeMap<usize, Vec<B>>,
+}
+
+impl<B: StarkField> TwiddleCache<B> {
+    /// Returns a new empty twiddle cache.
+    pub fn new() -> Self {
+        Self {
+            twiddles: BTreeMap::new(),
+            inv_twiddles: BTreeMap::new(),
+        }
+    }
+
+    // TWIDDLES
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns twiddles for the specified domain size, building and caching them if they are not
+    /// in the cache yet.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of size `domain_size`.
+    pub fn twiddles(&mut self, domain_size: usize) -> &[B] {
+        self.twiddles
+            .entry(domain_size)
+            .or_insert_with(|| super::get_twiddles(domain_size))
+    }
+
+    /// Returns inverse twiddles for the specified domain size, building and caching them if they
+    /// are not in the cache yet.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of size `domain_size`.
+    pub fn inv_twiddles(&mut self, domain_size: usize) -> &[B] {
+        self.inv_twiddles
+            .entry(domain_size)
+            .or_insert_with(|| super::get_inv_twiddles(domain_size))
+    }
+
+    // CACHE LOOKUPS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns twiddles for the specified domain size if they have already been cached.
+    pub fn get_twiddles(&self, domain_size: usize) -> Option<&[B]> {
+        self.twiddles.get(&domain_size).map(|twiddles| twiddles.as_slice())
+    }
+
+    /// Returns inverse twiddles for the specified domain size if they have already been cached.
+    pub fn get_inv_twiddles(&self, domain_size: usize) -> Option<&[B]> {
+        self.inv_twiddles.get(&domain_size).map(|twiddles| twiddles.as_slice())
+    }
+
+    /// Adds the provided twiddles to the cache.
+    ///
+    /// # Panics
+    /// Panics if the length of `twiddles` is not a power of two.
+    pub fn insert_twiddles(&mut self, twiddles: Vec<B>) {
+        assert!(twiddles.len().is_power_of_two(), "number of twiddles must be a power of 2");
+        self.twiddles.insert(twiddles.len() * 2, twiddles);
+    }
+
+    /// Removes all twiddles from the cache.
+    pub fn clear(&mut self) {
+        self.twiddles.clear();
+        self.inv_twiddles.clear();
+    }
+}
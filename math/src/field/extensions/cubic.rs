@@ -17,7 +17,7 @@ use utils::{
     Serializable, SliceReader,
 };
 
-use super::{ExtensibleField, ExtensionOf, FieldElement};
+use super::{base_elements_mut, ExtensibleField, ExtensionOf, FieldElement};
 
 // QUADRATIC EXTENSION FIELD
 // ================================================================================================
@@ -28,7 +28,8 @@ use super::{ExtensibleField, ExtensionOf, FieldElement};
 /// polynomial defined by the implementation of the [ExtensibleField] trait, and α, β, γ are base
 /// field elements.
 #[repr(C)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Copy, Clone, Debug, Eq, Default)]
+#[cfg_attr(not(feature = "constant-time"), derive(PartialEq))]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CubeExtension<B: ExtensibleField<3>>(B, B, B);
 
@@ -102,6 +103,24 @@ impl<B: ExtensibleField<3>> FieldElement for CubeExtension<B> {
         Self(result[0], result[1], result[2])
     }
 
+    // BATCH ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    fn add_slices(a: &mut [Self], b: &[Self]) {
+        // addition in the extension field is performed component-wise, and thus, it can be
+        // delegated to the base field
+        assert_eq!(a.len(), b.len(), "number of values must be the same for both slices");
+        // SAFETY: CubeExtension is a `#[repr(C)]` struct consisting of 3 base field elements
+        let a = unsafe { base_elements_mut(a) };
+        B::add_slices(a, Self::slice_as_base_elements(b));
+    }
+
+    fn mul_base_slice(a: &mut [Self], b: B) {
+        // SAFETY: CubeExtension is a `#[repr(C)]` struct consisting of 3 base field elements
+        let a = unsafe { base_elements_mut(a) };
+        B::mul_base_slice(a, b);
+    }
+
     // BASE ELEMENT CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
@@ -188,6 +207,18 @@ impl<B: ExtensibleField<3>> fmt::Display for CubeExtension<B> {
     }
 }
 
+// EQUALITY CHECKS
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "constant-time")]
+impl<B: ExtensibleField<3>> PartialEq for CubeExtension<B> {
+    /// Compares all base elements of the extension elements; unlike the derived implementation,
+    /// this does not short-circuit on the first mismatch.
+    fn eq(&self, other: &Self) -> bool {
+        (self.0 == other.0) & (self.1 == other.1) & (self.2 == other.2)
+    }
+}
+
 // OVERLOADED OPERATORS
 // ------------------------------------------------------------------------------------------------
 
@@ -320,7 +351,7 @@ impl<B: ExtensibleField<3>> TryFrom<u128> for CubeExtension<B> {
     }
 }
 
-impl<'a, B: ExtensibleField<3>> TryFrom<&'a [u8]> for CubeExtension<B> {
+impl<B: ExtensibleField<3>> TryFrom<&[u8]> for CubeExtension<B> {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
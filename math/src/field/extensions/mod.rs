@@ -9,4 +9,25 @@ pub use quadratic::QuadExtension;
 mod cubic;
 pub use cubic::CubeExtension;
 
+mod quartic;
+pub use quartic::QuartExtension;
+
 use super::{ExtensibleField, ExtensionOf, FieldElement};
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Re-interprets a mutable slice of extension field elements as a mutable slice of the underlying
+/// base field elements.
+///
+/// # Safety
+/// `E` must be a `#[repr(C)]` struct consisting of exactly `E::EXTENSION_DEGREE` base field
+/// elements (and nothing else). Otherwise, the returned slice would cover memory which does not
+/// hold valid base field elements.
+unsafe fn base_elements_mut<E: FieldElement>(elements: &mut [E]) -> &mut [E::BaseField] {
+    let len = elements.len() * E::EXTENSION_DEGREE;
+    // SAFETY: the caller guarantees that the memory of `len / E::EXTENSION_DEGREE` elements of
+    // type `E` is the memory of `len` consecutive base field elements; the returned slice
+    // borrows `elements` mutably, and thus, no aliasing is possible
+    unsafe { core::slice::from_raw_parts_mut(elements.as_mut_ptr() as *mut E::BaseField, len) }
+}
@@ -17,7 +17,7 @@ use utils::{
     Serializable, SliceReader,
 };
 
-use super::{ExtensibleField, ExtensionOf, FieldElement};
+use super::{base_elements_mut, ExtensibleField, ExtensionOf, FieldElement};
 
 // QUADRATIC EXTENSION FIELD
 // ================================================================================================
@@ -28,7 +28,8 @@ use super::{ExtensibleField, ExtensionOf, FieldElement};
 /// defined by the implementation of the [ExtensibleField] trait, and α and β are base field
 /// elements.
 #[repr(C)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Copy, Clone, Debug, Eq, Default)]
+#[cfg_attr(not(feature = "constant-time"), derive(PartialEq))]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct QuadExtension<B: ExtensibleField<2>>(B, B);
 
@@ -99,6 +100,24 @@ impl<B: ExtensibleField<2>> FieldElement for QuadExtension<B> {
         Self(result[0], result[1])
     }
 
+    // BATCH ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    fn add_slices(a: &mut [Self], b: &[Self]) {
+        // addition in the extension field is performed component-wise, and thus, it can be
+        // delegated to the base field
+        assert_eq!(a.len(), b.len(), "number of values must be the same for both slices");
+        // SAFETY: QuadExtension is a `#[repr(C)]` struct consisting of 2 base field elements
+        let a = unsafe { base_elements_mut(a) };
+        B::add_slices(a, Self::slice_as_base_elements(b));
+    }
+
+    fn mul_base_slice(a: &mut [Self], b: B) {
+        // SAFETY: QuadExtension is a `#[repr(C)]` struct consisting of 2 base field elements
+        let a = unsafe { base_elements_mut(a) };
+        B::mul_base_slice(a, b);
+    }
+
     // BASE ELEMENT CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
@@ -184,6 +203,18 @@ impl<B: ExtensibleField<2>> fmt::Display for QuadExtension<B> {
     }
 }
 
+// EQUALITY CHECKS
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "constant-time")]
+impl<B: ExtensibleField<2>> PartialEq for QuadExtension<B> {
+    /// Compares all base elements of the extension elements; unlike the derived implementation,
+    /// this does not short-circuit on the first mismatch.
+    fn eq(&self, other: &Self) -> bool {
+        (self.0 == other.0) & (self.1 == other.1)
+    }
+}
+
 // OVERLOADED OPERATORS
 // ------------------------------------------------------------------------------------------------
 
@@ -315,7 +346,7 @@ impl<B: ExtensibleField<2>> TryFrom<u128> for QuadExtension<B> {
     }
 }
 
-impl<'a, B: ExtensibleField<2>> TryFrom<&'a [u8]> for QuadExtension<B> {
+impl<B: ExtensibleField<2>> TryFrom<&[u8]> for QuadExtension<B> {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -9,6 +9,10 @@
 //! canonical form using `u128` as the backing type. However, this field was not chosen with any
 //! significant thought given to performance, and the implementations of most operations are
 //! sub-optimal as well.
+//!
+//! An alternative implementation of the same field which stores elements in Montgomery form is
+//! available in the [montgomery] module. It offers faster multiplication, and elements of the two
+//! implementations serialize into the same bytes.
 
 use alloc::{
     string::{String, ToString},
@@ -30,6 +34,8 @@ use utils::{
 
 use super::{ExtensibleField, FieldElement, StarkField};
 
+pub mod montgomery;
+
 #[cfg(test)]
 mod tests;
 
@@ -82,10 +88,17 @@ impl FieldElement for BaseElement {
     // ALGEBRA
     // --------------------------------------------------------------------------------------------
 
+    #[cfg(not(feature = "constant-time"))]
     fn inv(self) -> Self {
         BaseElement(inv(self.0))
     }
 
+    #[cfg(feature = "constant-time")]
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^(M - 2) = x^{-1}; for x = 0, this returns 0
+        self.exp(M - 2)
+    }
+
     fn conjugate(&self) -> Self {
         BaseElement(self.0)
     }
@@ -240,7 +253,7 @@ impl Div for BaseElement {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self {
-        Self(mul(self.0, inv(rhs.0)))
+        Self(mul(self.0, rhs.inv().0))
     }
 }
 
@@ -352,7 +365,7 @@ impl TryFrom<u128> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = String;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -403,6 +416,7 @@ impl Deserializable for BaseElement {
 // ================================================================================================
 
 /// Computes (a + b) % m; a and b are assumed to be valid field elements.
+#[cfg(not(feature = "constant-time"))]
 fn add(a: u128, b: u128) -> u128 {
     let z = M - b;
     if a < z {
@@ -412,7 +426,16 @@ fn add(a: u128, b: u128) -> u128 {
     }
 }
 
+/// Computes (a + b) % m; a and b are assumed to be valid field elements.
+#[cfg(feature = "constant-time")]
+fn add(a: u128, b: u128) -> u128 {
+    // we compute a + b = a - (m - b), and add m back if the subtraction underflows
+    let (z, borrow) = a.overflowing_sub(M - b);
+    z.wrapping_add(M & 0u128.wrapping_sub(borrow as u128))
+}
+
 /// Computes (a - b) % m; a and b are assumed to be valid field elements.
+#[cfg(not(feature = "constant-time"))]
 fn sub(a: u128, b: u128) -> u128 {
     if a < b {
         M - b + a
@@ -421,48 +444,47 @@ fn sub(a: u128, b: u128) -> u128 {
     }
 }
 
+/// Computes (a - b) % m; a and b are assumed to be valid field elements.
+#[cfg(feature = "constant-time")]
+fn sub(a: u128, b: u128) -> u128 {
+    let (z, borrow) = a.overflowing_sub(b);
+    z.wrapping_add(M & 0u128.wrapping_sub(borrow as u128))
+}
+
 /// Computes (a * b) % m; a and b are assumed to be valid field elements.
 fn mul(a: u128, b: u128) -> u128 {
     let (x0, x1, x2) = mul_128x64(a, (b >> 64) as u64); // x = a * b_hi
-    let (mut x0, mut x1, x2) = mul_reduce(x0, x1, x2); // x = x - (x >> 128) * m
-    if x2 == 1 {
-        // if there was an overflow beyond 128 bits, subtract
-        // modulus from the result to make sure it fits into
-        // 128 bits; this can potentially be removed in favor
-        // of checking overflow later
-        let (t0, t1) = sub_modulus(x0, x1); // x = x - m
-        x0 = t0;
-        x1 = t1;
-    }
+    let (x0, x1, x2) = mul_reduce(x0, x1, x2); // x = x - (x >> 128) * m
+
+    // if there was an overflow beyond 128 bits, subtract
+    // modulus from the result to make sure it fits into
+    // 128 bits; this can potentially be removed in favor
+    // of checking overflow later
+    let (x0, x1) = sub_modulus_if(x2 == 1, x0, x1); // x = x - m
 
     let (y0, y1, y2) = mul_128x64(a, b as u64); // y = a * b_lo
 
-    let (mut y1, carry) = add64_with_carry(y1, x0, 0); // y = y + (x << 64)
-    let (mut y2, y3) = add64_with_carry(y2, x1, carry);
-    if y3 == 1 {
-        // if there was an overflow beyond 192 bits, subtract
-        // modulus * 2^64 from the result to make sure it fits
-        // into 192 bits; this can potentially replace the
-        // previous overflow check (but needs to be proven)
-        let (t0, t1) = sub_modulus(y1, y2); // y = y - (m << 64)
-        y1 = t0;
-        y2 = t1;
-    }
+    let (y1, carry) = add64_with_carry(y1, x0, 0); // y = y + (x << 64)
+    let (y2, y3) = add64_with_carry(y2, x1, carry);
 
-    let (mut z0, mut z1, z2) = mul_reduce(y0, y1, y2); // z = y - (y >> 128) * m
+    // if there was an overflow beyond 192 bits, subtract
+    // modulus * 2^64 from the result to make sure it fits
+    // into 192 bits; this can potentially replace the
+    // previous overflow check (but needs to be proven)
+    let (y1, y2) = sub_modulus_if(y3 == 1, y1, y2); // y = y - (m << 64)
+
+    let (z0, z1, z2) = mul_reduce(y0, y1, y2); // z = y - (y >> 128) * m
 
     // make sure z is smaller than m
-    if z2 == 1 || (z1 == (M >> 64) as u64 && z0 >= (M as u64)) {
-        let (t0, t1) = sub_modulus(z0, z1); // z = z - m
-        z0 = t0;
-        z1 = t1;
-    }
+    let overflow = (z2 == 1) | ((z1 == (M >> 64) as u64) & (z0 >= (M as u64)));
+    let (z0, z1) = sub_modulus_if(overflow, z0, z1); // z = z - m
 
     ((z1 as u128) << 64) + (z0 as u128)
 }
 
 /// Computes y such that (x * y) % m = 1 except for when when x = 0; in such a case,
 /// 0 is returned; x is assumed to be a valid field element.
+#[cfg(not(feature = "constant-time"))]
 fn inv(x: u128) -> u128 {
     if x == 0 {
         return 0;
@@ -591,6 +613,28 @@ fn sub_modulus(a_lo: u64, a_hi: u64) -> (u64, u64) {
     (z as u64, (z >> 64) as u64)
 }
 
+/// Subtracts the modulus from a if `condition` is true; otherwise, returns a unchanged.
+#[cfg(not(feature = "constant-time"))]
+#[inline]
+fn sub_modulus_if(condition: bool, a_lo: u64, a_hi: u64) -> (u64, u64) {
+    if condition {
+        sub_modulus(a_lo, a_hi)
+    } else {
+        (a_lo, a_hi)
+    }
+}
+
+/// Subtracts the modulus from a if `condition` is true; otherwise, returns a unchanged.
+///
+/// The subtraction is always performed, and the result is selected using a bit mask.
+#[cfg(feature = "constant-time")]
+#[inline]
+fn sub_modulus_if(condition: bool, a_lo: u64, a_hi: u64) -> (u64, u64) {
+    let (t0, t1) = sub_modulus(a_lo, a_hi);
+    let mask = 0u64.wrapping_sub(condition as u64);
+    (a_lo ^ (mask & (a_lo ^ t0)), a_hi ^ (mask & (a_hi ^ t1)))
+}
+
 #[inline]
 fn sub_192x192(a0: u64, a1: u64, a2: u64, b0: u64, b1: u64, b2: u64) -> (u64, u64, u64) {
     let z0 = (a0 as u128).wrapping_sub(b0 as u128);
@@ -599,6 +643,7 @@ fn sub_192x192(a0: u64, a1: u64, a2: u64, b0: u64, b1: u64, b2: u64) -> (u64, u6
     (z0 as u64, z1 as u64, z2 as u64)
 }
 
+#[cfg(not(feature = "constant-time"))]
 #[inline]
 fn add_192x192(a0: u64, a1: u64, a2: u64, b0: u64, b1: u64, b2: u64) -> (u64, u64, u64) {
     let z0 = (a0 as u128) + (b0 as u128);
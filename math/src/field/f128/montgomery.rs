@@ -0,0 +1,538 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! An implementation of the 128-bit STARK-friendly prime field with modulus
+//! $2^{128} - 45 \cdot 2^{40} + 1$ using Montgomery representation.
+//!
+//! This field is the same as the one defined in the parent module, but field elements are stored
+//! in Montgomery form (i.e., as $x \cdot 2^{128} \mod M$). Multiplication then relies on
+//! Montgomery reduction which, thanks to the special form of the modulus, requires only a single
+//! full 128-bit multiplication and two 64x64-bit multiplications on top of the computation of the
+//! 256-bit product. This makes multiplication-heavy operations, such as FFTs and FRI folding,
+//! faster on most targets.
+//!
+//! Elements are converted to and from the canonical representation at serialization boundaries,
+//! and thus, serialized elements of this field are interchangeable with serialized elements of
+//! [f128::BaseElement](super::BaseElement). Conversion between the two types can also be done
+//! directly via [From] implementations.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{Debug, Display, Formatter},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use utils::{
+    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
+    Serializable,
+};
+
+#[cfg(not(feature = "constant-time"))]
+use super::inv;
+use super::{add, sub, ExtensibleField, FieldElement, StarkField, G, M};
+
+// CONSTANTS
+// ================================================================================================
+
+/// M = 2^128 - C; C is used for fast Montgomery reduction.
+const C: u128 = 0x2cffffffffff;
+
+/// -M^{-1} mod 2^128; this is used in Montgomery reduction.
+const M_PRIME: u128 = 0x0afffffff816ffffffffd2ffffffffff;
+
+/// 2^256 mod M; this is used for conversion of elements into Montgomery representation.
+const R2: u128 = 0x7e8ffffffffa60000000001;
+
+/// 2^384 mod M; this is used to convert the result of canonical inversion into Montgomery
+/// representation.
+#[cfg(not(feature = "constant-time"))]
+const R3: u128 = 0xf4ffffffe8450000003eedfffffffe9c;
+
+/// Number of bytes needed to represent field element
+const ELEMENT_BYTES: usize = core::mem::size_of::<u128>();
+
+// FIELD ELEMENT
+// ================================================================================================
+
+/// Represents a base field element using Montgomery representation.
+///
+/// Internal values represent x * R mod M where R = 2^128 mod M and x in [0, M). The backing type
+/// is `u128` and the internal values are always in the range [0, M).
+#[derive(Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u128", into = "u128"))]
+pub struct BaseElement(u128);
+
+impl BaseElement {
+    /// Creates a new field element from the provided `value`; the value is converted into
+    /// Montgomery representation.
+    ///
+    /// If the value is greater than or equal to the field modulus, modular reduction is
+    /// silently performed. This function can also be used to initialize constants.
+    pub const fn new(value: u128) -> Self {
+        let value = if value < M { value } else { value - M };
+        Self(mont_mul(value, R2))
+    }
+
+    /// Returns a new field element from the provided `value`. Assumes that `value` is already
+    /// in canonical Montgomery form.
+    pub const fn from_mont(value: u128) -> Self {
+        Self(value)
+    }
+
+    /// Returns the non-canonical u128 inner value.
+    pub const fn inner(&self) -> u128 {
+        self.0
+    }
+
+    /// Returns canonical integer representation of this field element.
+    #[inline(always)]
+    pub const fn as_int(&self) -> u128 {
+        mont_red(self.0, 0)
+    }
+}
+
+impl FieldElement for BaseElement {
+    type PositiveInteger = u128;
+    type BaseField = Self;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(C);
+
+    const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+
+    const IS_CANONICAL: bool = false;
+
+    // ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[cfg(not(feature = "constant-time"))]
+    fn inv(self) -> Self {
+        // inv() of the internal value x * R yields x^{-1} * R^{-1}; a Montgomery multiplication
+        // by R^3 brings the result back into Montgomery form: x^{-1} * R
+        Self(mont_mul(inv(self.0), R3))
+    }
+
+    #[cfg(feature = "constant-time")]
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^(M - 2) = x^{-1}; for x = 0, this returns 0
+        self.exp(M - 2)
+    }
+
+    fn conjugate(&self) -> Self {
+        Self(self.0)
+    }
+
+    // BASE ELEMENT CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => *self,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        // TODO: take endianness into account
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        if (p as usize) % mem::align_of::<u128>() != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+}
+
+impl StarkField for BaseElement {
+    /// sage: MODULUS = 2^128 - 45 * 2^40 + 1 \
+    /// sage: GF(MODULUS).is_prime_field() \
+    /// True \
+    /// sage: GF(MODULUS).order() \
+    /// 340282366920938463463374557953744961537
+    const MODULUS: Self::PositiveInteger = M;
+    const MODULUS_BITS: u32 = 128;
+
+    /// sage: GF(MODULUS).primitive_element() \
+    /// 3
+    const GENERATOR: Self = Self::new(3);
+
+    /// sage: is_odd((MODULUS - 1) / 2^40) \
+    /// True
+    const TWO_ADICITY: u32 = 40;
+
+    /// sage: k = (MODULUS - 1) / 2^40 \
+    /// sage: GF(MODULUS).primitive_element()^k \
+    /// 23953097886125630542083529559205016746
+    const TWO_ADIC_ROOT_OF_UNITY: Self = Self::new(G);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        Self::MODULUS.to_le_bytes().to_vec()
+    }
+
+    #[inline]
+    fn as_int(&self) -> Self::PositiveInteger {
+        mont_red(self.0, 0)
+    }
+}
+
+impl Randomizable for BaseElement {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl Debug for BaseElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for BaseElement {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_int())
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl Add for BaseElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(add(self.0, rhs.0))
+    }
+}
+
+impl AddAssign for BaseElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for BaseElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(sub(self.0, rhs.0))
+    }
+}
+
+impl SubAssign for BaseElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for BaseElement {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(mont_mul(self.0, rhs.0))
+    }
+}
+
+impl MulAssign for BaseElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for BaseElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for BaseElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for BaseElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(sub(0, self.0))
+    }
+}
+
+// QUADRATIC EXTENSION
+// ================================================================================================
+
+/// Defines a quadratic extension of the base field over an irreducible polynomial x<sup>2</sup> -
+/// x - 1. Thus, an extension element is defined as α + β * φ, where φ is a root of this polynomial,
+/// and α and β are base field elements.
+impl ExtensibleField<2> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
+        let z = a[0] * b[0];
+        [z + a[1] * b[1], (a[0] + a[1]) * (b[0] + b[1]) - z]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 2], b: Self) -> [Self; 2] {
+        [a[0] * b, a[1] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 2]) -> [Self; 2] {
+        [x[0] + x[1], Self::ZERO - x[1]]
+    }
+}
+
+// CUBIC EXTENSION
+// ================================================================================================
+
+/// Cubic extension for this field is not implemented as quadratic extension already provides
+/// sufficient security level.
+impl ExtensibleField<3> for BaseElement {
+    fn mul(_a: [Self; 3], _b: [Self; 3]) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn mul_base(_a: [Self; 3], _b: Self) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn frobenius(_x: [Self; 3]) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    fn is_supported() -> bool {
+        false
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl From<super::BaseElement> for BaseElement {
+    /// Converts an element in canonical representation into Montgomery representation.
+    fn from(value: super::BaseElement) -> Self {
+        Self(mont_mul(value.0, R2))
+    }
+}
+
+impl From<BaseElement> for super::BaseElement {
+    /// Converts an element in Montgomery representation into canonical representation.
+    fn from(value: BaseElement) -> Self {
+        super::BaseElement(value.as_int())
+    }
+}
+
+impl From<u64> for BaseElement {
+    /// Converts a 64-bit value into a field element.
+    fn from(value: u64) -> Self {
+        Self::new(value as u128)
+    }
+}
+
+impl From<u32> for BaseElement {
+    /// Converts a 32-bit value into a field element.
+    fn from(value: u32) -> Self {
+        Self::new(value as u128)
+    }
+}
+
+impl From<u16> for BaseElement {
+    /// Converts a 16-bit value into a field element.
+    fn from(value: u16) -> Self {
+        Self::new(value as u128)
+    }
+}
+
+impl From<u8> for BaseElement {
+    /// Converts an 8-bit value into a field element.
+    fn from(value: u8) -> Self {
+        Self::new(value as u128)
+    }
+}
+
+impl TryFrom<u128> for BaseElement {
+    type Error = String;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        if value >= M {
+            Err(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            ))
+        } else {
+            Ok(Self::new(value))
+        }
+    }
+}
+
+impl From<BaseElement> for u128 {
+    fn from(value: BaseElement) -> Self {
+        value.as_int()
+    }
+}
+
+impl TryFrom<&[u8]> for BaseElement {
+    type Error = String;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to encode the element in canonical
+    /// representation in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let value =
+            bytes.try_into().map(u128::from_le_bytes).map_err(|error| format!("{error}"))?;
+        if value >= M {
+            return Err(format!(
+                "cannot convert bytes into a field element: \
+                value {value} is greater or equal to the field modulus"
+            ));
+        }
+        Ok(Self::new(value))
+    }
+}
+
+impl AsBytes for BaseElement {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const BaseElement = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, BaseElement::ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for BaseElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // convert from Montgomery representation into canonical representation
+        target.write_bytes(&self.as_int().to_le_bytes());
+    }
+}
+
+impl Deserializable for BaseElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value = source.read_u128()?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(Self::new(value))
+    }
+}
+
+// FINITE FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes a * b * R^{-1} mod M; a and b are assumed to be smaller than M.
+#[inline(always)]
+const fn mont_mul(a: u128, b: u128) -> u128 {
+    let (lo, hi) = mul_128x128(a, b);
+    mont_red(lo, hi)
+}
+
+/// Computes (hi * 2^128 + lo) * R^{-1} mod M; hi is assumed to be smaller than M.
+///
+/// For q = lo * M' mod 2^128, the value x + q * M is divisible by 2^128. Since M = 2^128 - C,
+/// q * M = q * 2^128 - q * C, and the low 128 bits of q * C must be equal to lo. Thus, the result
+/// of the reduction is hi + q - ((q * C) >> 128), which is in the range [0, 2M).
+#[inline(always)]
+const fn mont_red(lo: u128, hi: u128) -> u128 {
+    let q = lo.wrapping_mul(M_PRIME);
+
+    // compute (q * C) >> 128; C is smaller than 2^46, and thus, the partial products do not
+    // overflow
+    let q0c = (q as u64 as u128) * C;
+    let q1c = (q >> 64) * C;
+    let qc_hi = (q1c + (q0c >> 64)) >> 64;
+
+    let (r, over) = hi.overflowing_add(q);
+    let (r, under) = r.overflowing_sub(qc_hi);
+    sub_modulus_if((over & !under) | (r >= M), r)
+}
+
+/// Subtracts the modulus from x if `condition` is true; otherwise, returns x unchanged.
+#[cfg(not(feature = "constant-time"))]
+#[inline(always)]
+const fn sub_modulus_if(condition: bool, x: u128) -> u128 {
+    if condition {
+        x.wrapping_sub(M)
+    } else {
+        x
+    }
+}
+
+/// Subtracts the modulus from x if `condition` is true; otherwise, returns x unchanged.
+///
+/// The modulus is masked out rather than subtracted conditionally.
+#[cfg(feature = "constant-time")]
+#[inline(always)]
+const fn sub_modulus_if(condition: bool, x: u128) -> u128 {
+    x.wrapping_sub(M & 0u128.wrapping_sub(condition as u128))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes a full 256-bit product of a and b and returns it as (lo, hi) 128-bit words.
+#[inline(always)]
+const fn mul_128x128(a: u128, b: u128) -> (u128, u128) {
+    let (a0, a1) = (a as u64 as u128, a >> 64);
+    let (b0, b1) = (b as u64 as u128, b >> 64);
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = (p00 >> 64) + (p01 as u64 as u128) + (p10 as u64 as u128);
+    let lo = (p00 as u64 as u128) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (lo, hi)
+}
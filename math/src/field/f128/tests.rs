@@ -7,9 +7,12 @@ use alloc::vec::Vec;
 
 use num_bigint::BigUint;
 use rand_utils::{rand_value, rand_vector};
-use utils::SliceReader;
+use utils::{Deserializable, SliceReader};
 
-use super::{AsBytes, BaseElement, ByteReader, DeserializationError, FieldElement, StarkField, M};
+use super::{
+    montgomery, AsBytes, BaseElement, ByteReader, DeserializationError, FieldElement, Serializable,
+    StarkField, M,
+};
 use crate::field::{ExtensionOf, QuadExtension};
 
 // BASIC ALGEBRA
@@ -69,7 +72,7 @@ fn mul() {
     assert_eq!(BaseElement::new(m - 2), t * BaseElement::from(2u8));
     assert_eq!(BaseElement::new(m - 4), t * BaseElement::from(4u8));
 
-    let t = (m + 1) / 2;
+    let t = m.div_ceil(2);
     assert_eq!(BaseElement::ONE, BaseElement::new(t) * BaseElement::from(2u8));
 
     // test random values
@@ -88,6 +91,27 @@ fn mul() {
     }
 }
 
+#[test]
+fn exp() {
+    let a = BaseElement::ZERO;
+    assert_eq!(a.exp(0), BaseElement::ONE);
+    assert_eq!(a.exp(1), BaseElement::ZERO);
+
+    let a = BaseElement::ONE;
+    assert_eq!(a.exp(0), BaseElement::ONE);
+    assert_eq!(a.exp(M - 1), BaseElement::ONE);
+
+    // test random values
+    let v: Vec<BaseElement> = rand_vector(100);
+    for (&a, &b) in v.iter().zip(v.iter().rev()) {
+        let power = b.as_int();
+        let expected = a.to_big_uint().modpow(&b.to_big_uint(), &BigUint::from(M));
+        let expected = BaseElement::from_big_uint(expected);
+        assert_eq!(expected, a.exp(power));
+        assert_eq!(expected, a.exp_vartime(power));
+    }
+}
+
 #[test]
 fn inv() {
     // identity
@@ -227,6 +251,61 @@ fn read_elements_from() {
     }
 }
 
+// MONTGOMERY REPRESENTATION
+// ================================================================================================
+
+#[test]
+fn mont_conversions() {
+    assert_eq!(0, montgomery::BaseElement::ZERO.as_int());
+    assert_eq!(1, montgomery::BaseElement::ONE.as_int());
+    assert_eq!(M - 1, montgomery::BaseElement::new(M - 1).as_int());
+    assert_eq!(
+        BaseElement::TWO_ADIC_ROOT_OF_UNITY,
+        BaseElement::from(montgomery::BaseElement::TWO_ADIC_ROOT_OF_UNITY)
+    );
+
+    let v: Vec<BaseElement> = rand_vector(1000);
+    for a in v {
+        let b = montgomery::BaseElement::from(a);
+        assert_eq!(a.as_int(), b.as_int());
+        assert_eq!(a, BaseElement::from(b));
+    }
+}
+
+#[test]
+fn mont_arithmetic() {
+    let m = montgomery::BaseElement::new(M - 1);
+    assert_eq!(montgomery::BaseElement::ONE, m * m);
+    assert_eq!(montgomery::BaseElement::ZERO, m + montgomery::BaseElement::ONE);
+    assert_eq!(montgomery::BaseElement::ZERO, montgomery::BaseElement::ZERO.inv());
+
+    let v1: Vec<BaseElement> = rand_vector(1000);
+    let v2: Vec<BaseElement> = rand_vector(1000);
+    for (&a, &b) in v1.iter().zip(v2.iter()) {
+        let (ma, mb) = (montgomery::BaseElement::from(a), montgomery::BaseElement::from(b));
+        assert_eq!(a + b, BaseElement::from(ma + mb));
+        assert_eq!(a - b, BaseElement::from(ma - mb));
+        assert_eq!(a * b, BaseElement::from(ma * mb));
+        assert_eq!(a.inv(), BaseElement::from(ma.inv()));
+        assert_eq!(-a, BaseElement::from(-ma));
+        assert_eq!(a.exp(b.as_int()), BaseElement::from(ma.exp(mb.as_int())));
+    }
+}
+
+#[test]
+fn mont_serialization() {
+    let v: Vec<BaseElement> = rand_vector(100);
+    let mv = v.iter().map(|&a| montgomery::BaseElement::from(a)).collect::<Vec<_>>();
+
+    // elements in both representations must serialize into the same bytes
+    let bytes = v.to_bytes();
+    assert_eq!(bytes, mv.to_bytes());
+
+    let mut reader = SliceReader::new(&bytes);
+    let result = Vec::<montgomery::BaseElement>::read_from(&mut reader).unwrap();
+    assert_eq!(mv, result);
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -0,0 +1,652 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! An implementation of a 31-bit STARK-friendly prime field with modulus $2^{31} - 2^{27} + 1$
+//! (also known as the BabyBear field).
+//!
+//! All operations in this field are implemented using Montgomery arithmetic. Base elements are
+//! stored in the Montgomery form using `u32` as the backing type, and are always kept in the
+//! range [0, M). Since the elements are small, arithmetic in this field maps well onto 32-bit
+//! architectures (e.g., RISC-V or embedded targets).
+//!
+//! To achieve adequate security, proofs must be generated in a quartic extension of this field.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{Debug, Display, Formatter},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use utils::{
+    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
+    Serializable,
+};
+
+use super::{ExtensibleField, FieldElement, StarkField};
+
+#[cfg(feature = "simd")]
+mod simd;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Field modulus = 2^31 - 2^27 + 1
+const M: u32 = 2013265921;
+
+/// 2^64 mod M; this is used for conversion of elements into Montgomery representation.
+const R2: u32 = 1172168163;
+
+/// -M^{-1} mod 2^32; this is used during element multiplication.
+const U: u32 = 2013265919;
+
+/// Number of bytes needed to represent field element
+const ELEMENT_BYTES: usize = core::mem::size_of::<u32>();
+
+// 2^27 root of unity
+const G: u32 = 440564289;
+
+// FIELD ELEMENT
+// ================================================================================================
+
+/// Represents base field element in the field.
+///
+/// Internal values are stored in Montgomery representation and are always in the range [0, M).
+/// The backing type is `u32`.
+///
+/// When `simd` feature is enabled, element-wise operations over slices of elements are
+/// vectorized using AVX2 (on x86_64 targets with AVX2 support) or NEON (on aarch64 targets).
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u64", into = "u64"))]
+pub struct BaseElement(u32);
+
+impl BaseElement {
+    /// Creates a new field element from the provided `value`; the value is reduced modulo M and
+    /// converted into Montgomery representation.
+    pub const fn new(value: u32) -> BaseElement {
+        // multiply the value with R2 to convert to Montgomery representation; this is OK because
+        // the product of R2 and any 32-bit value is small enough to be reduced by `mul()`
+        BaseElement(mul(value, R2))
+    }
+
+    /// Returns the non-canonical u32 inner value.
+    pub const fn inner(&self) -> u32 {
+        self.0
+    }
+}
+
+impl FieldElement for BaseElement {
+    type PositiveInteger = u64;
+    type BaseField = Self;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ZERO: Self = BaseElement::new(0);
+    const ONE: Self = BaseElement::new(1);
+
+    const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+    const IS_CANONICAL: bool = false;
+
+    // ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[inline]
+    fn double(self) -> Self {
+        Self(add(self.0, self.0))
+    }
+
+    #[cfg(not(feature = "constant-time"))]
+    fn exp(self, power: Self::PositiveInteger) -> Self {
+        let mut b = self;
+
+        if power == 0 {
+            return Self::ONE;
+        } else if b == Self::ZERO {
+            return Self::ZERO;
+        }
+
+        let mut r = if power & 1 == 1 { b } else { Self::ONE };
+        for i in 1..64 - power.leading_zeros() {
+            b = b.square();
+            if (power >> i) & 1 == 1 {
+                r *= b;
+            }
+        }
+
+        r
+    }
+
+    #[cfg(feature = "constant-time")]
+    fn exp(self, power: Self::PositiveInteger) -> Self {
+        let mut r = Self::ONE;
+        for i in (0..64).rev() {
+            r = r.square();
+            let b = r * self;
+            // constant-time selection between r and b
+            let mask = 0u32.wrapping_sub(((power >> i) & 1) as u32);
+            r.0 ^= mask & (r.0 ^ b.0);
+        }
+
+        r
+    }
+
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^(M - 2) = x^{-1}; for x = 0, this returns 0
+        self.exp(M as u64 - 2)
+    }
+
+    fn conjugate(&self) -> Self {
+        BaseElement(self.0)
+    }
+
+    // BATCH ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    #[cfg(feature = "simd")]
+    fn add_slices(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "number of values must be the same for both slices");
+        simd::add_slices(a, b);
+    }
+
+    #[cfg(feature = "simd")]
+    fn mul_slices(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "number of values must be the same for both slices");
+        simd::mul_slices(a, b);
+    }
+
+    #[cfg(feature = "simd")]
+    fn mul_base_slice(a: &mut [Self], b: Self) {
+        simd::mul_base_slice(a, b);
+    }
+
+    // BASE ELEMENT CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => *self,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        // TODO: take endianness into account
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        if (p as usize) % mem::align_of::<u32>() != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+}
+
+impl StarkField for BaseElement {
+    /// sage: MODULUS = 2^31 - 2^27 + 1 \
+    /// sage: GF(MODULUS).is_prime_field() \
+    /// True \
+    /// sage: GF(MODULUS).order() \
+    /// 2013265921
+    const MODULUS: Self::PositiveInteger = M as u64;
+    const MODULUS_BITS: u32 = 31;
+
+    /// sage: GF(MODULUS).primitive_element() \
+    /// 31
+    const GENERATOR: Self = BaseElement::new(31);
+
+    /// sage: is_odd((MODULUS - 1) / 2^27) \
+    /// True
+    const TWO_ADICITY: u32 = 27;
+
+    /// sage: k = (MODULUS - 1) / 2^27 \
+    /// sage: GF(MODULUS).primitive_element()^k \
+    /// 440564289
+    const TWO_ADIC_ROOT_OF_UNITY: Self = BaseElement::new(G);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        M.to_le_bytes().to_vec()
+    }
+
+    #[inline]
+    fn as_int(&self) -> Self::PositiveInteger {
+        // convert from Montgomery representation by multiplying by 1
+        mul(self.0, 1) as u64
+    }
+}
+
+impl Randomizable for BaseElement {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl Debug for BaseElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for BaseElement {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_int())
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl Add for BaseElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(add(self.0, rhs.0))
+    }
+}
+
+impl AddAssign for BaseElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for BaseElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(sub(self.0, rhs.0))
+    }
+}
+
+impl SubAssign for BaseElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for BaseElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul(self.0, rhs.0))
+    }
+}
+
+impl MulAssign for BaseElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for BaseElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for BaseElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for BaseElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(sub(0, self.0))
+    }
+}
+
+// QUADRATIC EXTENSION
+// ================================================================================================
+
+/// Defines a quadratic extension of the base field over an irreducible polynomial x<sup>2</sup> -
+/// 11. Thus, an extension element is defined as α + β * φ, where φ is a root of this polynomial,
+/// and α and β are base field elements.
+impl ExtensibleField<2> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
+        let a0b0 = a[0] * b[0];
+        let a1b1 = a[1] * b[1];
+        [a0b0 + mul_by_w(a1b1), (a[0] + a[1]) * (b[0] + b[1]) - a0b0 - a1b1]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 2], b: Self) -> [Self; 2] {
+        [a[0] * b, a[1] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 2]) -> [Self; 2] {
+        [x[0], -x[1]]
+    }
+}
+
+// CUBIC EXTENSION
+// ================================================================================================
+
+/// Defines a cubic extension of the base field over an irreducible polynomial x<sup>3</sup> - 2.
+/// Thus, an extension element is defined as α + β * φ + γ * φ^2, where φ is a root of this
+/// polynomial, and α, β and γ are base field elements.
+impl ExtensibleField<3> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 3], b: [Self; 3]) -> [Self; 3] {
+        // φ^3 = 2, and thus, all terms with φ^3 and φ^4 are folded into lower terms doubled
+        let a0b0 = a[0] * b[0];
+        let a1b1 = a[1] * b[1];
+        let a2b2 = a[2] * b[2];
+
+        let a1b2_a2b1 = (a[1] + a[2]) * (b[1] + b[2]) - a1b1 - a2b2;
+        let a0b1_a1b0 = (a[0] + a[1]) * (b[0] + b[1]) - a0b0 - a1b1;
+        let a0b2_a2b0 = (a[0] + a[2]) * (b[0] + b[2]) - a0b0 - a2b2;
+
+        [a0b0 + a1b2_a2b1.double(), a0b1_a1b0 + a2b2.double(), a0b2_a2b0 + a1b1]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 3], b: Self) -> [Self; 3] {
+        [a[0] * b, a[1] * b, a[2] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 3]) -> [Self; 3] {
+        // φ^p = 2^((p - 1) / 3) * φ; coefficients were computed using SageMath
+        [x[0], BaseElement::new(1314723123) * x[1], BaseElement::new(698542797) * x[2]]
+    }
+}
+
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Defines a quartic extension of the base field over an irreducible polynomial x<sup>4</sup> -
+/// 11. Thus, an extension element is defined as α + β * φ + γ * φ^2 + δ * φ^3, where φ is a root
+/// of this polynomial, and α, β, γ and δ are base field elements.
+impl ExtensibleField<4> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 4], b: [Self; 4]) -> [Self; 4] {
+        // φ^4 = 11, and thus, terms with φ^4, φ^5 and φ^6 are folded into lower terms
+        // multiplied by 11
+        [
+            a[0] * b[0] + mul_by_w(a[1] * b[3] + a[2] * b[2] + a[3] * b[1]),
+            a[0] * b[1] + a[1] * b[0] + mul_by_w(a[2] * b[3] + a[3] * b[2]),
+            a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + mul_by_w(a[3] * b[3]),
+            a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0],
+        ]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 4], b: Self) -> [Self; 4] {
+        [a[0] * b, a[1] * b, a[2] * b, a[3] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 4]) -> [Self; 4] {
+        // φ^p = 11^((p - 1) / 4) * φ; coefficients were computed using SageMath
+        [
+            x[0],
+            BaseElement::new(1728404513) * x[1],
+            -x[2],
+            BaseElement::new(284861408) * x[3],
+        ]
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl From<u32> for BaseElement {
+    /// Converts a 32-bit value into a field element; the value is reduced modulo M.
+    fn from(value: u32) -> Self {
+        BaseElement::new(value)
+    }
+}
+
+impl From<u16> for BaseElement {
+    /// Converts a 16-bit value into a field element.
+    fn from(value: u16) -> Self {
+        BaseElement::new(value as u32)
+    }
+}
+
+impl From<u8> for BaseElement {
+    /// Converts an 8-bit value into a field element.
+    fn from(value: u8) -> Self {
+        BaseElement::new(value as u32)
+    }
+}
+
+impl From<BaseElement> for u128 {
+    fn from(value: BaseElement) -> Self {
+        value.as_int() as u128
+    }
+}
+
+impl From<BaseElement> for u64 {
+    fn from(value: BaseElement) -> Self {
+        value.as_int()
+    }
+}
+
+impl From<BaseElement> for u32 {
+    fn from(value: BaseElement) -> Self {
+        value.as_int() as u32
+    }
+}
+
+impl TryFrom<u64> for BaseElement {
+    type Error = String;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        if value >= M as u64 {
+            Err(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            ))
+        } else {
+            Ok(Self::new(value as u32))
+        }
+    }
+}
+
+impl TryFrom<u128> for BaseElement {
+    type Error = String;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        if value >= M as u128 {
+            Err(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            ))
+        } else {
+            Ok(Self::new(value as u32))
+        }
+    }
+}
+
+impl TryFrom<[u8; 4]> for BaseElement {
+    type Error = String;
+
+    fn try_from(bytes: [u8; 4]) -> Result<Self, Self::Error> {
+        let value = u32::from_le_bytes(bytes);
+        Self::try_from(value as u64)
+    }
+}
+
+impl TryFrom<&[u8]> for BaseElement {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to encode the element in the canonical
+    /// representation in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let value = bytes
+            .try_into()
+            .map(u32::from_le_bytes)
+            .map_err(|error| DeserializationError::UnknownError(format!("{error}")))?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement::new(value))
+    }
+}
+
+impl AsBytes for BaseElement {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const BaseElement = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for BaseElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // convert from Montgomery representation into canonical representation
+        target.write_bytes(&(self.as_int() as u32).to_le_bytes());
+    }
+}
+
+impl Deserializable for BaseElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value = source.read_u32()?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement::new(value))
+    }
+}
+
+// FINITE FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes (a + b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+fn add(a: u32, b: u32) -> u32 {
+    reduce(a + b)
+}
+
+/// Computes (a - b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[cfg(not(feature = "constant-time"))]
+#[inline(always)]
+fn sub(a: u32, b: u32) -> u32 {
+    if a < b {
+        M - b + a
+    } else {
+        a - b
+    }
+}
+
+/// Computes (a - b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[cfg(feature = "constant-time")]
+#[inline(always)]
+fn sub(a: u32, b: u32) -> u32 {
+    let (z, borrow) = a.overflowing_sub(b);
+    z.wrapping_add(M & 0u32.wrapping_sub(borrow as u32))
+}
+
+/// Computes (a * b) / 2^32 reduced by M such that the output is in [0, M) range; the product of
+/// a and b is assumed to be smaller than 2^32 * M.
+#[inline(always)]
+const fn mul(a: u32, b: u32) -> u32 {
+    let z = (a as u64) * (b as u64);
+    let q = (z as u32).wrapping_mul(U);
+    let z = (z + (q as u64) * (M as u64)) >> 32;
+    reduce(z as u32)
+}
+
+/// Reduces z by M such that the output is in [0, M) range; z is assumed to be in [0, 2M).
+#[cfg(not(feature = "constant-time"))]
+#[inline(always)]
+const fn reduce(z: u32) -> u32 {
+    if z >= M {
+        z - M
+    } else {
+        z
+    }
+}
+
+/// Reduces z by M such that the output is in [0, M) range; z is assumed to be in [0, 2M).
+#[cfg(feature = "constant-time")]
+#[inline(always)]
+const fn reduce(z: u32) -> u32 {
+    let (r, borrow) = z.overflowing_sub(M);
+    r.wrapping_add(M & 0u32.wrapping_sub(borrow as u32))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Multiplies the specified element by 11; 11 is the non-residue used to define the quadratic
+/// and quartic extensions of the field.
+#[inline(always)]
+fn mul_by_w(value: BaseElement) -> BaseElement {
+    let x2 = value.double();
+    let x8 = x2.double().double();
+    x8 + x2 + value
+}
@@ -0,0 +1,290 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Vectorized arithmetic over slices of field elements.
+//!
+//! On x86_64 targets, AVX2 instructions are used if they are supported by the CPU at runtime; on
+//! aarch64 targets, NEON instructions are used. On all other targets, as well as for the trailing
+//! elements which do not fill a full vector, the scalar implementation is used.
+//!
+//! All vectorized functions rely on elements being stored in the range [0, M), and thus, being
+//! backed by a single `u32` value.
+
+use super::BaseElement;
+
+// PUBLIC FUNCTIONS
+// ================================================================================================
+
+/// Adds elements of `b` to the corresponding elements of `a`; `a` and `b` are assumed to have the
+/// same length.
+#[allow(unreachable_code)]
+pub fn add_slices(a: &mut [BaseElement], b: &[BaseElement]) {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 support was checked above
+        unsafe { avx2::add_slices(a, b) };
+        return;
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        // SAFETY: NEON is always available on aarch64 targets
+        unsafe { neon::add_slices(a, b) };
+        return;
+    }
+
+    a.iter_mut().zip(b).for_each(|(a, &b)| *a += b);
+}
+
+/// Multiplies elements of `a` by the corresponding elements of `b`; `a` and `b` are assumed to
+/// have the same length.
+#[allow(unreachable_code)]
+pub fn mul_slices(a: &mut [BaseElement], b: &[BaseElement]) {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 support was checked above
+        unsafe { avx2::mul_slices(a, b) };
+        return;
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        // SAFETY: NEON is always available on aarch64 targets
+        unsafe { neon::mul_slices(a, b) };
+        return;
+    }
+
+    a.iter_mut().zip(b).for_each(|(a, &b)| *a *= b);
+}
+
+/// Multiplies all elements of `a` by `b`.
+#[allow(unreachable_code)]
+pub fn mul_base_slice(a: &mut [BaseElement], b: BaseElement) {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 support was checked above
+        unsafe { avx2::mul_base_slice(a, b) };
+        return;
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        // SAFETY: NEON is always available on aarch64 targets
+        unsafe { neon::mul_base_slice(a, b) };
+        return;
+    }
+
+    a.iter_mut().for_each(|a| *a *= b);
+}
+
+// AVX2 IMPLEMENTATION
+// ================================================================================================
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use core::arch::x86_64::*;
+
+    use super::{
+        super::{M, U},
+        BaseElement,
+    };
+
+    /// Number of field elements in a single vector.
+    const LANES: usize = 8;
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn add_slices(a: &mut [BaseElement], b: &[BaseElement]) {
+        let m = _mm256_set1_epi32(M as i32);
+        let n = a.len() / LANES * LANES;
+        for i in (0..n).step_by(LANES) {
+            let x = load(&a[i..]);
+            let y = load(&b[i..]);
+            store(&mut a[i..], add(x, y, m));
+        }
+        for (a, &b) in a[n..].iter_mut().zip(&b[n..]) {
+            *a += b;
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn mul_slices(a: &mut [BaseElement], b: &[BaseElement]) {
+        let m = _mm256_set1_epi32(M as i32);
+        let u = _mm256_set1_epi32(U as i32);
+        let n = a.len() / LANES * LANES;
+        for i in (0..n).step_by(LANES) {
+            let x = load(&a[i..]);
+            let y = load(&b[i..]);
+            store(&mut a[i..], mul(x, y, m, u));
+        }
+        for (a, &b) in a[n..].iter_mut().zip(&b[n..]) {
+            *a *= b;
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn mul_base_slice(a: &mut [BaseElement], b: BaseElement) {
+        let m = _mm256_set1_epi32(M as i32);
+        let u = _mm256_set1_epi32(U as i32);
+        let y = _mm256_set1_epi32(b.0 as i32);
+        let n = a.len() / LANES * LANES;
+        for i in (0..n).step_by(LANES) {
+            let x = load(&a[i..]);
+            store(&mut a[i..], mul(x, y, m, u));
+        }
+        for value in a[n..].iter_mut() {
+            *value *= b;
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes (x + y) mod M for each lane; lanes of x and y are assumed to be in [0, M).
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn add(x: __m256i, y: __m256i, m: __m256i) -> __m256i {
+        // x + y is in [0, 2M) and thus, min(x + y, x + y - M) is the reduced value (when x + y
+        // is smaller than M, the subtraction wraps around to a value greater than x + y)
+        let z = _mm256_add_epi32(x, y);
+        _mm256_min_epu32(z, _mm256_sub_epi32(z, m))
+    }
+
+    /// Computes Montgomery product of x and y for each lane; lanes of x and y are assumed to be
+    /// in [0, M).
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn mul(x: __m256i, y: __m256i, m: __m256i, u: __m256i) -> __m256i {
+        // multiply even and odd lanes separately to get full 64-bit products
+        let evn = mont_reduce(_mm256_mul_epu32(x, y), m, u);
+        let x_odd = _mm256_srli_epi64::<32>(x);
+        let y_odd = _mm256_srli_epi64::<32>(y);
+        let odd = mont_reduce(_mm256_mul_epu32(x_odd, y_odd), m, u);
+
+        // results for even lanes are in the low halves of 64-bit words, and results for odd
+        // lanes need to be moved into the high halves of 64-bit words
+        let z = _mm256_blend_epi32::<0b10101010>(evn, _mm256_slli_epi64::<32>(odd));
+        _mm256_min_epu32(z, _mm256_sub_epi32(z, m))
+    }
+
+    /// Reduces 64-bit products z into (z / 2^32) mod M in the range [0, 2M); the results are
+    /// stored in the low halves of 64-bit words.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn mont_reduce(z: __m256i, m: __m256i, u: __m256i) -> __m256i {
+        // q = (z mod 2^32) * U mod 2^32 is in the low half of each 64-bit word
+        let q = _mm256_mullo_epi32(z, u);
+        let qm = _mm256_mul_epu32(q, m);
+        _mm256_srli_epi64::<32>(_mm256_add_epi64(z, qm))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn load(values: &[BaseElement]) -> __m256i {
+        debug_assert!(values.len() >= LANES);
+        _mm256_loadu_si256(values.as_ptr() as *const __m256i)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn store(values: &mut [BaseElement], v: __m256i) {
+        debug_assert!(values.len() >= LANES);
+        _mm256_storeu_si256(values.as_mut_ptr() as *mut __m256i, v)
+    }
+}
+
+// NEON IMPLEMENTATION
+// ================================================================================================
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use core::arch::aarch64::*;
+
+    use super::{
+        super::{M, U},
+        BaseElement,
+    };
+
+    /// Number of field elements in a single vector.
+    const LANES: usize = 4;
+
+    pub unsafe fn add_slices(a: &mut [BaseElement], b: &[BaseElement]) {
+        let m = vdupq_n_u32(M);
+        let n = a.len() / LANES * LANES;
+        for i in (0..n).step_by(LANES) {
+            let x = load(&a[i..]);
+            let y = load(&b[i..]);
+            store(&mut a[i..], add(x, y, m));
+        }
+        for (a, &b) in a[n..].iter_mut().zip(&b[n..]) {
+            *a += b;
+        }
+    }
+
+    pub unsafe fn mul_slices(a: &mut [BaseElement], b: &[BaseElement]) {
+        let m = vdupq_n_u32(M);
+        let n = a.len() / LANES * LANES;
+        for i in (0..n).step_by(LANES) {
+            let x = load(&a[i..]);
+            let y = load(&b[i..]);
+            store(&mut a[i..], mul(x, y, m));
+        }
+        for (a, &b) in a[n..].iter_mut().zip(&b[n..]) {
+            *a *= b;
+        }
+    }
+
+    pub unsafe fn mul_base_slice(a: &mut [BaseElement], b: BaseElement) {
+        let m = vdupq_n_u32(M);
+        let y = vdupq_n_u32(b.0);
+        let n = a.len() / LANES * LANES;
+        for i in (0..n).step_by(LANES) {
+            let x = load(&a[i..]);
+            store(&mut a[i..], mul(x, y, m));
+        }
+        for value in a[n..].iter_mut() {
+            *value *= b;
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes (x + y) mod M for each lane; lanes of x and y are assumed to be in [0, M).
+    #[inline]
+    unsafe fn add(x: uint32x4_t, y: uint32x4_t, m: uint32x4_t) -> uint32x4_t {
+        let z = vaddq_u32(x, y);
+        vminq_u32(z, vsubq_u32(z, m))
+    }
+
+    /// Computes Montgomery product of x and y for each lane; lanes of x and y are assumed to be
+    /// in [0, M).
+    #[inline]
+    unsafe fn mul(x: uint32x4_t, y: uint32x4_t, m: uint32x4_t) -> uint32x4_t {
+        let lo = mont_reduce(vmull_u32(vget_low_u32(x), vget_low_u32(y)));
+        let hi = mont_reduce(vmull_high_u32(x, y));
+        let z = vcombine_u32(lo, hi);
+        vminq_u32(z, vsubq_u32(z, m))
+    }
+
+    /// Reduces 64-bit products z into (z / 2^32) mod M in the range [0, 2M).
+    #[inline]
+    unsafe fn mont_reduce(z: uint64x2_t) -> uint32x2_t {
+        let q = vmul_u32(vmovn_u64(z), vdup_n_u32(U));
+        let qm = vmull_u32(q, vdup_n_u32(M));
+        vshrn_n_u64::<32>(vaddq_u64(z, qm))
+    }
+
+    #[inline]
+    unsafe fn load(values: &[BaseElement]) -> uint32x4_t {
+        debug_assert!(values.len() >= LANES);
+        vld1q_u32(values.as_ptr() as *const u32)
+    }
+
+    #[inline]
+    unsafe fn store(values: &mut [BaseElement], v: uint32x4_t) {
+        debug_assert!(values.len() >= LANES);
+        vst1q_u32(values.as_mut_ptr() as *mut u32, v)
+    }
+}
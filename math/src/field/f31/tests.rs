@@ -0,0 +1,439 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use num_bigint::BigUint;
+use proptest::prelude::*;
+use rand_utils::{rand_value, rand_vector};
+
+use super::{BaseElement, DeserializationError, FieldElement, Serializable, StarkField};
+use crate::{
+    fft,
+    field::{CubeExtension, ExtensionOf, QuadExtension, QuartExtension},
+    polynom,
+};
+
+// MANUAL TESTS
+// ================================================================================================
+
+#[test]
+fn add() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(r, r + BaseElement::ZERO);
+
+    // test addition within bounds
+    assert_eq!(BaseElement::from(5u8), BaseElement::from(2u8) + BaseElement::from(3u8));
+
+    // test overflow
+    let t = BaseElement::new(super::M - 1);
+    assert_eq!(BaseElement::ZERO, t + BaseElement::ONE);
+    assert_eq!(BaseElement::ONE, t + BaseElement::from(2u8));
+}
+
+#[test]
+fn sub() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(r, r - BaseElement::ZERO);
+
+    // test subtraction within bounds
+    assert_eq!(BaseElement::from(2u8), BaseElement::from(5u8) - BaseElement::from(3u8));
+
+    // test underflow
+    let expected = BaseElement::new(super::M - 2);
+    assert_eq!(expected, BaseElement::from(3u8) - BaseElement::from(5u8));
+}
+
+#[test]
+fn mul() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(BaseElement::ZERO, r * BaseElement::ZERO);
+    assert_eq!(r, r * BaseElement::ONE);
+
+    // test multiplication within bounds
+    assert_eq!(BaseElement::from(15u8), BaseElement::from(5u8) * BaseElement::from(3u8));
+
+    // test overflow
+    let m = super::M;
+    let t = BaseElement::new(m - 1);
+    assert_eq!(BaseElement::ONE, t * t);
+    assert_eq!(BaseElement::new(m - 2), t * BaseElement::from(2u8));
+    assert_eq!(BaseElement::new(m - 4), t * BaseElement::from(4u8));
+
+    let t = m.div_ceil(2);
+    assert_eq!(BaseElement::ONE, BaseElement::new(t) * BaseElement::from(2u8));
+}
+
+#[test]
+fn exp() {
+    let a = BaseElement::ZERO;
+    assert_eq!(a.exp(0), BaseElement::ONE);
+    assert_eq!(a.exp(1), BaseElement::ZERO);
+
+    let a = BaseElement::ONE;
+    assert_eq!(a.exp(0), BaseElement::ONE);
+    assert_eq!(a.exp(1), BaseElement::ONE);
+    assert_eq!(a.exp(3), BaseElement::ONE);
+
+    let a: BaseElement = rand_value();
+    assert_eq!(a.exp(3), a * a * a);
+}
+
+#[test]
+fn inv() {
+    // identity
+    assert_eq!(BaseElement::ONE, BaseElement::inv(BaseElement::ONE));
+    assert_eq!(BaseElement::ZERO, BaseElement::inv(BaseElement::ZERO));
+}
+
+#[test]
+fn element_as_int() {
+    let v = u32::MAX;
+    let e = BaseElement::new(v);
+    assert_eq!((v % super::M) as u64, e.as_int());
+}
+
+// QUADRATIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quad_mul_base() {
+    let a = <QuadExtension<BaseElement>>::new(rand_value(), rand_value());
+    let b0 = rand_value();
+    let b = <QuadExtension<BaseElement>>::new(b0, BaseElement::ZERO);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+// CUBIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn cube_mul_base() {
+    let a = <CubeExtension<BaseElement>>::new(rand_value(), rand_value(), rand_value());
+    let b0 = rand_value();
+    let b = <CubeExtension<BaseElement>>::new(b0, BaseElement::ZERO, BaseElement::ZERO);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+// QUARTIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quart_mul() {
+    // identity
+    let r: QuartExtension<BaseElement> = rand_value();
+    assert_eq!(<QuartExtension<BaseElement>>::ZERO, r * <QuartExtension<BaseElement>>::ZERO);
+    assert_eq!(r, r * <QuartExtension<BaseElement>>::ONE);
+
+    // test multiplication with reduction by x^4 - 11
+    let a = quart(15, 22, 8, 3);
+    let b = quart(20, 22, 6, super::M - 1);
+    assert_eq!(quart(1312, 880, 701, 353), a * b);
+}
+
+#[test]
+fn quart_mul_base() {
+    let a: QuartExtension<BaseElement> = rand_value();
+    let b0: BaseElement = rand_value();
+    let b = <QuartExtension<BaseElement>>::from(b0);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+#[test]
+fn quart_frobenius() {
+    // conjugate must be equal to raising the element to the power of the field modulus
+    let a: QuartExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(BaseElement::MODULUS), a.conjugate());
+}
+
+// BATCH ARITHMETIC
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn slice_arithmetic() {
+    // use a length which is not a multiple of vector width to exercise the scalar tail
+    let a: Vec<BaseElement> = rand_vector(37);
+    let b: Vec<BaseElement> = rand_vector(37);
+    let k: BaseElement = rand_value();
+
+    let mut c = a.clone();
+    BaseElement::add_slices(&mut c, &b);
+    assert_eq!(a.iter().zip(&b).map(|(&a, &b)| a + b).collect::<Vec<_>>(), c);
+
+    let mut c = a.clone();
+    BaseElement::mul_slices(&mut c, &b);
+    assert_eq!(a.iter().zip(&b).map(|(&a, &b)| a * b).collect::<Vec<_>>(), c);
+
+    let mut c = a.clone();
+    BaseElement::mul_base_slice(&mut c, k);
+    assert_eq!(a.iter().map(|&a| a * k).collect::<Vec<_>>(), c);
+
+    // extension elements are added and scaled via the base field
+    let a: Vec<QuartExtension<BaseElement>> = rand_vector(13);
+    let b: Vec<QuartExtension<BaseElement>> = rand_vector(13);
+
+    let mut c = a.clone();
+    QuartExtension::add_slices(&mut c, &b);
+    assert_eq!(a.iter().zip(&b).map(|(&a, &b)| a + b).collect::<Vec<_>>(), c);
+
+    let mut c = a.clone();
+    QuartExtension::mul_base_slice(&mut c, k);
+    assert_eq!(a.iter().map(|&a| a.mul_base(k)).collect::<Vec<_>>(), c);
+}
+
+// ROOTS OF UNITY
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn get_root_of_unity() {
+    let root_27 = BaseElement::get_root_of_unity(27);
+    assert_eq!(BaseElement::TWO_ADIC_ROOT_OF_UNITY, root_27);
+    assert_eq!(BaseElement::ONE, root_27.exp(1u64 << 27));
+
+    let root_26 = BaseElement::get_root_of_unity(26);
+    let expected = root_27.exp(2);
+    assert_eq!(expected, root_26);
+    assert_eq!(BaseElement::ONE, root_26.exp(1u64 << 26));
+}
+
+// FFT
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn fft_in_quartic_extension() {
+    let n = 64;
+    let p: Vec<QuartExtension<BaseElement>> = (0..n).map(|_| rand_value()).collect();
+    let domain = (0..n)
+        .map(|i| BaseElement::get_root_of_unity(6).exp(i as u64))
+        .collect::<Vec<_>>();
+    let expected = domain
+        .iter()
+        .map(|&x| polynom::eval(&p, QuartExtension::<BaseElement>::from(x)))
+        .collect::<Vec<_>>();
+
+    let twiddles = fft::get_twiddles::<BaseElement>(n);
+    let mut evaluations = p.clone();
+    fft::evaluate_poly(&mut evaluations, &twiddles);
+    assert_eq!(expected, evaluations);
+
+    let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(n);
+    fft::interpolate_poly(&mut evaluations, &inv_twiddles);
+    assert_eq!(p, evaluations);
+}
+
+// SERIALIZATION AND DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn try_from_slice() {
+    let bytes = vec![1, 0, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().as_int());
+
+    let bytes = vec![1, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+
+    let bytes = vec![1, 0, 0, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+
+    let bytes = vec![255, 255, 255, 255];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+}
+
+#[test]
+fn serialization() {
+    let a: QuartExtension<BaseElement> = rand_value();
+    let bytes = a.to_bytes();
+    assert_eq!(16, bytes.len());
+    assert_eq!(a, QuartExtension::<BaseElement>::try_from(bytes.as_slice()).unwrap());
+
+    // elements are serialized in canonical form
+    let b = BaseElement::new(7);
+    assert_eq!(vec![7, 0, 0, 0], b.to_bytes());
+}
+
+#[test]
+fn elements_as_bytes() {
+    let source = vec![
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+    ];
+
+    let mut expected = vec![];
+    expected.extend_from_slice(&source[0].0.to_le_bytes());
+    expected.extend_from_slice(&source[1].0.to_le_bytes());
+    expected.extend_from_slice(&source[2].0.to_le_bytes());
+    expected.extend_from_slice(&source[3].0.to_le_bytes());
+
+    assert_eq!(expected, BaseElement::elements_as_bytes(&source));
+}
+
+#[test]
+fn bytes_as_elements() {
+    let elements = vec![
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+    ];
+
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&elements[0].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[1].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[2].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[3].0.to_le_bytes());
+    bytes.extend_from_slice(&BaseElement::new(5).0.to_le_bytes());
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[..16]) };
+    assert!(result.is_ok());
+    assert_eq!(elements, result.unwrap());
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[..17]) };
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[1..17]) };
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
+// RANDOMIZED TESTS
+// ================================================================================================
+
+proptest! {
+
+    #[test]
+    fn add_proptest(a in any::<u32>(), b in any::<u32>()) {
+        let v1 = BaseElement::new(a);
+        let v2 = BaseElement::new(b);
+        let result = v1 + v2;
+
+        let expected = ((a % super::M) as u64 + (b % super::M) as u64) % super::M as u64;
+        prop_assert_eq!(expected, result.as_int());
+    }
+
+    #[test]
+    fn sub_proptest(a in any::<u32>(), b in any::<u32>()) {
+        let v1 = BaseElement::new(a);
+        let v2 = BaseElement::new(b);
+        let result = v1 - v2;
+
+        let a = a % super::M;
+        let b = b % super::M;
+        let expected = if a < b { super::M - b + a } else { a - b };
+
+        prop_assert_eq!(expected as u64, result.as_int());
+    }
+
+    #[test]
+    fn mul_proptest(a in any::<u32>(), b in any::<u32>()) {
+        let v1 = BaseElement::new(a);
+        let v2 = BaseElement::new(b);
+        let result = v1 * v2;
+
+        let expected = ((a as u64) * (b as u64)) % super::M as u64;
+        prop_assert_eq!(expected, result.as_int());
+    }
+
+    #[test]
+    fn exp_proptest(a in any::<u32>(), b in any::<u64>()) {
+        let result = BaseElement::new(a).exp(b);
+
+        let b = BigUint::from(b);
+        let m = BigUint::from(super::M);
+        let expected = BigUint::from(a).modpow(&b, &m).to_u64_digits().first().copied();
+        prop_assert_eq!(expected.unwrap_or(0), result.as_int());
+    }
+
+    #[test]
+    fn inv_proptest(a in any::<u32>()) {
+        let a = BaseElement::new(a);
+        let b = a.inv();
+
+        let expected = if a == BaseElement::ZERO { BaseElement::ZERO } else { BaseElement::ONE };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    #[test]
+    fn element_as_int_proptest(a in any::<u32>()) {
+        let e = BaseElement::new(a);
+        prop_assert_eq!((a % super::M) as u64, e.as_int());
+    }
+
+    // QUADRATIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn quad_mul_inv_proptest(a0 in any::<u32>(), a1 in any::<u32>()) {
+        let a = QuadExtension::<BaseElement>::new(BaseElement::new(a0), BaseElement::new(a1));
+        let b = a.inv();
+
+        let expected = if a == QuadExtension::<BaseElement>::ZERO {
+            QuadExtension::<BaseElement>::ZERO
+        } else {
+            QuadExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    // CUBIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn cube_mul_inv_proptest(a0 in any::<u32>(), a1 in any::<u32>(), a2 in any::<u32>()) {
+        let a = CubeExtension::<BaseElement>::new(BaseElement::new(a0), BaseElement::new(a1), BaseElement::new(a2));
+        let b = a.inv();
+
+        let expected = if a == CubeExtension::<BaseElement>::ZERO {
+            CubeExtension::<BaseElement>::ZERO
+        } else {
+            CubeExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    // QUARTIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn quart_mul_inv_proptest(
+        a0 in any::<u32>(),
+        a1 in any::<u32>(),
+        a2 in any::<u32>(),
+        a3 in any::<u32>(),
+    ) {
+        let a = quart(a0, a1, a2, a3);
+        let b = a.inv();
+
+        let expected = if a == QuartExtension::<BaseElement>::ZERO {
+            QuartExtension::<BaseElement>::ZERO
+        } else {
+            QuartExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn quart(a0: u32, a1: u32, a2: u32, a3: u32) -> QuartExtension<BaseElement> {
+    QuartExtension::new(
+        BaseElement::new(a0),
+        BaseElement::new(a1),
+        BaseElement::new(a2),
+        BaseElement::new(a3),
+    )
+}
@@ -42,6 +42,7 @@ const M: u64 = 4611624995532046337;
 const R2: u64 = 630444561284293700;
 
 /// 2^192 mod M; this is used during element inversion.
+#[cfg(not(feature = "constant-time"))]
 const R3: u64 = 732984146687909319;
 
 /// -M^{-1} mod 2^64; this is used during element multiplication.
@@ -99,6 +100,7 @@ impl FieldElement for BaseElement {
         Self(z - q)
     }
 
+    #[cfg(not(feature = "constant-time"))]
     fn exp(self, power: Self::PositiveInteger) -> Self {
         let mut b = self;
 
@@ -119,10 +121,31 @@ impl FieldElement for BaseElement {
         r
     }
 
+    #[cfg(feature = "constant-time")]
+    fn exp(self, power: Self::PositiveInteger) -> Self {
+        let mut r = Self::ONE;
+        for i in (0..64).rev() {
+            r = r.square();
+            let b = r * self;
+            // constant-time selection between r and b
+            let mask = 0u64.wrapping_sub((power >> i) & 1);
+            r.0 ^= mask & (r.0 ^ b.0);
+        }
+
+        r
+    }
+
+    #[cfg(not(feature = "constant-time"))]
     fn inv(self) -> Self {
         BaseElement(inv(self.0))
     }
 
+    #[cfg(feature = "constant-time")]
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^(M - 2) = x^{-1}; for x = 0, this returns 0
+        self.exp(M - 2)
+    }
+
     fn conjugate(&self) -> Self {
         BaseElement(self.0)
     }
@@ -294,7 +317,7 @@ impl Div for BaseElement {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self {
-        Self(mul(self.0, inv(rhs.0)))
+        Self(mul(self.0, rhs.inv().0))
     }
 }
 
@@ -462,7 +485,7 @@ impl TryFrom<[u8; 8]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -540,6 +563,7 @@ fn add(a: u64, b: u64) -> u64 {
 
 /// Computes (a - b) reduced by M such that the output is in [0, 2M) range; a and b are assumed to
 /// be in [0, 2M).
+#[cfg(not(feature = "constant-time"))]
 #[inline(always)]
 fn sub(a: u64, b: u64) -> u64 {
     if a < b {
@@ -549,6 +573,15 @@ fn sub(a: u64, b: u64) -> u64 {
     }
 }
 
+/// Computes (a - b) reduced by M such that the output is in [0, 2M) range; a and b are assumed to
+/// be in [0, 2M).
+#[cfg(feature = "constant-time")]
+#[inline(always)]
+fn sub(a: u64, b: u64) -> u64 {
+    let (z, borrow) = a.overflowing_sub(b);
+    z.wrapping_add((2 * M) & 0u64.wrapping_sub(borrow as u64))
+}
+
 /// Computes (a * b) reduced by M such that the output is in [0, 2M) range; a and b are assumed to
 /// be in [0, 2M).
 #[inline(always)]
@@ -561,6 +594,7 @@ const fn mul(a: u64, b: u64) -> u64 {
 
 /// Computes y such that (x * y) % M = 1 except for when when x = 0; in such a case, 0 is returned;
 /// x is assumed to in [0, 2M) range, and the output will also be in [0, 2M) range.
+#[cfg(not(feature = "constant-time"))]
 #[inline(always)]
 #[allow(clippy::many_single_char_names)]
 fn inv(x: u64) -> u64 {
@@ -613,6 +647,7 @@ fn inv(x: u64) -> u64 {
 // ================================================================================================
 
 /// Reduces any value in [0, 2M) range to [0, M) range
+#[cfg(not(feature = "constant-time"))]
 #[inline(always)]
 fn normalize(value: u64) -> u64 {
     if value >= M {
@@ -621,3 +656,11 @@ fn normalize(value: u64) -> u64 {
         value
     }
 }
+
+/// Reduces any value in [0, 2M) range to [0, M) range
+#[cfg(feature = "constant-time")]
+#[inline(always)]
+fn normalize(value: u64) -> u64 {
+    let (z, borrow) = value.overflowing_sub(M);
+    z.wrapping_add(M & 0u64.wrapping_sub(borrow as u64))
+}
@@ -0,0 +1,559 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! An implementation of a 31-bit prime field with modulus $2^{31} - 1$ (also known as the
+//! Mersenne-31 field).
+//!
+//! Base elements are stored in their canonical form using `u32` as the backing type, and are
+//! always kept in the range [0, M). Since the modulus is a Mersenne prime, reduction of products
+//! requires only shifts and additions, and all arithmetic operations are branch-free.
+//!
+//! The multiplicative group of this field has two-adicity of 1, and thus, this field cannot be
+//! used with the radix-2 FFT implemented in the [fft](crate::fft) module. Instead, polynomials
+//! over this field should be evaluated over domains defined on the circle curve
+//! x<sup>2</sup> + y<sup>2</sup> = 1 (see the [circle](crate::circle) module), the group of
+//! points of which has order 2<sup>31</sup>.
+//!
+//! To achieve adequate security, proofs must be generated in a quartic extension of this field.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{Debug, Display, Formatter},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use utils::{
+    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
+    Serializable,
+};
+
+use super::{ExtensibleField, FieldElement, StarkField};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Field modulus = 2^31 - 1
+const M: u32 = 2147483647;
+
+/// Number of bytes needed to represent field element
+const ELEMENT_BYTES: usize = core::mem::size_of::<u32>();
+
+// FIELD ELEMENT
+// ================================================================================================
+
+/// Represents base field element in the field.
+///
+/// Internal values are stored in their canonical form in the range [0, M). The backing type is
+/// `u32`.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u64", into = "u64"))]
+pub struct BaseElement(u32);
+
+impl BaseElement {
+    /// Creates a new field element from the provided `value`; the value is reduced modulo M.
+    pub const fn new(value: u32) -> BaseElement {
+        BaseElement(reduce(value))
+    }
+}
+
+impl FieldElement for BaseElement {
+    type PositiveInteger = u64;
+    type BaseField = Self;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ZERO: Self = BaseElement::new(0);
+    const ONE: Self = BaseElement::new(1);
+
+    const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+    const IS_CANONICAL: bool = true;
+
+    // ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[inline]
+    fn double(self) -> Self {
+        Self(add(self.0, self.0))
+    }
+
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^(M - 2) = x^{-1}; for x = 0, this returns 0
+        self.exp(M as u64 - 2)
+    }
+
+    fn conjugate(&self) -> Self {
+        BaseElement(self.0)
+    }
+
+    // BASE ELEMENT CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => *self,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        // TODO: take endianness into account
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        if (p as usize) % mem::align_of::<u32>() != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+}
+
+impl StarkField for BaseElement {
+    /// sage: MODULUS = 2^31 - 1 \
+    /// sage: GF(MODULUS).is_prime_field() \
+    /// True \
+    /// sage: GF(MODULUS).order() \
+    /// 2147483647
+    const MODULUS: Self::PositiveInteger = M as u64;
+    const MODULUS_BITS: u32 = 31;
+
+    /// sage: GF(MODULUS).primitive_element() \
+    /// 7
+    const GENERATOR: Self = BaseElement::new(7);
+
+    /// sage: is_odd((MODULUS - 1) / 2) \
+    /// True
+    const TWO_ADICITY: u32 = 1;
+
+    /// sage: k = (MODULUS - 1) / 2 \
+    /// sage: GF(MODULUS).primitive_element()^k \
+    /// 2147483646
+    const TWO_ADIC_ROOT_OF_UNITY: Self = BaseElement::new(M - 1);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        M.to_le_bytes().to_vec()
+    }
+
+    #[inline]
+    fn as_int(&self) -> Self::PositiveInteger {
+        self.0 as u64
+    }
+}
+
+impl Randomizable for BaseElement {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl Debug for BaseElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for BaseElement {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl Add for BaseElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(add(self.0, rhs.0))
+    }
+}
+
+impl AddAssign for BaseElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for BaseElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(sub(self.0, rhs.0))
+    }
+}
+
+impl SubAssign for BaseElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for BaseElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul(self.0, rhs.0))
+    }
+}
+
+impl MulAssign for BaseElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for BaseElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for BaseElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for BaseElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(sub(0, self.0))
+    }
+}
+
+// QUADRATIC EXTENSION
+// ================================================================================================
+
+/// Defines a quadratic extension of the base field over an irreducible polynomial
+/// x<sup>2</sup> + 1. Thus, an extension element is defined as α + β * i, where i is a root of
+/// this polynomial, and α and β are base field elements. This extension is also known as the
+/// complex extension of the Mersenne-31 field (CM31).
+impl ExtensibleField<2> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
+        let a0b0 = a[0] * b[0];
+        let a1b1 = a[1] * b[1];
+        [a0b0 - a1b1, (a[0] + a[1]) * (b[0] + b[1]) - a0b0 - a1b1]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 2], b: Self) -> [Self; 2] {
+        [a[0] * b, a[1] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 2]) -> [Self; 2] {
+        // i^p = -i since p = 3 mod 4
+        [x[0], -x[1]]
+    }
+}
+
+// CUBIC EXTENSION
+// ================================================================================================
+
+/// Cubic extension for this field is not implemented as quartic extension should be used to
+/// achieve adequate security level.
+impl ExtensibleField<3> for BaseElement {
+    fn mul(_a: [Self; 3], _b: [Self; 3]) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn mul_base(_a: [Self; 3], _b: Self) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn frobenius(_x: [Self; 3]) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    fn is_supported() -> bool {
+        false
+    }
+}
+
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Defines a quartic extension of the base field as a quadratic extension of the complex
+/// extension CM31 = F[i] / (i<sup>2</sup> + 1) over an irreducible polynomial u<sup>2</sup> -
+/// (2 + i). Thus, an extension element is defined as (α + β * i) + (γ + δ * i) * u, where α, β,
+/// γ and δ are base field elements. This extension is also known as QM31.
+impl ExtensibleField<4> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 4], b: [Self; 4]) -> [Self; 4] {
+        // (A + B * u) * (C + D * u) = (A * C + B * D * (2 + i)) + (A * D + B * C) * u, where
+        // A, B, C, and D are elements of CM31
+        let ac = cm31_mul([a[0], a[1]], [b[0], b[1]]);
+        let bd = cm31_mul([a[2], a[3]], [b[2], b[3]]);
+        let ab_cd = cm31_mul([a[0] + a[2], a[1] + a[3]], [b[0] + b[2], b[1] + b[3]]);
+
+        // (x + y * i) * (2 + i) = (2x - y) + (x + 2y) * i
+        let bd_w = [bd[0].double() - bd[1], bd[0] + bd[1].double()];
+
+        [
+            ac[0] + bd_w[0],
+            ac[1] + bd_w[1],
+            ab_cd[0] - ac[0] - bd[0],
+            ab_cd[1] - ac[1] - bd[1],
+        ]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 4], b: Self) -> [Self; 4] {
+        [a[0] * b, a[1] * b, a[2] * b, a[3] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 4]) -> [Self; 4] {
+        // i^p = -i, and u^p = (2 + i)^((p - 1) / 2) * u; the coefficients of (2 + i)^((p - 1) / 2)
+        // were computed using SageMath
+        let c0 = BaseElement::new(21189756);
+        let c1 = BaseElement::new(42379512);
+        [x[0], -x[1], x[2] * c0 + x[3] * c1, x[2] * c1 - x[3] * c0]
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl From<u32> for BaseElement {
+    /// Converts a 32-bit value into a field element; the value is reduced modulo M.
+    fn from(value: u32) -> Self {
+        BaseElement::new(value)
+    }
+}
+
+impl From<u16> for BaseElement {
+    /// Converts a 16-bit value into a field element.
+    fn from(value: u16) -> Self {
+        BaseElement::new(value as u32)
+    }
+}
+
+impl From<u8> for BaseElement {
+    /// Converts an 8-bit value into a field element.
+    fn from(value: u8) -> Self {
+        BaseElement::new(value as u32)
+    }
+}
+
+impl From<BaseElement> for u128 {
+    fn from(value: BaseElement) -> Self {
+        value.0 as u128
+    }
+}
+
+impl From<BaseElement> for u64 {
+    fn from(value: BaseElement) -> Self {
+        value.0 as u64
+    }
+}
+
+impl From<BaseElement> for u32 {
+    fn from(value: BaseElement) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<u64> for BaseElement {
+    type Error = String;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        if value >= M as u64 {
+            Err(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            ))
+        } else {
+            Ok(Self::new(value as u32))
+        }
+    }
+}
+
+impl TryFrom<u128> for BaseElement {
+    type Error = String;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        if value >= M as u128 {
+            Err(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            ))
+        } else {
+            Ok(Self::new(value as u32))
+        }
+    }
+}
+
+impl TryFrom<[u8; 4]> for BaseElement {
+    type Error = String;
+
+    fn try_from(bytes: [u8; 4]) -> Result<Self, Self::Error> {
+        let value = u32::from_le_bytes(bytes);
+        Self::try_from(value as u64)
+    }
+}
+
+impl TryFrom<&[u8]> for BaseElement {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to encode the element in the canonical
+    /// representation in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let value = bytes
+            .try_into()
+            .map(u32::from_le_bytes)
+            .map_err(|error| DeserializationError::UnknownError(format!("{error}")))?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement(value))
+    }
+}
+
+impl AsBytes for BaseElement {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const BaseElement = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for BaseElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.0.to_le_bytes());
+    }
+}
+
+impl Deserializable for BaseElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value = source.read_u32()?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement(value))
+    }
+}
+
+// FINITE FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes (a + b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+const fn add(a: u32, b: u32) -> u32 {
+    // a + b < 2^32, and thus, the sum does not overflow
+    reduce(a + b)
+}
+
+/// Computes (a - b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+const fn sub(a: u32, b: u32) -> u32 {
+    // if a < b, the subtraction wraps around to a - b + 2^32 = (a - b + M) + 2^31 + 1; masking
+    // out the top bit and subtracting 1 yields a - b + M
+    let (z, borrow) = a.overflowing_sub(b);
+    (z & M).wrapping_sub(borrow as u32)
+}
+
+/// Computes (a * b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+const fn mul(a: u32, b: u32) -> u32 {
+    // since 2^31 = 1 mod M, the product z = z_hi * 2^31 + z_lo is congruent to z_hi + z_lo; both
+    // values are smaller than 2^31, and thus, their sum does not overflow
+    let z = (a as u64) * (b as u64);
+    let z_lo = (z as u32) & M;
+    let z_hi = (z >> 31) as u32;
+    reduce(z_lo + z_hi)
+}
+
+/// Reduces any 32-bit value by M such that the output is in [0, M) range.
+#[inline(always)]
+const fn reduce(x: u32) -> u32 {
+    // since 2^31 = 1 mod M, x = x_hi * 2^31 + x_lo is congruent to x_hi + x_lo which is at most
+    // M + 1; the result is then reduced once more by adding 1 and folding the top bit again,
+    // which maps M to 0 and leaves smaller values unchanged
+    let x = (x & M) + (x >> 31);
+    let t = x + 1;
+    (t & M) + (t >> 31) - 1
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Multiplies two elements of the complex extension CM31 represented by their base field
+/// coordinates.
+#[inline(always)]
+fn cm31_mul(a: [BaseElement; 2], b: [BaseElement; 2]) -> [BaseElement; 2] {
+    <BaseElement as ExtensibleField<2>>::mul(a, b)
+}
@@ -0,0 +1,347 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use num_bigint::BigUint;
+use proptest::prelude::*;
+use rand_utils::rand_value;
+
+use super::{BaseElement, DeserializationError, FieldElement, Serializable, StarkField};
+use crate::field::{ExtensionOf, QuadExtension, QuartExtension};
+
+// MANUAL TESTS
+// ================================================================================================
+
+#[test]
+fn add() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(r, r + BaseElement::ZERO);
+
+    // test addition within bounds
+    assert_eq!(BaseElement::from(5u8), BaseElement::from(2u8) + BaseElement::from(3u8));
+
+    // test overflow
+    let t = BaseElement::new(super::M - 1);
+    assert_eq!(BaseElement::ZERO, t + BaseElement::ONE);
+    assert_eq!(BaseElement::ONE, t + BaseElement::from(2u8));
+    assert_eq!(BaseElement::new(super::M - 2), t + t);
+}
+
+#[test]
+fn sub() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(r, r - BaseElement::ZERO);
+
+    // test subtraction within bounds
+    assert_eq!(BaseElement::from(2u8), BaseElement::from(5u8) - BaseElement::from(3u8));
+
+    // test underflow
+    let expected = BaseElement::new(super::M - 2);
+    assert_eq!(expected, BaseElement::from(3u8) - BaseElement::from(5u8));
+    assert_eq!(BaseElement::ONE, BaseElement::ZERO - BaseElement::new(super::M - 1));
+}
+
+#[test]
+fn mul() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(BaseElement::ZERO, r * BaseElement::ZERO);
+    assert_eq!(r, r * BaseElement::ONE);
+
+    // test multiplication within bounds
+    assert_eq!(BaseElement::from(15u8), BaseElement::from(5u8) * BaseElement::from(3u8));
+
+    // test overflow
+    let m = super::M;
+    let t = BaseElement::new(m - 1);
+    assert_eq!(BaseElement::ONE, t * t);
+    assert_eq!(BaseElement::new(m - 2), t * BaseElement::from(2u8));
+    assert_eq!(BaseElement::new(m - 4), t * BaseElement::from(4u8));
+
+    let t = m.div_ceil(2);
+    assert_eq!(BaseElement::ONE, BaseElement::new(t) * BaseElement::from(2u8));
+}
+
+#[test]
+fn exp() {
+    let a = BaseElement::ZERO;
+    assert_eq!(a.exp(0), BaseElement::ONE);
+    assert_eq!(a.exp(1), BaseElement::ZERO);
+
+    let a = BaseElement::ONE;
+    assert_eq!(a.exp(0), BaseElement::ONE);
+    assert_eq!(a.exp(1), BaseElement::ONE);
+    assert_eq!(a.exp(3), BaseElement::ONE);
+
+    let a: BaseElement = rand_value();
+    assert_eq!(a.exp(3), a * a * a);
+}
+
+#[test]
+fn inv() {
+    // identity
+    assert_eq!(BaseElement::ONE, BaseElement::inv(BaseElement::ONE));
+    assert_eq!(BaseElement::ZERO, BaseElement::inv(BaseElement::ZERO));
+}
+
+#[test]
+fn element_as_int() {
+    let v = u32::MAX;
+    let e = BaseElement::new(v);
+    assert_eq!((v % super::M) as u64, e.as_int());
+
+    // the modulus itself is reduced to zero
+    assert_eq!(0, BaseElement::new(super::M).as_int());
+}
+
+#[test]
+fn generator() {
+    // the generator must have order M - 1 = 2 * 3^2 * 7 * 11 * 31 * 151 * 331
+    let g = BaseElement::GENERATOR;
+    let order = BaseElement::MODULUS - 1;
+    assert_eq!(BaseElement::ONE, g.exp(order));
+    for p in [2, 3, 7, 11, 31, 151, 331] {
+        assert_ne!(BaseElement::ONE, g.exp(order / p));
+    }
+
+    assert_eq!(BaseElement::TWO_ADIC_ROOT_OF_UNITY, g.exp(order >> BaseElement::TWO_ADICITY));
+}
+
+// QUADRATIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quad_mul() {
+    // identity
+    let r: QuadExtension<BaseElement> = rand_value();
+    assert_eq!(<QuadExtension<BaseElement>>::ZERO, r * <QuadExtension<BaseElement>>::ZERO);
+    assert_eq!(r, r * <QuadExtension<BaseElement>>::ONE);
+
+    // test multiplication with reduction by x^2 + 1
+    let a = quad(15, 22);
+    let b = quad(20, super::M - 1);
+    assert_eq!(quad(322, 425), a * b);
+}
+
+#[test]
+fn quad_mul_base() {
+    let a = <QuadExtension<BaseElement>>::new(rand_value(), rand_value());
+    let b0 = rand_value();
+    let b = <QuadExtension<BaseElement>>::new(b0, BaseElement::ZERO);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+#[test]
+fn quad_frobenius() {
+    // conjugate must be equal to raising the element to the power of the field modulus
+    let a: QuadExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(BaseElement::MODULUS), a.conjugate());
+}
+
+// QUARTIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quart_mul() {
+    // identity
+    let r: QuartExtension<BaseElement> = rand_value();
+    assert_eq!(<QuartExtension<BaseElement>>::ZERO, r * <QuartExtension<BaseElement>>::ZERO);
+    assert_eq!(r, r * <QuartExtension<BaseElement>>::ONE);
+
+    // test multiplication with reduction by i^2 + 1 and u^2 - (2 + i)
+    let a = quart(15, 22, 8, 3);
+    let b = quart(20, 22, 6, super::M - 1);
+    assert_eq!(quart(super::M - 92, 841, 206, 353), a * b);
+}
+
+#[test]
+fn quart_mul_base() {
+    let a: QuartExtension<BaseElement> = rand_value();
+    let b0: BaseElement = rand_value();
+    let b = <QuartExtension<BaseElement>>::from(b0);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+#[test]
+fn quart_frobenius() {
+    // conjugate must be equal to raising the element to the power of the field modulus
+    let a: QuartExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(BaseElement::MODULUS), a.conjugate());
+}
+
+// SERIALIZATION AND DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn try_from_slice() {
+    let bytes = vec![1, 0, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().as_int());
+
+    let bytes = vec![1, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+
+    let bytes = vec![1, 0, 0, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+
+    let bytes = vec![255, 255, 255, 127];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+}
+
+#[test]
+fn serialization() {
+    let a: QuartExtension<BaseElement> = rand_value();
+    let bytes = a.to_bytes();
+    assert_eq!(16, bytes.len());
+    assert_eq!(a, QuartExtension::<BaseElement>::try_from(bytes.as_slice()).unwrap());
+
+    let b = BaseElement::new(7);
+    assert_eq!(vec![7, 0, 0, 0], b.to_bytes());
+}
+
+#[test]
+fn bytes_as_elements() {
+    let elements = vec![
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+    ];
+
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&elements[0].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[1].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[2].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[3].0.to_le_bytes());
+    bytes.extend_from_slice(&BaseElement::new(5).0.to_le_bytes());
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[..16]) };
+    assert!(result.is_ok());
+    assert_eq!(elements, result.unwrap());
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[..17]) };
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[1..17]) };
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
+// RANDOMIZED TESTS
+// ================================================================================================
+
+proptest! {
+
+    #[test]
+    fn add_proptest(a in any::<u32>(), b in any::<u32>()) {
+        let v1 = BaseElement::new(a);
+        let v2 = BaseElement::new(b);
+        let result = v1 + v2;
+
+        let expected = ((a % super::M) as u64 + (b % super::M) as u64) % super::M as u64;
+        prop_assert_eq!(expected, result.as_int());
+    }
+
+    #[test]
+    fn sub_proptest(a in any::<u32>(), b in any::<u32>()) {
+        let v1 = BaseElement::new(a);
+        let v2 = BaseElement::new(b);
+        let result = v1 - v2;
+
+        let a = a % super::M;
+        let b = b % super::M;
+        let expected = if a < b { super::M - b + a } else { a - b };
+
+        prop_assert_eq!(expected as u64, result.as_int());
+    }
+
+    #[test]
+    fn mul_proptest(a in any::<u32>(), b in any::<u32>()) {
+        let v1 = BaseElement::new(a);
+        let v2 = BaseElement::new(b);
+        let result = v1 * v2;
+
+        let expected = ((a as u64) * (b as u64)) % super::M as u64;
+        prop_assert_eq!(expected, result.as_int());
+    }
+
+    #[test]
+    fn exp_proptest(a in any::<u32>(), b in any::<u64>()) {
+        let result = BaseElement::new(a).exp(b);
+
+        let b = BigUint::from(b);
+        let m = BigUint::from(super::M);
+        let expected = BigUint::from(a).modpow(&b, &m).to_u64_digits().first().copied();
+        prop_assert_eq!(expected.unwrap_or(0), result.as_int());
+    }
+
+    #[test]
+    fn inv_proptest(a in any::<u32>()) {
+        let a = BaseElement::new(a);
+        let b = a.inv();
+
+        let expected = if a == BaseElement::ZERO { BaseElement::ZERO } else { BaseElement::ONE };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    // QUADRATIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn quad_mul_inv_proptest(a0 in any::<u32>(), a1 in any::<u32>()) {
+        let a = quad(a0, a1);
+        let b = a.inv();
+
+        let expected = if a == QuadExtension::<BaseElement>::ZERO {
+            QuadExtension::<BaseElement>::ZERO
+        } else {
+            QuadExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    // QUARTIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn quart_mul_inv_proptest(
+        a0 in any::<u32>(),
+        a1 in any::<u32>(),
+        a2 in any::<u32>(),
+        a3 in any::<u32>(),
+    ) {
+        let a = quart(a0, a1, a2, a3);
+        let b = a.inv();
+
+        let expected = if a == QuartExtension::<BaseElement>::ZERO {
+            QuartExtension::<BaseElement>::ZERO
+        } else {
+            QuartExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn quad(a0: u32, a1: u32) -> QuadExtension<BaseElement> {
+    QuadExtension::new(BaseElement::new(a0), BaseElement::new(a1))
+}
+
+fn quart(a0: u32, a1: u32, a2: u32, a3: u32) -> QuartExtension<BaseElement> {
+    QuartExtension::new(
+        BaseElement::new(a0),
+        BaseElement::new(a1),
+        BaseElement::new(a2),
+        BaseElement::new(a3),
+    )
+}
@@ -24,7 +24,7 @@ use utils::{AsBytes, Deserializable, DeserializationError, Randomizable, Seriali
 /// Moreover, it defines interfaces for serializing and deserializing field elements.
 ///
 /// The elements could be in a prime field or an extension of a prime field. Currently, only
-/// quadratic and cubic field extensions are supported.
+/// quadratic, cubic, and quartic field extensions are supported.
 pub trait FieldElement:
     Copy
     + Clone
@@ -115,11 +115,34 @@ pub trait FieldElement:
     }
 
     /// Exponentiates this field element by `power` parameter.
+    #[cfg(not(feature = "constant-time"))]
     #[must_use]
     fn exp(self, power: Self::PositiveInteger) -> Self {
         self.exp_vartime(power)
     }
 
+    /// Exponentiates this field element by `power` parameter.
+    ///
+    /// This implementation processes all bits of `power` and performs the same sequence of field
+    /// operations for any `power`.
+    #[cfg(feature = "constant-time")]
+    #[must_use]
+    fn exp(self, power: Self::PositiveInteger) -> Self {
+        let int_one = Self::PositiveInteger::from(1u32);
+        let num_bits = (core::mem::size_of::<Self::PositiveInteger>() * 8) as u32;
+
+        let mut r = Self::ONE;
+        for i in (0..num_bits).rev() {
+            r = r.square();
+            // multiply r by self if the bit is set, and by ONE otherwise; the selection is done
+            // arithmetically to avoid branching on the bits of the power
+            let bit = Self::from(((power >> i) & int_one == int_one) as u8);
+            r *= Self::ONE + (self - Self::ONE) * bit;
+        }
+
+        r
+    }
+
     /// Exponentiates this field element by `power` parameter.
     /// This function is expressly variable time, to speed-up verifier computations.
     #[must_use]
@@ -157,6 +180,40 @@ pub trait FieldElement:
     #[must_use]
     fn conjugate(&self) -> Self;
 
+    // BATCH ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds elements of `b` to the corresponding elements of `a` and stores the results in `a`.
+    ///
+    /// Field implementations may override this method with a vectorized implementation.
+    ///
+    /// # Panics
+    /// Panics if lengths of `a` and `b` are not the same.
+    fn add_slices(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "number of values must be the same for both slices");
+        a.iter_mut().zip(b).for_each(|(a, &b)| *a += b);
+    }
+
+    /// Multiplies elements of `a` by the corresponding elements of `b` and stores the results in
+    /// `a`.
+    ///
+    /// Field implementations may override this method with a vectorized implementation.
+    ///
+    /// # Panics
+    /// Panics if lengths of `a` and `b` are not the same.
+    fn mul_slices(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "number of values must be the same for both slices");
+        a.iter_mut().zip(b).for_each(|(a, &b)| *a *= b);
+    }
+
+    /// Multiplies all elements of `a` by the base field element `b`.
+    ///
+    /// Field implementations may override this method with a vectorized implementation.
+    fn mul_base_slice(a: &mut [Self], b: Self::BaseField) {
+        let b = Self::from(b);
+        a.iter_mut().for_each(|a| *a *= b);
+    }
+
     // BASE ELEMENT CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
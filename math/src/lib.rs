@@ -16,38 +16,62 @@
 //! * Drawing random and pseudo-random elements from the field.
 //! * Computing roots of unity of a given order.
 //!
-//! Currently, there are two implementations of finite fields:
+//! Currently, there are five implementations of finite fields:
 //!
 //! * A 128-bit field with modulus 2<sup>128</sup> - 45 * 2<sup>40</sup> + 1. This field was not
 //!   chosen with any significant thought given to performance, and the implementation of most
 //!   operations is sub-optimal as well. Proofs generated in this field can support security level
 //!   of ~100 bits. If higher level of security is desired, proofs must be generated in a quadratic
-//!   extension of the field.
+//!   extension of the field. An implementation of the same field which uses Montgomery
+//!   representation and faster multiplication is available in the
+//!   [f128::montgomery](crate::fields::f128::montgomery) module.
 //! * A 62-bit field with modulus 2<sup>62</sup> - 111 * 2<sup>39</sup> + 1. This field supports
 //!   very fast modular arithmetic including branchless multiplication and addition. To achieve
 //!   adequate security (i.e. ~100 bits), proofs must be generated in a quadratic extension of this
 //!   field. For higher levels of security, a cubic extension field should be used.
-//! * A 64-bit field with modulus 2<sup>64</sup> - 2<sup>32</sup> + 1. This field is about 15%
-//!   slower than the 62-bit field described above, but it has a number of other attractive
-//!   properties. To achieve adequate security (i.e. ~100 bits), proofs must be generated in a
-//!   quadratic extension of this field. For higher levels of security, a cubic extension field
-//!   should be used.
+//! * A 64-bit field with modulus 2<sup>64</sup> - 2<sup>32</sup> + 1 (the Goldilocks field),
+//!   implemented in the [f64](crate::fields::f64) module. This field is about 15% slower than
+//!   the 62-bit field described above, but it has a number of other attractive properties. To
+//!   achieve adequate security (i.e. ~100 bits), proofs must be generated in a quadratic
+//!   extension of this field. For higher levels of security, a cubic extension field should be
+//!   used.
+//! * A 31-bit field with modulus 2<sup>31</sup> - 2<sup>27</sup> + 1 (the BabyBear field),
+//!   implemented in the [f31](crate::fields::f31) module. Elements of this field fit into 32-bit
+//!   machine words which makes it well-suited for verifiers running on 32-bit targets (e.g.,
+//!   RISC-V). To achieve adequate security, proofs must be generated in a quartic extension of
+//!   this field.
+//! * A 31-bit field with modulus 2<sup>31</sup> - 1 (the Mersenne-31 field), implemented in the
+//!   [m31](crate::fields::m31) module. Modular reduction in this field requires only shifts and
+//!   additions, which makes it one of the fastest fields for proof generation. The multiplicative
+//!   group of this field is not two-adic, and thus, polynomials over this field are evaluated
+//!   over [circle domains](circle) rather than multiplicative subgroups. To achieve adequate
+//!   security, proofs must be generated in a quartic extension of this field.
 //!
 //! ## Extension fields
 //!
-//! Currently, the library provides a generic way to create quadratic and cubic extensions of
-//! supported STARK fields. This can be done by implementing [ExtensibleField] trait for
-//! degrees 2 and 3.
+//! Currently, the library provides a generic way to create quadratic, cubic, and quartic
+//! extensions of supported STARK fields. This can be done by implementing [ExtensibleField] trait
+//! for degrees 2, 3, and 4.
 //!
 //! Quadratic extension fields are defined using the following irreducible polynomials:
 //! * For [f62](crate::fields::f62) field, the polynomial is x<sup>2</sup> - x - 1.
 //! * For [f64](crate::fields::f64) field, the polynomial is x<sup>2</sup> - x + 2.
 //! * For [f128](crate::fields::f128) field, the polynomial is x<sup>2</sup> - x - 1.
+//! * For [f31](crate::fields::f31) field, the polynomial is x<sup>2</sup> - 11.
+//! * For [m31](crate::fields::m31) field, the polynomial is x<sup>2</sup> + 1.
 //!
 //! Cubic extension fields are defined using the following irreducible polynomials:
 //! * For [f62](crate::fields::f62) field, the polynomial is x<sup>3</sup> + 2x + 2.
 //! * For [f64](crate::fields::f64) field, the polynomial is x<sup>3</sup> - x - 1.
 //! * For [f128](crate::fields::f128) field, cubic extensions are not supported.
+//! * For [f31](crate::fields::f31) field, the polynomial is x<sup>3</sup> - 2.
+//! * For [m31](crate::fields::m31) field, cubic extensions are not supported.
+//!
+//! Quartic extension fields are currently supported only for the following fields:
+//! * For [f31](crate::fields::f31) field, the extension is defined using the irreducible
+//!   polynomial x<sup>4</sup> - 11.
+//! * For [m31](crate::fields::m31) field, the extension is defined as a quadratic extension of
+//!   the quadratic extension above using the irreducible polynomial u<sup>2</sup> - (2 + i).
 //!
 //! # Polynomials
 //! [Polynomials](polynom) module implements basic polynomial operations such as:
@@ -55,7 +79,11 @@
 //! * Evaluation of a polynomial at a single or multiple point.
 //! * Interpolation of a polynomial from a set of points (using
 //!   [Lagrange](https://en.wikipedia.org/wiki/Lagrange_polynomial) interpolation).
+//! * Evaluation of a polynomial defined by its values at a set of points (using barycentric
+//!   Lagrange interpolation).
 //! * Addition, multiplication, subtraction, and division of polynomials.
+//! * FFT-based multiplication of large polynomials.
+//! * Division with remainder and greatest common divisor of polynomials.
 //! * Synthetic polynomial division (using
 //!   [Ruffini's](https://en.wikipedia.org/wiki/Ruffini%27s_rule) method).
 //!
@@ -64,6 +92,15 @@
 //! field (also called [Number-theoretic transform](https://en.wikipedia.org/wiki/Discrete_Fourier_transform_(general)#Number-theoretic_transform)).
 //! This can be used to interpolate and evaluate polynomials in *O(n log n)* time as long as
 //! the domain of the polynomial is a multiplicative subgroup with size which is a power of 2.
+//! Twiddles for domains of the same size can be re-used across multiple FFT operations via
+//! [TwiddleCache](fft::TwiddleCache).
+//!
+//! # Circle domains
+//! [Circle](circle) module contains an implementation of evaluation domains defined over the
+//! circle curve x<sup>2</sup> + y<sup>2</sup> = 1 in the Mersenne-31 field, together with an FFT
+//! algorithm for such domains (circle FFT). Both circle domains and two-adic cosets
+//! ([EvaluationDomain](fft::EvaluationDomain)) implement the [FftDomain](fft::FftDomain) trait
+//! which can be used to write low-degree extension code generic over the type of the domain.
 //!
 //! # Concurrent execution
 //!
@@ -75,23 +112,53 @@
 //!   - [get_power_series()]
 //!   - [get_power_series_with_offset()]
 //!   - [add_in_place()]
+//!   - [mul_slices()]
 //!   - [mul_acc()]
 //!   - [batch_inversion()]
+//!   - [batch_inversion_in_place()]
 //! * `fft` module:
 //!   - [evaluate_poly()](fft::evaluate_poly())
 //!   - [evaluate_poly_with_offset()](fft::evaluate_poly_with_offset())
 //!   - [interpolate_poly()](fft::interpolate_poly())
 //!   - [interpolate_poly_with_offset()][fft::interpolate_poly_with_offset()]
 //!   - [get_twiddles()](fft::get_twiddles())
-//!   - [get_inv_twiddles()](fft::get_twiddles())
+//!   - [get_inv_twiddles()](fft::get_inv_twiddles())
 //!
 //! Number of threads can be configured via `RAYON_NUM_THREADS` environment variable
+//!
+//! FFT operations over domains smaller than [fft::DEFAULT_MIN_CONCURRENT_SIZE] are executed in a
+//! single thread. This threshold can be tuned via [fft::set_min_concurrent_size()].
+//!
+//! # Vectorized arithmetic
+//!
+//! Element-wise operations over slices of field elements are performed via
+//! [FieldElement::add_slices()], [FieldElement::mul_slices()], and
+//! [FieldElement::mul_base_slice()]; these are used by [add_in_place()], [mul_slices()], and by
+//! FFT-based interpolation. When the crate is compiled with `simd` feature enabled, the
+//! [f31](crate::fields::f31) field implements these operations (and, via the base field, addition
+//! and scaling of extension field elements) using AVX2
+//! instructions on x86_64 targets (if AVX2 is supported by the CPU at runtime) and NEON
+//! instructions on aarch64 targets. For all other fields and targets, scalar implementations
+//! are used.
+//!
+//! # Constant-time arithmetic
+//!
+//! When the crate is compiled with `constant-time` feature enabled, arithmetic operations,
+//! exponentiation via [FieldElement::exp()], inversion, and equality checks in all fields and
+//! field extensions avoid branches and table lookups which depend on the values of the operands.
+//! This comes at a cost of performance (e.g., exponentiation processes all bits of the exponent,
+//! and inversion is computed via exponentiation), but protects private values from timing side
+//! channels. [FieldElement::exp_vartime()] remains variable-time regardless of this feature.
 
 #![no_std]
 
 #[macro_use]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod circle;
 pub mod fft;
 pub mod polynom;
 
@@ -103,10 +170,13 @@ pub mod fields {
     //! This module contains concrete implementations of base STARK fields as well as extensions
     //! of these field.
 
-    pub use super::field::{f128, f62, f64, CubeExtension, QuadExtension};
+    pub use super::field::{
+        f128, f31, f62, f64, m31, CubeExtension, QuadExtension, QuartExtension,
+    };
 }
 
 mod utils;
 pub use crate::utils::{
-    add_in_place, batch_inversion, get_power_series, get_power_series_with_offset, mul_acc,
+    add_in_place, batch_inversion, batch_inversion_in_place, get_power_series,
+    get_power_series_with_offset, mul_acc, mul_slices,
 };
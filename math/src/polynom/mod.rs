@@ -8,7 +8,11 @@
 //! This module provides a set of function for basic polynomial operations, including:
 //! - Polynomial evaluation using Horner method.
 //! - Polynomial interpolation using Lagrange method.
+//! - Evaluation of polynomials defined by their values at arbitrary points using barycentric
+//!   Lagrange interpolation.
 //! - Polynomial addition, subtraction, multiplication, and division.
+//! - FFT-based multiplication of large polynomials.
+//! - Polynomial division with remainder and greatest common divisor computation.
 //! - Synthetic polynomial division for efficient division by polynomials of the form
 //!   `x`^`a` - `b`.
 //!
@@ -29,11 +33,21 @@ use core::mem;
 
 use utils::group_slice_elements;
 
-use crate::{field::FieldElement, utils::batch_inversion};
+use crate::{
+    fft,
+    field::{FieldElement, StarkField},
+    utils::{batch_inversion, batch_inversion_in_place, mul_slices},
+};
 
 #[cfg(test)]
 mod tests;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Minimum length of both polynomials at which [mul()] switches to FFT-based multiplication.
+const MIN_FFT_MUL_SIZE: usize = 64;
+
 // POLYNOMIAL EVALUATION
 // ================================================================================================
 
@@ -215,6 +229,111 @@ where
     result
 }
 
+// BARYCENTRIC EVALUATION
+// ================================================================================================
+
+/// Returns barycentric weights for the provided set of X coordinates.
+///
+/// The weight for coordinate x<sub>j</sub> is defined as 1 / prod(x<sub>j</sub> - x<sub>k</sub>)
+/// for all k != j. Once computed, the weights can be used to evaluate a polynomial defined by
+/// its values at the X coordinates at any point via [eval_barycentric()] in O(`n`) time, without
+/// interpolating the polynomial first. The weights depend only on the X coordinates, and thus,
+/// can be re-used for any set of Y coordinates.
+///
+/// The complexity of computing the weights is O(`n`<sup>2</sup>), where `n` is the number of X
+/// coordinates; the X coordinates are not required to form a multiplicative subgroup (or a coset
+/// of one).
+///
+/// # Panics
+/// Panics if the X coordinates are not distinct.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let xs: Vec<BaseElement> = rand_vector(16);
+/// let ys: Vec<BaseElement> = rand_vector(16);
+/// let weights = get_barycentric_weights(&xs);
+///
+/// let p = interpolate(&xs, &ys, false);
+/// let x = BaseElement::new(42);
+/// assert_eq!(eval(&p, x), eval_barycentric(&xs, &ys, &weights, x));
+/// ```
+pub fn get_barycentric_weights<E>(xs: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    let mut weights = xs
+        .iter()
+        .enumerate()
+        .map(|(j, &xj)| {
+            let mut denominator = E::ONE;
+            for (k, &xk) in xs.iter().enumerate() {
+                if k != j {
+                    denominator *= xj - xk;
+                }
+            }
+            assert!(denominator != E::ZERO, "X coordinates must be distinct");
+            denominator
+        })
+        .collect::<Vec<_>>();
+
+    batch_inversion_in_place(&mut weights);
+    weights
+}
+
+/// Evaluates a polynomial defined by its values at a set of X coordinates at the specified
+/// point `x` using barycentric Lagrange interpolation.
+///
+/// The polynomial is the unique polynomial of degree smaller than `xs.len()` which evaluates to
+/// `ys[j]` at `xs[j]` for all j. The `weights` must be barycentric weights for `xs` computed via
+/// [get_barycentric_weights()]. The evaluation is computed as:
+///
+/// l(x) * sum(w<sub>j</sub> * y<sub>j</sub> / (x - x<sub>j</sub>))
+///
+/// where l(x) = prod(x - x<sub>j</sub>). This requires O(`n`) multiplications and a single
+/// batch inversion, where `n` is the number of X coordinates.
+///
+/// # Panics
+/// Panics if the number of X coordinates, Y coordinates, and weights is not the same.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// // p(x) = x^2 + 1
+/// let xs = [BaseElement::new(1), BaseElement::new(2), BaseElement::new(3)];
+/// let ys = [BaseElement::new(2), BaseElement::new(5), BaseElement::new(10)];
+/// let weights = get_barycentric_weights(&xs);
+///
+/// assert_eq!(BaseElement::new(17), eval_barycentric(&xs, &ys, &weights, BaseElement::new(4)));
+/// assert_eq!(BaseElement::new(5), eval_barycentric(&xs, &ys, &weights, BaseElement::new(2)));
+/// ```
+pub fn eval_barycentric<E>(xs: &[E], ys: &[E], weights: &[E], x: E) -> E
+where
+    E: FieldElement,
+{
+    assert_eq!(xs.len(), ys.len(), "number of X and Y coordinates must be the same");
+    assert_eq!(xs.len(), weights.len(), "number of X coordinates and weights must be the same");
+
+    // if x is one of the X coordinates, the corresponding Y coordinate is the result
+    if let Some(j) = xs.iter().position(|&xj| xj == x) {
+        return ys[j];
+    }
+
+    let mut differences = xs.iter().map(|&xj| x - xj).collect::<Vec<_>>();
+    let vanishing_value = differences.iter().fold(E::ONE, |acc, &d| acc * d);
+    batch_inversion_in_place(&mut differences);
+
+    let sum = differences
+        .iter()
+        .zip(ys.iter().zip(weights))
+        .fold(E::ZERO, |acc, (&inv_d, (&y, &w))| acc + w * y * inv_d);
+
+    vanishing_value * sum
+}
+
 // POLYNOMIAL MATH OPERATIONS
 // ================================================================================================
 
@@ -299,6 +418,9 @@ where
 /// polynomial will be in the coefficient form as well. The length of the returned vector
 /// will be a.len() + b.len() - 1.
 ///
+/// For small polynomials, schoolbook multiplication is used. When both polynomials are large,
+/// the multiplication is performed via [mul_fft()].
+///
 /// # Examples
 /// ```
 /// # use winter_math::polynom::*;
@@ -322,6 +444,13 @@ where
     E: FieldElement,
 {
     let result_len = a.len() + b.len() - 1;
+    if a.len() >= MIN_FFT_MUL_SIZE
+        && b.len() >= MIN_FFT_MUL_SIZE
+        && result_len.next_power_of_two().ilog2() <= E::BaseField::TWO_ADICITY
+    {
+        return mul_fft(a, b);
+    }
+
     let mut result = vec![E::ZERO; result_len];
     for i in 0..a.len() {
         for j in 0..b.len() {
@@ -332,6 +461,62 @@ where
     result
 }
 
+/// Returns a polynomial resulting from multiplying two polynomials together using FFT.
+///
+/// Both polynomials are evaluated over a multiplicative subgroup of size equal to the smallest
+/// power of two which is greater than or equal to a.len() + b.len() - 1; the evaluations are
+/// multiplied point-wise and the product is interpolated back into coefficient form. The
+/// complexity of this operation is O(`n` log(`n`)), where `n` is the size of the subgroup.
+///
+/// Polynomials `a` and `b` are expected to be in the coefficient form, and the returned
+/// polynomial will be in the coefficient form as well. The length of the returned vector
+/// will be a.len() + b.len() - 1.
+///
+/// # Panics
+/// Panics if:
+/// * Either of the polynomials is empty.
+/// * Base field of `E` does not contain a multiplicative subgroup of size
+///   (a.len() + b.len() - 1).next_power_of_two().
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let p1: Vec<BaseElement> = rand_vector(100);
+/// let p2: Vec<BaseElement> = rand_vector(50);
+///
+/// let product = mul_fft(&p1, &p2);
+/// assert_eq!(149, product.len());
+///
+/// let x = BaseElement::new(42);
+/// assert_eq!(eval(&p1, x) * eval(&p2, x), eval(&product, x));
+/// ```
+pub fn mul_fft<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    assert!(!a.is_empty() && !b.is_empty(), "cannot multiply empty polynomials");
+    let result_len = a.len() + b.len() - 1;
+    let domain_size = core::cmp::max(result_len.next_power_of_two(), 2);
+
+    let twiddles = fft::get_twiddles::<E::BaseField>(domain_size);
+    let mut result = a.to_vec();
+    result.resize(domain_size, E::ZERO);
+    fft::evaluate_poly(&mut result, &twiddles);
+
+    let mut b_evaluations = b.to_vec();
+    b_evaluations.resize(domain_size, E::ZERO);
+    fft::evaluate_poly(&mut b_evaluations, &twiddles);
+
+    mul_slices(&mut result, &b_evaluations);
+
+    let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(domain_size);
+    fft::interpolate_poly(&mut result, &inv_twiddles);
+    result.truncate(result_len);
+    result
+}
+
 /// Returns a polynomial resulting from multiplying a given polynomial by a scalar value.
 ///
 /// Specifically, multiplies every coefficient of polynomial `p` by constant `k` and returns
@@ -398,6 +583,48 @@ where
 /// assert_eq!(expected, div(&p1, &p2));
 /// ```
 pub fn div<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    div_rem(a, b).0
+}
+
+/// Returns the quotient and the remainder resulting from dividing one polynomial by another.
+///
+/// Specifically, divides polynomial `a` by polynomial `b` and returns a tuple `(q, r)` such that
+/// a(x) = q(x) * b(x) + r(x) and the degree of r(x) is smaller than the degree of b(x). Both
+/// polynomials are expected to be in the coefficient form, and the returned polynomials will be
+/// in the coefficient form as well. The length of the quotient will be
+/// degree_of(a) - degree_of(b) + 1, and the length of the remainder will be degree_of(b) (i.e.,
+/// the remainder may contain leading ZERO coefficients, and when `b` is a constant, the remainder
+/// is an empty vector).
+///
+/// # Panics
+/// Panics if:
+/// * Polynomial `b` is empty.
+/// * Degree of polynomial `b` is zero and the constant coefficient is ZERO.
+/// * The degree of polynomial `b` is greater than the degree of polynomial `a`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// // p1(x) = x^3 + x^2 + 2 * x + 3
+/// let p1 = [
+///     BaseElement::new(3),
+///     BaseElement::new(2),
+///     BaseElement::new(1),
+///     BaseElement::new(1),
+/// ];
+/// // p2(x) = x^2 + 2
+/// let p2 = [BaseElement::new(2), BaseElement::ZERO, BaseElement::ONE];
+///
+/// // expected quotient = x + 1, expected remainder = 1
+/// let (quotient, remainder) = div_rem(&p1, &p2);
+/// assert_eq!(vec![BaseElement::ONE, BaseElement::ONE], quotient);
+/// assert_eq!(vec![BaseElement::ONE, BaseElement::ZERO], remainder);
+/// ```
+pub fn div_rem<E>(a: &[E], b: &[E]) -> (Vec<E>, Vec<E>)
 where
     E: FieldElement,
 {
@@ -411,9 +638,10 @@ where
         assert!(b[0] != E::ZERO, "cannot divide polynomial by zero");
     }
 
+    let inv_lead = b[bpos].inv();
     let mut result = vec![E::ZERO; apos - bpos + 1];
     for i in (0..result.len()).rev() {
-        let quot = a[apos] / b[bpos];
+        let quot = a[apos] * inv_lead;
         result[i] = quot;
         for j in (0..bpos).rev() {
             a[i + j] -= b[j] * quot;
@@ -421,7 +649,52 @@ where
         apos = apos.wrapping_sub(1);
     }
 
-    result
+    // after the reduction, the lowest bpos coefficients of a contain the remainder
+    a.truncate(bpos);
+    (result, a)
+}
+
+/// Returns the greatest common divisor of two polynomials.
+///
+/// The divisor is computed using the Euclidean algorithm and is returned as a monic polynomial
+/// (i.e., its leading coefficient is ONE) without leading ZERO coefficients. If both `a` and `b`
+/// are ZERO polynomials, an empty vector is returned.
+///
+/// Polynomials `a` and `b` are expected to be in the coefficient form, and the returned
+/// polynomial will be in the coefficient form as well.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// // p1(x) = (x + 1) * (x + 2)
+/// let p1 = mul(&[BaseElement::ONE, BaseElement::ONE], &[BaseElement::new(2), BaseElement::ONE]);
+/// // p2(x) = (x + 1) * (x + 3)
+/// let p2 = mul(&[BaseElement::ONE, BaseElement::ONE], &[BaseElement::new(3), BaseElement::ONE]);
+///
+/// // expected result = x + 1
+/// assert_eq!(vec![BaseElement::ONE, BaseElement::ONE], gcd(&p1, &p2));
+/// ```
+pub fn gcd<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    let mut a = remove_leading_zeros(a);
+    let mut b = remove_leading_zeros(b);
+    if a.len() < b.len() {
+        mem::swap(&mut a, &mut b);
+    }
+
+    while !b.is_empty() {
+        let (_, remainder) = div_rem(&a, &b);
+        a = mem::replace(&mut b, remove_leading_zeros(&remainder));
+    }
+
+    if let Some(&lead) = a.last() {
+        let inv_lead = lead.inv();
+        a.iter_mut().for_each(|coeff| *coeff *= inv_lead);
+    }
+    a
 }
 
 /// Returns a polynomial resulting from dividing a polynomial by a polynomial of special form.
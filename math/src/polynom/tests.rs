@@ -5,6 +5,8 @@
 
 use alloc::vec::Vec;
 
+use rand_utils::rand_vector;
+
 use super::remove_leading_zeros;
 use crate::{
     field::{f128::BaseElement, FieldElement, StarkField},
@@ -38,6 +40,24 @@ fn eval() {
     assert_eq!(poly[0] + poly[1] * x + poly[2] * x2 + poly[3] * x3, super::eval(&poly, x));
 }
 
+#[test]
+fn eval_barycentric() {
+    let xs: Vec<BaseElement> = rand_vector(12);
+    let ys: Vec<BaseElement> = rand_vector(12);
+    let weights = super::get_barycentric_weights(&xs);
+    let poly = super::interpolate(&xs, &ys, false);
+
+    // evaluation at random points matches evaluation of the interpolated polynomial
+    for x in rand_vector::<BaseElement>(8) {
+        assert_eq!(super::eval(&poly, x), super::eval_barycentric(&xs, &ys, &weights, x));
+    }
+
+    // evaluation at the interpolation points returns the original values
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        assert_eq!(y, super::eval_barycentric(&xs, &ys, &weights, x));
+    }
+}
+
 #[test]
 fn add() {
     let poly1: [BaseElement; 3] = [
@@ -158,6 +178,68 @@ fn div() {
     assert_eq!(vec![BaseElement::new(11269864713250585702u128)], super::div(&poly3, &poly1));
 }
 
+#[test]
+fn mul_fft() {
+    // small polynomials: compare against schoolbook multiplication
+    let poly1: Vec<BaseElement> = rand_vector(7);
+    let poly2: Vec<BaseElement> = rand_vector(12);
+    assert_eq!(super::mul(&poly1, &poly2), super::mul_fft(&poly1, &poly2));
+    assert_eq!(super::mul(&poly1[..1], &poly2[..1]), super::mul_fft(&poly1[..1], &poly2[..1]));
+
+    // large polynomials: mul() uses FFT, so check the result against evaluations of the factors
+    let poly1: Vec<BaseElement> = rand_vector(300);
+    let poly2: Vec<BaseElement> = rand_vector(129);
+    let product = super::mul(&poly1, &poly2);
+    assert_eq!(428, product.len());
+    for x in rand_vector::<BaseElement>(8) {
+        assert_eq!(super::eval(&poly1, x) * super::eval(&poly2, x), super::eval(&product, x));
+    }
+}
+
+#[test]
+fn div_rem() {
+    let poly1: Vec<BaseElement> = rand_vector(9);
+    let poly2: Vec<BaseElement> = rand_vector(4);
+    let (quotient, remainder) = super::div_rem(&poly1, &poly2);
+    assert_eq!(6, quotient.len());
+    assert_eq!(3, remainder.len());
+    assert_eq!(poly1, super::add(&super::mul(&quotient, &poly2), &remainder));
+
+    // divides evenly
+    let poly3 = super::mul(&poly1, &poly2);
+    let (quotient, remainder) = super::div_rem(&poly3, &poly2);
+    assert_eq!(poly1, quotient);
+    assert_eq!(Vec::<BaseElement>::new(), remove_leading_zeros(&remainder));
+
+    // division by a constant
+    let k = BaseElement::new(11269864713250585702u128);
+    let (quotient, remainder) = super::div_rem(&poly1, &[k]);
+    assert_eq!(super::mul_by_scalar(&poly1, k.inv()), quotient);
+    assert!(remainder.is_empty());
+}
+
+#[test]
+fn gcd() {
+    let common: Vec<BaseElement> = rand_vector(5);
+    let poly1 = super::mul(&common, &rand_vector::<BaseElement>(4));
+    let poly2 = super::mul(&common, &rand_vector::<BaseElement>(7));
+
+    // the result is monic and divides both polynomials; with overwhelming probability the
+    // random cofactors are co-prime, and thus the result is the monic version of common
+    let expected = super::mul_by_scalar(&common, common[4].inv());
+    assert_eq!(expected, super::gcd(&poly1, &poly2));
+    assert_eq!(expected, super::gcd(&poly2, &poly1));
+
+    // gcd with ZERO polynomial
+    assert_eq!(expected, super::gcd(&common, &[BaseElement::ZERO]));
+    assert_eq!(Vec::<BaseElement>::new(), super::gcd::<BaseElement>(&[], &[BaseElement::ZERO]));
+
+    // co-prime polynomials
+    let poly1 = [BaseElement::ONE, BaseElement::ONE];
+    let poly2 = [BaseElement::new(2), BaseElement::ONE];
+    assert_eq!(vec![BaseElement::ONE], super::gcd(&poly1, &poly2));
+}
+
 #[test]
 fn syn_div() {
     // ----- division by degree 1 polynomial ------------------------------------------------------
@@ -85,7 +85,8 @@ where
 /// Computes element-wise sum of the provided vectors, and stores the result in the first vector.
 ///
 /// When `concurrent` feature is enabled, the summation is performed concurrently in multiple
-/// threads.
+/// threads. When `simd` feature is enabled, fields which provide vectorized arithmetic use it to
+/// add the values.
 ///
 /// # Panics
 /// Panics if lengths of `a` and `b` vectors are not the same.
@@ -110,7 +111,44 @@ where
     E: FieldElement,
 {
     assert!(a.len() == b.len(), "number of values must be the same for both operands");
-    iter_mut!(a).zip(b).for_each(|(a, &b)| *a += b);
+    batch_iter_mut!(a, 1024, |batch: &mut [E], batch_offset: usize| {
+        E::add_slices(batch, &b[batch_offset..batch_offset + batch.len()]);
+    });
+}
+
+/// Computes element-wise product of the provided vectors, and stores the result in the first
+/// vector.
+///
+/// When `concurrent` feature is enabled, the multiplication is performed concurrently in multiple
+/// threads. When `simd` feature is enabled, fields which provide vectorized arithmetic use it to
+/// multiply the values.
+///
+/// # Panics
+/// Panics if lengths of `a` and `b` vectors are not the same.
+///
+/// # Examples
+/// ```
+/// # use winter_math::mul_slices;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(2048);
+/// let b: Vec<BaseElement> = rand_vector(2048);
+///
+/// let mut c = a.clone();
+/// mul_slices(&mut c, &b);
+///
+/// for ((a, b), c) in a.into_iter().zip(b).zip(c) {
+///     assert_eq!(a * b, c);
+/// }
+/// ```
+pub fn mul_slices<E>(a: &mut [E], b: &[E])
+where
+    E: FieldElement,
+{
+    assert!(a.len() == b.len(), "number of values must be the same for both operands");
+    batch_iter_mut!(a, 1024, |batch: &mut [E], batch_offset: usize| {
+        E::mul_slices(batch, &b[batch_offset..batch_offset + batch.len()]);
+    });
 }
 
 /// Multiplies a sequence of values by a scalar and accumulates the results.
@@ -183,6 +221,37 @@ where
     result
 }
 
+/// Replaces each element of the provided sequence with its multiplicative inverse using batch
+/// inversion method.
+///
+/// This is an in-place version of [batch_inversion()]. Any ZEROs in the provided sequence are
+/// left unchanged.
+///
+/// When `concurrent` feature is enabled, the inversion is performed concurrently in multiple
+/// threads.
+///
+/// # Examples
+/// ```
+/// # use winter_math::batch_inversion_in_place;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(2048);
+/// let mut b = a.clone();
+/// batch_inversion_in_place(&mut b);
+///
+/// for (&a, &b) in a.iter().zip(b.iter()) {
+///     assert_eq!(a.inv(), b);
+/// }
+/// ```
+pub fn batch_inversion_in_place<E>(values: &mut [E])
+where
+    E: FieldElement,
+{
+    batch_iter_mut!(values, 1024, |batch: &mut [E], _batch_offset: usize| {
+        serial_batch_inversion_in_place(batch);
+    });
+}
+
 // HELPER FUNCTIONS
 // ------------------------------------------------------------------------------------------------
 
@@ -214,3 +283,24 @@ fn serial_batch_inversion<E: FieldElement>(values: &[E], result: &mut [E]) {
         }
     }
 }
+
+fn serial_batch_inversion_in_place<E: FieldElement>(values: &mut [E]) {
+    let mut prefixes = Vec::with_capacity(values.len());
+    let mut last = E::ONE;
+    for &value in values.iter() {
+        prefixes.push(last);
+        if value != E::ZERO {
+            last *= value;
+        }
+    }
+
+    last = last.inv();
+
+    for (value, prefix) in values.iter_mut().zip(prefixes).rev() {
+        if *value != E::ZERO {
+            let inverse = last * prefix;
+            last *= *value;
+            *value = inverse;
+        }
+    }
+}
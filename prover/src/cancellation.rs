@@ -0,0 +1,181 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "async")]
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::ProverError;
+
+// CANCELLATION TOKEN
+// ================================================================================================
+
+/// A token which can be used to cancel proof generation started via
+/// [Prover::prove_async()](crate::Prover::prove_async).
+///
+/// Clones of a token share the same state; thus, a proving service can keep a clone of the token
+/// passed to the prover, and cancel proof generation (e.g., on a timeout) by invoking
+/// [CancellationToken::cancel()] on the clone. Cancellation is cooperative: the prover checks
+/// the token at the boundaries of proof generation phases, and returns
+/// [ProverError::Cancelled] once it observes that the token has been cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Returns a new token which has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of proof generation which uses this token (or any of its clones).
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Returns true if cancellation has been requested via this token or any of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+// PHASE BOUNDARIES
+// ================================================================================================
+
+/// Marks the end of a proof generation phase.
+///
+/// Yields control back to the executor once so that other tasks can make progress, and then
+/// returns an error if the specified cancellation token (if any) has been cancelled.
+#[cfg(feature = "async")]
+pub(crate) async fn complete_phase(
+    cancellation: Option<&CancellationToken>,
+) -> Result<(), ProverError> {
+    YieldNow { yielded: false }.await;
+    check_cancellation(cancellation)
+}
+
+/// Marks the end of a proof generation phase.
+///
+/// Returns an error if the specified cancellation token (if any) has been cancelled.
+#[cfg(not(feature = "async"))]
+pub(crate) fn complete_phase(cancellation: Option<&CancellationToken>) -> Result<(), ProverError> {
+    check_cancellation(cancellation)
+}
+
+fn check_cancellation(cancellation: Option<&CancellationToken>) -> Result<(), ProverError> {
+    match cancellation {
+        Some(token) if token.is_cancelled() => Err(ProverError::Cancelled),
+        _ => Ok(()),
+    }
+}
+
+/// A future which returns [Poll::Pending] the first time it is polled, and is ready the second
+/// time it is polled.
+#[cfg(feature = "async")]
+struct YieldNow {
+    yielded: bool,
+}
+
+#[cfg(feature = "async")]
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "async")]
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    use super::{check_cancellation, CancellationToken};
+    use crate::ProverError;
+    #[cfg(feature = "async")]
+    use crate::{
+        tests::{build_fib_trace, FibProver},
+        FieldExtension, ProofOptions, Prover,
+    };
+
+    #[test]
+    fn cancellation_token() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        assert_eq!(Ok(()), check_cancellation(Some(&token)));
+        assert_eq!(Ok(()), check_cancellation(None));
+
+        // cancelling a clone cancels the original token
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(Err(ProverError::Cancelled), check_cancellation(Some(&token)));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn prove_async() {
+        let prover = FibProver::new(build_options());
+        let token = CancellationToken::new();
+        let mut future = pin!(prover.prove_async(build_fib_trace(32), &token));
+
+        // proof generation runs to completion if the token is not cancelled
+        let waker = noop_waker();
+        let mut context = Context::from_waker(&waker);
+        let proof = loop {
+            if let Poll::Ready(result) = future.as_mut().poll(&mut context) {
+                break result.unwrap();
+            }
+        };
+        assert_eq!(16, proof.trace_info().length());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn prove_async_cancelled() {
+        let prover = FibProver::new(build_options());
+        let token = CancellationToken::new();
+        let mut future = pin!(prover.prove_async(build_fib_trace(32), &token));
+
+        // the future yields at the end of the first phase; cancelling the token at this point
+        // aborts proof generation once the future is polled again
+        let waker = noop_waker();
+        let mut context = Context::from_waker(&waker);
+        assert!(future.as_mut().poll(&mut context).is_pending());
+        token.cancel();
+        assert_eq!(Poll::Ready(Err(ProverError::Cancelled)), future.as_mut().poll(&mut context));
+    }
+
+    #[cfg(feature = "async")]
+    fn build_options() -> ProofOptions {
+        ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7)
+    }
+
+    /// Returns a waker which does nothing when woken; the tests poll futures in a loop instead.
+    #[cfg(feature = "async")]
+    fn noop_waker() -> Waker {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| RAW_WAKER, |_| {}, |_| {}, |_| {});
+        const RAW_WAKER: RawWaker = RawWaker::new(core::ptr::null(), &VTABLE);
+        // SAFETY: none of the functions in the vtable use the data pointer
+        unsafe { Waker::from_raw(RAW_WAKER) }
+    }
+}
@@ -10,12 +10,14 @@ use air::{
     proof::{Commitments, Context, OodFrame, Proof, Queries, TraceOodFrame},
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
 };
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, RandomCoin, Transcript, TranscriptLabel};
 use fri::FriProof;
 use math::{FieldElement, ToElements};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
+use crate::ProverError;
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -24,7 +26,7 @@ where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: Transcript<BaseField = E::BaseField, Hasher = H>,
 {
     air: &'a A,
     public_coin: R,
@@ -43,13 +45,16 @@ where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
-    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    R: Transcript<BaseField = A::BaseField, Hasher = H>,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new prover channel for the specified `air` and public inputs.
+    ///
+    /// Public inputs are expected to be provided as elements returned from
+    /// [PublicInputs::to_transcript_elements()](air::PublicInputs::to_transcript_elements).
     pub fn new(air: &'a A, mut pub_inputs_elements: Vec<A::BaseField>) -> Self {
-        let context = Context::new::<A::BaseField>(air.trace_info().clone(), air.options().clone());
+        let context = Context::from_air(air);
 
         // build a seed for the public coin; the initial seed is a hash of the proof context and
         // the public inputs, but as the protocol progresses, the coin will be reseeded with the
@@ -68,33 +73,54 @@ where
         }
     }
 
+    /// Returns this channel converted into a channel which draws random values from the field
+    /// `D`.
+    ///
+    /// This is used to draw the out-of-domain point and all subsequent random values from an
+    /// extension of the field in which the composition polynomial is built.
+    pub fn into_field<D>(self) -> ProverChannel<'a, A, D, H, R>
+    where
+        D: FieldElement<BaseField = A::BaseField>,
+    {
+        ProverChannel {
+            air: self.air,
+            public_coin: self.public_coin,
+            context: self.context,
+            commitments: self.commitments,
+            ood_frame: self.ood_frame,
+            pow_nonce: self.pow_nonce,
+            _field_element: PhantomData,
+        }
+    }
+
     // COMMITMENT METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Commits the prover the extended execution trace.
     pub fn commit_trace(&mut self, trace_root: H::Digest) {
         self.commitments.add::<H>(&trace_root);
-        self.public_coin.reseed(trace_root);
+        self.public_coin.absorb(TranscriptLabel::TraceCommitment, trace_root);
     }
 
     /// Commits the prover to the evaluations of the constraint composition polynomial.
     pub fn commit_constraints(&mut self, constraint_root: H::Digest) {
         self.commitments.add::<H>(&constraint_root);
-        self.public_coin.reseed(constraint_root);
+        self.public_coin.absorb(TranscriptLabel::ConstraintCommitment, constraint_root);
     }
 
     /// Saves the evaluations of trace polynomials over the out-of-domain evaluation frame. This
-    /// also reseeds the public coin with the hashes of the evaluation frame states.
+    /// also absorbs the hashes of the evaluation frame states into the public coin.
     pub fn send_ood_trace_states(&mut self, trace_ood_frame: &TraceOodFrame<E>) {
         let trace_states_hash = self.ood_frame.set_trace_states::<E, H>(trace_ood_frame);
-        self.public_coin.reseed(trace_states_hash);
+        self.public_coin.absorb(TranscriptLabel::OodTraceFrame, trace_states_hash);
     }
 
     /// Saves the evaluations of constraint composition polynomial columns at the out-of-domain
-    /// point. This also reseeds the public coin wit the hash of the evaluations.
+    /// point. This also absorbs the hash of the evaluations into the public coin.
     pub fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
         self.ood_frame.set_constraint_evaluations(evaluations);
-        self.public_coin.reseed(H::hash_elements(evaluations));
+        self.public_coin
+            .absorb(TranscriptLabel::OodConstraintEvaluations, H::hash_elements(evaluations));
     }
 
     // PUBLIC COIN METHODS
@@ -116,7 +142,9 @@ where
 
     /// Returns an out-of-domain point drawn uniformly at random from the public coin.
     pub fn get_ood_point(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw OOD point")
+        self.public_coin
+            .squeeze(TranscriptLabel::OodTraceFrame)
+            .expect("failed to draw OOD point")
     }
 
     /// Returns a set of coefficients for constructing a DEEP composition polynomial.
@@ -131,21 +159,31 @@ where
     /// Returns a set of positions in the LDE domain against which the evaluations of trace and
     /// constraint composition polynomials should be queried.
     ///
-    /// The positions are drawn from the public coin uniformly at random. Duplicate positions
-    /// are removed from the returned vector.
-    pub fn get_query_positions(&mut self) -> Vec<usize> {
+    /// The positions are drawn from the public coin uniformly at random and without replacement,
+    /// and thus, the number of returned positions is equal to the number of queries specified by
+    /// the proof options. The positions are returned in ascending order.
+    ///
+    /// # Errors
+    /// Returns an error if the public coin could not draw the required number of distinct
+    /// positions.
+    pub fn get_query_positions(&mut self) -> Result<Vec<usize>, ProverError> {
         let num_queries = self.context.options().num_queries();
         let lde_domain_size = self.context.lde_domain_size();
         let mut positions = self
             .public_coin
-            .draw_integers(num_queries, lde_domain_size, self.pow_nonce)
-            .expect("failed to draw query position");
+            .squeeze_integers(
+                TranscriptLabel::QuerySeed,
+                num_queries,
+                lde_domain_size,
+                self.pow_nonce,
+            )
+            .map_err(ProverError::FailedToDrawQueryPositions)?;
 
-        // remove any duplicate positions from the list
         positions.sort_unstable();
         positions.dedup();
+        assert_eq!(num_queries, positions.len(), "query positions must be distinct");
 
-        positions
+        Ok(positions)
     }
 
     /// Determines a nonce, which when hashed with the current seed of the public coin results
@@ -153,19 +191,7 @@ where
     /// in the proof options.
     pub fn grind_query_seed(&mut self) {
         let grinding_factor = self.context.options().grinding_factor();
-
-        #[cfg(not(feature = "concurrent"))]
-        let nonce = (1..u64::MAX)
-            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
-
-        #[cfg(feature = "concurrent")]
-        let nonce = (1..u64::MAX)
-            .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
-
-        self.pow_nonce = nonce;
+        self.pow_nonce = fri::ProverChannel::<E>::grind_fri_query_seed(self, grinding_factor);
     }
 
     // PROOF BUILDER
@@ -204,18 +230,46 @@ where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
-    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    R: Transcript<BaseField = A::BaseField, Hasher = H>,
 {
     type Hasher = H;
 
     /// Commits the prover to a FRI layer.
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.add::<H>(&layer_root);
-        self.public_coin.reseed(layer_root);
+        self.public_coin.absorb(TranscriptLabel::FriLayer, layer_root);
     }
 
     /// Returns a new alpha drawn from the public coin.
     fn draw_fri_alpha(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw FRI alpha")
+        self.public_coin
+            .squeeze(TranscriptLabel::FriLayer)
+            .expect("failed to draw FRI alpha")
+    }
+
+    /// Absorbs a hash of DEEP values of a FRI layer into the public coin.
+    fn commit_fri_deep_values(&mut self, values: &[E]) {
+        self.public_coin
+            .absorb(TranscriptLabel::FriDeepValues, H::hash_elements(values));
+    }
+
+    /// Determines a nonce, which when hashed with the current seed of the public coin results
+    /// in a new seed with at least `grinding_factor` leading zeros.
+    ///
+    /// The smallest such nonce is returned even when the search is performed in multiple threads;
+    /// thus, the nonce does not depend on the number of threads used to generate the proof.
+    fn grind_fri_query_seed(&mut self, grinding_factor: u32) -> u64 {
+        #[cfg(not(feature = "concurrent"))]
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+
+        #[cfg(feature = "concurrent")]
+        let nonce = (1..u64::MAX)
+            .into_par_iter()
+            .find_first(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+
+        nonce
     }
 }
@@ -0,0 +1,310 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+
+use air::{ProofOptions, TraceInfo};
+use math::FieldElement;
+use utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+use crate::{matrix::ColMatrix, CompositionPoly, ProverError};
+
+// CHECKPOINT PHASE
+// ================================================================================================
+
+/// Phase of proof generation after which a [ProverCheckpoint] was saved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CheckpointPhase {
+    /// All segments of the execution trace have been committed to.
+    TraceCommitted = 1,
+    /// The evaluations of the constraint composition polynomial have been committed to.
+    ConstraintsCommitted = 2,
+}
+
+// PROVER CHECKPOINT
+// ================================================================================================
+
+/// Intermediate state of proof generation from which proof generation can be resumed via
+/// [Prover::resume()](crate::Prover::resume), possibly on a different machine.
+///
+/// A checkpoint contains the segments of the execution trace exactly as they were committed to
+/// (i.e., after blinding for zero-knowledge proofs) together with the commitments to them, and,
+/// once constraint evaluations have been committed to, the columns of the constraint composition
+/// polynomial and the commitment to their evaluations. Low-degree extensions, Merkle trees, and
+/// FRI layers are not part of a checkpoint: these are rebuilt from the committed data when proof
+/// generation is resumed, and the state of the Fiat-Shamir transcript is restored by replaying
+/// the recorded commitments.
+///
+/// A checkpoint can be serialized via [Serializable] and saved to disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProverCheckpoint {
+    trace_info: TraceInfo,
+    options: ProofOptions,
+    pub_inputs: Vec<u8>,
+    main_trace: Vec<u8>,
+    main_trace_root: Vec<u8>,
+    aux_trace: Option<(Vec<u8>, Vec<u8>)>,
+    constraints: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl ProverCheckpoint {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new checkpoint for the [CheckpointPhase::TraceCommitted] phase of proof
+    /// generation.
+    pub(crate) fn new<E, D>(
+        trace_info: TraceInfo,
+        options: ProofOptions,
+        pub_inputs_elements: &[E::BaseField],
+        main_trace: &ColMatrix<E::BaseField>,
+        main_trace_root: &D,
+        aux_trace: Option<(&ColMatrix<E>, &D)>,
+    ) -> Self
+    where
+        E: FieldElement,
+        D: Serializable,
+    {
+        let mut pub_inputs = Vec::new();
+        pub_inputs.write_many(pub_inputs_elements);
+
+        ProverCheckpoint {
+            trace_info,
+            options,
+            pub_inputs,
+            main_trace: columns_to_bytes(main_trace),
+            main_trace_root: main_trace_root.to_bytes(),
+            aux_trace: aux_trace.map(|(trace, root)| (columns_to_bytes(trace), root.to_bytes())),
+            constraints: None,
+        }
+    }
+
+    /// Advances this checkpoint to the [CheckpointPhase::ConstraintsCommitted] phase by
+    /// recording the provided composition polynomial and the commitment to its evaluations.
+    pub(crate) fn add_constraints<E, D>(&mut self, composition_poly: &CompositionPoly<E>, root: &D)
+    where
+        E: FieldElement,
+        D: Serializable,
+    {
+        self.constraints = Some((columns_to_bytes(composition_poly.data()), root.to_bytes()));
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the phase of proof generation after which this checkpoint was saved.
+    pub fn phase(&self) -> CheckpointPhase {
+        if self.constraints.is_some() {
+            CheckpointPhase::ConstraintsCommitted
+        } else {
+            CheckpointPhase::TraceCommitted
+        }
+    }
+
+    /// Returns the layout of the execution trace for which the proof is being generated.
+    pub fn trace_info(&self) -> &TraceInfo {
+        &self.trace_info
+    }
+
+    /// Returns the options with which the proof is being generated.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    // STATE RESTORATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes sure this checkpoint was saved while generating a proof with the specified options
+    /// for an execution trace with the specified public inputs.
+    pub(crate) fn validate<B: Serializable>(
+        &self,
+        options: &ProofOptions,
+        pub_inputs_elements: &[B],
+    ) -> Result<(), ProverError> {
+        if &self.options != options {
+            return Err(invalid("checkpoint was saved with different proof options"));
+        }
+        let mut pub_inputs = Vec::new();
+        pub_inputs.write_many(pub_inputs_elements);
+        if self.pub_inputs != pub_inputs {
+            return Err(invalid("checkpoint was saved for different public inputs"));
+        }
+        Ok(())
+    }
+
+    /// Deserializes the trace segments, the composition polynomial, and the commitments recorded
+    /// in this checkpoint.
+    pub(crate) fn read_state<E, D>(&self) -> Result<CheckpointState<E, D>, ProverError>
+    where
+        E: FieldElement,
+        D: Deserializable,
+    {
+        let main_trace = columns_from_bytes(&self.main_trace)?;
+        if main_trace.num_cols() != self.trace_info.main_trace_width()
+            || main_trace.num_rows() != self.trace_info.length()
+        {
+            return Err(invalid("shape of the main trace segment does not match trace info"));
+        }
+        let main_trace_root = digest_from_bytes(&self.main_trace_root)?;
+
+        let aux_trace = match &self.aux_trace {
+            Some((trace, root)) => {
+                let trace = columns_from_bytes::<E>(trace)?;
+                if trace.num_cols() != self.trace_info.aux_segment_width()
+                    || trace.num_rows() != self.trace_info.length()
+                {
+                    return Err(invalid(
+                        "shape of the auxiliary trace segment does not match trace info",
+                    ));
+                }
+                Some((trace, digest_from_bytes(root)?))
+            },
+            None => None,
+        };
+        if aux_trace.is_some() != self.trace_info.is_multi_segment() {
+            return Err(invalid("auxiliary trace segment does not match trace info"));
+        }
+
+        let constraints = match &self.constraints {
+            Some((columns, root)) => {
+                let columns = columns_from_bytes::<E>(columns)?;
+                if columns.num_rows() != self.trace_info.length() {
+                    return Err(invalid(
+                        "length of composition polynomial columns does not match trace length",
+                    ));
+                }
+                Some((CompositionPoly::from_columns(columns), digest_from_bytes(root)?))
+            },
+            None => None,
+        };
+
+        Ok(CheckpointState {
+            main_trace,
+            main_trace_root,
+            aux_trace,
+            constraints,
+        })
+    }
+}
+
+impl Serializable for ProverCheckpoint {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.trace_info.write_into(target);
+        self.options.write_into(target);
+        write_bytes(target, &self.pub_inputs);
+        write_bytes(target, &self.main_trace);
+        write_bytes(target, &self.main_trace_root);
+        target.write_bool(self.aux_trace.is_some());
+        if let Some((trace, root)) = &self.aux_trace {
+            write_bytes(target, trace);
+            write_bytes(target, root);
+        }
+        target.write_bool(self.constraints.is_some());
+        if let Some((columns, root)) = &self.constraints {
+            write_bytes(target, columns);
+            write_bytes(target, root);
+        }
+    }
+}
+
+impl Deserializable for ProverCheckpoint {
+    /// Reads a prover checkpoint from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid checkpoint could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let trace_info = TraceInfo::read_from(source)?;
+        let options = ProofOptions::read_from(source)?;
+        let pub_inputs = read_bytes(source)?;
+        let main_trace = read_bytes(source)?;
+        let main_trace_root = read_bytes(source)?;
+        let aux_trace = if source.read_bool()? {
+            Some((read_bytes(source)?, read_bytes(source)?))
+        } else {
+            None
+        };
+        let constraints = if source.read_bool()? {
+            Some((read_bytes(source)?, read_bytes(source)?))
+        } else {
+            None
+        };
+
+        Ok(ProverCheckpoint {
+            trace_info,
+            options,
+            pub_inputs,
+            main_trace,
+            main_trace_root,
+            aux_trace,
+            constraints,
+        })
+    }
+}
+
+// CHECKPOINT STATE
+// ================================================================================================
+
+/// Trace segments, composition polynomial, and commitments deserialized from a checkpoint.
+pub(crate) struct CheckpointState<E: FieldElement, D> {
+    pub main_trace: ColMatrix<E::BaseField>,
+    pub main_trace_root: D,
+    pub aux_trace: Option<(ColMatrix<E>, D)>,
+    pub constraints: Option<(CompositionPoly<E>, D)>,
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an [ProverError::InvalidCheckpoint] error with the specified message.
+pub(crate) fn invalid(message: &str) -> ProverError {
+    ProverError::InvalidCheckpoint(message.to_string())
+}
+
+fn columns_to_bytes<E: FieldElement>(matrix: &ColMatrix<E>) -> Vec<u8> {
+    let mut result = Vec::new();
+    result.write_usize(matrix.num_cols());
+    result.write_usize(matrix.num_rows());
+    for column in matrix.columns() {
+        result.write_many(column);
+    }
+    result
+}
+
+fn columns_from_bytes<E: FieldElement>(bytes: &[u8]) -> Result<ColMatrix<E>, ProverError> {
+    let mut source = SliceReader::new(bytes);
+    let num_cols = source.read_usize().map_err(|err| invalid(&err.to_string()))?;
+    let num_rows = source.read_usize().map_err(|err| invalid(&err.to_string()))?;
+    if num_cols == 0 || num_rows < 2 || !num_rows.is_power_of_two() {
+        return Err(invalid("trace segment or composition polynomial has invalid shape"));
+    }
+
+    let mut columns = Vec::with_capacity(num_cols);
+    for _ in 0..num_cols {
+        columns.push(source.read_many(num_rows).map_err(|err| invalid(&err.to_string()))?);
+    }
+    if source.has_more_bytes() {
+        return Err(invalid(&DeserializationError::UnconsumedBytes.to_string()));
+    }
+
+    Ok(ColMatrix::new(columns))
+}
+
+fn digest_from_bytes<D: Deserializable>(bytes: &[u8]) -> Result<D, ProverError> {
+    D::read_from_bytes(bytes).map_err(|err| invalid(&err.to_string()))
+}
+
+fn write_bytes<W: ByteWriter>(target: &mut W, bytes: &[u8]) {
+    target.write_usize(bytes.len());
+    target.write_bytes(bytes);
+}
+
+fn read_bytes<R: ByteReader>(source: &mut R) -> Result<Vec<u8>, DeserializationError> {
+    let num_bytes = source.read_usize()?;
+    source.read_vec(num_bytes)
+}
@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::string::ToString;
+
+use air::ProofOptions;
+use math::fields::{CubeExtension, QuadExtension};
+
+use crate::{Prover, ProverError, StarkDomain, TraceLde, TracePolyTable};
+
+// COMMITTED TRACE
+// ================================================================================================
+
+/// An execution trace together with the low-degree extension of its main segment and the
+/// commitment to this extension.
+///
+/// A committed trace is built via [Prover::commit_trace()], and any number of proofs for it can
+/// then be generated via [Prover::prove_committed()]. Building the LDE of the main trace segment
+/// and the Merkle tree over it is usually the most expensive phase of proof generation; a
+/// committed trace makes it possible to skip this phase when proofs for the same trace are
+/// generated with different protocol parameters (e.g., when sweeping over different numbers of
+/// queries or grinding factors for benchmarking purposes).
+///
+/// Only proof options which do not affect the commitment to the main trace segment may differ
+/// between the proofs generated for the same committed trace: the blowup factor and the field
+/// extension must be the same as the ones the trace was committed with. Zero-knowledge proofs
+/// cannot be generated from a committed trace because the number of blinded rows depends on the
+/// number of queries. The auxiliary trace segment (if any) is rebuilt for every proof as it
+/// depends on the randomness drawn from the public coin.
+pub struct CommittedTrace<P: Prover> {
+    pub(crate) trace: P::Trace,
+    pub(crate) options: ProofOptions,
+    pub(crate) domain: StarkDomain<P::BaseField>,
+    pub(crate) main_segment: MainSegmentCommitment<P>,
+}
+
+impl<P: Prover> CommittedTrace<P> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the execution trace which was committed to.
+    pub fn trace(&self) -> &P::Trace {
+        &self.trace
+    }
+
+    /// Returns the options with which the trace was committed to.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    /// Consumes this committed trace and returns the underlying execution trace.
+    pub fn into_trace(self) -> P::Trace {
+        self.trace
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes sure a proof with the specified options can be generated from this committed trace.
+    pub(crate) fn validate(&self, options: &ProofOptions) -> Result<(), ProverError> {
+        if options.is_zk() {
+            return Err(incompatible(
+                "zero-knowledge proofs cannot be generated from a committed trace",
+            ));
+        }
+        if options.blowup_factor() != self.options.blowup_factor() {
+            return Err(incompatible("trace was committed to with a different blowup factor"));
+        }
+        if options.field_extension() != self.options.field_extension() {
+            return Err(incompatible("trace was committed to with a different field extension"));
+        }
+        Ok(())
+    }
+}
+
+// MAIN SEGMENT COMMITMENT
+// ================================================================================================
+
+/// LDE of the main trace segment and the main trace polynomials instantiated for the extension
+/// field specified by the proof options with which the trace was committed to.
+pub(crate) enum MainSegmentCommitment<P: Prover> {
+    Base(P::TraceLde<P::BaseField>, TracePolyTable<P::BaseField>),
+    Quadratic(
+        P::TraceLde<QuadExtension<P::BaseField>>,
+        TracePolyTable<QuadExtension<P::BaseField>>,
+    ),
+    Cubic(
+        P::TraceLde<CubeExtension<P::BaseField>>,
+        TracePolyTable<CubeExtension<P::BaseField>>,
+    ),
+}
+
+impl<P: Prover> MainSegmentCommitment<P> {
+    /// Removes the auxiliary trace segment added to the trace LDE while generating a proof.
+    pub fn remove_aux_trace(&mut self) {
+        match self {
+            Self::Base(trace_lde, _) => trace_lde.remove_aux_trace(),
+            Self::Quadratic(trace_lde, _) => trace_lde.remove_aux_trace(),
+            Self::Cubic(trace_lde, _) => trace_lde.remove_aux_trace(),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an [ProverError::IncompatibleCommittedTrace] error with the specified message.
+pub(crate) fn incompatible(reason: &str) -> ProverError {
+    ProverError::IncompatibleCommittedTrace(reason.to_string())
+}
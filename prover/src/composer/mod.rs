@@ -58,6 +58,9 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     /// - Then, combine together all T'_i(x) and T''_i(x) polynomials using a random linear
     ///   combination as T(x) = sum((T'_i(x) + T''_i(x)) * cc_i) for all i, where cc_i is
     ///   the coefficient for the random linear combination drawn from the public coin.
+    /// - If the evaluation frame has rows at offsets other than 0 and 1, an additional term
+    ///   (T_i(x) - T_i(z * g^k)) / (x - z * g^k) is computed for each such offset k and included
+    ///   into the linear combination using the same coefficient cc_i.
     /// - If a Lagrange kernel is present, combine one additional term defined as
     ///   (T_l(x) - p_S(x)) / Z_S(x), where:
     ///
@@ -68,26 +71,37 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///    ${(a, T_l(a)): a \in S}$.
     /// 4. $Z_S(X)$ is the polynomial of minimal degree vanishing over the set $S$.
     ///
-    /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_trace_state`
-    /// parameter.
+    /// Note that evaluations of T_i(z) and T_i(z * g) (as well as T_i(z * g^k) for any additional
+    /// row offsets) are passed in via the `ood_trace_state` parameter.
     /// If a Lagrange kernel is present, the evaluations of $T_l$ over the set $S$ are provided
     /// separately via `ood_trace_state`.
-    pub fn add_trace_polys(
+    ///
+    /// Polynomials of the auxiliary trace segment may be defined over a subfield `F` of the field
+    /// of this DEEP composition polynomial.
+    pub fn add_trace_polys<F>(
         &mut self,
-        trace_polys: TracePolyTable<E>,
+        trace_polys: TracePolyTable<F>,
         ood_trace_states: TraceOodFrame<E>,
-    ) {
+    ) where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: ExtensionOf<F>,
+    {
         assert!(self.coefficients.is_empty());
 
-        // compute a second out-of-domain point offset from z by exactly trace generator; this
-        // point defines the "next" computation state in relation to point z
+        // compute out-of-domain points offset from z by powers of trace generator corresponding
+        // to the row offsets of the evaluation frame; for the default frame, these are z and
+        // z * g, where z * g defines the "next" computation state in relation to point z
         let trace_length = trace_polys.poly_size();
-        let g = E::from(E::BaseField::get_root_of_unity(trace_length.ilog2()));
-        let next_z = self.z * g;
+        let g = E::BaseField::get_root_of_unity(trace_length.ilog2());
+        let ood_points: Vec<E> = ood_trace_states
+            .offsets()
+            .iter()
+            .map(|&offset| self.z.mul_base(g.exp((offset as u64).into())))
+            .collect();
 
-        // combine trace polynomials into 2 composition polynomials T'(x) and T''(x)
-        let mut t1_composition = vec![E::ZERO; trace_length];
-        let mut t2_composition = vec![E::ZERO; trace_length];
+        // combine trace polynomials into one composition polynomial per row of the frame, e.g.,
+        // T'(x) and T''(x) for the default frame
+        let mut compositions = vec![vec![E::ZERO; trace_length]; ood_points.len()];
 
         // index of a trace polynomial; we declare it here so that we can maintain index continuity
         // across all trace segments
@@ -95,55 +109,40 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
         // --- merge polynomials of the main trace segment ----------------------------------------
         for poly in trace_polys.main_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states.current_row()[i],
-                self.cc.trace[i],
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states.next_row()[i],
-                self.cc.trace[i],
-            );
+            // compute T(x) - T(z * g^k) for each row offset k, multiply it by a pseudo-random
+            // coefficient, and add the result into the corresponding composition polynomial
+            for (row_idx, composition) in compositions.iter_mut().enumerate() {
+                acc_trace_poly::<E::BaseField, E>(
+                    composition,
+                    poly,
+                    ood_trace_states.row(row_idx)[i],
+                    self.cc.trace[i],
+                );
+            }
 
             i += 1;
         }
 
         // --- merge polynomials of the auxiliary trace segment ----------------------------------
         for poly in trace_polys.aux_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states.current_row()[i],
-                self.cc.trace[i],
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states.next_row()[i],
-                self.cc.trace[i],
-            );
+            // compute T(x) - T(z * g^k) for each row offset k, multiply it by a pseudo-random
+            // coefficient, and add the result into the corresponding composition polynomial
+            for (row_idx, composition) in compositions.iter_mut().enumerate() {
+                acc_trace_poly::<F, E>(
+                    composition,
+                    poly,
+                    ood_trace_states.row(row_idx)[i],
+                    self.cc.trace[i],
+                );
+            }
 
             i += 1;
         }
 
-        // divide the composition polynomials by (x - z) and (x - z * g), respectively,
-        // and add the resulting polynomials together; the output of this step
-        // is a single trace polynomial T(x) and deg(T(x)) = trace_length - 2.
-        let mut trace_poly =
-            merge_trace_compositions(vec![t1_composition, t2_composition], vec![self.z, next_z]);
+        // divide the composition polynomials by (x - z * g^k) for their respective row offsets
+        // k, and add the resulting polynomials together; the output of this step is a single
+        // trace polynomial T(x) and deg(T(x)) = trace_length - 2.
+        let mut trace_poly = merge_trace_compositions(compositions, ood_points);
 
         // finally compose the final term associated to the Lagrange kernel trace polynomial if
         // there is one present.
@@ -170,7 +169,10 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
             // compute the numerator
             let p_s = polynom::interpolate(&xs, ood_eval_frame.inner(), true);
-            let mut numerator = polynom::sub(poly, &p_s);
+            let mut numerator: Vec<E> = poly.iter().map(|&coeff| E::from(coeff)).collect();
+            for (coeff, &p_s_coeff) in numerator.iter_mut().zip(p_s.iter()) {
+                *coeff -= p_s_coeff;
+            }
 
             // divide by the zero polynomial of the set S
             syn_div_roots_in_place(&mut numerator, &xs);
@@ -201,16 +203,26 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///   public coin.
     ///
     /// Note that evaluations of H_i(x) at z are passed in via the `ood_evaluations` parameter.
-    pub fn add_composition_poly(
+    ///
+    /// The composition polynomial may be defined over a subfield `F` of the field of this DEEP
+    /// composition polynomial.
+    pub fn add_composition_poly<F>(
         &mut self,
-        composition_poly: CompositionPoly<E>,
+        composition_poly: CompositionPoly<F>,
         ood_evaluations: Vec<E>,
-    ) {
+    ) where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: ExtensionOf<F>,
+    {
         assert!(!self.coefficients.is_empty());
 
         let z = self.z;
 
-        let mut column_polys = composition_poly.into_columns();
+        let mut column_polys: Vec<Vec<E>> = composition_poly
+            .into_columns()
+            .into_iter()
+            .map(|column| column.into_iter().map(E::from).collect())
+            .collect();
 
         // Divide out the OOD point z from column polynomials
         iter_mut!(column_polys).zip(ood_evaluations).for_each(|(poly, value_at_z)| {
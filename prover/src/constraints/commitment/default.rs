@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::MerkleTree;
+use tracing::info_span;
+
+use super::{
+    CompositionPoly, ConstraintCommitment, ElementHasher, FieldElement, Queries, RowMatrix,
+    StarkDomain,
+};
+use crate::DEFAULT_SEGMENT_WIDTH;
+
+// CONSTRAINT COMMITMENT
+// ================================================================================================
+
+/// Constraint evaluation commitment.
+///
+/// The commitment consists of two components:
+/// * Evaluations of composition polynomial columns over the LDE domain.
+/// * Merkle tree where each leaf in the tree corresponds to a row in the composition polynomial
+///   evaluation matrix.
+pub struct DefaultConstraintCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>>
+{
+    evaluations: RowMatrix<E>,
+    commitment: MerkleTree<H>,
+}
+
+impl<E, H> DefaultConstraintCommitment<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Evaluates columns of the provided composition polynomial over the LDE domain, and builds
+    /// a commitment to these evaluations.
+    ///
+    /// The commitment is computed by hashing each row in the evaluation matrix, and then building
+    /// a Merkle tree from the resulting hashes.
+    pub fn new(composition_poly: &CompositionPoly<E>, domain: &StarkDomain<E::BaseField>) -> Self {
+        // evaluate composition polynomial columns over the LDE domain
+        let domain_size = domain.lde_domain_size();
+        let evaluations = info_span!("evaluate_composition_poly_columns").in_scope(|| {
+            RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(composition_poly.data(), domain)
+        });
+        assert_eq!(evaluations.num_cols(), composition_poly.num_columns());
+        assert_eq!(evaluations.num_rows(), domain_size);
+
+        // build constraint evaluation commitment
+        let commitment = info_span!(
+            "compute_constraint_evaluation_commitment",
+            tree_depth = domain_size.ilog2()
+        )
+        .in_scope(|| evaluations.commit_to_rows());
+        assert_eq!(commitment.depth(), domain_size.ilog2() as usize);
+
+        DefaultConstraintCommitment { evaluations, commitment }
+    }
+}
+
+impl<E, H> ConstraintCommitment<E> for DefaultConstraintCommitment<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    type HashFn = H;
+
+    /// Returns the root of the commitment Merkle tree.
+    fn commitment(&self) -> H::Digest {
+        *self.commitment.root()
+    }
+
+    /// Returns constraint evaluations at the specified positions along with Merkle authentication
+    /// paths from the root of the commitment to these evaluations.
+    fn query(self, positions: &[usize]) -> Queries {
+        // build Merkle authentication paths to the leaves specified by positions
+        let merkle_proof = self
+            .commitment
+            .prove_batch(positions)
+            .expect("failed to generate a Merkle proof for constraint queries");
+
+        // determine a set of evaluations corresponding to each position
+        let mut evaluations = Vec::new();
+        for &position in positions {
+            let row = self.evaluations.row(position).to_vec();
+            evaluations.push(row);
+        }
+
+        Queries::new(merkle_proof, evaluations)
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::proof::Queries;
+use crypto::{ElementHasher, Hasher};
+use math::FieldElement;
+
+use super::{CompositionPoly, RowMatrix, StarkDomain};
+
+mod default;
+pub use default::DefaultConstraintCommitment;
+
+// CONSTRAINT COMMITMENT
+// ================================================================================================
+/// Contains evaluations of composition polynomial columns over the LDE domain and a commitment to
+/// these evaluations.
+///
+/// This trait, together with [TraceLde](crate::TraceLde) and
+/// [ConstraintEvaluator](crate::ConstraintEvaluator) traits, defines the computationally heavy
+/// parts of proof generation: low-degree extension of composition polynomial columns and hashing
+/// of the resulting rows are performed when the commitment is instantiated (see
+/// [Prover::new_constraint_commitment()](crate::Prover::new_constraint_commitment)). Thus,
+/// hardware-accelerated (e.g., GPU-based) implementations can be plugged into the prover by
+/// implementing these traits.
+pub trait ConstraintCommitment<E: FieldElement> {
+    /// The hash function used for building the commitment to composition polynomial evaluations.
+    type HashFn: ElementHasher<BaseField = E::BaseField>;
+
+    /// Returns the commitment to the evaluations of composition polynomial columns.
+    fn commitment(&self) -> <Self::HashFn as Hasher>::Digest;
+
+    /// Returns composition polynomial evaluations at the specified positions along with
+    /// authentication paths from the commitment to these evaluations.
+    fn query(self, positions: &[usize]) -> Queries;
+}
@@ -5,7 +5,7 @@
 
 use alloc::vec::Vec;
 
-use math::{fft, polynom::degree_of, FieldElement};
+use math::{fft::FftDomain, polynom::degree_of, ExtensionOf, FieldElement};
 
 use super::{ColMatrix, StarkDomain};
 
@@ -67,14 +67,24 @@ impl<E: FieldElement> CompositionPoly<E> {
 
         // at this point, combined_poly contains evaluations of the combined constraint polynomial;
         // we interpolate this polynomial to transform it into coefficient form.
-        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(trace.len());
-        fft::interpolate_poly_with_offset(&mut trace, &inv_twiddles, domain.offset());
+        assert_eq!(
+            trace.len(),
+            domain.ce_domain_size(),
+            "composition polynomial trace must span the constraint evaluation domain"
+        );
+        domain.ce_domain().interpolate(&mut trace);
 
         let polys = segment(trace, domain.trace_length(), num_cols);
 
         CompositionPoly { data: ColMatrix::new(polys) }
     }
 
+    /// Returns a composition polynomial consisting of the provided column polynomials in
+    /// coefficient form.
+    pub(crate) fn from_columns(columns: ColMatrix<E>) -> Self {
+        CompositionPoly { data: columns }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -95,8 +105,31 @@ impl<E: FieldElement> CompositionPoly<E> {
         self.column_len() - 1
     }
 
+    /// Appends a column with the provided coefficients to this composition polynomial.
+    ///
+    /// This is used to mask the composition polynomial with a random polynomial when generating
+    /// zero-knowledge proofs.
+    ///
+    /// # Panics
+    /// Panics if the number of coefficients is not equal to the length of column polynomials.
+    pub fn add_randomizer_column(&mut self, coefficients: Vec<E>) {
+        assert_eq!(
+            coefficients.len(),
+            self.column_len(),
+            "randomizer column must have {} coefficients, but had {}",
+            self.column_len(),
+            coefficients.len()
+        );
+        self.data.merge_column(coefficients);
+    }
+
     /// Returns evaluations of all composition polynomial columns at point z.
-    pub fn evaluate_at(&self, z: E) -> Vec<E> {
+    ///
+    /// The point may be drawn from an extension of the field of the composition polynomial.
+    pub fn evaluate_at<D>(&self, z: D) -> Vec<D>
+    where
+        D: FieldElement<BaseField = E::BaseField> + ExtensionOf<E>,
+    {
         self.data.evaluate_columns_at(z)
     }
 
@@ -8,8 +8,8 @@ use alloc::vec::Vec;
 #[cfg(debug_assertions)]
 use air::TransitionConstraints;
 #[cfg(debug_assertions)]
-use math::fft;
-use math::{batch_inversion, FieldElement, StarkField};
+use math::fft::EvaluationDomain;
+use math::{batch_inversion_in_place, fft, polynom, FieldElement, StarkField};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 use utils::{batch_iter_mut, iter_mut, uninit_vector};
@@ -35,6 +35,8 @@ pub struct ConstraintEvaluationTable<'a, E: FieldElement> {
     aux_transition_evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
     expected_transition_degrees: Vec<usize>,
+    #[cfg(debug_assertions)]
+    transition_divisor_indexes: Vec<usize>,
 }
 
 impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
@@ -42,6 +44,10 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     // --------------------------------------------------------------------------------------------
     /// Returns a new constraint evaluation table with number of columns equal to the number of
     /// specified divisors, and number of rows equal to the size of constraint evaluation domain.
+    ///
+    /// Memory for the table is allocated, but not written to; thus, physical memory for each
+    /// fragment of the table is placed by the operating system (e.g., on the NUMA node of the
+    /// thread) which first writes into the fragment.
     #[cfg(not(debug_assertions))]
     pub fn new(
         domain: &'a StarkDomain<E::BaseField>,
@@ -75,6 +81,15 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         let expected_transition_degrees =
             build_transition_constraint_degrees(transition_constraints, domain.trace_length());
 
+        // record the divisor of each transition constraint; transition constraint divisors are
+        // assumed to be at the front of the divisor list
+        let transition_divisor_indexes = transition_constraints
+            .main_constraint_divisors()
+            .iter()
+            .chain(transition_constraints.aux_constraint_divisors())
+            .copied()
+            .collect();
+
         ConstraintEvaluationTable {
             evaluations: uninit_matrix(num_columns, num_rows),
             divisors,
@@ -82,6 +97,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
             main_transition_evaluations: uninit_matrix(num_tm_columns, num_rows),
             aux_transition_evaluations: uninit_matrix(num_ta_columns, num_rows),
             expected_transition_degrees,
+            transition_divisor_indexes,
         }
     }
 
@@ -96,9 +112,9 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     /// Returns number of columns in this table.
     ///
-    /// The first column always contains the value of combined transition constraint evaluations;
-    /// the remaining columns contain values of assertion constraint evaluations combined based on
-    /// common divisors.
+    /// The first columns always contain the values of combined transition constraint evaluations
+    /// (one column per distinct transition constraint divisor); the remaining columns contain
+    /// values of assertion constraint evaluations combined based on common divisors.
     #[allow(dead_code)]
     pub fn num_columns(&self) -> usize {
         self.evaluations.len()
@@ -109,7 +125,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     /// Break the table into the number of specified fragments. All fragments can be updated
     /// independently - e.g. in different threads.
-    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<E>> {
+    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<'_, E>> {
         let fragment_size = self.num_rows() / num_fragments;
         assert!(
             fragment_size >= MIN_FRAGMENT_SIZE,
@@ -180,32 +196,40 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     #[cfg(debug_assertions)]
     pub fn validate_transition_degrees(&mut self) {
-        // evaluate transition constraint divisor (which is assumed to be the first one in the
-        // divisor list) over the constraint evaluation domain. this is used later to compute
+        // evaluate transition constraint divisors (which are assumed to be at the front of the
+        // divisor list) over the constraint evaluation domain. these are used later to compute
         // actual degrees of transition constraint evaluations.
-        let div_values = evaluate_divisor::<E::BaseField>(
-            &self.divisors[0],
-            self.num_rows(),
-            self.domain.offset(),
-        );
+        let num_divisors = self.transition_divisor_indexes.iter().max().map_or(0, |&i| i + 1);
+        let inv_div_values = self.divisors[..num_divisors]
+            .iter()
+            .map(|divisor| {
+                let mut values = evaluate_divisor::<E::BaseField>(divisor, self.domain.ce_domain());
+                batch_inversion_in_place(&mut values);
+                values
+            })
+            .collect::<Vec<_>>();
+        let mut divisor_indexes = self.transition_divisor_indexes.iter();
 
         // collect actual degrees for all transition constraints by interpolating saved
         // constraint evaluations into polynomials and checking their degree; also
         // determine max transition constraint degree
         let mut actual_degrees = Vec::with_capacity(self.expected_transition_degrees.len());
         let mut max_degree = 0;
-        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
+        let domain = self.domain;
+        let inv_twiddles = domain.ce_inv_twiddles();
 
         // first process transition constraint evaluations for the main trace segment
         for evaluations in self.main_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
+            let inv_div_values = &inv_div_values[*divisor_indexes.next().unwrap()];
+            let degree = get_transition_poly_degree(evaluations, inv_twiddles, inv_div_values);
             actual_degrees.push(degree);
             max_degree = core::cmp::max(max_degree, degree);
         }
 
         // then process transition constraint evaluations for the auxiliary trace segment
         for evaluations in self.aux_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
+            let inv_div_values = &inv_div_values[*divisor_indexes.next().unwrap()];
+            let degree = get_transition_poly_degree(evaluations, inv_twiddles, inv_div_values);
             actual_degrees.push(degree);
             max_degree = core::cmp::max(max_degree, degree);
         }
@@ -321,10 +345,21 @@ fn acc_column<E: FieldElement>(
     result: &mut [E],
 ) {
     let numerator = divisor.numerator();
-    assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
+    assert!(
+        numerator.len() == 1 || divisor.exemptions().is_empty(),
+        "complex divisors with exemptions are not yet supported"
+    );
 
-    // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b)
-    let z = get_inv_evaluation(divisor, domain);
+    // compute inverse evaluations of the divisor's numerator, which usually has the form
+    // (x^a - b); if the evaluations have been precomputed for the domain, use them instead
+    let inv_evaluations;
+    let z = match domain.divisor_inv_evaluations(numerator) {
+        Some(z) => z,
+        None => {
+            inv_evaluations = get_inv_evaluation(divisor, domain);
+            &inv_evaluations
+        },
+    };
 
     // divide column values by the divisor; for boundary constraints this computed simply as
     // multiplication of column value by the inverse of divisor numerator; for transition
@@ -367,11 +402,15 @@ fn acc_column<E: FieldElement>(
 }
 
 /// Computes evaluations of the divisor's numerator over the domain of the specified size and offset.
-fn get_inv_evaluation<B: StarkField>(
+pub(crate) fn get_inv_evaluation<B: StarkField>(
     divisor: &ConstraintDivisor<B>,
     domain: &StarkDomain<B>,
 ) -> Vec<B> {
     let numerator = divisor.numerator();
+    if numerator.len() > 1 {
+        return get_inv_complex_evaluation(numerator, domain);
+    }
+
     let a = numerator[0].0 as u64; // numerator degree
     let b = numerator[0].1;
 
@@ -402,7 +441,46 @@ fn get_inv_evaluation<B: StarkField>(
     );
 
     // compute 1 / (x^a - b)
-    batch_inversion(&evaluations)
+    batch_inversion_in_place(&mut evaluations);
+    evaluations
+}
+
+/// Computes inverse evaluations of a divisor's numerator consisting of multiple terms over the
+/// entire constraint evaluation domain.
+///
+/// The numerator is expanded into a polynomial which is then evaluated using FFT. Since the
+/// degree of the numerator may be equal to the size of the domain, the polynomial is first
+/// reduced modulo $x^N - s^N$, where $N$ is the size of the domain and $s$ is the domain offset;
+/// this does not change the evaluations of the polynomial over the domain.
+fn get_inv_complex_evaluation<B: StarkField>(
+    numerator: &[(usize, B)],
+    domain: &StarkDomain<B>,
+) -> Vec<B> {
+    // expand the numerator into a polynomial by multiplying all (x^a - b) terms together
+    let poly = numerator.iter().fold(vec![B::ONE], |poly, &(a, b)| {
+        let mut term = vec![B::ZERO; a + 1];
+        term[0] = -b;
+        term[a] = B::ONE;
+        polynom::mul(&poly, &term)
+    });
+
+    // reduce the polynomial modulo x^N - s^N
+    let domain_size = domain.ce_domain_size();
+    let offset_exp = domain.offset().exp((domain_size as u64).into());
+    let mut reduced = vec![B::ZERO; domain_size];
+    let mut factor = B::ONE;
+    for chunk in poly.chunks(domain_size) {
+        for (r, &c) in reduced.iter_mut().zip(chunk) {
+            *r += c * factor;
+        }
+        factor *= offset_exp;
+    }
+
+    // evaluate the polynomial over the domain and compute inverses of the evaluations
+    let twiddles = fft::get_twiddles::<B>(domain_size);
+    let mut evaluations = fft::evaluate_poly_with_offset(&reduced, &twiddles, domain.offset(), 1);
+    batch_inversion_in_place(&mut evaluations);
+    evaluations
 }
 
 // DEBUG HELPERS
@@ -411,9 +489,10 @@ fn get_inv_evaluation<B: StarkField>(
 /// Returns evaluation degrees of all transition constraints.
 ///
 /// An evaluation degree is defined as degree of transition constraints in the context of a given
-/// execution trace accounting for constraint divisor degree. For most constraints, this degree is
-/// computed as `([trace_length - 1] * [constraint degree]) - [divisor degree]`. However, for
-/// constraints which rely on periodic columns this computation is slightly more complex.
+/// execution trace accounting for the degree of the constraint's divisor. For most constraints,
+/// this degree is computed as `([trace_length - 1] * [constraint degree]) - [divisor degree]`.
+/// However, for constraints which rely on periodic columns this computation is slightly more
+/// complex.
 ///
 /// The general idea is that evaluation degree is the degree of rational function `C(x) / z(x)`,
 /// where `C(x)` is the constraint polynomial and `z(x)` is the divisor polynomial.
@@ -424,12 +503,24 @@ fn build_transition_constraint_degrees<E: FieldElement>(
 ) -> Vec<usize> {
     let mut result = Vec::new();
 
-    for degree in constraints.main_constraint_degrees() {
-        result.push(degree.get_evaluation_degree(trace_length) - constraints.divisor().degree())
+    let divisors = constraints.divisors();
+
+    for (degree, &divisor_idx) in constraints
+        .main_constraint_degrees()
+        .iter()
+        .zip(constraints.main_constraint_divisors())
+    {
+        let divisor_degree = divisors[divisor_idx].degree();
+        result.push(degree.get_evaluation_degree(trace_length).saturating_sub(divisor_degree))
     }
 
-    for degree in constraints.aux_constraint_degrees() {
-        result.push(degree.get_evaluation_degree(trace_length) - constraints.divisor().degree())
+    for (degree, &divisor_idx) in constraints
+        .aux_constraint_degrees()
+        .iter()
+        .zip(constraints.aux_constraint_divisors())
+    {
+        let divisor_degree = divisors[divisor_idx].degree();
+        result.push(degree.get_evaluation_degree(trace_length).saturating_sub(divisor_degree))
     }
 
     result
@@ -439,23 +530,24 @@ fn build_transition_constraint_degrees<E: FieldElement>(
 ///
 /// The degree is computed as follows:
 /// - First, we divide the polynomial evaluations by the evaluations of transition constraint
-///   divisor (`div_values`). This is needed because it is possible for the numerator portions of
-///   transition constraints to have a degree which is larger than the size of the evaluation
-///   domain (and thus, interpolating the numerator would yield an incorrect result). However,
-///   once the divisor values are divided out, the degree of the resulting polynomial should be
-///   smaller than the size of the evaluation domain, and thus, we can interpolate safely.
+///   divisor (by multiplying them with inverse divisor evaluations `inv_div_values`). This is
+///   needed because it is possible for the numerator portions of transition constraints to have
+///   a degree which is larger than the size of the evaluation domain (and thus, interpolating the
+///   numerator would yield an incorrect result). However, once the divisor values are divided
+///   out, the degree of the resulting polynomial should be smaller than the size of the
+///   evaluation domain, and thus, we can interpolate safely.
 /// - Then, we interpolate the polynomial over the domain specified by `inv_twiddles`.
 /// - And finally, we get the degree from the interpolated polynomial.
 #[cfg(debug_assertions)]
 fn get_transition_poly_degree<E: FieldElement>(
     evaluations: &[E],
     inv_twiddles: &[E::BaseField],
-    div_values: &[E::BaseField],
+    inv_div_values: &[E::BaseField],
 ) -> usize {
     let mut evaluations = evaluations
         .iter()
-        .zip(div_values)
-        .map(|(&c, &d)| c / E::from(d))
+        .zip(inv_div_values)
+        .map(|(&c, &d)| c.mul_base(d))
         .collect::<Vec<_>>();
     fft::interpolate_poly(&mut evaluations, inv_twiddles);
     math::polynom::degree_of(&evaluations)
@@ -467,10 +559,7 @@ fn get_transition_poly_degree<E: FieldElement>(
 #[cfg(debug_assertions)]
 fn evaluate_divisor<E: FieldElement>(
     divisor: &ConstraintDivisor<E::BaseField>,
-    domain_size: usize,
-    domain_offset: E::BaseField,
+    domain: &EvaluationDomain<E::BaseField>,
 ) -> Vec<E> {
-    let g = E::BaseField::get_root_of_unity(domain_size.trailing_zeros());
-    let domain = math::get_power_series_with_offset(g, domain_offset, domain_size);
-    domain.into_iter().map(|x| E::from(divisor.evaluate_at(x))).collect()
+    domain.elements().into_iter().map(|x| E::from(divisor.evaluate_at(x))).collect()
 }
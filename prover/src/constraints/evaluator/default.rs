@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::{sync::Arc, vec::Vec};
+
 use air::{
     Air, AuxRandElements, ConstraintCompositionCoefficients, EvaluationFrame, TransitionConstraints,
 };
@@ -35,14 +37,15 @@ const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 ///
 /// When `concurrent` feature is enabled, the extended execution trace is split into sets of
 /// sequential evaluation frames (called fragments), and frames in each fragment are evaluated
-/// in separate threads.
+/// in separate threads. When `numa` feature is enabled as well, fragments are scheduled on the
+/// NUMA nodes which own their part of the evaluation table.
 pub struct DefaultConstraintEvaluator<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> {
     air: &'a A,
     boundary_constraints: BoundaryConstraints<E>,
     transition_constraints: TransitionConstraints<E>,
     lagrange_constraints_evaluator: Option<LagrangeKernelConstraintsBatchEvaluator<E>>,
     aux_rand_elements: Option<AuxRandElements<E>>,
-    periodic_values: PeriodicValueTable<E::BaseField>,
+    periodic_values: Arc<PeriodicValueTable<E::BaseField>>,
 }
 
 impl<'a, A, E> ConstraintEvaluator<E> for DefaultConstraintEvaluator<'a, A, E>
@@ -60,7 +63,7 @@ where
         )
     )]
     fn evaluate<T: TraceLde<E>>(
-        self,
+        mut self,
         trace: &T,
         domain: &StarkDomain<<E as FieldElement>::BaseField>,
     ) -> CompositionPolyTrace<E> {
@@ -70,10 +73,18 @@ where
             "extended trace length is not consistent with evaluation domain"
         );
 
-        // build a list of constraint divisors; currently, all transition constraints have the same
-        // divisor which we put at the front of the list; boundary constraint divisors are appended
-        // after that
-        let mut divisors = vec![self.transition_constraints.divisor().clone()];
+        // build periodic value table, unless the table has been precomputed for the domain from
+        // the same periodic columns
+        let periodic_polys = self.air.get_periodic_column_polys();
+        self.periodic_values = match domain.periodic_values() {
+            Some(table) if table.is_built_from(&periodic_polys) => table.clone(),
+            _ => Arc::new(PeriodicValueTable::from_polys(self.air, periodic_polys)),
+        };
+
+        // build a list of constraint divisors; transition constraint divisors go at the front of
+        // the list (the default divisor being the first one); boundary constraint divisors are
+        // appended after that
+        let mut divisors = self.transition_constraints.divisors().to_vec();
         divisors.append(&mut self.boundary_constraints.get_divisors());
 
         // allocate space for constraint evaluations; when we are in debug mode, we also allocate
@@ -103,13 +114,7 @@ where
         // we evaluate constraints for all segments. otherwise, we evaluate constraints only
         // for the main segment.
         let mut fragments = evaluation_table.fragments(num_fragments);
-        iter_mut!(fragments).for_each(|fragment| {
-            if self.air.trace_info().is_multi_segment() {
-                self.evaluate_fragment_full(trace, domain, fragment);
-            } else {
-                self.evaluate_fragment_main(trace, domain, fragment);
-            }
-        });
+        self.evaluate_fragments(trace, domain, &mut fragments);
 
         // when in debug mode, make sure expected transition constraint degrees align with
         // actual degrees we got during constraint evaluation
@@ -147,8 +152,9 @@ where
         // evaluations
         let transition_constraints =
             air.get_transition_constraints(&composition_coefficients.transition);
-        // build periodic value table
-        let periodic_values = PeriodicValueTable::new(air);
+        // periodic value table is built when constraints are evaluated, as it may have been
+        // precomputed for the evaluation domain
+        let periodic_values = Arc::new(PeriodicValueTable::from_polys(air, Vec::new()));
 
         // build boundary constraint groups; these will be used to evaluate and compose boundary
         // constraint evaluations.
@@ -190,6 +196,48 @@ where
     // EVALUATION HELPERS
     // --------------------------------------------------------------------------------------------
 
+    /// Evaluates constraints for all fragments of the evaluation table.
+    ///
+    /// When `numa` feature is enabled and the machine has more than one NUMA node, fragments are
+    /// assigned to nodes in contiguous ranges, and each fragment is evaluated in a thread pinned
+    /// to the CPUs of its node. Memory of the evaluation table is not written to before this
+    /// point, and thus, the memory of each fragment is placed on the node which evaluates it.
+    fn evaluate_fragments<T: TraceLde<E>>(
+        &self,
+        trace: &T,
+        domain: &StarkDomain<A::BaseField>,
+        fragments: &mut [EvaluationTableFragment<E>],
+    ) {
+        #[cfg(all(feature = "numa", target_os = "linux"))]
+        if fragments.len() > 1 {
+            if let Some(topology) = crate::numa::NumaTopology::detect() {
+                let num_fragments = fragments.len();
+                fragments.par_iter_mut().enumerate().for_each(|(i, fragment)| {
+                    let _guard = topology.pin_to_node(topology.fragment_node(i, num_fragments));
+                    self.evaluate_fragment(trace, domain, fragment);
+                });
+                return;
+            }
+        }
+
+        iter_mut!(fragments).for_each(|fragment| self.evaluate_fragment(trace, domain, fragment));
+    }
+
+    /// Evaluates constraints for a single fragment of the evaluation table over all segments of
+    /// the execution trace.
+    fn evaluate_fragment<T: TraceLde<E>>(
+        &self,
+        trace: &T,
+        domain: &StarkDomain<A::BaseField>,
+        fragment: &mut EvaluationTableFragment<E>,
+    ) {
+        if self.air.trace_info().is_multi_segment() {
+            self.evaluate_fragment_full(trace, domain, fragment);
+        } else {
+            self.evaluate_fragment_main(trace, domain, fragment);
+        }
+    }
+
     /// Evaluates constraints for a single fragment of the evaluation table.
     ///
     /// This evaluates constraints only over the main segment of the execution trace.
@@ -200,9 +248,14 @@ where
         fragment: &mut EvaluationTableFragment<E>,
     ) {
         // initialize buffers to hold trace values and evaluation results at each step;
-        let mut main_frame = EvaluationFrame::new(trace.trace_info().main_trace_width());
+        let frame_offsets = self.air.context().frame_offsets();
+        let mut main_frame = EvaluationFrame::with_offsets(
+            trace.trace_info().main_trace_width(),
+            frame_offsets.to_vec(),
+        );
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
         let mut t_evaluations = vec![E::BaseField::ZERO; self.num_main_transition_constraints()];
+        let num_t_divisors = self.transition_constraints.num_divisors();
 
         // this will be used to convert steps in constraint evaluation domain to steps in
         // LDE domain
@@ -212,14 +265,17 @@ where
             let step = i + fragment.offset();
 
             // update evaluation frame buffer with data from the execution trace; this will
-            // read current and next rows from the trace into the buffer; data in the trace
-            // table is extended over the LDE domain, so, we need to convert step in constraint
-            // evaluation domain, into a step in LDE domain, in case these domains are different
+            // read current and next rows (as well as rows at any additional frame offsets) from
+            // the trace into the buffer; data in the trace table is extended over the LDE domain,
+            // so, we need to convert step in constraint evaluation domain, into a step in LDE
+            // domain, in case these domains are different
             trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
 
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer
-            evaluations[0] = self.evaluate_main_transition(&main_frame, step, &mut t_evaluations);
+            // evaluate transition constraints and save the merged results into the first slots of
+            // the evaluations buffer (one slot per transition constraint divisor)
+            let (t_merged, b_evaluations) = evaluations.split_at_mut(num_t_divisors);
+            t_merged.fill(E::ZERO);
+            self.evaluate_main_transition(&main_frame, step, &mut t_evaluations, t_merged);
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
@@ -228,12 +284,7 @@ where
             // evaluate boundary constraints; the results go into remaining slots of the
             // evaluations buffer
             let main_state = main_frame.current();
-            self.boundary_constraints.evaluate_main(
-                main_state,
-                domain,
-                step,
-                &mut evaluations[1..],
-            );
+            self.boundary_constraints.evaluate_main(main_state, domain, step, b_evaluations);
 
             // record the result in the evaluation table
             fragment.update_row(i, &evaluations);
@@ -251,11 +302,19 @@ where
         fragment: &mut EvaluationTableFragment<E>,
     ) {
         // initialize buffers to hold trace values and evaluation results at each step
-        let mut main_frame = EvaluationFrame::new(trace.trace_info().main_trace_width());
-        let mut aux_frame = EvaluationFrame::new(trace.trace_info().aux_segment_width());
+        let frame_offsets = self.air.context().frame_offsets();
+        let mut main_frame = EvaluationFrame::with_offsets(
+            trace.trace_info().main_trace_width(),
+            frame_offsets.to_vec(),
+        );
+        let mut aux_frame = EvaluationFrame::with_offsets(
+            trace.trace_info().aux_segment_width(),
+            frame_offsets.to_vec(),
+        );
         let mut tm_evaluations = vec![E::BaseField::ZERO; self.num_main_transition_constraints()];
         let mut ta_evaluations = vec![E::ZERO; self.num_aux_transition_constraints()];
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
+        let num_t_divisors = self.transition_constraints.num_divisors();
 
         // this will be used to convert steps in constraint evaluation domain to steps in
         // LDE domain
@@ -268,13 +327,20 @@ where
             trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
             trace.read_aux_trace_frame_into(step << lde_shift, &mut aux_frame);
 
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer; we evaluate and compose constraints in the same function, we
-            // can just add up the results of evaluating main and auxiliary constraints.
-            evaluations[0] = self.evaluate_main_transition(&main_frame, step, &mut tm_evaluations);
-
-            evaluations[0] +=
-                self.evaluate_aux_transition(&main_frame, &aux_frame, step, &mut ta_evaluations);
+            // evaluate transition constraints and save the merged results into the first slots of
+            // the evaluations buffer (one slot per transition constraint divisor); we evaluate and
+            // compose constraints in the same function, so we can just add up the results of
+            // evaluating main and auxiliary constraints.
+            let (t_merged, b_evaluations) = evaluations.split_at_mut(num_t_divisors);
+            t_merged.fill(E::ZERO);
+            self.evaluate_main_transition(&main_frame, step, &mut tm_evaluations, t_merged);
+            self.evaluate_aux_transition(
+                &main_frame,
+                &aux_frame,
+                step,
+                &mut ta_evaluations,
+                t_merged,
+            );
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
@@ -289,7 +355,7 @@ where
                 aux_state,
                 domain,
                 step,
-                &mut evaluations[1..],
+                b_evaluations,
             );
 
             // record the result in the evaluation table
@@ -326,6 +392,9 @@ where
     /// Evaluates transition constraints of the main execution trace at the specified step of the
     /// constraint evaluation domain.
     ///
+    /// Constraint evaluations are merged using random linear combinations, and the result for
+    /// each transition constraint divisor is added to the corresponding slot of `result`.
+    ///
     /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
     /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
     fn evaluate_main_transition(
@@ -333,7 +402,8 @@ where
         main_frame: &EvaluationFrame<E::BaseField>,
         step: usize,
         evaluations: &mut [E::BaseField],
-    ) -> E {
+        result: &mut [E],
+    ) {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::BaseField::ZERO);
 
@@ -344,17 +414,23 @@ where
         // the results into evaluations buffer
         self.air.evaluate_transition(main_frame, periodic_values, evaluations);
 
-        // merge transition constraint evaluations into a single value and return it;
-        // we can do this here because all transition constraints have the same divisor.
-        evaluations
+        // merge transition constraint evaluations into a single value per divisor; we can do this
+        // here because all constraints merged into the same value have the same divisor.
+        for ((&const_eval, coef), &divisor_idx) in evaluations
             .iter()
-            .zip(self.transition_constraints.main_constraint_coef().iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef.mul_base(const_eval))
+            .zip(self.transition_constraints.main_constraint_coef())
+            .zip(self.transition_constraints.main_constraint_divisors())
+        {
+            result[divisor_idx] += coef.mul_base(const_eval);
+        }
     }
 
     /// Evaluates all transition constraints (i.e., for main and the auxiliary trace segment) at the
     /// specified step of the constraint evaluation domain.
     ///
+    /// Constraint evaluations are merged using random linear combinations, and the result for
+    /// each transition constraint divisor is added to the corresponding slot of `result`.
+    ///
     /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
     /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
     fn evaluate_aux_transition(
@@ -363,32 +439,50 @@ where
         aux_frame: &EvaluationFrame<E>,
         step: usize,
         evaluations: &mut [E],
-    ) -> E {
+        result: &mut [E],
+    ) {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::ZERO);
 
         // get periodic values at the evaluation step
         let periodic_values = self.periodic_values.get_row(step);
 
+        let aux_rand_elements = self
+            .aux_rand_elements
+            .as_ref()
+            .expect("expected aux rand elements to be present")
+            .rand_elements();
+
         // evaluate transition constraints over the auxiliary trace segment and save the results into
-        // evaluations buffer
-        self.air.evaluate_aux_transition(
+        // evaluations buffer; constraints of lookup arguments (if any) are placed at the end
+        let num_lookups = self.air.context().num_lookups();
+        let (aux_evaluations, lookup_evaluations) =
+            evaluations.split_at_mut(evaluations.len() - num_lookups);
+        if !aux_evaluations.is_empty() {
+            self.air.evaluate_aux_transition(
+                main_frame,
+                aux_frame,
+                periodic_values,
+                aux_rand_elements,
+                aux_evaluations,
+            );
+        }
+        self.air.evaluate_lookup_transition(
             main_frame,
             aux_frame,
-            periodic_values,
-            self.aux_rand_elements
-                .as_ref()
-                .expect("expected aux rand elements to be present")
-                .rand_elements(),
-            evaluations,
+            aux_rand_elements,
+            lookup_evaluations,
         );
 
-        // merge transition constraint evaluations into a single value and return it;
-        // we can do this here because all transition constraints have the same divisor.
-        evaluations
+        // merge transition constraint evaluations into a single value per divisor; we can do this
+        // here because all constraints merged into the same value have the same divisor.
+        for ((&const_eval, coef), &divisor_idx) in evaluations
             .iter()
-            .zip(self.transition_constraints.aux_constraint_coef().iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef * const_eval)
+            .zip(self.transition_constraints.aux_constraint_coef())
+            .zip(self.transition_constraints.aux_constraint_divisors())
+        {
+            result[divisor_idx] += coef * const_eval;
+        }
     }
 
     // ACCESSORS
@@ -10,6 +10,9 @@ use air::{
     LagrangeKernelEvaluationFrame, LagrangeKernelRandElements,
 };
 use math::{batch_inversion, FieldElement};
+use utils::batch_iter_mut;
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 use crate::{StarkDomain, TraceLde};
 
@@ -64,47 +67,67 @@ impl<E: FieldElement> LagrangeKernelConstraintsBatchEvaluator<E> {
         );
         let boundary_divisors_inv = self.compute_boundary_divisors_inv(domain);
 
-        let mut frame = LagrangeKernelEvaluationFrame::new_empty();
-
-        for step in 0..domain.ce_domain_size() {
-            // compute Lagrange kernel frame
-            trace.read_lagrange_kernel_frame_into(
-                step << lde_shift,
-                self.lagrange_kernel_constraints.lagrange_kernel_col_idx,
-                &mut frame,
-            );
-
-            // Compute the combined transition and boundary constraints evaluations for this row
-            let combined_evaluations = {
-                let mut combined_evaluations = E::ZERO;
-
-                // combine transition constraints
-                for trans_constraint_idx in
-                    0..self.lagrange_kernel_constraints.transition.num_constraints()
-                {
-                    let numerator = self
-                        .lagrange_kernel_constraints
-                        .transition
-                        .evaluate_ith_numerator(&frame, &self.rand_elements, trans_constraint_idx);
-                    let inv_divisor = trans_constraints_divisors
-                        .get_inverse_divisor_eval(trans_constraint_idx, step);
-
-                    combined_evaluations += numerator * inv_divisor;
+        // when `concurrent` feature is enabled, the constraint evaluation domain is broken into
+        // batches of consecutive steps which are evaluated in multiple threads; each batch writes
+        // only into its own part of the accumulator, and thus, the result does not depend on the
+        // number of threads
+        batch_iter_mut!(
+            combined_evaluations_acc,
+            128, // min batch size
+            |batch: &mut [E], batch_offset: usize| {
+                let mut frame = LagrangeKernelEvaluationFrame::new_empty();
+                for (i, acc_value) in batch.iter_mut().enumerate() {
+                    let step = batch_offset + i;
+
+                    // compute Lagrange kernel frame
+                    trace.read_lagrange_kernel_frame_into(
+                        step << lde_shift,
+                        self.lagrange_kernel_constraints.lagrange_kernel_col_idx,
+                        &mut frame,
+                    );
+
+                    *acc_value += self.evaluate_step(
+                        &frame,
+                        step,
+                        &trans_constraints_divisors,
+                        &boundary_divisors_inv,
+                    );
                 }
+            }
+        );
+    }
 
-                // combine boundary constraints
-                {
-                    let boundary_numerator =
-                        self.lagrange_kernel_constraints.boundary.evaluate_numerator_at(&frame);
+    /// Computes the combined transition and boundary constraint evaluations for the specified
+    /// step of the constraint evaluation domain.
+    fn evaluate_step(
+        &self,
+        frame: &LagrangeKernelEvaluationFrame<E>,
+        step: usize,
+        trans_constraints_divisors: &LagrangeKernelTransitionConstraintsDivisor<E>,
+        boundary_divisors_inv: &[E],
+    ) -> E {
+        let mut combined_evaluations = E::ZERO;
+
+        // combine transition constraints
+        for trans_constraint_idx in 0..self.lagrange_kernel_constraints.transition.num_constraints()
+        {
+            let numerator = self.lagrange_kernel_constraints.transition.evaluate_ith_numerator(
+                frame,
+                &self.rand_elements,
+                trans_constraint_idx,
+            );
+            let inv_divisor =
+                trans_constraints_divisors.get_inverse_divisor_eval(trans_constraint_idx, step);
 
-                    combined_evaluations += boundary_numerator * boundary_divisors_inv[step];
-                }
+            combined_evaluations += numerator * inv_divisor;
+        }
 
-                combined_evaluations
-            };
+        // combine boundary constraints
+        let boundary_numerator =
+            self.lagrange_kernel_constraints.boundary.evaluate_numerator_at(frame);
+        combined_evaluations += boundary_numerator * boundary_divisors_inv[step];
 
-            combined_evaluations_acc[step] += combined_evaluations;
-        }
+        combined_evaluations
     }
 
     // HELPERS
@@ -17,7 +17,7 @@ use boundary::BoundaryConstraints;
 mod lagrange;
 
 mod periodic_table;
-use periodic_table::PeriodicValueTable;
+pub(crate) use periodic_table::PeriodicValueTable;
 
 // CONSTRAINT EVALUATOR TRAIT
 // ================================================================================================
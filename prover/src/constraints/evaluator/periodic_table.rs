@@ -10,6 +10,7 @@ use math::{fft, StarkField};
 use utils::uninit_vector;
 
 pub struct PeriodicValueTable<B: StarkField> {
+    polys: Vec<Vec<B>>,
     values: Vec<B>,
     length: usize,
     width: usize,
@@ -22,11 +23,20 @@ impl<B: StarkField> PeriodicValueTable<B> {
     /// values of all periodic columns normalized to the same length. This enables simple lookup
     /// into the able using step index of the constraint evaluation domain.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> PeriodicValueTable<B> {
-        // get a list of polynomials describing periodic columns from AIR. if there are no
-        // periodic columns return an empty table
-        let polys = air.get_periodic_column_polys();
+        Self::from_polys(air, air.get_periodic_column_polys())
+    }
+
+    /// Builds a table of periodic column values for the specified AIR from the provided
+    /// polynomials describing the periodic columns of the AIR.
+    pub fn from_polys<A: Air<BaseField = B>>(air: &A, polys: Vec<Vec<B>>) -> PeriodicValueTable<B> {
+        // if there are no periodic columns return an empty table
         if polys.is_empty() {
-            return PeriodicValueTable { values: Vec::new(), length: 0, width: 0 };
+            return PeriodicValueTable {
+                polys,
+                values: Vec::new(),
+                length: 0,
+                width: 0,
+            };
         }
 
         // determine the size of the biggest polynomial in the set. unwrap is OK here
@@ -62,6 +72,7 @@ impl<B: StarkField> PeriodicValueTable<B> {
         }
 
         PeriodicValueTable {
+            polys,
             values,
             length: column_length,
             width: row_width,
@@ -71,6 +82,11 @@ impl<B: StarkField> PeriodicValueTable<B> {
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns true if this table was built from the specified periodic column polynomials.
+    pub fn is_built_from(&self, polys: &[Vec<B>]) -> bool {
+        self.polys == polys
+    }
+
     pub fn is_empty(&self) -> bool {
         self.width == 0
     }
@@ -0,0 +1,131 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#[cfg(not(feature = "async"))]
+use air::proof::Proof;
+use air::Air;
+#[cfg(not(feature = "async"))]
+use crypto::HashFunctionVisitor;
+use crypto::{ElementHasher, HashFunction, HashFunctionField};
+use math::ExtensibleField;
+
+#[cfg(not(feature = "async"))]
+use crate::ProverError;
+use crate::{Prover, Trace};
+
+// PROVER FAMILY
+// ================================================================================================
+
+/// Defines a family of STARK provers for a computation which differ only in the hash function
+/// used by the protocol.
+///
+/// Implementing this trait allows a [DefaultProver] to select the hash function at runtime. The
+/// family specifies the AIR and the execution trace of the computation, and builds a [Prover]
+/// instantiated with any of the hash functions listed in [HashFunction].
+pub trait ProverFamily {
+    /// Base field for the computation described by the provers of this family.
+    type BaseField: HashFunctionField + ExtensibleField<2> + ExtensibleField<3>;
+
+    /// Algebraic intermediate representation (AIR) for the computation.
+    type Air: Air<BaseField = Self::BaseField>;
+
+    /// Execution trace of the computation.
+    type Trace: Trace<BaseField = Self::BaseField> + Send + Sync;
+
+    /// Prover of this family instantiated with hash function `H`.
+    type Prover<H>: Prover<
+        BaseField = Self::BaseField,
+        Air = Self::Air,
+        Trace = Self::Trace,
+        HashFn = H,
+    >
+    where
+        H: ElementHasher<BaseField = Self::BaseField>;
+
+    /// Returns a prover of this family which uses hash function `H`.
+    fn build_prover<H>(&self) -> Self::Prover<H>
+    where
+        H: ElementHasher<BaseField = Self::BaseField>;
+}
+
+// DEFAULT PROVER
+// ================================================================================================
+
+/// A STARK prover which uses a hash function selected at runtime.
+///
+/// Unlike a [Prover], for which the hash function is specified via a generic parameter, the hash
+/// function of this prover is specified via the [HashFunction] enum. This is convenient for
+/// services which need to generate proofs with different hash functions (e.g., depending on
+/// the preferences of a specific request): proof generation code for all supported hash functions
+/// is instantiated once, and the appropriate instance is selected when a proof is generated.
+pub struct DefaultProver<F: ProverFamily> {
+    family: F,
+    hash_fn: HashFunction,
+}
+
+impl<F: ProverFamily> DefaultProver<F> {
+    /// Returns a new prover which generates proofs using the provers of the specified `family`
+    /// instantiated with the specified hash function.
+    pub fn new(family: F, hash_fn: HashFunction) -> Self {
+        Self { family, hash_fn }
+    }
+
+    /// Returns the family of provers used by this prover.
+    pub fn family(&self) -> &F {
+        &self.family
+    }
+
+    /// Returns the hash function used by this prover.
+    pub fn hash_fn(&self) -> HashFunction {
+        self.hash_fn
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace.
+    ///
+    /// The proof is generated by the prover of this prover's family instantiated with the hash
+    /// function of this prover; thus, the proof is identical to the proof which would be
+    /// generated by invoking [Prover::prove()] on that prover directly.
+    ///
+    /// This method is not available when the `async` feature is enabled.
+    ///
+    /// # Errors
+    /// Returns an error if the hash function of this prover is not defined over the base field
+    /// of the computation, or if proof generation fails.
+    #[cfg(not(feature = "async"))]
+    pub fn prove(&self, trace: F::Trace) -> Result<Proof, ProverError>
+    where
+        <F::Air as Air>::PublicInputs: Send,
+        <F::Air as Air>::GkrProof: Send,
+    {
+        let visitor = ProveVisitor { family: &self.family, trace };
+        F::BaseField::dispatch(self.hash_fn, visitor)
+            .unwrap_or(Err(ProverError::UnsupportedHashFunction(self.hash_fn)))
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Generates a proof for the provided trace using the prover of the specified family.
+#[cfg(not(feature = "async"))]
+struct ProveVisitor<'a, F: ProverFamily> {
+    family: &'a F,
+    trace: F::Trace,
+}
+
+#[cfg(not(feature = "async"))]
+impl<F> HashFunctionVisitor<F::BaseField> for ProveVisitor<'_, F>
+where
+    F: ProverFamily,
+    <F::Air as Air>::PublicInputs: Send,
+    <F::Air as Air>::GkrProof: Send,
+{
+    type Output = Result<Proof, ProverError>;
+
+    fn visit<H: ElementHasher<BaseField = F::BaseField>>(self) -> Self::Output {
+        self.family.build_prover::<H>().prove(self.trace)
+    }
+}
@@ -3,33 +3,55 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 
 use air::Air;
-use math::{fft, get_power_series, StarkField};
+use math::{
+    fft::{EvaluationDomain, FftDomain},
+    get_power_series, StarkField,
+};
+
+use crate::constraints::PeriodicValueTable;
 
 // TYPES AND INTERFACES
 // ================================================================================================
 
+/// Numerator of a constraint divisor together with inverse evaluations of the numerator over the
+/// constraint evaluation domain.
+pub(crate) type DivisorInvEvaluations<B> = (Vec<(usize, B)>, Vec<B>);
+
 /// Info about domains related to specific instance of proof generation.
 pub struct StarkDomain<B: StarkField> {
-    /// Twiddles which can be used to evaluate polynomials in the trace domain. Length of this
-    /// vector is half the length of the trace domain size.
-    trace_twiddles: Vec<B>,
+    /// Trace domain (a subgroup of size equal to the trace length) with cached twiddles and
+    /// inverse twiddles; these are re-used for all evaluations and interpolations of trace
+    /// polynomials.
+    trace_domain: EvaluationDomain<B>,
+
+    /// Constraint evaluation domain (a coset shifted by the domain offset) with cached inverse
+    /// twiddles and powers of the offset; these are re-used for interpolation of constraint
+    /// evaluations.
+    ce_domain: EvaluationDomain<B>,
 
     /// [g^i for i in (0..ce_domain_size)] where g is the constraint evaluation domain generator.
-    ce_domain: Vec<B>,
+    ce_domain_elements: Vec<B>,
 
-    /// LDE domain size / constraint evaluation domain size
-    ce_to_lde_blowup: usize,
+    /// Low-degree extension domain; this domain does not cache twiddles as polynomials are
+    /// evaluated over it using trace domain twiddles.
+    lde_domain: EvaluationDomain<B>,
 
     /// A mask which can be used to compute (x % ce_domain_size) via binary AND. This takes
     /// advantage of the fact that ce_domain_size is a power of two. The mask is then simply
     /// ce_domain_size - 1.
     ce_domain_mod_mask: usize,
 
-    /// Offset of the low-degree extension domain.
-    domain_offset: B,
+    /// Values of periodic columns extended over the constraint evaluation domain; these are set
+    /// only for domains built as a part of a [ProvingKey](crate::ProvingKey).
+    periodic_values: Option<Arc<PeriodicValueTable<B>>>,
+
+    /// Inverse evaluations of constraint divisor numerators over the constraint evaluation
+    /// domain keyed by numerator; these are set only for domains built as a part of a
+    /// [ProvingKey](crate::ProvingKey).
+    divisor_inv_evaluations: Vec<DivisorInvEvaluations<B>>,
 }
 
 // STARK DOMAIN IMPLEMENTATION
@@ -38,19 +60,15 @@ pub struct StarkDomain<B: StarkField> {
 impl<B: StarkField> StarkDomain<B> {
     /// Returns a new STARK domain initialized with the provided `context`.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
-        let trace_twiddles = fft::get_twiddles(air.trace_length());
-
-        // build constraint evaluation domain
-        let domain_gen = B::get_root_of_unity(air.ce_domain_size().ilog2());
-        let ce_domain = get_power_series(domain_gen, air.ce_domain_size());
-
-        StarkDomain {
-            trace_twiddles,
-            ce_domain,
-            ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
-            ce_domain_mod_mask: air.ce_domain_size() - 1,
-            domain_offset: air.domain_offset(),
-        }
+        let trace_domain = EvaluationDomain::new(air.trace_length(), B::ONE)
+            .with_twiddles()
+            .with_inv_twiddles();
+        Self::from_trace_domain(
+            trace_domain,
+            air.ce_domain_size(),
+            air.lde_domain_size(),
+            air.domain_offset(),
+        )
     }
 
     /// Returns a new STARK domain initialized with the provided custom inputs.
@@ -62,30 +80,57 @@ impl<B: StarkField> StarkDomain<B> {
         );
         assert!(blowup_factor.is_power_of_two(), "blowup factor must be a power of 2");
 
-        let ce_domain_size = trace_twiddles.len() * blowup_factor * 2;
-        let domain_gen = B::get_root_of_unity(ce_domain_size.ilog2());
-        let ce_domain = get_power_series(domain_gen, ce_domain_size);
+        let trace_domain =
+            EvaluationDomain::from_twiddles(trace_twiddles, B::ONE).with_inv_twiddles();
+        let ce_domain_size = trace_domain.size() * blowup_factor;
+        Self::from_trace_domain(trace_domain, ce_domain_size, ce_domain_size, domain_offset)
+    }
+
+    /// Builds constraint evaluation and LDE domains of the specified sizes shifted by
+    /// `domain_offset` and returns a new STARK domain for the provided trace domain.
+    fn from_trace_domain(
+        trace_domain: EvaluationDomain<B>,
+        ce_domain_size: usize,
+        lde_domain_size: usize,
+        domain_offset: B,
+    ) -> Self {
+        let ce_domain = EvaluationDomain::new(ce_domain_size, domain_offset)
+            .with_inv_twiddles()
+            .with_offset_powers();
+        let ce_domain_elements = get_power_series(ce_domain.generator(), ce_domain_size);
 
         StarkDomain {
-            trace_twiddles,
+            trace_domain,
             ce_domain,
-            ce_to_lde_blowup: 1,
+            ce_domain_elements,
+            lde_domain: EvaluationDomain::new(lde_domain_size, domain_offset),
             ce_domain_mod_mask: ce_domain_size - 1,
-            domain_offset,
+            periodic_values: None,
+            divisor_inv_evaluations: Vec::new(),
         }
     }
 
     // EXECUTION TRACE
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the trace domain.
+    pub fn trace_domain(&self) -> &EvaluationDomain<B> {
+        &self.trace_domain
+    }
+
     /// Returns length of the execution trace for this computation.
     pub fn trace_length(&self) -> usize {
-        &self.trace_twiddles.len() * 2
+        self.trace_domain.size()
     }
 
     /// Returns twiddles which can be used to evaluate trace polynomials.
     pub fn trace_twiddles(&self) -> &[B] {
-        &self.trace_twiddles
+        self.trace_domain.twiddles()
+    }
+
+    /// Returns inverse twiddles which can be used to interpolate trace polynomials.
+    pub fn trace_inv_twiddles(&self) -> &[B] {
+        self.trace_domain.inv_twiddles()
     }
 
     /// Returns blowup factor from trace to constraint evaluation domain.
@@ -101,27 +146,38 @@ impl<B: StarkField> StarkDomain<B> {
     // CONSTRAINT EVALUATION DOMAIN
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the constraint evaluation domain.
+    pub fn ce_domain(&self) -> &EvaluationDomain<B> {
+        &self.ce_domain
+    }
+
     /// Returns the size of the constraint evaluation domain for this computation.
     #[inline(always)]
     pub fn ce_domain_size(&self) -> usize {
-        self.ce_domain.len()
+        self.ce_domain_elements.len()
     }
 
     /// Returns the generator of constraint evaluation domain.
     pub fn ce_domain_generator(&self) -> B {
-        B::get_root_of_unity(self.ce_domain_size().ilog2())
+        self.ce_domain.generator()
+    }
+
+    /// Returns inverse twiddles which can be used to interpolate polynomials from their
+    /// evaluations over the constraint evaluation domain.
+    pub fn ce_inv_twiddles(&self) -> &[B] {
+        self.ce_domain.inv_twiddles()
     }
 
     /// Returns blowup factor from constraint evaluation to LDE domain.
     pub fn ce_to_lde_blowup(&self) -> usize {
-        self.ce_to_lde_blowup
+        self.lde_domain_size() / self.ce_domain_size()
     }
 
     /// Returns s * g^step where g is the constraint evaluation domain generator and s is the
     /// domain offset.
     #[inline(always)]
     pub fn get_ce_x_at(&self, step: usize) -> B {
-        self.ce_domain[step] * self.domain_offset
+        self.ce_domain_elements[step] * self.offset()
     }
 
     /// Returns (s * g^step)^power where g is the constraint evaluation domain generator and s is
@@ -137,19 +193,51 @@ impl<B: StarkField> StarkDomain<B> {
         // power of two. this is also the reason why we can do & ce_domain_mod_mask instead of
         // performing the actual modulus operation.
         let index = step.wrapping_mul(power as usize) & self.ce_domain_mod_mask;
-        self.ce_domain[index] * offset_exp
+        self.ce_domain_elements[index] * offset_exp
     }
 
     // LOW-DEGREE EXTENSION DOMAIN
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the low-degree extension domain.
+    pub fn lde_domain(&self) -> &EvaluationDomain<B> {
+        &self.lde_domain
+    }
+
     /// Returns the size of the low-degree extension domain.
     pub fn lde_domain_size(&self) -> usize {
-        self.ce_domain_size() * self.ce_to_lde_blowup()
+        self.lde_domain.size()
     }
 
     /// Returns LDE domain offset.
     pub fn offset(&self) -> B {
-        self.domain_offset
+        self.lde_domain.offset()
+    }
+
+    // PRECOMPUTED CONSTRAINT TABLES
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns periodic column values precomputed over the constraint evaluation domain, if any.
+    pub(crate) fn periodic_values(&self) -> Option<&Arc<PeriodicValueTable<B>>> {
+        self.periodic_values.as_ref()
+    }
+
+    /// Returns inverse evaluations of a divisor with the specified numerator precomputed over the
+    /// constraint evaluation domain, if any.
+    pub(crate) fn divisor_inv_evaluations(&self, numerator: &[(usize, B)]) -> Option<&[B]> {
+        self.divisor_inv_evaluations
+            .iter()
+            .find(|(n, _)| n == numerator)
+            .map(|(_, inv_evaluations)| inv_evaluations.as_slice())
+    }
+
+    /// Sets tables precomputed over the constraint evaluation domain of this domain.
+    pub(crate) fn set_constraint_tables(
+        &mut self,
+        periodic_values: PeriodicValueTable<B>,
+        divisor_inv_evaluations: Vec<DivisorInvEvaluations<B>>,
+    ) {
+        self.periodic_values = Some(Arc::new(periodic_values));
+        self.divisor_inv_evaluations = divisor_inv_evaluations;
     }
 }
@@ -5,8 +5,11 @@
 
 //! Contains common error types for prover and verifier.
 
+use alloc::{string::String, vec::Vec};
 use core::fmt;
 
+use crypto::{HashFunction, RandomCoinError};
+
 // PROVER ERROR
 // ================================================================================================
 /// Represents an error returned by the prover during an execution of the protocol.
@@ -21,6 +24,46 @@ pub enum ProverError {
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when zero-knowledge proofs are requested but the AIR does not exempt
+    /// the blinded rows of the execution trace from transition constraints.
+    InsufficientTransitionExemptions(usize, usize),
+    /// This error occurs when zero-knowledge proofs are requested but the AIR places an
+    /// assertion against one of the blinded rows of the execution trace.
+    AssertionOnBlindedRow(usize),
+    /// This error occurs when zero-knowledge proofs are requested but a transition constraint
+    /// with a custom divisor is enforced on a step whose evaluation frame includes blinded rows
+    /// of the execution trace.
+    TransitionConstraintOnBlindedRow(usize),
+    /// This error occurs when zero-knowledge proofs are requested for an AIR with a Lagrange
+    /// kernel column.
+    UnsupportedZkLagrangeKernel,
+    /// This error occurs when an execution trace validated via
+    /// [Prover::validate_trace()](crate::Prover::validate_trace) does not satisfy some of the
+    /// constraints of the AIR.
+    UnsatisfiedConstraints(Vec<ConstraintViolation>),
+    /// This error occurs when proof generation is cancelled via
+    /// [CancellationToken::cancel()](crate::CancellationToken::cancel).
+    Cancelled,
+    /// This error occurs when proof generation is resumed from a checkpoint which is malformed,
+    /// was saved for a different proof, or whose recorded commitments do not match the
+    /// commitments rebuilt from its contents.
+    InvalidCheckpoint(String),
+    /// This error occurs when a hash function selected at runtime via
+    /// [DefaultProver](crate::DefaultProver) is not defined over the base field of the AIR.
+    UnsupportedHashFunction(HashFunction),
+    /// This error occurs when a proof is generated from a
+    /// [CommittedTrace](crate::CommittedTrace) using proof options which would change the
+    /// commitment to the main trace segment.
+    IncompatibleCommittedTrace(String),
+    /// This error occurs when a proof is generated with a [ProvingKey](crate::ProvingKey) which
+    /// was built for different proof options or for an execution trace of a different shape.
+    IncompatibleProvingKey(String),
+    /// This error occurs when the number of queries specified by proof options is not smaller
+    /// than the size of the LDE domain, and thus, distinct query positions cannot be drawn.
+    TooManyQueries(usize, usize),
+    /// This error occurs when the public coin fails to draw the required number of distinct query
+    /// positions from the LDE domain.
+    FailedToDrawQueryPositions(RandomCoinError),
 }
 
 impl fmt::Display for ProverError {
@@ -36,6 +79,134 @@ impl fmt::Display for ProverError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the specified base field")
             }
+            Self::InsufficientTransitionExemptions(expected, actual) => {
+                write!(f, "zero-knowledge proofs require at least {expected} transition exemptions, but the AIR specifies {actual}")
+            }
+            Self::AssertionOnBlindedRow(step) => {
+                write!(f, "an assertion is placed against step {step} which is blinded in zero-knowledge proofs")
+            }
+            Self::TransitionConstraintOnBlindedRow(step) => {
+                write!(f, "a transition constraint is enforced on step {step} which references rows blinded in zero-knowledge proofs")
+            }
+            Self::UnsupportedZkLagrangeKernel => {
+                write!(f, "zero-knowledge proofs are not supported for AIRs with a Lagrange kernel column")
+            }
+            Self::UnsatisfiedConstraints(violations) => {
+                write!(f, "execution trace does not satisfy {} constraint evaluations", violations.len())?;
+                for violation in violations.iter().take(MAX_REPORTED_VIOLATIONS) {
+                    write!(f, "\n  {violation}")?;
+                }
+                if violations.len() > MAX_REPORTED_VIOLATIONS {
+                    write!(f, "\n  ...")?;
+                }
+                Ok(())
+            }
+            Self::Cancelled => {
+                write!(f, "proof generation was cancelled")
+            }
+            Self::InvalidCheckpoint(reason) => {
+                write!(f, "failed to resume proof generation from checkpoint: {reason}")
+            }
+            Self::UnsupportedHashFunction(hash_fn) => {
+                write!(f, "hash function {hash_fn} is not supported for the specified base field")
+            }
+            Self::IncompatibleCommittedTrace(reason) => {
+                write!(f, "failed to generate proof from committed trace: {reason}")
+            }
+            Self::IncompatibleProvingKey(reason) => {
+                write!(f, "failed to generate proof with proving key: {reason}")
+            }
+            Self::TooManyQueries(num_queries, lde_domain_size) => {
+                write!(f, "the number of queries must be smaller than the LDE domain size {lde_domain_size}, but was {num_queries}")
+            }
+            Self::FailedToDrawQueryPositions(err) => {
+                write!(f, "failed to draw query positions: {err}")
+            }
+        }
+    }
+}
+
+// CONSTRAINT VIOLATION
+// ================================================================================================
+/// Describes a single constraint which is not satisfied by an execution trace at a specific step.
+///
+/// Values are recorded in their printed form because constraints over the main and the auxiliary
+/// trace segments evaluate to elements of different fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintViolation {
+    /// The value in the specified column of the main trace segment at the specified step is not
+    /// equal to the value required by an assertion.
+    MainAssertion {
+        column: usize,
+        step: usize,
+        expected: String,
+        actual: String,
+    },
+    /// The value in the specified column of the auxiliary trace segment at the specified step is
+    /// not equal to the value required by an assertion.
+    AuxAssertion {
+        column: usize,
+        step: usize,
+        expected: String,
+        actual: String,
+    },
+    /// The first value of the Lagrange kernel column (located at the specified column of the
+    /// auxiliary trace segment) is not equal to the value required by the Lagrange kernel
+    /// boundary constraint.
+    LagrangeKernelAssertion {
+        column: usize,
+        expected: String,
+        actual: String,
+    },
+    /// The main transition constraint at the specified index did not evaluate to zero at the
+    /// specified step.
+    MainTransition {
+        constraint: usize,
+        step: usize,
+        value: String,
+    },
+    /// The auxiliary transition constraint at the specified index did not evaluate to zero at the
+    /// specified step.
+    AuxTransition {
+        constraint: usize,
+        step: usize,
+        value: String,
+    },
+    /// The Lagrange kernel transition constraint at the specified index did not evaluate to zero
+    /// at the specified step.
+    LagrangeKernelTransition {
+        constraint: usize,
+        step: usize,
+        value: String,
+    },
+}
+
+/// Maximum number of constraint violations listed when printing a
+/// [ProverError::UnsatisfiedConstraints] error.
+const MAX_REPORTED_VIOLATIONS: usize = 10;
+
+impl fmt::Display for ConstraintViolation {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MainAssertion { column, step, expected, actual } => {
+                write!(f, "assertion main_trace({column}, {step}) == {expected} is not satisfied; the value was {actual}")
+            }
+            Self::AuxAssertion { column, step, expected, actual } => {
+                write!(f, "assertion aux_trace({column}, {step}) == {expected} is not satisfied; the value was {actual}")
+            }
+            Self::LagrangeKernelAssertion { column, expected, actual } => {
+                write!(f, "Lagrange kernel assertion aux_trace({column}, 0) == {expected} is not satisfied; the value was {actual}")
+            }
+            Self::MainTransition { constraint, step, value } => {
+                write!(f, "main transition constraint {constraint} evaluated to {value} instead of ZERO at step {step}")
+            }
+            Self::AuxTransition { constraint, step, value } => {
+                write!(f, "auxiliary transition constraint {constraint} evaluated to {value} instead of ZERO at step {step}")
+            }
+            Self::LagrangeKernelTransition { constraint, step, value } => {
+                write!(f, "Lagrange transition constraint {constraint} evaluated to {value} instead of ZERO at step {step}")
+            }
         }
     }
 }
@@ -0,0 +1,140 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+
+use air::{Air, ConstraintDivisor, ProofOptions, TraceInfo};
+use math::StarkField;
+
+use crate::{
+    constraints::{get_inv_evaluation, PeriodicValueTable},
+    domain::DivisorInvEvaluations,
+    ProverError, StarkDomain,
+};
+
+// PROVING KEY
+// ================================================================================================
+
+/// Data needed to generate proofs for a given computation which depends only on the AIR of the
+/// computation and on proof options.
+///
+/// A proving key is built via [Prover::build_proving_key()](crate::Prover::build_proving_key),
+/// and any number of proofs can then be generated with it via
+/// [Prover::prove_with_key()](crate::Prover::prove_with_key). The key contains:
+/// * The [StarkDomain] of the computation, including twiddles for the trace and constraint
+///   evaluation domains.
+/// * Values of periodic columns extended over the constraint evaluation domain.
+/// * Inverse evaluations of the transition constraint divisors and of the assertion divisors
+///   over the constraint evaluation domain.
+///
+/// Proofs generated with a proving key are identical to the proofs generated without it. A key
+/// can be used for any execution trace of the same shape as the trace it was built for, as long
+/// as the proof options are the same. Periodic column values and divisors are used only if the
+/// AIR instance of the proven trace defines the same periodic columns and divisors as the AIR
+/// instance the key was built from (e.g., when assertions depend on public inputs, divisors of
+/// such assertions are evaluated for every proof as usual).
+pub struct ProvingKey<B: StarkField> {
+    trace_info: TraceInfo,
+    options: ProofOptions,
+    num_composition_columns: usize,
+    domain: StarkDomain<B>,
+}
+
+impl<B: StarkField> ProvingKey<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new proving key for the computation described by the specified AIR instance.
+    pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
+        let mut domain = StarkDomain::new(air);
+
+        // evaluate periodic columns over the constraint evaluation domain
+        let periodic_values = PeriodicValueTable::new(air);
+
+        // evaluate all distinct divisors of transition constraints and assertions; the inverse
+        // evaluations depend only on the numerators of the divisors
+        let context = air.context();
+        let mut divisors = (0..context.num_transition_constraints())
+            .map(|i| context.get_transition_divisor(i))
+            .collect::<Vec<_>>();
+        divisors.extend(
+            air.get_assertions()
+                .iter()
+                .map(|assertion| ConstraintDivisor::from_assertion(assertion, air.trace_length())),
+        );
+        let mut divisor_inv_evaluations: Vec<DivisorInvEvaluations<B>> = Vec::new();
+        for divisor in divisors {
+            if divisor_inv_evaluations.iter().all(|(n, _)| n != divisor.numerator()) {
+                let inv_evaluations = get_inv_evaluation(&divisor, &domain);
+                divisor_inv_evaluations.push((divisor.numerator().to_vec(), inv_evaluations));
+            }
+        }
+
+        domain.set_constraint_tables(periodic_values, divisor_inv_evaluations);
+
+        ProvingKey {
+            trace_info: air.trace_info().clone(),
+            options: air.options().clone(),
+            num_composition_columns: context.num_constraint_composition_columns(),
+            domain,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the layout of the execution trace for which this key was built.
+    pub fn trace_info(&self) -> &TraceInfo {
+        &self.trace_info
+    }
+
+    /// Returns the proof options for which this key was built.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    /// Returns the number of columns of the constraint composition polynomial of the computation.
+    pub fn num_composition_columns(&self) -> usize {
+        self.num_composition_columns
+    }
+
+    /// Returns the STARK domain of the computation.
+    pub fn domain(&self) -> &StarkDomain<B> {
+        &self.domain
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes sure a proof for the specified AIR instance can be generated with this key.
+    pub(crate) fn validate<A: Air<BaseField = B>>(&self, air: &A) -> Result<(), ProverError> {
+        if air.options() != &self.options {
+            return Err(incompatible("key was built for different proof options"));
+        }
+        let trace_info = air.trace_info();
+        if trace_info.length() != self.trace_info.length()
+            || trace_info.main_trace_width() != self.trace_info.main_trace_width()
+            || trace_info.aux_segment_width() != self.trace_info.aux_segment_width()
+        {
+            return Err(incompatible("key was built for an execution trace of a different shape"));
+        }
+        if air.ce_domain_size() != self.domain.ce_domain_size()
+            || air.lde_domain_size() != self.domain.lde_domain_size()
+            || air.domain_offset() != self.domain.offset()
+            || air.context().num_constraint_composition_columns() != self.num_composition_columns
+        {
+            return Err(incompatible("key was built for constraints of different degrees"));
+        }
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an [ProverError::IncompatibleProvingKey] error with the specified message.
+fn incompatible(reason: &str) -> ProverError {
+    ProverError::IncompatibleProvingKey(reason.to_string())
+}
@@ -33,6 +33,19 @@
 //! be in the range between 15 KB (for very small computations) and 300 KB (for very large
 //! computations).
 //!
+//! # Zero-knowledge
+//! If zero-knowledge is enabled in [ProofOptions] (see [ProofOptions::with_zk()]), the prover
+//! overwrites the last rows of every trace segment with random values, appends a random column
+//! to the constraint composition polynomial, and salts FRI layer commitments. The randomness is
+//! derived from a secret seed supplied via [Prover::zk_seed()]. The AIR must exempt the blinded
+//! rows from transition constraints and must not place assertions against them.
+//!
+//! # Deterministic proof generation
+//! Proof generation is deterministic: the proof for a given execution trace depends only on the
+//! proof options and, for zero-knowledge proofs, on the seed returned from [Prover::zk_seed()].
+//! In particular, the proof-of-work nonce for query seed grinding is the smallest valid nonce,
+//! and thus, proofs generated with and without the `concurrent` feature are byte-identical.
+//!
 //! Proof generation time is also highly dependent on the specifics of a given computation, but
 //! also depends on the capabilities of the machine used to generate the proofs (i.e. on number
 //! of CPU cores and memory bandwidth).
@@ -42,24 +55,32 @@
 #[macro_use]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[cfg(feature = "async")]
 use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{borrow::Borrow, time::Duration};
 
 use air::AuxRandElements;
 pub use air::{
-    proof, proof::Proof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, LagrangeKernelRandElements, ProofOptions, TraceInfo,
+    proof, proof::Proof, Air, AirComposition, AirContext, Assertion, BoundaryConstraint,
+    BoundaryConstraintGroup, ComposedInputs, ConstraintCompositionCoefficients, ConstraintDivisor,
+    DeepCompositionCoefficients, EvaluationFrame, Expr, FieldExtension, LagrangeKernelRandElements,
+    LookupArgument, MemoryBudget, ProofOptions, PublicInputs, PublicSequence, SecurityTargetError,
+    SpeedPreference, StoneFriParameters, StoneParametersError, StoneProofParameters, TraceInfo,
     TransitionConstraintDegree,
 };
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin};
+pub use crypto::HashFunction;
+use crypto::{ElementHasher, Hasher, Transcript};
 use fri::FriProver;
 pub use math;
 use math::{
     fft::infer_degree,
     fields::{CubeExtension, QuadExtension},
-    ExtensibleField, FieldElement, StarkField, ToElements,
+    ExtensibleField, ExtensionOf, FieldElement, StarkField,
 };
 use maybe_async::maybe_async;
 use tracing::{event, info_span, instrument, Level};
@@ -77,23 +98,58 @@ use matrix::{ColMatrix, RowMatrix};
 mod constraints;
 pub use constraints::{
     CompositionPoly, CompositionPolyTrace, ConstraintCommitment, ConstraintEvaluator,
-    DefaultConstraintEvaluator,
+    DefaultConstraintCommitment, DefaultConstraintEvaluator,
 };
 
 mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
+#[cfg(feature = "mmap")]
+pub use trace::MmapTraceLde;
 pub use trace::{
-    AuxTraceWithMetadata, DefaultTraceLde, Trace, TraceLde, TracePolyTable, TraceTable,
-    TraceTableFragment,
+    AuxTraceWithMetadata, DefaultTraceLde, Trace, TraceBuilder, TraceLde, TracePolyTable,
+    TraceTable, TraceTableFragment,
 };
 
 mod channel;
 use channel::ProverChannel;
 
 mod errors;
-pub use errors::ProverError;
+pub use errors::{ConstraintViolation, ProverError};
+
+mod cancellation;
+use cancellation::complete_phase;
+pub use cancellation::CancellationToken;
+
+mod checkpoint;
+use checkpoint::{invalid, CheckpointState};
+pub use checkpoint::{CheckpointPhase, ProverCheckpoint};
+
+mod committed;
+pub use committed::CommittedTrace;
+use committed::{incompatible, MainSegmentCommitment};
+
+mod key;
+pub use key::ProvingKey;
+
+mod memory;
+pub use memory::MemoryEstimate;
+
+mod observer;
+use observer::PhaseTimer;
+pub use observer::ProverObserver;
+
+mod zk;
+use zk::ZkRandomness;
+
+mod lookup;
+
+mod dispatch;
+pub use dispatch::{DefaultProver, ProverFamily};
+
+#[cfg(all(feature = "numa", target_os = "linux"))]
+mod numa;
 
 #[cfg(test)]
 pub mod tests;
@@ -125,10 +181,12 @@ pub type ProverGkrProof<P> = <<P as Prover>::Air as Air>::GkrProof;
 /// return from [Prover::options] method.
 ///
 /// To further customize the prover, implementers can specify custom implementations of the
-/// [RandomCoin], [TraceLde], and [ConstraintEvaluator] associated types (default implementations
-/// of these types are provided with the prover). For example, providing custom implementations
-/// of [TraceLde] and/or [ConstraintEvaluator] can be beneficial when some steps of proof
-/// generation can be delegated to non-CPU hardware (e.g., GPUs).
+/// [RandomCoin](Prover::RandomCoin), [TraceLde], and [ConstraintEvaluator] associated types
+/// (default implementations of these types are provided with the prover). For example, providing
+/// a [Transcript] which binds protocol phase labels into its state allows using alternative
+/// Fiat-Shamir transcripts, while custom implementations of [TraceLde] and/or
+/// [ConstraintEvaluator] can be beneficial when some steps of proof generation can be delegated
+/// to non-CPU hardware (e.g., GPUs).
 #[maybe_async]
 pub trait Prover {
     /// Base field for the computation described by this prover.
@@ -143,8 +201,8 @@ pub trait Prover {
     /// Hash function to be used.
     type HashFn: ElementHasher<BaseField = Self::BaseField>;
 
-    /// PRNG to be used for generating random field elements.
-    type RandomCoin: RandomCoin<BaseField = Self::BaseField, Hasher = Self::HashFn> + Send + Sync;
+    /// Fiat-Shamir transcript (PRNG) to be used for generating random field elements.
+    type RandomCoin: Transcript<BaseField = Self::BaseField, Hasher = Self::HashFn> + Send + Sync;
 
     /// Trace low-degree extension for building the LDEs of trace segments and their commitments.
     type TraceLde<E>: TraceLde<E, HashFn = Self::HashFn> + Send + Sync
@@ -156,6 +214,12 @@ pub trait Prover {
     where
         E: FieldElement<BaseField = Self::BaseField>;
 
+    /// Constraint commitment for building the LDE of composition polynomial columns and the
+    /// commitment to the resulting evaluations.
+    type ConstraintCommitment<E>: ConstraintCommitment<E, HashFn = Self::HashFn> + Send
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+
     // REQUIRED METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -197,9 +261,66 @@ pub trait Prover {
     where
         E: FieldElement<BaseField = Self::BaseField>;
 
+    /// Evaluates columns of the provided composition polynomial over the LDE domain, and returns
+    /// a new [ConstraintCommitment] instance which holds these evaluations and a commitment to
+    /// them.
+    async fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a secret seed from which randomness used to generate zero-knowledge proofs is
+    /// derived.
+    ///
+    /// This method is invoked only when zero-knowledge is enabled in the options of this prover,
+    /// and may be invoked several times during generation of a single proof. For the proofs to
+    /// be zero-knowledge, the seed must be sampled uniformly at random for every proof and must
+    /// never be revealed.
+    ///
+    /// This seed is the only source of randomness used by the prover: blinding values, the random
+    /// composition column, and FRI layer salts are all derived from it, and the proof-of-work
+    /// nonce is always the smallest valid one. Thus, two proofs generated for the same trace from
+    /// the same seed are identical, regardless of the number of threads used to generate them.
+    /// Returning a fixed seed can be used to make proofs reproducible (e.g., for audits or for
+    /// differential testing across versions); such proofs are not zero-knowledge with respect
+    /// to anyone who knows the seed.
+    fn zk_seed(&self) -> [u8; 32] {
+        unimplemented!("`Prover::zk_seed` needs to be implemented when zero-knowledge proofs are enabled in proof options.")
+    }
+
+    /// Returns true if this prover saves checkpoints from which proof generation can be resumed.
+    ///
+    /// When this returns true, [Prover::save_checkpoint()] is invoked once all segments of the
+    /// execution trace have been committed to, and once again after the constraint evaluations
+    /// have been committed to. Building a checkpoint requires copying the execution trace, and
+    /// thus, checkpoints are not saved by default. Checkpoints are never saved for AIRs with a
+    /// Lagrange kernel column.
+    fn saves_checkpoints(&self) -> bool {
+        false
+    }
+
+    /// Saves the provided checkpoint (e.g., by writing its serialized bytes to disk).
+    ///
+    /// This method is invoked only when [Prover::saves_checkpoints()] returns true. A checkpoint
+    /// saved later during generation of a proof supersedes the checkpoints saved earlier.
+    #[allow(unused_variables)]
+    fn save_checkpoint(&self, checkpoint: &ProverCheckpoint) {
+        unimplemented!("`Prover::save_checkpoint` needs to be implemented when `Prover::saves_checkpoints` returns true.")
+    }
+
+    /// Returns an observer which is notified about progress of proof generation.
+    ///
+    /// By default, no observer is attached to the prover.
+    fn observer(&self) -> Option<&dyn ProverObserver> {
+        None
+    }
+
     /// Builds the GKR proof. If the [`Air`] doesn't use a GKR proof, leave unimplemented.
     #[allow(unused_variables)]
     async fn generate_gkr_proof<E>(
@@ -214,6 +335,10 @@ pub trait Prover {
     }
 
     /// Builds and returns the auxiliary trace.
+    ///
+    /// The returned trace must not include accumulator columns of lookup arguments defined by
+    /// the AIR; these are built by the prover automatically. If all auxiliary columns are
+    /// accumulator columns of lookup arguments, this method is not invoked.
     #[allow(unused_variables)]
     async fn build_aux_trace<E>(
         &self,
@@ -235,6 +360,457 @@ pub trait Prover {
     /// Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
     async fn prove(&self, trace: Self::Trace) -> Result<Proof, ProverError>
+    where
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        self.prove_with_cancellation(trace, None).await
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, or an error if proof generation is cancelled via the provided
+    /// `cancellation` token.
+    ///
+    /// Unlike [prove()](Prover::prove), the returned future yields control back to the executor
+    /// at the boundaries of proof generation phases (i.e., after the execution trace is
+    /// committed to, after the constraint evaluations are committed to, and after the FRI proof
+    /// is built). At each of these boundaries, the prover checks whether the `cancellation`
+    /// token has been cancelled, and if so, returns [ProverError::Cancelled]. This allows a
+    /// proving service to time out or preempt proof generation jobs without terminating the
+    /// process. Note that work within a single phase is not interrupted.
+    #[cfg(feature = "async")]
+    async fn prove_async(
+        &self,
+        trace: Self::Trace,
+        cancellation: &CancellationToken,
+    ) -> Result<Proof, ProverError>
+    where
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        self.prove_with_cancellation(trace, Some(cancellation)).await
+    }
+
+    /// Returns STARK proofs attesting to correct executions of computations defined by the
+    /// provided traces.
+    ///
+    /// The returned proofs are identical to the proofs which would be returned from
+    /// [prove()](Prover::prove) for each of the traces, and each of them can be verified
+    /// independently. However, data which depends only on the shape of the execution trace and on
+    /// proof options (i.e., the trace, constraint evaluation, and LDE domains together with their
+    /// twiddles) is built only once for all traces of the same shape. When the `concurrent`
+    /// feature is enabled, all proofs are generated using the same pool of worker threads.
+    ///
+    /// Periodic column values are not shared between the traces because they are defined by AIR
+    /// instances which may depend on public inputs. FRI instances of different proofs are not
+    /// combined into a single low-degree test either, as the verifier accepts standalone proofs
+    /// only.
+    async fn prove_batch(&self, traces: Vec<Self::Trace>) -> Result<Vec<Proof>, ProverError>
+    where
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        match self.options().field_extension() {
+            FieldExtension::None => {
+                match self.options().ood_field_extension() {
+                    FieldExtension::None => {
+                        self.generate_proof_batch::<Self::BaseField, Self::BaseField>(traces).await
+                    },
+                    FieldExtension::Quadratic => {
+                        if !<QuadExtension<Self::BaseField>>::is_supported() {
+                            return Err(ProverError::UnsupportedFieldExtension(2));
+                        }
+                        self.generate_proof_batch::<Self::BaseField, QuadExtension<Self::BaseField>>(traces)
+                        .await
+                    },
+                    FieldExtension::Cubic => {
+                        if !<CubeExtension<Self::BaseField>>::is_supported() {
+                            return Err(ProverError::UnsupportedFieldExtension(3));
+                        }
+                        self.generate_proof_batch::<Self::BaseField, CubeExtension<Self::BaseField>>(traces)
+                        .await
+                    },
+                }
+            },
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(2));
+                }
+                self.generate_proof_batch::<QuadExtension<Self::BaseField>, QuadExtension<Self::BaseField>>(traces)
+                    .await
+            },
+            FieldExtension::Cubic => {
+                if !<CubeExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(3));
+                }
+                self.generate_proof_batch::<CubeExtension<Self::BaseField>, CubeExtension<Self::BaseField>>(traces)
+                    .await
+            },
+        }
+    }
+
+    /// Resumes generation of a STARK proof from the provided checkpoint, and returns the proof.
+    ///
+    /// The checkpoint must have been saved via [Prover::save_checkpoint()] by a prover with the
+    /// same options while generating a proof for an execution trace with the specified public
+    /// inputs. For zero-knowledge proofs, [Prover::zk_seed()] must also return the same seed as
+    /// it did when the checkpoint was saved.
+    ///
+    /// Phases of proof generation completed before the checkpoint was saved are not repeated:
+    /// the low-degree extensions of the trace segments (and of the composition polynomial, if
+    /// constraint evaluations were committed to) are rebuilt from the data recorded in the
+    /// checkpoint, and the state of the Fiat-Shamir transcript is restored by replaying the
+    /// recorded commitments. Thus, the resulting proof is identical to the proof which would have
+    /// been generated had proof generation not been interrupted.
+    ///
+    /// # Errors
+    /// Returns an error if the checkpoint is malformed, was saved for different options or
+    /// public inputs, or if the commitments rebuilt from the checkpoint do not match the
+    /// commitments recorded in it.
+    async fn resume(
+        &self,
+        pub_inputs: <Self::Air as Air>::PublicInputs,
+        checkpoint: ProverCheckpoint,
+    ) -> Result<Proof, ProverError>
+    where
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        match self.options().field_extension() {
+            FieldExtension::None => match self.options().ood_field_extension() {
+                FieldExtension::None => {
+                    self.resume_proof::<Self::BaseField, Self::BaseField>(
+                        pub_inputs, checkpoint, None,
+                    )
+                    .await
+                },
+                FieldExtension::Quadratic => {
+                    if !<QuadExtension<Self::BaseField>>::is_supported() {
+                        return Err(ProverError::UnsupportedFieldExtension(2));
+                    }
+                    self.resume_proof::<Self::BaseField, QuadExtension<Self::BaseField>>(
+                        pub_inputs, checkpoint, None,
+                    )
+                    .await
+                },
+                FieldExtension::Cubic => {
+                    if !<CubeExtension<Self::BaseField>>::is_supported() {
+                        return Err(ProverError::UnsupportedFieldExtension(3));
+                    }
+                    self.resume_proof::<Self::BaseField, CubeExtension<Self::BaseField>>(
+                        pub_inputs, checkpoint, None,
+                    )
+                    .await
+                },
+            },
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(2));
+                }
+                self.resume_proof::<QuadExtension<Self::BaseField>, QuadExtension<Self::BaseField>>(
+                    pub_inputs, checkpoint, None,
+                )
+                .await
+            },
+            FieldExtension::Cubic => {
+                if !<CubeExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(3));
+                }
+                self.resume_proof::<CubeExtension<Self::BaseField>, CubeExtension<Self::BaseField>>(
+                    pub_inputs, checkpoint, None,
+                )
+                .await
+            },
+        }
+    }
+
+    /// Extends the main segment of the provided execution `trace`, commits to the extension, and
+    /// returns the trace together with the resulting commitment.
+    ///
+    /// Proofs for the returned [CommittedTrace] can be generated via
+    /// [prove_committed()](Prover::prove_committed) by provers of the same type; the options of
+    /// these provers may differ from the options of this prover in parameters which do not affect
+    /// the commitment to the main trace segment (e.g., number of queries, grinding factor, or FRI
+    /// parameters).
+    ///
+    /// # Errors
+    /// Returns an error if zero-knowledge is enabled in the options of this prover, or if the
+    /// base field does not support the field extension specified by these options.
+    async fn commit_trace(&self, trace: Self::Trace) -> Result<CommittedTrace<Self>, ProverError>
+    where
+        Self: Sized,
+    {
+        if self.options().is_zk() {
+            return Err(incompatible(
+                "zero-knowledge proofs cannot be generated from a committed trace",
+            ));
+        }
+
+        let pub_inputs = self.get_pub_inputs(&trace);
+        let air = Self::Air::new(trace.info().clone(), pub_inputs, self.options().clone());
+        let lde_domain_size = air.lde_domain_size();
+        let trace_length = air.trace_length();
+        let domain = info_span!("build_domain", trace_length, lde_domain_size)
+            .in_scope(|| StarkDomain::new(&air));
+
+        let main_trace = trace.main_segment();
+        let main_segment = match self.options().field_extension() {
+            FieldExtension::None => {
+                let (trace_lde, trace_polys) =
+                    self.new_trace_lde(air.trace_info(), main_trace, &domain).await;
+                MainSegmentCommitment::Base(trace_lde, trace_polys)
+            },
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(2));
+                }
+                let (trace_lde, trace_polys) =
+                    self.new_trace_lde(air.trace_info(), main_trace, &domain).await;
+                MainSegmentCommitment::Quadratic(trace_lde, trace_polys)
+            },
+            FieldExtension::Cubic => {
+                if !<CubeExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(3));
+                }
+                let (trace_lde, trace_polys) =
+                    self.new_trace_lde(air.trace_info(), main_trace, &domain).await;
+                MainSegmentCommitment::Cubic(trace_lde, trace_polys)
+            },
+        };
+
+        Ok(CommittedTrace {
+            trace,
+            options: self.options().clone(),
+            domain,
+            main_segment,
+        })
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// execution trace of the provided [CommittedTrace].
+    ///
+    /// The returned proof is identical to the proof which would be returned from
+    /// [prove()](Prover::prove) for the same trace. However, the low-degree extension of the main
+    /// trace segment and the commitment to it are taken from the `committed_trace` rather than
+    /// rebuilt; the auxiliary trace segment (if any) is built and committed to as usual.
+    ///
+    /// # Errors
+    /// Returns an error if the options of this prover are not compatible with the options with
+    /// which the trace was committed to (see [CommittedTrace] for details), or if proof generation
+    /// fails.
+    async fn prove_committed(
+        &self,
+        committed_trace: &mut CommittedTrace<Self>,
+    ) -> Result<Proof, ProverError>
+    where
+        Self: Sized,
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        committed_trace.validate(self.options())?;
+
+        let CommittedTrace { trace, domain, main_segment, .. } = committed_trace;
+        let result = match main_segment {
+            MainSegmentCommitment::Base(trace_lde, trace_polys) => {
+                let main_commitment = Some((trace_lde, trace_polys.clone()));
+                match self.options().ood_field_extension() {
+                    FieldExtension::None => {
+                        self.generate_proof::<Self::BaseField, Self::BaseField, _>(
+                            &*trace,
+                            Some(domain),
+                            main_commitment,
+                            None,
+                        )
+                        .await
+                    },
+                    FieldExtension::Quadratic => {
+                        if !<QuadExtension<Self::BaseField>>::is_supported() {
+                            return Err(ProverError::UnsupportedFieldExtension(2));
+                        }
+                        self.generate_proof::<Self::BaseField, QuadExtension<Self::BaseField>, _>(
+                            &*trace,
+                            Some(domain),
+                            main_commitment,
+                            None,
+                        )
+                        .await
+                    },
+                    FieldExtension::Cubic => {
+                        if !<CubeExtension<Self::BaseField>>::is_supported() {
+                            return Err(ProverError::UnsupportedFieldExtension(3));
+                        }
+                        self.generate_proof::<Self::BaseField, CubeExtension<Self::BaseField>, _>(
+                            &*trace,
+                            Some(domain),
+                            main_commitment,
+                            None,
+                        )
+                        .await
+                    },
+                }
+            },
+            MainSegmentCommitment::Quadratic(trace_lde, trace_polys) => {
+                let main_commitment = Some((trace_lde, trace_polys.clone()));
+                self.generate_proof::<QuadExtension<Self::BaseField>, QuadExtension<Self::BaseField>, _>(
+                    &*trace,
+                    Some(domain),
+                    main_commitment,
+                    None,
+                )
+                .await
+            },
+            MainSegmentCommitment::Cubic(trace_lde, trace_polys) => {
+                let main_commitment = Some((trace_lde, trace_polys.clone()));
+                self.generate_proof::<CubeExtension<Self::BaseField>, CubeExtension<Self::BaseField>, _>(
+                    &*trace,
+                    Some(domain),
+                    main_commitment,
+                    None,
+                )
+                .await
+            },
+        };
+
+        // the auxiliary trace segment depends on the randomness drawn during proof generation,
+        // and thus, cannot be reused for subsequent proofs
+        main_segment.remove_aux_trace();
+
+        result
+    }
+
+    /// Returns a [ProvingKey] for the computation described by the provided execution `trace`.
+    ///
+    /// The key contains the data needed for proof generation which depends only on the AIR of the
+    /// computation and on the options of this prover (i.e., the STARK domain with its twiddles,
+    /// periodic column values and constraint divisors evaluated over the constraint evaluation
+    /// domain). Proofs for any number of traces of the same shape can then be generated via
+    /// [prove_with_key()](Prover::prove_with_key) without rebuilding this data.
+    ///
+    /// The trace is used only to instantiate the AIR of the computation; it is not retained in
+    /// the key.
+    fn build_proving_key(&self, trace: &Self::Trace) -> ProvingKey<Self::BaseField> {
+        let pub_inputs = self.get_pub_inputs(trace);
+        let air = Self::Air::new(trace.info().clone(), pub_inputs, self.options().clone());
+        let lde_domain_size = air.lde_domain_size();
+        let trace_length = air.trace_length();
+        info_span!("build_proving_key", trace_length, lde_domain_size)
+            .in_scope(|| ProvingKey::new(&air))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace using data precomputed in the provided proving `key`.
+    ///
+    /// The returned proof is identical to the proof which would be returned from
+    /// [prove()](Prover::prove) for the same trace.
+    ///
+    /// # Errors
+    /// Returns an error if the key was built for different proof options or for an execution
+    /// trace of a different shape, or if proof generation fails.
+    async fn prove_with_key(
+        &self,
+        key: &ProvingKey<Self::BaseField>,
+        trace: Self::Trace,
+    ) -> Result<Proof, ProverError>
+    where
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        let pub_inputs = self.get_pub_inputs(&trace);
+        let air = Self::Air::new(trace.info().clone(), pub_inputs, self.options().clone());
+        key.validate(&air)?;
+        drop(air);
+
+        self.prove_with_domain(trace, Some(key.domain()), None).await
+    }
+
+    /// Checks whether the provided execution `trace` satisfies all constraints of this prover's
+    /// AIR, and returns an error describing all unsatisfied constraints if it does not.
+    ///
+    /// Unlike [prove()](Prover::prove), which produces a proof the verifier rejects when the
+    /// trace is invalid, this method evaluates every assertion and every transition constraint
+    /// over the execution trace itself (rather than over its low-degree extension). Thus, each
+    /// [ConstraintViolation] in the returned error identifies the exact constraint, the step at
+    /// which the constraint does not hold, and the value to which the constraint evaluated.
+    ///
+    /// If the trace has an auxiliary segment, the segment is built using random elements drawn
+    /// from a public coin seeded with the public inputs only; these elements are different from
+    /// the ones used during proof generation.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for debugging purposes only.
+    async fn validate_trace(&self, trace: &Self::Trace) -> Result<(), ProverError>
+    where
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        match self.options().field_extension() {
+            FieldExtension::None => self.check_trace_constraints::<Self::BaseField>(trace).await,
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(2));
+                }
+                self.check_trace_constraints::<QuadExtension<Self::BaseField>>(trace).await
+            },
+            FieldExtension::Cubic => {
+                if !<CubeExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(3));
+                }
+                self.check_trace_constraints::<CubeExtension<Self::BaseField>>(trace).await
+            },
+        }
+    }
+
+    /// Returns an estimate of the memory needed to generate a proof for an instance of the
+    /// computation described by the specified trace layout and public inputs, using the
+    /// specified proof options.
+    ///
+    /// The estimate is computed without building the execution trace, and thus, can be used to
+    /// schedule proof generation jobs of different sizes onto machines with sufficient memory.
+    /// Public inputs are needed to instantiate the AIR of the computation, which defines the
+    /// degrees of the constraints and the number of constraint divisors. The estimate accounts
+    /// for the default implementations of the prover components; see [MemoryEstimate] for
+    /// details.
+    fn estimate_memory(
+        trace_info: &TraceInfo,
+        pub_inputs: <Self::Air as Air>::PublicInputs,
+        options: &ProofOptions,
+    ) -> MemoryEstimate
+    where
+        Self: Sized,
+    {
+        let air = Self::Air::new(trace_info.clone(), pub_inputs, options.clone());
+        MemoryEstimate::new::<Self::Air, Self::HashFn>(&air)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Selects the extension field in which proof generation is performed based on the options
+    /// of this prover, and generates the proof for the provided execution `trace`.
+    ///
+    /// If `cancellation` token is provided, proof generation is aborted at the next phase
+    /// boundary after the token is cancelled.
+    #[doc(hidden)]
+    async fn prove_with_cancellation(
+        &self,
+        trace: Self::Trace,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<Proof, ProverError>
+    where
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        self.prove_with_domain(trace, None, cancellation).await
+    }
+
+    /// Selects the extension field in which proof generation is performed based on the options
+    /// of this prover, and generates the proof for the provided execution `trace` over the
+    /// provided `domain` (or over a newly built domain if `domain` is not provided).
+    #[doc(hidden)]
+    async fn prove_with_domain(
+        &self,
+        trace: Self::Trace,
+        domain: Option<&StarkDomain<Self::BaseField>>,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<Proof, ProverError>
     where
         <Self::Air as Air>::PublicInputs: Send,
         <Self::Air as Air>::GkrProof: Send,
@@ -243,120 +819,282 @@ pub trait Prover {
         // of static dispatch for selecting two generic parameter: extension field and hash
         // function.
         match self.options().field_extension() {
-            FieldExtension::None => self.generate_proof::<Self::BaseField>(trace).await,
+            FieldExtension::None => match self.options().ood_field_extension() {
+                FieldExtension::None => {
+                    self.generate_proof::<Self::BaseField, Self::BaseField, _>(
+                        trace,
+                        domain,
+                        None,
+                        cancellation,
+                    )
+                    .await
+                },
+                FieldExtension::Quadratic => {
+                    if !<QuadExtension<Self::BaseField>>::is_supported() {
+                        return Err(ProverError::UnsupportedFieldExtension(2));
+                    }
+                    self.generate_proof::<Self::BaseField, QuadExtension<Self::BaseField>, _>(
+                        trace,
+                        domain,
+                        None,
+                        cancellation,
+                    )
+                    .await
+                },
+                FieldExtension::Cubic => {
+                    if !<CubeExtension<Self::BaseField>>::is_supported() {
+                        return Err(ProverError::UnsupportedFieldExtension(3));
+                    }
+                    self.generate_proof::<Self::BaseField, CubeExtension<Self::BaseField>, _>(
+                        trace,
+                        domain,
+                        None,
+                        cancellation,
+                    )
+                    .await
+                },
+            },
             FieldExtension::Quadratic => {
                 if !<QuadExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(2));
                 }
-                self.generate_proof::<QuadExtension<Self::BaseField>>(trace).await
+                self.generate_proof::<QuadExtension<Self::BaseField>, QuadExtension<Self::BaseField>, _>(
+                    trace,
+                    domain,
+                    None,
+                    cancellation,
+                )
+                .await
             },
             FieldExtension::Cubic => {
                 if !<CubeExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(3));
                 }
-                self.generate_proof::<CubeExtension<Self::BaseField>>(trace).await
+                self.generate_proof::<CubeExtension<Self::BaseField>, CubeExtension<Self::BaseField>, _>(
+                    trace,
+                    domain,
+                    None,
+                    cancellation,
+                )
+                .await
             },
         }
     }
 
-    // HELPER METHODS
-    // --------------------------------------------------------------------------------------------
+    /// Generates proofs for all provided execution `traces` one after another, sharing the STARK
+    /// domain between traces of the same shape.
+    #[doc(hidden)]
+    async fn generate_proof_batch<E, D>(
+        &self,
+        traces: Vec<Self::Trace>,
+    ) -> Result<Vec<Proof>, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        D: FieldElement<BaseField = Self::BaseField> + ExtensionOf<E>,
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        let mut domains: Vec<StarkDomain<Self::BaseField>> = Vec::new();
+        let mut proofs = Vec::with_capacity(traces.len());
+        for trace in traces {
+            // find a domain built for an execution trace of the same shape, or build a new one
+            let domain_idx = {
+                let pub_inputs = self.get_pub_inputs(&trace);
+                let air = Self::Air::new(trace.info().clone(), pub_inputs, self.options().clone());
+                let domain_idx = domains.iter().position(|domain| {
+                    domain.trace_length() == air.trace_length()
+                        && domain.ce_domain_size() == air.ce_domain_size()
+                        && domain.lde_domain_size() == air.lde_domain_size()
+                        && domain.offset() == air.domain_offset()
+                });
+                domain_idx.unwrap_or_else(|| {
+                    domains.push(StarkDomain::new(&air));
+                    domains.len() - 1
+                })
+            };
+
+            let proof = self
+                .generate_proof::<E, D, _>(trace, Some(&domains[domain_idx]), None, None)
+                .await?;
+            proofs.push(proof);
+        }
+
+        Ok(proofs)
+    }
 
     /// Performs the actual proof generation procedure, generating the proof that the provided
     /// execution `trace` is valid against this prover's AIR.
+    ///
+    /// If `domain` is provided, it must have been built for an execution trace of the same shape
+    /// and the same proof options; otherwise, a new domain is built. Similarly, if
+    /// `main_commitment` is provided, it must contain the LDE of the main segment of the `trace`
+    /// (without an auxiliary segment) and the main trace polynomials built over this domain;
+    /// otherwise, the main trace segment is extended and committed to.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    async fn generate_proof<E>(&self, trace: Self::Trace) -> Result<Proof, ProverError>
+    async fn generate_proof<E, D, T>(
+        &self,
+        trace: T,
+        domain: Option<&StarkDomain<Self::BaseField>>,
+        main_commitment: Option<(&mut Self::TraceLde<E>, TracePolyTable<E>)>,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<Proof, ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
+        D: FieldElement<BaseField = Self::BaseField> + ExtensionOf<E>,
+        T: Borrow<Self::Trace> + Send,
         <Self::Air as Air>::PublicInputs: Send,
         <Self::Air as Air>::GkrProof: Send,
     {
         // 0 ----- instantiate AIR and prover channel ---------------------------------------------
 
         // serialize public inputs; these will be included in the seed for the public coin
-        let pub_inputs = self.get_pub_inputs(&trace);
-        let pub_inputs_elements = pub_inputs.to_elements();
+        let execution_trace: &Self::Trace = trace.borrow();
+        let pub_inputs = self.get_pub_inputs(execution_trace);
+        let pub_inputs_elements = pub_inputs.to_transcript_elements();
 
         // create an instance of AIR for the provided parameters. this takes a generic description
         // of the computation (provided via AIR type), and creates a description of a specific
         // execution of the computation for the provided public inputs.
-        let air = Self::Air::new(trace.info().clone(), pub_inputs, self.options().clone());
+        let air =
+            Self::Air::new(execution_trace.info().clone(), pub_inputs, self.options().clone());
+
+        // make sure distinct query positions can be drawn from the LDE domain, and that
+        // zero-knowledge proofs can be generated for this AIR, if they were requested
+        validate_num_queries(&air)?;
+        if air.options().is_zk() {
+            zk::validate_air(&air)?;
+        }
 
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
         let mut channel = ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new(
             &air,
-            pub_inputs_elements,
+            pub_inputs_elements.clone(),
         );
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
 
-        // build computation domain; this is used later for polynomial evaluations
+        // build computation domain (unless it has been provided); this is used later for
+        // polynomial evaluations
         let lde_domain_size = air.lde_domain_size();
         let trace_length = air.trace_length();
-        let domain = info_span!("build_domain", trace_length, lde_domain_size)
-            .in_scope(|| StarkDomain::new(&air));
+        let new_domain;
+        let domain = match domain {
+            Some(domain) => domain,
+            None => {
+                new_domain = info_span!("build_domain", trace_length, lde_domain_size)
+                    .in_scope(|| StarkDomain::new(&air));
+                &new_domain
+            },
+        };
         assert_eq!(domain.lde_domain_size(), lde_domain_size);
         assert_eq!(domain.trace_length(), trace_length);
 
-        // commit to the main trace segment
-        let (mut trace_lde, mut trace_polys) =
-            self.commit_to_main_trace_segment(&trace, &domain, &mut channel).await;
+        // commit to the main trace segment; for zero-knowledge proofs, the last rows of the trace
+        // are blinded with random values first
+        let blinded_main_trace = air.options().is_zk().then(|| {
+            let mut main_trace = execution_trace.main_segment().clone();
+            let mut rng = ZkRandomness::<Self::HashFn>::new(&self.zk_seed(), zk::MAIN_TRACE_LABEL);
+            zk::blind_rows(&mut main_trace, air.context().num_blinding_rows(), &mut rng);
+            main_trace
+        });
+        let main_trace = blinded_main_trace.as_ref().unwrap_or(execution_trace.main_segment());
+        let timer = PhaseTimer::start();
+        let mut new_trace_lde;
+        let (trace_lde, mut trace_polys) = match main_commitment {
+            Some((trace_lde, trace_polys)) => {
+                // the main trace segment has already been extended and committed to; thus, we
+                // only need to write the root of its Merkle tree into the channel
+                channel.commit_trace(trace_lde.get_main_trace_commitment());
+                (trace_lde, trace_polys)
+            },
+            None => {
+                let (trace_lde, trace_polys) =
+                    self.commit_to_main_trace_segment(&air, main_trace, domain, &mut channel).await;
+                new_trace_lde = trace_lde;
+                (&mut new_trace_lde, trace_polys)
+            },
+        };
+        let mut commitment_duration = timer.elapsed();
 
         // build the auxiliary trace segment, and append the resulting segments to trace commitment
         // and trace polynomial table structs
-        let aux_trace_with_metadata = if air.trace_info().is_multi_segment() {
-            let (gkr_proof, lagrange_rand_elements) =
-                if air.context().has_lagrange_kernel_aux_column() {
-                    let (gkr_proof, lagrange_rand_elements) =
-                        self.generate_gkr_proof(&trace, channel.public_coin()).await;
-
-                    (Some(gkr_proof), Some(lagrange_rand_elements))
-                } else {
-                    (None, None)
-                };
-
-            let aux_rand_elements = {
-                let rand_elements = air
-                    .get_aux_rand_elements(channel.public_coin())
-                    .expect("failed to draw random elements for the auxiliary trace segment");
-
-                AuxRandElements::new_with_lagrange(rand_elements, lagrange_rand_elements)
-            };
-
-            let aux_trace = self.build_aux_trace(&trace, &aux_rand_elements).await;
+        let (aux_trace_with_metadata, aux_trace_root) = if air.trace_info().is_multi_segment() {
+            let timer = PhaseTimer::start();
+            let AuxTraceWithMetadata {
+                mut aux_trace,
+                aux_rand_elements,
+                gkr_proof,
+            } = self
+                .build_aux_trace_with_metadata(&air, execution_trace, channel.public_coin())
+                .await;
+
+            // for zero-knowledge proofs, blind the last rows of the auxiliary trace segment
+            if air.options().is_zk() {
+                let num_blinding_rows = air.context().num_blinding_rows();
+                let mut aux_assertions = air.get_aux_assertions(aux_rand_elements.rand_elements());
+                aux_assertions.extend(air.get_lookup_assertions());
+                zk::validate_assertions(&aux_assertions, trace_length, num_blinding_rows)?;
+                let mut rng =
+                    ZkRandomness::<Self::HashFn>::new(&self.zk_seed(), zk::AUX_TRACE_LABEL);
+                zk::blind_rows(&mut aux_trace, num_blinding_rows, &mut rng);
+            }
+            if let Some(observer) = self.observer() {
+                observer.on_trace_built(air.trace_info(), timer.elapsed());
+            }
 
             // commit to the auxiliary trace segment
-            let aux_segment_polys = {
-                // extend the auxiliary trace segment and build a Merkle tree from the extended
-                // trace
-                let span = info_span!("commit_to_aux_trace_segment").entered();
-                let (aux_segment_polys, aux_segment_root) =
-                    trace_lde.set_aux_trace(&aux_trace, &domain);
-
-                // commit to the LDE of the extended auxiliary trace segment by writing the root of
-                // its Merkle tree into the channel
-                channel.commit_trace(aux_segment_root);
-
-                drop(span);
-                aux_segment_polys
-            };
-
-            trace_polys
-                .add_aux_segment(aux_segment_polys, air.context().lagrange_kernel_aux_column_idx());
+            let timer = PhaseTimer::start();
+            let aux_trace_root = self.commit_to_aux_trace_segment(
+                &air,
+                &aux_trace,
+                trace_lde,
+                &mut trace_polys,
+                domain,
+                &mut channel,
+            );
+            commitment_duration += timer.elapsed();
 
-            Some(AuxTraceWithMetadata { aux_trace, aux_rand_elements, gkr_proof })
+            (
+                Some(AuxTraceWithMetadata { aux_trace, aux_rand_elements, gkr_proof }),
+                Some(aux_trace_root),
+            )
         } else {
-            None
+            if let Some(observer) = self.observer() {
+                observer.on_trace_built(air.trace_info(), Duration::ZERO);
+            }
+            (None, None)
         };
+        if let Some(observer) = self.observer() {
+            observer.on_trace_committed(air.trace_info(), lde_domain_size, commitment_duration);
+        }
+
+        // save a checkpoint from which proof generation can be resumed once the trace has been
+        // committed to
+        let mut checkpoint =
+            if self.saves_checkpoints() && !air.context().has_lagrange_kernel_aux_column() {
+                let aux_trace = aux_trace_with_metadata.as_ref().map(|atm| &atm.aux_trace);
+                let checkpoint = ProverCheckpoint::new::<E, _>(
+                    air.trace_info().clone(),
+                    air.options().clone(),
+                    &pub_inputs_elements,
+                    main_trace,
+                    &trace_lde.get_main_trace_commitment(),
+                    aux_trace.zip(aux_trace_root.as_ref()),
+                );
+                self.save_checkpoint(&checkpoint);
+                Some(checkpoint)
+            } else {
+                None
+            };
 
         // make sure the specified trace (including auxiliary segment) is valid against the AIR.
         // This checks validity of both, assertions and state transitions. We do this in debug
         // mode only because this is a very expensive operation.
         #[cfg(debug_assertions)]
-        trace.validate(&air, aux_trace_with_metadata.as_ref());
+        execution_trace.validate(&air, aux_trace_with_metadata.as_ref());
 
         // Destructure `aux_trace_with_metadata`.
         let (aux_trace, aux_rand_elements, gkr_proof) = match aux_trace_with_metadata {
@@ -365,43 +1103,264 @@ pub trait Prover {
         };
 
         // drop the main trace and aux trace segment as they are no longer needed
+        drop(blinded_main_trace);
         drop(trace);
         drop(aux_trace);
+        complete_phase(cancellation).await?;
 
         // 2 ----- evaluate constraints -----------------------------------------------------------
         // evaluate constraints specified by the AIR over the constraint evaluation domain, and
         // compute random linear combinations of these evaluations using coefficients drawn from
         // the channel
         let ce_domain_size = air.ce_domain_size();
+        let timer = PhaseTimer::start();
         let composition_poly_trace = self
             .new_evaluator(&air, aux_rand_elements, channel.get_constraint_composition_coeffs())
             .await
-            .evaluate(&trace_lde, &domain);
+            .evaluate(trace_lde, domain);
         assert_eq!(composition_poly_trace.num_rows(), ce_domain_size);
+        if let Some(observer) = self.observer() {
+            observer.on_constraints_evaluated(ce_domain_size, timer.elapsed());
+        }
 
         // 3 ----- commit to constraint evaluations -----------------------------------------------
+        let timer = PhaseTimer::start();
         let (constraint_commitment, composition_poly) = self
-            .commit_to_constraint_evaluations(&air, composition_poly_trace, &domain, &mut channel)
+            .commit_to_constraint_evaluations(&air, composition_poly_trace, domain, &mut channel)
+            .await;
+        if let Some(observer) = self.observer() {
+            observer.on_constraints_committed(composition_poly.num_columns(), timer.elapsed());
+        }
+
+        // update the checkpoint once constraint evaluations have been committed to
+        if let Some(checkpoint) = checkpoint.as_mut() {
+            checkpoint.add_constraints(&composition_poly, &constraint_commitment.commitment());
+            self.save_checkpoint(checkpoint);
+        }
+        drop(checkpoint);
+        complete_phase(cancellation).await?;
+
+        // 4 - 8 ----- build the proof ------------------------------------------------------------
+        self.complete_proof::<E, D>(
+            &air,
+            channel,
+            domain,
+            trace_lde,
+            trace_polys,
+            constraint_commitment,
+            composition_poly,
+            gkr_proof,
+            cancellation,
+        )
+        .await
+    }
+
+    /// Resumes proof generation from the provided checkpoint, generating the proof that the
+    /// execution trace recorded in the checkpoint is valid against this prover's AIR.
+    #[doc(hidden)]
+    async fn resume_proof<E, D>(
+        &self,
+        pub_inputs: <Self::Air as Air>::PublicInputs,
+        checkpoint: ProverCheckpoint,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<Proof, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        D: FieldElement<BaseField = Self::BaseField> + ExtensionOf<E>,
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        // 0 ----- instantiate AIR and prover channel ---------------------------------------------
+
+        // make sure the checkpoint was saved for the same proof, and read its contents
+        let pub_inputs_elements = pub_inputs.to_transcript_elements();
+        checkpoint.validate(self.options(), &pub_inputs_elements)?;
+        let air =
+            Self::Air::new(checkpoint.trace_info().clone(), pub_inputs, self.options().clone());
+        validate_num_queries(&air)?;
+        if air.context().has_lagrange_kernel_aux_column() {
+            return Err(invalid(
+                "checkpoints are not supported for AIRs with a Lagrange kernel column",
+            ));
+        }
+        let CheckpointState {
+            main_trace,
+            main_trace_root,
+            aux_trace,
+            constraints,
+        } = checkpoint.read_state::<E, <Self::HashFn as Hasher>::Digest>()?;
+        drop(checkpoint);
+
+        let mut channel = ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new(
+            &air,
+            pub_inputs_elements,
+        );
+
+        // 1 ----- restore the commitment to the execution trace ----------------------------------
+
+        let lde_domain_size = air.lde_domain_size();
+        let trace_length = air.trace_length();
+        let domain = info_span!("build_domain", trace_length, lde_domain_size)
+            .in_scope(|| StarkDomain::new(&air));
+
+        // the main trace segment recorded in the checkpoint has already been blinded (for
+        // zero-knowledge proofs), and thus, it is committed to as is
+        let timer = PhaseTimer::start();
+        let (mut trace_lde, mut trace_polys) = self
+            .commit_to_main_trace_segment(&air, &main_trace, &domain, &mut channel)
             .await;
+        if trace_lde.get_main_trace_commitment() != main_trace_root {
+            return Err(invalid(
+                "commitment to the main trace segment does not match the checkpoint",
+            ));
+        }
+        drop(main_trace);
+
+        // draw the same random elements which were used to build the auxiliary trace segment,
+        // and commit to the segment recorded in the checkpoint
+        let aux_rand_elements = match aux_trace {
+            Some((aux_trace, aux_trace_root)) => {
+                let rand_elements = air
+                    .get_aux_rand_elements(channel.public_coin())
+                    .expect("failed to draw random elements for the auxiliary trace segment");
+                let root = self.commit_to_aux_trace_segment(
+                    &air,
+                    &aux_trace,
+                    &mut trace_lde,
+                    &mut trace_polys,
+                    &domain,
+                    &mut channel,
+                );
+                if root != aux_trace_root {
+                    return Err(invalid(
+                        "commitment to the auxiliary trace segment does not match the checkpoint",
+                    ));
+                }
+                Some(AuxRandElements::new_with_lagrange(rand_elements, None))
+            },
+            None => None,
+        };
+        if let Some(observer) = self.observer() {
+            observer.on_trace_committed(air.trace_info(), lde_domain_size, timer.elapsed());
+        }
+        complete_phase(cancellation).await?;
+
+        // 2 - 3 ----- restore or build the commitment to constraint evaluations ------------------
+        let timer = PhaseTimer::start();
+        let (constraint_commitment, composition_poly) = match constraints {
+            Some((composition_poly, constraint_root)) => {
+                if composition_poly.num_columns()
+                    != air.context().num_constraint_composition_columns()
+                {
+                    return Err(invalid(
+                        "number of composition polynomial columns does not match the AIR",
+                    ));
+                }
+
+                // composition coefficients are not needed as the composition polynomial has
+                // already been built, but they are still drawn to advance the public coin
+                channel.get_constraint_composition_coeffs();
+                let constraint_commitment =
+                    self.new_constraint_commitment(&composition_poly, &domain).await;
+                if constraint_commitment.commitment() != constraint_root {
+                    return Err(invalid(
+                        "commitment to constraint evaluations does not match the checkpoint",
+                    ));
+                }
+                channel.commit_constraints(constraint_root);
+                (constraint_commitment, composition_poly)
+            },
+            None => {
+                let composition_poly_trace = self
+                    .new_evaluator(
+                        &air,
+                        aux_rand_elements,
+                        channel.get_constraint_composition_coeffs(),
+                    )
+                    .await
+                    .evaluate(&trace_lde, &domain);
+                if let Some(observer) = self.observer() {
+                    observer.on_constraints_evaluated(air.ce_domain_size(), timer.elapsed());
+                }
+                self.commit_to_constraint_evaluations(
+                    &air,
+                    composition_poly_trace,
+                    &domain,
+                    &mut channel,
+                )
+                .await
+            },
+        };
+        if let Some(observer) = self.observer() {
+            observer.on_constraints_committed(composition_poly.num_columns(), timer.elapsed());
+        }
+        complete_phase(cancellation).await?;
+
+        // 4 - 8 ----- build the proof ------------------------------------------------------------
+        self.complete_proof::<E, D>(
+            &air,
+            channel,
+            &domain,
+            &trace_lde,
+            trace_polys,
+            constraint_commitment,
+            composition_poly,
+            None,
+            cancellation,
+        )
+        .await
+    }
+
+    /// Completes proof generation once the execution trace and constraint evaluations have been
+    /// committed to: builds the DEEP composition polynomial, computes FRI layers for it, and
+    /// queries all commitments at pseudo-random positions to build the proof.
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    async fn complete_proof<E, D>(
+        &self,
+        air: &Self::Air,
+        channel: ProverChannel<'_, Self::Air, E, Self::HashFn, Self::RandomCoin>,
+        domain: &StarkDomain<Self::BaseField>,
+        trace_lde: &Self::TraceLde<E>,
+        trace_polys: TracePolyTable<E>,
+        constraint_commitment: Self::ConstraintCommitment<E>,
+        composition_poly: CompositionPoly<E>,
+        gkr_proof: Option<ProverGkrProof<Self>>,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<Proof, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        D: FieldElement<BaseField = Self::BaseField> + ExtensionOf<E>,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        let lde_domain_size = air.lde_domain_size();
+        let trace_length = air.trace_length();
+
+        // from this point on, random values are drawn from the field D, which is either the same
+        // as E or an extension of the base field when E is the base field itself
+        let mut channel = channel.into_field::<D>();
 
         // 4 ----- build DEEP composition polynomial ----------------------------------------------
         let deep_composition_poly = {
             let span = info_span!("build_deep_composition_poly").entered();
-            // draw an out-of-domain point z. Depending on the type of E, the point is drawn either
-            // from the base field or from an extension field defined by E.
+            // draw an out-of-domain point z. Depending on the type of D, the point is drawn either
+            // from the base field or from an extension field defined by D.
             //
             // The purpose of sampling from the extension field here (instead of the base field) is
             // to increase security. Soundness is limited by the size of the field that the random
             // point is drawn from, and we can potentially save on performance by only drawing this
             // point from an extension field, rather than increasing the size of the field overall.
+            // For the same reason, D may be an extension of the field in which constraints are
+            // evaluated (see ProofOptions::with_ood_field_extension()).
             let z = channel.get_ood_point();
 
             // evaluate trace and constraint polynomials at the OOD point z, and send the results to
-            // the verifier. the trace polynomials are actually evaluated over two points: z and z *
-            // g, where g is the generator of the trace domain. Additionally, if the Lagrange kernel
-            // auxiliary column is present, we also evaluate that column over the points: z, z * g,
-            // z * g^2, z * g^4, ..., z * g^(2^(v-1)), where v = log(trace_len).
-            let ood_trace_states = trace_polys.get_ood_frame(z);
+            // the verifier. the trace polynomials are actually evaluated over points z * g^k for
+            // each row offset k of the evaluation frame (by default, z and z * g), where g is the
+            // generator of the trace domain. Additionally, if the Lagrange kernel auxiliary column
+            // is present, we also evaluate that column over the points: z, z * g, z * g^2,
+            // z * g^4, ..., z * g^(2^(v-1)), where v = log(trace_len).
+            let ood_trace_states = trace_polys.get_ood_frame(z, air.context().frame_offsets());
             channel.send_ood_trace_states(&ood_trace_states);
 
             let ood_evaluations = composition_poly.evaluate_at(z);
@@ -433,7 +1392,7 @@ pub trait Prover {
         // 5 ----- evaluate DEEP composition polynomial over LDE domain ---------------------------
         let deep_evaluations = {
             let span = info_span!("evaluate_deep_composition_poly").entered();
-            let deep_evaluations = deep_composition_poly.evaluate(&domain);
+            let deep_evaluations = deep_composition_poly.evaluate(domain);
             // we check the following condition in debug mode only because infer_degree is an
             // expensive operation
             debug_assert_eq!(trace_length - 2, infer_degree(&deep_evaluations, domain.offset()));
@@ -442,37 +1401,61 @@ pub trait Prover {
             deep_evaluations
         };
 
-        // 6 ----- compute FRI layers for the composition polynomial ------------------------------
-        let fri_options = air.options().to_fri_options();
-        let num_layers = fri_options.num_fri_layers(lde_domain_size);
-        let mut fri_prover = FriProver::new(fri_options);
-        info_span!("compute_fri_layers", num_layers)
-            .in_scope(|| fri_prover.build_layers(&mut channel, deep_evaluations));
+        // the FRI prover is not `Send` unless the hash function is, and thus, it must go out of
+        // scope before the phase boundary (i.e., before the await point) is reached
+        let (query_positions, fri_proof, timer) = {
+            // 6 ----- compute FRI layers for the composition polynomial --------------------------
+            let fri_options = air.options().to_fri_options();
+            let num_layers = fri_options.num_fri_layers(lde_domain_size);
+            let mut fri_prover = FriProver::new(fri_options);
+            if air.options().is_zk() {
+                let salt_seed = zk::get_fri_salt_seed::<Self::HashFn>(&self.zk_seed());
+                fri_prover = fri_prover.with_salt_seed(salt_seed);
+            }
+            info_span!("compute_fri_layers", num_layers).in_scope(|| {
+                let mut evaluations = deep_evaluations;
+                for _ in 0..num_layers {
+                    let timer = PhaseTimer::start();
+                    let layer = fri_prover.commit_layer(&mut channel, &mut evaluations);
+                    if let Some(observer) = self.observer() {
+                        observer.on_fri_layer_committed(
+                            layer.index(),
+                            layer.domain_size(),
+                            timer.elapsed(),
+                        );
+                    }
+                }
+                fri_prover.finalize(&mut channel, evaluations);
+            });
 
-        // 7 ----- determine query positions ------------------------------------------------------
-        let query_positions = {
-            let grinding_factor = air.options().grinding_factor();
-            let num_positions = air.options().num_queries();
-            let span =
-                info_span!("determine_query_positions", grinding_factor, num_positions,).entered();
+            // 7 ----- determine query positions --------------------------------------------------
+            let timer = PhaseTimer::start();
+            let query_positions = {
+                let grinding_factor = air.options().grinding_factor();
+                let num_positions = air.options().num_queries();
+                let span = info_span!("determine_query_positions", grinding_factor, num_positions,)
+                    .entered();
 
-            // apply proof-of-work to the query seed
-            channel.grind_query_seed();
+                // apply proof-of-work to the query seed
+                channel.grind_query_seed();
 
-            // generate pseudo-random query positions
-            let query_positions = channel.get_query_positions();
-            event!(Level::DEBUG, "query_positions_len: {}", query_positions.len());
+                // generate pseudo-random query positions
+                let query_positions = channel.get_query_positions()?;
+                event!(Level::DEBUG, "query_positions_len: {}", query_positions.len());
 
-            drop(span);
-            query_positions
+                drop(span);
+                query_positions
+            };
+
+            // generate FRI proof
+            let fri_proof = fri_prover.build_proof(&query_positions);
+            (query_positions, fri_proof, timer)
         };
+        complete_phase(cancellation).await?;
 
         // 8 ----- build proof object -------------------------------------------------------------
         let proof = {
             let span = info_span!("build_proof_object").entered();
-            // generate FRI proof
-            let fri_proof = fri_prover.build_proof(&query_positions);
-
             // query the execution trace at the selected position; for each query, we need the
             // state of the trace at that position + Merkle authentication path
             let trace_queries = trace_lde.query(&query_positions);
@@ -495,6 +1478,10 @@ pub trait Prover {
             drop(span);
             proof
         };
+        if let Some(observer) = self.observer() {
+            let proof_size = proof.to_bytes().len();
+            observer.on_queries_built(query_positions.len(), proof_size, timer.elapsed());
+        }
 
         Ok(proof)
     }
@@ -505,16 +1492,17 @@ pub trait Prover {
     /// The extension is done by first interpolating the evaluations of the polynomial so that we
     /// get the composition polynomial in coefficient form; then breaking the polynomial into
     /// columns each of size equal to trace length, and finally evaluating each composition
-    /// polynomial column over the LDE domain.
+    /// polynomial column over the LDE domain and committing to the evaluations via
+    /// [Prover::new_constraint_commitment()].
     ///
-    /// The commitment is computed by hashing each row in the evaluation matrix, and then building
-    /// a Merkle tree from the resulting hashes.
+    /// For zero-knowledge proofs, the last of `num_constraint_composition_columns` columns is a
+    /// random polynomial rather than a segment of the composition polynomial.
     async fn build_constraint_commitment<E>(
         &self,
         composition_poly_trace: CompositionPolyTrace<E>,
         num_constraint_composition_columns: usize,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> (ConstraintCommitment<E, Self::HashFn>, CompositionPoly<E>)
+    ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>)
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
@@ -522,43 +1510,125 @@ pub trait Prover {
         // - interpolate the trace into a polynomial in coefficient form
         // - "break" the polynomial into a set of column polynomials each of degree equal to
         //   trace_length - 1
+        // - for zero-knowledge proofs, append a random column polynomial of the same degree
+        let num_randomizer_columns = usize::from(self.options().is_zk());
         let composition_poly = info_span!(
             "build_composition_poly_columns",
             num_columns = num_constraint_composition_columns
         )
         .in_scope(|| {
-            CompositionPoly::new(composition_poly_trace, domain, num_constraint_composition_columns)
+            let mut composition_poly = CompositionPoly::new(
+                composition_poly_trace,
+                domain,
+                num_constraint_composition_columns - num_randomizer_columns,
+            );
+            if num_randomizer_columns > 0 {
+                let mut rng =
+                    ZkRandomness::<Self::HashFn>::new(&self.zk_seed(), zk::COMPOSITION_LABEL);
+                composition_poly.add_randomizer_column(rng.draw_many(domain.trace_length()));
+            }
+            composition_poly
         });
         assert_eq!(composition_poly.num_columns(), num_constraint_composition_columns);
         assert_eq!(composition_poly.column_degree(), domain.trace_length() - 1);
 
-        // then, evaluate composition polynomial columns over the LDE domain
-        let domain_size = domain.lde_domain_size();
-        let composed_evaluations = info_span!("evaluate_composition_poly_columns").in_scope(|| {
-            RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(composition_poly.data(), domain)
-        });
-        assert_eq!(composed_evaluations.num_cols(), num_constraint_composition_columns);
-        assert_eq!(composed_evaluations.num_rows(), domain_size);
-
-        // finally, build constraint evaluation commitment
-        let constraint_commitment = info_span!(
-            "compute_constraint_evaluation_commitment",
-            tree_depth = domain_size.ilog2()
-        )
-        .in_scope(|| {
-            let commitment = composed_evaluations.commit_to_rows();
-            ConstraintCommitment::new(composed_evaluations, commitment)
-        });
-        assert_eq!(constraint_commitment.tree_depth(), domain_size.ilog2() as usize);
+        // then, evaluate composition polynomial columns over the LDE domain and commit to the
+        // resulting evaluations
+        let constraint_commitment = self.new_constraint_commitment(&composition_poly, domain).await;
 
         (constraint_commitment, composition_poly)
     }
 
+    /// Performs the actual trace validation procedure, checking the provided `trace` against all
+    /// constraints of this prover's AIR using field `E` for the auxiliary trace segment.
+    #[doc(hidden)]
+    async fn check_trace_constraints<E>(&self, trace: &Self::Trace) -> Result<(), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        let pub_inputs = self.get_pub_inputs(trace);
+        let pub_inputs_elements = pub_inputs.to_transcript_elements();
+        let air = Self::Air::new(trace.info().clone(), pub_inputs, self.options().clone());
+
+        let aux_trace_with_metadata = if air.trace_info().is_multi_segment() {
+            let mut channel = ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new(
+                &air,
+                pub_inputs_elements,
+            );
+            Some(
+                self.build_aux_trace_with_metadata::<E>(&air, trace, channel.public_coin())
+                    .await,
+            )
+        } else {
+            None
+        };
+
+        let violations = trace.find_constraint_violations(&air, aux_trace_with_metadata.as_ref());
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ProverError::UnsatisfiedConstraints(violations))
+        }
+    }
+
+    /// Builds the auxiliary trace segment (including accumulator columns of lookup arguments)
+    /// using random elements drawn from the provided public coin, together with the GKR proof if
+    /// the AIR has a Lagrange kernel column.
     #[doc(hidden)]
     #[instrument(skip_all)]
-    async fn commit_to_main_trace_segment<E>(
+    async fn build_aux_trace_with_metadata<E>(
         &self,
+        air: &Self::Air,
         trace: &Self::Trace,
+        public_coin: &mut Self::RandomCoin,
+    ) -> AuxTraceWithMetadata<E, ProverGkrProof<Self>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        let (gkr_proof, lagrange_rand_elements) = if air.context().has_lagrange_kernel_aux_column()
+        {
+            let (gkr_proof, lagrange_rand_elements) =
+                self.generate_gkr_proof(trace, public_coin).await;
+
+            (Some(gkr_proof), Some(lagrange_rand_elements))
+        } else {
+            (None, None)
+        };
+
+        let aux_rand_elements = {
+            let rand_elements = air
+                .get_aux_rand_elements(public_coin)
+                .expect("failed to draw random elements for the auxiliary trace segment");
+
+            AuxRandElements::new_with_lagrange(rand_elements, lagrange_rand_elements)
+        };
+
+        // build the auxiliary columns defined by the prover (if any), and complement them with
+        // accumulator columns of lookup arguments
+        let custom_aux_trace = if air.trace_info().aux_segment_width() > air.context().num_lookups()
+        {
+            Some(self.build_aux_trace(trace, &aux_rand_elements).await)
+        } else {
+            None
+        };
+        let aux_trace = lookup::insert_lookup_columns(
+            air,
+            trace.main_segment(),
+            custom_aux_trace,
+            aux_rand_elements.rand_elements(),
+        );
+
+        AuxTraceWithMetadata { aux_trace, aux_rand_elements, gkr_proof }
+    }
+
+    #[doc(hidden)]
+    #[instrument(skip_all)]
+    async fn commit_to_main_trace_segment<E>(
+        &self,
+        air: &Self::Air,
+        main_trace: &ColMatrix<Self::BaseField>,
         domain: &StarkDomain<Self::BaseField>,
         channel: &mut ProverChannel<Self::Air, E, Self::HashFn, Self::RandomCoin>,
     ) -> (Self::TraceLde<E>, TracePolyTable<E>)
@@ -567,7 +1637,7 @@ pub trait Prover {
     {
         // extend the main execution trace and build a Merkle tree from the extended trace
         let (trace_lde, trace_polys) =
-            self.new_trace_lde(trace.info(), trace.main_segment(), domain).await;
+            self.new_trace_lde(air.trace_info(), main_trace, domain).await;
 
         // get the commitment to the main trace segment LDE
         let main_trace_root = trace_lde.get_main_trace_commitment();
@@ -579,6 +1649,33 @@ pub trait Prover {
         (trace_lde, trace_polys)
     }
 
+    #[doc(hidden)]
+    #[instrument(skip_all)]
+    fn commit_to_aux_trace_segment<E>(
+        &self,
+        air: &Self::Air,
+        aux_trace: &ColMatrix<E>,
+        trace_lde: &mut Self::TraceLde<E>,
+        trace_polys: &mut TracePolyTable<E>,
+        domain: &StarkDomain<Self::BaseField>,
+        channel: &mut ProverChannel<Self::Air, E, Self::HashFn, Self::RandomCoin>,
+    ) -> <Self::HashFn as Hasher>::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // extend the auxiliary trace segment and build a Merkle tree from the extended trace
+        let (aux_segment_polys, aux_segment_root) = trace_lde.set_aux_trace(aux_trace, domain);
+
+        // commit to the LDE of the extended auxiliary trace segment by writing the root of its
+        // Merkle tree into the channel
+        channel.commit_trace(aux_segment_root);
+
+        trace_polys
+            .add_aux_segment(aux_segment_polys, air.context().lagrange_kernel_aux_column_idx());
+
+        aux_segment_root
+    }
+
     #[doc(hidden)]
     #[instrument(skip_all)]
     async fn commit_to_constraint_evaluations<E>(
@@ -587,7 +1684,7 @@ pub trait Prover {
         composition_poly_trace: CompositionPolyTrace<E>,
         domain: &StarkDomain<Self::BaseField>,
         channel: &mut ProverChannel<Self::Air, E, Self::HashFn, Self::RandomCoin>,
-    ) -> (ConstraintCommitment<E, Self::HashFn>, CompositionPoly<E>)
+    ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>)
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
@@ -603,8 +1700,22 @@ pub trait Prover {
 
         // then, commit to the evaluations of constraints by writing the root of the constraint
         // Merkle tree into the channel
-        channel.commit_constraints(constraint_commitment.root());
+        channel.commit_constraints(constraint_commitment.commitment());
 
         (constraint_commitment, composition_poly)
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure the number of queries specified by the proof options of the provided AIR is smaller
+/// than the size of the LDE domain; otherwise, the required number of distinct query positions
+/// cannot be drawn from the domain.
+fn validate_num_queries<A: Air>(air: &A) -> Result<(), ProverError> {
+    let num_queries = air.options().num_queries();
+    if num_queries >= air.lde_domain_size() {
+        return Err(ProverError::TooManyQueries(num_queries, air.lde_domain_size()));
+    }
+    Ok(())
+}
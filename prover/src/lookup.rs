@@ -0,0 +1,95 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use air::{Air, LookupArgument};
+use math::{batch_inversion, FieldElement};
+
+use crate::matrix::ColMatrix;
+
+// LOOKUP ACCUMULATORS
+// ================================================================================================
+
+/// Builds LogUp accumulator columns for all lookup arguments of the specified AIR and inserts
+/// them into the auxiliary trace segment.
+///
+/// `aux_trace` holds the auxiliary columns built by the prover (if any); the accumulator columns
+/// are inserted right after these columns, but before the Lagrange kernel column (if any).
+///
+/// # Panics
+/// Panics if the number of columns in the provided auxiliary trace is not equal to the width of
+/// the auxiliary trace segment less the number of lookup arguments.
+pub fn insert_lookup_columns<A, E>(
+    air: &A,
+    main_trace: &ColMatrix<A::BaseField>,
+    aux_trace: Option<ColMatrix<E>>,
+    aux_rand_elements: &[E],
+) -> ColMatrix<E>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+{
+    let context = air.context();
+    let mut columns = aux_trace.map(|trace| trace.into_columns()).unwrap_or_default();
+    assert_eq!(
+        columns.len(),
+        air.trace_info().aux_segment_width() - context.num_lookups(),
+        "expected {} auxiliary columns, but received {}",
+        air.trace_info().aux_segment_width() - context.num_lookups(),
+        columns.len()
+    );
+    if context.num_lookups() == 0 {
+        return ColMatrix::new(columns);
+    }
+
+    let alpha = aux_rand_elements[aux_rand_elements.len() - 2];
+    let beta = aux_rand_elements[aux_rand_elements.len() - 1];
+    let lookup_columns = context
+        .lookups()
+        .iter()
+        .map(|lookup| build_accumulator(lookup, main_trace, alpha, beta));
+
+    let offset = context.lookup_aux_column_offset();
+    columns.splice(offset..offset, lookup_columns);
+    ColMatrix::new(columns)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a column which accumulates the LogUp sum of the specified lookup argument over all rows
+/// of the main trace; the first value in the column is zero.
+fn build_accumulator<E: FieldElement>(
+    lookup: &LookupArgument,
+    main_trace: &ColMatrix<E::BaseField>,
+    alpha: E,
+    beta: E,
+) -> Vec<E> {
+    let num_rows = main_trace.num_rows();
+    let num_terms = lookup.witness_columns().len() + 1;
+
+    // compute denominators of all terms in all rows except for the last one, and invert them all
+    // at once
+    let mut row = vec![E::BaseField::ZERO; main_trace.num_cols()];
+    let mut denominators = Vec::with_capacity((num_rows - 1) * num_terms);
+    for step in 0..num_rows - 1 {
+        main_trace.read_row_into(step, &mut row);
+        denominators.extend(lookup.get_denominators(&row, alpha, beta));
+    }
+    let inv_denominators = batch_inversion(&denominators);
+
+    let mut result = Vec::with_capacity(num_rows);
+    result.push(E::ZERO);
+    for (step, inv_denominators) in inv_denominators.chunks(num_terms).enumerate() {
+        let multiplicity = E::from(main_trace.get(lookup.multiplicity_column(), step));
+        let delta = inv_denominators[1..]
+            .iter()
+            .fold(multiplicity * inv_denominators[0], |acc, &inv| acc - inv);
+        result.push(result[step] + delta);
+    }
+
+    result
+}
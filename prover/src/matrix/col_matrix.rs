@@ -7,7 +7,10 @@ use alloc::vec::Vec;
 use core::{iter::FusedIterator, slice};
 
 use crypto::{ElementHasher, MerkleTree};
-use math::{fft, polynom, FieldElement};
+use math::{
+    fft::{self, FftDomain},
+    polynom, FieldElement,
+};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 use utils::{batch_iter_mut, iter, iter_mut, uninit_vector};
@@ -167,15 +170,23 @@ impl<E: FieldElement> ColMatrix<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns an iterator over the columns of this matrix.
-    pub fn columns(&self) -> ColumnIter<E> {
+    pub fn columns(&self) -> ColumnIter<'_, E> {
         ColumnIter::new(self)
     }
 
     /// Returns a mutable iterator over the columns of this matrix.
-    pub fn columns_mut(&mut self) -> ColumnIterMut<E> {
+    pub fn columns_mut(&mut self) -> ColumnIterMut<'_, E> {
         ColumnIterMut::new(self)
     }
 
+    /// Returns an iterator over the rows of this matrix.
+    ///
+    /// Since the matrix is stored in column-major order, each row is assembled by reading one
+    /// value from every column; the matrix itself is never transposed.
+    pub fn rows(&self) -> RowIter<'_, E> {
+        RowIter::new(self)
+    }
+
     // POLYNOMIAL METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -191,14 +202,25 @@ impl<E: FieldElement> ColMatrix<E> {
     ///   coefficients of a degree `num_rows - 1` polynomial.
     pub fn interpolate_columns(&self) -> Self {
         let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
-        let columns = iter!(self.columns)
-            .map(|evaluations| {
-                let mut column = evaluations.clone();
-                fft::interpolate_poly(&mut column, &inv_twiddles);
-                column
-            })
-            .collect();
-        Self { columns }
+        self.interpolate_columns_with(&inv_twiddles)
+    }
+
+    /// Interpolates columns of the matrix into polynomials in coefficient form using inverse
+    /// twiddles cached in the specified [StarkDomain], and returns the result.
+    ///
+    /// This is equivalent to [ColMatrix::interpolate_columns()] but avoids re-building inverse
+    /// twiddles for the trace domain.
+    ///
+    /// # Panics
+    /// Panics if the number of rows in this matrix is not equal to the trace length of the
+    /// specified domain.
+    pub fn interpolate_columns_over(&self, domain: &StarkDomain<E::BaseField>) -> Self {
+        assert_eq!(
+            self.num_rows(),
+            domain.trace_length(),
+            "number of rows must be equal to the trace length of the domain"
+        );
+        self.interpolate_columns_with(domain.trace_inv_twiddles())
     }
 
     /// Interpolates columns of the matrix into polynomials in coefficient form and returns the
@@ -241,6 +263,30 @@ impl<E: FieldElement> ColMatrix<E> {
         Self { columns }
     }
 
+    /// Extends columns of this matrix from the `source` domain onto the `target` domain and
+    /// returns the result.
+    ///
+    /// Each column of the matrix is interpreted as evaluations of a polynomial over the `source`
+    /// domain. The polynomial is interpolated over the `source` domain and then evaluated over
+    /// the `target` domain. Unlike [ColMatrix::evaluate_columns_over()], this works with any
+    /// domain which implements [FftDomain] (e.g., circle domains over the Mersenne-31 field).
+    ///
+    /// # Panics
+    /// Panics if the number of rows in this matrix is not equal to the size of the `source`
+    /// domain, or if the `target` domain is smaller than the `source` domain.
+    pub fn extend_columns<D>(&self, source: &D, target: &D) -> Self
+    where
+        D: FftDomain<E::BaseField> + Sync,
+    {
+        assert_eq!(
+            self.num_rows(),
+            source.size(),
+            "number of rows must be equal to the size of the source domain"
+        );
+        let columns = iter!(self.columns).map(|column| source.extend(column, target)).collect();
+        Self { columns }
+    }
+
     /// Evaluates polynomials contained in the columns of this matrix at a single point `x`.
     pub fn evaluate_columns_at<F>(&self, x: F) -> Vec<F>
     where
@@ -295,6 +341,21 @@ impl<E: FieldElement> ColMatrix<E> {
     pub fn into_columns(self) -> Vec<Vec<E>> {
         self.columns
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Interpolates columns of the matrix into polynomials using the provided inverse twiddles.
+    fn interpolate_columns_with(&self, inv_twiddles: &[E::BaseField]) -> Self {
+        let columns = iter!(self.columns)
+            .map(|evaluations| {
+                let mut column = evaluations.clone();
+                fft::interpolate_poly(&mut column, inv_twiddles);
+                column
+            })
+            .collect();
+        Self { columns }
+    }
 }
 
 // COLUMN ITERATOR
@@ -348,6 +409,47 @@ impl<'a, E: FieldElement> Default for ColumnIter<'a, E> {
     }
 }
 
+// ROW ITERATOR
+// ================================================================================================
+
+/// Iterator over rows of [ColMatrix].
+///
+/// Each row is returned as a vector containing values of all columns at that row.
+pub struct RowIter<'a, E: FieldElement> {
+    matrix: &'a ColMatrix<E>,
+    cursor: usize,
+}
+
+impl<'a, E: FieldElement> RowIter<'a, E> {
+    pub fn new(matrix: &'a ColMatrix<E>) -> Self {
+        Self { matrix, cursor: 0 }
+    }
+}
+
+impl<'a, E: FieldElement> Iterator for RowIter<'a, E> {
+    type Item = Vec<E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.matrix.num_rows() - self.cursor {
+            0 => None,
+            _ => {
+                let row = self.matrix.columns.iter().map(|column| column[self.cursor]).collect();
+                self.cursor += 1;
+                Some(row)
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.matrix.num_rows() - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, E: FieldElement> ExactSizeIterator for RowIter<'a, E> {}
+
+impl<'a, E: FieldElement> FusedIterator for RowIter<'a, E> {}
+
 // MUTABLE COLUMN ITERATOR
 // ================================================================================================
 
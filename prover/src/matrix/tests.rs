@@ -8,7 +8,12 @@ use alloc::vec::Vec;
 use rand_utils::rand_vector;
 
 use crate::{
-    math::{fields::f64::BaseElement, get_power_series, polynom, StarkField},
+    math::{
+        circle::{self, CircleDomain},
+        fft::{EvaluationDomain, FftDomain},
+        fields::{f64::BaseElement, m31},
+        get_power_series, polynom, FieldElement, StarkField,
+    },
     ColMatrix, RowMatrix,
 };
 
@@ -41,6 +46,51 @@ fn test_eval_poly_with_offset_matrix() {
     }
 }
 
+#[test]
+fn test_extend_columns() {
+    let n = 64;
+    let num_polys = 16;
+    let blowup_factor = 4;
+
+    // extend evaluations over a subgroup onto a coset of a larger subgroup
+    let columns: Vec<Vec<BaseElement>> = (0..num_polys).map(|_| rand_vector(n)).collect();
+    let source = EvaluationDomain::new(n, BaseElement::ONE);
+    let target = EvaluationDomain::new(n * blowup_factor, BaseElement::GENERATOR);
+    let extended = ColMatrix::new(columns.clone()).extend_columns(&source, &target);
+
+    // the result must be the same as evaluating interpolated polynomials over the coset
+    let polys = ColMatrix::new(columns).interpolate_columns();
+    let row_matrix = RowMatrix::evaluate_polys::<8>(&polys, blowup_factor);
+    for row in 0..n * blowup_factor {
+        let mut extended_row = vec![BaseElement::ZERO; num_polys];
+        extended.read_row_into(row, &mut extended_row);
+        assert_eq!(row_matrix.row(row), extended_row);
+    }
+}
+
+#[test]
+fn test_extend_columns_over_circle() {
+    let log_n = 5;
+    let num_polys = 8;
+
+    let columns: Vec<Vec<m31::BaseElement>> =
+        (0..num_polys).map(|_| rand_vector(1 << log_n)).collect();
+    let source = CircleDomain::new(log_n);
+    let target = CircleDomain::new(log_n + 3);
+    let extended = ColMatrix::new(columns.clone()).extend_columns(&source, &target);
+    assert_eq!(target.size(), extended.num_rows());
+
+    // extended columns must be evaluations of the interpolated polynomials over the target domain
+    for (i, column) in columns.into_iter().enumerate() {
+        let mut coefficients = column;
+        source.interpolate(&mut coefficients);
+        for (j, point) in target.points().iter().enumerate() {
+            let expected = circle::eval_poly(&coefficients, point.x(), point.y());
+            assert_eq!(expected, extended.get(i, j));
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
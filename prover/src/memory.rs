@@ -0,0 +1,202 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::collections::BTreeSet;
+use core::{cmp, fmt, mem};
+
+use air::Air;
+use crypto::Hasher;
+
+// MEMORY ESTIMATE
+// ================================================================================================
+
+/// Estimate of the memory needed to generate a STARK proof for a computation.
+///
+/// The estimate is computed via [Prover::estimate_memory()](crate::Prover::estimate_memory) from
+/// the trace layout and the proof options, and thus, is available before the execution trace is
+/// built. All values are measured in bytes and account for the data structures which dominate
+/// memory usage of the default prover components:
+/// - Trace LDE: the execution trace polynomials, the low-degree extensions of all trace segments,
+///   and the Merkle trees committing to these extensions.
+/// - Constraint evaluations: the table of constraint evaluations over the constraint evaluation
+///   domain.
+/// - Composition polynomial: the coefficients of the constraint composition polynomial columns,
+///   their low-degree extension, and the Merkle tree committing to it.
+/// - FRI layers: the evaluations of the DEEP composition polynomial and of all subsequent FRI
+///   layers, together with the Merkle trees committing to them.
+///
+/// Memory used by the execution trace itself, by the evaluations of periodic columns, and by
+/// temporary buffers is not included. Auxiliary trace assertions are also not accounted for when
+/// estimating the size of the constraint evaluation table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    trace_lde: usize,
+    constraint_evaluations: usize,
+    composition_poly: usize,
+    fri_layers: usize,
+}
+
+impl MemoryEstimate {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an estimate of the memory needed to generate a proof for the specified AIR using
+    /// hash function `H`.
+    pub(crate) fn new<A: Air, H: Hasher>(air: &A) -> Self {
+        let context = air.context();
+        let trace_info = context.trace_info();
+        let options = air.options();
+
+        let base_size = mem::size_of::<A::BaseField>();
+        let ext_size = base_size * options.field_extension().degree() as usize;
+        let digest_size = mem::size_of::<H::Digest>();
+
+        let trace_len = context.trace_len();
+        let lde_domain_size = context.lde_domain_size();
+
+        // a Merkle tree over n leaves consists of n leaf digests and n - 1 internal nodes
+        let merkle_tree_size = |num_leaves: usize| 2 * num_leaves * digest_size;
+
+        // trace polynomials, their LDEs, and commitments to the LDEs of all trace segments
+        let main_width = trace_info.main_trace_width();
+        let aux_width = trace_info.aux_segment_width();
+        let mut trace_lde = (trace_len + lde_domain_size) * main_width * base_size
+            + merkle_tree_size(lde_domain_size);
+        if aux_width > 0 {
+            trace_lde += (trace_len + lde_domain_size) * aux_width * ext_size
+                + merkle_tree_size(lde_domain_size);
+        }
+
+        // the constraint evaluation table contains a column for each distinct constraint divisor:
+        // the default transition divisor, custom transition divisors, and boundary divisors
+        let boundary_divisors = air
+            .get_assertions()
+            .iter()
+            .map(|assertion| (assertion.first_step(), assertion.stride()))
+            .collect::<BTreeSet<_>>();
+        let num_divisors = 1 + context.custom_transition_divisors().len() + boundary_divisors.len();
+        let constraint_evaluations = context.ce_domain_size() * num_divisors * ext_size;
+
+        // composition polynomial columns, their LDE, and the commitment to the LDE
+        let num_composition_columns = context.num_constraint_composition_columns();
+        let composition_poly = (trace_len + lde_domain_size) * num_composition_columns * ext_size
+            + merkle_tree_size(lde_domain_size);
+
+        // evaluations of all FRI layers, and commitments to them; a layer folded by a factor of
+        // N is committed to via a Merkle tree with a leaf for every N evaluations
+        let fri_options = options.to_fri_options();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let mut fri_layers = 0;
+        let mut layer_size = lde_domain_size;
+        for layer_idx in 0..num_fri_layers {
+            let folding_factor = fri_options.folding_factor_at(layer_idx);
+            fri_layers += layer_size * ext_size + merkle_tree_size(layer_size / folding_factor);
+            layer_size /= folding_factor;
+        }
+        fri_layers += layer_size * ext_size;
+
+        Self {
+            trace_lde,
+            constraint_evaluations,
+            composition_poly,
+            fri_layers,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of bytes needed for the trace polynomials, the LDEs of all trace
+    /// segments, and the commitments to these LDEs.
+    pub fn trace_lde(&self) -> usize {
+        self.trace_lde
+    }
+
+    /// Returns the number of bytes needed for the table of constraint evaluations.
+    pub fn constraint_evaluations(&self) -> usize {
+        self.constraint_evaluations
+    }
+
+    /// Returns the number of bytes needed for the constraint composition polynomial, its LDE,
+    /// and the commitment to the LDE.
+    pub fn composition_poly(&self) -> usize {
+        self.composition_poly
+    }
+
+    /// Returns the number of bytes needed for the evaluations of all FRI layers and the
+    /// commitments to them.
+    pub fn fri_layers(&self) -> usize {
+        self.fri_layers
+    }
+
+    /// Returns the expected peak number of bytes used during proof generation.
+    ///
+    /// The trace LDE is kept in memory for the entire duration of proof generation. The table of
+    /// constraint evaluations is released once the composition polynomial is built from it, and
+    /// thus, the peak is reached either while constraints are evaluated, or while FRI layers are
+    /// built.
+    pub fn peak(&self) -> usize {
+        self.trace_lde
+            + cmp::max(
+                self.constraint_evaluations + self.composition_poly,
+                self.composition_poly + self.fri_layers,
+            )
+    }
+}
+
+impl fmt::Display for MemoryEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const MIB: f64 = (1 << 20) as f64;
+        write!(
+            f,
+            "peak: {:.1} MiB (trace LDE: {:.1} MiB, constraint evaluations: {:.1} MiB, \
+            composition polynomial: {:.1} MiB, FRI layers: {:.1} MiB)",
+            self.peak() as f64 / MIB,
+            self.trace_lde as f64 / MIB,
+            self.constraint_evaluations as f64 / MIB,
+            self.composition_poly as f64 / MIB,
+            self.fri_layers as f64 / MIB,
+        )
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use air::Assertion;
+    use crypto::hashers::Blake3_256;
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    use super::MemoryEstimate;
+    use crate::tests::MockAir;
+
+    #[test]
+    fn memory_estimate() {
+        type Hasher = Blake3_256<BaseElement>;
+        const MIB: usize = 1 << 20;
+
+        // 4 columns of 16-byte elements, 2^16 rows, blowup factor 8
+        let assertions = vec![Assertion::single(0, 0, BaseElement::ONE)];
+        let air = MockAir::with_assertions(assertions, 1 << 16);
+        let estimate = MemoryEstimate::new::<_, Hasher>(&air);
+
+        // trace polynomials (4 MiB), trace LDE (32 MiB), and Merkle tree (32 MiB)
+        assert_eq!(68 * MIB, estimate.trace_lde());
+
+        // degree 2 constraints are evaluated over a domain 2x larger than the trace with one
+        // column for the transition divisor and one for the boundary divisor
+        assert_eq!(4 * MIB, estimate.constraint_evaluations());
+
+        // one composition column (1 MiB), its LDE (8 MiB), and Merkle tree (32 MiB)
+        assert_eq!(41 * MIB, estimate.composition_poly());
+        assert!(estimate.fri_layers() > 8 * MIB);
+        assert_eq!(
+            estimate.trace_lde() + estimate.composition_poly() + estimate.fri_layers(),
+            estimate.peak()
+        );
+    }
+}
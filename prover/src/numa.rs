@@ -0,0 +1,197 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! NUMA-aware scheduling of work over fragments of large tables.
+//!
+//! On machines with multiple NUMA nodes (e.g., dual-socket machines), memory is attached to a
+//! specific node, and accessing memory attached to a different node is considerably slower. Large
+//! tables (such as the constraint evaluation table) are allocated without being initialized; on
+//! Linux, physical pages of such allocations are placed on the node of the thread which first
+//! writes to them. Thus, pinning the thread which processes a fragment of a table to the CPUs of
+//! a given node places the memory of the fragment on that node, and keeps all subsequent accesses
+//! to the fragment local to the node.
+
+use alloc::vec::Vec;
+use core::mem;
+use std::fs;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Directory in which the Linux kernel describes NUMA nodes of the machine.
+const NODE_DIR: &str = "/sys/devices/system/node";
+
+// NUMA TOPOLOGY
+// ================================================================================================
+
+/// CPUs of all NUMA nodes of the machine which are available to the current process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumaTopology {
+    nodes: Vec<Vec<usize>>,
+}
+
+impl NumaTopology {
+    /// Returns the NUMA topology of the machine, or `None` if the topology could not be read or
+    /// the current process can run on CPUs of fewer than two nodes.
+    pub fn detect() -> Option<Self> {
+        let available_cpus = get_affinity()?;
+
+        let mut node_ids = fs::read_dir(NODE_DIR)
+            .ok()?
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                name.strip_prefix("node")?.parse::<usize>().ok()
+            })
+            .collect::<Vec<_>>();
+        node_ids.sort_unstable();
+
+        let nodes = node_ids
+            .into_iter()
+            .filter_map(|node_id| {
+                let cpu_list = fs::read_to_string(format!("{NODE_DIR}/node{node_id}/cpulist"));
+                let cpus = parse_cpu_list(&cpu_list.ok()?)?
+                    .into_iter()
+                    .filter(|cpu| available_cpus.contains(cpu))
+                    .collect::<Vec<_>>();
+                (!cpus.is_empty()).then_some(cpus)
+            })
+            .collect::<Vec<_>>();
+
+        Self::from_nodes(nodes)
+    }
+
+    /// Returns a topology consisting of the specified nodes, or `None` if there are fewer than
+    /// two nodes.
+    fn from_nodes(nodes: Vec<Vec<usize>>) -> Option<Self> {
+        (nodes.len() > 1).then_some(Self { nodes })
+    }
+
+    /// Returns the number of NUMA nodes in this topology.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the node to which the fragment with the specified index should be assigned when
+    /// a table is broken into `num_fragments` fragments.
+    ///
+    /// Fragments are assigned to nodes in contiguous ranges of equal size (up to rounding), so
+    /// that each node owns a contiguous part of the table.
+    pub fn fragment_node(&self, fragment_idx: usize, num_fragments: usize) -> usize {
+        debug_assert!(fragment_idx < num_fragments, "fragment index out of bounds");
+        fragment_idx * self.num_nodes() / num_fragments
+    }
+
+    /// Pins the current thread to the CPUs of the specified node until the returned guard is
+    /// dropped; the previous affinity of the thread is restored at that point.
+    ///
+    /// If the thread could not be pinned, the thread keeps running on its current CPUs.
+    pub fn pin_to_node(&self, node: usize) -> AffinityGuard {
+        let previous = get_affinity();
+        let pinned = previous.is_some() && set_affinity(&self.nodes[node]);
+        AffinityGuard {
+            previous: if pinned { previous } else { None },
+        }
+    }
+}
+
+/// Restores CPU affinity of the current thread when dropped.
+pub struct AffinityGuard {
+    previous: Option<Vec<usize>>,
+}
+
+impl Drop for AffinityGuard {
+    fn drop(&mut self) {
+        if let Some(cpus) = self.previous.take() {
+            set_affinity(&cpus);
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses a list of CPUs in the format used by the Linux kernel (e.g., "0-3,8,10-11").
+///
+/// Returns `None` if the list is malformed.
+fn parse_cpu_list(cpu_list: &str) -> Option<Vec<usize>> {
+    let mut result = Vec::new();
+    for range in cpu_list.trim().split(',').filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (start.parse::<usize>().ok()?, end.parse::<usize>().ok()?);
+                if start > end {
+                    return None;
+                }
+                result.extend(start..=end);
+            },
+            None => result.push(range.parse().ok()?),
+        }
+    }
+    Some(result)
+}
+
+/// Returns the CPUs on which the current thread is allowed to run.
+fn get_affinity() -> Option<Vec<usize>> {
+    // SAFETY: `cpu_set_t` is a plain bit mask, and the kernel writes at most
+    // `size_of::<cpu_set_t>()` bytes into it
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        if libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            return None;
+        }
+        let cpus = (0..libc::CPU_SETSIZE as usize).filter(|&cpu| libc::CPU_ISSET(cpu, &set));
+        Some(cpus.collect())
+    }
+}
+
+/// Restricts the current thread to run on the specified CPUs; returns false if the affinity of
+/// the thread could not be changed.
+fn set_affinity(cpus: &[usize]) -> bool {
+    // SAFETY: `cpu_set_t` is a plain bit mask, and CPUs beyond the size of the mask are skipped
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        for &cpu in cpus.iter().filter(|&&cpu| cpu < libc::CPU_SETSIZE as usize) {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{parse_cpu_list, NumaTopology};
+
+    #[test]
+    fn cpu_list_parsing() {
+        assert_eq!(Some(vec![0, 1, 2, 3, 8, 10, 11]), parse_cpu_list("0-3,8,10-11\n"));
+        assert_eq!(Some(vec![]), parse_cpu_list("\n"));
+        assert_eq!(None, parse_cpu_list("3-1"));
+        assert_eq!(None, parse_cpu_list("0-a"));
+    }
+
+    #[test]
+    fn fragment_assignment() {
+        assert_eq!(None, NumaTopology::from_nodes(vec![vec![0, 1]]));
+
+        let topology = NumaTopology::from_nodes(vec![vec![0, 1], vec![2, 3]]).unwrap();
+        let nodes = (0..8).map(|i| topology.fragment_node(i, 8)).collect::<Vec<_>>();
+        assert_eq!(vec![0, 0, 0, 0, 1, 1, 1, 1], nodes);
+
+        // with fewer fragments than nodes, some nodes get no fragments
+        let topology = NumaTopology::from_nodes(vec![vec![0], vec![1], vec![2], vec![3]]).unwrap();
+        assert_eq!(vec![0, 2], (0..2).map(|i| topology.fragment_node(i, 2)).collect::<Vec<_>>());
+
+        // pinning the thread to a node and releasing the guard restores the original affinity
+        let affinity = super::get_affinity().unwrap();
+        let topology = NumaTopology::from_nodes(vec![vec![affinity[0]], affinity.clone()]).unwrap();
+        drop(topology.pin_to_node(0));
+        assert_eq!(Some(affinity), super::get_affinity());
+    }
+}
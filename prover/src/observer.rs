@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::time::Duration;
+
+use air::TraceInfo;
+
+// PROVER OBSERVER TRAIT
+// ================================================================================================
+
+/// Defines an interface for observing progress of proof generation by a [Prover](crate::Prover).
+///
+/// The prover invokes methods of this trait at the end of each phase of proof generation. This
+/// can be used to expose progress indicators and metrics (e.g., per-phase timings and sizes of
+/// the committed data) from proving services without parsing debug logs. All methods have
+/// default implementations which do nothing, and thus, implementors need to override only the
+/// methods they are interested in.
+///
+/// An observer can be attached to a prover by overriding
+/// [Prover::observer()](crate::Prover::observer). Durations are measured only when the crate is
+/// compiled with the `std` feature enabled; otherwise, all reported durations are zero.
+pub trait ProverObserver: Send + Sync {
+    /// Invoked once all segments of the execution trace described by `trace_info` have been
+    /// built; `duration` is the time spent on building the auxiliary trace segment (if any).
+    fn on_trace_built(&self, _trace_info: &TraceInfo, _duration: Duration) {}
+
+    /// Invoked once all segments of the execution trace have been extended over the LDE domain
+    /// of `lde_domain_size` rows and committed to.
+    fn on_trace_committed(
+        &self,
+        _trace_info: &TraceInfo,
+        _lde_domain_size: usize,
+        _duration: Duration,
+    ) {
+    }
+
+    /// Invoked once the constraints have been evaluated over the constraint evaluation domain of
+    /// `ce_domain_size` rows.
+    fn on_constraints_evaluated(&self, _ce_domain_size: usize, _duration: Duration) {}
+
+    /// Invoked once `num_columns` columns of the constraint composition polynomial have been
+    /// extended over the LDE domain and committed to.
+    fn on_constraints_committed(&self, _num_columns: usize, _duration: Duration) {}
+
+    /// Invoked once evaluations of the FRI layer at `layer_idx` over a domain of `domain_size`
+    /// elements have been committed to and folded into the next layer.
+    fn on_fri_layer_committed(&self, _layer_idx: usize, _domain_size: usize, _duration: Duration) {}
+
+    /// Invoked once the execution trace, the constraint evaluations, and FRI layers have been
+    /// queried at `num_queries` positions and the proof of `proof_size` bytes has been built.
+    fn on_queries_built(&self, _num_queries: usize, _proof_size: usize, _duration: Duration) {}
+}
+
+// PHASE TIMER
+// ================================================================================================
+
+/// Measures the time elapsed since the start of a proof generation phase.
+pub(crate) struct PhaseTimer {
+    #[cfg(feature = "std")]
+    start: std::time::Instant,
+}
+
+impl PhaseTimer {
+    /// Starts measuring the duration of a new phase.
+    pub fn start() -> Self {
+        PhaseTimer {
+            #[cfg(feature = "std")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Returns the time elapsed since this timer was started.
+    #[cfg(feature = "std")]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns zero as time cannot be measured without the `std` feature.
+    #[cfg(not(feature = "std"))]
+    pub fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{Air, ProofOptions, TraceInfo};
+use math::StarkField;
+
+use super::{Trace, TraceTable};
+use crate::ProverError;
+
+// TRACE BUILDER
+// ================================================================================================
+/// Defines how an execution trace of a computation is built from an initial state and a state
+/// transition function.
+///
+/// Implementing this trait instead of filling a [TraceTable] by hand ties the code which builds
+/// the execution trace to the AIR against which the trace is proven: [build()](Self::build)
+/// derives a [TraceTable] (which implements the [Trace] trait) from the initial state and the
+/// transition function, [validate()](Self::validate) checks the resulting trace against all
+/// assertions and transition constraints of the AIR, and
+/// [get_pub_inputs()](Self::get_pub_inputs) extracts the public inputs for the AIR from the
+/// trace. A [Prover](crate::Prover) can delegate its own
+/// [get_pub_inputs()](crate::Prover::get_pub_inputs) method to the builder.
+///
+/// Only the main segment of the execution trace is built and validated by a trace builder. If
+/// the trace info returned from [trace_info()](Self::trace_info) declares an auxiliary segment,
+/// this segment is built by the prover as usual, and constraints against it can be checked via
+/// [Prover::validate_trace()](crate::Prover::validate_trace).
+pub trait TraceBuilder {
+    /// Base field for the execution trace built by this builder.
+    type BaseField: StarkField;
+
+    /// Algebraic intermediate representation (AIR) against which the built trace is proven.
+    type Air: Air<BaseField = Self::BaseField>;
+
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the layout of the execution trace built by this builder.
+    fn trace_info(&self) -> TraceInfo;
+
+    /// Initializes the first state of the computation (i.e., the first row of the main trace
+    /// segment).
+    ///
+    /// The provided `state` is filled with ZEROs, and its length is equal to the width of the
+    /// main trace segment.
+    fn init_state(&self, state: &mut [Self::BaseField]);
+
+    /// Updates the provided `state` at the specified `step` to the state of the computation at
+    /// the next step.
+    fn update_state(&self, step: usize, state: &mut [Self::BaseField]);
+
+    /// Returns public inputs for an instance of the computation defined by the provided trace.
+    fn get_pub_inputs(
+        &self,
+        trace: &TraceTable<Self::BaseField>,
+    ) -> <Self::Air as Air>::PublicInputs;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds the execution trace by applying the transition function of this builder to the
+    /// initial state of the computation until all rows of the main trace segment are filled.
+    ///
+    /// # Panics
+    /// Panics if the trace length is greater than the biggest multiplicative subgroup in the
+    /// base field.
+    fn build(&self) -> TraceTable<Self::BaseField> {
+        let info = self.trace_info();
+        let mut trace =
+            TraceTable::with_meta(info.main_trace_width(), info.length(), info.meta().to_vec())
+                .with_aux_segment(
+                    info.aux_segment_width(),
+                    info.get_num_aux_segment_rand_elements(),
+                );
+        trace.fill(|state| self.init_state(state), |step, state| self.update_state(step, state));
+        trace
+    }
+
+    /// Checks the provided trace against all assertions and transition constraints of the AIR
+    /// instantiated with public inputs extracted from the trace and the specified `options`.
+    ///
+    /// Constraints against the auxiliary trace segment (if any) are not checked.
+    ///
+    /// NOTE: this is an expensive operation as all constraints are evaluated at every step of the
+    /// execution trace.
+    ///
+    /// # Errors
+    /// Returns [ProverError::UnsatisfiedConstraints] listing every constraint which does not hold
+    /// if the trace is not valid against the AIR.
+    fn validate(
+        &self,
+        trace: &TraceTable<Self::BaseField>,
+        options: &ProofOptions,
+    ) -> Result<(), ProverError> {
+        let pub_inputs = self.get_pub_inputs(trace);
+        let air = Self::Air::new(trace.info().clone(), pub_inputs, options.clone());
+        let violations = trace.find_constraint_violations::<Self::Air, Self::BaseField>(&air, None);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ProverError::UnsatisfiedConstraints(violations))
+        }
+    }
+
+    /// Builds the execution trace via [build()](Self::build) and checks it against the AIR via
+    /// [validate()](Self::validate).
+    ///
+    /// # Errors
+    /// Returns [ProverError::UnsatisfiedConstraints] if the built trace is not valid against the
+    /// AIR.
+    fn build_validated(
+        &self,
+        options: &ProofOptions,
+    ) -> Result<TraceTable<Self::BaseField>, ProverError> {
+        let trace = self.build();
+        self.validate(&trace, options)?;
+        Ok(trace)
+    }
+}
@@ -3,12 +3,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::{string::ToString, vec::Vec};
+
 use air::{Air, AuxRandElements, EvaluationFrame, LagrangeKernelBoundaryConstraint, TraceInfo};
 use math::{polynom, FieldElement, StarkField};
 
-use super::ColMatrix;
+use super::{ColMatrix, ConstraintViolation, ProverError};
 
 mod trace_lde;
+#[cfg(feature = "mmap")]
+pub use trace_lde::MmapTraceLde;
 pub use trace_lde::{DefaultTraceLde, TraceLde};
 
 mod poly_table;
@@ -17,6 +21,9 @@ pub use poly_table::TracePolyTable;
 mod trace_table;
 pub use trace_table::{TraceTable, TraceTableFragment};
 
+mod builder;
+pub use builder::TraceBuilder;
+
 #[cfg(test)]
 mod tests;
 
@@ -89,6 +96,9 @@ pub trait Trace: Sized {
 
     /// Checks if this trace is valid against the specified AIR, and panics if not.
     ///
+    /// The panic message lists the constraints which are not satisfied by this trace (see
+    /// [find_constraint_violations()](Trace::find_constraint_violations)).
+    ///
     /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
     fn validate<A, E>(
         &self,
@@ -97,6 +107,36 @@ pub trait Trace: Sized {
     ) where
         A: Air<BaseField = Self::BaseField>,
         E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let violations = self.find_constraint_violations(air, aux_trace_with_metadata);
+        if !violations.is_empty() {
+            panic!("{}", ProverError::UnsatisfiedConstraints(violations));
+        }
+    }
+
+    /// Evaluates all assertions and transition constraints of the specified AIR over this trace
+    /// and returns the list of constraints which are not satisfied.
+    ///
+    /// Constraints are evaluated directly over the rows of the execution trace (rather than over
+    /// the LDE), and thus, every returned [ConstraintViolation] identifies the exact constraint,
+    /// the step at which the constraint does not hold, and the value to which the constraint
+    /// evaluated. An empty list is returned if this trace satisfies all constraints.
+    ///
+    /// If `aux_trace_with_metadata` is not provided, only the constraints against the main trace
+    /// segment are checked.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for debugging purposes only.
+    ///
+    /// # Panics
+    /// Panics if the width of the main trace segment is not consistent with the AIR.
+    fn find_constraint_violations<A, E>(
+        &self,
+        air: &A,
+        aux_trace_with_metadata: Option<&AirAuxTraceWithMetadata<A, E>>,
+    ) -> Vec<ConstraintViolation>
+    where
+        A: Air<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField>,
     {
         // make sure the width align; if they don't something went terribly wrong
         assert_eq!(
@@ -107,18 +147,22 @@ pub trait Trace: Sized {
             air.trace_info().main_trace_width(),
         );
 
+        let mut violations = Vec::new();
+
         // --- 1. make sure the assertions are valid ----------------------------------------------
 
         // first, check assertions against the main segment of the execution trace
         for assertion in air.get_assertions() {
             assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == self.main_segment().get(assertion.column(), step),
-                    "trace does not satisfy assertion main_trace({}, {}) == {}",
-                    assertion.column(),
-                    step,
-                    value
-                );
+                let actual = self.main_segment().get(assertion.column(), step);
+                if value != actual {
+                    violations.push(ConstraintViolation::MainAssertion {
+                        column: assertion.column(),
+                        step,
+                        expected: value.to_string(),
+                        actual: actual.to_string(),
+                    });
+                }
             });
         }
 
@@ -127,16 +171,20 @@ pub trait Trace: Sized {
             let aux_trace = &aux_trace_with_metadata.aux_trace;
             let aux_rand_elements = &aux_trace_with_metadata.aux_rand_elements;
 
-            for assertion in air.get_aux_assertions(aux_rand_elements.rand_elements()) {
+            let mut aux_assertions = air.get_aux_assertions(aux_rand_elements.rand_elements());
+            aux_assertions.extend(air.get_lookup_assertions());
+            for assertion in aux_assertions {
                 // get the matrix and verify the assertion against it
                 assertion.apply(self.length(), |step, value| {
-                    assert!(
-                        value == aux_trace.get(assertion.column(), step),
-                        "trace does not satisfy assertion aux_trace({}, {}) == {}",
-                        assertion.column(),
-                        step,
-                        value
-                    );
+                    let actual = aux_trace.get(assertion.column(), step);
+                    if value != actual {
+                        violations.push(ConstraintViolation::AuxAssertion {
+                            column: assertion.column(),
+                            step,
+                            expected: value.to_string(),
+                            actual: actual.to_string(),
+                        });
+                    }
                 });
             }
 
@@ -149,10 +197,14 @@ pub trait Trace: Sized {
                             .expect("expected Lagrange kernel rand elements to be present"),
                     );
 
-                assert_eq!(
-                    boundary_constraint_assertion_value,
-                    aux_trace.get(lagrange_kernel_col_idx, 0)
-                );
+                let actual = aux_trace.get(lagrange_kernel_col_idx, 0);
+                if boundary_constraint_assertion_value != actual {
+                    violations.push(ConstraintViolation::LagrangeKernelAssertion {
+                        column: lagrange_kernel_col_idx,
+                        expected: boundary_constraint_assertion_value.to_string(),
+                        actual: actual.to_string(),
+                    });
+                }
             }
         }
 
@@ -165,19 +217,37 @@ pub trait Trace: Sized {
 
         // initialize buffers to hold evaluation frames and results of constraint evaluations
         let mut x = Self::BaseField::ONE;
-        let mut main_frame = EvaluationFrame::new(self.main_trace_width());
-        let mut aux_frame = if air.trace_info().is_multi_segment() {
-            Some(EvaluationFrame::<E>::new(self.aux_trace_width()))
-        } else {
-            None
-        };
+        let frame_offsets = air.context().frame_offsets();
+        let mut main_frame =
+            EvaluationFrame::with_offsets(self.main_trace_width(), frame_offsets.to_vec());
+        let mut aux_frame = aux_trace_with_metadata.map(|_| {
+            EvaluationFrame::<E>::with_offsets(self.aux_trace_width(), frame_offsets.to_vec())
+        });
         let mut main_evaluations =
             vec![Self::BaseField::ZERO; air.context().num_main_transition_constraints()];
         let mut aux_evaluations = vec![E::ZERO; air.context().num_aux_transition_constraints()];
+        let num_lookups = air.context().num_lookups();
+        let num_main_constraints = main_evaluations.len();
+
+        // by default, we check transition constraints on all steps except the last k steps, where
+        // k is the number of steps exempt from transition constraints (guaranteed to be at least
+        // 1); constraints with custom divisors are checked on the steps where their divisors
+        // vanish
+        let last_enforced_step = self.length() - air.context().num_transition_exemptions();
+        let custom_divisors = air.context().custom_transition_divisors();
+        let is_enforced = |constraint_idx: usize, step: usize, x: Self::BaseField| {
+            let divisor = custom_divisors.get(&constraint_idx);
+            divisor.map_or(step < last_enforced_step, |divisor| divisor.vanishes_at(x))
+        };
+        // steps past the last enforced step need to be visited only if some constraints have
+        // custom divisors
+        let num_steps = if custom_divisors.is_empty() {
+            last_enforced_step
+        } else {
+            self.length()
+        };
 
-        // we check transition constraints on all steps except the last k steps, where k is the
-        // number of steps exempt from transition constraints (guaranteed to be at least 1)
-        for step in 0..self.length() - air.context().num_transition_exemptions() {
+        for step in 0..num_steps {
             // build periodic values
             for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
                 let num_cycles = air.trace_length() / p.len();
@@ -190,33 +260,50 @@ pub trait Trace: Sized {
             self.read_main_frame(step, &mut main_frame);
             air.evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
             for (i, &evaluation) in main_evaluations.iter().enumerate() {
-                assert!(
-                    evaluation == Self::BaseField::ZERO,
-                    "main transition constraint {i} did not evaluate to ZERO at step {step}"
-                );
+                if evaluation != Self::BaseField::ZERO && is_enforced(i, step, x) {
+                    violations.push(ConstraintViolation::MainTransition {
+                        constraint: i,
+                        step,
+                        value: evaluation.to_string(),
+                    });
+                }
             }
 
             // evaluate transition constraints for the auxiliary trace segment (if any) and make
             // sure they all evaluate to zeros
-            if let Some(ref mut aux_frame) = aux_frame {
-                let aux_trace_with_metadata =
-                    aux_trace_with_metadata.expect("expected aux trace to be present");
+            if let (Some(aux_frame), Some(aux_trace_with_metadata)) =
+                (aux_frame.as_mut(), aux_trace_with_metadata)
+            {
                 let aux_trace = &aux_trace_with_metadata.aux_trace;
                 let aux_rand_elements = &aux_trace_with_metadata.aux_rand_elements;
 
                 read_aux_frame(aux_trace, step, aux_frame);
-                air.evaluate_aux_transition(
+                let num_custom = aux_evaluations.len() - num_lookups;
+                let (custom_evaluations, lookup_evaluations) =
+                    aux_evaluations.split_at_mut(num_custom);
+                if !custom_evaluations.is_empty() {
+                    air.evaluate_aux_transition(
+                        &main_frame,
+                        aux_frame,
+                        &periodic_values,
+                        aux_rand_elements.rand_elements(),
+                        custom_evaluations,
+                    );
+                }
+                air.evaluate_lookup_transition(
                     &main_frame,
                     aux_frame,
-                    &periodic_values,
                     aux_rand_elements.rand_elements(),
-                    &mut aux_evaluations,
+                    lookup_evaluations,
                 );
                 for (i, &evaluation) in aux_evaluations.iter().enumerate() {
-                    assert!(
-                        evaluation == E::ZERO,
-                        "auxiliary transition constraint {i} did not evaluate to ZERO at step {step}"
-                    );
+                    if evaluation != E::ZERO && is_enforced(num_main_constraints + i, step, x) {
+                        violations.push(ConstraintViolation::AuxTransition {
+                            constraint: i,
+                            step,
+                            value: evaluation.to_string(),
+                        });
+                    }
                 }
             }
 
@@ -226,9 +313,9 @@ pub trait Trace: Sized {
 
         // evaluate transition constraints for Lagrange kernel column (if any) and make sure
         // they all evaluate to zeros
-        if let Some(col_idx) = air.context().lagrange_kernel_aux_column_idx() {
-            let aux_trace_with_metadata =
-                aux_trace_with_metadata.expect("expected aux trace to be present");
+        if let (Some(col_idx), Some(aux_trace_with_metadata)) =
+            (air.context().lagrange_kernel_aux_column_idx(), aux_trace_with_metadata)
+        {
             let aux_trace = &aux_trace_with_metadata.aux_trace;
             let aux_rand_elements = &aux_trace_with_metadata.aux_rand_elements;
 
@@ -250,13 +337,18 @@ pub trait Trace: Sized {
                     let evaluation = (r[v - constraint_idx] * c[x_current])
                         - ((E::ONE - r[v - constraint_idx]) * c[x_next]);
 
-                    assert!(
-                        evaluation == E::ZERO,
-                        "Lagrange transition constraint {constraint_idx} did not evaluate to ZERO at step {x_current}"
-                    );
+                    if evaluation != E::ZERO {
+                        violations.push(ConstraintViolation::LagrangeKernelTransition {
+                            constraint: constraint_idx,
+                            step: x_current,
+                            value: evaluation.to_string(),
+                        });
+                    }
                 }
             }
         }
+
+        violations
     }
 }
 
@@ -272,15 +364,12 @@ fn read_aux_frame<E>(aux_segment: &ColMatrix<E>, row_idx: usize, frame: &mut Eva
 where
     E: FieldElement,
 {
-    for (current_frame_cell, aux_segment_col) in
-        frame.current_mut().iter_mut().zip(aux_segment.columns())
-    {
-        *current_frame_cell = aux_segment_col[row_idx];
-    }
-
-    let next_row_idx = (row_idx + 1) % aux_segment.num_rows();
-    for (next_frame_cell, aux_segment_col) in frame.next_mut().iter_mut().zip(aux_segment.columns())
-    {
-        *next_frame_cell = aux_segment_col[next_row_idx];
+    for idx in 0..frame.num_rows() {
+        let frame_row_idx = (row_idx + frame.offsets()[idx]) % aux_segment.num_rows();
+        for (frame_cell, aux_segment_col) in
+            frame.row_mut(idx).iter_mut().zip(aux_segment.columns())
+        {
+            *frame_cell = aux_segment_col[frame_row_idx];
+        }
     }
 }
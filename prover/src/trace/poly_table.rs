@@ -6,7 +6,7 @@
 use alloc::vec::Vec;
 
 use air::{proof::TraceOodFrame, LagrangeKernelEvaluationFrame};
-use math::{FieldElement, StarkField};
+use math::{ExtensionOf, FieldElement, StarkField};
 
 use crate::{matrix::ColumnIter, ColMatrix};
 
@@ -19,6 +19,7 @@ use crate::{matrix::ColumnIter, ColMatrix};
 /// However, coefficients of the polynomials for the auxiliary trace segment (including
 /// the Lagrange kernel polynomial when present) may be either in the base field, or in
 /// the extension field, depending on whether extension field is being used.
+#[derive(Clone)]
 pub struct TracePolyTable<E: FieldElement> {
     main_trace_polys: ColMatrix<E::BaseField>,
     aux_trace_polys: Option<ColMatrix<E>>,
@@ -69,7 +70,12 @@ impl<E: FieldElement> TracePolyTable<E> {
     }
 
     /// Evaluates all trace polynomials (across all trace segments) at the specified point `x`.
-    pub fn evaluate_at(&self, x: E) -> Vec<E> {
+    ///
+    /// The point may be drawn from an extension of the field of the auxiliary trace polynomials.
+    pub fn evaluate_at<D>(&self, x: D) -> Vec<D>
+    where
+        D: FieldElement<BaseField = E::BaseField> + ExtensionOf<E>,
+    {
         let mut result = self.main_trace_polys.evaluate_columns_at(x);
         for aux_polys in self.aux_trace_polys.iter() {
             result.append(&mut aux_polys.evaluate_columns_at(x));
@@ -78,15 +84,24 @@ impl<E: FieldElement> TracePolyTable<E> {
     }
 
     /// Returns an out-of-domain evaluation frame constructed by evaluating trace polynomials for
-    /// all columns at points z and z * g, where g is the generator of the trace domain.
+    /// all columns at points z * g^k for each of the specified row `offsets` k (e.g., z and z * g
+    /// for the default offsets), where g is the generator of the trace domain.
     /// Additionally, if the Lagrange kernel auxiliary column is present, we also evaluate that
     /// column over the points: z, z * g, z * g^2, z * g^4, ..., z * g^(2^(v-1)), where v =
     /// log(trace_len).
-    pub fn get_ood_frame(&self, z: E) -> TraceOodFrame<E> {
+    ///
+    /// The out-of-domain point `z` may be drawn from an extension of the field of the auxiliary
+    /// trace polynomials.
+    pub fn get_ood_frame<D>(&self, z: D, offsets: &[usize]) -> TraceOodFrame<D>
+    where
+        D: FieldElement<BaseField = E::BaseField> + ExtensionOf<E>,
+    {
         let log_trace_len = self.poly_size().ilog2();
-        let g = E::from(E::BaseField::get_root_of_unity(log_trace_len));
-        let current_row = self.evaluate_at(z);
-        let next_row = self.evaluate_at(z * g);
+        let g = E::BaseField::get_root_of_unity(log_trace_len);
+        let rows = offsets
+            .iter()
+            .map(|&offset| self.evaluate_at(z.mul_base(g.exp((offset as u64).into()))))
+            .collect();
 
         let lagrange_kernel_frame =
             self.lagrange_kernel_poly.as_ref().map(|lagrange_kernel_col_poly| {
@@ -98,7 +113,7 @@ impl<E: FieldElement> TracePolyTable<E> {
 
         let main_trace_width = self.main_trace_polys.num_cols();
 
-        TraceOodFrame::new(current_row, next_row, main_trace_width, lagrange_kernel_frame)
+        TraceOodFrame::new(rows, offsets.to_vec(), main_trace_width, lagrange_kernel_frame)
     }
 
     /// Returns an iterator over the polynomials of the main trace segment.
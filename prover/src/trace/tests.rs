@@ -5,9 +5,9 @@
 
 use alloc::vec::Vec;
 
-use math::fields::f128::BaseElement;
+use math::{fields::f128::BaseElement, FieldElement};
 
-use crate::{tests::build_fib_trace, Trace};
+use crate::{tests::build_fib_trace, Trace, TraceTable};
 
 #[test]
 fn new_trace_table() {
@@ -29,3 +29,63 @@ fn new_trace_table() {
         .collect();
     assert_eq!(expected, trace.get_column(1));
 }
+
+#[test]
+fn fill_trace_table_in_parallel() {
+    let trace_length = 32;
+
+    // the first column is a counter, and the second column accumulates the values of the first
+    // column within every fragment of 8 rows
+    let mut trace = TraceTable::new(2, trace_length);
+    trace.fill_parallel(
+        8,
+        |step, row| row[0] = BaseElement::from(step as u32),
+        |_, prev_row, next_row| {
+            next_row[0] = prev_row[0] + BaseElement::ONE;
+            next_row[1] = prev_row[1] + prev_row[0];
+        },
+    );
+
+    let mut sum = BaseElement::ZERO;
+    for step in 0..trace_length {
+        if step % 8 == 0 {
+            sum = BaseElement::ZERO;
+        }
+        assert_eq!(BaseElement::from(step as u32), trace.get(0, step));
+        assert_eq!(sum, trace.get(1, step));
+        sum += trace.get(0, step);
+    }
+
+    // rows which do not depend on each other are filled independently
+    let mut trace = TraceTable::new(2, trace_length);
+    trace.fill_rows_parallel(|step, row| {
+        row[0] = BaseElement::from(step as u32);
+        row[1] = row[0].square();
+    });
+
+    for step in 0..trace_length {
+        let value = BaseElement::from(step as u32);
+        assert_eq!(value, trace.get(0, step));
+        assert_eq!(value.square(), trace.get(1, step));
+    }
+}
+
+#[test]
+fn iterate_trace_table() {
+    let trace = build_fib_trace(16);
+
+    // columns are returned directly from the underlying storage
+    assert_eq!(2, trace.columns().len());
+    for (i, column) in trace.columns().enumerate() {
+        assert_eq!(trace.get_column(i).as_ptr(), column.as_ptr());
+    }
+
+    // rows are assembled from the columns
+    let rows = trace.rows();
+    assert_eq!(trace.length(), rows.len());
+    let mut expected = vec![BaseElement::ZERO; trace.width()];
+    for (step, row) in rows.enumerate() {
+        trace.read_row_into(step, &mut expected);
+        assert_eq!(expected, row);
+    }
+}
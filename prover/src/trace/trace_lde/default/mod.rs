@@ -148,33 +148,46 @@ where
         (aux_segment_polys, root_hash)
     }
 
-    /// Reads current and next rows from the main trace segment into the specified frame.
+    /// Removes the auxiliary trace segment (if any) together with the commitment to it.
+    fn remove_aux_trace(&mut self) {
+        self.aux_segment_lde = None;
+        self.aux_segment_tree = None;
+    }
+
+    /// Reads rows at all offsets of the specified frame (by default, current and next rows) from
+    /// the main trace segment into the frame.
     fn read_main_trace_frame_into(
         &self,
         lde_step: usize,
         frame: &mut EvaluationFrame<E::BaseField>,
     ) {
-        // at the end of the trace, next state wraps around and we read the first step again
-        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
+        // at the end of the trace, subsequent states wrap around and we read the first steps
+        // again
+        for idx in 0..frame.num_rows() {
+            let row_lde_step = (lde_step + frame.offsets()[idx] * self.blowup()) % self.trace_len();
 
-        // copy main trace segment values into the frame
-        frame.current_mut().copy_from_slice(self.main_segment_lde.row(lde_step));
-        frame.next_mut().copy_from_slice(self.main_segment_lde.row(next_lde_step));
+            // copy main trace segment values into the frame
+            frame.row_mut(idx).copy_from_slice(self.main_segment_lde.row(row_lde_step));
+        }
     }
 
-    /// Reads current and next rows from the auxiliary trace segment into the specified frame.
+    /// Reads rows at all offsets of the specified frame (by default, current and next rows) from
+    /// the auxiliary trace segment into the frame.
     ///
     /// # Panics
     /// This currently assumes that there is exactly one auxiliary trace segment, and will panic
     /// otherwise.
     fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
-        // at the end of the trace, next state wraps around and we read the first step again
-        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
-
-        // copy auxiliary trace segment values into the frame
         let segment = self.aux_segment_lde.as_ref().expect("expected aux segment to be present");
-        frame.current_mut().copy_from_slice(segment.row(lde_step));
-        frame.next_mut().copy_from_slice(segment.row(next_lde_step));
+
+        // at the end of the trace, subsequent states wrap around and we read the first steps
+        // again
+        for idx in 0..frame.num_rows() {
+            let row_lde_step = (lde_step + frame.offsets()[idx] * self.blowup()) % self.trace_len();
+
+            // copy auxiliary trace segment values into the frame
+            frame.row_mut(idx).copy_from_slice(segment.row(row_lde_step));
+        }
     }
 
     fn read_lagrange_kernel_frame_into(
@@ -265,7 +278,7 @@ where
             blowup = domain.trace_to_lde_blowup()
         )
         .entered();
-        let trace_polys = trace.interpolate_columns();
+        let trace_polys = trace.interpolate_columns_over(domain);
         let trace_lde =
             RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&trace_polys, domain);
         drop(span);
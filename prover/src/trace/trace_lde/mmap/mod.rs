@@ -0,0 +1,316 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use air::{LagrangeKernelEvaluationFrame, MemoryBudget};
+use crypto::MmapMerkleTree;
+use tracing::info_span;
+
+use super::{
+    ColMatrix, ElementHasher, EvaluationFrame, FieldElement, Hasher, Queries, StarkDomain,
+    TraceInfo, TraceLde, TracePolyTable,
+};
+
+mod segment;
+use segment::MmapSegment;
+
+#[cfg(test)]
+mod tests;
+
+// MEMORY-MAPPED TRACE LOW DEGREE EXTENSION
+// ================================================================================================
+/// Contains all segments of the extended execution trace and the commitments to these segments;
+/// unlike [DefaultTraceLde](super::DefaultTraceLde), the extended segments and the Merkle trees
+/// committing to them are stored in temporary files rather than in memory.
+///
+/// Trace segments are extended in chunks of columns which are as wide as the specified
+/// [MemoryBudget] allows; each chunk is written to disk before the next chunk is extended. The
+/// resulting files are memory-mapped, and thus, the prover reads extended rows from disk (or from
+/// the OS page cache) whenever it needs them. This trades proof generation time for memory, and
+/// makes it possible to prove computations with traces whose LDEs do not fit into memory.
+///
+/// Trace polynomials in coefficient form are still kept in memory. Proofs generated using this
+/// struct are identical to the proofs generated using [DefaultTraceLde](super::DefaultTraceLde).
+pub struct MmapTraceLde<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    // low-degree extension of the main segment of the trace
+    main_segment_lde: MmapSegment<E::BaseField>,
+    // commitment to the main segment of the trace
+    main_segment_tree: MmapMerkleTree<H>,
+    // low-degree extensions of the auxiliary segment of the trace
+    aux_segment_lde: Option<MmapSegment<E>>,
+    // commitment to the auxiliary segment of the trace
+    aux_segment_tree: Option<MmapMerkleTree<H>>,
+    blowup: usize,
+    memory_budget: MemoryBudget,
+    trace_info: TraceInfo,
+}
+
+impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> MmapTraceLde<E, H> {
+    /// Takes the main trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
+    /// polynomial evaluations, and creates a new [MmapTraceLde] with the LDE of the main trace
+    /// segment and the commitment.
+    ///
+    /// The `memory_budget` (usually, the one returned from
+    /// [ProofOptions::memory_budget()](crate::ProofOptions::memory_budget)) limits the number of
+    /// columns which are extended at once; the same budget is used for the auxiliary trace
+    /// segment.
+    ///
+    /// Returns a tuple containing a [TracePolyTable] with the trace polynomials for the main trace
+    /// segment and the new [MmapTraceLde].
+    ///
+    /// # Panics
+    /// Panics if a temporary file for the extended trace could not be created, written to, or
+    /// mapped into memory.
+    pub fn new(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+        memory_budget: MemoryBudget,
+    ) -> (Self, TracePolyTable<E>) {
+        // extend the main execution trace and build a Merkle tree from the extended trace
+        let (main_segment_lde, main_segment_tree, main_segment_polys) =
+            build_trace_commitment::<E, E::BaseField, H>(main_trace, domain, memory_budget);
+
+        let trace_poly_table = TracePolyTable::new(main_segment_polys);
+        let trace_lde = MmapTraceLde {
+            main_segment_lde,
+            main_segment_tree,
+            aux_segment_lde: None,
+            aux_segment_tree: None,
+            blowup: domain.trace_to_lde_blowup(),
+            memory_budget,
+            trace_info: trace_info.clone(),
+        };
+
+        (trace_lde, trace_poly_table)
+    }
+
+    // TEST HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns number of columns in the main segment of the execution trace.
+    #[cfg(test)]
+    pub fn main_segment_width(&self) -> usize {
+        self.main_segment_lde.num_cols()
+    }
+
+    /// Returns the number of chunks in which the main segment of the execution trace is stored.
+    #[cfg(test)]
+    pub fn main_segment_num_chunks(&self) -> usize {
+        self.main_segment_lde.num_chunks()
+    }
+}
+
+impl<E, H> TraceLde<E> for MmapTraceLde<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField> + Sync,
+{
+    type HashFn = H;
+
+    /// Returns the commitment to the low-degree extension of the main trace segment.
+    fn get_main_trace_commitment(&self) -> <Self::HashFn as Hasher>::Digest {
+        *self.main_segment_tree.root()
+    }
+
+    /// Takes auxiliary trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain, and commits to the
+    /// polynomial evaluations.
+    ///
+    /// Returns a tuple containing the column polynomials in coefficient from and the commitment
+    /// to the polynomial evaluations over the LDE domain.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if any of the following are true:
+    /// - the number of rows in the provided `aux_trace` does not match the main trace.
+    /// - the auxiliary trace has been previously set already.
+    /// - a temporary file for the extended trace could not be created, written to, or mapped
+    ///   into memory.
+    fn set_aux_trace(
+        &mut self,
+        aux_trace: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest) {
+        // check errors
+        assert!(
+            usize::from(self.aux_segment_lde.is_some()) < self.trace_info.num_aux_segments(),
+            "the auxiliary trace has already been added"
+        );
+
+        // extend the auxiliary trace segment and build a Merkle tree from the extended trace
+        let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
+            build_trace_commitment::<E, E, H>(aux_trace, domain, self.memory_budget);
+        assert_eq!(
+            self.main_segment_lde.num_rows(),
+            aux_segment_lde.num_rows(),
+            "the number of rows in the auxiliary segment must be the same as in the main segment"
+        );
+
+        // save the lde and commitment
+        self.aux_segment_lde = Some(aux_segment_lde);
+        let root_hash = *aux_segment_tree.root();
+        self.aux_segment_tree = Some(aux_segment_tree);
+
+        (aux_segment_polys, root_hash)
+    }
+
+    /// Removes the auxiliary trace segment (if any) together with the commitment to it.
+    fn remove_aux_trace(&mut self) {
+        self.aux_segment_lde = None;
+        self.aux_segment_tree = None;
+    }
+
+    /// Reads rows at all offsets of the specified frame (by default, current and next rows) from
+    /// the main trace segment into the frame.
+    fn read_main_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E::BaseField>,
+    ) {
+        // at the end of the trace, subsequent states wrap around and we read the first steps
+        // again
+        for idx in 0..frame.num_rows() {
+            let row_lde_step = (lde_step + frame.offsets()[idx] * self.blowup()) % self.trace_len();
+            self.main_segment_lde.read_row_into(row_lde_step, frame.row_mut(idx));
+        }
+    }
+
+    /// Reads rows at all offsets of the specified frame (by default, current and next rows) from
+    /// the auxiliary trace segment into the frame.
+    ///
+    /// # Panics
+    /// This currently assumes that there is exactly one auxiliary trace segment, and will panic
+    /// otherwise.
+    fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
+        let segment = self.aux_segment_lde.as_ref().expect("expected aux segment to be present");
+
+        // at the end of the trace, subsequent states wrap around and we read the first steps
+        // again
+        for idx in 0..frame.num_rows() {
+            let row_lde_step = (lde_step + frame.offsets()[idx] * self.blowup()) % self.trace_len();
+            segment.read_row_into(row_lde_step, frame.row_mut(idx));
+        }
+    }
+
+    fn read_lagrange_kernel_frame_into(
+        &self,
+        lde_step: usize,
+        lagrange_kernel_aux_column_idx: usize,
+        frame: &mut LagrangeKernelEvaluationFrame<E>,
+    ) {
+        let frame = frame.frame_mut();
+        frame.truncate(0);
+
+        let aux_segment =
+            self.aux_segment_lde.as_ref().expect("expected aux segment to be present");
+
+        frame.push(aux_segment.get(lagrange_kernel_aux_column_idx, lde_step));
+
+        let frame_length = self.trace_info.length().ilog2() as usize + 1;
+        for i in 0..frame_length - 1 {
+            let shift = self.blowup() * (1 << i);
+            let next_lde_step = (lde_step + shift) % self.trace_len();
+
+            frame.push(aux_segment.get(lagrange_kernel_aux_column_idx, next_lde_step));
+        }
+    }
+
+    /// Returns trace table rows at the specified positions along with Merkle authentication paths
+    /// from the commitment root to these rows.
+    fn query(&self, positions: &[usize]) -> Vec<Queries> {
+        // build queries for the main trace segment
+        let mut result = vec![build_segment_queries(
+            &self.main_segment_lde,
+            &self.main_segment_tree,
+            positions,
+        )];
+
+        // build queries for the auxiliary trace segment
+        if let Some(ref segment_tree) = self.aux_segment_tree {
+            let segment_lde =
+                self.aux_segment_lde.as_ref().expect("expected aux segment to be present");
+            result.push(build_segment_queries(segment_lde, segment_tree, positions));
+        }
+
+        result
+    }
+
+    /// Returns the number of rows in the execution trace.
+    fn trace_len(&self) -> usize {
+        self.main_segment_lde.num_rows()
+    }
+
+    /// Returns blowup factor which was used to extend original execution trace into trace LDE.
+    fn blowup(&self) -> usize {
+        self.blowup
+    }
+
+    /// Returns the trace info of the execution trace.
+    fn trace_info(&self) -> &TraceInfo {
+        &self.trace_info
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes a low-degree extension (LDE) of the provided execution trace over the specified
+/// domain in chunks of columns limited by `memory_budget`, and builds a commitment to the
+/// extended trace.
+///
+/// The extended trace and the Merkle tree committing to it are stored in temporary files.
+fn build_trace_commitment<E, F, H>(
+    trace: &ColMatrix<F>,
+    domain: &StarkDomain<E::BaseField>,
+    memory_budget: MemoryBudget,
+) -> (MmapSegment<F>, MmapMerkleTree<H>, ColMatrix<F>)
+where
+    E: FieldElement,
+    F: FieldElement<BaseField = E::BaseField>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    // extend the execution trace
+    let (trace_lde, trace_polys) = info_span!(
+        "extend_execution_trace",
+        num_cols = trace.num_cols(),
+        blowup = domain.trace_to_lde_blowup()
+    )
+    .in_scope(|| MmapSegment::new(trace, domain, memory_budget));
+    assert_eq!(trace_lde.num_cols(), trace.num_cols());
+    assert_eq!(trace_polys.num_rows(), trace.num_rows());
+    assert_eq!(trace_lde.num_rows(), domain.lde_domain_size());
+
+    // build trace commitment
+    let tree_depth = trace_lde.num_rows().ilog2() as usize;
+    let trace_tree = info_span!("compute_execution_trace_commitment", tree_depth)
+        .in_scope(|| trace_lde.commit_to_rows());
+    assert_eq!(trace_tree.depth(), tree_depth);
+
+    (trace_lde, trace_tree, trace_polys)
+}
+
+fn build_segment_queries<E, H>(
+    segment_lde: &MmapSegment<E>,
+    segment_tree: &MmapMerkleTree<H>,
+    positions: &[usize],
+) -> Queries
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    // for each position, read the corresponding row from the trace segment LDE and put all these
+    // rows into a single vector
+    let trace_states = positions.iter().map(|&pos| segment_lde.row(pos)).collect::<Vec<_>>();
+
+    // build Merkle authentication paths to the leaves specified by positions
+    let trace_proof = segment_tree
+        .prove_batch(positions)
+        .expect("failed to generate a Merkle proof for trace queries");
+
+    Queries::new(trace_proof, trace_states)
+}
@@ -0,0 +1,276 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::PathBuf,
+    process,
+};
+
+use air::MemoryBudget;
+use crypto::{ElementHasher, MmapMerkleTree, VectorCommitment};
+use math::FieldElement;
+use memmap2::Mmap;
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+use utils::{batch_iter_mut, uninit_vector};
+
+use crate::{ColMatrix, RowMatrix, StarkDomain, DEFAULT_SEGMENT_WIDTH};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Counter used to generate unique names for temporary files backing segment chunks.
+static NEXT_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+
+// MEMORY-MAPPED SEGMENT
+// ================================================================================================
+
+/// Low-degree extension of a single trace segment stored in temporary files.
+///
+/// The extension is split into chunks, each containing a contiguous range of segment columns.
+/// Every chunk is written in row-major order into a separate file which is then memory-mapped;
+/// the files are removed when the segment is dropped.
+pub struct MmapSegment<E: FieldElement> {
+    chunks: Vec<SegmentChunk>,
+    num_rows: usize,
+    num_cols: usize,
+    _element: PhantomData<E>,
+}
+
+impl<E: FieldElement> MmapSegment<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Interpolates columns of the provided trace segment into polynomials, evaluates the
+    /// polynomials over the LDE domain, and writes the evaluations into temporary files.
+    ///
+    /// Columns are extended in chunks which are as wide as the specified memory `budget` allows
+    /// (but always contain at least one column); each chunk is written to disk before the next
+    /// chunk is extended.
+    ///
+    /// Returns the segment LDE together with the column polynomials in coefficient form.
+    ///
+    /// # Panics
+    /// Panics if a temporary file could not be created, written to, or mapped into memory.
+    pub fn new(
+        trace: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+        budget: MemoryBudget,
+    ) -> (Self, ColMatrix<E>) {
+        let num_rows = domain.lde_domain_size();
+        let num_cols = trace.num_cols();
+        let chunk_width = get_chunk_width::<E>(num_rows, num_cols, budget);
+
+        let mut chunks = Vec::with_capacity(num_cols.div_ceil(chunk_width));
+        let mut polys = Vec::with_capacity(num_cols);
+        for first_col in (0..num_cols).step_by(chunk_width) {
+            let chunk_polys = ColMatrix::new(
+                trace.columns().skip(first_col).take(chunk_width).map(|c| c.to_vec()).collect(),
+            )
+            .interpolate_columns_over(domain);
+            let chunk_lde =
+                RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&chunk_polys, domain);
+            assert_eq!(chunk_lde.num_rows(), num_rows);
+
+            chunks.push(SegmentChunk::new(&chunk_lde));
+            polys.extend(chunk_polys.into_columns());
+        }
+
+        let segment = MmapSegment {
+            chunks,
+            num_rows,
+            num_cols,
+            _element: PhantomData,
+        };
+
+        (segment, ColMatrix::new(polys))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in this segment.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of columns in this segment.
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Returns the number of chunks in which this segment is stored.
+    #[cfg(test)]
+    pub fn num_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the value at the specified column and row.
+    pub fn get(&self, col_idx: usize, row_idx: usize) -> E {
+        let mut first_col = 0;
+        for chunk in self.chunks.iter() {
+            if col_idx < first_col + chunk.num_cols {
+                return chunk.read_element(row_idx, col_idx - first_col);
+            }
+            first_col += chunk.num_cols;
+        }
+        panic!("column index {col_idx} is out of bounds");
+    }
+
+    /// Copies values of the specified row into the provided slice.
+    ///
+    /// # Panics
+    /// Panics if the length of `row` is not equal to the number of columns in this segment.
+    pub fn read_row_into(&self, row_idx: usize, row: &mut [E]) {
+        assert_eq!(row.len(), self.num_cols, "row must contain {} values", self.num_cols);
+        let mut first_col = 0;
+        for chunk in self.chunks.iter() {
+            chunk.read_row_into(row_idx, &mut row[first_col..first_col + chunk.num_cols]);
+            first_col += chunk.num_cols;
+        }
+    }
+
+    /// Returns the values of the specified row.
+    pub fn row(&self, row_idx: usize) -> Vec<E> {
+        let mut row = vec![E::ZERO; self.num_cols];
+        self.read_row_into(row_idx, &mut row);
+        row
+    }
+
+    // COMMITMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to this segment.
+    ///
+    /// The commitment is built by hashing each row of the segment, and then building a Merkle
+    /// tree from the resulting hashes; the resulting tree is identical to the one which would be
+    /// built from the same rows held in memory, but its nodes are kept in a temporary file.
+    pub fn commit_to_rows<H>(&self) -> MmapMerkleTree<H>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        // allocate vector to store row hashes
+        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows) };
+
+        // iterate though segment rows, hashing each row
+        batch_iter_mut!(
+            &mut row_hashes,
+            128, // min batch size
+            |batch: &mut [H::Digest], batch_offset: usize| {
+                let mut row = vec![E::ZERO; self.num_cols];
+                for (i, row_hash) in batch.iter_mut().enumerate() {
+                    self.read_row_into(batch_offset + i, &mut row);
+                    *row_hash = H::hash_elements(&row);
+                }
+            }
+        );
+
+        // build Merkle tree out of hashed rows
+        MmapMerkleTree::commit(row_hashes).expect("failed to construct trace Merkle tree")
+    }
+}
+
+// SEGMENT CHUNK
+// ================================================================================================
+
+/// A contiguous range of segment columns stored in row-major order in a memory-mapped file.
+struct SegmentChunk {
+    mmap: Mmap,
+    num_cols: usize,
+    // declared after `mmap` so that the file is unmapped before it is removed
+    _temp_file: TempFile,
+}
+
+impl SegmentChunk {
+    /// Writes all rows of the provided matrix into a new temporary file, and maps the file into
+    /// memory.
+    fn new<E: FieldElement>(lde: &RowMatrix<E>) -> Self {
+        let file_id = NEXT_FILE_ID.fetch_add(1, Ordering::Relaxed);
+        let path =
+            std::env::temp_dir().join(format!("winter-trace-lde-{}-{file_id}.bin", process::id()));
+        let temp_file = TempFile(path);
+
+        let file = File::create(&temp_file.0).expect("failed to create trace LDE file");
+        let mut writer = BufWriter::new(file);
+        let mut buf = Vec::with_capacity(lde.num_cols() * E::ELEMENT_BYTES);
+        for row_idx in 0..lde.num_rows() {
+            buf.clear();
+            lde.row(row_idx).iter().for_each(|value| value.write_into(&mut buf));
+            writer.write_all(&buf).expect("failed to write trace LDE file");
+        }
+        writer.flush().expect("failed to write trace LDE file");
+        drop(writer);
+
+        let file = File::open(&temp_file.0).expect("failed to open trace LDE file");
+        // SAFETY: the mapping is read-only, and the file is not modified while it is mapped.
+        let mmap = unsafe { Mmap::map(&file) }.expect("failed to map trace LDE file");
+        assert_eq!(mmap.len(), lde.num_rows() * lde.num_cols() * E::ELEMENT_BYTES);
+
+        SegmentChunk {
+            mmap,
+            num_cols: lde.num_cols(),
+            _temp_file: temp_file,
+        }
+    }
+
+    /// Reads the element at the specified row and column of this chunk.
+    fn read_element<E: FieldElement>(&self, row_idx: usize, col_idx: usize) -> E {
+        let offset = (row_idx * self.num_cols + col_idx) * E::ELEMENT_BYTES;
+        E::read_from_bytes(&self.mmap[offset..offset + E::ELEMENT_BYTES])
+            .expect("failed to read trace LDE element from mapped file")
+    }
+
+    /// Reads elements of the specified row of this chunk into the provided slice.
+    fn read_row_into<E: FieldElement>(&self, row_idx: usize, row: &mut [E]) {
+        let row_bytes = self.num_cols * E::ELEMENT_BYTES;
+        let offset = row_idx * row_bytes;
+        let bytes = &self.mmap[offset..offset + row_bytes];
+        for (value, bytes) in row.iter_mut().zip(bytes.chunks(E::ELEMENT_BYTES)) {
+            *value = E::read_from_bytes(bytes)
+                .expect("failed to read trace LDE element from mapped file");
+        }
+    }
+}
+
+// TEMPORARY FILE
+// ================================================================================================
+
+/// Path to a temporary file which is removed when this struct is dropped.
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of columns which can be extended at once within the specified budget.
+///
+/// Extending a chunk of columns requires memory for the polynomials of the chunk and for their
+/// evaluations over the LDE domain of `lde_domain_size` rows; the latter dominates, and thus,
+/// the budget is divided by the size of a single extended column.
+fn get_chunk_width<E: FieldElement>(
+    lde_domain_size: usize,
+    num_cols: usize,
+    budget: MemoryBudget,
+) -> usize {
+    match budget.max_bytes() {
+        None => num_cols.max(1),
+        Some(max_bytes) => {
+            let column_bytes = lde_domain_size * E::ELEMENT_BYTES;
+            (max_bytes / column_bytes).clamp(1, num_cols.max(1))
+        },
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{EvaluationFrame, MemoryBudget};
+use crypto::hashers::Blake3_256;
+use math::{fields::f128::BaseElement, FieldElement};
+
+use crate::{
+    tests::{build_fib_trace, MockAir},
+    DefaultTraceLde, MmapTraceLde, StarkDomain, Trace, TraceLde,
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+#[test]
+fn extend_trace_table_in_chunks() {
+    // build the trace and the domain
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+
+    // build the trace LDE in memory, and in chunks of a single column each (the budget is exactly
+    // enough for one extended column)
+    let (expected_lde, expected_polys) =
+        DefaultTraceLde::<BaseElement, Blake3>::new(trace.info(), trace.main_segment(), &domain);
+    let budget = MemoryBudget::Bytes(domain.lde_domain_size() * BaseElement::ELEMENT_BYTES);
+    let (trace_lde, trace_polys) = MmapTraceLde::<BaseElement, Blake3>::new(
+        trace.info(),
+        trace.main_segment(),
+        &domain,
+        budget,
+    );
+
+    // check the shape of the extended trace
+    assert_eq!(2, trace_lde.main_segment_width());
+    assert_eq!(2, trace_lde.main_segment_num_chunks());
+    assert_eq!(64, trace_lde.trace_len());
+
+    // trace polynomials and the commitment must be the same as for the in-memory trace LDE
+    for i in 0..2 {
+        assert_eq!(expected_polys.get_main_trace_poly(i), trace_polys.get_main_trace_poly(i));
+    }
+    assert_eq!(expected_lde.get_main_trace_commitment(), trace_lde.get_main_trace_commitment());
+
+    // frames read from the extended trace must be the same as for the in-memory trace LDE
+    let mut expected_frame = EvaluationFrame::new(2);
+    let mut frame = EvaluationFrame::new(2);
+    for lde_step in [0, 1, 31, 56, 63] {
+        expected_lde.read_main_trace_frame_into(lde_step, &mut expected_frame);
+        trace_lde.read_main_trace_frame_into(lde_step, &mut frame);
+        assert_eq!(expected_frame.current(), frame.current());
+        assert_eq!(expected_frame.next(), frame.next());
+    }
+
+    // queries must be the same as for the in-memory trace LDE
+    let positions = [1, 7, 20, 63];
+    assert_eq!(expected_lde.query(&positions), trace_lde.query(&positions));
+}
+
+#[test]
+fn extend_trace_table_with_unlimited_budget() {
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+
+    let (expected_lde, _) =
+        DefaultTraceLde::<BaseElement, Blake3>::new(trace.info(), trace.main_segment(), &domain);
+    let (trace_lde, _) = MmapTraceLde::<BaseElement, Blake3>::new(
+        trace.info(),
+        trace.main_segment(),
+        &domain,
+        MemoryBudget::Unlimited,
+    );
+
+    // with unlimited budget, all columns are extended at once
+    assert_eq!(1, trace_lde.main_segment_num_chunks());
+    assert_eq!(expected_lde.get_main_trace_commitment(), trace_lde.get_main_trace_commitment());
+}
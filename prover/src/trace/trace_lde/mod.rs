@@ -14,6 +14,11 @@ use crate::StarkDomain;
 mod default;
 pub use default::DefaultTraceLde;
 
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapTraceLde;
+
 // TRACE LOW DEGREE EXTENSION
 // ================================================================================================
 /// Contains all segments of the extended execution trace and their commitments.
@@ -48,6 +53,12 @@ pub trait TraceLde<E: FieldElement>: Sync {
         domain: &StarkDomain<E::BaseField>,
     ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest);
 
+    /// Removes the auxiliary trace segment (if any) together with the commitment to it.
+    ///
+    /// This allows the LDE of the main trace segment to be reused in another proof, for which
+    /// the auxiliary trace segment is built using different random elements.
+    fn remove_aux_trace(&mut self);
+
     /// Reads current and next rows from the main trace segment into the specified frame.
     fn read_main_trace_frame_into(
         &self,
@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use alloc::vec::Vec;
+use core::{cmp, mem};
 
 use air::{EvaluationFrame, TraceInfo};
 use math::StarkField;
@@ -12,6 +13,7 @@ use utils::uninit_vector;
 use utils::{iterators::*, rayon};
 
 use super::{ColMatrix, Trace};
+use crate::matrix::{ColumnIter, RowIter};
 
 // CONSTANTS
 // ================================================================================================
@@ -58,6 +60,12 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](TraceTableFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [TraceTableFragment::fill()] method are identical to the
 /// semantics of the [TraceTable::fill()] method.
+///
+/// Alternatively, [fill_parallel()](TraceTable::fill_parallel) method breaks the trace into
+/// fragments and fills them in parallel using the provided closures, and, for traces in which
+/// every row can be computed independently of all other rows,
+/// [fill_rows_parallel()](TraceTable::fill_rows_parallel) method fills all rows of the trace in
+/// parallel.
 #[derive(Debug, Clone)]
 pub struct TraceTable<B: StarkField> {
     info: TraceInfo,
@@ -75,7 +83,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
     ///   `B`, or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
@@ -90,7 +98,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
     ///   field `B`, or is not a power of two.
     /// * Length of `meta` is greater than 65535;
@@ -112,7 +120,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * The `columns` vector is empty or has over 255 columns.
+    /// * The `columns` vector is empty or has over 65535 columns.
     /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all columns.
@@ -136,6 +144,61 @@ impl<B: StarkField> TraceTable<B> {
         Self { info, trace: ColMatrix::new(columns) }
     }
 
+    /// Creates a new execution trace for an [AirComposition](crate::AirComposition) by joining
+    /// the main segments of the two provided component traces.
+    ///
+    /// The resulting trace consists of the columns of `first` followed by the columns of
+    /// `second`, and is described by a trace info built via [TraceInfo::compose()].
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Lengths of the component traces are not the same.
+    /// * Any of the component traces has an auxiliary segment.
+    /// * Total width of the component traces is greater than 65535.
+    pub fn compose<T1, T2>(first: &T1, second: &T2) -> Self
+    where
+        T1: Trace<BaseField = B>,
+        T2: Trace<BaseField = B>,
+    {
+        let info = TraceInfo::compose(first.info(), second.info());
+        let columns = first
+            .main_segment()
+            .columns()
+            .chain(second.main_segment().columns())
+            .map(|column| column.to_vec())
+            .collect();
+
+        Self { info, trace: ColMatrix::new(columns) }
+    }
+
+    /// Declares that this execution trace is followed by an auxiliary trace segment of the
+    /// specified width, and returns the updated trace.
+    ///
+    /// Columns of the auxiliary segment are not stored in the trace table; instead, they are
+    /// built by [Prover::build_aux_trace()](crate::Prover::build_aux_trace) after the prover
+    /// commits to the main trace segment and draws `num_aux_segment_rands` random elements from
+    /// the public coin.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Total width of the main and auxiliary segments is greater than 65535.
+    /// * `aux_segment_width` is zero but `num_aux_segment_rands` is not.
+    /// * `num_aux_segment_rands` is greater than 255.
+    pub fn with_aux_segment(
+        mut self,
+        aux_segment_width: usize,
+        num_aux_segment_rands: usize,
+    ) -> Self {
+        self.info = TraceInfo::new_multi_segment(
+            self.info.main_trace_width(),
+            aux_segment_width,
+            num_aux_segment_rands,
+            self.info.length(),
+            self.info.meta().to_vec(),
+        );
+        self
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -181,6 +244,69 @@ impl<B: StarkField> TraceTable<B> {
         self.trace.update_row(step, state);
     }
 
+    /// Fills all rows in the execution trace by building fragments of the trace in parallel
+    /// (when `concurrent` feature is enabled).
+    ///
+    /// The trace is broken into fragments of `fragment_length` rows each, and every fragment is
+    /// filled by executing the provided closures as follows:
+    /// - `init` closure is used to initialize the first row of the fragment; it receives the
+    ///   index of this row in the trace, and a mutable reference to the row initialized to all
+    ///   zeros. Thus, the values of the first row of every fragment must be computable from the
+    ///   row index alone (e.g., when each fragment covers a set of independent hash cycles).
+    /// - `update` closure is used to populate all subsequent rows of the fragment; it receives
+    ///   three parameters:
+    ///   - index of the last updated row in the trace.
+    ///   - a reference to the last updated row.
+    ///   - a mutable reference to the next row, which initially contains a copy of the last
+    ///     updated row.
+    ///
+    /// # Panics
+    /// Panics if `fragment_length` is smaller than 2, greater than the length of the trace,
+    /// or is not a power of two.
+    pub fn fill_parallel<I, U>(&mut self, fragment_length: usize, init: I, update: U)
+    where
+        I: Fn(usize, &mut [B]) + Send + Sync,
+        U: Fn(usize, &[B], &mut [B]) + Send + Sync,
+    {
+        let width = self.width();
+        self.fragments(fragment_length).for_each(|mut fragment| {
+            let offset = fragment.offset();
+            let mut prev_row = vec![B::ZERO; width];
+            let mut next_row = vec![B::ZERO; width];
+            init(offset, &mut prev_row);
+            fragment.update_row(0, &prev_row);
+
+            for i in 0..fragment.length() - 1 {
+                next_row.copy_from_slice(&prev_row);
+                update(offset + i, &prev_row, &mut next_row);
+                fragment.update_row(i + 1, &next_row);
+                mem::swap(&mut prev_row, &mut next_row);
+            }
+        });
+    }
+
+    /// Fills all rows in the execution trace independently of each other, using multiple
+    /// threads when `concurrent` feature is enabled.
+    ///
+    /// The `fill` closure is executed once for every row of the trace; it receives the index of
+    /// the row, and a mutable reference to the row initialized to all zeros. The contents of the
+    /// row are copied into the trace after the closure returns.
+    pub fn fill_rows_parallel<F>(&mut self, fill: F)
+    where
+        F: Fn(usize, &mut [B]) + Send + Sync,
+    {
+        let width = self.width();
+        let fragment_length = self.parallel_fragment_length();
+        self.fragments(fragment_length).for_each(|mut fragment| {
+            let mut row = vec![B::ZERO; width];
+            for i in 0..fragment.length() {
+                row.fill(B::ZERO);
+                fill(fragment.offset() + i, &mut row);
+                fragment.update_row(i, &row);
+            }
+        });
+    }
+
     // FRAGMENTS
     // --------------------------------------------------------------------------------------------
 
@@ -196,7 +322,7 @@ impl<B: StarkField> TraceTable<B> {
     pub fn fragments(
         &mut self,
         fragment_length: usize,
-    ) -> alloc::vec::IntoIter<TraceTableFragment<B>> {
+    ) -> alloc::vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_iter()
     }
 
@@ -212,13 +338,25 @@ impl<B: StarkField> TraceTable<B> {
     pub fn fragments(
         &mut self,
         fragment_length: usize,
-    ) -> rayon::vec::IntoIter<TraceTableFragment<B>> {
+    ) -> rayon::vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_par_iter()
     }
 
+    /// Returns the length of fragments into which the trace is broken when rows of the trace can
+    /// be filled independently of each other; the trace is split evenly between the available
+    /// threads.
+    fn parallel_fragment_length(&self) -> usize {
+        #[cfg(feature = "concurrent")]
+        let num_fragments = rayon::current_num_threads().next_power_of_two();
+        #[cfg(not(feature = "concurrent"))]
+        let num_fragments = 1;
+
+        cmp::max(self.info.length() / num_fragments, MIN_FRAGMENT_LENGTH)
+    }
+
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
-    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
+    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<'_, B>> {
         assert!(
             fragment_length >= MIN_FRAGMENT_LENGTH,
             "fragment length must be at least {MIN_FRAGMENT_LENGTH}, but was {fragment_length}"
@@ -272,6 +410,22 @@ impl<B: StarkField> TraceTable<B> {
     pub fn read_row_into(&self, step: usize, target: &mut [B]) {
         self.trace.read_row_into(step, target);
     }
+
+    /// Returns an iterator over the columns of this execution trace.
+    ///
+    /// Columns are returned as slices into the underlying storage, and thus, are not copied.
+    pub fn columns(&self) -> ColumnIter<'_, B> {
+        self.trace.columns()
+    }
+
+    /// Returns an iterator over the rows of this execution trace.
+    ///
+    /// Each row is assembled from values of all columns at the corresponding step. To avoid
+    /// allocating a vector per row, [read_row_into()](TraceTable::read_row_into) can be used
+    /// with a reusable buffer instead.
+    pub fn rows(&self) -> RowIter<'_, B> {
+        self.trace.rows()
+    }
 }
 
 // TRACE TRAIT IMPLEMENTATION
@@ -285,9 +439,10 @@ impl<B: StarkField> Trace for TraceTable<B> {
     }
 
     fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
-        let next_row_idx = (row_idx + 1) % self.info.length();
-        self.trace.read_row_into(row_idx, frame.current_mut());
-        self.trace.read_row_into(next_row_idx, frame.next_mut());
+        for idx in 0..frame.num_rows() {
+            let frame_row_idx = (row_idx + frame.offsets()[idx]) % self.info.length();
+            self.trace.read_row_into(frame_row_idx, frame.row_mut(idx));
+        }
     }
 
     fn main_segment(&self) -> &ColMatrix<B> {
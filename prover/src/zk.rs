@@ -0,0 +1,133 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use air::{Air, Assertion};
+use crypto::{Digest, ElementHasher, FieldPrng};
+use math::FieldElement;
+
+use crate::{matrix::ColMatrix, ProverError};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Label of the randomness used to blind the main trace segment.
+pub const MAIN_TRACE_LABEL: u64 = 0;
+
+/// Label of the randomness used to blind the auxiliary trace segment.
+pub const AUX_TRACE_LABEL: u64 = 1;
+
+/// Label of the randomness used to build the composition polynomial randomizer column.
+pub const COMPOSITION_LABEL: u64 = 2;
+
+/// Label of the randomness used to salt FRI layer commitments.
+pub const FRI_LABEL: u64 = 3;
+
+// ZERO-KNOWLEDGE RANDOMNESS
+// ================================================================================================
+
+/// Generator of random values used to blind execution traces and to mask the constraint
+/// composition polynomial in zero-knowledge proofs.
+///
+/// The values are derived from the secret seed returned by [Prover::zk_seed()](
+/// crate::Prover::zk_seed) and a label; using distinct labels for different parts of the
+/// protocol makes the resulting streams of values independent of each other.
+pub struct ZkRandomness<H: ElementHasher> {
+    prng: FieldPrng<H::BaseField, H>,
+}
+
+impl<H: ElementHasher> ZkRandomness<H> {
+    /// Returns a new generator for the specified secret `seed` and `label`.
+    pub fn new(seed: &[u8; 32], label: u64) -> Self {
+        Self {
+            prng: FieldPrng::new(H::merge_with_int(H::hash(seed), label)),
+        }
+    }
+
+    /// Returns the specified number of random elements in the field `E`.
+    pub fn draw_many<E>(&mut self, num_elements: usize) -> Vec<E>
+    where
+        E: FieldElement<BaseField = H::BaseField>,
+    {
+        let elements = self
+            .prng
+            .draw_many(num_elements * E::EXTENSION_DEGREE)
+            .expect("failed to draw random field elements");
+        E::slice_from_base_elements(&elements).to_vec()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the seed from which salts for FRI layer commitments are derived.
+pub fn get_fri_salt_seed<H: ElementHasher>(seed: &[u8; 32]) -> [u8; 32] {
+    H::merge_with_int(H::hash(seed), FRI_LABEL).as_bytes()
+}
+
+/// Overwrites the last `num_rows` rows of the provided matrix with random values.
+pub fn blind_rows<E, H>(matrix: &mut ColMatrix<E>, num_rows: usize, rng: &mut ZkRandomness<H>)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let first_blinded_row = matrix.num_rows() - num_rows;
+    for column in matrix.columns_mut() {
+        column[first_blinded_row..].copy_from_slice(&rng.draw_many::<E>(num_rows));
+    }
+}
+
+/// Makes sure that zero-knowledge proofs can be generated for the specified AIR.
+///
+/// Specifically, this checks that blinded rows of the execution trace are exempt from transition
+/// constraints (including constraints with custom divisors), and that no assertions against the
+/// main trace segment are placed on these rows.
+pub fn validate_air<A: Air>(air: &A) -> Result<(), ProverError> {
+    if air.context().has_lagrange_kernel_aux_column() {
+        return Err(ProverError::UnsupportedZkLagrangeKernel);
+    }
+
+    // transitions from unblinded rows which reference any of the blinded rows via the evaluation
+    // frame must also be exempt
+    let num_blinding_rows = air.context().num_blinding_rows();
+    let min_exemptions = num_blinding_rows + air.context().max_frame_offset();
+    let num_exemptions = air.context().num_transition_exemptions();
+    if num_exemptions < min_exemptions {
+        return Err(ProverError::InsufficientTransitionExemptions(min_exemptions, num_exemptions));
+    }
+
+    // constraints with custom divisors must not be enforced on any of the steps exempt from the
+    // default divisor
+    let trace_length = air.trace_length();
+    let g = air.trace_domain_generator();
+    for divisor in air.context().custom_transition_divisors().values() {
+        for step in trace_length - min_exemptions..trace_length {
+            if divisor.vanishes_at(g.exp((step as u64).into())) {
+                return Err(ProverError::TransitionConstraintOnBlindedRow(step));
+            }
+        }
+    }
+
+    validate_assertions(&air.get_assertions(), air.trace_length(), num_blinding_rows)
+}
+
+/// Makes sure none of the provided assertions is placed against the last `num_blinding_rows`
+/// rows of an execution trace of the specified length.
+pub fn validate_assertions<E: FieldElement>(
+    assertions: &[Assertion<E>],
+    trace_length: usize,
+    num_blinding_rows: usize,
+) -> Result<(), ProverError> {
+    let first_blinded_row = trace_length - num_blinding_rows;
+    for assertion in assertions {
+        let mut last_step = 0;
+        assertion.apply(trace_length, |step, _| last_step = last_step.max(step));
+        if last_step >= first_blinded_row {
+            return Err(ProverError::AssertionOnBlindedRow(last_step));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::FieldElement;
+
+// COMPOSITION POLYNOMIAL
+// ================================================================================================
+
+/// A multivariate polynomial used to combine evaluations of multi-linear polynomials.
+///
+/// The sum-check protocol in this crate proves claims of the form:
+///
+/// sum(g(f<sub>0</sub>(x), ..., f<sub>k-1</sub>(x))) for all x in {0, 1}<sup>n</sup>
+///
+/// where f<sub>i</sub> are multi-linear polynomials and g is a composition polynomial.
+pub trait CompositionPolynomial<E: FieldElement>: Sync {
+    /// Returns the number of inputs of this composition polynomial (i.e., the number of
+    /// multi-linear polynomials it combines).
+    fn num_inputs(&self) -> usize;
+
+    /// Returns the maximum degree of this composition polynomial in any single variable.
+    fn max_degree(&self) -> usize;
+
+    /// Evaluates this composition polynomial at the specified point.
+    ///
+    /// The length of `query` is guaranteed to be equal to [CompositionPolynomial::num_inputs()].
+    fn evaluate(&self, query: &[E]) -> E;
+}
+
+// PRODUCT COMPOSITION
+// ================================================================================================
+
+/// A composition polynomial which computes the product of all of its inputs.
+///
+/// This can be used to prove claims about sums of products of multi-linear polynomials, e.g.,
+/// inner products.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProductComposition {
+    num_inputs: usize,
+}
+
+impl ProductComposition {
+    /// Returns a new product composition polynomial with the specified number of inputs.
+    ///
+    /// # Panics
+    /// Panics if `num_inputs` is zero.
+    pub fn new(num_inputs: usize) -> Self {
+        assert!(num_inputs > 0, "number of inputs must be greater than zero");
+        Self { num_inputs }
+    }
+}
+
+impl<E: FieldElement> CompositionPolynomial<E> for ProductComposition {
+    fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn max_degree(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn evaluate(&self, query: &[E]) -> E {
+        query.iter().fold(E::ONE, |acc, &value| acc * value)
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+use crypto::RandomCoinError;
+
+// PROVER ERROR
+// ================================================================================================
+
+/// Defines errors which can occur during sum-check proof generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SumCheckProverError {
+    /// Attempt to draw a random value from the transcript failed.
+    RandomCoinError(RandomCoinError),
+}
+
+impl fmt::Display for SumCheckProverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RandomCoinError(err) => {
+                write!(f, "failed to draw a random value from the transcript: {err}")
+            },
+        }
+    }
+}
+
+// VERIFIER ERROR
+// ================================================================================================
+
+/// Defines errors which can occur during sum-check proof verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SumCheckVerifierError {
+    /// Attempt to draw a random value from the transcript failed.
+    RandomCoinError(RandomCoinError),
+    /// Number of round proofs does not match the number of variables.
+    NumRoundsMismatch(usize, usize),
+    /// Number of evaluations of a round polynomial does not match the degree of the composition
+    /// polynomial.
+    RoundPolyDegreeMismatch(usize, usize, usize),
+    /// Sum of round polynomial evaluations at 0 and 1 is not equal to the claim for that round.
+    InconsistentRoundSum(usize),
+    /// Number of openings does not match the number of inputs of the composition polynomial.
+    NumOpeningsMismatch(usize, usize),
+    /// Composition polynomial evaluated at the openings does not match the final claim.
+    FinalEvaluationMismatch,
+}
+
+impl fmt::Display for SumCheckVerifierError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RandomCoinError(err) => {
+                write!(f, "failed to draw a random value from the transcript: {err}")
+            }
+            Self::NumRoundsMismatch(expected, actual) => {
+                write!(f, "expected {expected} round proofs, but received {actual}")
+            }
+            Self::RoundPolyDegreeMismatch(round, expected, actual) => write!(f,
+                "expected {expected} evaluations of the round polynomial in round {round}, but received {actual}"
+            ),
+            Self::InconsistentRoundSum(round) => {
+                write!(f, "round polynomial in round {round} is not consistent with the claimed sum")
+            }
+            Self::NumOpeningsMismatch(expected, actual) => {
+                write!(f, "expected {expected} openings, but received {actual}")
+            }
+            Self::FinalEvaluationMismatch => {
+                write!(f, "composition polynomial evaluated at the openings does not match the final claim")
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains multi-linear polynomials and an implementation of the sum-check protocol
+//! over the fields used by the Winterfell STARK prover and verifier.
+//!
+//! The sum-check protocol allows a prover to convince a verifier that the sum of a multivariate
+//! polynomial over the boolean hypercube is equal to a claimed value, while the verifier needs to
+//! evaluate the polynomial at a single random point only. This is a building block for GKR and
+//! LogUp-style arguments, which can be combined with the FRI-based commitments used elsewhere in
+//! Winterfell.
+//!
+//! # Multi-linear polynomials
+//! [MultiLinearPoly] represents a multi-linear polynomial by its evaluations over the boolean
+//! hypercube. It supports evaluation at arbitrary points, as well as fixing (folding) its first
+//! variable to a given value.
+//!
+//! # Proof generation
+//! Sum-check proofs are generated via [sum_check_prove()] for claims of the form:
+//!
+//! sum(g(f<sub>0</sub>(x), ..., f<sub>k-1</sub>(x))) for all x in {0, 1}<sup>n</sup>
+//!
+//! where f<sub>i</sub> are multi-linear polynomials and g is a [CompositionPolynomial]. The
+//! protocol is made non-interactive via a Fiat-Shamir [Transcript](crypto::Transcript).
+//!
+//! # Proof verification
+//! Sum-check proofs are verified via [sum_check_verify()]. On success, the verifier returns a
+//! [FinalOpeningClaim] which specifies evaluations of f<sub>i</sub> at a random point. These
+//! evaluations must be checked by the caller against commitments to f<sub>i</sub>.
+
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+mod composition;
+pub use composition::{CompositionPolynomial, ProductComposition};
+
+mod multilinear;
+pub use multilinear::MultiLinearPoly;
+
+mod prover;
+pub use prover::sum_check_prove;
+
+mod verifier;
+pub use verifier::sum_check_verify;
+
+mod proof;
+pub use proof::{FinalOpeningClaim, RoundProof, SumCheckProof};
+
+mod errors;
+pub use errors::{SumCheckProverError, SumCheckVerifierError};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,192 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::FieldElement;
+
+// MULTI-LINEAR POLYNOMIAL
+// ================================================================================================
+
+/// A multi-linear polynomial in `n` variables represented by its evaluations over the boolean
+/// hypercube {0, 1}<sup>n</sup>.
+///
+/// The evaluations are stored in lexicographic order with the first variable being the most
+/// significant one; that is, the value at index `i` is the evaluation of the polynomial at
+/// (b<sub>0</sub>, ..., b<sub>n-1</sub>) where `i` = sum(b<sub>j</sub> * 2<sup>n-1-j</sup>).
+/// Given such evaluations, the polynomial is the unique multi-linear extension of the function
+/// defined by them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiLinearPoly<E: FieldElement> {
+    num_variables: usize,
+    evaluations: Vec<E>,
+}
+
+impl<E: FieldElement> MultiLinearPoly<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new multi-linear polynomial defined by the provided evaluations over the boolean
+    /// hypercube.
+    ///
+    /// # Panics
+    /// Panics if the number of evaluations is not a power of two.
+    pub fn from_evaluations(evaluations: Vec<E>) -> Self {
+        assert!(
+            evaluations.len().is_power_of_two(),
+            "number of evaluations must be a power of 2, but was {}",
+            evaluations.len()
+        );
+
+        Self {
+            num_variables: evaluations.len().ilog2() as usize,
+            evaluations,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of variables of this polynomial.
+    pub fn num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    /// Returns the number of evaluations of this polynomial over the boolean hypercube.
+    pub fn num_evaluations(&self) -> usize {
+        self.evaluations.len()
+    }
+
+    /// Returns evaluations of this polynomial over the boolean hypercube.
+    pub fn evaluations(&self) -> &[E] {
+        &self.evaluations
+    }
+
+    // EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the evaluation of this polynomial at the specified point.
+    ///
+    /// # Panics
+    /// Panics if the number of coordinates of `point` is not equal to the number of variables
+    /// of this polynomial.
+    pub fn evaluate(&self, point: &[E]) -> E {
+        assert_eq!(
+            point.len(),
+            self.num_variables,
+            "number of point coordinates must be equal to the number of variables"
+        );
+
+        let mut poly = self.clone();
+        for &r in point {
+            poly.fold(r);
+        }
+        poly.evaluations[0]
+    }
+
+    /// Returns the sum of evaluations of this polynomial over the boolean hypercube.
+    pub fn sum(&self) -> E {
+        self.evaluations.iter().fold(E::ZERO, |acc, &value| acc + value)
+    }
+
+    // PARTIAL EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new polynomial in `n - 1` variables resulting from fixing the first variable of
+    /// this polynomial to `r`.
+    ///
+    /// # Panics
+    /// Panics if this polynomial has no variables.
+    pub fn fix_variable(&self, r: E) -> Self {
+        let mut result = self.clone();
+        result.fold(r);
+        result
+    }
+
+    /// Fixes the first variable of this polynomial to `r` in place.
+    ///
+    /// This is the in-place counterpart of [MultiLinearPoly::fix_variable()]: after folding, the
+    /// number of variables is reduced by one and the number of evaluations is halved. For each
+    /// index `i` in the lower half of the hypercube, the new evaluation is computed as
+    /// v<sub>i</sub> + r * (v<sub>i + half</sub> - v<sub>i</sub>).
+    ///
+    /// # Panics
+    /// Panics if this polynomial has no variables.
+    pub fn fold(&mut self, r: E) {
+        assert!(self.num_variables > 0, "cannot fold a polynomial with no variables");
+
+        let half = self.evaluations.len() / 2;
+        let (low, high) = self.evaluations.split_at_mut(half);
+        for (low, &high) in low.iter_mut().zip(high.iter()) {
+            *low += r * (high - *low);
+        }
+
+        self.evaluations.truncate(half);
+        self.num_variables -= 1;
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use math::{fields::f128::BaseElement, FieldElement};
+    use rand_utils::{rand_value, rand_vector};
+
+    use super::MultiLinearPoly;
+
+    #[test]
+    fn evaluate_over_hypercube() {
+        let evaluations: Vec<BaseElement> = rand_vector(8);
+        let poly = MultiLinearPoly::from_evaluations(evaluations.clone());
+        assert_eq!(3, poly.num_variables());
+
+        for (i, &expected) in evaluations.iter().enumerate() {
+            let point = (0..3)
+                .map(|j| BaseElement::from(((i >> (2 - j)) & 1) as u32))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, poly.evaluate(&point));
+        }
+    }
+
+    #[test]
+    fn evaluate_two_variables() {
+        // f(x0, x1) = v0 (1 - x0)(1 - x1) + v1 (1 - x0) x1 + v2 x0 (1 - x1) + v3 x0 x1
+        let v: Vec<BaseElement> = rand_vector(4);
+        let poly = MultiLinearPoly::from_evaluations(v.clone());
+
+        let x0: BaseElement = rand_value();
+        let x1: BaseElement = rand_value();
+        let one = BaseElement::ONE;
+        let expected = v[0] * (one - x0) * (one - x1)
+            + v[1] * (one - x0) * x1
+            + v[2] * x0 * (one - x1)
+            + v[3] * x0 * x1;
+        assert_eq!(expected, poly.evaluate(&[x0, x1]));
+    }
+
+    #[test]
+    fn fix_variable() {
+        let poly = MultiLinearPoly::from_evaluations(rand_vector::<BaseElement>(16));
+        let point: Vec<BaseElement> = rand_vector(4);
+
+        let fixed = poly.fix_variable(point[0]);
+        assert_eq!(3, fixed.num_variables());
+        assert_eq!(poly.evaluate(&point), fixed.evaluate(&point[1..]));
+
+        let mut folded = poly.clone();
+        folded.fold(point[0]);
+        assert_eq!(fixed, folded);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_evaluations_not_power_of_two() {
+        MultiLinearPoly::from_evaluations(rand_vector::<BaseElement>(6));
+    }
+}
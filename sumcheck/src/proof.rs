@@ -0,0 +1,134 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::FieldElement;
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// SUM-CHECK PROOF
+// ================================================================================================
+
+/// A proof generated by the sum-check prover.
+///
+/// The proof consists of a round polynomial for each variable of the summed polynomials, and of
+/// evaluations of the summed multi-linear polynomials at the point defined by the randomness
+/// drawn during the protocol. The verifier checks the latter against the claimed sum, but it is
+/// up to the caller to check that these evaluations are consistent with commitments to the
+/// multi-linear polynomials (e.g., via a polynomial commitment scheme).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SumCheckProof<E: FieldElement> {
+    round_proofs: Vec<RoundProof<E>>,
+    openings: Vec<E>,
+}
+
+impl<E: FieldElement> SumCheckProof<E> {
+    /// Returns a new sum-check proof instantiated from the specified round proofs and openings.
+    pub fn new(round_proofs: Vec<RoundProof<E>>, openings: Vec<E>) -> Self {
+        Self { round_proofs, openings }
+    }
+
+    /// Returns round proofs of this proof; there is one round proof per variable.
+    pub fn round_proofs(&self) -> &[RoundProof<E>] {
+        &self.round_proofs
+    }
+
+    /// Returns evaluations of the summed multi-linear polynomials at the point defined by the
+    /// randomness drawn during the protocol.
+    pub fn openings(&self) -> &[E] {
+        &self.openings
+    }
+}
+
+impl<E: FieldElement> Serializable for SumCheckProof<E> {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.round_proofs.write_into(target);
+        self.openings.write_into(target);
+    }
+}
+
+impl<E: FieldElement> Deserializable for SumCheckProof<E> {
+    /// Reads a sum-check proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let round_proofs = Vec::<RoundProof<E>>::read_from(source)?;
+        let openings = Vec::<E>::read_from(source)?;
+        Ok(Self { round_proofs, openings })
+    }
+}
+
+// ROUND PROOF
+// ================================================================================================
+
+/// A round polynomial of the sum-check protocol.
+///
+/// The round polynomial is a univariate polynomial of degree `d` represented by its evaluations
+/// at points 0, 1, ..., `d`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundProof<E: FieldElement> {
+    evaluations: Vec<E>,
+}
+
+impl<E: FieldElement> RoundProof<E> {
+    /// Returns a new round proof instantiated from the evaluations of the round polynomial at
+    /// points 0, 1, ..., `d`.
+    pub fn new(evaluations: Vec<E>) -> Self {
+        Self { evaluations }
+    }
+
+    /// Returns evaluations of the round polynomial at points 0, 1, ..., `d`.
+    pub fn evaluations(&self) -> &[E] {
+        &self.evaluations
+    }
+}
+
+impl<E: FieldElement> Serializable for RoundProof<E> {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.evaluations.write_into(target);
+    }
+}
+
+impl<E: FieldElement> Deserializable for RoundProof<E> {
+    /// Reads a round proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid round proof could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let evaluations = Vec::<E>::read_from(source)?;
+        Ok(Self { evaluations })
+    }
+}
+
+// FINAL OPENING CLAIM
+// ================================================================================================
+
+/// A claim about evaluations of multi-linear polynomials at a single point, which remains to be
+/// checked after the sum-check verifier accepts a proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinalOpeningClaim<E: FieldElement> {
+    eval_point: Vec<E>,
+    openings: Vec<E>,
+}
+
+impl<E: FieldElement> FinalOpeningClaim<E> {
+    /// Returns a new final opening claim for the specified point and evaluations.
+    pub fn new(eval_point: Vec<E>, openings: Vec<E>) -> Self {
+        Self { eval_point, openings }
+    }
+
+    /// Returns the point at which the multi-linear polynomials are claimed to be evaluated.
+    pub fn eval_point(&self) -> &[E] {
+        &self.eval_point
+    }
+
+    /// Returns the claimed evaluations of the multi-linear polynomials at the evaluation point.
+    pub fn openings(&self) -> &[E] {
+        &self.openings
+    }
+}
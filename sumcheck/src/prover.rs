@@ -0,0 +1,113 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{ElementHasher, Transcript, TranscriptLabel};
+use math::FieldElement;
+
+use crate::{
+    CompositionPolynomial, MultiLinearPoly, RoundProof, SumCheckProof, SumCheckProverError,
+};
+
+// SUM-CHECK PROVER
+// ================================================================================================
+
+/// Generates a sum-check proof for the sum of `composition` applied to the multi-linear
+/// polynomials `mls` over the boolean hypercube.
+///
+/// In each round, the prover sends evaluations of the round polynomial at points 0, 1, ..., `d`
+/// (where `d` is the maximum degree of the composition polynomial) by absorbing them into the
+/// `transcript`, and then squeezes a random challenge from the `transcript` which is used to fix
+/// the first variable of all multi-linear polynomials. After all variables are fixed, the
+/// evaluations of the multi-linear polynomials at the resulting point are included into the
+/// proof.
+///
+/// The claimed sum itself is not absorbed into the transcript; if needed, this should be done by
+/// the caller before invoking this function.
+///
+/// # Errors
+/// Returns an error if a random challenge could not be squeezed from the transcript.
+///
+/// # Panics
+/// Panics if:
+/// * The number of multi-linear polynomials is not equal to the number of inputs of the
+///   composition polynomial.
+/// * The multi-linear polynomials do not all have the same number of variables.
+pub fn sum_check_prove<E, P, T>(
+    composition: &P,
+    mut mls: Vec<MultiLinearPoly<E>>,
+    transcript: &mut T,
+) -> Result<SumCheckProof<E>, SumCheckProverError>
+where
+    E: FieldElement,
+    P: CompositionPolynomial<E>,
+    T: Transcript<BaseField = E::BaseField>,
+{
+    assert_eq!(
+        mls.len(),
+        composition.num_inputs(),
+        "number of multi-linear polynomials must be equal to the number of composition inputs"
+    );
+    let num_variables = mls[0].num_variables();
+    assert!(
+        mls.iter().all(|ml| ml.num_variables() == num_variables),
+        "all multi-linear polynomials must have the same number of variables"
+    );
+
+    let mut round_proofs = Vec::with_capacity(num_variables);
+    for _ in 0..num_variables {
+        let evaluations = compute_round_poly(composition, &mls);
+        transcript.absorb(TranscriptLabel::SumCheckRound, T::Hasher::hash_elements(&evaluations));
+        round_proofs.push(RoundProof::new(evaluations));
+
+        let r: E = transcript
+            .squeeze(TranscriptLabel::SumCheckRound)
+            .map_err(SumCheckProverError::RandomCoinError)?;
+        mls.iter_mut().for_each(|ml| ml.fold(r));
+    }
+
+    let openings = mls.iter().map(|ml| ml.evaluations()[0]).collect();
+    Ok(SumCheckProof::new(round_proofs, openings))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes evaluations of the round polynomial at points 0, 1, ..., `d`.
+///
+/// The round polynomial is the sum of the composition polynomial over the remaining boolean
+/// hypercube with the first variable left free. Since all multi-linear polynomials are linear
+/// in the first variable, their values at t + 1 are obtained from their values at t by adding
+/// the difference between the upper and the lower halves of their evaluations.
+fn compute_round_poly<E, P>(composition: &P, mls: &[MultiLinearPoly<E>]) -> Vec<E>
+where
+    E: FieldElement,
+    P: CompositionPolynomial<E>,
+{
+    // round polynomials are always sent as evaluations at 0 and 1 at minimum, so that the
+    // verifier can check them against the claimed sum
+    let degree = composition.max_degree().max(1);
+    let half = mls[0].num_evaluations() / 2;
+
+    let mut result = vec![E::ZERO; degree + 1];
+    let mut query = vec![E::ZERO; mls.len()];
+    let mut deltas = vec![E::ZERO; mls.len()];
+    for i in 0..half {
+        for ((query, delta), ml) in query.iter_mut().zip(deltas.iter_mut()).zip(mls) {
+            let evaluations = ml.evaluations();
+            *query = evaluations[i];
+            *delta = evaluations[i + half] - evaluations[i];
+        }
+
+        result[0] += composition.evaluate(&query);
+        for value in result.iter_mut().skip(1) {
+            query.iter_mut().zip(deltas.iter()).for_each(|(q, &d)| *q += d);
+            *value += composition.evaluate(&query);
+        }
+    }
+
+    result
+}
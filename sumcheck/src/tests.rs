@@ -0,0 +1,111 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
+use math::{fields::f128::BaseElement, FieldElement};
+use rand_utils::rand_vector;
+use utils::{Deserializable, Serializable, SliceReader};
+
+use super::{
+    sum_check_prove, sum_check_verify, MultiLinearPoly, ProductComposition, SumCheckProof,
+    SumCheckVerifierError,
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+type Coin = DefaultRandomCoin<Blake3>;
+
+const NUM_VARIABLES: usize = 4;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn sum_check_prove_verify() {
+    let (mls, claim) = build_inner_product_claim();
+    let composition = ProductComposition::new(2);
+
+    let mut transcript = Coin::new(&[]);
+    let proof = sum_check_prove(&composition, mls.clone(), &mut transcript).unwrap();
+    assert_eq!(NUM_VARIABLES, proof.round_proofs().len());
+
+    let mut transcript = Coin::new(&[]);
+    let opening_claim =
+        sum_check_verify(claim, NUM_VARIABLES, &composition, &proof, &mut transcript).unwrap();
+
+    // openings must be evaluations of the multi-linear polynomials at the evaluation point
+    let expected = mls.iter().map(|ml| ml.evaluate(opening_claim.eval_point())).collect::<Vec<_>>();
+    assert_eq!(expected, opening_claim.openings());
+}
+
+#[test]
+fn sum_check_verify_wrong_claim() {
+    let (mls, claim) = build_inner_product_claim();
+    let composition = ProductComposition::new(2);
+
+    let mut transcript = Coin::new(&[]);
+    let proof = sum_check_prove(&composition, mls, &mut transcript).unwrap();
+
+    let mut transcript = Coin::new(&[]);
+    let result = sum_check_verify(
+        claim + BaseElement::ONE,
+        NUM_VARIABLES,
+        &composition,
+        &proof,
+        &mut transcript,
+    );
+    assert_eq!(Err(SumCheckVerifierError::InconsistentRoundSum(0)), result);
+}
+
+#[test]
+fn sum_check_verify_wrong_openings() {
+    let (mls, claim) = build_inner_product_claim();
+    let composition = ProductComposition::new(2);
+
+    let mut transcript = Coin::new(&[]);
+    let proof = sum_check_prove(&composition, mls, &mut transcript).unwrap();
+
+    let mut openings = proof.openings().to_vec();
+    openings[0] += BaseElement::ONE;
+    let proof = SumCheckProof::new(proof.round_proofs().to_vec(), openings);
+
+    let mut transcript = Coin::new(&[]);
+    let result = sum_check_verify(claim, NUM_VARIABLES, &composition, &proof, &mut transcript);
+    assert_eq!(Err(SumCheckVerifierError::FinalEvaluationMismatch), result);
+}
+
+#[test]
+fn sum_check_proof_serialization() {
+    let (mls, _) = build_inner_product_claim();
+    let composition = ProductComposition::new(2);
+
+    let mut transcript = Coin::new(&[]);
+    let proof = sum_check_prove(&composition, mls, &mut transcript).unwrap();
+
+    let bytes = proof.to_bytes();
+    let mut reader = SliceReader::new(&bytes);
+    let parsed = SumCheckProof::<BaseElement>::read_from(&mut reader).unwrap();
+    assert_eq!(proof, parsed);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds two random multi-linear polynomials and computes the sum of their products over the
+/// boolean hypercube.
+fn build_inner_product_claim() -> (Vec<MultiLinearPoly<BaseElement>>, BaseElement) {
+    let n = 1 << NUM_VARIABLES;
+    let a = MultiLinearPoly::from_evaluations(rand_vector::<BaseElement>(n));
+    let b = MultiLinearPoly::from_evaluations(rand_vector::<BaseElement>(n));
+
+    let claim = a
+        .evaluations()
+        .iter()
+        .zip(b.evaluations())
+        .fold(BaseElement::ZERO, |acc, (&a, &b)| acc + a * b);
+
+    (vec![a, b], claim)
+}
@@ -0,0 +1,91 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{ElementHasher, Transcript, TranscriptLabel};
+use math::{polynom, FieldElement};
+
+use crate::{CompositionPolynomial, FinalOpeningClaim, SumCheckProof, SumCheckVerifierError};
+
+// SUM-CHECK VERIFIER
+// ================================================================================================
+
+/// Verifies a sum-check proof for the claim that the sum of `composition` applied to a set of
+/// multi-linear polynomials in `num_variables` variables over the boolean hypercube is equal to
+/// `claim`.
+///
+/// The `transcript` must be in the same state as the prover's transcript was at the start of
+/// proof generation. On success, returns a [FinalOpeningClaim] which specifies the evaluations
+/// of the multi-linear polynomials at a random point. The caller is responsible for checking
+/// these evaluations against commitments to the multi-linear polynomials.
+///
+/// # Errors
+/// Returns an error if:
+/// * The number of round proofs is not equal to `num_variables`.
+/// * A round polynomial is not of the expected degree or is inconsistent with the claim for its
+///   round.
+/// * The openings are inconsistent with the final claim.
+/// * A random challenge could not be squeezed from the transcript.
+pub fn sum_check_verify<E, P, T>(
+    claim: E,
+    num_variables: usize,
+    composition: &P,
+    proof: &SumCheckProof<E>,
+    transcript: &mut T,
+) -> Result<FinalOpeningClaim<E>, SumCheckVerifierError>
+where
+    E: FieldElement,
+    P: CompositionPolynomial<E>,
+    T: Transcript<BaseField = E::BaseField>,
+{
+    let round_proofs = proof.round_proofs();
+    if round_proofs.len() != num_variables {
+        return Err(SumCheckVerifierError::NumRoundsMismatch(num_variables, round_proofs.len()));
+    }
+
+    // round polynomials are evaluated at points 0, 1, ..., d; barycentric weights for these
+    // points are the same for all rounds
+    let degree = composition.max_degree().max(1);
+    let xs = (0..=degree as u32).map(E::from).collect::<Vec<_>>();
+    let weights = polynom::get_barycentric_weights(&xs);
+
+    let mut claim = claim;
+    let mut eval_point = Vec::with_capacity(num_variables);
+    for (round, round_proof) in round_proofs.iter().enumerate() {
+        let evaluations = round_proof.evaluations();
+        if evaluations.len() != degree + 1 {
+            return Err(SumCheckVerifierError::RoundPolyDegreeMismatch(
+                round,
+                degree + 1,
+                evaluations.len(),
+            ));
+        }
+        if evaluations[0] + evaluations[1] != claim {
+            return Err(SumCheckVerifierError::InconsistentRoundSum(round));
+        }
+
+        transcript.absorb(TranscriptLabel::SumCheckRound, T::Hasher::hash_elements(evaluations));
+        let r: E = transcript
+            .squeeze(TranscriptLabel::SumCheckRound)
+            .map_err(SumCheckVerifierError::RandomCoinError)?;
+
+        claim = polynom::eval_barycentric(&xs, evaluations, &weights, r);
+        eval_point.push(r);
+    }
+
+    let openings = proof.openings();
+    if openings.len() != composition.num_inputs() {
+        return Err(SumCheckVerifierError::NumOpeningsMismatch(
+            composition.num_inputs(),
+            openings.len(),
+        ));
+    }
+    if composition.evaluate(openings) != claim {
+        return Err(SumCheckVerifierError::FinalEvaluationMismatch);
+    }
+
+    Ok(FinalOpeningClaim::new(eval_point, openings.to_vec()))
+}
@@ -0,0 +1,184 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Functions for encoding byte sequences as text.
+//!
+//! Serialized values often need to be passed around as text (e.g., in JSON-RPC requests or as
+//! command-line arguments). This module provides hex and base64 encodings for such cases.
+//! Decoding functions validate their inputs and return a [DeserializationError] describing the
+//! first problem found, rather than silently ignoring malformed input.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::DeserializationError;
+
+// CONSTANTS
+// ================================================================================================
+
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const BASE64_PADDING: u8 = b'=';
+
+// HEX
+// ================================================================================================
+
+/// Returns a lowercase hex encoding of the specified `bytes`, without a `0x` prefix.
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        result.push(HEX_ALPHABET[(byte >> 4) as usize] as char);
+        result.push(HEX_ALPHABET[(byte & 0xf) as usize] as char);
+    }
+    result
+}
+
+/// Returns bytes decoded from the specified hex string.
+///
+/// The string may optionally start with a `0x` prefix, and both lowercase and uppercase digits
+/// are accepted.
+///
+/// # Errors
+/// Returns an error if the string contains an odd number of digits, or if any of its characters
+/// is not a hex digit.
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, DeserializationError> {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+    if digits.len() % 2 != 0 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "hex string must contain an even number of digits, but contained {}",
+            digits.len()
+        )));
+    }
+
+    let prefix_len = hex.len() - digits.len();
+    let mut result = Vec::with_capacity(digits.len() / 2);
+    for (i, pair) in digits.chunks(2).enumerate() {
+        let high = decode_hex_digit(pair[0], prefix_len + 2 * i)?;
+        let low = decode_hex_digit(pair[1], prefix_len + 2 * i + 1)?;
+        result.push((high << 4) | low);
+    }
+
+    Ok(result)
+}
+
+// BASE64
+// ================================================================================================
+
+/// Returns a base64 encoding of the specified `bytes` using the standard alphabet and padding
+/// (as defined in RFC 4648).
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut block = 0u32;
+        for (i, &byte) in chunk.iter().enumerate() {
+            block |= (byte as u32) << (16 - 8 * i);
+        }
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (block >> (18 - 6 * i)) & 0x3f;
+                result.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                result.push(BASE64_PADDING as char);
+            }
+        }
+    }
+    result
+}
+
+/// Returns bytes decoded from the specified base64 string.
+///
+/// The string must use the standard alphabet (as defined in RFC 4648); trailing padding is
+/// optional, but if present, it must be complete.
+///
+/// # Errors
+/// Returns an error if:
+/// * The string contains a character which is not in the base64 alphabet, or padding anywhere
+///   other than at its end.
+/// * The length of the string is not valid for a base64 encoding.
+/// * Unused bits of the last character are not zero, i.e., the encoding is not canonical.
+pub fn from_base64(base64: &str) -> Result<Vec<u8>, DeserializationError> {
+    let input = base64.as_bytes();
+    let data = match input.iter().position(|&c| c == BASE64_PADDING) {
+        Some(pos) => {
+            let padding = &input[pos..];
+            if padding.len() > 2 || padding.iter().any(|&c| c != BASE64_PADDING) {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "base64 padding at position {pos} is not at the end of the string"
+                )));
+            }
+            if input.len() % 4 != 0 {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "padded base64 string must have length divisible by 4, but had length {}",
+                    input.len()
+                )));
+            }
+            &input[..pos]
+        },
+        None => input,
+    };
+
+    if data.len() % 4 == 1 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "base64 string with {} characters of data is truncated",
+            data.len()
+        )));
+    }
+
+    let mut result = Vec::with_capacity(data.len() * 3 / 4);
+    for (chunk_idx, chunk) in data.chunks(4).enumerate() {
+        let mut block = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            block |= decode_base64_char(c, chunk_idx * 4 + i)? << (18 - 6 * i);
+        }
+
+        // a chunk of n characters encodes n - 1 bytes; the remaining bits must be zero
+        let num_bytes = chunk.len() - 1;
+        if block & ((1 << (24 - 8 * num_bytes)) - 1) != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "base64 string has non-zero trailing bits".to_string(),
+            ));
+        }
+        for i in 0..num_bytes {
+            result.push((block >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    Ok(result)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn decode_hex_digit(digit: u8, position: usize) -> Result<u8, DeserializationError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(DeserializationError::InvalidValue(format!(
+            "invalid hex digit {:?} at position {position}",
+            digit as char
+        ))),
+    }
+}
+
+fn decode_base64_char(c: u8, position: usize) -> Result<u32, DeserializationError> {
+    match c {
+        b'A'..=b'Z' => Ok((c - b'A') as u32),
+        b'a'..=b'z' => Ok((c - b'a' + 26) as u32),
+        b'0'..=b'9' => Ok((c - b'0' + 52) as u32),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => Err(DeserializationError::InvalidValue(format!(
+            "invalid base64 character {:?} at position {position}",
+            c as char
+        ))),
+    }
+}
@@ -18,6 +18,8 @@ pub enum DeserializationError {
     UnexpectedEOF,
     /// Deserialization has finished but not all bytes have been consumed.
     UnconsumedBytes,
+    /// Bytes in the input were serialized using a format version which is not supported.
+    UnsupportedVersion(u8),
     /// An unknown error has occurred.
     UnknownError(String),
 }
@@ -28,6 +30,7 @@ impl fmt::Display for DeserializationError {
             Self::InvalidValue(err_msg) => write!(f, "{err_msg}"),
             Self::UnexpectedEOF => write!(f, "unexpected EOF"),
             Self::UnconsumedBytes => write!(f, "not all bytes were consumed"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported format version {version}"),
             Self::UnknownError(err_msg) => write!(f, "unknown error: {err_msg}"),
         }
     }
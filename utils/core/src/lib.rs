@@ -13,6 +13,7 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod encoding;
 pub mod iterators;
 
 use alloc::vec::Vec;
@@ -336,105 +336,42 @@ impl<'a> ReadAdapter<'a> {
 
     /// Takes the next `N` bytes from the input as an array, returning an error if the operation fails
     fn read_exact<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
-        let buf = self.buffer();
         let mut output = [0; N];
-        match buf.len() {
-            0 => {
-                let buf = self.non_empty_reader_buffer_mut()?;
-                if buf.len() < N {
-                    return Err(DeserializationError::UnexpectedEOF);
-                }
-                // SAFETY: This copy is guaranteed to be safe, as we have validated above
-                // that `buf` has at least N bytes, and `output` is defined to be exactly
-                // N bytes.
-                unsafe {
-                    core::ptr::copy_nonoverlapping(buf.as_ptr(), output.as_mut_ptr(), N);
-                }
+
+        // if there is nothing in the local buffer, try to take the bytes from the reader buffer
+        // directly
+        if self.buffer().is_empty() {
+            let buf = self.non_empty_reader_buffer_mut()?;
+            if buf.len() >= N {
+                output.copy_from_slice(&buf[..N]);
                 self.reader.get_mut().consume(N);
-            },
-            n if n >= N => {
-                // SAFETY: This copy is guaranteed to be safe, as we have validated above
-                // that `buf` has at least N bytes, and `output` is defined to be exactly
-                // N bytes.
-                unsafe {
-                    core::ptr::copy_nonoverlapping(buf.as_ptr(), output.as_mut_ptr(), N);
-                }
-                self.pos += N;
-            },
-            n => {
-                // We have to fill from both the local and reader buffers
-                self.non_empty_reader_buffer_mut()?;
-                let reader_buf = self.reader_buffer();
-                match reader_buf.len() {
-                    #[cfg(debug_assertions)]
-                    0 => unreachable!("expected reader buffer to be non-empty to reach here"),
-                    #[cfg(not(debug_assertions))]
-                    // SAFETY: The call to `non_empty_reader_buffer_mut` will return an error
-                    // if `reader_buffer` is non-empty, as a result is is impossible to reach
-                    // here with a length of 0.
-                    0 => unsafe { core::hint::unreachable_unchecked() },
-                    // We got enough in one request
-                    m if m + n >= N => {
-                        let needed = N - n;
-                        let dst = output.as_mut_ptr();
-                        // SAFETY: Both copies are guaranteed to be in-bounds:
-                        //
-                        // * `output` is defined to be exactly N bytes
-                        // * `buf` is guaranteed to be < N bytes
-                        // * `reader_buf` is guaranteed to have the remaining bytes needed,
-                        // and we only copy exactly that many bytes
-                        unsafe {
-                            core::ptr::copy_nonoverlapping(self.buffer().as_ptr(), dst, n);
-                            core::ptr::copy_nonoverlapping(reader_buf.as_ptr(), dst.add(n), needed);
-                            drop(reader_buf);
-                        }
-                        self.pos += n;
-                        self.reader.get_mut().consume(needed);
-                    },
-                    // We didn't get enough, but haven't necessarily reached eof yet, so fall back
-                    // to filling `self.buf`
-                    m => {
-                        let needed = N - (m + n);
-                        drop(reader_buf);
-                        self.buffer_at_least(needed)?;
-                        debug_assert!(self.buffer().len() >= N, "expected buffer to be at least {N} bytes after call to buffer_at_least");
-                        // SAFETY: This is guaranteed to be an in-bounds copy
-                        unsafe {
-                            core::ptr::copy_nonoverlapping(
-                                self.buffer().as_ptr(),
-                                output.as_mut_ptr(),
-                                N,
-                            );
-                        }
-                        self.pos += N;
-                        return Ok(output);
-                    },
-                }
-            },
+                return Ok(output);
+            }
         }
 
+        // otherwise, the bytes may be split between the local buffer, the reader buffer, and
+        // the data which has not been read from the underlying reader yet; so, we accumulate
+        // them in the local buffer first
+        self.buffer_at_least(N)?;
+        output.copy_from_slice(&self.buffer()[..N]);
+        self.pos += N;
+
         // Check if we should reset our internal buffer
-        if self.buffer().is_empty() && self.pos > 0 {
-            unsafe {
-                self.buf.set_len(0);
-            }
+        if self.buffer().is_empty() {
+            self.buf.clear();
+            self.pos = 0;
         }
 
         Ok(output)
     }
 
-    /// Fill `self.buf` with `count` bytes
+    /// Fill `self.buf` until at least `count` unread bytes are available in it
     ///
     /// This should only be called when we can't read from the reader directly
-    fn buffer_at_least(&mut self, mut count: usize) -> Result<(), DeserializationError> {
+    fn buffer_at_least(&mut self, count: usize) -> Result<(), DeserializationError> {
         // Read until we have at least `count` bytes, or until we reach end-of-file,
         // which ever comes first.
-        loop {
-            // If we have succesfully read `count` bytes, we're done
-            if count == 0 || self.buf.len() >= count {
-                break Ok(());
-            }
-
+        while self.buffer().len() < count {
             // This operation will return an error if the underlying reader hits EOF
             self.non_empty_reader_buffer_mut()?;
 
@@ -447,8 +384,8 @@ impl<'a> ReadAdapter<'a> {
             let consumed = buf.len();
             self.buf.extend_from_slice(buf);
             reader.consume(consumed);
-            count = count.saturating_sub(consumed);
         }
+        Ok(())
     }
 }
 
@@ -504,7 +441,9 @@ impl<'a> ByteReader for ReadAdapter<'a> {
         // this will return an error if we hit EOF first
         self.buffer_at_least(len)?;
 
-        Ok(&self.buffer()[0..len])
+        let start = self.pos;
+        self.pos += len;
+        Ok(&self.buf[start..start + len])
     }
 
     #[inline]
@@ -680,7 +619,7 @@ impl<'a> ByteReader for SliceReader<'a> {
 
 #[cfg(all(test, feature = "std"))]
 mod tests {
-    use std::io::Cursor;
+    use std::{io::Cursor, vec::Vec};
 
     use super::*;
     use crate::ByteWriter;
@@ -726,6 +665,34 @@ mod tests {
         assert_eq!(adapter.read_u8(), Err(DeserializationError::UnexpectedEOF));
     }
 
+    #[test]
+    fn read_adapter_chunked() {
+        /// A reader which returns at most 3 bytes on every read.
+        struct ChunkedReader(Vec<u8>);
+
+        impl std::io::Read for ChunkedReader {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let len = buf.len().min(3).min(self.0.len());
+                buf[..len].copy_from_slice(&self.0[..len]);
+                self.0.drain(..len);
+                Ok(len)
+            }
+        }
+
+        let bytes = (0..64).collect::<Vec<u8>>();
+        let mut reader = ChunkedReader(bytes.clone());
+        let mut adapter = ReadAdapter::new(&mut reader);
+
+        // reads which span multiple chunks return consecutive bytes of the input
+        assert_eq!(adapter.read_u8(), Ok(0));
+        assert_eq!(adapter.read_array(), Ok([1, 2, 3, 4, 5, 6, 7, 8]));
+        assert_eq!(adapter.read_slice(10), Ok(&bytes[9..19]));
+        assert_eq!(adapter.read_u32(), Ok(u32::from_le_bytes([19, 20, 21, 22])));
+        assert_eq!(adapter.read_vec(40), Ok(bytes[23..63].to_vec()));
+        assert_eq!(adapter.read_u8(), Ok(63));
+        assert!(!adapter.has_more_bytes());
+    }
+
     #[test]
     fn read_adapter_roundtrip() {
         const VALUE: usize = 2048;
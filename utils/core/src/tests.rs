@@ -3,11 +3,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
+use alloc::{string::ToString, vec::Vec};
 
-use proptest::prelude::{any, proptest};
+use proptest::{
+    collection::vec,
+    prelude::{any, proptest},
+};
 
-use super::{ByteReader, ByteWriter, Serializable, SliceReader};
+use super::{
+    encoding::{from_base64, from_hex, to_base64, to_hex},
+    ByteReader, ByteWriter, DeserializationError, Serializable, SliceReader,
+};
 
 // SLICE READER TESTS
 // ================================================================================================
@@ -146,6 +152,56 @@ fn write_serializable_array_batch() {
     }
 }
 
+// ENCODING TESTS
+// ================================================================================================
+
+#[test]
+fn hex_encoding() {
+    assert_eq!("", to_hex(&[]));
+    assert_eq!("00ff1a", to_hex(&[0, 255, 26]));
+
+    assert_eq!(vec![0, 255, 26], from_hex("00ff1a").unwrap());
+    assert_eq!(vec![0, 255, 26], from_hex("0x00FF1a").unwrap());
+    assert_eq!(Vec::<u8>::new(), from_hex("0x").unwrap());
+
+    assert!(matches!(from_hex("0x0"), Err(DeserializationError::InvalidValue(_))));
+    assert_eq!(
+        Err(DeserializationError::InvalidValue(
+            "invalid hex digit 'g' at position 3".to_string()
+        )),
+        from_hex("0x0g")
+    );
+}
+
+#[test]
+fn base64_encoding() {
+    // test vectors from RFC 4648
+    let vectors = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+    for (bytes, encoded) in vectors {
+        assert_eq!(encoded, to_base64(bytes.as_bytes()));
+        assert_eq!(bytes.as_bytes(), from_base64(encoded).unwrap());
+        assert_eq!(bytes.as_bytes(), from_base64(encoded.trim_end_matches('=')).unwrap());
+    }
+    assert_eq!(vec![0xfb, 0xff], from_base64("+/8=").unwrap());
+
+    // truncated input, incomplete or misplaced padding, invalid characters, and non-zero trailing
+    // bits are rejected
+    for invalid in ["Z", "Zm9vY", "Zg=", "Zg===", "Z===", "Zg==Zg==", "Zm9v=", "Zm-v", "Zh=="] {
+        assert!(
+            matches!(from_base64(invalid), Err(DeserializationError::InvalidValue(_))),
+            "{invalid} should be rejected"
+        );
+    }
+}
+
 // UTILS - RANDOMIZED - UINT SERIALIZATION AND DESERIALIZATION
 // ================================================================================================
 proptest! {
@@ -157,4 +213,10 @@ proptest! {
         let mut reader = SliceReader::new(&target);
         assert_eq!(a, reader.read_usize().unwrap());
     }
+
+    #[test]
+    fn encoding_proptest(bytes in vec(any::<u8>(), 0..100)) {
+        assert_eq!(bytes, from_hex(&to_hex(&bytes)).unwrap());
+        assert_eq!(bytes, from_base64(&to_base64(&bytes)).unwrap());
+    }
 }
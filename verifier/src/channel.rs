@@ -6,36 +6,111 @@
 use alloc::{string::ToString, vec::Vec};
 
 use air::{
-    proof::{Proof, Queries, Table, TraceOodFrame},
+    get_air_digest,
+    proof::{Context, OodFrame, Proof, Queries, Table, TraceOodFrame},
     Air,
 };
-use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
+use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
 use fri::VerifierChannel as FriVerifierChannel;
-use math::{FieldElement, StarkField};
+use math::{ExtensionOf, FieldElement, StarkField};
 
 use crate::VerifierError;
 
+// PROOF CHANNEL
+// ================================================================================================
+
+/// A source of proof data for the verification procedure.
+///
+/// Proof data is read in the order in which it is needed by the verifier. This trait is
+/// implemented by [VerifierChannel], which parses the entire proof upfront, and by
+/// [StreamChannel](crate::stream::StreamChannel), which reads sections of a proof from a byte
+/// stream only when they are requested.
+///
+/// Queried values of auxiliary trace segments and constraint evaluations are elements of the
+/// field `F` in which constraints are evaluated, while the out-of-domain frame and FRI proof are
+/// over the field `E`, which is either the same as `F` or its extension (see
+/// [ProofOptions::ood_field_extension()](air::ProofOptions::ood_field_extension)).
+pub(crate) trait ProofChannel<F, E>: FriVerifierChannel<E>
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField>,
+{
+    /// Returns execution trace commitments sent by the prover.
+    ///
+    /// For computations requiring multiple trace segment, the returned slice will contain a
+    /// commitment for each trace segment.
+    fn read_trace_commitments(&self) -> &[<Self::Hasher as Hasher>::Digest];
+
+    /// Returns constraint evaluation commitment sent by the prover.
+    fn read_constraint_commitment(&self) -> <Self::Hasher as Hasher>::Digest;
+
+    /// Returns the serialized GKR proof, if any.
+    fn read_gkr_proof(&self) -> Option<&Vec<u8>>;
+
+    /// Returns trace polynomial evaluations at out-of-domain points z and z * g, where g is the
+    /// generator of the LDE domain.
+    ///
+    /// For computations requiring multiple trace segments, evaluations of auxiliary trace
+    /// polynomials are also included.
+    fn read_ood_trace_frame(&mut self) -> Result<TraceOodFrame<E>, VerifierError>;
+
+    /// Returns evaluations of composition polynomial columns at z^m, where z is the out-of-domain
+    /// point, and m is the number of composition polynomial columns.
+    fn read_ood_constraint_evaluations(&mut self) -> Result<Vec<E>, VerifierError>;
+
+    /// Returns query proof-of-work nonce sent by the prover.
+    fn read_pow_nonce(&self) -> u64;
+
+    /// Returns trace states at the specified positions of the LDE domain. This also checks if
+    /// the trace states are valid against the trace commitment sent by the prover.
+    ///
+    /// For computations requiring multiple trace segments, trace states for auxiliary segments
+    /// are also included as the second value of the returned tuple (trace states for all auxiliary
+    /// segments are merged into a single table). Otherwise, the second value is None.
+    #[allow(clippy::type_complexity)]
+    fn read_queried_trace_states(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<(Table<F::BaseField>, Option<Table<F>>), VerifierError>;
+
+    /// Returns constraint evaluations at the specified positions of the LDE domain. This also
+    /// checks if the constraint evaluations are valid against the constraint commitment sent by
+    /// the prover.
+    fn read_constraint_evaluations(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<Table<F>, VerifierError>;
+}
+
 // VERIFIER CHANNEL
 // ================================================================================================
 
 /// A view into a [Proof] for a computation structured to simulate an "interactive" channel.
 ///
 /// A channel is instantiated for a specific proof, which is parsed into structs over the
-/// appropriate field (specified by type parameter `E`). This also validates that the proof is
-/// well-formed in the context of the computation for the specified [Air].
-pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+/// appropriate fields (specified by type parameters `F` and `E`). This also validates that the
+/// proof is well-formed in the context of the computation for the specified [Air].
+pub struct VerifierChannel<F, E, H>
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
     // trace queries
     trace_roots: Vec<H::Digest>,
-    trace_queries: Option<TraceQueries<E, H>>,
+    trace_queries: Option<TraceQueries<F, H>>,
     // constraint queries
     constraint_root: H::Digest,
-    constraint_queries: Option<ConstraintQueries<E, H>>,
+    constraint_queries: Option<ConstraintQueries<F, H>>,
     // FRI proof
     fri_roots: Option<Vec<H::Digest>>,
     fri_layer_proofs: Vec<BatchMerkleProof<H>>,
     fri_layer_queries: Vec<Vec<E>>,
+    fri_layer_salts: Vec<Vec<H::Digest>>,
     fri_remainder: Option<Vec<E>>,
+    fri_deep_values: Option<Vec<E>>,
     fri_num_partitions: usize,
+    fri_pow_nonce: u64,
     // out-of-domain frame
     ood_trace_frame: Option<TraceOodFrame<E>>,
     ood_constraint_evaluations: Option<Vec<E>>,
@@ -44,7 +119,12 @@ pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::Base
     gkr_proof: Option<Vec<u8>>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChannel<E, H> {
+impl<F, E, H> VerifierChannel<F, E, H>
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates and returns a new [VerifierChannel] initialized from the specified `proof`.
@@ -68,11 +148,9 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         if E::BaseField::get_modulus_le_bytes() != context.field_modulus_bytes() {
             return Err(VerifierError::InconsistentBaseField);
         }
-        let constraint_frame_width = air.context().num_constraint_composition_columns();
-
+        check_num_queries(air)?;
+        check_air_digest(air, &context)?;
         let num_trace_segments = air.trace_info().num_segments();
-        let main_trace_width = air.trace_info().main_trace_width();
-        let aux_trace_width = air.trace_info().aux_segment_width();
         let lde_domain_size = air.lde_domain_size();
         let fri_options = air.options().to_fri_options();
 
@@ -88,17 +166,19 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
 
         // --- parse FRI proofs -------------------------------------------------------------------
         let fri_num_partitions = fri_proof.num_partitions();
+        let fri_pow_nonce = fri_proof.pow_nonce();
         let fri_remainder = fri_proof
             .parse_remainder()
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let (fri_layer_queries, fri_layer_proofs) = fri_proof
-            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
+        let fri_deep_values = fri_proof
+            .parse_deep_values()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let (fri_layer_queries, fri_layer_salts, fri_layer_proofs) = fri_proof
+            .parse_layers::<H, E, MerkleTree<H>>(lde_domain_size, &fri_options)
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
-        let (ood_trace_frame, ood_constraint_evaluations) = ood_frame
-            .parse(main_trace_width, aux_trace_width, constraint_frame_width)
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let (ood_trace_frame, ood_constraint_evaluations) = parse_ood_frame(ood_frame, air)?;
 
         Ok(VerifierChannel {
             // trace queries
@@ -111,8 +191,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             fri_roots: Some(fri_roots),
             fri_layer_proofs,
             fri_layer_queries,
+            fri_layer_salts,
             fri_remainder: Some(fri_remainder),
+            fri_deep_values: Some(fri_deep_values),
             fri_num_partitions,
+            fri_pow_nonce,
             // out-of-domain evaluation
             ood_trace_frame: Some(ood_trace_frame),
             ood_constraint_evaluations: Some(ood_constraint_evaluations),
@@ -121,104 +204,86 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             gkr_proof,
         })
     }
+}
 
-    // DATA READERS
-    // --------------------------------------------------------------------------------------------
+// PROOF CHANNEL IMPLEMENTATION
+// ================================================================================================
 
-    /// Returns execution trace commitments sent by the prover.
-    ///
-    /// For computations requiring multiple trace segment, the returned slice will contain a
-    /// commitment for each trace segment.
-    pub fn read_trace_commitments(&self) -> &[H::Digest] {
+impl<F, E, H> ProofChannel<F, E> for VerifierChannel<F, E, H>
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn read_trace_commitments(&self) -> &[H::Digest] {
         &self.trace_roots
     }
 
-    /// Returns constraint evaluation commitment sent by the prover.
-    pub fn read_constraint_commitment(&self) -> H::Digest {
+    fn read_constraint_commitment(&self) -> H::Digest {
         self.constraint_root
     }
 
-    /// Returns trace polynomial evaluations at out-of-domain points z and z * g, where g is the
-    /// generator of the LDE domain.
-    ///
-    /// For computations requiring multiple trace segments, evaluations of auxiliary trace
-    /// polynomials are also included.
-    pub fn read_ood_trace_frame(&mut self) -> TraceOodFrame<E> {
-        self.ood_trace_frame.take().expect("already read")
+    fn read_gkr_proof(&self) -> Option<&Vec<u8>> {
+        self.gkr_proof.as_ref()
     }
 
-    /// Returns evaluations of composition polynomial columns at z^m, where z is the out-of-domain
-    /// point, and m is the number of composition polynomial columns.
-    pub fn read_ood_constraint_evaluations(&mut self) -> Vec<E> {
-        self.ood_constraint_evaluations.take().expect("already read")
+    fn read_ood_trace_frame(&mut self) -> Result<TraceOodFrame<E>, VerifierError> {
+        Ok(self.ood_trace_frame.take().expect("already read"))
     }
 
-    /// Returns query proof-of-work nonce sent by the prover.
-    pub fn read_pow_nonce(&self) -> u64 {
-        self.pow_nonce
+    fn read_ood_constraint_evaluations(&mut self) -> Result<Vec<E>, VerifierError> {
+        Ok(self.ood_constraint_evaluations.take().expect("already read"))
     }
 
-    /// Returns the serialized GKR proof, if any.
-    pub fn read_gkr_proof(&self) -> Option<&Vec<u8>> {
-        self.gkr_proof.as_ref()
+    fn read_pow_nonce(&self) -> u64 {
+        self.pow_nonce
     }
 
-    /// Returns trace states at the specified positions of the LDE domain. This also checks if
-    /// the trace states are valid against the trace commitment sent by the prover.
-    ///
-    /// For computations requiring multiple trace segments, trace states for auxiliary segments
-    /// are also included as the second value of the returned tuple (trace states for all auxiliary
-    /// segments are merged into a single table). Otherwise, the second value is None.
-    #[allow(clippy::type_complexity)]
-    pub fn read_queried_trace_states(
+    fn read_queried_trace_states(
         &mut self,
         positions: &[usize],
-    ) -> Result<(Table<E::BaseField>, Option<Table<E>>), VerifierError> {
+    ) -> Result<(Table<F::BaseField>, Option<Table<F>>), VerifierError> {
         let queries = self.trace_queries.take().expect("already read");
-
-        // make sure the states included in the proof correspond to the trace commitment
-        for (root, proof) in self.trace_roots.iter().zip(queries.query_proofs.iter()) {
-            MerkleTree::verify_batch(root, positions, proof)
-                .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
-        }
-
-        Ok((queries.main_states, queries.aux_states))
+        queries.authenticate(&self.trace_roots, positions)
     }
 
-    /// Returns constraint evaluations at the specified positions of the LDE domain. This also
-    /// checks if the constraint evaluations are valid against the constraint commitment sent by
-    /// the prover.
-    pub fn read_constraint_evaluations(
+    fn read_constraint_evaluations(
         &mut self,
         positions: &[usize],
-    ) -> Result<Table<E>, VerifierError> {
+    ) -> Result<Table<F>, VerifierError> {
         let queries = self.constraint_queries.take().expect("already read");
-
-        MerkleTree::verify_batch(&self.constraint_root, positions, &queries.query_proofs)
-            .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
-
-        Ok(queries.evaluations)
+        queries.authenticate(&self.constraint_root, positions)
     }
 }
 
 // FRI VERIFIER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<E, H> FriVerifierChannel<E> for VerifierChannel<E, H>
+impl<F, E, H> FriVerifierChannel<E> for VerifierChannel<F, E, H>
 where
-    E: FieldElement,
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
     H: ElementHasher<BaseField = E::BaseField>,
 {
     type Hasher = H;
+    type VectorCommitment = MerkleTree<H>;
 
     fn read_fri_num_partitions(&self) -> usize {
         self.fri_num_partitions
     }
 
+    fn read_fri_pow_nonce(&self) -> u64 {
+        self.fri_pow_nonce
+    }
+
     fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
         self.fri_roots.take().expect("already read")
     }
 
+    fn read_fri_deep_values(&mut self) -> Vec<E> {
+        self.fri_deep_values.take().expect("already read")
+    }
+
     fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
         self.fri_layer_proofs.remove(0)
     }
@@ -227,6 +292,10 @@ where
         self.fri_layer_queries.remove(0)
     }
 
+    fn take_next_fri_layer_salts(&mut self) -> Vec<H::Digest> {
+        self.fri_layer_salts.remove(0)
+    }
+
     fn take_fri_remainder(&mut self) -> Vec<E> {
         self.fri_remainder.take().expect("already read")
     }
@@ -240,7 +309,7 @@ where
 /// * Merkle authentication paths for all queries.
 ///
 /// Trace states for all auxiliary segments are stored in a single table.
-struct TraceQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+pub(crate) struct TraceQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     query_proofs: Vec<BatchMerkleProof<H>>,
     main_states: Table<E::BaseField>,
     aux_states: Option<Table<E>>,
@@ -307,6 +376,23 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
             aux_states: aux_trace_states,
         })
     }
+
+    /// Checks that the queried trace states are valid against the specified trace commitments
+    /// at the specified positions, and returns the trace states.
+    #[allow(clippy::type_complexity)]
+    pub fn authenticate(
+        self,
+        roots: &[H::Digest],
+        positions: &[usize],
+    ) -> Result<(Table<E::BaseField>, Option<Table<E>>), VerifierError> {
+        // make sure the states included in the proof correspond to the trace commitment
+        for (segment, (root, proof)) in roots.iter().zip(self.query_proofs.iter()).enumerate() {
+            MerkleTree::verify_batch(root, positions, proof)
+                .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment(segment))?;
+        }
+
+        Ok((self.main_states, self.aux_states))
+    }
 }
 
 // CONSTRAINT QUERIES
@@ -315,7 +401,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
 /// Container of constraint evaluation query data, including:
 /// * Queried constraint evaluation values.
 /// * Merkle authentication paths for all queries.
-struct ConstraintQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+pub(crate) struct ConstraintQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     query_proofs: BatchMerkleProof<H>,
     evaluations: Table<E>,
 }
@@ -340,4 +426,60 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
 
         Ok(Self { query_proofs, evaluations })
     }
+
+    /// Checks that the queried constraint evaluations are valid against the specified constraint
+    /// commitment at the specified positions, and returns the evaluations.
+    pub fn authenticate(
+        self,
+        root: &H::Digest,
+        positions: &[usize],
+    ) -> Result<Table<E>, VerifierError> {
+        MerkleTree::verify_batch(root, positions, &self.query_proofs)
+            .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
+
+        Ok(self.evaluations)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure the number of queries specified by the proof options of the specified AIR is
+/// smaller than the size of the LDE domain, as otherwise the required number of distinct query
+/// positions cannot be drawn.
+pub(crate) fn check_num_queries<A: Air>(air: &A) -> Result<(), VerifierError> {
+    let num_queries = air.options().num_queries();
+    if num_queries >= air.lde_domain_size() {
+        return Err(VerifierError::TooManyQueries(num_queries, air.lde_domain_size()));
+    }
+    Ok(())
+}
+
+/// Makes sure the AIR digest in the provided proof `context` matches the digest of the specified
+/// AIR.
+pub(crate) fn check_air_digest<A: Air>(air: &A, context: &Context) -> Result<(), VerifierError> {
+    if *context.air_digest() != get_air_digest(air) {
+        return Err(VerifierError::InconsistentAirDigest);
+    }
+    Ok(())
+}
+
+/// Parses the provided out-of-domain frame into trace polynomial evaluations and evaluations of
+/// composition polynomial columns in the specified field.
+pub(crate) fn parse_ood_frame<A, E>(
+    ood_frame: OodFrame,
+    air: &A,
+) -> Result<(TraceOodFrame<E>, Vec<E>), VerifierError>
+where
+    A: Air<BaseField = E::BaseField>,
+    E: FieldElement,
+{
+    ood_frame
+        .parse(
+            air.trace_info().main_trace_width(),
+            air.trace_info().aux_segment_width(),
+            air.context().num_constraint_composition_columns(),
+            air.context().frame_offsets(),
+        )
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))
 }
@@ -0,0 +1,711 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+
+use air::{
+    proof::{Queries, Table},
+    Air, PublicInputs,
+};
+use crypto::{ElementHasher, MerkleTree, Transcript, TranscriptLabel};
+use math::{FieldElement, StarkField, ToElements};
+
+use super::{
+    evaluate_ood_constraints, fold, CircuitWitness, DigestWire, IntegerWire, Operation,
+    VerificationCircuit, Wire,
+};
+use crate::{
+    channel::parse_ood_frame,
+    recursion::{domain_point, fold_position},
+    Proof, VerifierError,
+};
+
+// CIRCUIT CONSTRUCTION
+// ================================================================================================
+
+/// Builds the verification circuit for the specified proof, following the same steps as
+/// `perform_verification()` but recording every check as an operation instead of executing it.
+#[allow(clippy::type_complexity)]
+pub(super) fn build_circuit<AIR, E, HashFn, RandCoin>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<(VerificationCircuit<E>, CircuitWitness<E, HashFn::Digest>), VerifierError>
+where
+    AIR: Air<BaseField = E::BaseField>,
+    E: FieldElement,
+    HashFn: ElementHasher<BaseField = E::BaseField>,
+    RandCoin: Transcript<BaseField = E::BaseField, Hasher = HashFn>,
+{
+    assert_eq!(
+        proof.options().field_extension().degree() as usize,
+        E::EXTENSION_DEGREE,
+        "field extension of the proof does not match the specified field"
+    );
+
+    let mut public_coin_seed = proof.context.to_elements();
+    public_coin_seed.append(&mut pub_inputs.to_transcript_elements());
+    let air = AIR::new(proof.trace_info().clone(), pub_inputs, proof.options().clone());
+    let fri_options = air.options().to_fri_options();
+    assert!(!air.options().is_zk(), "zero-knowledge proofs are not supported");
+    assert_eq!(
+        air.options().ood_field_extension(),
+        air.options().field_extension(),
+        "proofs with a separate out-of-domain field extension are not supported"
+    );
+    assert!(
+        !air.context().has_lagrange_kernel_aux_column(),
+        "proofs with Lagrange kernel columns are not supported"
+    );
+    assert!(!fri_options.deep_fri(), "proofs generated using DEEP-FRI are not supported");
+    assert_eq!(
+        proof.fri_proof.num_partitions(),
+        1,
+        "proofs with partitioned FRI layer commitments are not supported"
+    );
+
+    let Proof {
+        context,
+        num_unique_queries,
+        commitments,
+        trace_queries,
+        constraint_queries,
+        ood_frame,
+        fri_proof,
+        pow_nonce,
+        ..
+    } = proof;
+    if E::BaseField::get_modulus_le_bytes() != context.field_modulus_bytes() {
+        return Err(VerifierError::InconsistentBaseField);
+    }
+    crate::channel::check_num_queries(&air)?;
+    crate::channel::check_air_digest(&air, &context)?;
+
+    // --- parse the proof ------------------------------------------------------------------------
+    let trace_info = air.trace_info();
+    let lde_domain_size = air.lde_domain_size();
+    let domain_offset = air.domain_offset();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+
+    let (trace_roots, constraint_root, fri_roots) = commitments
+        .parse::<HashFn>(trace_info.num_segments(), num_fri_layers)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    let (ood_trace_frame, ood_constraint_evaluations) = parse_ood_frame::<AIR, E>(ood_frame, &air)?;
+    let fri_pow_nonce = fri_proof.pow_nonce();
+    let remainder = fri_proof
+        .parse_remainder::<E>()
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    let (fri_layer_queries, _, fri_layer_proofs) = fri_proof
+        .parse_layers::<HashFn, E, MerkleTree<HashFn>>(lde_domain_size, &fri_options)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    if fri_layer_queries.len() != num_fri_layers {
+        return Err(VerifierError::ProofDeserializationError(format!(
+            "expected {num_fri_layers} FRI layers, but received {}",
+            fri_layer_queries.len()
+        )));
+    }
+    if trace_queries.len() != trace_info.num_segments() {
+        return Err(VerifierError::ProofDeserializationError(format!(
+            "expected {} trace segment queries, but received {}",
+            trace_info.num_segments(),
+            trace_queries.len()
+        )));
+    }
+
+    // the number of remainder coefficients determines the layout of the circuit; thus, unlike
+    // the verifier, we require the remainder to have exactly the maximum allowed number of
+    // coefficients (this is always the case for proofs generated by the prover)
+    let remainder_len = (0..num_fri_layers)
+        .fold(air.trace_length(), |len, depth| len / fri_options.folding_factor_at(depth));
+    if remainder.len() != remainder_len {
+        return Err(VerifierError::FriVerificationFailed(
+            fri::VerifierError::RemainderDegreeMismatch(remainder_len - 1),
+        ));
+    }
+
+    let mut builder = CircuitBuilder::<E, HashFn, RandCoin>::new(&public_coin_seed);
+    let public_inputs = builder.public_inputs.clone();
+
+    // --- transcript -----------------------------------------------------------------------------
+    let trace_roots: Vec<DigestWire> = trace_roots.iter().map(|&r| builder.digest(r)).collect();
+    builder.absorb(TranscriptLabel::TraceCommitment, trace_roots[0]);
+    let aux_rand_elements = if trace_info.is_multi_segment() {
+        let elements =
+            builder.squeeze_many(None, trace_info.get_num_aux_segment_rand_elements())?;
+        builder.absorb(TranscriptLabel::TraceCommitment, trace_roots[1]);
+        elements
+    } else {
+        Vec::new()
+    };
+    let transition_coefficients =
+        builder.squeeze_many(None, air.context().num_transition_constraints())?;
+    let boundary_coefficients = builder.squeeze_many(None, air.context().num_assertions())?;
+
+    let constraint_root = builder.digest(constraint_root);
+    builder.absorb(TranscriptLabel::ConstraintCommitment, constraint_root);
+    let z = builder.squeeze(Some(TranscriptLabel::OodTraceFrame))?;
+
+    // out-of-domain frame; the frame is hashed column by column
+    let ood_frame: Vec<Vec<Wire>> = (0..ood_trace_frame.num_rows())
+        .map(|k| builder.elements(ood_trace_frame.row(k)))
+        .collect();
+    let ood_frame_columns: Vec<Wire> = (0..trace_info.width())
+        .flat_map(|col| ood_frame.iter().map(move |row| row[col]))
+        .collect();
+    let ood_frame_digest = builder.hash(ood_frame_columns, false);
+    let ood_evaluation = builder.evaluate_constraints(
+        &air,
+        ood_frame.clone(),
+        aux_rand_elements,
+        transition_coefficients,
+        boundary_coefficients,
+        z,
+    );
+    builder.absorb(TranscriptLabel::OodTraceFrame, ood_frame_digest);
+
+    let ood_constraint_evaluations = builder.elements(&ood_constraint_evaluations);
+    let ood_constraint_digest = builder.hash(ood_constraint_evaluations.clone(), false);
+    builder.absorb(TranscriptLabel::OodConstraintEvaluations, ood_constraint_digest);
+
+    // H(z) = \sum_i z^(i * n) * H_i(z), where n is the trace length
+    let mut z_n = z;
+    for _ in 0..air.trace_length().ilog2() {
+        z_n = builder.mul(z_n, z_n);
+    }
+    let composed_evaluation = builder.horner(&ood_constraint_evaluations, z_n);
+    builder.assert_equal(ood_evaluation, composed_evaluation);
+
+    let deep_trace_coefficients = builder.squeeze_many(None, trace_info.width())?;
+    let deep_constraint_coefficients =
+        builder.squeeze_many(None, air.context().num_constraint_composition_columns())?;
+
+    let fri_roots: Vec<DigestWire> = fri_roots.iter().map(|&r| builder.digest(r)).collect();
+    let mut fri_alphas = Vec::with_capacity(fri_roots.len());
+    for &root in fri_roots.iter() {
+        builder.absorb(TranscriptLabel::FriLayer, root);
+        fri_alphas.push(builder.squeeze(Some(TranscriptLabel::FriLayer))?);
+    }
+    if fri_options.grinding_factor() > 0 {
+        let nonce = builder.integer(fri_pow_nonce);
+        builder.proof_of_work(nonce, fri_options.grinding_factor());
+    }
+
+    let pow_nonce = builder.integer(pow_nonce);
+    builder.proof_of_work(pow_nonce, air.options().grinding_factor());
+    let positions = builder.squeeze_integers(
+        TranscriptLabel::QuerySeed,
+        pow_nonce,
+        air.options().num_queries(),
+        lde_domain_size,
+    )?;
+
+    // --- parse queries --------------------------------------------------------------------------
+    let mut unique_positions: Vec<usize> =
+        positions.iter().map(|&p| builder.integers[p.0] as usize).collect();
+    unique_positions.sort_unstable();
+    unique_positions.dedup();
+    if unique_positions.len() != num_unique_queries as usize {
+        return Err(VerifierError::ProofDeserializationError(format!(
+            "expected {} unique queries, but the proof specifies {num_unique_queries}",
+            unique_positions.len()
+        )));
+    }
+
+    let mut trace_queries = trace_queries.into_iter();
+    let (main_paths, main_states) = parse_queries::<HashFn, E::BaseField>(
+        trace_queries.next().expect("no main trace segment queries"),
+        &unique_positions,
+        lde_domain_size,
+        trace_info.main_trace_width(),
+    )?;
+    let aux_queries = trace_queries
+        .next()
+        .map(|queries| {
+            parse_queries::<HashFn, E>(
+                queries,
+                &unique_positions,
+                lde_domain_size,
+                trace_info.aux_segment_width(),
+            )
+        })
+        .transpose()?;
+    let (constraint_paths, constraint_states) = parse_queries::<HashFn, E>(
+        constraint_queries,
+        &unique_positions,
+        lde_domain_size,
+        air.context().num_constraint_composition_columns(),
+    )?;
+
+    // FRI layer values and paths are indexed by the positions in the folded domain, listed in
+    // the order in which they were first queried
+    let mut fri_layers = Vec::with_capacity(num_fri_layers);
+    let mut layer_positions = unique_positions.clone();
+    let mut layer_domain_size = lde_domain_size;
+    for (depth, (values, proof)) in fri_layer_queries.into_iter().zip(fri_layer_proofs).enumerate()
+    {
+        let folding_factor = fri_options.folding_factor_at(depth);
+        let mut folded_positions = Vec::new();
+        for &position in layer_positions.iter() {
+            let (folded_position, _) = fold_position(position, layer_domain_size, folding_factor);
+            if !folded_positions.contains(&folded_position) {
+                folded_positions.push(folded_position);
+            }
+        }
+        let paths = proof
+            .into_paths(&folded_positions)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        fri_layers.push((folded_positions.clone(), values, paths));
+        layer_positions = folded_positions;
+        layer_domain_size /= folding_factor;
+    }
+
+    // --- queries --------------------------------------------------------------------------------
+    let g_trace = air.trace_domain_generator();
+    let ood_points: Vec<Wire> = ood_trace_frame
+        .offsets()
+        .iter()
+        .map(|&offset| {
+            if offset == 0 {
+                z
+            } else {
+                let g = builder.constant(E::from(g_trace.exp_vartime((offset as u64).into())));
+                builder.mul(z, g)
+            }
+        })
+        .collect();
+
+    let remainder = builder.elements(&remainder);
+    let remainder_root = *fri_roots.last().expect("no remainder commitment");
+    builder.hash_into(remainder.clone(), false, remainder_root);
+
+    for &position in positions.iter() {
+        let row = unique_positions
+            .binary_search(&(builder.integers[position.0] as usize))
+            .expect("queried position not found");
+
+        // authenticate the queried trace and constraint rows
+        let main_row: Vec<E> = main_states.get_row(row).iter().map(|&v| E::from(v)).collect();
+        let mut trace_row = builder.elements(&main_row);
+        let leaf = builder.hash(trace_row.clone(), true);
+        builder.merkle_path(leaf, position, &main_paths[row], trace_roots[0]);
+        if let Some((aux_paths, aux_states)) = aux_queries.as_ref() {
+            let aux_row = builder.elements(aux_states.get_row(row));
+            let leaf = builder.hash(aux_row.clone(), false);
+            builder.merkle_path(leaf, position, &aux_paths[row], trace_roots[1]);
+            trace_row.extend(aux_row);
+        }
+        let constraint_row = builder.elements(constraint_states.get_row(row));
+        let leaf = builder.hash(constraint_row.clone(), false);
+        builder.merkle_path(leaf, position, &constraint_paths[row], constraint_root);
+
+        // evaluate the DEEP composition polynomial at the queried position
+        let x = builder.domain_point(position, lde_domain_size, domain_offset);
+        let mut evaluation = None;
+        for (&ood_point, ood_row) in ood_points.iter().zip(ood_frame.iter()) {
+            let numerator =
+                builder.linear_combination(&trace_row, ood_row, &deep_trace_coefficients);
+            let denominator = builder.sub(x, ood_point);
+            let term = builder.div(numerator, denominator);
+            evaluation = Some(match evaluation {
+                Some(acc) => builder.add(acc, term),
+                None => term,
+            });
+        }
+        let numerator = builder.linear_combination(
+            &constraint_row,
+            &ood_constraint_evaluations,
+            &deep_constraint_coefficients,
+        );
+        let denominator = builder.sub(x, z);
+        let term = builder.div(numerator, denominator);
+        let mut evaluation = builder.add(evaluation.expect("empty out-of-domain frame"), term);
+
+        // check the FRI layers
+        let mut position = position;
+        let mut domain_size = lde_domain_size;
+        for (depth, (folded_positions, values, paths)) in fri_layers.iter().enumerate() {
+            let folding_factor = fri_options.folding_factor_at(depth);
+            let (folded, index) = builder.fold_position(position, domain_size, folding_factor);
+            let idx = folded_positions
+                .iter()
+                .position(|&p| p as u64 == builder.integers[folded.0])
+                .expect("folded position not found");
+
+            let values =
+                builder.elements(&values[idx * folding_factor..(idx + 1) * folding_factor]);
+            let leaf = builder.hash(values.clone(), false);
+            builder.merkle_path(leaf, folded, &paths[idx], fri_roots[depth]);
+            let value = builder.select(values.clone(), index);
+            builder.assert_equal(value, evaluation);
+
+            let x = builder.domain_point(folded, domain_size, domain_offset);
+            evaluation = builder.fold_evaluations(values, x, fri_alphas[depth]);
+            position = folded;
+            domain_size /= folding_factor;
+        }
+
+        // check the remainder
+        let x = builder.domain_point(position, domain_size, domain_offset);
+        let value = builder.horner(&remainder, x);
+        builder.assert_equal(value, evaluation);
+    }
+
+    Ok(builder.into_circuit(public_inputs))
+}
+
+// CIRCUIT BUILDER
+// ================================================================================================
+
+/// Records operations of a verification circuit together with the values of their wires.
+///
+/// The builder drives a live transcript so that values squeezed out of the transcript are
+/// assigned to the wires as they are recorded.
+struct CircuitBuilder<E, HashFn, RandCoin>
+where
+    E: FieldElement,
+    HashFn: ElementHasher<BaseField = E::BaseField>,
+    RandCoin: Transcript<BaseField = E::BaseField, Hasher = HashFn>,
+{
+    operations: Vec<Operation<E>>,
+    public_inputs: Vec<Wire>,
+    elements: Vec<E>,
+    digests: Vec<HashFn::Digest>,
+    integers: Vec<u64>,
+    transcript: RandCoin,
+}
+
+impl<E, HashFn, RandCoin> CircuitBuilder<E, HashFn, RandCoin>
+where
+    E: FieldElement,
+    HashFn: ElementHasher<BaseField = E::BaseField>,
+    RandCoin: Transcript<BaseField = E::BaseField, Hasher = HashFn>,
+{
+    /// Returns a new builder with a transcript initialized with the specified seed.
+    fn new(seed: &[E::BaseField]) -> Self {
+        let mut builder = CircuitBuilder {
+            operations: Vec::new(),
+            public_inputs: Vec::new(),
+            elements: Vec::new(),
+            digests: Vec::new(),
+            integers: Vec::new(),
+            transcript: RandCoin::new(seed),
+        };
+        let seed: Vec<E> = seed.iter().map(|&value| E::from(value)).collect();
+        builder.public_inputs = builder.elements(&seed);
+        builder
+            .operations
+            .push(Operation::TranscriptInit { seed: builder.public_inputs.clone() });
+        builder
+    }
+
+    fn into_circuit(
+        self,
+        public_inputs: Vec<Wire>,
+    ) -> (VerificationCircuit<E>, CircuitWitness<E, HashFn::Digest>) {
+        let circuit = VerificationCircuit {
+            operations: self.operations,
+            public_inputs,
+            num_elements: self.elements.len(),
+            num_digests: self.digests.len(),
+            num_integers: self.integers.len(),
+        };
+        let witness = CircuitWitness {
+            elements: self.elements,
+            digests: self.digests,
+            integers: self.integers,
+        };
+        (circuit, witness)
+    }
+
+    // WIRE ALLOCATION
+    // --------------------------------------------------------------------------------------------
+
+    fn element(&mut self, value: E) -> Wire {
+        self.elements.push(value);
+        Wire(self.elements.len() - 1)
+    }
+
+    fn elements(&mut self, values: &[E]) -> Vec<Wire> {
+        values.iter().map(|&value| self.element(value)).collect()
+    }
+
+    fn digest(&mut self, value: HashFn::Digest) -> DigestWire {
+        self.digests.push(value);
+        DigestWire(self.digests.len() - 1)
+    }
+
+    fn integer(&mut self, value: u64) -> IntegerWire {
+        self.integers.push(value);
+        IntegerWire(self.integers.len() - 1)
+    }
+
+    fn value(&self, wire: Wire) -> E {
+        self.elements[wire.0]
+    }
+
+    // ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    fn constant(&mut self, value: E) -> Wire {
+        let out = self.element(value);
+        self.operations.push(Operation::Constant { value, out });
+        out
+    }
+
+    fn add(&mut self, a: Wire, b: Wire) -> Wire {
+        let out = self.element(self.value(a) + self.value(b));
+        self.operations.push(Operation::Add { a, b, out });
+        out
+    }
+
+    fn sub(&mut self, a: Wire, b: Wire) -> Wire {
+        let out = self.element(self.value(a) - self.value(b));
+        self.operations.push(Operation::Sub { a, b, out });
+        out
+    }
+
+    fn mul(&mut self, a: Wire, b: Wire) -> Wire {
+        let out = self.element(self.value(a) * self.value(b));
+        self.operations.push(Operation::Mul { a, b, out });
+        out
+    }
+
+    fn div(&mut self, a: Wire, b: Wire) -> Wire {
+        let out = self.element(self.value(a) * self.value(b).inv());
+        self.operations.push(Operation::Div { a, b, out });
+        out
+    }
+
+    fn assert_equal(&mut self, a: Wire, b: Wire) {
+        self.operations.push(Operation::AssertEqual { a, b });
+    }
+
+    /// Returns sum_i c_i * (a_i - b_i).
+    fn linear_combination(&mut self, a: &[Wire], b: &[Wire], coefficients: &[Wire]) -> Wire {
+        let mut result = None;
+        for ((&a, &b), &c) in a.iter().zip(b).zip(coefficients) {
+            let diff = self.sub(a, b);
+            let term = self.mul(diff, c);
+            result = Some(match result {
+                Some(acc) => self.add(acc, term),
+                None => term,
+            });
+        }
+        result.expect("empty linear combination")
+    }
+
+    /// Returns the evaluation at x of the polynomial with the specified coefficients, listed
+    /// from the lowest to the highest degree.
+    fn horner(&mut self, coefficients: &[Wire], x: Wire) -> Wire {
+        let (&last, rest) = coefficients.split_last().expect("empty polynomial");
+        rest.iter().rev().fold(last, |acc, &coefficient| {
+            let acc = self.mul(acc, x);
+            self.add(acc, coefficient)
+        })
+    }
+
+    // QUERY POSITIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn domain_point(
+        &mut self,
+        position: IntegerWire,
+        domain_size: usize,
+        offset: E::BaseField,
+    ) -> Wire {
+        let x = domain_point(offset, domain_size, self.integers[position.0] as usize);
+        let out = self.element(E::from(x));
+        self.operations
+            .push(Operation::DomainPoint { position, domain_size, offset, out });
+        out
+    }
+
+    fn fold_position(
+        &mut self,
+        position: IntegerWire,
+        domain_size: usize,
+        folding_factor: usize,
+    ) -> (IntegerWire, IntegerWire) {
+        let (folded_position, value_index) =
+            fold_position(self.integers[position.0] as usize, domain_size, folding_factor);
+        let folded = self.integer(folded_position as u64);
+        let index = self.integer(value_index as u64);
+        self.operations.push(Operation::FoldPosition {
+            position,
+            domain_size,
+            folding_factor,
+            folded,
+            index,
+        });
+        (folded, index)
+    }
+
+    fn select(&mut self, values: Vec<Wire>, index: IntegerWire) -> Wire {
+        let out = self.element(self.value(values[self.integers[index.0] as usize]));
+        self.operations.push(Operation::Select { values, index, out });
+        out
+    }
+
+    fn fold_evaluations(&mut self, values: Vec<Wire>, x: Wire, alpha: Wire) -> Wire {
+        let evaluations: Vec<E> = values.iter().map(|&w| self.value(w)).collect();
+        let x_value = self.value(x).base_element(0);
+        let value =
+            fold(&evaluations, x_value, self.value(alpha)).expect("unsupported folding factor");
+        let out = self.element(value);
+        self.operations.push(Operation::FoldEvaluations { values, x, alpha, out });
+        out
+    }
+
+    // CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    fn evaluate_constraints<AIR: Air<BaseField = E::BaseField>>(
+        &mut self,
+        air: &AIR,
+        frame: Vec<Vec<Wire>>,
+        aux_rand_elements: Vec<Wire>,
+        transition_coefficients: Vec<Wire>,
+        boundary_coefficients: Vec<Wire>,
+        z: Wire,
+    ) -> Wire {
+        let values = |wires: &[Wire]| -> Vec<E> { wires.iter().map(|&w| self.value(w)).collect() };
+        let rows: Vec<Vec<E>> = frame.iter().map(|row| values(row)).collect();
+        let value = evaluate_ood_constraints(
+            air,
+            &rows,
+            values(&aux_rand_elements),
+            values(&transition_coefficients),
+            values(&boundary_coefficients),
+            self.value(z),
+        );
+        let out = self.element(value);
+        self.operations.push(Operation::EvaluateConstraints {
+            frame,
+            aux_rand_elements,
+            transition_coefficients,
+            boundary_coefficients,
+            z,
+            out,
+        });
+        out
+    }
+
+    // HASHING
+    // --------------------------------------------------------------------------------------------
+
+    fn hash_value(&self, inputs: &[Wire], base_field: bool) -> HashFn::Digest {
+        let values: Vec<E> = inputs.iter().map(|&w| self.value(w)).collect();
+        if base_field {
+            let values: Vec<E::BaseField> =
+                values.iter().map(|value| value.base_element(0)).collect();
+            HashFn::hash_elements(&values)
+        } else {
+            HashFn::hash_elements(&values)
+        }
+    }
+
+    fn hash(&mut self, inputs: Vec<Wire>, base_field: bool) -> DigestWire {
+        let out = self.digest(self.hash_value(&inputs, base_field));
+        self.operations.push(Operation::HashElements { inputs, base_field, out });
+        out
+    }
+
+    /// Records a hash whose output is an already allocated digest wire.
+    fn hash_into(&mut self, inputs: Vec<Wire>, base_field: bool, out: DigestWire) {
+        self.operations.push(Operation::HashElements { inputs, base_field, out });
+    }
+
+    /// Records a Merkle path; `path` is in the format returned from
+    /// [BatchMerkleProof::into_paths()](crypto::BatchMerkleProof::into_paths), i.e., the first
+    /// element of the path is the leaf itself.
+    fn merkle_path(
+        &mut self,
+        leaf: DigestWire,
+        index: IntegerWire,
+        path: &[HashFn::Digest],
+        root: DigestWire,
+    ) {
+        let path = path[1..].iter().map(|&node| self.digest(node)).collect();
+        self.operations.push(Operation::MerklePath { leaf, index, path, root });
+    }
+
+    // TRANSCRIPT
+    // --------------------------------------------------------------------------------------------
+
+    fn absorb(&mut self, label: TranscriptLabel, digest: DigestWire) {
+        self.transcript.absorb(label, self.digests[digest.0]);
+        self.operations.push(Operation::TranscriptAbsorb { label, digest });
+    }
+
+    fn squeeze(&mut self, label: Option<TranscriptLabel>) -> Result<Wire, VerifierError> {
+        let value = match label {
+            Some(label) => self.transcript.squeeze::<E>(label),
+            None => self.transcript.draw::<E>(),
+        }
+        .map_err(|_| VerifierError::RandomCoinError)?;
+        let out = self.element(value);
+        self.operations.push(Operation::TranscriptSqueeze { label, out });
+        Ok(out)
+    }
+
+    fn squeeze_many(
+        &mut self,
+        label: Option<TranscriptLabel>,
+        num_elements: usize,
+    ) -> Result<Vec<Wire>, VerifierError> {
+        (0..num_elements).map(|_| self.squeeze(label)).collect()
+    }
+
+    fn proof_of_work(&mut self, nonce: IntegerWire, grinding_factor: u32) {
+        self.operations
+            .push(Operation::TranscriptProofOfWork { nonce, grinding_factor });
+    }
+
+    fn squeeze_integers(
+        &mut self,
+        label: TranscriptLabel,
+        nonce: IntegerWire,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<IntegerWire>, VerifierError> {
+        let values = self
+            .transcript
+            .squeeze_integers(label, num_values, domain_size, self.integers[nonce.0])
+            .map_err(|_| VerifierError::RandomCoinError)?;
+        let outs: Vec<IntegerWire> =
+            values.into_iter().map(|value| self.integer(value as u64)).collect();
+        self.operations.push(Operation::TranscriptSqueezeIntegers {
+            label,
+            nonce,
+            domain_size,
+            outs: outs.clone(),
+        });
+        Ok(outs)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses the specified queries into a table of rows and the Merkle paths of these rows; the
+/// rows and paths are listed in the order of the specified (sorted) positions.
+#[allow(clippy::type_complexity)]
+fn parse_queries<HashFn, E>(
+    queries: Queries,
+    positions: &[usize],
+    domain_size: usize,
+    width: usize,
+) -> Result<(Vec<Vec<HashFn::Digest>>, Table<E>), VerifierError>
+where
+    HashFn: ElementHasher,
+    E: FieldElement<BaseField = HashFn::BaseField>,
+{
+    let (proof, table) = queries
+        .parse::<HashFn, E>(domain_size, positions.len(), width)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    let paths = proof
+        .into_paths(positions)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    Ok((paths, table))
+}
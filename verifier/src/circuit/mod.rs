@@ -0,0 +1,513 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Flattened description of the checks performed by the verifier.
+//!
+//! A [VerificationCircuit] lists all field operations, hashes, and transcript operations which
+//! the verifier performs to verify a proof, as a sequence of [Operation]s over three kinds of
+//! wires: field elements ([Wire]), hash digests ([DigestWire]), and integers ([IntegerWire]).
+//! A [CircuitWitness] assigns a value to each wire. Together, they are intended to ease wrapping
+//! proofs into other proof systems (e.g., Groth16 or PLONK): a wrapping circuit needs to provide
+//! a gadget for each kind of operation, and to enforce the operations of the verification
+//! circuit over the values of the witness.
+//!
+//! Every operation is a relation which must hold between the values of its wires; "output" wires
+//! of an operation are usually, but not necessarily, wires which do not appear in preceding
+//! operations. Transcript operations must be enforced in the order in which they are listed since
+//! they update the state of the Fiat-Shamir transcript.
+//!
+//! The circuit depends only on the AIR instance and proof options of the verified proof, and not
+//! on the values included in the proof. Specifically:
+//! * The number of queries is equal to the number of queries specified by the proof options;
+//!   positions drawn more than once are checked more than once.
+//! * Values which depend on the drawn query positions (e.g., x coordinates of queried positions,
+//!   values selected from FRI layer openings, and Merkle authentication paths) are expressed via
+//!   operations over [IntegerWire]s holding the positions.
+//!
+//! Thus, a wrapping circuit can be built once for a given computation and set of proof options
+//! and used to wrap any proof of this computation. [VerificationCircuit::check()] is a reference
+//! implementation of all operations and can be used to validate a wrapping implementation.
+//!
+//! Constraints of the computation are not flattened: [Operation::EvaluateConstraints] refers to
+//! the evaluation of all constraints of the AIR as performed by the verifier, and a wrapping
+//! circuit must implement this evaluation for the specific AIR. Zero-knowledge proofs, proofs
+//! with Lagrange kernel columns, and proofs with partitioned FRI layer commitments are not
+//! supported.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use air::{Air, AuxRandElements, ConstraintCompositionCoefficients, EvaluationFrame};
+use crypto::{ElementHasher, MerkleTree, Transcript, TranscriptLabel};
+use math::{FieldElement, StarkField};
+
+use crate::{evaluator::evaluate_constraints, recursion::fold_evaluations, Proof, VerifierError};
+
+mod builder;
+
+// WIRES
+// ================================================================================================
+
+/// Index of a field element in the [CircuitWitness].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wire(pub usize);
+
+/// Index of a hash digest in the [CircuitWitness].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigestWire(pub usize);
+
+/// Index of an integer in the [CircuitWitness].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerWire(pub usize);
+
+// OPERATIONS
+// ================================================================================================
+
+/// A single relation enforced by a [VerificationCircuit].
+///
+/// All field elements are elements of the field `E` in which the proof is verified; wires which
+/// hold values of the base field (e.g., values of the main trace segment) hold these values
+/// mapped into `E`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation<E: FieldElement> {
+    // --- field arithmetic -----------------------------------------------------------------------
+    /// out = value
+    Constant { value: E, out: Wire },
+    /// out = a + b
+    Add { a: Wire, b: Wire, out: Wire },
+    /// out = a - b
+    Sub { a: Wire, b: Wire, out: Wire },
+    /// out = a * b
+    Mul { a: Wire, b: Wire, out: Wire },
+    /// out * b = a, where b is not zero
+    Div { a: Wire, b: Wire, out: Wire },
+    /// a = b
+    AssertEqual { a: Wire, b: Wire },
+
+    // --- query positions ------------------------------------------------------------------------
+    /// out = offset * g^position, where g is the generator of the multiplicative subgroup of size
+    /// `domain_size` and position < `domain_size`.
+    DomainPoint {
+        position: IntegerWire,
+        domain_size: usize,
+        offset: E::BaseField,
+        out: Wire,
+    },
+    /// position = index * (domain_size / folding_factor) + folded, where folded is smaller than
+    /// domain_size / folding_factor and index is smaller than `folding_factor`; see
+    /// [fold_position()](crate::recursion::fold_position).
+    FoldPosition {
+        position: IntegerWire,
+        domain_size: usize,
+        folding_factor: usize,
+        folded: IntegerWire,
+        index: IntegerWire,
+    },
+    /// out = values\[index\], where index < values.len()
+    Select {
+        values: Vec<Wire>,
+        index: IntegerWire,
+        out: Wire,
+    },
+    /// out = fold_evaluations(values, x, alpha); see
+    /// [fold_evaluations()](crate::recursion::fold_evaluations).
+    FoldEvaluations {
+        values: Vec<Wire>,
+        x: Wire,
+        alpha: Wire,
+        out: Wire,
+    },
+
+    // --- constraints ----------------------------------------------------------------------------
+    /// out is the evaluation of the constraint composition polynomial at z, computed from the
+    /// out-of-domain trace `frame` as done by the verifier for the AIR of the verified proof.
+    ///
+    /// Each row of the frame holds values of the main trace segment followed by values of the
+    /// auxiliary trace segment; rows are listed in the order of the frame offsets of the AIR.
+    EvaluateConstraints {
+        frame: Vec<Vec<Wire>>,
+        aux_rand_elements: Vec<Wire>,
+        transition_coefficients: Vec<Wire>,
+        boundary_coefficients: Vec<Wire>,
+        z: Wire,
+        out: Wire,
+    },
+
+    // --- hashing --------------------------------------------------------------------------------
+    /// out = hash_elements(inputs); if `base_field` is true, the inputs are hashed as elements of
+    /// the base field.
+    HashElements {
+        inputs: Vec<Wire>,
+        base_field: bool,
+        out: DigestWire,
+    },
+    /// `leaf` is the leaf at `index` of the Merkle tree with the specified `root`, as
+    /// authenticated by `path` (listed from the sibling of the leaf up to the children of the
+    /// root).
+    MerklePath {
+        leaf: DigestWire,
+        index: IntegerWire,
+        path: Vec<DigestWire>,
+        root: DigestWire,
+    },
+
+    // --- transcript -----------------------------------------------------------------------------
+    /// Initializes the transcript with the specified seed of base field elements.
+    TranscriptInit { seed: Vec<Wire> },
+    /// Absorbs the `digest` into the transcript under the specified label.
+    TranscriptAbsorb {
+        label: TranscriptLabel,
+        digest: DigestWire,
+    },
+    /// out is the next element squeezed out of the transcript; if the label is None, the element
+    /// is drawn from the underlying random coin directly.
+    TranscriptSqueeze {
+        label: Option<TranscriptLabel>,
+        out: Wire,
+    },
+    /// The proof-of-work `nonce` hashed with the state of the transcript has at least
+    /// `grinding_factor` leading zeros.
+    TranscriptProofOfWork { nonce: IntegerWire, grinding_factor: u32 },
+    /// `outs` are the integers in the range [0, domain_size) squeezed out of the transcript under
+    /// the specified label after the transcript absorbs the `nonce`.
+    TranscriptSqueezeIntegers {
+        label: TranscriptLabel,
+        nonce: IntegerWire,
+        domain_size: usize,
+        outs: Vec<IntegerWire>,
+    },
+}
+
+// VERIFICATION CIRCUIT
+// ================================================================================================
+
+/// Values assigned to the wires of a [VerificationCircuit].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitWitness<E: FieldElement, D> {
+    /// Values of field element wires.
+    pub elements: Vec<E>,
+    /// Values of digest wires.
+    pub digests: Vec<D>,
+    /// Values of integer wires.
+    pub integers: Vec<u64>,
+}
+
+/// Flattened description of the checks performed by the verifier for proofs of a single
+/// computation.
+///
+/// See the [module documentation](self) for details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationCircuit<E: FieldElement> {
+    operations: Vec<Operation<E>>,
+    public_inputs: Vec<Wire>,
+    num_elements: usize,
+    num_digests: usize,
+    num_integers: usize,
+}
+
+impl<E: FieldElement> VerificationCircuit<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the verification circuit for the specified `proof` together with the witness
+    /// extracted from the proof.
+    ///
+    /// The witness is extracted without checking the proof; thus, the proof is valid if and only
+    /// if all operations of the returned circuit are satisfied by the returned witness (see
+    /// [check()](Self::check)).
+    ///
+    /// # Errors
+    /// Returns an error if the proof cannot be parsed, if a random value cannot be drawn from
+    /// the transcript, or if the remainder of the FRI proof has an unexpected number of
+    /// coefficients.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The degree of `E` does not match the field extension specified by the proof options, or
+    ///   the out-of-domain point is drawn from a different field extension.
+    /// * The proof is a zero-knowledge proof, contains a Lagrange kernel column, or commits to
+    ///   FRI layers using more than one partition.
+    #[allow(clippy::type_complexity)]
+    pub fn build<AIR, HashFn, RandCoin>(
+        proof: Proof,
+        pub_inputs: AIR::PublicInputs,
+    ) -> Result<(Self, CircuitWitness<E, HashFn::Digest>), VerifierError>
+    where
+        AIR: Air<BaseField = E::BaseField>,
+        HashFn: ElementHasher<BaseField = E::BaseField>,
+        RandCoin: Transcript<BaseField = E::BaseField, Hasher = HashFn>,
+    {
+        builder::build_circuit::<AIR, E, HashFn, RandCoin>(proof, pub_inputs)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the operations of this circuit.
+    pub fn operations(&self) -> &[Operation<E>] {
+        &self.operations
+    }
+
+    /// Returns the wires holding the seed of the transcript; these are the elements of the proof
+    /// context followed by the public inputs of the computation.
+    pub fn public_inputs(&self) -> &[Wire] {
+        &self.public_inputs
+    }
+
+    /// Returns the number of field element wires in this circuit.
+    pub fn num_elements(&self) -> usize {
+        self.num_elements
+    }
+
+    /// Returns the number of digest wires in this circuit.
+    pub fn num_digests(&self) -> usize {
+        self.num_digests
+    }
+
+    /// Returns the number of integer wires in this circuit.
+    pub fn num_integers(&self) -> usize {
+        self.num_integers
+    }
+
+    // CHECKING
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that all operations of this circuit are satisfied by the specified `witness`.
+    ///
+    /// This is a reference implementation of all operations; `air` must be the AIR instance of
+    /// the proof for which the circuit was built.
+    ///
+    /// # Errors
+    /// Returns an error if the witness does not assign a value to every wire of the circuit, or
+    /// if an operation is not satisfied.
+    pub fn check<AIR, HashFn, RandCoin>(
+        &self,
+        air: &AIR,
+        witness: &CircuitWitness<E, HashFn::Digest>,
+    ) -> Result<(), CircuitError>
+    where
+        AIR: Air<BaseField = E::BaseField>,
+        HashFn: ElementHasher<BaseField = E::BaseField>,
+        RandCoin: Transcript<BaseField = E::BaseField, Hasher = HashFn>,
+    {
+        if witness.elements.len() != self.num_elements
+            || witness.digests.len() != self.num_digests
+            || witness.integers.len() != self.num_integers
+        {
+            return Err(CircuitError::WitnessSizeMismatch);
+        }
+
+        let elements = |wires: &[Wire]| wires.iter().map(|w| witness.elements[w.0]).collect();
+        let mut transcript: Option<RandCoin> = None;
+        for (i, operation) in self.operations.iter().enumerate() {
+            let e = |w: &Wire| witness.elements[w.0];
+            let d = |w: &DigestWire| witness.digests[w.0];
+            let n = |w: &IntegerWire| witness.integers[w.0];
+
+            let is_satisfied = match operation {
+                Operation::Constant { value, out } => e(out) == *value,
+                Operation::Add { a, b, out } => e(out) == e(a) + e(b),
+                Operation::Sub { a, b, out } => e(out) == e(a) - e(b),
+                Operation::Mul { a, b, out } => e(out) == e(a) * e(b),
+                Operation::Div { a, b, out } => e(b) != E::ZERO && e(out) * e(b) == e(a),
+                Operation::AssertEqual { a, b } => e(a) == e(b),
+                Operation::DomainPoint { position, domain_size, offset, out } => {
+                    let g = E::BaseField::get_root_of_unity(domain_size.ilog2());
+                    n(position) < *domain_size as u64
+                        && e(out) == E::from(*offset * g.exp(n(position).into()))
+                },
+                Operation::FoldPosition {
+                    position,
+                    domain_size,
+                    folding_factor,
+                    folded,
+                    index,
+                } => {
+                    let folded_size = (domain_size / folding_factor) as u64;
+                    n(position) < *domain_size as u64
+                        && n(folded) == n(position) % folded_size
+                        && n(index) == n(position) / folded_size
+                },
+                Operation::Select { values, index, out } => {
+                    (n(index) as usize) < values.len() && e(out) == e(&values[n(index) as usize])
+                },
+                Operation::FoldEvaluations { values, x, alpha, out } => {
+                    let values: Vec<E> = elements(values);
+                    fold(&values, e(x).base_element(0), e(alpha)) == Some(e(out))
+                },
+                Operation::EvaluateConstraints {
+                    frame,
+                    aux_rand_elements,
+                    transition_coefficients,
+                    boundary_coefficients,
+                    z,
+                    out,
+                } => {
+                    let rows: Vec<Vec<E>> = frame.iter().map(|row| elements(row)).collect();
+                    let evaluation = evaluate_ood_constraints(
+                        air,
+                        &rows,
+                        elements(aux_rand_elements),
+                        elements(transition_coefficients),
+                        elements(boundary_coefficients),
+                        e(z),
+                    );
+                    e(out) == evaluation
+                },
+                Operation::HashElements { inputs, base_field, out } => {
+                    let values: Vec<E> = elements(inputs);
+                    let digest = if *base_field {
+                        let values: Vec<E::BaseField> =
+                            values.iter().map(|value| value.base_element(0)).collect();
+                        HashFn::hash_elements(&values)
+                    } else {
+                        HashFn::hash_elements(&values)
+                    };
+                    d(out) == digest
+                },
+                Operation::MerklePath { leaf, index, path, root } => {
+                    let mut proof = Vec::with_capacity(path.len() + 1);
+                    proof.push(d(leaf));
+                    proof.extend(path.iter().map(d));
+                    !path.is_empty()
+                        && (n(index) as usize) < (1 << path.len())
+                        && MerkleTree::<HashFn>::verify(d(root), n(index) as usize, &proof).is_ok()
+                },
+                Operation::TranscriptInit { seed } => {
+                    let seed: Vec<E::BaseField> =
+                        seed.iter().map(|w| e(w).base_element(0)).collect();
+                    transcript = Some(RandCoin::new(&seed));
+                    true
+                },
+                Operation::TranscriptAbsorb { label, digest } => match transcript.as_mut() {
+                    Some(transcript) => {
+                        transcript.absorb(*label, d(digest));
+                        true
+                    },
+                    None => false,
+                },
+                Operation::TranscriptSqueeze { label, out } => {
+                    let value = match (transcript.as_mut(), label) {
+                        (Some(transcript), Some(label)) => transcript.squeeze::<E>(*label).ok(),
+                        (Some(transcript), None) => transcript.draw::<E>().ok(),
+                        (None, _) => None,
+                    };
+                    value == Some(e(out))
+                },
+                Operation::TranscriptProofOfWork { nonce, grinding_factor } => {
+                    match transcript.as_ref() {
+                        Some(transcript) => {
+                            transcript.check_leading_zeros(n(nonce)) >= *grinding_factor
+                        },
+                        None => false,
+                    }
+                },
+                Operation::TranscriptSqueezeIntegers { label, nonce, domain_size, outs } => {
+                    let values = transcript.as_mut().and_then(|transcript| {
+                        transcript.squeeze_integers(*label, outs.len(), *domain_size, n(nonce)).ok()
+                    });
+                    let expected: Vec<usize> = outs.iter().map(|w| n(w) as usize).collect();
+                    values == Some(expected)
+                },
+            };
+
+            if !is_satisfied {
+                return Err(CircuitError::UnsatisfiedOperation(i));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// CIRCUIT ERROR
+// ================================================================================================
+
+/// Represents an error returned when a witness does not satisfy a [VerificationCircuit].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// This error occurs when the number of values in the witness does not match the number of
+    /// wires in the circuit.
+    WitnessSizeMismatch,
+    /// This error occurs when the operation with the specified index is not satisfied by the
+    /// witness.
+    UnsatisfiedOperation(usize),
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WitnessSizeMismatch => {
+                write!(f, "number of witness values does not match the number of circuit wires")
+            },
+            Self::UnsatisfiedOperation(index) => {
+                write!(f, "operation {index} is not satisfied by the witness")
+            },
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates constraints of the specified AIR over the out-of-domain frame `rows` as done by the
+/// verifier; each row holds the values of the main trace segment followed by the values of the
+/// auxiliary trace segment.
+fn evaluate_ood_constraints<AIR, E>(
+    air: &AIR,
+    rows: &[Vec<E>],
+    aux_rand_elements: Vec<E>,
+    transition_coefficients: Vec<E>,
+    boundary_coefficients: Vec<E>,
+    z: E,
+) -> E
+where
+    AIR: Air<BaseField = E::BaseField>,
+    E: FieldElement,
+{
+    let main_width = air.trace_info().main_trace_width();
+    let offsets = air.context().frame_offsets().to_vec();
+    let main_frame = EvaluationFrame::from_rows_with_offsets(
+        rows.iter().map(|row| row[..main_width].to_vec()).collect(),
+        offsets.clone(),
+    );
+    let (aux_frame, aux_rand_elements) = if air.trace_info().is_multi_segment() {
+        let frame = EvaluationFrame::from_rows_with_offsets(
+            rows.iter().map(|row| row[main_width..].to_vec()).collect(),
+            offsets,
+        );
+        (Some(frame), Some(AuxRandElements::new(aux_rand_elements)))
+    } else {
+        (None, None)
+    };
+    let coefficients = ConstraintCompositionCoefficients {
+        transition: transition_coefficients,
+        boundary: boundary_coefficients,
+        lagrange: None,
+    };
+    evaluate_constraints(
+        air,
+        coefficients,
+        &main_frame,
+        &aux_frame,
+        None,
+        aux_rand_elements.as_ref(),
+        z,
+    )
+}
+
+/// Folds the specified values as done by [fold_evaluations()]; returns None if the number of
+/// values is not a supported folding factor.
+fn fold<E: FieldElement>(values: &[E], x: E::BaseField, alpha: E) -> Option<E> {
+    match values.len() {
+        2 => Some(fold_evaluations::<E, 2>(values.try_into().ok()?, x, alpha)),
+        4 => Some(fold_evaluations::<E, 4>(values.try_into().ok()?, x, alpha)),
+        8 => Some(fold_evaluations::<E, 8>(values.try_into().ok()?, x, alpha)),
+        16 => Some(fold_evaluations::<E, 16>(values.try_into().ok()?, x, alpha)),
+        32 => Some(fold_evaluations::<E, 32>(values.try_into().ok()?, x, alpha)),
+        64 => Some(fold_evaluations::<E, 64>(values.try_into().ok()?, x, alpha)),
+        _ => None,
+    }
+}
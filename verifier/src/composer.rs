@@ -16,7 +16,7 @@ use math::{batch_inversion, polynom, FieldElement};
 pub struct DeepComposer<E: FieldElement> {
     cc: DeepCompositionCoefficients<E>,
     x_coordinates: Vec<E>,
-    z: [E; 2],
+    z: Vec<E>,
     g_trace: E::BaseField,
     lagrange_kernel_column_idx: Option<usize>,
 }
@@ -30,18 +30,24 @@ impl<E: FieldElement> DeepComposer<E> {
         cc: DeepCompositionCoefficients<E>,
     ) -> Self {
         // compute LDE domain coordinates for all query positions
-        let g_lde = air.lde_domain_generator();
-        let domain_offset = air.domain_offset();
-        let x_coordinates: Vec<E> = query_positions
+        let lde_domain = air.lde_domain();
+        let x_coordinates: Vec<E> =
+            query_positions.iter().map(|&p| E::from(lde_domain.get_x_at(p))).collect();
+        let g_trace = air.trace_domain_generator();
+
+        // compute out-of-domain points for all row offsets of the evaluation frame; for the
+        // default frame, these are z and z * g
+        let z = air
+            .context()
+            .frame_offsets()
             .iter()
-            .map(|&p| E::from(g_lde.exp_vartime((p as u64).into()) * domain_offset))
+            .map(|&offset| z.mul_base(g_trace.exp((offset as u64).into())))
             .collect();
-        let g_trace = air.trace_domain_generator();
 
         DeepComposer {
             cc,
             x_coordinates,
-            z: [z, z * E::from(g_trace)],
+            z,
             g_trace,
             lagrange_kernel_column_idx: air.context().lagrange_kernel_aux_column_idx(),
         }
@@ -57,6 +63,9 @@ impl<E: FieldElement> DeepComposer<E> {
     /// - Then, combine all T'_i(x) and T''_i(x) values together by computing
     ///   T(x) = sum((T'_i(x) + T''_i(x)) * cc_i) for all i, where cc_i is the coefficient for
     ///   for the random linear combination drawn from the public coin.
+    /// - If the evaluation frame has rows at offsets other than 0 and 1, an additional term
+    ///   (T_i(x) - T_i(z * g^k)) / (x - z * g^k) is computed for each such offset k and included
+    ///   into the linear combination using the same coefficient cc_i.
     /// - If a Lagrange kernel is present, combine one additional term defined as
     ///   (T_l(x) - p_S(x)) / Z_S(x), where:
     ///
@@ -72,15 +81,24 @@ impl<E: FieldElement> DeepComposer<E> {
     ///
     /// If a Lagrange kernel is present, the evaluations of $T_l$ over the set $S$ are received
     /// from the prover and passed separately via `ood_lagrange_kernel_frame`.
-    pub fn compose_trace_columns(
+    ///
+    /// Queried auxiliary trace states are elements of the field `F` in which constraints are
+    /// evaluated; `E` is either the same field or its extension.
+    pub fn compose_trace_columns<F>(
         &self,
         queried_main_trace_states: Table<E::BaseField>,
-        queried_aux_trace_states: Option<Table<E>>,
+        queried_aux_trace_states: Option<Table<F>>,
         ood_main_frame: EvaluationFrame<E>,
         ood_aux_frame: Option<EvaluationFrame<E>>,
         ood_lagrange_kernel_frame: Option<&LagrangeKernelEvaluationFrame<E>>,
-    ) -> Vec<E> {
-        let ood_main_trace_states = [ood_main_frame.current(), ood_main_frame.next()];
+    ) -> Vec<E>
+    where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: From<F>,
+    {
+        let num_rows = self.z.len();
+        let ood_main_trace_states: Vec<&[E]> =
+            (0..num_rows).map(|k| ood_main_frame.row(k)).collect();
 
         // compose columns of of the main trace segment; we do this separately for numerators of
         // each query; we also track common denominator for each query separately; this way we can
@@ -91,34 +109,32 @@ impl<E: FieldElement> DeepComposer<E> {
 
         for ((_, row), &x) in (0..n).zip(queried_main_trace_states.rows()).zip(&self.x_coordinates)
         {
-            let mut t1_num = E::ZERO;
-            let mut t2_num = E::ZERO;
+            let mut t_num = vec![E::ZERO; num_rows];
 
             for (i, &value) in row.iter().enumerate() {
                 let value = E::from(value);
-                // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t1_num += (value - ood_main_trace_states[0][i]) * self.cc.trace[i];
-
-                // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t2_num += (value - ood_main_trace_states[1][i]) * self.cc.trace[i];
+                // compute the numerator of (T_i(x) - T_i(z * g^k)) / (x - z * g^k) for each row
+                // offset k, multiply it by a composition coefficient, and add the result to the
+                // numerator aggregator for this offset
+                for (num, ood_row) in t_num.iter_mut().zip(ood_main_trace_states.iter()) {
+                    *num += (value - ood_row[i]) * self.cc.trace[i];
+                }
             }
-            // compute the common denominator as (x - z) * (x - z * g)
-            let t1_den = x - self.z[0];
-            let t2_den = x - self.z[1];
-            result_den.push(t1_den * t2_den);
-
-            // add the numerators of T'_i(x) and T''_i(x) together; we can do this because later on
-            // we'll use the common denominator computed above.
-            result_num.push(t1_num * t2_den + t2_num * t1_den);
+
+            // compute the common denominator as (x - z) * (x - z * g) * ... and add the
+            // numerators together; we can do this because later on we'll use the common
+            // denominator
+            let t_den: Vec<E> = self.z.iter().map(|&z| x - z).collect();
+            result_den.push(t_den.iter().fold(E::ONE, |acc, &d| acc * d));
+            result_num.push(combine_numerators(&t_num, &t_den));
         }
 
         // if the trace has auxiliary segments, compose columns from these segments as well; we
         // also do this separately for numerators and denominators.
         if let Some(queried_aux_trace_states) = queried_aux_trace_states {
             let ood_aux_frame = ood_aux_frame.expect("missing auxiliary OOD frame");
-            let ood_aux_trace_states = [ood_aux_frame.current(), ood_aux_frame.next()];
+            let ood_aux_trace_states: Vec<&[E]> =
+                (0..num_rows).map(|k| ood_aux_frame.row(k)).collect();
 
             // we define this offset here because composition of the main trace columns has
             // consumed some number of composition coefficients already.
@@ -131,31 +147,32 @@ impl<E: FieldElement> DeepComposer<E> {
             for ((j, row), &x) in
                 (0..n).zip(queried_aux_trace_states.rows()).zip(&self.x_coordinates)
             {
-                let mut t1_num = E::ZERO;
-                let mut t2_num = E::ZERO;
+                let mut t_num = vec![E::ZERO; num_rows];
 
                 let row = &row[..lagrange_ker_col_idx];
                 for (i, &value) in row.iter().enumerate() {
-                    // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t1_num += (value - ood_aux_trace_states[0][i]) * self.cc.trace[cc_offset + i];
-
-                    // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t2_num += (value - ood_aux_trace_states[1][i]) * self.cc.trace[cc_offset + i];
+                    let value = E::from(value);
+                    // compute the numerator of (T_i(x) - T_i(z * g^k)) / (x - z * g^k) for each
+                    // row offset k, multiply it by a composition coefficient, and add the result
+                    // to the numerator aggregator for this offset
+                    for (num, ood_row) in t_num.iter_mut().zip(ood_aux_trace_states.iter()) {
+                        *num += (value - ood_row[i]) * self.cc.trace[cc_offset + i];
+                    }
                 }
 
-                // compute the common denominators (x - z) and (x - z * g), and use the to aggregate
-                // numerators into the common numerator computed for the main trace of this query
-                let t1_den = x - self.z[0];
-                let t2_den = x - self.z[1];
-                result_num[j] += t1_num * t2_den + t2_num * t1_den;
+                // compute the denominators (x - z * g^k), and use them to aggregate numerators
+                // into the common numerator computed for the main trace of this query
+                let t_den: Vec<E> = self.z.iter().map(|&z| x - z).collect();
+                result_num[j] += combine_numerators(&t_num, &t_den);
             }
 
             // if a Lagrange kernel trace polynomial is present, we include its associated term
             // separately. Note that, for performance reasons, we divide by Z_{S^{'}} instead of
             // Z_S, where  $S^{'} := {z.g^2, ..., z.g^{2^{log_2(\nu) - 1}}}$. This is done as
-            // the final linear combination is divided by `(x - z) . (x - z.g)`.
+            // the final linear combination is divided by `(x - z) . (x - z.g)`. If the evaluation
+            // frame has additional rows, the final linear combination is also divided by
+            // `(x - z.g^k)` for each additional row offset k; thus, we multiply the numerator of
+            // the term by these factors.
             if let Some(ood_lagrange_kernel_frame) = ood_lagrange_kernel_frame {
                 let mut result_lag_num = Vec::<E>::with_capacity(n);
                 let mut result_lag_den = Vec::<E>::with_capacity(n);
@@ -178,14 +195,18 @@ impl<E: FieldElement> DeepComposer<E> {
                     // takes on the values `g`, `g^2`, `g^4`, `g^8`, ...
                     g_exp *= g_exp;
                 }
-                let p_s = polynom::interpolate(&xs, &ys, true);
+                // p_s is evaluated via barycentric weights so that it never needs to be
+                // interpolated into coefficient form
+                let p_s_weights = polynom::get_barycentric_weights(&xs);
                 let z_s_prime = polynom::poly_from_roots(&xs[2..]);
 
                 for (row, &x) in queried_aux_trace_states.rows().zip(&self.x_coordinates) {
-                    let value = row[lagrange_ker_col_idx];
+                    let value = E::from(row[lagrange_ker_col_idx]);
                     let cc = self.cc.lagrange.unwrap();
 
-                    result_lag_num.push((value - polynom::eval(&p_s, x)) * cc);
+                    let p_s_at_x = polynom::eval_barycentric(&xs, &ys, &p_s_weights, x);
+                    let extra_den = self.z[2..].iter().fold(E::ONE, |acc, &z| acc * (x - z));
+                    result_lag_num.push((value - p_s_at_x) * cc * extra_den);
                     result_lag_den.push(polynom::eval(&z_s_prime, x));
                 }
 
@@ -210,12 +231,17 @@ impl<E: FieldElement> DeepComposer<E> {
     ///   public coin.
     ///
     /// Note that values of H_i(z) are received from the prover and passed into this function
-    /// via the `ood_evaluations` parameter.
-    pub fn compose_constraint_evaluations(
+    /// via the `ood_evaluations` parameter, while queried values H_i(x) are elements of the field
+    /// `F` in which constraints are evaluated.
+    pub fn compose_constraint_evaluations<F>(
         &self,
-        queried_evaluations: Table<E>,
+        queried_evaluations: Table<F>,
         ood_evaluations: Vec<E>,
-    ) -> Vec<E> {
+    ) -> Vec<E>
+    where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: From<F>,
+    {
         assert_eq!(queried_evaluations.num_rows(), self.x_coordinates.len());
 
         let n = queried_evaluations.num_rows();
@@ -231,7 +257,8 @@ impl<E: FieldElement> DeepComposer<E> {
             for (i, &evaluation) in query_values.iter().enumerate() {
                 // compute the numerator of H'_i(x) as (H_i(x) - H_i(z)), multiply it by a
                 // composition coefficient, and add the result to the numerator aggregator
-                composition_num += (evaluation - ood_evaluations[i]) * self.cc.constraints[i];
+                composition_num +=
+                    (E::from(evaluation) - ood_evaluations[i]) * self.cc.constraints[i];
             }
             result_num.push(composition_num);
             result_den.push(x - z);
@@ -255,3 +282,19 @@ impl<E: FieldElement> DeepComposer<E> {
         result
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns sum(num_k * prod_{j != k}(den_j)), which is the numerator of sum(num_k / den_k) over
+/// the common denominator prod(den_k).
+fn combine_numerators<E: FieldElement>(numerators: &[E], denominators: &[E]) -> E {
+    numerators.iter().enumerate().fold(E::ZERO, |acc, (k, &num)| {
+        let others = denominators
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != k)
+            .fold(E::ONE, |acc, (_, &den)| acc * den);
+        acc + num * others
+    })
+}
@@ -0,0 +1,87 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::marker::PhantomData;
+
+use air::{proof::Proof, Air};
+use crypto::{
+    DefaultRandomCoin, ElementHasher, HashFunction, HashFunctionField, HashFunctionVisitor,
+};
+
+use crate::{verify, AcceptableOptions, VerifierError};
+
+// DEFAULT VERIFIER
+// ================================================================================================
+
+/// A STARK verifier which uses a hash function selected at runtime.
+///
+/// This is a counterpart of `DefaultProver` from the prover crate: instead of specifying the hash
+/// function via a generic parameter of [verify()], the hash function is specified via the
+/// [HashFunction] enum. Proofs are verified using [DefaultRandomCoin] instantiated with the
+/// selected hash function.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DefaultVerifier {
+    hash_fn: HashFunction,
+}
+
+impl DefaultVerifier {
+    /// Returns a new verifier which uses the specified hash function.
+    pub fn new(hash_fn: HashFunction) -> Self {
+        Self { hash_fn }
+    }
+
+    /// Returns the hash function used by this verifier.
+    pub fn hash_fn(&self) -> HashFunction {
+        self.hash_fn
+    }
+
+    /// Verifies that the specified computation was executed correctly against the specified
+    /// inputs.
+    ///
+    /// This is equivalent to invoking [verify()] with the hash function of this verifier and
+    /// [DefaultRandomCoin] instantiated with this hash function.
+    ///
+    /// # Errors
+    /// Returns an error if the hash function of this verifier is not defined over the base field
+    /// of the AIR, or if the proof is not valid (see [verify()] for details).
+    pub fn verify<AIR>(
+        &self,
+        proof: Proof,
+        pub_inputs: AIR::PublicInputs,
+        acceptable_options: &AcceptableOptions,
+    ) -> Result<(), VerifierError>
+    where
+        AIR: Air,
+        AIR::BaseField: HashFunctionField,
+    {
+        let visitor = VerifyVisitor::<AIR> {
+            proof,
+            pub_inputs,
+            acceptable_options,
+            _air: PhantomData,
+        };
+        AIR::BaseField::dispatch(self.hash_fn, visitor)
+            .unwrap_or(Err(VerifierError::UnsupportedHashFunction(self.hash_fn)))
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Verifies the provided proof against the specified AIR.
+struct VerifyVisitor<'a, AIR: Air> {
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &'a AcceptableOptions,
+    _air: PhantomData<AIR>,
+}
+
+impl<AIR: Air> HashFunctionVisitor<AIR::BaseField> for VerifyVisitor<'_, AIR> {
+    type Output = Result<(), VerifierError>;
+
+    fn visit<H: ElementHasher<BaseField = AIR::BaseField>>(self) -> Self::Output {
+        verify::<AIR, H, DefaultRandomCoin<H>>(self.proof, self.pub_inputs, self.acceptable_options)
+    }
+}
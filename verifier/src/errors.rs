@@ -8,6 +8,8 @@
 use alloc::string::String;
 use core::fmt;
 
+use crypto::HashFunction;
+
 // VERIFIER ERROR
 // ================================================================================================
 /// Represents an error returned by the verifier during an execution of the protocol.
@@ -18,6 +20,10 @@ pub enum VerifierError {
     /// This error occurs when base field read by a verifier from a proof does not match the
     /// base field of AIR with which the verifier was instantiated.
     InconsistentBaseField,
+    /// This error occurs when the digest of the AIR structure included in a proof does not match
+    /// the digest of the AIR with which the verifier was instantiated, i.e., when the proof was
+    /// generated for a structurally different AIR.
+    InconsistentAirDigest,
     /// This error occurs when the base field in which the proof was generated does not support
     /// field extension of degree specified by the proof.
     UnsupportedFieldExtension(usize),
@@ -28,10 +34,15 @@ pub enum VerifierError {
     RandomCoinError,
     /// This error occurs when constraints evaluated over out-of-domain trace rows do not match
     /// evaluations of the constraint composition polynomial at the out-of-domain point.
+    ///
+    /// The proof contains only evaluations of the composition polynomial into which all
+    /// constraints are merged, and thus, the mismatch cannot be attributed to a specific
+    /// constraint; `Prover::validate_trace()` can be used to find unsatisfied constraints.
     InconsistentOodConstraintEvaluations,
     /// This error occurs when Merkle authentication paths of trace queries do not resolve to the
-    /// execution trace commitment included in the proof.
-    TraceQueryDoesNotMatchCommitment,
+    /// commitment to the trace segment with the specified index (0 for the main segment)
+    /// included in the proof.
+    TraceQueryDoesNotMatchCommitment(usize),
     /// This error occurs when Merkle authentication paths of constraint evaluation queries do not
     /// resolve to the constraint evaluation commitment included in the proof.
     ConstraintQueryDoesNotMatchCommitment,
@@ -39,6 +50,10 @@ pub enum VerifierError {
     /// coin resolves to a value which does not meet the proof-of-work threshold specified by the
     // proof options.
     QuerySeedProofOfWorkVerificationFailed,
+    /// This error occurs when the evaluation of the DEEP composition polynomial derived from trace
+    /// and constraint evaluation queries at the specified position of the LDE domain does not
+    /// match the evaluation committed to in the first FRI layer.
+    InconsistentDeepComposition(usize),
     /// This error occurs when the DEEP composition polynomial evaluations derived from trace and
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
@@ -54,6 +69,29 @@ pub enum VerifierError {
     /// This error occurs when the parameters, that were used to generate the proof, do not match
     /// any of the set of parameters expected by the verifier.
     UnacceptableProofOptions,
+    /// This error occurs when a proof was generated with fewer queries than required by the
+    /// policy of the verifier.
+    InsufficientNumQueries(usize, usize),
+    /// This error occurs when a proof was generated with a smaller blowup factor than required by
+    /// the policy of the verifier.
+    InsufficientBlowupFactor(usize, usize),
+    /// This error occurs when a proof was generated with a hash function not allowed by the
+    /// policy of the verifier; None identifies a hash function which cannot be selected at
+    /// runtime via [HashFunction].
+    UnacceptableHashFunction(Option<HashFunction>),
+    /// This error occurs when a proof was generated with a field extension of a degree not
+    /// allowed by the policy of the verifier.
+    UnacceptableFieldExtension(usize),
+    /// This error occurs when the options of a proof do not declare proven security, but the
+    /// policy of the verifier requires it.
+    ProvenSecurityRequired,
+    /// This error occurs when a hash function selected at runtime via
+    /// [DefaultVerifier](crate::DefaultVerifier) is not defined over the base field of the AIR.
+    UnsupportedHashFunction(HashFunction),
+    /// This error occurs when the number of queries specified by the proof options is not
+    /// smaller than the size of the LDE domain, and thus, distinct query positions cannot be
+    /// drawn.
+    TooManyQueries(usize, usize),
 }
 
 impl fmt::Display for VerifierError {
@@ -66,6 +104,9 @@ impl fmt::Display for VerifierError {
             Self::InconsistentBaseField =>  {
                 write!(f, "base field of the proof does not match base field of the specified AIR")
             }
+            Self::InconsistentAirDigest =>  {
+                write!(f, "proof was generated for an AIR with a different structure than the specified AIR")
+            }
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the proof base field")
             }
@@ -78,8 +119,8 @@ impl fmt::Display for VerifierError {
             Self::InconsistentOodConstraintEvaluations => {
                 write!(f, "constraint evaluations over the out-of-domain frame are inconsistent")
             }
-            Self::TraceQueryDoesNotMatchCommitment => {
-                write!(f, "trace query did not match the commitment")
+            Self::TraceQueryDoesNotMatchCommitment(segment) => {
+                write!(f, "trace query did not match the commitment to trace segment {segment}")
             }
             Self::ConstraintQueryDoesNotMatchCommitment => {
                 write!(f, "constraint query did not match the commitment")
@@ -87,6 +128,9 @@ impl fmt::Display for VerifierError {
             Self::QuerySeedProofOfWorkVerificationFailed => {
                 write!(f, "query seed proof-of-work verification failed")
             }
+            Self::InconsistentDeepComposition(position) => {
+                write!(f, "DEEP composition evaluation at query position {position} did not match the first FRI layer")
+            }
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {err}")
             }
@@ -97,6 +141,30 @@ impl fmt::Display for VerifierError {
                 write!(f, "insufficient proof security level: expected at least {minimal_security} bits of proven security, but was {proof_security} bits")
             }
             Self::UnacceptableProofOptions => {write!(f, "invalid proof options: security parameters do not match the acceptable parameter set")}
+            Self::InsufficientNumQueries(min_num_queries, num_queries) => {
+                write!(f, "invalid proof options: expected at least {min_num_queries} queries, but was {num_queries}")
+            }
+            Self::InsufficientBlowupFactor(min_blowup_factor, blowup_factor) => {
+                write!(f, "invalid proof options: expected blowup factor of at least {min_blowup_factor}, but was {blowup_factor}")
+            }
+            Self::UnacceptableHashFunction(Some(hash_fn)) => {
+                write!(f, "invalid proof options: hash function {hash_fn} is not acceptable")
+            }
+            Self::UnacceptableHashFunction(None) => {
+                write!(f, "invalid proof options: hash function is not acceptable")
+            }
+            Self::UnacceptableFieldExtension(degree) => {
+                write!(f, "invalid proof options: field extension of degree {degree} is not acceptable")
+            }
+            Self::ProvenSecurityRequired => {
+                write!(f, "invalid proof options: proof options must declare proven security")
+            }
+            Self::UnsupportedHashFunction(hash_fn) => {
+                write!(f, "hash function {hash_fn} is not supported for the proof base field")
+            }
+            Self::TooManyQueries(num_queries, lde_domain_size) => {
+                write!(f, "invalid proof options: the number of queries must be smaller than the LDE domain size {lde_domain_size}, but was {num_queries}")
+            }
         }
     }
 }
@@ -9,7 +9,7 @@ use air::{
     Air, AuxRandElements, ConstraintCompositionCoefficients, EvaluationFrame,
     LagrangeKernelEvaluationFrame,
 };
-use math::{polynom, FieldElement};
+use math::{batch_inversion_in_place, polynom, FieldElement};
 
 // CONSTRAINT EVALUATION
 // ================================================================================================
@@ -50,18 +50,30 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
         let aux_rand_elements =
             aux_rand_elements.expect("expected aux rand elements to be present");
 
-        air.evaluate_aux_transition(
+        // constraints of lookup arguments (if any) are placed after all other constraints
+        let num_lookups = air.context().num_lookups();
+        let (custom_evaluations, lookup_evaluations) =
+            t_evaluations2.split_at_mut(t_constraints.num_aux_constraints() - num_lookups);
+        if !custom_evaluations.is_empty() {
+            air.evaluate_aux_transition(
+                main_trace_frame,
+                aux_trace_frame,
+                &periodic_values,
+                aux_rand_elements.rand_elements(),
+                custom_evaluations,
+            );
+        }
+        air.evaluate_lookup_transition(
             main_trace_frame,
             aux_trace_frame,
-            &periodic_values,
             aux_rand_elements.rand_elements(),
-            &mut t_evaluations2,
+            lookup_evaluations,
         );
     }
 
     // merge all constraint evaluations into a single value by computing their random linear
-    // combination using coefficients drawn from the public coin. this also divides the result
-    // by the divisor of transition constraints.
+    // combination using coefficients drawn from the public coin. this also divides the
+    // evaluations by the divisors of their respective transition constraints.
     let mut result = t_constraints.combine_evaluations::<E>(&t_evaluations1, &t_evaluations2, x);
 
     // 2 ----- evaluate boundary constraints ------------------------------------------------------
@@ -73,21 +85,29 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     );
 
     // iterate over boundary constraint groups for the main trace segment (each group has a
-    // distinct divisor), evaluate constraints in each group and add their combination to the
-    // result
+    // distinct divisor), evaluate combinations of constraints in each group as well as their
+    // divisors
+    let mut numerators = Vec::new();
+    let mut denominators = Vec::new();
     for group in b_constraints.main_constraints().iter() {
-        result += group.evaluate_at(main_trace_frame.current(), x);
+        numerators.push(group.evaluate_numerator_at(main_trace_frame.current(), x));
+        denominators.push(group.divisor().evaluate_at(x));
     }
 
-    // iterate over boundary constraint groups for the auxiliary trace segment (each group has a
-    // distinct divisor), evaluate constraints in each group and add their combination to the
-    // result
+    // do the same for boundary constraint groups for the auxiliary trace segment
     if let Some(aux_trace_frame) = aux_trace_frame {
         for group in b_constraints.aux_constraints().iter() {
-            result += group.evaluate_at(aux_trace_frame.current(), x);
+            numerators.push(group.evaluate_numerator_at(aux_trace_frame.current(), x));
+            denominators.push(group.divisor().evaluate_at(x));
         }
     }
 
+    // invert all divisors at once and add divided constraint combinations to the result
+    batch_inversion_in_place(&mut denominators);
+    for (&numerator, &inv_denominator) in numerators.iter().zip(denominators.iter()) {
+        result += numerator * inv_denominator;
+    }
+
     // 3 ----- evaluate Lagrange kernel constraints ------------------------------------
 
     if let Some(lagrange_kernel_column_frame) = lagrange_kernel_frame {
@@ -15,6 +15,13 @@
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
 //!    [Proof] and related public inputs as parameters.
 //!
+//! To verify many proofs for the same computation at once, [verify_batch()] function can be used
+//! instead; it returns the result of verification for each of the proofs.
+//!
+//! To verify a large proof without buffering all of it in memory, [verify_stream()] function can
+//! be used instead; it reads the proof from a [ByteReader] section by section as verification
+//! progresses. For this, the proof must be serialized via [Proof::write_stream_into()].
+//!
 //! # Performance
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
 //! computation being verified. In vast majority of cases proofs can be verified in 3 - 5 ms
@@ -33,26 +40,35 @@ extern crate alloc;
 
 use alloc::{string::ToString, vec::Vec};
 
+use air::{
+    proof::Context, AuxRandElements, GkrVerifier, LagrangeConstraintsCompositionCoefficients,
+    LagrangeKernelRandElements,
+};
 pub use air::{
     proof::Proof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
     ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, ProofOptions, TraceInfo, TransitionConstraintDegree,
+    EvaluationFrame, FieldExtension, LookupArgument, ProofOptions, PublicInputs, TraceInfo,
+    TransitionConstraintDegree,
 };
-use air::{AuxRandElements, GkrVerifier};
 pub use crypto;
-use crypto::{ElementHasher, Hasher, RandomCoin};
+pub use crypto::HashFunction;
+use crypto::{ElementHasher, Hasher, Transcript, TranscriptLabel};
 use fri::FriVerifier;
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement, ToElements,
+    ExtensionOf, FieldElement, ToElements,
 };
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+#[cfg(feature = "std")]
+pub use utils::ReadAdapter;
 pub use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
 mod channel;
-use channel::VerifierChannel;
+use channel::{ProofChannel, VerifierChannel};
 
 mod evaluator;
 use evaluator::evaluate_constraints;
@@ -63,6 +79,18 @@ use composer::DeepComposer;
 mod errors;
 pub use errors::VerifierError;
 
+mod dispatch;
+pub use dispatch::DefaultVerifier;
+
+mod stream;
+pub use stream::verify_stream;
+
+pub mod circuit;
+
+pub mod recursion;
+
+pub mod solidity;
+
 // VERIFIER
 // ================================================================================================
 
@@ -86,7 +114,7 @@ pub fn verify<AIR, HashFn, RandCoin>(
 where
     AIR: Air,
     HashFn: ElementHasher<BaseField = AIR::BaseField>,
-    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    RandCoin: Transcript<BaseField = AIR::BaseField, Hasher = HashFn>,
 {
     // check that `proof` was generated with an acceptable set of parameters from the point of view
     // of the verifier
@@ -96,18 +124,55 @@ where
     // public inputs, but as the protocol progresses, the coin will be reseeded with the info
     // received from the prover
     let mut public_coin_seed = proof.context.to_elements();
-    public_coin_seed.append(&mut pub_inputs.to_elements());
+    public_coin_seed.append(&mut pub_inputs.to_transcript_elements());
 
     // create AIR instance for the computation specified in the proof
     let air = AIR::new(proof.trace_info().clone(), pub_inputs, proof.options().clone());
 
     // figure out which version of the generic proof verification procedure to run. this is a sort
-    // of static dispatch for selecting two generic parameter: extension field and hash function.
+    // of static dispatch for selecting generic parameters: the field in which constraints are
+    // evaluated, the field from which the out-of-domain point is drawn, and hash function.
     match air.options().field_extension() {
-        FieldExtension::None => {
-            let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(air, channel, public_coin)
+        FieldExtension::None => match air.options().ood_field_extension() {
+            FieldExtension::None => {
+                let public_coin = RandCoin::new(&public_coin_seed);
+                let channel = VerifierChannel::new(&air, proof)?;
+                perform_verification::<AIR, AIR::BaseField, AIR::BaseField, HashFn, _, RandCoin>(
+                    &air,
+                    channel,
+                    public_coin,
+                )
+            },
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<AIR::BaseField>>::is_supported() {
+                    return Err(VerifierError::UnsupportedFieldExtension(2));
+                }
+                let public_coin = RandCoin::new(&public_coin_seed);
+                let channel = VerifierChannel::new(&air, proof)?;
+                perform_verification::<
+                    AIR,
+                    AIR::BaseField,
+                    QuadExtension<AIR::BaseField>,
+                    HashFn,
+                    _,
+                    RandCoin,
+                >(&air, channel, public_coin)
+            },
+            FieldExtension::Cubic => {
+                if !<CubeExtension<AIR::BaseField>>::is_supported() {
+                    return Err(VerifierError::UnsupportedFieldExtension(3));
+                }
+                let public_coin = RandCoin::new(&public_coin_seed);
+                let channel = VerifierChannel::new(&air, proof)?;
+                perform_verification::<
+                    AIR,
+                    AIR::BaseField,
+                    CubeExtension<AIR::BaseField>,
+                    HashFn,
+                    _,
+                    RandCoin,
+                >(&air, channel, public_coin)
+            },
         },
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseField>>::is_supported() {
@@ -115,11 +180,14 @@ where
             }
             let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(
-                air,
-                channel,
-                public_coin,
-            )
+            perform_verification::<
+                AIR,
+                QuadExtension<AIR::BaseField>,
+                QuadExtension<AIR::BaseField>,
+                HashFn,
+                _,
+                RandCoin,
+            >(&air, channel, public_coin)
         },
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseField>>::is_supported() {
@@ -127,29 +195,73 @@ where
             }
             let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(
-                air,
-                channel,
-                public_coin,
-            )
+            perform_verification::<
+                AIR,
+                CubeExtension<AIR::BaseField>,
+                CubeExtension<AIR::BaseField>,
+                HashFn,
+                _,
+                RandCoin,
+            >(&air, channel, public_coin)
         },
     }
 }
 
+/// Verifies that the specified computations were executed correctly against the specified
+/// inputs, and returns the result of verification for each of the provided proofs.
+///
+/// Each of the proofs is verified exactly as it would be verified by [verify()]; the returned
+/// vector contains a result for every `(proof, pub_inputs)` pair in the order in which the pairs
+/// were provided, and thus, an invalid proof does not affect the results for other proofs in the
+/// batch.
+///
+/// When the `concurrent` feature is enabled, proofs are verified in parallel using all available
+/// worker threads. Merkle authentication paths of each proof are already verified as batch
+/// openings by [verify()]; FRI instances of different proofs are not combined into a single
+/// low-degree test because each proof is generated with its own Fiat-Shamir transcript.
+pub fn verify_batch<AIR, HashFn, RandCoin>(
+    proofs: Vec<(Proof, AIR::PublicInputs)>,
+    acceptable_options: &AcceptableOptions,
+) -> Vec<Result<(), VerifierError>>
+where
+    AIR: Air,
+    AIR::PublicInputs: Send,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: Transcript<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    #[cfg(not(feature = "concurrent"))]
+    let proofs = proofs.into_iter();
+
+    #[cfg(feature = "concurrent")]
+    let proofs = proofs.into_par_iter();
+
+    proofs
+        .map(|(proof, pub_inputs)| {
+            verify::<AIR, HashFn, RandCoin>(proof, pub_inputs, acceptable_options)
+        })
+        .collect()
+}
+
 // VERIFICATION PROCEDURE
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H, R>(
-    air: A,
-    mut channel: VerifierChannel<E, H>,
+///
+/// Random elements needed to build the auxiliary trace and the constraint composition polynomial
+/// are drawn from the field `F`, while the out-of-domain point and all subsequent random elements
+/// are drawn from the field `E`, which is either the same as `F` or its extension.
+fn perform_verification<A, F, E, H, C, R>(
+    air: &A,
+    mut channel: C,
     mut public_coin: R,
 ) -> Result<(), VerifierError>
 where
-    E: FieldElement<BaseField = A::BaseField>,
+    F: FieldElement<BaseField = A::BaseField>,
+    E: FieldElement<BaseField = A::BaseField> + ExtensionOf<F>,
     A: Air,
     H: ElementHasher<BaseField = A::BaseField>,
-    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    C: ProofChannel<F, E, Hasher = H>,
+    R: Transcript<BaseField = A::BaseField, Hasher = H>,
 {
     // 1 ----- trace commitment -------------------------------------------------------------------
     // Read the commitments to evaluations of the trace polynomials over the LDE domain sent by the
@@ -161,51 +273,20 @@ where
     // commitment is used to draw a set of random coefficients which the prover uses to compute
     // constraint composition polynomial.
     const MAIN_TRACE_IDX: usize = 0;
-    const AUX_TRACE_IDX: usize = 1;
     let trace_commitments = channel.read_trace_commitments();
 
     // reseed the coin with the commitment to the main trace segment
-    public_coin.reseed(trace_commitments[MAIN_TRACE_IDX]);
+    public_coin.absorb(TranscriptLabel::TraceCommitment, trace_commitments[MAIN_TRACE_IDX]);
 
     // process auxiliary trace segments (if any), to build a set of random elements for each segment
-    let aux_trace_rand_elements = if air.trace_info().is_multi_segment() {
-        if air.context().has_lagrange_kernel_aux_column() {
-            let gkr_proof = {
-                let gkr_proof_serialized = channel
-                    .read_gkr_proof()
-                    .expect("Expected an a GKR proof because trace has lagrange kernel column");
-
-                Deserializable::read_from_bytes(gkr_proof_serialized)
-                    .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?
-            };
-            let lagrange_rand_elements = air
-                .get_auxiliary_proof_verifier::<E>()
-                .verify::<E, _>(gkr_proof, &mut public_coin)
-                .map_err(|err| VerifierError::GkrProofVerificationFailed(err.to_string()))?;
-
-            let rand_elements = air.get_aux_rand_elements(&mut public_coin).expect(
-                "failed to generate the random elements needed to build the auxiliary trace",
-            );
-
-            public_coin.reseed(trace_commitments[AUX_TRACE_IDX]);
-
-            Some(AuxRandElements::new_with_lagrange(rand_elements, Some(lagrange_rand_elements)))
-        } else {
-            let rand_elements = air.get_aux_rand_elements(&mut public_coin).expect(
-                "failed to generate the random elements needed to build the auxiliary trace",
-            );
-
-            public_coin.reseed(trace_commitments[AUX_TRACE_IDX]);
-
-            Some(AuxRandElements::new(rand_elements))
-        }
-    } else {
-        None
-    };
+    let aux_trace_rand_elements =
+        draw_aux_rand_elements(air, &channel, &mut public_coin, trace_commitments)?
+            .map(lift_aux_rand_elements::<F, E>);
 
     // build random coefficients for the composition polynomial
     let constraint_coeffs = air
-        .get_constraint_composition_coefficients(&mut public_coin)
+        .get_constraint_composition_coefficients::<F, R>(&mut public_coin)
+        .map(lift_constraint_coeffs::<F, E>)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 2 ----- constraint commitment --------------------------------------------------------------
@@ -215,8 +296,10 @@ where
     // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
     // and sends the results back to the verifier.
     let constraint_commitment = channel.read_constraint_commitment();
-    public_coin.reseed(constraint_commitment);
-    let z = public_coin.draw::<E>().map_err(|_| VerifierError::RandomCoinError)?;
+    public_coin.absorb(TranscriptLabel::ConstraintCommitment, constraint_commitment);
+    let z = public_coin
+        .squeeze::<E>(TranscriptLabel::OodTraceFrame)
+        .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
     // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
@@ -225,12 +308,12 @@ where
     // read the out-of-domain trace frames (the main trace frame and auxiliary trace frame, if
     // provided) sent by the prover and evaluate constraints over them; also, reseed the public
     // coin with the OOD frames received from the prover.
-    let ood_trace_frame = channel.read_ood_trace_frame();
+    let ood_trace_frame = channel.read_ood_trace_frame()?;
     let ood_main_trace_frame = ood_trace_frame.main_frame();
     let ood_aux_trace_frame = ood_trace_frame.aux_frame();
     let ood_lagrange_kernel_frame = ood_trace_frame.lagrange_kernel_frame();
     let ood_constraint_evaluation_1 = evaluate_constraints(
-        &air,
+        air,
         constraint_coeffs,
         &ood_main_trace_frame,
         &ood_aux_trace_frame,
@@ -238,24 +321,29 @@ where
         aux_trace_rand_elements.as_ref(),
         z,
     );
-    public_coin.reseed(ood_trace_frame.hash::<H>());
+    public_coin.absorb(TranscriptLabel::OodTraceFrame, ood_trace_frame.hash::<H>());
 
     // read evaluations of composition polynomial columns sent by the prover, and reduce them into
     // a single value by computing \sum_{i=0}^{m-1}(z^(i * l) * value_i), where value_i is the
     // evaluation of the ith column polynomial H_i(X) at z, l is the trace length and m is
     // the number of composition column polynomials. This computes H(z) (i.e.
     // the evaluation of the composition polynomial at z) using the fact that
-    // H(X) = \sum_{i=0}^{m-1} X^{i * l} H_i(X).
+    // H(X) = \sum_{i=0}^{m-1} X^{i * l} H_i(X). For zero-knowledge proofs, the last column is
+    // a random polynomial which is not a part of H(X), and thus, it is skipped.
     // Also, reseed the public coin with the OOD constraint evaluations received from the prover.
-    let ood_constraint_evaluations = channel.read_ood_constraint_evaluations();
-    let ood_constraint_evaluation_2 =
-        ood_constraint_evaluations
-            .iter()
-            .enumerate()
-            .fold(E::ZERO, |result, (i, &value)| {
-                result + z.exp_vartime(((i * (air.trace_length())) as u32).into()) * value
-            });
-    public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
+    let ood_constraint_evaluations = channel.read_ood_constraint_evaluations()?;
+    let num_randomizer_columns = air.context().num_composition_randomizer_columns();
+    let ood_constraint_evaluation_2 = ood_constraint_evaluations
+        [..ood_constraint_evaluations.len() - num_randomizer_columns]
+        .iter()
+        .enumerate()
+        .fold(E::ZERO, |result, (i, &value)| {
+            result + z.exp_vartime(((i * (air.trace_length())) as u32).into()) * value
+        });
+    public_coin.absorb(
+        TranscriptLabel::OodConstraintEvaluations,
+        H::hash_elements(&ood_constraint_evaluations),
+    );
 
     // finally, make sure the values are the same
     if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
@@ -298,14 +386,7 @@ where
     // interactive version of the protocol, the verifier sends these query positions to the prover,
     // and the prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
-    let mut query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size(), pow_nonce)
-        .map_err(|_| VerifierError::RandomCoinError)?;
-
-    // remove any potential duplicates from the positions as the prover will send openings only
-    // for unique queries
-    query_positions.sort_unstable();
-    query_positions.dedup();
+    let query_positions = draw_query_positions(air, &mut public_coin, pow_nonce)?;
 
     // read evaluations of trace and constraint composition polynomials at the queried positions;
     // this also checks that the read values are valid against trace and constraint commitments
@@ -315,7 +396,7 @@ where
 
     // 6 ----- DEEP composition -------------------------------------------------------------------
     // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
+    let composer = DeepComposer::new(air, &query_positions, z, deep_coefficients);
     let t_composition = composer.compose_trace_columns(
         queried_main_trace_states,
         queried_aux_trace_states,
@@ -329,16 +410,149 @@ where
 
     // 7 ----- Verify low-degree proof -------------------------------------------------------------
     // make sure that evaluations of the DEEP composition polynomial we computed in the previous
-    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
-    fri_verifier
-        .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree;
+    // an inconsistency at the first FRI layer means that the DEEP composition polynomial
+    // evaluations we computed do not match the evaluations committed to by the prover
+    fri_verifier.verify(&mut channel, &deep_evaluations, &query_positions).map_err(
+        |err| match err {
+            fri::VerifierError::InvalidLayerFolding(0, position) => {
+                VerifierError::InconsistentDeepComposition(position)
+            },
+            err => VerifierError::FriVerificationFailed(err),
+        },
+    )
+}
+
+/// Draws random elements needed to build auxiliary trace segments (if any) from the public coin.
+///
+/// If the trace has a Lagrange kernel column, the GKR proof is read from the `channel` and
+/// verified first. The public coin is reseeded with the commitment to the auxiliary trace segment
+/// after the random elements have been drawn.
+fn draw_aux_rand_elements<A, F, E, H, C, R>(
+    air: &A,
+    channel: &C,
+    public_coin: &mut R,
+    trace_commitments: &[H::Digest],
+) -> Result<Option<AuxRandElements<F>>, VerifierError>
+where
+    F: FieldElement<BaseField = A::BaseField>,
+    E: FieldElement<BaseField = A::BaseField> + ExtensionOf<F>,
+    A: Air,
+    H: ElementHasher<BaseField = A::BaseField>,
+    C: ProofChannel<F, E, Hasher = H>,
+    R: Transcript<BaseField = A::BaseField, Hasher = H>,
+{
+    const AUX_TRACE_IDX: usize = 1;
+
+    if !air.trace_info().is_multi_segment() {
+        return Ok(None);
+    }
+
+    let lagrange_rand_elements = if air.context().has_lagrange_kernel_aux_column() {
+        let gkr_proof = {
+            let gkr_proof_serialized = channel
+                .read_gkr_proof()
+                .expect("Expected an a GKR proof because trace has lagrange kernel column");
+
+            Deserializable::read_from_bytes(gkr_proof_serialized)
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?
+        };
+        let lagrange_rand_elements = air
+            .get_auxiliary_proof_verifier::<F>()
+            .verify::<F, _>(gkr_proof, public_coin)
+            .map_err(|err| VerifierError::GkrProofVerificationFailed(err.to_string()))?;
+        Some(lagrange_rand_elements)
+    } else {
+        None
+    };
+
+    let rand_elements = air
+        .get_aux_rand_elements(public_coin)
+        .expect("failed to generate the random elements needed to build the auxiliary trace");
+
+    public_coin.absorb(TranscriptLabel::TraceCommitment, trace_commitments[AUX_TRACE_IDX]);
+
+    Ok(Some(AuxRandElements::new_with_lagrange(rand_elements, lagrange_rand_elements)))
+}
+
+/// Draws pseudo-random query positions for the LDE domain from the public coin using the
+/// specified proof-of-work `nonce`.
+///
+/// The returned positions are sorted and contain no duplicates as the prover sends openings only
+/// for unique queries. Positions are drawn without replacement, and thus, the number of returned
+/// positions is always equal to the number of queries specified by the proof options; otherwise,
+/// security of the proof would be lower than the one estimated from the proof options.
+fn draw_query_positions<A, H, R>(
+    air: &A,
+    public_coin: &mut R,
+    nonce: u64,
+) -> Result<Vec<usize>, VerifierError>
+where
+    A: Air,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: Transcript<BaseField = A::BaseField, Hasher = H>,
+{
+    let mut query_positions = public_coin
+        .squeeze_integers(
+            TranscriptLabel::QuerySeed,
+            air.options().num_queries(),
+            air.lde_domain_size(),
+            nonce,
+        )
+        .map_err(|_| VerifierError::RandomCoinError)?;
+
+    query_positions.sort_unstable();
+    query_positions.dedup();
+    if query_positions.len() != air.options().num_queries() {
+        return Err(VerifierError::RandomCoinError);
+    }
+
+    Ok(query_positions)
+}
+
+/// Maps random elements drawn for the auxiliary trace from the field `F` into its extension `E`.
+fn lift_aux_rand_elements<F, E>(elements: AuxRandElements<F>) -> AuxRandElements<E>
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let rand_elements = elements.rand_elements().iter().copied().map(E::from).collect();
+    let lagrange = elements
+        .lagrange()
+        .map(|lagrange| LagrangeKernelRandElements::new(lift_elements(lagrange)));
+    AuxRandElements::new_with_lagrange(rand_elements, lagrange)
+}
+
+/// Maps constraint composition coefficients drawn from the field `F` into its extension `E`.
+fn lift_constraint_coeffs<F, E>(
+    coeffs: ConstraintCompositionCoefficients<F>,
+) -> ConstraintCompositionCoefficients<E>
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    ConstraintCompositionCoefficients {
+        transition: lift_elements(&coeffs.transition),
+        boundary: lift_elements(&coeffs.boundary),
+        lagrange: coeffs.lagrange.map(|lagrange| LagrangeConstraintsCompositionCoefficients {
+            transition: lift_elements(&lagrange.transition),
+            boundary: E::from(lagrange.boundary),
+        }),
+    }
+}
+
+fn lift_elements<F, E>(elements: &[F]) -> Vec<E>
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    elements.iter().copied().map(E::from).collect()
 }
 
 // ACCEPTABLE OPTIONS
 // ================================================================================================
-// Specifies either the minimal, conjectured or proven, security level or a set of
-// `ProofOptions` that are acceptable by the verification procedure.
+// Specifies either the minimal, conjectured or proven, security level, a set of `ProofOptions`, or
+// a policy on individual proof parameters that are acceptable by the verification procedure.
 pub enum AcceptableOptions {
     /// Minimal acceptable conjectured security level
     MinConjecturedSecurity(u32),
@@ -346,14 +560,25 @@ pub enum AcceptableOptions {
     MinProvenSecurity(u32),
     /// Set of acceptable proof parameters
     OptionSet(Vec<ProofOptions>),
+    /// Policy with requirements on individual proof parameters
+    Policy(OptionsPolicy),
 }
 
 impl AcceptableOptions {
     /// Checks that a proof was generated using an acceptable set of parameters.
     pub fn validate<H: Hasher>(&self, proof: &Proof) -> Result<(), VerifierError> {
+        self.validate_context::<H>(&proof.context)
+    }
+
+    /// Checks that a proof with the specified context was generated using an acceptable set of
+    /// parameters.
+    pub(crate) fn validate_context<H: Hasher>(
+        &self,
+        context: &Context,
+    ) -> Result<(), VerifierError> {
         match self {
             AcceptableOptions::MinConjecturedSecurity(minimal_security) => {
-                let proof_security = proof.security_level::<H>(true);
+                let proof_security = context.security_level::<H>(true);
                 if proof_security < *minimal_security {
                     return Err(VerifierError::InsufficientConjecturedSecurity(
                         *minimal_security,
@@ -362,7 +587,7 @@ impl AcceptableOptions {
                 }
             },
             AcceptableOptions::MinProvenSecurity(minimal_security) => {
-                let proof_security = proof.security_level::<H>(false);
+                let proof_security = context.security_level::<H>(false);
                 if proof_security < *minimal_security {
                     return Err(VerifierError::InsufficientProvenSecurity(
                         *minimal_security,
@@ -371,10 +596,151 @@ impl AcceptableOptions {
                 }
             },
             AcceptableOptions::OptionSet(options) => {
-                if !options.iter().any(|opt| opt == proof.options()) {
+                if !options.iter().any(|opt| opt == context.options()) {
                     return Err(VerifierError::UnacceptableProofOptions);
                 }
             },
+            AcceptableOptions::Policy(policy) => policy.validate::<H>(context)?,
+        }
+        Ok(())
+    }
+}
+
+// OPTIONS POLICY
+// ================================================================================================
+
+/// Requirements on individual parameters of proofs acceptable by the verification procedure.
+///
+/// A new policy accepts proofs generated with any parameters; requirements are added via the
+/// `with_*` methods. The policy is checked against the options embedded in a proof before any
+/// cryptographic work is done, and thus, proofs which do not satisfy the policy are rejected
+/// cheaply.
+///
+/// A minimal security level required via [with_min_security_level()](Self::with_min_security_level)
+/// is checked using the soundness bounds declared by the proof options (see
+/// [Proof::declared_security_level()]). Thus, to require provable security bounds, the policy
+/// should also require proofs to declare proven security via
+/// [with_proven_security()](Self::with_proven_security).
+///
+/// # Examples
+/// ```
+/// # use winter_verifier::{AcceptableOptions, FieldExtension, HashFunction, OptionsPolicy};
+/// let policy = OptionsPolicy::new()
+///     .with_min_num_queries(27)
+///     .with_min_blowup_factor(8)
+///     .with_min_security_level(100)
+///     .with_proven_security()
+///     .with_hash_functions(&[HashFunction::Blake3_256, HashFunction::Rp64_256])
+///     .with_field_extensions(&[FieldExtension::Quadratic, FieldExtension::Cubic]);
+/// let acceptable_options = AcceptableOptions::Policy(policy);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptionsPolicy {
+    min_num_queries: usize,
+    min_blowup_factor: usize,
+    min_security_level: u32,
+    proven_security: bool,
+    hash_functions: Option<Vec<HashFunction>>,
+    field_extensions: Option<Vec<FieldExtension>>,
+}
+
+impl OptionsPolicy {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new policy which accepts proofs generated with any parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires proofs to be generated with at least the specified number of queries.
+    pub fn with_min_num_queries(mut self, num_queries: usize) -> Self {
+        self.min_num_queries = num_queries;
+        self
+    }
+
+    /// Requires proofs to be generated with at least the specified blowup factor.
+    pub fn with_min_blowup_factor(mut self, blowup_factor: usize) -> Self {
+        self.min_blowup_factor = blowup_factor;
+        self
+    }
+
+    /// Requires proofs to achieve at least the specified security level (in bits), computed using
+    /// the soundness bounds declared by the proof options.
+    pub fn with_min_security_level(mut self, bits: u32) -> Self {
+        self.min_security_level = bits;
+        self
+    }
+
+    /// Requires proof options to declare proven security, i.e., that the security level of
+    /// proofs is computed using provable, rather than conjectured, soundness bounds.
+    pub fn with_proven_security(mut self) -> Self {
+        self.proven_security = true;
+        self
+    }
+
+    /// Requires proofs to be generated with one of the specified hash functions.
+    ///
+    /// Hash functions which cannot be selected at runtime via [HashFunction] (e.g., custom hash
+    /// functions) are rejected by such a policy.
+    pub fn with_hash_functions(mut self, hash_fns: &[HashFunction]) -> Self {
+        self.hash_functions = Some(hash_fns.to_vec());
+        self
+    }
+
+    /// Requires proofs to be generated with one of the specified field extensions.
+    pub fn with_field_extensions(mut self, field_extensions: &[FieldExtension]) -> Self {
+        self.field_extensions = Some(field_extensions.to_vec());
+        self
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that proofs with the specified context, generated using hash function `H`, satisfy
+    /// this policy.
+    ///
+    /// # Errors
+    /// Returns an error describing the first requirement of this policy which is not satisfied.
+    pub fn validate<H: Hasher>(&self, context: &Context) -> Result<(), VerifierError> {
+        let options = context.options();
+        if options.num_queries() < self.min_num_queries {
+            return Err(VerifierError::InsufficientNumQueries(
+                self.min_num_queries,
+                options.num_queries(),
+            ));
+        }
+        if options.blowup_factor() < self.min_blowup_factor {
+            return Err(VerifierError::InsufficientBlowupFactor(
+                self.min_blowup_factor,
+                options.blowup_factor(),
+            ));
+        }
+        if let Some(hash_fns) = &self.hash_functions {
+            if !H::HASH_FUNCTION.is_some_and(|hash_fn| hash_fns.contains(&hash_fn)) {
+                return Err(VerifierError::UnacceptableHashFunction(H::HASH_FUNCTION));
+            }
+        }
+        if let Some(field_extensions) = &self.field_extensions {
+            if !field_extensions.contains(&options.field_extension()) {
+                return Err(VerifierError::UnacceptableFieldExtension(
+                    options.field_extension().degree() as usize,
+                ));
+            }
+        }
+        if self.proven_security && !options.uses_proven_security() {
+            return Err(VerifierError::ProvenSecurityRequired);
+        }
+        let security_level = context.declared_security_level::<H>();
+        if security_level < self.min_security_level {
+            return Err(if options.uses_proven_security() {
+                VerifierError::InsufficientProvenSecurity(self.min_security_level, security_level)
+            } else {
+                VerifierError::InsufficientConjecturedSecurity(
+                    self.min_security_level,
+                    security_level,
+                )
+            });
         }
         Ok(())
     }
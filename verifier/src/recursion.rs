@@ -0,0 +1,508 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Building blocks for verifying STARK proofs recursively.
+//!
+//! To verify a proof inside another computation (i.e., inside an AIR), the computations performed
+//! by the verifier need to be re-executed by the execution trace of this computation. This module
+//! exposes these computations in a form suitable for such re-execution:
+//!
+//! * [replay_transcript()] replays the Fiat-Shamir transcript of a proof and returns all random
+//!   values drawn by the verifier as [VerifierChallenges]. This does not check the proof; it
+//!   only derives the values against which the proof would be checked.
+//! * [compose_trace_columns()] and [compose_constraint_columns()] compute a single evaluation of
+//!   the DEEP composition polynomial from the queried trace and constraint evaluations.
+//! * [fold_position()], [fold_evaluations()], and [evaluate_remainder()] perform the checks of
+//!   the FRI query phase for a single query.
+//! * [extract_query_witness()] extracts from a proof all values which are checked at the queried
+//!   positions as a [QueryWitness].
+//!
+//! All of these functions, apart from [replay_transcript()] and [extract_query_witness()], are pure functions over generic
+//! [FieldElement]s which do not allocate; their documentation describes the relations they
+//! compute in a form which can be expressed as polynomial constraints over the inputs and the
+//! output.
+//!
+//! Hashing is not covered by this module: to verify a proof recursively, Merkle authentication
+//! paths of the queried values and the public coin need to be verified by the outer computation
+//! as well (usually, via an arithmetization of an algebraic hash function). DEEP-FRI folding and
+//! Lagrange kernel columns are not covered either.
+//!
+//! For a description of the full verification, including hashing, see the
+//! [circuit](crate::circuit) module.
+
+use alloc::{string::ToString, vec::Vec};
+
+use air::{
+    proof::TraceOodFrame, Air, AuxRandElements, ConstraintCompositionCoefficients,
+    DeepCompositionCoefficients, PublicInputs,
+};
+use crypto::{ElementHasher, MerkleTree, Transcript, TranscriptLabel};
+use fri::FriVerifier;
+use math::{FieldElement, StarkField, ToElements};
+
+use crate::{
+    channel::{ProofChannel, VerifierChannel},
+    draw_aux_rand_elements, draw_query_positions, Proof, VerifierError,
+};
+
+// VERIFIER CHALLENGES
+// ================================================================================================
+
+/// Random values drawn by the verifier from the public coin while verifying a proof.
+///
+/// The values are listed in the order in which they are drawn during the verification.
+#[derive(Debug, Clone)]
+pub struct VerifierChallenges<E: FieldElement> {
+    /// Random elements used to build auxiliary trace segments, if the trace has any.
+    pub aux_rand_elements: Option<AuxRandElements<E>>,
+    /// Coefficients for the random linear combination of constraints.
+    pub constraint_coefficients: ConstraintCompositionCoefficients<E>,
+    /// Out-of-domain point z at which trace and constraint composition polynomials are evaluated.
+    pub z: E,
+    /// Coefficients for the random linear combination of the DEEP composition polynomial.
+    pub deep_coefficients: DeepCompositionCoefficients<E>,
+    /// Values α used to fold each FRI layer, including the value drawn after the commitment to
+    /// the remainder.
+    pub fri_alphas: Vec<E>,
+    /// Sorted positions in the LDE domain at which the prover was queried, without duplicates.
+    pub query_positions: Vec<usize>,
+}
+
+// TRANSCRIPT REPLAY
+// ================================================================================================
+
+/// Replays the Fiat-Shamir transcript of the specified `proof` and returns the values drawn by
+/// the verifier from the public coin.
+///
+/// The public coin is seeded and updated exactly as in [verify()](crate::verify); however, the
+/// proof is not checked: out-of-domain evaluations, query openings, and FRI layers are not
+/// verified. Proof-of-work nonces are checked since the query positions are derived from them.
+///
+/// # Errors
+/// Returns an error if the proof cannot be parsed, if a GKR proof included in the proof fails to
+/// verify, if a random value cannot be drawn from the public coin, or if proof-of-work nonces do
+/// not satisfy the grinding factors specified by the proof options.
+///
+/// # Panics
+/// Panics if the degree of `E` does not match the field extension specified by the proof options,
+/// or if the out-of-domain point is drawn from a different field extension.
+pub fn replay_transcript<AIR, E, HashFn, RandCoin>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<VerifierChallenges<E>, VerifierError>
+where
+    AIR: Air,
+    E: FieldElement<BaseField = AIR::BaseField>,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: Transcript<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    assert_eq!(
+        proof.options().field_extension().degree() as usize,
+        E::EXTENSION_DEGREE,
+        "field extension of the proof does not match the specified field"
+    );
+    assert_eq!(
+        proof.options().ood_field_extension(),
+        proof.options().field_extension(),
+        "proofs with a separate out-of-domain field extension are not supported"
+    );
+
+    let mut public_coin_seed = proof.context.to_elements();
+    public_coin_seed.append(&mut pub_inputs.to_transcript_elements());
+    let air = AIR::new(proof.trace_info().clone(), pub_inputs, proof.options().clone());
+
+    let public_coin = RandCoin::new(&public_coin_seed);
+    let mut channel = VerifierChannel::<E, E, HashFn>::new(&air, proof)?;
+    let (challenges, ..) = replay(&air, &mut channel, public_coin)?;
+    Ok(challenges)
+}
+
+// QUERY WITNESS
+// ================================================================================================
+
+/// Values of a proof which are checked by the verifier at the queried positions.
+///
+/// Together with [VerifierChallenges], these are all the values needed to re-execute the query
+/// phase of the verifier (apart from the authentication of FRI layer values) inside another
+/// computation.
+#[derive(Debug, Clone)]
+pub struct QueryWitness<E: FieldElement> {
+    /// Random values drawn by the verifier from the public coin of the proof.
+    pub challenges: VerifierChallenges<E>,
+    /// Size of the LDE domain of the proof.
+    pub domain_size: usize,
+    /// Offset of the LDE domain of the proof.
+    pub domain_offset: E::BaseField,
+    /// Evaluations of the DEEP composition polynomial at the queried positions.
+    pub deep_evaluations: Vec<E>,
+    /// For each queried position, the values opened at each of the FRI layers for the position
+    /// into which the queried position is folded; the values are listed as expected by
+    /// [fold_evaluations()].
+    pub layer_values: Vec<Vec<Vec<E>>>,
+    /// Coefficients of the FRI remainder polynomial, from the lowest to the highest degree.
+    pub remainder: Vec<E>,
+}
+
+/// Extracts the values which the verifier checks at the queried positions of the specified
+/// `proof`.
+///
+/// The transcript of the proof is replayed as in [replay_transcript()], and the queried trace
+/// rows and constraint evaluations are authenticated against the commitments of the proof. These
+/// are then combined into evaluations of the DEEP composition polynomial at the queried positions
+/// using [compose_trace_columns()] and [compose_constraint_columns()]. FRI layer values are
+/// parsed, but not authenticated against the FRI layer commitments, and the FRI folding is not
+/// checked; thus, the proof should be checked via [verify()](crate::verify) before using the
+/// returned values.
+///
+/// # Errors
+/// Returns an error if the transcript of the proof cannot be replayed or if the queried values
+/// cannot be parsed or authenticated.
+///
+/// # Panics
+/// Panics if:
+/// * The degree of `E` does not match the field extension specified by the proof options, or the
+///   out-of-domain point is drawn from a different field extension.
+/// * The trace of the proof contains a Lagrange kernel column.
+pub fn extract_query_witness<AIR, E, HashFn, RandCoin>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<QueryWitness<E>, VerifierError>
+where
+    AIR: Air,
+    E: FieldElement<BaseField = AIR::BaseField>,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: Transcript<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    assert_eq!(
+        proof.options().field_extension().degree() as usize,
+        E::EXTENSION_DEGREE,
+        "field extension of the proof does not match the specified field"
+    );
+    assert_eq!(
+        proof.options().ood_field_extension(),
+        proof.options().field_extension(),
+        "proofs with a separate out-of-domain field extension are not supported"
+    );
+
+    let mut public_coin_seed = proof.context.to_elements();
+    public_coin_seed.append(&mut pub_inputs.to_transcript_elements());
+    let air = AIR::new(proof.trace_info().clone(), pub_inputs, proof.options().clone());
+    assert!(
+        !air.context().has_lagrange_kernel_aux_column(),
+        "proofs with Lagrange kernel columns are not supported"
+    );
+
+    let domain_size = air.lde_domain_size();
+    let domain_offset = air.domain_offset();
+    let fri_options = air.options().to_fri_options();
+    let fri_proof = proof.fri_proof.clone();
+
+    let public_coin = RandCoin::new(&public_coin_seed);
+    let mut channel = VerifierChannel::<E, E, HashFn>::new(&air, proof)?;
+    let (challenges, ood_trace_frame, ood_constraint_evaluations) =
+        replay(&air, &mut channel, public_coin)?;
+    let positions = &challenges.query_positions;
+
+    // compute evaluations of the DEEP composition polynomial from the authenticated trace rows
+    // and constraint evaluations
+    let (main_states, aux_states) = channel.read_queried_trace_states(positions)?;
+    let constraint_evaluations = channel.read_constraint_evaluations(positions)?;
+    let g_trace = air.trace_domain_generator();
+    let ood_points: Vec<E> = ood_trace_frame
+        .offsets()
+        .iter()
+        .map(|&offset| ood_point(challenges.z, g_trace, offset))
+        .collect();
+    let ood_rows: Vec<&[E]> =
+        (0..ood_trace_frame.num_rows()).map(|k| ood_trace_frame.row(k)).collect();
+    let cc = &challenges.deep_coefficients;
+    let deep_evaluations = positions
+        .iter()
+        .enumerate()
+        .map(|(i, &position)| {
+            let x = domain_point(domain_offset, domain_size, position);
+            let mut trace_values: Vec<E> =
+                main_states.get_row(i).iter().map(|&value| E::from(value)).collect();
+            if let Some(aux_states) = aux_states.as_ref() {
+                trace_values.extend_from_slice(aux_states.get_row(i));
+            }
+            compose_trace_columns(x, &ood_points, &trace_values, &ood_rows, &cc.trace)
+                + compose_constraint_columns(
+                    x,
+                    challenges.z,
+                    constraint_evaluations.get_row(i),
+                    &ood_constraint_evaluations,
+                    &cc.constraints,
+                )
+        })
+        .collect();
+
+    // collect the values opened at each FRI layer for each query; the values of a layer are
+    // listed in the order in which the folded positions were first queried
+    let remainder = fri_proof
+        .parse_remainder::<E>()
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    let (layer_queries, ..) = fri_proof
+        .parse_layers::<HashFn, E, MerkleTree<HashFn>>(domain_size, &fri_options)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+    let mut layer_values: Vec<Vec<Vec<E>>> =
+        (0..positions.len()).map(|_| Vec::with_capacity(layer_queries.len())).collect();
+    let mut layer_positions = positions.clone();
+    let mut layer_domain_size = domain_size;
+    for (depth, layer) in layer_queries.iter().enumerate() {
+        let folding_factor = fri_options.folding_factor_at(depth);
+        let mut folded_positions = Vec::new();
+        for &position in layer_positions.iter() {
+            let (folded_position, _) = fold_position(position, layer_domain_size, folding_factor);
+            if !folded_positions.contains(&folded_position) {
+                folded_positions.push(folded_position);
+            }
+        }
+        for (values, &position) in layer_values.iter_mut().zip(positions.iter()) {
+            let position = position % layer_domain_size;
+            let (folded_position, _) = fold_position(position, layer_domain_size, folding_factor);
+            let idx = folded_positions.iter().position(|&p| p == folded_position).unwrap();
+            values.push(layer[idx * folding_factor..(idx + 1) * folding_factor].to_vec());
+        }
+        layer_positions = folded_positions;
+        layer_domain_size /= folding_factor;
+    }
+
+    Ok(QueryWitness {
+        challenges,
+        domain_size,
+        domain_offset,
+        deep_evaluations,
+        layer_values,
+        remainder,
+    })
+}
+
+/// Replays the transcript of the proof read from the specified `channel` and returns the values
+/// drawn by the verifier together with the out-of-domain evaluations read from the channel.
+#[allow(clippy::type_complexity)]
+fn replay<AIR, E, HashFn, RandCoin>(
+    air: &AIR,
+    channel: &mut VerifierChannel<E, E, HashFn>,
+    mut public_coin: RandCoin,
+) -> Result<(VerifierChallenges<E>, TraceOodFrame<E>, Vec<E>), VerifierError>
+where
+    AIR: Air,
+    E: FieldElement<BaseField = AIR::BaseField>,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: Transcript<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    // trace and constraint commitments
+    let trace_commitments = channel.read_trace_commitments();
+    public_coin.absorb(TranscriptLabel::TraceCommitment, trace_commitments[0]);
+    let aux_rand_elements =
+        draw_aux_rand_elements(air, channel, &mut public_coin, trace_commitments)?;
+    let constraint_coefficients = air
+        .get_constraint_composition_coefficients(&mut public_coin)
+        .map_err(|_| VerifierError::RandomCoinError)?;
+
+    public_coin.absorb(TranscriptLabel::ConstraintCommitment, channel.read_constraint_commitment());
+    let z = public_coin
+        .squeeze::<E>(TranscriptLabel::OodTraceFrame)
+        .map_err(|_| VerifierError::RandomCoinError)?;
+
+    // out-of-domain evaluations
+    let ood_trace_frame = channel.read_ood_trace_frame()?;
+    public_coin.absorb(TranscriptLabel::OodTraceFrame, ood_trace_frame.hash::<HashFn>());
+    let ood_constraint_evaluations = channel.read_ood_constraint_evaluations()?;
+    public_coin.absorb(
+        TranscriptLabel::OodConstraintEvaluations,
+        HashFn::hash_elements(&ood_constraint_evaluations),
+    );
+
+    // FRI commitments
+    let deep_coefficients = air
+        .get_deep_composition_coefficients::<E, RandCoin>(&mut public_coin)
+        .map_err(|_| VerifierError::RandomCoinError)?;
+    let fri_verifier = FriVerifier::new(
+        channel,
+        &mut public_coin,
+        air.options().to_fri_options(),
+        air.trace_poly_degree(),
+    )
+    .map_err(VerifierError::FriVerificationFailed)?;
+
+    // query positions
+    let pow_nonce = channel.read_pow_nonce();
+    if public_coin.check_leading_zeros(pow_nonce) < air.options().grinding_factor() {
+        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
+    }
+    let query_positions = draw_query_positions(air, &mut public_coin, pow_nonce)?;
+
+    let challenges = VerifierChallenges {
+        aux_rand_elements,
+        constraint_coefficients,
+        z,
+        deep_coefficients,
+        fri_alphas: fri_verifier.layer_alphas().to_vec(),
+        query_positions,
+    };
+    Ok((challenges, ood_trace_frame, ood_constraint_evaluations))
+}
+
+// DEEP COMPOSITION
+// ================================================================================================
+
+/// Returns the point at which the evaluation domain with the specified `offset` and `size`
+/// is evaluated at the specified `position`; that is, offset * g^position, where g is the
+/// generator of the multiplicative subgroup of the specified size.
+///
+/// All layers of the FRI protocol use the offset of the LDE domain; thus, this function returns
+/// the x coordinates of the queried positions for the LDE domain as well as for all FRI layers.
+pub fn domain_point<B: StarkField>(offset: B, size: usize, position: usize) -> B {
+    debug_assert!(size.is_power_of_two(), "domain size must be a power of two");
+    offset * B::get_root_of_unity(size.ilog2()).exp_vartime((position as u64).into())
+}
+
+/// Returns the out-of-domain point for the evaluation frame row at the specified `row_offset`;
+/// that is, z * g^row_offset, where g is the generator of the trace domain.
+///
+/// For the default evaluation frame, the out-of-domain points are z and z * g.
+pub fn ood_point<E: FieldElement>(z: E, g_trace: E::BaseField, row_offset: usize) -> E {
+    z.mul_base(g_trace.exp_vartime((row_offset as u64).into()))
+}
+
+/// Returns the evaluation of the trace part of the DEEP composition polynomial at `x`.
+///
+/// The result is computed as:
+///
+/// sum_k sum_i c_i * (t_i - o_{k, i}) / (x - z_k)
+///
+/// where:
+/// * z_k is the k-th element of `ood_points` (see [ood_point()]).
+/// * t_i is the i-th element of `trace_values`; these are the values of the queried trace row at
+///   `x`, with the values of the main trace segment (mapped into `E`) followed by the values of
+///   the auxiliary trace segment.
+/// * o_{k, i} is the i-th element of the k-th element of `ood_trace_rows`; these are the rows of
+///   the out-of-domain trace frame, laid out in the same way as `trace_values`.
+/// * c_i is the i-th element of `coefficients`; these are the trace coefficients of
+///   [DeepCompositionCoefficients].
+///
+/// To express this computation as constraints, the output r can be split into per-row terms
+/// r = sum_k r_k, each of which is constrained as r_k * (x - z_k) = sum_i c_i * (t_i - o_{k, i}).
+///
+/// # Panics
+/// Panics if the number of `ood_trace_rows` is not equal to the number of `ood_points`.
+pub fn compose_trace_columns<E: FieldElement>(
+    x: E::BaseField,
+    ood_points: &[E],
+    trace_values: &[E],
+    ood_trace_rows: &[&[E]],
+    coefficients: &[E],
+) -> E {
+    assert_eq!(ood_points.len(), ood_trace_rows.len(), "inconsistent number of OOD rows");
+    let x = E::from(x);
+
+    ood_points.iter().zip(ood_trace_rows).fold(E::ZERO, |result, (&z, ood_row)| {
+        let numerator = trace_values
+            .iter()
+            .zip(ood_row.iter())
+            .zip(coefficients)
+            .fold(E::ZERO, |acc, ((&value, &ood_value), &cc)| acc + (value - ood_value) * cc);
+        result + numerator / (x - z)
+    })
+}
+
+/// Returns the evaluation of the constraint part of the DEEP composition polynomial at `x`.
+///
+/// The result r is computed as:
+///
+/// r = sum_i c_i * (h_i - h_i(z)) / (x - z)
+///
+/// where h_i and h_i(z) are the i-th elements of `values` and `ood_values` respectively (i.e.,
+/// the evaluations of the i-th constraint composition column at `x` and at `z`), and c_i is the
+/// i-th element of `coefficients`; these are the constraint coefficients of
+/// [DeepCompositionCoefficients]. As a constraint, this can be expressed as
+/// r * (x - z) = sum_i c_i * (h_i - h_i(z)).
+///
+/// The full evaluation of the DEEP composition polynomial at `x` is the sum of the value returned
+/// from this function and the value returned from [compose_trace_columns()].
+pub fn compose_constraint_columns<E: FieldElement>(
+    x: E::BaseField,
+    z: E,
+    values: &[E],
+    ood_values: &[E],
+    coefficients: &[E],
+) -> E {
+    let numerator = values
+        .iter()
+        .zip(ood_values)
+        .zip(coefficients)
+        .fold(E::ZERO, |acc, ((&value, &ood_value), &cc)| acc + (value - ood_value) * cc);
+    numerator / (E::from(x) - z)
+}
+
+// FRI FOLDING
+// ================================================================================================
+
+/// Returns the position in the folded domain into which the specified `position` of a FRI layer
+/// is folded, together with the index of the position's value among the values of the folded
+/// position.
+///
+/// For a layer with `domain_size` values folded by `folding_factor` N, a position p is folded
+/// into p mod (domain_size / N), and its value is the (p / (domain_size / N))-th of the N values
+/// which the prover sends for the folded position.
+pub fn fold_position(position: usize, domain_size: usize, folding_factor: usize) -> (usize, usize) {
+    let folded_domain_size = domain_size / folding_factor;
+    (position % folded_domain_size, position / folded_domain_size)
+}
+
+/// Folds the `values` of a FRI layer polynomial at a single position of the folded domain using
+/// the specified `alpha`, and returns the value of the folded polynomial.
+///
+/// The j-th element of `values` must be the evaluation of the layer polynomial f at x * ω^j,
+/// where `x` is the x coordinate of the folded position in the current layer domain (see
+/// [domain_point()]) and ω is a primitive N-th root of unity. The returned value is the
+/// evaluation at `alpha` of the polynomial of degree smaller than N interpolating these values.
+/// This is the same value as computed by the FRI verifier, and it must be equal to the value of
+/// the next layer polynomial at the folded position.
+///
+/// For N = 2, the relation between the values v_0, v_1 and the output r can be expressed with
+/// a single constraint: 2 * x * r = x * (v_0 + v_1) + alpha * (v_0 - v_1).
+///
+/// # Panics
+/// Panics if N is not a power of two.
+pub fn fold_evaluations<E: FieldElement, const N: usize>(
+    values: &[E; N],
+    x: E::BaseField,
+    alpha: E,
+) -> E {
+    assert!(N.is_power_of_two(), "folding factor must be a power of two");
+    let omega = E::BaseField::get_root_of_unity(N.ilog2());
+
+    // evaluate the interpolating polynomial at alpha using the Lagrange basis over the coset
+    // x * <ω>; for the j-th point p_j = x * ω^j, the basis polynomial at alpha is
+    // (alpha^N - x^N) * p_j / (N * x^N * (alpha - p_j))
+    let x_n = E::from(x.exp_vartime((N as u64).into()));
+    let numerator = alpha.exp_vartime((N as u64).into()) - x_n;
+    let denominator = x_n.mul_base(E::BaseField::from(N as u32));
+
+    let mut point = E::from(x);
+    let mut result = E::ZERO;
+    for &value in values {
+        if alpha == point {
+            return value;
+        }
+        result += value * point / (alpha - point);
+        point = point.mul_base(omega);
+    }
+    result * numerator / denominator
+}
+
+/// Returns the evaluation at `x` of the FRI remainder polynomial with the specified
+/// `coefficients`, listed from the lowest to the highest degree.
+///
+/// The value of the last FRI layer polynomial at a queried position must be equal to the value
+/// returned from this function for the x coordinate of this position (see [domain_point()]).
+pub fn evaluate_remainder<E: FieldElement>(coefficients: &[E], x: E::BaseField) -> E {
+    let x = E::from(x);
+    coefficients.iter().rev().fold(E::ZERO, |acc, &coeff| acc * x + coeff)
+}
@@ -0,0 +1,329 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+
+use air::{
+    proof::{Proof, Table},
+    Air, PublicInputs,
+};
+use crypto::{
+    BatchMerkleProof, DefaultRandomCoin, Digest, ElementHasher, Hasher as _, MerkleTree,
+    RandomCoin, Transcript, TranscriptLabel,
+};
+use fri::{folding::fold_positions, utils::map_positions_to_indexes};
+use math::{
+    fields::{f64::BaseElement, QuadExtension},
+    FieldElement, ToElements,
+};
+
+use super::{check_air_support, Hasher, SolidityError};
+use crate::FieldExtension;
+
+// PROOF CALLDATA
+// ================================================================================================
+
+/// Public inputs and a proof serialized into the format expected by verifier contracts emitted by
+/// [ContractGenerator](super::ContractGenerator).
+///
+/// Generated contracts expose a single `verify(uint256[] publicInputs, bytes proof)` function.
+/// Public inputs are passed as the field elements via which they are absorbed into the protocol
+/// transcript, while the proof is passed as a sequence of 32-byte big-endian words:
+/// * Commitments to the execution trace, to the constraint composition polynomial, and to all FRI
+///   layers, followed by the out-of-domain evaluation frame and the out-of-domain evaluations of
+///   the constraint composition polynomial.
+/// * The query proof-of-work nonce, the number of FRI partitions, and the FRI remainder.
+/// * For each query in the order in which queries are drawn by the verifier: the queried trace
+///   row, the queried row of the constraint composition polynomial, and the queried rows of all
+///   FRI layers, each followed by a Merkle authentication path against the corresponding
+///   commitment.
+///
+/// Elements of an extension field occupy a word per base field coordinate. Unlike in a [Proof],
+/// Merkle paths are not compressed, as verifying individual paths is cheaper than verifying a
+/// batch proof on-chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofCalldata {
+    public_inputs: Vec<u64>,
+    proof: Vec<u8>,
+}
+
+impl ProofCalldata {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes the specified proof and public inputs into calldata for a verifier contract
+    /// generated for the computation described by AIR `A`.
+    ///
+    /// The proof is not verified, and thus, serialization of an invalid proof succeeds as long as
+    /// the proof is well-formed.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The proof cannot be verified by generated contracts (e.g., it was generated with options
+    ///   which are not supported by generated contracts).
+    /// * The proof is not well-formed in the context of AIR `A`.
+    pub fn new<A: Air<BaseField = BaseElement>>(
+        proof: Proof,
+        pub_inputs: A::PublicInputs,
+    ) -> Result<Self, SolidityError> {
+        let public_inputs = pub_inputs
+            .to_transcript_elements()
+            .iter()
+            .map(|element| element.as_int())
+            .collect::<Vec<_>>();
+
+        let mut seed = proof.context.to_elements();
+        seed.append(&mut pub_inputs.to_transcript_elements());
+        let air = A::new(proof.trace_info().clone(), pub_inputs, proof.options().clone());
+        check_air_support(&air)?;
+
+        let proof = match air.options().field_extension() {
+            FieldExtension::None => serialize::<A, BaseElement>(&air, proof, &seed)?,
+            FieldExtension::Quadratic => {
+                serialize::<A, QuadExtension<BaseElement>>(&air, proof, &seed)?
+            },
+            FieldExtension::Cubic => return Err(SolidityError::UnsupportedFieldExtension(3)),
+        };
+
+        Ok(Self { public_inputs, proof })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public inputs which should be passed to the `verify()` function of a verifier
+    /// contract.
+    pub fn public_inputs(&self) -> &[u64] {
+        &self.public_inputs
+    }
+
+    /// Returns the serialized proof which should be passed to the `verify()` function of a
+    /// verifier contract.
+    pub fn proof(&self) -> &[u8] {
+        &self.proof
+    }
+
+    /// Returns ABI-encoded data of a call to the `verify()` function of a verifier contract,
+    /// including the function selector.
+    pub fn to_abi_call(&self) -> Vec<u8> {
+        let selector = &Hasher::hash(b"verify(uint256[],bytes)").as_bytes()[..4];
+        let num_inputs = self.public_inputs.len();
+
+        let mut result = selector.to_vec();
+        // head: offsets of the dynamic arguments
+        write_word(&mut result, 64);
+        write_word(&mut result, 64 + 32 * (num_inputs as u64 + 1));
+        // tail: public inputs array followed by proof bytes padded to a whole number of words
+        write_word(&mut result, num_inputs as u64);
+        for &input in self.public_inputs.iter() {
+            write_word(&mut result, input);
+        }
+        write_word(&mut result, self.proof.len() as u64);
+        result.extend_from_slice(&self.proof);
+        result.resize(result.len() + (32 - self.proof.len() % 32) % 32, 0);
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// A queried row of a Merkle tree together with the authentication path for this row.
+type OpenedRow<E> = (Vec<E>, Vec<<Hasher as crypto::Hasher>::Digest>);
+
+/// Serializes the proof into a sequence of words expected by generated contracts.
+fn serialize<A, E>(air: &A, proof: Proof, seed: &[BaseElement]) -> Result<Vec<u8>, SolidityError>
+where
+    A: Air<BaseField = BaseElement>,
+    E: FieldElement<BaseField = BaseElement>,
+{
+    let Proof {
+        num_unique_queries,
+        commitments,
+        mut trace_queries,
+        constraint_queries,
+        ood_frame,
+        fri_proof,
+        pow_nonce,
+        ..
+    } = proof;
+
+    let context = air.context();
+    let lde_domain_size = air.lde_domain_size();
+    let fri_options = air.options().to_fri_options();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+    let folding_factor = fri_options.folding_factor();
+    let num_queries = num_unique_queries as usize;
+    let main_trace_width = context.trace_info().main_trace_width();
+    let num_composition_columns = context.num_constraint_composition_columns();
+
+    // --- parse the proof ------------------------------------------------------------------------
+    let (trace_roots, constraint_root, fri_roots) =
+        commitments.parse::<Hasher>(1, num_fri_layers).map_err(deserialization_error)?;
+    let (ood_trace_frame, ood_evaluations) = ood_frame
+        .parse::<E>(main_trace_width, 0, num_composition_columns, context.frame_offsets())
+        .map_err(deserialization_error)?;
+    let num_partitions = fri_proof.num_partitions();
+    let remainder = fri_proof.parse_remainder::<E>().map_err(deserialization_error)?;
+    let (layer_queries, _, layer_proofs) = fri_proof
+        .parse_layers::<Hasher, E, MerkleTree<Hasher>>(lde_domain_size, &fri_options)
+        .map_err(deserialization_error)?;
+    if trace_queries.len() != 1 {
+        return Err(deserialization_error("expected queries for a single trace segment"));
+    }
+    let (trace_proof, trace_states) = trace_queries
+        .remove(0)
+        .parse::<Hasher, BaseElement>(lde_domain_size, num_queries, main_trace_width)
+        .map_err(deserialization_error)?;
+    let (constraint_proof, constraint_evaluations) = constraint_queries
+        .parse::<Hasher, E>(lde_domain_size, num_queries, num_composition_columns)
+        .map_err(deserialization_error)?;
+
+    // --- replay the transcript to determine query positions -------------------------------------
+    let mut coin = DefaultRandomCoin::<Hasher>::new(seed);
+    coin.absorb(TranscriptLabel::TraceCommitment, trace_roots[0]);
+    air.get_constraint_composition_coefficients::<E, _>(&mut coin)
+        .map_err(|_| SolidityError::RandomCoinError)?;
+    coin.absorb(TranscriptLabel::ConstraintCommitment, constraint_root);
+    coin.squeeze::<E>(TranscriptLabel::OodTraceFrame)
+        .map_err(|_| SolidityError::RandomCoinError)?;
+    coin.absorb(TranscriptLabel::OodTraceFrame, ood_trace_frame.hash::<Hasher>());
+    coin.absorb(
+        TranscriptLabel::OodConstraintEvaluations,
+        Hasher::hash_elements(&ood_evaluations),
+    );
+    air.get_deep_composition_coefficients::<E, _>(&mut coin)
+        .map_err(|_| SolidityError::RandomCoinError)?;
+    for &root in fri_roots.iter() {
+        coin.absorb(TranscriptLabel::FriLayer, root);
+        coin.squeeze::<E>(TranscriptLabel::FriLayer)
+            .map_err(|_| SolidityError::RandomCoinError)?;
+    }
+    let positions = coin
+        .squeeze_integers(
+            TranscriptLabel::QuerySeed,
+            air.options().num_queries(),
+            lde_domain_size,
+            pow_nonce,
+        )
+        .map_err(|_| SolidityError::RandomCoinError)?;
+
+    let mut unique_positions = positions.clone();
+    unique_positions.sort_unstable();
+    unique_positions.dedup();
+    if unique_positions.len() != num_queries {
+        return Err(deserialization_error("number of unique queries does not match query seed"));
+    }
+
+    // --- expand batch proofs into individual Merkle paths ---------------------------------------
+    let trace_rows = open_rows(&trace_states, trace_proof, &unique_positions)?;
+    let constraint_rows = open_rows(&constraint_evaluations, constraint_proof, &unique_positions)?;
+
+    // rows of FRI layers are keyed by folded positions; the order of rows in each layer is the
+    // same as the order in which the FRI verifier requests them
+    let mut layer_rows = Vec::with_capacity(num_fri_layers);
+    let mut layer_positions = unique_positions.clone();
+    let mut domain_size = lde_domain_size;
+    for (values, layer_proof) in layer_queries.into_iter().zip(layer_proofs) {
+        let folded_positions = fold_positions(&layer_positions, domain_size, folding_factor);
+        let indexes = map_positions_to_indexes(
+            &folded_positions,
+            domain_size,
+            folding_factor,
+            num_partitions,
+        );
+        let paths = layer_proof.into_paths(&indexes).map_err(deserialization_error)?;
+        if values.len() != folded_positions.len() * folding_factor {
+            return Err(deserialization_error("unexpected number of FRI layer values"));
+        }
+        let rows = folded_positions
+            .iter()
+            .zip(values.chunks(folding_factor).zip(paths))
+            .map(|(&position, (row, path))| (position, (row.to_vec(), path[1..].to_vec())))
+            .collect::<BTreeMap<_, _>>();
+        layer_rows.push(rows);
+        layer_positions = folded_positions;
+        domain_size /= folding_factor;
+    }
+
+    // --- write the header -----------------------------------------------------------------------
+    let mut result = Vec::new();
+    write_digest(&mut result, &trace_roots[0]);
+    write_digest(&mut result, &constraint_root);
+    for root in fri_roots.iter() {
+        write_digest(&mut result, root);
+    }
+    for column in 0..main_trace_width {
+        for row in 0..ood_trace_frame.num_rows() {
+            write_element(&mut result, ood_trace_frame.row(row)[column]);
+        }
+    }
+    write_elements(&mut result, &ood_evaluations);
+    write_word(&mut result, pow_nonce);
+    write_word(&mut result, num_partitions as u64);
+    write_word(&mut result, remainder.len() as u64);
+    write_elements(&mut result, &remainder);
+
+    // --- write queries in the order in which they were drawn ------------------------------------
+    for position in positions {
+        let (row, path) = &trace_rows[&position];
+        write_elements(&mut result, row);
+        path.iter().for_each(|node| write_digest(&mut result, node));
+
+        let (row, path) = &constraint_rows[&position];
+        write_elements(&mut result, row);
+        path.iter().for_each(|node| write_digest(&mut result, node));
+
+        let mut position = position;
+        let mut domain_size = lde_domain_size;
+        for rows in layer_rows.iter() {
+            domain_size /= folding_factor;
+            position %= domain_size;
+            let (row, path) = &rows[&position];
+            write_elements(&mut result, row);
+            path.iter().for_each(|node| write_digest(&mut result, node));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Expands a batch proof for the rows of the specified table into individual Merkle paths, and
+/// returns the rows together with their paths keyed by row positions.
+fn open_rows<E: FieldElement>(
+    table: &Table<E>,
+    proof: BatchMerkleProof<Hasher>,
+    positions: &[usize],
+) -> Result<BTreeMap<usize, OpenedRow<E>>, SolidityError> {
+    let paths = proof.into_paths(positions).map_err(deserialization_error)?;
+    Ok(positions
+        .iter()
+        .zip(table.rows().zip(paths))
+        .map(|(&position, (row, path))| (position, (row.to_vec(), path[1..].to_vec())))
+        .collect())
+}
+
+fn write_word(target: &mut Vec<u8>, value: u64) {
+    target.extend_from_slice(&[0; 24]);
+    target.extend_from_slice(&value.to_be_bytes());
+}
+
+fn write_digest<D: Digest>(target: &mut Vec<u8>, digest: &D) {
+    target.extend_from_slice(&digest.as_bytes());
+}
+
+fn write_element<E: FieldElement<BaseField = BaseElement>>(target: &mut Vec<u8>, element: E) {
+    for coordinate in E::slice_as_base_elements(&[element]) {
+        write_word(target, coordinate.as_int());
+    }
+}
+
+fn write_elements<E: FieldElement<BaseField = BaseElement>>(target: &mut Vec<u8>, elements: &[E]) {
+    elements.iter().for_each(|&element| write_element(target, element));
+}
+
+fn deserialization_error<T: ToString>(err: T) -> SolidityError {
+    SolidityError::ProofDeserializationError(err.to_string())
+}
@@ -0,0 +1,571 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Generator of Solidity verifier contracts.
+//!
+//! A [ContractGenerator] emits the source code of a Solidity contract which verifies STARK
+//! proofs for a single computation. Transition constraints of the computation are described via
+//! [Expr] expressions which are translated into Solidity code, while all other parameters
+//! (boundary constraints, constraint divisors, periodic columns, and protocol parameters) are
+//! taken from the AIR instance passed to the generator. [ProofCalldata] serializes a [Proof]
+//! into the format expected by the generated contract.
+//!
+//! The generated contract replicates the verification procedure of [verify()](crate::verify)
+//! for proofs which:
+//! * Are generated over the 64-bit field (`math::fields::f64`) with no field extension or with
+//!   the quadratic field extension, and draw the out-of-domain point from the same field.
+//! * Use [Keccak256](crypto::hashers::Keccak256) as the hash function and
+//!   [DefaultRandomCoin](crypto::DefaultRandomCoin) as the Fiat-Shamir transcript.
+//! * Are not zero-knowledge, and are generated for computations with a single (main) trace
+//!   segment.
+//! * Are generated for computations with assertions which place a single value against one or
+//!   more steps of the trace (i.e., single and periodic assertions).
+//!
+//! The contract is generated for a specific AIR instance, and thus, for specific trace length and
+//! proof options. Assertion values are embedded into the contract as constants unless they are
+//! marked as public via [ContractGenerator::with_public_assertion()], in which case they are read
+//! from the public inputs passed to the contract.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+
+use air::{proof::Context, Air, Assertion, ConstraintDivisor, EvaluationFrame, Expr};
+use crypto::{hashers::Keccak256, DefaultRandomCoin, RandomCoin, TranscriptLabel};
+use math::{fields::f64::BaseElement, FieldElement, ToElements};
+
+use crate::FieldExtension;
+
+mod calldata;
+pub use calldata::ProofCalldata;
+
+/// Source code of the contract with placeholders for computation-specific parameters.
+const TEMPLATE: &str = include_str!("verifier.sol");
+
+/// Hash function which must be used to generate proofs verified by generated contracts.
+type Hasher = Keccak256<BaseElement>;
+
+// CONTRACT GENERATOR
+// ================================================================================================
+
+/// Generates Solidity contracts which verify proofs for the computation described by an AIR.
+///
+/// See the [module documentation](self) for the supported computations and proof options.
+pub struct ContractGenerator<'a, A: Air<BaseField = BaseElement>> {
+    air: &'a A,
+    constraints: Vec<Expr<BaseElement>>,
+    public_assertions: Vec<(usize, usize, usize)>,
+    contract_name: String,
+}
+
+impl<'a, A: Air<BaseField = BaseElement>> ContractGenerator<'a, A> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new generator of verifier contracts for the specified AIR.
+    ///
+    /// `constraints` must contain an expression for each transition constraint of the AIR in the
+    /// order in which the constraints are evaluated by
+    /// [Air::evaluate_transition()](air::Air::evaluate_transition); the expressions are checked
+    /// against the AIR when the contract is generated.
+    pub fn new(air: &'a A, constraints: Vec<Expr<BaseElement>>) -> Self {
+        Self {
+            air,
+            constraints,
+            public_assertions: Vec::new(),
+            contract_name: "StarkVerifier".to_string(),
+        }
+    }
+
+    /// Sets the name of the generated contract; the default name is `StarkVerifier`.
+    pub fn with_contract_name(mut self, name: &str) -> Self {
+        self.contract_name = name.to_string();
+        self
+    }
+
+    /// Marks the value of the assertion against the specified column and step as public.
+    ///
+    /// Instead of embedding the value into the contract, the contract reads it from the public
+    /// inputs at the specified index of the vector returned by
+    /// [ToElements::to_elements()](math::ToElements::to_elements) for the public inputs of the
+    /// computation. This makes it possible to verify proofs for different instances of the
+    /// computation (e.g., with different results) using the same contract.
+    pub fn with_public_assertion(mut self, column: usize, step: usize, input_idx: usize) -> Self {
+        self.public_assertions.push((column, step, input_idx));
+        self
+    }
+
+    // CONTRACT GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the source code of a Solidity contract which verifies proofs for the computation
+    /// described by the AIR of this generator.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The AIR or its proof options are not supported by generated contracts.
+    /// * The expressions of this generator do not describe transition constraints of the AIR.
+    /// * A public assertion does not match any of the assertions of the AIR.
+    pub fn generate(&self) -> Result<String, SolidityError> {
+        check_air_support(self.air)?;
+        self.check_constraints()?;
+
+        let air = self.air;
+        let context = air.context();
+        let options = air.options();
+        let fri_options = options.to_fri_options();
+
+        let trace_length = air.trace_length();
+        let lde_domain_size = air.lde_domain_size();
+        let lde_depth = lde_domain_size.ilog2() as usize;
+        let folding_factor = fri_options.folding_factor();
+        let log_folding_factor = folding_factor.ilog2() as usize;
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let ext_degree = options.field_extension().degree() as usize;
+        let frame_rows = context.frame_offsets().len();
+        let num_composition_columns = context.num_constraint_composition_columns();
+
+        // each query contains a trace row, a row of the constraint composition polynomial, and a
+        // row of each FRI layer, together with Merkle authentication paths for these rows
+        let mut query_words = context.trace_info().main_trace_width()
+            + num_composition_columns * ext_degree
+            + 2 * lde_depth;
+        for layer in 0..num_fri_layers {
+            query_words +=
+                folding_factor * ext_degree + lde_depth - (layer + 1) * log_folding_factor;
+        }
+
+        let constraint_evaluation = self.build_constraint_evaluation()?;
+        let mut functions = String::new();
+        self.write_parameter_functions(&mut functions)
+            .and_then(|_| self.write_periodic_columns(&mut functions))
+            .expect("failed to write generated functions");
+        functions.push_str(&constraint_evaluation);
+
+        let parameters = [
+            ("CONTRACT_NAME", self.contract_name.clone()),
+            ("EXT_DEGREE", ext_degree.to_string()),
+            ("TRACE_LENGTH", trace_length.to_string()),
+            ("TRACE_WIDTH", context.trace_info().main_trace_width().to_string()),
+            ("FRAME_ROWS", frame_rows.to_string()),
+            ("NUM_TRANSITION_CONSTRAINTS", context.num_transition_constraints().to_string()),
+            ("NUM_ASSERTIONS", context.num_assertions().to_string()),
+            ("NUM_COMPOSITION_COLUMNS", num_composition_columns.to_string()),
+            ("LDE_DOMAIN_SIZE", lde_domain_size.to_string()),
+            ("LDE_DEPTH", lde_depth.to_string()),
+            ("LDE_GENERATOR", air.lde_domain_generator().as_int().to_string()),
+            ("DOMAIN_OFFSET", air.domain_offset().as_int().to_string()),
+            ("NUM_QUERIES", options.num_queries().to_string()),
+            ("GRINDING_FACTOR", options.grinding_factor().to_string()),
+            ("FOLDING_FACTOR", folding_factor.to_string()),
+            ("LOG_FOLDING_FACTOR", log_folding_factor.to_string()),
+            ("NUM_FRI_LAYERS", num_fri_layers.to_string()),
+            (
+                "MAX_REMAINDER_LENGTH",
+                (trace_length >> (num_fri_layers * log_folding_factor)).to_string(),
+            ),
+            ("QUERY_SIZE", (query_words * 32).to_string()),
+            ("LABEL_TRACE_COMMITMENT", TranscriptLabel::TraceCommitment.tag().to_string()),
+            (
+                "LABEL_CONSTRAINT_COMMITMENT",
+                TranscriptLabel::ConstraintCommitment.tag().to_string(),
+            ),
+            ("LABEL_OOD_TRACE_FRAME", TranscriptLabel::OodTraceFrame.tag().to_string()),
+            (
+                "LABEL_OOD_CONSTRAINT_EVALUATIONS",
+                TranscriptLabel::OodConstraintEvaluations.tag().to_string(),
+            ),
+            ("LABEL_FRI_LAYER", TranscriptLabel::FriLayer.tag().to_string()),
+            ("GENERATED_FUNCTIONS", functions),
+        ];
+
+        let mut source = TEMPLATE.to_string();
+        for (name, value) in parameters {
+            source = source.replace(&format!("{{{{{name}}}}}"), &value);
+        }
+        Ok(source)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes sure the expressions of this generator evaluate to the same values as transition
+    /// constraints of the AIR over a pseudo-random evaluation frame.
+    fn check_constraints(&self) -> Result<(), SolidityError> {
+        let air = self.air;
+        let context = air.context();
+        let num_constraints = context.num_main_transition_constraints();
+        if self.constraints.len() != num_constraints {
+            return Err(SolidityError::ConstraintCountMismatch(
+                num_constraints,
+                self.constraints.len(),
+            ));
+        }
+
+        let width = context.trace_info().main_trace_width();
+        let offsets = context.frame_offsets().to_vec();
+        let num_periodic_columns = air.get_periodic_column_values().len();
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            check_expr(constraint, width, offsets.len(), num_periodic_columns)
+                .map_err(|reason| SolidityError::InvalidConstraint(i, reason))?;
+        }
+
+        // the frame and the values of periodic columns are drawn from a random coin seeded with
+        // the proof context, and thus, are fixed for a given AIR
+        let seed = Context::from_air(air).to_elements();
+        let mut coin = DefaultRandomCoin::<Hasher>::new(&seed);
+        let mut draw = || coin.draw::<BaseElement>().expect("failed to draw a random element");
+        let rows = offsets.iter().map(|_| (0..width).map(|_| draw()).collect()).collect();
+        let frame = EvaluationFrame::from_rows_with_offsets(rows, offsets);
+        let periodic_values = (0..num_periodic_columns).map(|_| draw()).collect::<Vec<_>>();
+
+        let mut expected = vec![BaseElement::ZERO; num_constraints];
+        air.evaluate_transition(&frame, &periodic_values, &mut expected);
+        for (i, (constraint, expected)) in self.constraints.iter().zip(expected).enumerate() {
+            if constraint.evaluate(&frame, &periodic_values) != expected {
+                return Err(SolidityError::ConstraintMismatch(i));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes functions returning the elements of the proof context and the offsets of the
+    /// evaluation frame rows.
+    fn write_parameter_functions(&self, out: &mut String) -> fmt::Result {
+        let air = self.air;
+        let context = Context::from_air(air);
+        let context_elements: Vec<BaseElement> = context.to_elements();
+        let g_trace = air.trace_domain_generator();
+        let offset_factors = air
+            .context()
+            .frame_offsets()
+            .iter()
+            .map(|&offset| g_trace.exp(offset as u64))
+            .collect::<Vec<_>>();
+
+        writeln!(out)?;
+        writeln!(out, "    /// Returns the elements of the proof context.")?;
+        write_array_function(out, "contextElements", &context_elements)?;
+        writeln!(out)?;
+        writeln!(out, "    /// Returns g^k for the offset k of each row of the evaluation frame.")?;
+        write_array_function(out, "frameOffsetFactors", &offset_factors)
+    }
+
+    /// Writes a function evaluating periodic columns at the out-of-domain point.
+    fn write_periodic_columns(&self, out: &mut String) -> fmt::Result {
+        let polys = self.air.get_periodic_column_polys();
+        let z = if polys.is_empty() { "/* z */" } else { "z" };
+
+        writeln!(out)?;
+        writeln!(out, "    function evaluatePeriodicColumns(uint256 {z})")?;
+        writeln!(out, "        private")?;
+        writeln!(out, "        pure")?;
+        writeln!(out, "        returns (uint256[] memory periodic)")?;
+        writeln!(out, "    {{")?;
+        writeln!(out, "        periodic = new uint256[]({});", polys.len())?;
+        if !polys.is_empty() {
+            writeln!(out, "        uint256[] memory coefficients;")?;
+        }
+        for (i, poly) in polys.iter().enumerate() {
+            let num_cycles = self.air.trace_length() / poly.len();
+            writeln!(out, "        coefficients = new uint256[]({});", poly.len())?;
+            for (j, coefficient) in poly.iter().enumerate() {
+                writeln!(out, "        coefficients[{j}] = {};", coefficient.as_int())?;
+            }
+            writeln!(
+                out,
+                "        periodic[{i}] = evaluatePolynomial(coefficients, eExp(z, {num_cycles}));"
+            )?;
+        }
+        writeln!(out, "    }}")
+    }
+
+    /// Returns a function evaluating a random linear combination of all transition and boundary
+    /// constraints divided by their divisors at the out-of-domain point.
+    fn build_constraint_evaluation(&self) -> Result<String, SolidityError> {
+        let air = self.air;
+        let context = air.context();
+        let frame_rows = context.frame_offsets().len();
+
+        // constraints sharing the same divisor are combined first, and then each combination is
+        // divided by the divisor
+        let num_constraints = context.num_main_transition_constraints();
+        let t_constraints =
+            air.get_transition_constraints(&vec![BaseElement::ZERO; num_constraints]);
+        let mut divisors = t_constraints.divisors().to_vec();
+
+        let mut lines = Vec::new();
+        for (i, (constraint, &divisor_idx)) in self
+            .constraints
+            .iter()
+            .zip(t_constraints.main_constraint_divisors())
+            .enumerate()
+        {
+            let expr = write_expr(constraint, frame_rows);
+            lines.push(format!(
+                "numerators[{divisor_idx}] = eAdd(numerators[{divisor_idx}], \
+                eMul(c.transitionCoeffs[{i}], {expr}));"
+            ));
+        }
+
+        // boundary constraints are assigned composition coefficients in the natural order of
+        // their assertions
+        let mut assertions = air.get_assertions();
+        assertions.sort();
+        for (column, step, _) in self.public_assertions.iter() {
+            if !assertions.iter().any(|a| a.column() == *column && a.first_step() == *step) {
+                return Err(SolidityError::UnknownPublicAssertion(*column, *step));
+            }
+        }
+
+        let mut uses_public_inputs = false;
+        for (i, assertion) in assertions.iter().enumerate() {
+            if assertion.values().len() != 1 {
+                return Err(SolidityError::UnsupportedAssertion(assertion.to_string()));
+            }
+            let divisor = ConstraintDivisor::from_assertion(assertion, air.trace_length());
+            let divisor_idx = match divisors.iter().position(|d| *d == divisor) {
+                Some(idx) => idx,
+                None => {
+                    divisors.push(divisor);
+                    divisors.len() - 1
+                },
+            };
+            let value = match self.get_public_input_idx(assertion) {
+                Some(input_idx) => {
+                    uses_public_inputs = true;
+                    format!("publicInput(publicInputs, {input_idx})")
+                },
+                None => assertion.values()[0].as_int().to_string(),
+            };
+            lines.push(format!(
+                "numerators[{divisor_idx}] = eAdd(numerators[{divisor_idx}], \
+                eMul(c.boundaryCoeffs[{i}], eSub(frame[{}], {value})));",
+                assertion.column() * frame_rows
+            ));
+        }
+
+        // divide combinations of constraints by their divisors
+        lines.push("uint256 z = c.z;".to_string());
+        for (idx, divisor) in divisors.iter().enumerate() {
+            let mut numerator = format!("numerators[{idx}]");
+            for exemption in divisor.exemptions() {
+                numerator = format!("eMul({numerator}, eSub(z, {}))", exemption.as_int());
+            }
+            let mut denominator = String::new();
+            for (degree, constant) in divisor.numerator() {
+                let term = format!("eSub(eExp(z, {degree}), {})", constant.as_int());
+                denominator = if denominator.is_empty() {
+                    term
+                } else {
+                    format!("eMul({denominator}, {term})")
+                };
+            }
+            lines.push(format!("result = eAdd(result, eMul({numerator}, eInv({denominator})));"));
+        }
+
+        let periodic = if air.get_periodic_column_values().is_empty() {
+            "/* periodic */"
+        } else {
+            "periodic"
+        };
+        let public_inputs = if uses_public_inputs {
+            "publicInputs"
+        } else {
+            "/* publicInputs */"
+        };
+
+        let body = lines.iter().map(|line| format!("        {line}\n")).collect::<String>();
+        Ok(format!(
+            "
+    function evaluateConstraints(
+        uint256[] memory frame,
+        uint256[] memory {periodic},
+        Challenges memory c,
+        uint256[] calldata {public_inputs}
+    ) private pure returns (uint256 result) {{
+        uint256[] memory numerators = new uint256[]({});
+{body}    }}
+",
+            divisors.len()
+        ))
+    }
+
+    /// Returns the index of the public input holding the value of the specified assertion, if
+    /// the assertion was marked as public.
+    fn get_public_input_idx(&self, assertion: &Assertion<BaseElement>) -> Option<usize> {
+        self.public_assertions
+            .iter()
+            .find(|(column, step, _)| {
+                assertion.column() == *column && assertion.first_step() == *step
+            })
+            .map(|&(_, _, input_idx)| input_idx)
+    }
+}
+
+// SOLIDITY ERROR
+// ================================================================================================
+
+/// Represents an error returned while generating a Solidity verifier contract or serializing a
+/// proof into calldata for such a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolidityError {
+    /// This error occurs when proofs are generated with a field extension not supported by
+    /// generated contracts.
+    UnsupportedFieldExtension(usize),
+    /// This error occurs when proofs are generated in zero-knowledge mode.
+    ZeroKnowledgeNotSupported,
+    /// This error occurs when the execution trace of a computation has an auxiliary segment.
+    AuxTraceNotSupported,
+    /// This error occurs when an assertion places more than one value against a column.
+    UnsupportedAssertion(String),
+    /// This error occurs when the number of constraint expressions does not match the number of
+    /// transition constraints of the AIR.
+    ConstraintCountMismatch(usize, usize),
+    /// This error occurs when the constraint expression at the specified index refers to data
+    /// which is not available to generated contracts.
+    InvalidConstraint(usize, String),
+    /// This error occurs when the constraint expression at the specified index does not evaluate
+    /// to the same value as the corresponding transition constraint of the AIR.
+    ConstraintMismatch(usize),
+    /// This error occurs when an assertion marked as public does not match any of the assertions
+    /// of the AIR.
+    UnknownPublicAssertion(usize, usize),
+    /// This error occurs when a proof cannot be deserialized.
+    ProofDeserializationError(String),
+    /// This error occurs when a random value cannot be drawn while replaying the transcript of a
+    /// proof.
+    RandomCoinError,
+}
+
+impl fmt::Display for SolidityError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedFieldExtension(degree) => {
+                write!(f, "field extension of degree {degree} is not supported by Solidity verifiers")
+            }
+            Self::ZeroKnowledgeNotSupported => {
+                write!(f, "zero-knowledge proofs are not supported by Solidity verifiers")
+            }
+            Self::AuxTraceNotSupported => {
+                write!(f, "auxiliary trace segments are not supported by Solidity verifiers")
+            }
+            Self::UnsupportedAssertion(assertion) => {
+                write!(f, "assertion {assertion} is not supported by Solidity verifiers")
+            }
+            Self::ConstraintCountMismatch(expected, actual) => {
+                write!(f, "expected {expected} constraint expressions, but received {actual}")
+            }
+            Self::InvalidConstraint(idx, reason) => {
+                write!(f, "constraint expression {idx} is invalid: {reason}")
+            }
+            Self::ConstraintMismatch(idx) => {
+                write!(f, "constraint expression {idx} does not match transition constraint {idx} of the AIR")
+            }
+            Self::UnknownPublicAssertion(column, step) => {
+                write!(f, "no assertion against column {column} at step {step} is defined by the AIR")
+            }
+            Self::ProofDeserializationError(msg) => {
+                write!(f, "proof deserialization failed: {msg}")
+            }
+            Self::RandomCoinError => {
+                write!(f, "failed to draw a random value from a random coin")
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure proofs for the specified AIR can be verified by generated contracts.
+fn check_air_support<A: Air>(air: &A) -> Result<(), SolidityError> {
+    if air.options().is_zk() {
+        return Err(SolidityError::ZeroKnowledgeNotSupported);
+    }
+    if air.trace_info().is_multi_segment() {
+        return Err(SolidityError::AuxTraceNotSupported);
+    }
+    if air.options().field_extension() == FieldExtension::Cubic {
+        return Err(SolidityError::UnsupportedFieldExtension(3));
+    }
+    if air.options().ood_field_extension() != air.options().field_extension() {
+        let degree = air.options().ood_field_extension().degree() as usize;
+        return Err(SolidityError::UnsupportedFieldExtension(degree));
+    }
+    Ok(())
+}
+
+/// Makes sure the expression refers only to the main trace segment and periodic columns.
+fn check_expr(
+    expr: &Expr<BaseElement>,
+    width: usize,
+    frame_rows: usize,
+    num_periodic_columns: usize,
+) -> Result<(), String> {
+    match expr {
+        Expr::Const(_) => Ok(()),
+        Expr::Main { column, row } => {
+            if *column >= width {
+                return Err(format!("column {column} is outside of the main trace segment"));
+            }
+            if *row >= frame_rows {
+                return Err(format!("row {row} is outside of the evaluation frame"));
+            }
+            Ok(())
+        },
+        Expr::Aux { .. } => Err("auxiliary trace columns are not supported".to_string()),
+        Expr::Rand(_) => Err("random elements are not supported".to_string()),
+        Expr::Periodic(idx) => {
+            if *idx >= num_periodic_columns {
+                return Err(format!("periodic column {idx} is not defined by the AIR"));
+            }
+            Ok(())
+        },
+        Expr::Add(lhs, rhs) | Expr::Sub(lhs, rhs) | Expr::Mul(lhs, rhs) => {
+            check_expr(lhs, width, frame_rows, num_periodic_columns)?;
+            check_expr(rhs, width, frame_rows, num_periodic_columns)
+        },
+        Expr::Neg(expr) | Expr::Exp(expr, _) => {
+            check_expr(expr, width, frame_rows, num_periodic_columns)
+        },
+    }
+}
+
+/// Translates the expression into Solidity code; the expression must be valid as checked by
+/// [check_expr()].
+fn write_expr(expr: &Expr<BaseElement>, frame_rows: usize) -> String {
+    match expr {
+        Expr::Const(value) => value.as_int().to_string(),
+        Expr::Main { column, row } => format!("frame[{}]", column * frame_rows + row),
+        Expr::Periodic(idx) => format!("periodic[{idx}]"),
+        Expr::Add(lhs, rhs) => {
+            format!("eAdd({}, {})", write_expr(lhs, frame_rows), write_expr(rhs, frame_rows))
+        },
+        Expr::Sub(lhs, rhs) => {
+            format!("eSub({}, {})", write_expr(lhs, frame_rows), write_expr(rhs, frame_rows))
+        },
+        Expr::Mul(lhs, rhs) => {
+            format!("eMul({}, {})", write_expr(lhs, frame_rows), write_expr(rhs, frame_rows))
+        },
+        Expr::Neg(expr) => format!("eSub(0, {})", write_expr(expr, frame_rows)),
+        Expr::Exp(expr, power) => format!("eExp({}, {power})", write_expr(expr, frame_rows)),
+        Expr::Aux { .. } | Expr::Rand(_) => unreachable!("expression was not validated"),
+    }
+}
+
+/// Writes a function returning the specified elements as an array.
+fn write_array_function(out: &mut String, name: &str, values: &[BaseElement]) -> fmt::Result {
+    writeln!(out, "    function {name}() private pure returns (uint256[] memory values) {{")?;
+    writeln!(out, "        values = new uint256[]({});", values.len())?;
+    for (i, value) in values.iter().enumerate() {
+        writeln!(out, "        values[{i}] = {};", value.as_int())?;
+    }
+    writeln!(out, "    }}")
+}
@@ -0,0 +1,651 @@
+// SPDX-License-Identifier: MIT
+// This contract was generated by winter-verifier; do not edit it by hand.
+pragma solidity ^0.8.20;
+
+/// @title {{CONTRACT_NAME}}
+/// @notice Verifies Winterfell STARK proofs for a single computation. Proofs must be generated
+/// using the Keccak256 hash function and the default random coin over the 64-bit prime field,
+/// and must be serialized into calldata via `winter_verifier::solidity::ProofCalldata`.
+contract {{CONTRACT_NAME}} {
+    // ERRORS
+    // --------------------------------------------------------------------------------------------
+
+    error MalformedProof();
+    error MalformedPublicInputs();
+    error InvalidFieldElement();
+    error RandomCoinError();
+    error InconsistentOodConstraintEvaluations();
+    error TraceQueryDoesNotMatchCommitment(uint256 segment);
+    error ConstraintQueryDoesNotMatchCommitment();
+    error QuerySeedProofOfWorkVerificationFailed();
+    error InconsistentDeepComposition(uint256 position);
+    error InvalidLayerFolding(uint256 layer, uint256 position);
+    error LayerCommitmentMismatch(uint256 layer);
+    error RemainderCommitmentMismatch();
+    error RemainderDegreeMismatch(uint256 maxDegree);
+    error InvalidRemainderFolding(uint256 position);
+
+    // FIELD PARAMETERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Modulus of the base field; elements of the quadratic extension are packed into a single
+    /// word as `c0 | (c1 << 64)`, and thus, base field elements are valid extension elements.
+    uint256 internal constant P = 0xFFFFFFFF00000001;
+    uint256 internal constant MASK64 = 0xFFFFFFFFFFFFFFFF;
+    uint256 internal constant EXT_DEGREE = {{EXT_DEGREE}};
+
+    // COMPUTATION PARAMETERS
+    // --------------------------------------------------------------------------------------------
+
+    uint256 internal constant TRACE_LENGTH = {{TRACE_LENGTH}};
+    uint256 internal constant TRACE_WIDTH = {{TRACE_WIDTH}};
+    uint256 internal constant FRAME_ROWS = {{FRAME_ROWS}};
+    uint256 internal constant NUM_TRANSITION_CONSTRAINTS = {{NUM_TRANSITION_CONSTRAINTS}};
+    uint256 internal constant NUM_ASSERTIONS = {{NUM_ASSERTIONS}};
+    uint256 internal constant NUM_COMPOSITION_COLUMNS = {{NUM_COMPOSITION_COLUMNS}};
+
+    // PROTOCOL PARAMETERS
+    // --------------------------------------------------------------------------------------------
+
+    uint256 internal constant LDE_DOMAIN_SIZE = {{LDE_DOMAIN_SIZE}};
+    uint256 internal constant LDE_DEPTH = {{LDE_DEPTH}};
+    uint256 internal constant LDE_GENERATOR = {{LDE_GENERATOR}};
+    uint256 internal constant DOMAIN_OFFSET = {{DOMAIN_OFFSET}};
+    uint256 internal constant NUM_QUERIES = {{NUM_QUERIES}};
+    uint256 internal constant GRINDING_FACTOR = {{GRINDING_FACTOR}};
+    uint256 internal constant FOLDING_FACTOR = {{FOLDING_FACTOR}};
+    uint256 internal constant LOG_FOLDING_FACTOR = {{LOG_FOLDING_FACTOR}};
+    uint256 internal constant NUM_FRI_LAYERS = {{NUM_FRI_LAYERS}};
+    uint256 internal constant MAX_REMAINDER_LENGTH = {{MAX_REMAINDER_LENGTH}};
+    uint256 internal constant MAX_DRAW_ATTEMPTS = 1000;
+
+    /// Number of bytes of calldata used by a single query.
+    uint256 internal constant QUERY_SIZE = {{QUERY_SIZE}};
+
+    // TRANSCRIPT LABELS
+    // --------------------------------------------------------------------------------------------
+
+    uint256 internal constant LABEL_TRACE_COMMITMENT = {{LABEL_TRACE_COMMITMENT}};
+    uint256 internal constant LABEL_CONSTRAINT_COMMITMENT = {{LABEL_CONSTRAINT_COMMITMENT}};
+    uint256 internal constant LABEL_OOD_TRACE_FRAME = {{LABEL_OOD_TRACE_FRAME}};
+    uint256 internal constant LABEL_OOD_CONSTRAINT_EVALUATIONS = {{LABEL_OOD_CONSTRAINT_EVALUATIONS}};
+    uint256 internal constant LABEL_FRI_LAYER = {{LABEL_FRI_LAYER}};
+
+    // DATA STRUCTURES
+    // --------------------------------------------------------------------------------------------
+
+    struct Transcript {
+        bytes32 seed;
+        uint256 counter;
+    }
+
+    struct Proof {
+        bytes32 traceRoot;
+        bytes32 constraintRoot;
+        bytes32[] friRoots;
+        uint256[] oodFrame;
+        uint256[] oodEvaluations;
+        uint256 powNonce;
+        uint256 numPartitions;
+        uint256[] remainder;
+        uint256 queriesOffset;
+    }
+
+    struct Challenges {
+        uint256[] transitionCoeffs;
+        uint256[] boundaryCoeffs;
+        uint256 z;
+        uint256[] deepCoeffs;
+        uint256[] alphas;
+        uint256[] positions;
+    }
+
+    struct FriQuery {
+        uint256 position;
+        uint256 evaluation;
+        uint256 ptr;
+    }
+
+    // VERIFIER
+    // --------------------------------------------------------------------------------------------
+
+    /// @notice Returns true if the proof attests to a correct execution of the computation against
+    /// the specified public inputs; reverts otherwise.
+    /// @param publicInputs Public inputs of the computation converted into transcript elements.
+    /// @param proof Proof serialized into a sequence of 32-byte words.
+    function verify(uint256[] calldata publicInputs, bytes calldata proof)
+        external
+        pure
+        returns (bool)
+    {
+        Proof memory p = parseProof(proof);
+        Challenges memory c = drawChallenges(p, publicInputs);
+        checkOodConsistency(p, c, publicInputs);
+
+        if (hashElements(p.remainder, EXT_DEGREE) != p.friRoots[NUM_FRI_LAYERS]) {
+            revert RemainderCommitmentMismatch();
+        }
+        for (uint256 i = 0; i < NUM_QUERIES; i++) {
+            verifyQuery(proof, p, c, i);
+        }
+        return true;
+    }
+
+    /// Replays the Fiat-Shamir transcript of the proof and returns all random values drawn by
+    /// the verifier.
+    function drawChallenges(Proof memory p, uint256[] calldata publicInputs)
+        private
+        pure
+        returns (Challenges memory c)
+    {
+        Transcript memory t = Transcript(hashPublicInputs(publicInputs), 0);
+
+        absorb(t, LABEL_TRACE_COMMITMENT, p.traceRoot);
+        c.transitionCoeffs = drawMany(t, NUM_TRANSITION_CONSTRAINTS);
+        c.boundaryCoeffs = drawMany(t, NUM_ASSERTIONS);
+
+        absorb(t, LABEL_CONSTRAINT_COMMITMENT, p.constraintRoot);
+        c.z = draw(t);
+
+        absorb(t, LABEL_OOD_TRACE_FRAME, hashElements(p.oodFrame, EXT_DEGREE));
+        absorb(t, LABEL_OOD_CONSTRAINT_EVALUATIONS, hashElements(p.oodEvaluations, EXT_DEGREE));
+        c.deepCoeffs = drawMany(t, TRACE_WIDTH + NUM_COMPOSITION_COLUMNS);
+
+        c.alphas = new uint256[](NUM_FRI_LAYERS + 1);
+        for (uint256 i = 0; i <= NUM_FRI_LAYERS; i++) {
+            absorb(t, LABEL_FRI_LAYER, p.friRoots[i]);
+            c.alphas[i] = draw(t);
+        }
+
+        uint256 head = leU64(mergeWithInt(t.seed, p.powNonce));
+        if ((head & ((1 << GRINDING_FACTOR) - 1)) != 0) {
+            revert QuerySeedProofOfWorkVerificationFailed();
+        }
+        c.positions = drawPositions(t, p.powNonce);
+    }
+
+    /// Makes sure that constraints evaluated over the out-of-domain frame are consistent with
+    /// the evaluations of the constraint composition polynomial columns.
+    function checkOodConsistency(
+        Proof memory p,
+        Challenges memory c,
+        uint256[] calldata publicInputs
+    ) private pure {
+        uint256[] memory periodic = evaluatePeriodicColumns(c.z);
+        uint256 result = evaluateConstraints(p.oodFrame, periodic, c, publicInputs);
+
+        uint256 expected = 0;
+        uint256 power = 1;
+        uint256 zn = eExp(c.z, TRACE_LENGTH);
+        for (uint256 i = 0; i < NUM_COMPOSITION_COLUMNS; i++) {
+            expected = eAdd(expected, eMul(power, p.oodEvaluations[i]));
+            power = eMul(power, zn);
+        }
+        if (result != expected) {
+            revert InconsistentOodConstraintEvaluations();
+        }
+    }
+
+    /// Verifies trace and constraint openings of a single query, and the FRI layers derived from
+    /// the DEEP composition polynomial evaluated at the query position.
+    function verifyQuery(bytes calldata proof, Proof memory p, Challenges memory c, uint256 query)
+        private
+        pure
+    {
+        uint256 position = c.positions[query];
+        uint256 ptr = p.queriesOffset + query * QUERY_SIZE;
+
+        uint256[] memory traceRow;
+        (traceRow, ptr) = readElements(proof, ptr, TRACE_WIDTH, 1);
+        if (verifyMerklePath(proof, ptr, hashElements(traceRow, 1), position, LDE_DEPTH) != p.traceRoot) {
+            revert TraceQueryDoesNotMatchCommitment(0);
+        }
+        ptr += LDE_DEPTH * 32;
+
+        uint256[] memory constraintRow;
+        (constraintRow, ptr) = readElements(proof, ptr, NUM_COMPOSITION_COLUMNS, EXT_DEGREE);
+        bytes32 leaf = hashElements(constraintRow, EXT_DEGREE);
+        if (verifyMerklePath(proof, ptr, leaf, position, LDE_DEPTH) != p.constraintRoot) {
+            revert ConstraintQueryDoesNotMatchCommitment();
+        }
+        ptr += LDE_DEPTH * 32;
+
+        uint256 x = mulB(DOMAIN_OFFSET, expB(LDE_GENERATOR, position));
+        uint256 evaluation = composeDeep(p, c, traceRow, constraintRow, x);
+        verifyFriQuery(proof, p, c, FriQuery(position, evaluation, ptr));
+    }
+
+    /// Computes the evaluation of the DEEP composition polynomial at x.
+    function composeDeep(
+        Proof memory p,
+        Challenges memory c,
+        uint256[] memory traceRow,
+        uint256[] memory constraintRow,
+        uint256 x
+    ) private pure returns (uint256 result) {
+        uint256[] memory offsetFactors = frameOffsetFactors();
+        for (uint256 k = 0; k < FRAME_ROWS; k++) {
+            uint256 numerator = 0;
+            for (uint256 i = 0; i < TRACE_WIDTH; i++) {
+                uint256 difference = eSub(traceRow[i], p.oodFrame[i * FRAME_ROWS + k]);
+                numerator = eAdd(numerator, eMul(difference, c.deepCoeffs[i]));
+            }
+            uint256 zk = eMulBase(c.z, offsetFactors[k]);
+            result = eAdd(result, eMul(numerator, eInv(eSub(x, zk))));
+        }
+
+        uint256 cNumerator = 0;
+        for (uint256 j = 0; j < NUM_COMPOSITION_COLUMNS; j++) {
+            uint256 difference = eSub(constraintRow[j], p.oodEvaluations[j]);
+            cNumerator = eAdd(cNumerator, eMul(difference, c.deepCoeffs[TRACE_WIDTH + j]));
+        }
+        uint256 z0 = eMulBase(c.z, offsetFactors[0]);
+        result = eAdd(result, eMul(cNumerator, eInv(eSub(x, z0))));
+    }
+
+    // FRI
+    // --------------------------------------------------------------------------------------------
+
+    function verifyFriQuery(
+        bytes calldata proof,
+        Proof memory p,
+        Challenges memory c,
+        FriQuery memory q
+    ) private pure {
+        for (uint256 layer = 0; layer < NUM_FRI_LAYERS; layer++) {
+            verifyFriLayer(proof, p, c, q, layer);
+        }
+
+        uint256 x = mulB(DOMAIN_OFFSET, expB(layerGenerator(NUM_FRI_LAYERS), q.position));
+        if (evaluatePolynomial(p.remainder, x) != q.evaluation) {
+            revert InvalidRemainderFolding(q.position);
+        }
+    }
+
+    function verifyFriLayer(
+        bytes calldata proof,
+        Proof memory p,
+        Challenges memory c,
+        FriQuery memory q,
+        uint256 layer
+    ) private pure {
+        uint256 rowLength = (LDE_DOMAIN_SIZE >> (layer * LOG_FOLDING_FACTOR)) / FOLDING_FACTOR;
+        uint256 folded = q.position % rowLength;
+
+        uint256[] memory row;
+        (row, q.ptr) = readElements(proof, q.ptr, FOLDING_FACTOR, EXT_DEGREE);
+        if (row[q.position / rowLength] != q.evaluation) {
+            if (layer == 0) {
+                revert InconsistentDeepComposition(q.position);
+            }
+            revert InvalidLayerFolding(layer, q.position);
+        }
+
+        bytes32 leaf = hashElements(row, EXT_DEGREE);
+        uint256 index = partitionIndex(folded, rowLength, p.numPartitions);
+        uint256 depth = LDE_DEPTH - (layer + 1) * LOG_FOLDING_FACTOR;
+        if (verifyMerklePath(proof, q.ptr, leaf, index, depth) != p.friRoots[layer]) {
+            revert LayerCommitmentMismatch(layer);
+        }
+        q.ptr += depth * 32;
+
+        uint256 xe = mulB(DOMAIN_OFFSET, expB(layerGenerator(layer), folded));
+        q.evaluation = foldRow(row, xe, c.alphas[layer]);
+        q.position = folded;
+    }
+
+    /// Evaluates the polynomial interpolated over points xe * ω^k and the specified row values at
+    /// alpha, where ω is a root of unity of order FOLDING_FACTOR.
+    function foldRow(uint256[] memory row, uint256 xe, uint256 alpha)
+        private
+        pure
+        returns (uint256 result)
+    {
+        uint256 root = expB(LDE_GENERATOR, LDE_DOMAIN_SIZE / FOLDING_FACTOR);
+        uint256 xeN = expB(xe, FOLDING_FACTOR);
+        uint256 vanishing = eSub(eExp(alpha, FOLDING_FACTOR), xeN);
+        uint256 scale = invB(mulB(FOLDING_FACTOR, xeN));
+
+        uint256 xk = xe;
+        for (uint256 k = 0; k < FOLDING_FACTOR; k++) {
+            uint256 difference = eSub(alpha, xk);
+            if (difference == 0) {
+                return row[k];
+            }
+            uint256 weight = eMulBase(eMul(vanishing, eInv(difference)), mulB(xk, scale));
+            result = eAdd(result, eMul(row[k], weight));
+            xk = mulB(xk, root);
+        }
+    }
+
+    /// Returns the generator of the domain of the FRI layer at the specified depth.
+    function layerGenerator(uint256 layer) private pure returns (uint256) {
+        return expB(LDE_GENERATOR, 1 << (layer * LOG_FOLDING_FACTOR));
+    }
+
+    /// Maps a position in a folded domain to an index of a leaf in the layer commitment.
+    function partitionIndex(uint256 position, uint256 domainSize, uint256 numPartitions)
+        private
+        pure
+        returns (uint256)
+    {
+        if (numPartitions == 1) {
+            return position;
+        }
+        uint256 partitionSize = domainSize / numPartitions;
+        uint256 partition = position % numPartitions;
+        return partition * partitionSize + (position - partition) / numPartitions;
+    }
+
+    // PROOF PARSING
+    // --------------------------------------------------------------------------------------------
+
+    function parseProof(bytes calldata proof) private pure returns (Proof memory p) {
+        uint256 ptr = 0;
+        p.traceRoot = bytes32(readWord(proof, ptr));
+        p.constraintRoot = bytes32(readWord(proof, ptr + 32));
+        ptr += 64;
+
+        p.friRoots = new bytes32[](NUM_FRI_LAYERS + 1);
+        for (uint256 i = 0; i <= NUM_FRI_LAYERS; i++) {
+            p.friRoots[i] = bytes32(readWord(proof, ptr));
+            ptr += 32;
+        }
+
+        (p.oodFrame, ptr) = readElements(proof, ptr, TRACE_WIDTH * FRAME_ROWS, EXT_DEGREE);
+        (p.oodEvaluations, ptr) = readElements(proof, ptr, NUM_COMPOSITION_COLUMNS, EXT_DEGREE);
+
+        p.powNonce = readWord(proof, ptr);
+        p.numPartitions = readWord(proof, ptr + 32);
+        uint256 remainderLength = readWord(proof, ptr + 64);
+        ptr += 96;
+        if (p.powNonce > MASK64) {
+            revert MalformedProof();
+        }
+        if (p.numPartitions == 0 || (p.numPartitions & (p.numPartitions - 1)) != 0) {
+            revert MalformedProof();
+        }
+        if (remainderLength == 0) {
+            revert MalformedProof();
+        }
+        if (remainderLength > MAX_REMAINDER_LENGTH) {
+            revert RemainderDegreeMismatch(MAX_REMAINDER_LENGTH - 1);
+        }
+
+        (p.remainder, ptr) = readElements(proof, ptr, remainderLength, EXT_DEGREE);
+        p.queriesOffset = ptr;
+        if (proof.length != ptr + NUM_QUERIES * QUERY_SIZE) {
+            revert MalformedProof();
+        }
+    }
+
+    function readWord(bytes calldata proof, uint256 ptr) private pure returns (uint256 value) {
+        if (ptr + 32 > proof.length) {
+            revert MalformedProof();
+        }
+        assembly {
+            value := calldataload(add(proof.offset, ptr))
+        }
+    }
+
+    /// Reads the specified number of field elements of the specified extension degree; each
+    /// coordinate of an element occupies a separate word.
+    function readElements(bytes calldata proof, uint256 ptr, uint256 count, uint256 degree)
+        private
+        pure
+        returns (uint256[] memory values, uint256 end)
+    {
+        values = new uint256[](count);
+        for (uint256 i = 0; i < count; i++) {
+            uint256 value = 0;
+            for (uint256 j = 0; j < degree; j++) {
+                uint256 coordinate = readWord(proof, ptr);
+                if (coordinate >= P) {
+                    revert InvalidFieldElement();
+                }
+                value |= coordinate << (64 * j);
+                ptr += 32;
+            }
+            values[i] = value;
+        }
+        end = ptr;
+    }
+
+    function publicInput(uint256[] calldata publicInputs, uint256 index)
+        private
+        pure
+        returns (uint256)
+    {
+        if (publicInputs.length == 0 || index >= publicInputs[0] || index + 1 >= publicInputs.length) {
+            revert MalformedPublicInputs();
+        }
+        return publicInputs[index + 1];
+    }
+
+    // HASHING AND MERKLE PATHS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root computed from a Merkle path which starts at the specified word of the
+    /// proof and authenticates the specified leaf at the specified index.
+    function verifyMerklePath(
+        bytes calldata proof,
+        uint256 ptr,
+        bytes32 leaf,
+        uint256 index,
+        uint256 depth
+    ) private pure returns (bytes32 node) {
+        node = leaf;
+        for (uint256 i = 0; i < depth; i++) {
+            bytes32 sibling = bytes32(readWord(proof, ptr + i * 32));
+            if ((index & 1) == 0) {
+                node = keccak256(abi.encodePacked(node, sibling));
+            } else {
+                node = keccak256(abi.encodePacked(sibling, node));
+            }
+            index >>= 1;
+        }
+    }
+
+    /// Hashes the canonical little-endian encodings of the coordinates of the specified elements.
+    function hashElements(uint256[] memory values, uint256 degree) private pure returns (bytes32) {
+        bytes memory buffer = new bytes(values.length * degree * 8);
+        uint256 offset = 0;
+        for (uint256 i = 0; i < values.length; i++) {
+            for (uint256 j = 0; j < degree; j++) {
+                uint256 coordinate = reverseBytes64((values[i] >> (64 * j)) & MASK64);
+                assembly {
+                    mstore(add(add(buffer, 32), offset), shl(192, coordinate))
+                }
+                offset += 8;
+            }
+        }
+        return keccak256(buffer);
+    }
+
+    function hashPublicInputs(uint256[] calldata publicInputs) private pure returns (bytes32) {
+        uint256[] memory context = contextElements();
+        uint256[] memory elements = new uint256[](context.length + publicInputs.length);
+        for (uint256 i = 0; i < context.length; i++) {
+            elements[i] = context[i];
+        }
+        for (uint256 i = 0; i < publicInputs.length; i++) {
+            if (publicInputs[i] >= P) {
+                revert InvalidFieldElement();
+            }
+            elements[context.length + i] = publicInputs[i];
+        }
+        return hashElements(elements, 1);
+    }
+
+    function mergeWithInt(bytes32 seed, uint256 value) private pure returns (bytes32) {
+        return keccak256(abi.encodePacked(seed, uint64(reverseBytes64(value))));
+    }
+
+    /// Interprets the first 8 bytes of the digest as a little-endian integer.
+    function leU64(bytes32 digest) private pure returns (uint256) {
+        return reverseBytes64(uint256(digest) >> 192);
+    }
+
+    function reverseBytes64(uint256 v) private pure returns (uint256) {
+        v = ((v & 0xFF00FF00FF00FF00) >> 8) | ((v & 0x00FF00FF00FF00FF) << 8);
+        v = ((v & 0xFFFF0000FFFF0000) >> 16) | ((v & 0x0000FFFF0000FFFF) << 16);
+        return ((v & 0xFFFFFFFF00000000) >> 32) | ((v & 0x00000000FFFFFFFF) << 32);
+    }
+
+    // RANDOM COIN
+    // --------------------------------------------------------------------------------------------
+
+    function absorb(Transcript memory t, uint256 label, bytes32 data) private pure {
+        t.seed = keccak256(abi.encodePacked(t.seed, mergeWithInt(data, label)));
+        t.counter = 0;
+    }
+
+    function draw(Transcript memory t) private pure returns (uint256) {
+        for (uint256 i = 0; i < MAX_DRAW_ATTEMPTS; i++) {
+            t.counter += 1;
+            uint256 value = uint256(mergeWithInt(t.seed, t.counter));
+            uint256 c0 = reverseBytes64(value >> 192);
+            uint256 c1 = EXT_DEGREE == 2 ? reverseBytes64((value >> 128) & MASK64) : 0;
+            if (c0 < P && c1 < P) {
+                return c0 | (c1 << 64);
+            }
+        }
+        revert RandomCoinError();
+    }
+
+    function drawMany(Transcript memory t, uint256 count) private pure returns (uint256[] memory values) {
+        values = new uint256[](count);
+        for (uint256 i = 0; i < count; i++) {
+            values[i] = draw(t);
+        }
+    }
+
+    function drawPositions(Transcript memory t, uint256 nonce)
+        private
+        pure
+        returns (uint256[] memory positions)
+    {
+        t.seed = mergeWithInt(t.seed, nonce);
+        t.counter = 0;
+        positions = new uint256[](NUM_QUERIES);
+        uint256 count = 0;
+        while (count < NUM_QUERIES) {
+            if (t.counter == 1000) {
+                revert RandomCoinError();
+            }
+            t.counter += 1;
+            uint256 position = leU64(mergeWithInt(t.seed, t.counter)) & (LDE_DOMAIN_SIZE - 1);
+
+            // positions are drawn without replacement; a position drawn already is skipped
+            bool isDrawn = false;
+            for (uint256 i = 0; i < count; i++) {
+                if (positions[i] == position) {
+                    isDrawn = true;
+                    break;
+                }
+            }
+            if (!isDrawn) {
+                positions[count] = position;
+                count += 1;
+            }
+        }
+    }
+
+    // FIELD ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    function mulB(uint256 a, uint256 b) private pure returns (uint256) {
+        return mulmod(a, b, P);
+    }
+
+    function expB(uint256 base, uint256 exponent) private pure returns (uint256 result) {
+        result = 1;
+        while (exponent > 0) {
+            if ((exponent & 1) == 1) {
+                result = mulmod(result, base, P);
+            }
+            base = mulmod(base, base, P);
+            exponent >>= 1;
+        }
+    }
+
+    function invB(uint256 a) private pure returns (uint256) {
+        return expB(a, P - 2);
+    }
+
+    function eAdd(uint256 a, uint256 b) private pure returns (uint256) {
+        if (EXT_DEGREE == 1) {
+            return addmod(a, b, P);
+        }
+        return addmod(a & MASK64, b & MASK64, P) | (addmod(a >> 64, b >> 64, P) << 64);
+    }
+
+    function eSub(uint256 a, uint256 b) private pure returns (uint256) {
+        if (EXT_DEGREE == 1) {
+            return addmod(a, P - b, P);
+        }
+        uint256 c0 = addmod(a & MASK64, P - (b & MASK64), P);
+        uint256 c1 = addmod(a >> 64, P - (b >> 64), P);
+        return c0 | (c1 << 64);
+    }
+
+    /// Multiplies elements of the quadratic extension defined by the polynomial x^2 - x + 2.
+    function eMul(uint256 a, uint256 b) private pure returns (uint256) {
+        if (EXT_DEGREE == 1) {
+            return mulmod(a, b, P);
+        }
+        uint256 a0 = a & MASK64;
+        uint256 a1 = a >> 64;
+        uint256 b0 = b & MASK64;
+        uint256 b1 = b >> 64;
+        uint256 a1b1 = mulmod(a1, b1, P);
+        uint256 c0 = addmod(mulmod(a0, b0, P), P - mulmod(2, a1b1, P), P);
+        uint256 c1 = addmod(addmod(mulmod(a0, b1, P), mulmod(a1, b0, P), P), a1b1, P);
+        return c0 | (c1 << 64);
+    }
+
+    function eMulBase(uint256 a, uint256 b) private pure returns (uint256) {
+        if (EXT_DEGREE == 1) {
+            return mulmod(a, b, P);
+        }
+        return mulmod(a & MASK64, b, P) | (mulmod(a >> 64, b, P) << 64);
+    }
+
+    function eExp(uint256 base, uint256 exponent) private pure returns (uint256 result) {
+        result = 1;
+        while (exponent > 0) {
+            if ((exponent & 1) == 1) {
+                result = eMul(result, base);
+            }
+            base = eMul(base, base);
+            exponent >>= 1;
+        }
+    }
+
+    function eInv(uint256 a) private pure returns (uint256) {
+        if (EXT_DEGREE == 1) {
+            return invB(a);
+        }
+        uint256 a0 = a & MASK64;
+        uint256 a1 = a >> 64;
+        uint256 norm = addmod(mulmod(a0, addmod(a0, a1, P), P), mulmod(2, mulmod(a1, a1, P), P), P);
+        uint256 normInv = invB(norm);
+        uint256 c0 = mulmod(addmod(a0, a1, P), normInv, P);
+        uint256 c1 = mulmod((P - a1) % P, normInv, P);
+        return c0 | (c1 << 64);
+    }
+
+    /// Evaluates the polynomial with the specified coefficients at x using Horner's method.
+    function evaluatePolynomial(uint256[] memory coefficients, uint256 x)
+        private
+        pure
+        returns (uint256 result)
+    {
+        for (uint256 i = coefficients.length; i > 0; i--) {
+            result = eAdd(eMul(result, x), coefficients[i - 1]);
+        }
+    }
+
+    // COMPUTATION-SPECIFIC FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+{{GENERATED_FUNCTIONS}}}
@@ -0,0 +1,390 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+use core::marker::PhantomData;
+
+use air::{
+    proof::{Commitments, Context, OodFrame, Queries, Table, TraceOodFrame},
+    Air, FieldExtension, PublicInputs,
+};
+use crypto::{BatchMerkleProof, ElementHasher, MerkleTree, Transcript};
+use fri::{FriOptions, FriProof, FriProofLayer, VerifierChannel as FriVerifierChannel};
+use math::{
+    fields::{CubeExtension, QuadExtension},
+    ExtensionOf, FieldElement, StarkField, ToElements,
+};
+use utils::{ByteReader, Deserializable, DeserializationError};
+
+use crate::{
+    channel::{
+        check_air_digest, check_num_queries, parse_ood_frame, ConstraintQueries, ProofChannel,
+        TraceQueries,
+    },
+    perform_verification, AcceptableOptions, VerifierError,
+};
+
+// STREAMING VERIFIER
+// ================================================================================================
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// by reading the proof from the specified byte `source`.
+///
+/// The proof is expected to be serialized via
+/// [Proof::write_stream_into()](air::proof::Proof::write_stream_into()). Sections of the proof
+/// are read from the `source` and verified as they are needed: the proof context is read and
+/// checked against `acceptable_options` before anything else, and trace queries, constraint
+/// queries, and FRI layers are read only after all preceding checks passed. Thus, the entire
+/// proof is never buffered in memory, and verification of an invalid proof stops as soon as an
+/// inconsistency is detected. To read a proof from a `std::io::Read` source, the source can be
+/// wrapped into a `ReadAdapter`.
+///
+/// When verification succeeds, the `source` is left positioned right after the end of the proof.
+///
+/// # Errors
+/// Returns an error if the proof could not be read from the `source`, or if the proof does not
+/// attest to a correct execution of the computation (see [verify()](crate::verify()) for details).
+pub fn verify_stream<AIR, HashFn, RandCoin, R>(
+    source: &mut R,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: Transcript<BaseField = AIR::BaseField, Hasher = HashFn>,
+    R: ByteReader,
+{
+    // read the proof context and check that the proof was generated with an acceptable set of
+    // parameters before reading any other part of the proof
+    let context = Context::read_from(source).map_err(deserialization_error)?;
+    acceptable_options.validate_context::<HashFn>(&context)?;
+    if AIR::BaseField::get_modulus_le_bytes() != context.field_modulus_bytes() {
+        return Err(VerifierError::InconsistentBaseField);
+    }
+
+    // build a seed for the public coin in the same way as it is done in verify()
+    let mut public_coin_seed = context.to_elements();
+    public_coin_seed.append(&mut pub_inputs.to_transcript_elements());
+    let public_coin = RandCoin::new(&public_coin_seed);
+
+    let air = AIR::new(context.trace_info().clone(), pub_inputs, context.options().clone());
+    check_num_queries(&air)?;
+    check_air_digest(&air, &context)?;
+
+    match air.options().field_extension() {
+        FieldExtension::None => match air.options().ood_field_extension() {
+            FieldExtension::None => {
+                let channel = StreamChannel::new(&air, source)?;
+                perform_verification::<AIR, AIR::BaseField, AIR::BaseField, HashFn, _, RandCoin>(
+                    &air,
+                    channel,
+                    public_coin,
+                )
+            },
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<AIR::BaseField>>::is_supported() {
+                    return Err(VerifierError::UnsupportedFieldExtension(2));
+                }
+                let channel = StreamChannel::new(&air, source)?;
+                perform_verification::<
+                    AIR,
+                    AIR::BaseField,
+                    QuadExtension<AIR::BaseField>,
+                    HashFn,
+                    _,
+                    RandCoin,
+                >(&air, channel, public_coin)
+            },
+            FieldExtension::Cubic => {
+                if !<CubeExtension<AIR::BaseField>>::is_supported() {
+                    return Err(VerifierError::UnsupportedFieldExtension(3));
+                }
+                let channel = StreamChannel::new(&air, source)?;
+                perform_verification::<
+                    AIR,
+                    AIR::BaseField,
+                    CubeExtension<AIR::BaseField>,
+                    HashFn,
+                    _,
+                    RandCoin,
+                >(&air, channel, public_coin)
+            },
+        },
+        FieldExtension::Quadratic => {
+            if !<QuadExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(2));
+            }
+            let channel = StreamChannel::new(&air, source)?;
+            perform_verification::<
+                AIR,
+                QuadExtension<AIR::BaseField>,
+                QuadExtension<AIR::BaseField>,
+                HashFn,
+                _,
+                RandCoin,
+            >(&air, channel, public_coin)
+        },
+        FieldExtension::Cubic => {
+            if !<CubeExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(3));
+            }
+            let channel = StreamChannel::new(&air, source)?;
+            perform_verification::<
+                AIR,
+                CubeExtension<AIR::BaseField>,
+                CubeExtension<AIR::BaseField>,
+                HashFn,
+                _,
+                RandCoin,
+            >(&air, channel, public_coin)
+        },
+    }
+}
+
+// STREAM CHANNEL
+// ================================================================================================
+
+/// A channel which reads sections of a proof from a byte stream as they are requested by the
+/// verifier.
+///
+/// Only the proof header (commitments, FRI remainder, DEEP values, and proof-of-work nonces) is
+/// read when the channel is instantiated. The out-of-domain frame, the queries, and each of the
+/// FRI layers are read and parsed when the verifier requests them, and are released as soon as
+/// the verifier is done with them.
+///
+/// Queries are parsed into elements of the field `F`, while the out-of-domain frame and FRI layers
+/// are parsed into elements of the field `E`.
+pub(crate) struct StreamChannel<'a, A, F, E, H, R>
+where
+    A: Air<BaseField = E::BaseField>,
+    F: FieldElement<BaseField = E::BaseField>,
+    E: FieldElement + ExtensionOf<F>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: ByteReader,
+{
+    air: &'a A,
+    source: &'a mut R,
+    num_unique_queries: usize,
+    // commitments
+    trace_roots: Vec<H::Digest>,
+    constraint_root: H::Digest,
+    fri_roots: Option<Vec<H::Digest>>,
+    // FRI proof
+    fri_options: FriOptions,
+    fri_domain_size: usize,
+    fri_layer_proof: Option<BatchMerkleProof<H>>,
+    fri_layer_queries: Option<Vec<E>>,
+    fri_layer_salts: Option<Vec<H::Digest>>,
+    fri_remainder: Option<Vec<E>>,
+    fri_deep_values: Option<Vec<E>>,
+    fri_num_partitions: usize,
+    fri_pow_nonce: u64,
+    // out-of-domain frame
+    ood_constraint_evaluations: Option<Vec<E>>,
+    // query proof-of-work
+    pow_nonce: u64,
+    gkr_proof: Option<Vec<u8>>,
+    _field: PhantomData<F>,
+}
+
+impl<'a, A, F, E, H, R> StreamChannel<'a, A, F, E, H, R>
+where
+    A: Air<BaseField = E::BaseField>,
+    F: FieldElement<BaseField = E::BaseField>,
+    E: FieldElement + ExtensionOf<F>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: ByteReader,
+{
+    /// Creates a new [StreamChannel] by reading the proof header from the specified `source`.
+    ///
+    /// The proof context is expected to have been read from the `source` already.
+    pub fn new(air: &'a A, source: &'a mut R) -> Result<Self, VerifierError> {
+        let lde_domain_size = air.lde_domain_size();
+        let fri_options = air.options().to_fri_options();
+
+        let num_unique_queries = source.read_u8().map_err(deserialization_error)? as usize;
+
+        // --- parse commitments ------------------------------------------------------------------
+        let commitments = Commitments::read_from(source).map_err(deserialization_error)?;
+        let (trace_roots, constraint_root, fri_roots) = commitments
+            .parse::<H>(
+                air.trace_info().num_segments(),
+                fri_options.num_fri_layers(lde_domain_size),
+            )
+            .map_err(deserialization_error)?;
+        let gkr_proof = Option::<Vec<u8>>::read_from(source).map_err(deserialization_error)?;
+
+        // --- parse FRI remainder and DEEP values ------------------------------------------------
+        let fri_proof = FriProof::read_header_from(source).map_err(deserialization_error)?;
+        let fri_remainder = fri_proof.parse_remainder().map_err(deserialization_error)?;
+        let fri_deep_values = fri_proof.parse_deep_values().map_err(deserialization_error)?;
+
+        let pow_nonce = source.read_u64().map_err(deserialization_error)?;
+
+        Ok(StreamChannel {
+            air,
+            source,
+            num_unique_queries,
+            // commitments
+            trace_roots,
+            constraint_root,
+            fri_roots: Some(fri_roots),
+            // FRI proof
+            fri_options,
+            fri_domain_size: lde_domain_size,
+            fri_layer_proof: None,
+            fri_layer_queries: None,
+            fri_layer_salts: None,
+            fri_remainder: Some(fri_remainder),
+            fri_deep_values: Some(fri_deep_values),
+            fri_num_partitions: fri_proof.num_partitions(),
+            fri_pow_nonce: fri_proof.pow_nonce(),
+            // out-of-domain frame
+            ood_constraint_evaluations: None,
+            // query seed
+            pow_nonce,
+            gkr_proof,
+            _field: PhantomData,
+        })
+    }
+}
+
+impl<A, F, E, H, R> ProofChannel<F, E> for StreamChannel<'_, A, F, E, H, R>
+where
+    A: Air<BaseField = E::BaseField>,
+    F: FieldElement<BaseField = E::BaseField>,
+    E: FieldElement + ExtensionOf<F>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: ByteReader,
+{
+    fn read_trace_commitments(&self) -> &[H::Digest] {
+        &self.trace_roots
+    }
+
+    fn read_constraint_commitment(&self) -> H::Digest {
+        self.constraint_root
+    }
+
+    fn read_gkr_proof(&self) -> Option<&Vec<u8>> {
+        self.gkr_proof.as_ref()
+    }
+
+    fn read_ood_trace_frame(&mut self) -> Result<TraceOodFrame<E>, VerifierError> {
+        let ood_frame = OodFrame::read_from(self.source).map_err(deserialization_error)?;
+        let (ood_trace_frame, ood_constraint_evaluations) = parse_ood_frame(ood_frame, self.air)?;
+        self.ood_constraint_evaluations = Some(ood_constraint_evaluations);
+        Ok(ood_trace_frame)
+    }
+
+    fn read_ood_constraint_evaluations(&mut self) -> Result<Vec<E>, VerifierError> {
+        Ok(self.ood_constraint_evaluations.take().expect("OOD frame has not been read yet"))
+    }
+
+    fn read_pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
+    fn read_queried_trace_states(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<(Table<F::BaseField>, Option<Table<F>>), VerifierError> {
+        let num_trace_segments = self.air.trace_info().num_segments();
+        let mut queries = Vec::with_capacity(num_trace_segments);
+        for _ in 0..num_trace_segments {
+            queries.push(Queries::read_from(self.source).map_err(deserialization_error)?);
+        }
+
+        TraceQueries::<F, H>::new(queries, self.air, self.num_unique_queries)?
+            .authenticate(&self.trace_roots, positions)
+    }
+
+    fn read_constraint_evaluations(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<Table<F>, VerifierError> {
+        let queries = Queries::read_from(self.source).map_err(deserialization_error)?;
+        ConstraintQueries::<F, H>::new(queries, self.air, self.num_unique_queries)?
+            .authenticate(&self.constraint_root, positions)
+    }
+}
+
+// FRI VERIFIER CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+impl<A, F, E, H, R> FriVerifierChannel<E> for StreamChannel<'_, A, F, E, H, R>
+where
+    A: Air<BaseField = E::BaseField>,
+    F: FieldElement<BaseField = E::BaseField>,
+    E: FieldElement + ExtensionOf<F>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: ByteReader,
+{
+    type Hasher = H;
+    type VectorCommitment = MerkleTree<H>;
+
+    fn read_fri_num_partitions(&self) -> usize {
+        self.fri_num_partitions
+    }
+
+    fn read_fri_pow_nonce(&self) -> u64 {
+        self.fri_pow_nonce
+    }
+
+    fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
+        self.fri_roots.take().expect("already read")
+    }
+
+    fn read_fri_deep_values(&mut self) -> Vec<E> {
+        self.fri_deep_values.take().expect("already read")
+    }
+
+    fn read_next_fri_layer(&mut self, layer_idx: usize) -> Result<(), fri::VerifierError> {
+        let folding_factor = self.fri_options.folding_factor_at(layer_idx);
+        self.fri_domain_size /= folding_factor;
+
+        let (layer_queries, layer_salts, layer_proof) = FriProofLayer::read_from(self.source)
+            .and_then(|layer| {
+                layer.parse::<H, E, MerkleTree<H>>(
+                    self.fri_domain_size,
+                    folding_factor,
+                    self.fri_options.salted_commitments(),
+                )
+            })
+            .map_err(|err| {
+                fri::VerifierError::ProofDeserializationError(format!(
+                    "failed to read FRI layer {layer_idx}: {err}"
+                ))
+            })?;
+
+        self.fri_layer_proof = Some(layer_proof);
+        self.fri_layer_queries = Some(layer_queries);
+        self.fri_layer_salts = Some(layer_salts);
+        Ok(())
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
+        self.fri_layer_proof.take().expect("FRI layer has not been read yet")
+    }
+
+    fn take_next_fri_layer_queries(&mut self) -> Vec<E> {
+        self.fri_layer_queries.take().expect("FRI layer has not been read yet")
+    }
+
+    fn take_next_fri_layer_salts(&mut self) -> Vec<H::Digest> {
+        self.fri_layer_salts.take().expect("FRI layer has not been read yet")
+    }
+
+    fn take_fri_remainder(&mut self) -> Vec<E> {
+        self.fri_remainder.take().expect("already read")
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn deserialization_error(err: DeserializationError) -> VerifierError {
+    VerifierError::ProofDeserializationError(err.to_string())
+}
@@ -150,8 +150,8 @@
 //! ```no_run
 //! use winterfell::{
 //!     math::{fields::f128::BaseElement, FieldElement, ToElements},
-//!     Air, AirContext, Assertion, GkrVerifier, EvaluationFrame,
-//!     ProofOptions, TraceInfo, TransitionConstraintDegree,
+//!     Air, AirContext, Assertion, ByteWriter, GkrVerifier, EvaluationFrame,
+//!     ProofOptions, Serializable, TraceInfo, TransitionConstraintDegree,
 //!     crypto::{hashers::Blake3_256, DefaultRandomCoin},
 //! };
 //!
@@ -168,6 +168,15 @@
 //!     }
 //! }
 //!
+//! // We also need to describe how public inputs can be serialized into bytes; together with the
+//! // field elements, these bytes are absorbed into the proof transcript.
+//! impl Serializable for PublicInputs {
+//!     fn write_into<W: ByteWriter>(&self, target: &mut W) {
+//!         target.write(self.start);
+//!         target.write(self.result);
+//!     }
+//! }
+//!
 //! // For a specific instance of our computation, we'll keep track of the public inputs and
 //! // the computation's context which we'll build in the constructor. The context is used
 //! // internally by the Winterfell prover/verifier when interpreting this AIR.
@@ -265,8 +274,9 @@
 //! };
 //!
 //! # use winterfell::{
-//! #   Air, AirContext, Assertion, AuxRandElements, ByteWriter, DefaultConstraintEvaluator,
-//! #   EvaluationFrame, TraceInfo, TransitionConstraintDegree,
+//! #   Air, AirContext, Assertion, AuxRandElements, ByteWriter, CompositionPoly,
+//! #   DefaultConstraintCommitment, DefaultConstraintEvaluator, EvaluationFrame, Serializable,
+//! #   TraceInfo, TransitionConstraintDegree,
 //! # };
 //! #
 //! # pub struct PublicInputs {
@@ -280,6 +290,13 @@
 //! #     }
 //! # }
 //! #
+//! # impl Serializable for PublicInputs {
+//! #     fn write_into<W: ByteWriter>(&self, target: &mut W) {
+//! #         target.write(self.start);
+//! #         target.write(self.result);
+//! #     }
+//! # }
+//! #
 //! # pub struct WorkAir {
 //! #     context: AirContext<BaseElement>,
 //! #     start: BaseElement,
@@ -351,6 +368,8 @@
 //!     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
 //!     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
 //!         DefaultConstraintEvaluator<'a, Self::Air, E>;
+//!     type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+//!         DefaultConstraintCommitment<E, Self::HashFn>;
 //!
 //!     // Our public inputs consist of the first and last value in the execution trace.
 //!     fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
@@ -382,6 +401,14 @@
 //!     ) -> Self::ConstraintEvaluator<'a, E> {
 //!         DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
 //!     }
+//!
+//!     fn new_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+//!         &self,
+//!         composition_poly: &CompositionPoly<E>,
+//!         domain: &StarkDomain<Self::BaseField>,
+//!     ) -> Self::ConstraintCommitment<E> {
+//!         DefaultConstraintCommitment::new(composition_poly, domain)
+//!     }
 //! }
 //! ```
 //!
@@ -398,9 +425,9 @@
 //! #    math::{fields::f128::BaseElement, FieldElement, ToElements},
 //! #    matrix::ColMatrix,
 //! #    Air, AirContext, Assertion, AuxRandElements, ByteWriter, DefaultConstraintEvaluator,
-//! #    DefaultTraceLde, EvaluationFrame, TraceInfo,
+//! #    CompositionPoly, DefaultConstraintCommitment, DefaultTraceLde, EvaluationFrame, TraceInfo,
 //! #    TransitionConstraintDegree, TraceTable, FieldExtension, Prover,
-//! #    ProofOptions, StarkDomain, Proof, Trace, TracePolyTable,
+//! #    ProofOptions, Serializable, StarkDomain, Proof, Trace, TracePolyTable,
 //! # };
 //! #
 //! # pub fn build_do_work_trace(start: BaseElement, n: usize) -> TraceTable<BaseElement> {
@@ -429,6 +456,13 @@
 //! #     }
 //! # }
 //! #
+//! # impl Serializable for PublicInputs {
+//! #     fn write_into<W: ByteWriter>(&self, target: &mut W) {
+//! #         target.write(self.start);
+//! #         target.write(self.result);
+//! #     }
+//! # }
+//! #
 //! # pub struct WorkAir {
 //! #     context: AirContext<BaseElement>,
 //! #     start: BaseElement,
@@ -494,6 +528,8 @@
 //! #    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
 //! #    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
 //! #        DefaultConstraintEvaluator<'a, Self::Air, E>;
+//! #    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+//! #        DefaultConstraintCommitment<E, Self::HashFn>;
 //! #
 //! #    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
 //! #        let last_step = trace.length() - 1;
@@ -525,6 +561,14 @@
 //! #        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
 //! #    }
 //! #
+//! #    fn new_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+//! #        &self,
+//! #        composition_poly: &CompositionPoly<E>,
+//! #        domain: &StarkDomain<Self::BaseField>,
+//! #    ) -> Self::ConstraintCommitment<E> {
+//! #        DefaultConstraintCommitment::new(composition_poly, domain)
+//! #    }
+//! #
 //! #  }
 //! #
 //! // We'll just hard-code the parameters here for this example. We'll also just run the
@@ -592,16 +636,26 @@
 extern crate std;
 
 pub use air::{AuxRandElements, GkrVerifier};
+#[cfg(feature = "mmap")]
+pub use prover::MmapTraceLde;
 pub use prover::{
-    crypto, iterators, math, matrix, Air, AirContext, Assertion, AuxTraceWithMetadata,
-    BoundaryConstraint, BoundaryConstraintGroup, ByteReader, ByteWriter, CompositionPolyTrace,
-    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintEvaluator,
-    DeepCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde, Deserializable,
-    DeserializationError, EvaluationFrame, FieldExtension, Proof, ProofOptions, Prover,
-    ProverError, ProverGkrProof, Serializable, SliceReader, StarkDomain, Trace, TraceInfo,
+    crypto, iterators, math, matrix, Air, AirComposition, AirContext, Assertion,
+    AuxTraceWithMetadata, BoundaryConstraint, BoundaryConstraintGroup, ByteReader, ByteWriter,
+    CancellationToken, CheckpointPhase, CommittedTrace, ComposedInputs, CompositionPoly,
+    CompositionPolyTrace, ConstraintCommitment, ConstraintCompositionCoefficients,
+    ConstraintDivisor, ConstraintEvaluator, ConstraintViolation, DeepCompositionCoefficients,
+    DefaultConstraintCommitment, DefaultConstraintEvaluator, DefaultProver, DefaultTraceLde,
+    Deserializable, DeserializationError, EvaluationFrame, Expr, FieldExtension, HashFunction,
+    LookupArgument, MemoryBudget, MemoryEstimate, Proof, ProofOptions, Prover, ProverCheckpoint,
+    ProverError, ProverFamily, ProverGkrProof, ProverObserver, ProvingKey, PublicInputs,
+    PublicSequence, SecurityTargetError, Serializable, SliceReader, SpeedPreference, StarkDomain,
+    StoneFriParameters, StoneParametersError, StoneProofParameters, Trace, TraceBuilder, TraceInfo,
     TraceLde, TracePolyTable, TraceTable, TraceTableFragment, TransitionConstraintDegree,
 };
-pub use verifier::{verify, AcceptableOptions, VerifierError};
+pub use verifier::{
+    circuit, recursion, solidity, verify, verify_batch, verify_stream, AcceptableOptions,
+    DefaultVerifier, OptionsPolicy, VerifierError,
+};
 
 #[cfg(test)]
 mod tests;
@@ -3,12 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use std::{vec, vec::Vec};
+use std::{marker::PhantomData, string::ToString, sync::Mutex, time::Duration, vec, vec::Vec};
 
-use air::LagrangeKernelRandElements;
+use air::{proof::Context, LagrangeKernelRandElements};
 use prover::{
-    crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin},
-    math::{fields::f64::BaseElement, ExtensionOf, FieldElement},
+    crypto::{
+        hashers::{Blake3_256, Keccak256},
+        DefaultRandomCoin, Digest, ElementHasher, Hasher, RandomCoin,
+    },
+    math::{
+        fields::{f64::BaseElement, QuadExtension},
+        ExtensionOf, FieldElement, ToElements,
+    },
     matrix::ColMatrix,
 };
 
@@ -32,114 +38,2954 @@ fn test_complex_lagrange_kernel_air() {
     .unwrap()
 }
 
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap_trace_lde() {
+    // the budget allows extending a single column at a time, and thus, the auxiliary segment is
+    // extended and stored in two chunks
+    let trace = LagrangeComplexTrace::new(2_usize.pow(10), AUX_TRACE_WIDTH);
+    let budget = MemoryBudget::Bytes(trace.len() * 2 * BaseElement::ELEMENT_BYTES);
+    let prover = MmapLagrangeComplexProver {
+        inner: LagrangeComplexProver::new(AUX_TRACE_WIDTH),
+        options: ProofOptions::new(1, 2, 0, FieldExtension::None, 2, 1).with_memory_budget(budget),
+    };
+    let proof = prover.prove(trace.clone()).unwrap();
+
+    // the proof must be identical to the proof generated with the trace LDE kept in memory
+    let expected_proof = LagrangeComplexProver::new(AUX_TRACE_WIDTH).prove(trace).unwrap();
+    assert_eq!(expected_proof.to_bytes(), proof.to_bytes());
+
+    verify::<
+        LagrangeKernelComplexAir,
+        Blake3_256<BaseElement>,
+        DefaultRandomCoin<Blake3_256<BaseElement>>,
+    >(proof, (), &AcceptableOptions::MinConjecturedSecurity(0))
+    .unwrap()
+}
+
+#[test]
+fn test_zk_counter_air() {
+    let trace = build_counter_trace(2_usize.pow(8));
+
+    let prover = ZkCounterProver::new([1; 32]);
+    let proof = prover.prove(trace.clone()).unwrap();
+    assert!(proof.options().is_zk());
+
+    verify::<CounterAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof.clone(),
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+
+    // proofs for the same trace generated from the same seed must be identical
+    let same_proof = ZkCounterProver::new([1; 32]).prove(trace.clone()).unwrap();
+    assert_eq!(proof.to_bytes(), same_proof.to_bytes());
+
+    // proofs for the same trace generated from different seeds must be different
+    let other_proof = ZkCounterProver::new([2; 32]).prove(trace).unwrap();
+    assert_ne!(proof.to_bytes(), other_proof.to_bytes());
+}
+
+#[test]
+fn test_zk_lagrange_kernel_air_unsupported() {
+    let trace = LagrangeComplexTrace::new(2_usize.pow(10), AUX_TRACE_WIDTH);
+    let prover = LagrangeComplexProver {
+        aux_trace_width: AUX_TRACE_WIDTH,
+        options: ProofOptions::new(1, 2, 0, FieldExtension::None, 2, 1).with_zk(),
+    };
+
+    assert_eq!(Err(ProverError::UnsupportedZkLagrangeKernel), prover.prove(trace));
+}
+
+#[test]
+fn test_permutation_rap_air() {
+    let trace = build_permutation_trace(2_usize.pow(8));
+
+    let prover = PermutationProver::new();
+    let proof = prover.prove(trace).unwrap();
+
+    verify::<PermutationAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_lookup_air() {
+    let trace = build_lookup_trace(2_usize.pow(8));
+
+    let prover = LookupProver::new();
+    let proof = prover.prove(trace).unwrap();
+
+    verify::<LookupAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_multi_row_frame_air() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace = build_multi_row_trace(2_usize.pow(8));
+
+    let prover = MultiRowProver::new(options.clone());
+    let proof = prover.prove(trace.clone()).unwrap();
+
+    verify::<MultiRowAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+
+    // the same computation must also be provable in zero-knowledge mode
+    let prover = MultiRowProver::new(options.with_zk());
+    let proof = prover.prove(trace).unwrap();
+
+    verify::<MultiRowAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_custom_divisor_air() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace = build_custom_divisor_trace(2_usize.pow(8));
+
+    let prover = CustomDivisorProver::new(options.clone());
+    let proof = prover.prove(trace.clone()).unwrap();
+
+    verify::<CustomDivisorAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+
+    // constraints with custom divisors are enforced on the last steps of the trace; thus, these
+    // steps cannot be blinded
+    let prover = CustomDivisorProver::new(options.with_zk());
+    assert!(matches!(
+        prover.prove(trace),
+        Err(ProverError::TransitionConstraintOnBlindedRow(_))
+    ));
+}
+
+#[test]
+fn test_public_sequence_air() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let sequence = PublicSequence::new((1..6_u32).map(|i| BaseElement::from(i * i)).collect());
+    let trace = build_public_sequence_trace(2_usize.pow(8), &sequence);
+
+    let prover = PublicSequenceProver::new(options, sequence.clone());
+    let proof = prover.prove(trace).unwrap();
+
+    let pub_inputs = PublicSequenceInputs { sequence, sum: BaseElement::from(55_u32) };
+    verify::<
+        PublicSequenceAir,
+        Blake3_256<BaseElement>,
+        DefaultRandomCoin<Blake3_256<BaseElement>>,
+    >(proof.clone(), pub_inputs, &AcceptableOptions::MinConjecturedSecurity(0))
+    .unwrap();
+
+    // the proof must not verify against a different sequence with the same sum
+    let sequence = PublicSequence::new([4_u32, 1, 9, 16, 25].map(BaseElement::from).to_vec());
+    let pub_inputs = PublicSequenceInputs { sequence, sum: BaseElement::from(55_u32) };
+    assert!(verify::<
+        PublicSequenceAir,
+        Blake3_256<BaseElement>,
+        DefaultRandomCoin<Blake3_256<BaseElement>>,
+    >(proof, pub_inputs, &AcceptableOptions::MinConjecturedSecurity(0))
+    .is_err());
+}
+
+#[test]
+fn test_validate_trace() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let sequence = PublicSequence::new((1..6_u32).map(|i| BaseElement::from(i * i)).collect());
+    let prover = PublicSequenceProver::new(options, sequence.clone());
+
+    let mut trace = build_public_sequence_trace(2_usize.pow(8), &sequence);
+    assert_eq!(Ok(()), prover.validate_trace(&trace));
+
+    // tampering with the accumulator at step 10 breaks the second transition constraint at the
+    // transitions into and out of that step
+    let delta = BaseElement::from(7_u32);
+    trace.set(2, 10, trace.get(2, 10) + delta);
+    let expected = vec![
+        ConstraintViolation::MainTransition {
+            constraint: 1,
+            step: 9,
+            value: delta.to_string(),
+        },
+        ConstraintViolation::MainTransition {
+            constraint: 1,
+            step: 10,
+            value: (-delta).to_string(),
+        },
+    ];
+    assert_eq!(
+        Err(ProverError::UnsatisfiedConstraints(expected)),
+        prover.validate_trace(&trace)
+    );
+
+    // constraints over the auxiliary trace segment are validated as well
+    let trace = build_lookup_trace(2_usize.pow(8));
+    assert_eq!(Ok(()), LookupProver::new().validate_trace(&trace));
+}
+
+#[test]
+fn test_trace_builder() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace_len = 2_usize.pow(8);
+
+    // a trace built by the builder is valid against the AIR, and is identical to the trace
+    // filled by hand
+    let builder = CounterTraceBuilder { trace_len, drift_step: None };
+    let trace = builder.build_validated(&options).unwrap();
+    assert_eq!(build_counter_trace(trace_len).get_column(0), trace.get_column(0));
+
+    let prover = CounterProver::<Blake3_256<BaseElement>> {
+        options: options.clone(),
+        _hasher: PhantomData,
+    };
+    let proof = prover.prove(trace).unwrap();
+    verify::<CounterAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+
+    // a transition function which drifts from the AIR is detected before a proof is generated
+    let builder = CounterTraceBuilder { trace_len, drift_step: Some(100) };
+    let expected = vec![ConstraintViolation::MainTransition {
+        constraint: 0,
+        step: 100,
+        value: BaseElement::ONE.to_string(),
+    }];
+    assert_eq!(
+        ProverError::UnsatisfiedConstraints(expected),
+        builder.build_validated(&options).unwrap_err()
+    );
+}
+
+#[test]
+fn test_air_composition() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let sequence = PublicSequence::new((1..6_u32).map(|i| BaseElement::from(i * i)).collect());
+    let trace_len = 2_usize.pow(8);
+    let trace = TraceTable::compose(
+        &build_public_sequence_trace(trace_len, &sequence),
+        &build_custom_divisor_trace(trace_len),
+    );
+    assert_eq!(5, trace.main_trace_width());
+
+    let prover = ComposedProver::new(options, sequence.clone());
+    let proof = prover.prove(trace).unwrap();
+
+    let pub_inputs = ComposedInputs {
+        first: PublicSequenceInputs { sequence, sum: BaseElement::from(55_u32) },
+        second: (),
+    };
+    verify::<ComposedAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        pub_inputs,
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_periodic_offset_air() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let pub_inputs = PeriodicOffsetInputs { cycle_length: 8, offset: 3 };
+    let trace = build_periodic_offset_trace(2_usize.pow(8), &pub_inputs);
+
+    let prover = PeriodicOffsetProver::new(options, pub_inputs.clone());
+    let proof = prover.prove(trace).unwrap();
+
+    verify::<
+        PeriodicOffsetAir,
+        Blake3_256<BaseElement>,
+        DefaultRandomCoin<Blake3_256<BaseElement>>,
+    >(proof.clone(), pub_inputs, &AcceptableOptions::MinConjecturedSecurity(0))
+    .unwrap();
+
+    // the proof must not verify against a periodic column with a different offset
+    let pub_inputs = PeriodicOffsetInputs { cycle_length: 8, offset: 4 };
+    assert!(verify::<
+        PeriodicOffsetAir,
+        Blake3_256<BaseElement>,
+        DefaultRandomCoin<Blake3_256<BaseElement>>,
+    >(proof, pub_inputs, &AcceptableOptions::MinConjecturedSecurity(0))
+    .is_err());
+}
+
+#[test]
+fn test_dynamic_width_air() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace_len = 2_usize.pow(8);
+
+    // the same AIR is used to prove computations over traces of different widths, including
+    // traces with more than 255 columns
+    for num_registers in [1, 5, 12, 300] {
+        let trace = build_dynamic_width_trace(trace_len, num_registers);
+        assert_eq!(num_registers, trace.main_trace_width());
+
+        let prover = DynamicWidthProver::new(options.clone());
+        let proof = prover.prove(trace).unwrap();
+        let proof = Proof::from_bytes(&proof.to_bytes()).unwrap();
+
+        verify::<
+            DynamicWidthAir,
+            Blake3_256<BaseElement>,
+            DefaultRandomCoin<Blake3_256<BaseElement>>,
+        >(
+            proof,
+            DynamicWidthInputs { num_registers },
+            &AcceptableOptions::MinConjecturedSecurity(0),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_cancelled_proof() {
+    let prover = PermutationProver::new();
+
+    // a cancelled token aborts proof generation at the first phase boundary
+    let token = CancellationToken::new();
+    token.cancel();
+    let trace = build_permutation_trace(2_usize.pow(8));
+    assert_eq!(Err(ProverError::Cancelled), prover.prove_with_cancellation(trace, Some(&token)));
+
+    // a token which has not been cancelled does not affect proof generation
+    let token = CancellationToken::new();
+    let trace = build_permutation_trace(2_usize.pow(8));
+    let proof = prover.prove_with_cancellation(trace, Some(&token)).unwrap();
+
+    verify::<PermutationAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_resume_from_checkpoint() {
+    let prover = PermutationProver::with_checkpoints();
+    let trace = build_permutation_trace(2_usize.pow(8));
+    let proof = prover.prove(trace).unwrap();
+
+    // a checkpoint is saved once the trace is committed to, and once constraint evaluations are
+    // committed to
+    let checkpoints = prover.checkpoints.lock().unwrap().clone();
+    let phases = [CheckpointPhase::TraceCommitted, CheckpointPhase::ConstraintsCommitted];
+    assert_eq!(phases.len(), checkpoints.len());
+
+    // resuming from any of the checkpoints produces the same proof
+    for (bytes, phase) in checkpoints.iter().zip(phases) {
+        let checkpoint = ProverCheckpoint::read_from_bytes(bytes).unwrap();
+        assert_eq!(phase, checkpoint.phase());
+        let resumed_proof = PermutationProver::new().resume((), checkpoint).unwrap();
+        assert_eq!(proof.to_bytes(), resumed_proof.to_bytes());
+    }
+
+    // a checkpoint cannot be resumed with different proof options
+    let checkpoint = ProverCheckpoint::read_from_bytes(&checkpoints[0]).unwrap();
+    let prover = PermutationProver {
+        options: ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 15),
+        ..PermutationProver::new()
+    };
+    assert!(matches!(prover.resume((), checkpoint), Err(ProverError::InvalidCheckpoint(_))));
+
+    verify::<PermutationAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_prover_observer() {
+    let prover = PermutationProver::with_observer();
+    let trace = build_permutation_trace(2_usize.pow(8));
+    let air = PermutationAir::new(trace.info().clone(), (), prover.options.clone());
+    let proof = prover.prove(trace).unwrap();
+
+    // the observer is notified about each phase in the order in which the phases are executed
+    let events = prover.observer.as_ref().unwrap().0.lock().unwrap().clone();
+    let lde_domain_size = air.lde_domain_size();
+    let num_fri_layers = prover.options.to_fri_options().num_fri_layers(lde_domain_size);
+    let mut expected = vec![
+        ("trace_built", air.trace_length()),
+        ("trace_committed", lde_domain_size),
+        ("constraints_evaluated", air.ce_domain_size()),
+        ("constraints_committed", air.context().num_constraint_composition_columns()),
+    ];
+    for i in 0..num_fri_layers {
+        expected.push(("fri_layer_committed", lde_domain_size / 4_usize.pow(i as u32)));
+    }
+    expected.push(("queries_built", proof.to_bytes().len()));
+    assert_eq!(expected, events);
+}
+
+#[test]
+fn test_query_seed_grinding() {
+    let grinding_factor = 16;
+    let prover = PermutationProver {
+        options: ProofOptions::new(4, 8, grinding_factor, FieldExtension::Quadratic, 4, 7),
+        ..PermutationProver::new()
+    };
+    let trace = build_permutation_trace(2_usize.pow(8));
+    let proof = prover.prove(trace.clone()).unwrap();
+
+    // the smallest valid nonce is always found, and thus, proof generation is deterministic
+    assert_eq!(proof.to_bytes(), prover.prove(trace).unwrap().to_bytes());
+
+    // a proof with a nonce which does not satisfy the proof-of-work is rejected
+    let mut invalid_proof = proof.clone();
+    invalid_proof.pow_nonce += 1;
+    let result = verify::<
+        PermutationAir,
+        Blake3_256<BaseElement>,
+        DefaultRandomCoin<Blake3_256<BaseElement>>,
+    >(invalid_proof, (), &AcceptableOptions::MinConjecturedSecurity(0));
+    assert_eq!(Err(VerifierError::QuerySeedProofOfWorkVerificationFailed), result);
+
+    verify::<PermutationAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_too_many_queries() {
+    // a trace of 8 rows extended by a blowup factor of 2 results in an LDE domain of 16 points,
+    // from which at most 15 distinct query positions can be drawn
+    let options = ProofOptions::new(16, 2, 0, FieldExtension::None, 2, 1);
+    let prover =
+        CounterProverFamily { options: options.clone() }.build_prover::<Blake3_256<BaseElement>>();
+    assert_eq!(Err(ProverError::TooManyQueries(16, 16)), prover.prove(build_counter_trace(8)));
+
+    let prover = CounterProverFamily {
+        options: ProofOptions::new(15, 2, 0, FieldExtension::None, 2, 1),
+    }
+    .build_prover::<Blake3_256<BaseElement>>();
+    let mut proof = prover.prove(build_counter_trace(8)).unwrap();
+    let verify_proof = |proof| {
+        verify::<CounterAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+            proof,
+            (),
+            &AcceptableOptions::MinConjecturedSecurity(0),
+        )
+    };
+    verify_proof(proof.clone()).unwrap();
+
+    // the verifier rejects such options before drawing query positions
+    proof.context = Context::from_air(&CounterAir::new(proof.trace_info().clone(), (), options));
+    assert_eq!(Err(VerifierError::TooManyQueries(16, 16)), verify_proof(proof));
+
+    // all but one position of an LDE domain of 256 points can be queried; drawing these needs
+    // more than 1000 draws from the public coin on average
+    let prover = CounterProverFamily {
+        options: ProofOptions::new(255, 2, 0, FieldExtension::None, 2, 1),
+    }
+    .build_prover::<Blake3_256<BaseElement>>();
+    let proof = prover.prove(build_counter_trace(128)).unwrap();
+    assert_eq!(255, proof.num_unique_queries);
+    verify_proof(proof).unwrap();
+}
+
+#[test]
+fn test_options_policy() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace = build_counter_trace(2_usize.pow(8));
+    let prover = CounterProverFamily { options }.build_prover::<Blake3_256<BaseElement>>();
+    let proof = prover.prove(trace).unwrap();
+    let verify_with = |policy: OptionsPolicy| {
+        verify::<CounterAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+            proof.clone(),
+            (),
+            &AcceptableOptions::Policy(policy),
+        )
+    };
+
+    let policy = OptionsPolicy::new()
+        .with_min_num_queries(4)
+        .with_min_blowup_factor(8)
+        .with_hash_functions(&[HashFunction::Blake3_256])
+        .with_field_extensions(&[FieldExtension::Quadratic]);
+    verify_with(policy.clone()).unwrap();
+
+    // each requirement of the policy is checked against the options embedded in the proof
+    assert_eq!(
+        Err(VerifierError::InsufficientNumQueries(5, 4)),
+        verify_with(policy.clone().with_min_num_queries(5))
+    );
+    assert_eq!(
+        Err(VerifierError::InsufficientBlowupFactor(16, 8)),
+        verify_with(policy.clone().with_min_blowup_factor(16))
+    );
+    assert_eq!(
+        Err(VerifierError::UnacceptableHashFunction(Some(HashFunction::Blake3_256))),
+        verify_with(policy.clone().with_hash_functions(&[HashFunction::Sha3_256]))
+    );
+    assert_eq!(
+        Err(VerifierError::UnacceptableFieldExtension(2)),
+        verify_with(policy.with_field_extensions(&[FieldExtension::None, FieldExtension::Cubic]))
+    );
+
+    // hash functions which cannot be selected at runtime are rejected by an allow-list
+    let prover = CounterProverFamily { options: proof.options().clone() }
+        .build_prover::<Keccak256<BaseElement>>();
+    let proof = prover.prove(build_counter_trace(2_usize.pow(8))).unwrap();
+    let policy = OptionsPolicy::new().with_hash_functions(&[HashFunction::Sha3_256]);
+    assert_eq!(
+        Err(VerifierError::UnacceptableHashFunction(None)),
+        verify::<CounterAir, Keccak256<BaseElement>, DefaultRandomCoin<Keccak256<BaseElement>>>(
+            proof,
+            (),
+            &AcceptableOptions::Policy(policy)
+        )
+    );
+}
+
+#[test]
+fn test_proven_security_policy() {
+    type Blake3 = Blake3_256<BaseElement>;
+
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let prove_with = |options: ProofOptions| {
+        let trace = build_counter_trace(2_usize.pow(8));
+        CounterProverFamily { options }.build_prover::<Blake3>().prove(trace).unwrap()
+    };
+    let verify_with = |proof: Proof, policy: OptionsPolicy| {
+        verify::<CounterAir, Blake3, DefaultRandomCoin<Blake3>>(
+            proof,
+            (),
+            &AcceptableOptions::Policy(policy),
+        )
+    };
+
+    let conjectured_proof = prove_with(options.clone());
+    let proven_proof = prove_with(options.with_proven_security());
+    assert!(proven_proof.options().uses_proven_security());
+
+    // a policy requiring proven security rejects proofs which do not declare it
+    let policy = OptionsPolicy::new().with_proven_security();
+    verify_with(proven_proof.clone(), policy.clone()).unwrap();
+    assert_eq!(
+        Err(VerifierError::ProvenSecurityRequired),
+        verify_with(conjectured_proof.clone(), policy)
+    );
+
+    // the minimal security level is checked using the bounds declared by the proof
+    let conjectured_level = conjectured_proof.security_level::<Blake3>(true);
+    let proven_level = proven_proof.security_level::<Blake3>(false);
+    assert_eq!(proven_level, proven_proof.declared_security_level::<Blake3>());
+    assert!(proven_level < conjectured_level);
+
+    let policy = OptionsPolicy::new().with_min_security_level(conjectured_level);
+    verify_with(conjectured_proof, policy.clone()).unwrap();
+    assert_eq!(
+        Err(VerifierError::InsufficientProvenSecurity(conjectured_level, proven_level)),
+        verify_with(proven_proof, policy)
+    );
+}
+
+#[test]
+fn test_verify_stream() {
+    type Blake3 = Blake3_256<BaseElement>;
+
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace = build_counter_trace(2_usize.pow(8));
+    let proof = CounterProverFamily { options }.build_prover::<Blake3>().prove(trace).unwrap();
+    let stream = proof.to_stream_bytes();
+
+    // the stream omits only the format version header and the number of FRI layers, which is
+    // implied by the proof options
+    assert_eq!(proof.to_bytes().len(), stream.len() + 4);
+
+    // the proof can be verified from a stream which is delivered in small chunks
+    let mut chunked = ChunkedReader { bytes: &stream, chunk_size: 100 };
+    let mut source = verifier::ReadAdapter::new(&mut chunked);
+    verify_stream::<CounterAir, Blake3, DefaultRandomCoin<Blake3>, _>(
+        &mut source,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+
+    let verify_bytes = |bytes: &[u8], acceptable_options: &AcceptableOptions| {
+        let mut source = SliceReader::new(bytes);
+        let result = verify_stream::<CounterAir, Blake3, DefaultRandomCoin<Blake3>, _>(
+            &mut source,
+            (),
+            acceptable_options,
+        );
+        (result, source.has_more_bytes())
+    };
+    let acceptable_options = AcceptableOptions::MinConjecturedSecurity(0);
+
+    // the entire stream is consumed by the verifier
+    assert_eq!((Ok(()), false), verify_bytes(&stream, &acceptable_options));
+
+    // proof options are checked before anything but the proof context is read
+    let context_size = proof.context.to_bytes().len();
+    assert_eq!(
+        Err(VerifierError::InsufficientConjecturedSecurity(
+            128,
+            proof.security_level::<Blake3>(true)
+        )),
+        verify_bytes(&stream[..context_size], &AcceptableOptions::MinConjecturedSecurity(128)).0
+    );
+
+    // a truncated stream is rejected when the missing section is requested
+    assert!(matches!(
+        verify_bytes(&stream[..stream.len() - 1], &acceptable_options).0,
+        Err(VerifierError::FriVerificationFailed(_))
+    ));
+
+    // a stream with a tampered FRI layer is rejected
+    let mut tampered = stream.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(verify_bytes(&tampered, &acceptable_options).0.is_err());
+}
+
+/// A reader which returns at most `chunk_size` bytes on every read.
+struct ChunkedReader<'a> {
+    bytes: &'a [u8],
+    chunk_size: usize,
+}
+
+impl std::io::Read for ChunkedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(self.chunk_size).min(self.bytes.len());
+        buf[..len].copy_from_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn test_ood_field_extension() {
+    type Blake3 = Blake3_256<BaseElement>;
+
+    // constraints are evaluated in the base field, while the out-of-domain point is drawn from
+    // the quadratic extension
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 7)
+        .with_ood_field_extension(FieldExtension::Quadratic);
+    let trace = build_permutation_trace(2_usize.pow(8));
+    let proof = PermutationProver::with_options(options).prove(trace).unwrap();
+    assert_eq!(FieldExtension::Quadratic, proof.options().ood_field_extension());
+
+    verify::<PermutationAir, Blake3, DefaultRandomCoin<Blake3>>(
+        proof.clone(),
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+
+    let stream = proof.to_stream_bytes();
+    verify_stream::<PermutationAir, Blake3, DefaultRandomCoin<Blake3>, _>(
+        &mut SliceReader::new(&stream),
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+
+    // random elements for the Lagrange kernel column are drawn in the base field as well
+    let prover = LagrangeComplexProver {
+        options: ProofOptions::new(1, 2, 0, FieldExtension::None, 2, 1)
+            .with_ood_field_extension(FieldExtension::Quadratic),
+        ..LagrangeComplexProver::new(AUX_TRACE_WIDTH)
+    };
+    let proof = prover
+        .prove(LagrangeComplexTrace::new(2_usize.pow(10), AUX_TRACE_WIDTH))
+        .unwrap();
+    verify::<LagrangeKernelComplexAir, Blake3, DefaultRandomCoin<Blake3>>(
+        proof,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_recursion_helpers() {
+    use recursion::{
+        compose_constraint_columns, compose_trace_columns, domain_point, evaluate_remainder,
+        extract_query_witness, fold_evaluations, fold_position, ood_point, replay_transcript,
+    };
+
+    type Blake3 = Blake3_256<BaseElement>;
+    type E = QuadExtension<BaseElement>;
+
+    let proof = PermutationProver::new().prove(build_permutation_trace(2_usize.pow(8))).unwrap();
+    let air = PermutationAir::new(proof.trace_info().clone(), (), proof.options().clone());
+    let challenges = replay_transcript::<PermutationAir, E, Blake3, DefaultRandomCoin<Blake3>>(
+        proof.clone(),
+        (),
+    )
+    .unwrap();
+    let positions = &challenges.query_positions;
+    assert_eq!(proof.num_unique_queries as usize, positions.len());
+
+    // parse the queried values from the proof
+    let lde_domain_size = air.lde_domain_size();
+    let offset = air.domain_offset();
+    let (_, main_states) = proof.trace_queries[0]
+        .clone()
+        .parse::<Blake3, BaseElement>(lde_domain_size, positions.len(), 2)
+        .unwrap();
+    let (_, aux_states) = proof.trace_queries[1]
+        .clone()
+        .parse::<Blake3, E>(lde_domain_size, positions.len(), 1)
+        .unwrap();
+    let num_composition_columns = air.context().num_constraint_composition_columns();
+    let (_, constraint_evaluations) = proof
+        .constraint_queries
+        .clone()
+        .parse::<Blake3, E>(lde_domain_size, positions.len(), num_composition_columns)
+        .unwrap();
+    let (ood_frame, ood_evaluations) = proof
+        .ood_frame
+        .clone()
+        .parse::<E>(2, 1, num_composition_columns, air.context().frame_offsets())
+        .unwrap();
+    let fri_options = air.options().to_fri_options();
+    let remainder = proof.fri_proof.parse_remainder::<E>().unwrap();
+    let (layer_queries, ..) = proof
+        .fri_proof
+        .clone()
+        .parse_layers::<Blake3, E, crypto::MerkleTree<Blake3>>(lde_domain_size, &fri_options)
+        .unwrap();
+
+    // the evaluations of the DEEP composition polynomial at the queried positions match the
+    // values committed to in the first FRI layer, and the values of each FRI layer fold into the
+    // values of the next layer
+    let z = challenges.z;
+    let g_trace = air.trace_domain_generator();
+    let ood_points = [ood_point(z, g_trace, 0), ood_point(z, g_trace, 1)];
+    let ood_rows = [ood_frame.row(0), ood_frame.row(1)];
+    let cc = &challenges.deep_coefficients;
+    let folding_factor = fri_options.folding_factor();
+    assert_eq!(4, folding_factor);
+
+    let mut evaluations: Vec<E> = positions
+        .iter()
+        .enumerate()
+        .map(|(i, &position)| {
+            let x = domain_point(offset, lde_domain_size, position);
+            let mut trace_values: Vec<E> =
+                main_states.get_row(i).iter().map(|&v| E::from(v)).collect();
+            trace_values.extend_from_slice(aux_states.get_row(i));
+            compose_trace_columns(x, &ood_points, &trace_values, &ood_rows, &cc.trace)
+                + compose_constraint_columns(
+                    x,
+                    z,
+                    constraint_evaluations.get_row(i),
+                    &ood_evaluations,
+                    &cc.constraints,
+                )
+        })
+        .collect();
+
+    let mut positions = positions.clone();
+    let mut domain_size = lde_domain_size;
+    for (layer, &alpha) in layer_queries.iter().zip(&challenges.fri_alphas) {
+        let mut folded_positions = Vec::new();
+        let mut folded_evaluations = Vec::new();
+        for (&position, &evaluation) in positions.iter().zip(&evaluations) {
+            let (folded_position, value_idx) = fold_position(position, domain_size, folding_factor);
+            let row_idx = folded_positions.iter().position(|&p| p == folded_position);
+            let row_idx = row_idx.unwrap_or(folded_positions.len());
+            let values: [E; 4] =
+                layer[row_idx * folding_factor..][..folding_factor].try_into().unwrap();
+            assert_eq!(evaluation, values[value_idx]);
+
+            if row_idx == folded_positions.len() {
+                let x = domain_point(offset, domain_size, folded_position);
+                folded_positions.push(folded_position);
+                folded_evaluations.push(fold_evaluations(&values, x, alpha));
+            }
+        }
+        positions = folded_positions;
+        evaluations = folded_evaluations;
+        domain_size /= folding_factor;
+    }
+    for (&position, &evaluation) in positions.iter().zip(&evaluations) {
+        let x = domain_point(offset, domain_size, position);
+        assert_eq!(evaluation, evaluate_remainder(&remainder, x));
+    }
+
+    // the extracted query witness holds the same values as computed above
+    let witness =
+        extract_query_witness::<PermutationAir, E, Blake3, DefaultRandomCoin<Blake3>>(proof, ())
+            .unwrap();
+    assert_eq!(remainder, witness.remainder);
+    assert_eq!(challenges.query_positions, witness.challenges.query_positions);
+    for (i, &position) in challenges.query_positions.iter().enumerate() {
+        let (_, value_idx) = fold_position(position, lde_domain_size, folding_factor);
+        assert_eq!(layer_queries.len(), witness.layer_values[i].len());
+        assert_eq!(witness.deep_evaluations[i], witness.layer_values[i][0][value_idx]);
+    }
+
+    // a folded value is independent of how the values are interpolated
+    let values = [E::from(3u32), E::from(5u32)];
+    let x = BaseElement::from(7u32);
+    let alpha = E::from(11u32);
+    let two_x = E::from(x + x);
+    assert_eq!(
+        two_x * fold_evaluations(&values, x, alpha),
+        E::from(x) * (values[0] + values[1]) + alpha * (values[0] - values[1])
+    );
+    assert_eq!(values[1], fold_evaluations(&values, x, E::from(-x)));
+}
+
+#[test]
+fn test_verification_circuit() {
+    use circuit::{CircuitError, VerificationCircuit};
+
+    type Blake3 = Blake3_256<BaseElement>;
+    type E = QuadExtension<BaseElement>;
+
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let prove = |values: [u32; 5]| {
+        let sequence = PublicSequence::new(values.map(BaseElement::from).to_vec());
+        let trace = build_public_sequence_trace(2_usize.pow(8), &sequence);
+        let proof = PublicSequenceProver::new(options.clone(), sequence.clone())
+            .prove(trace)
+            .unwrap();
+        let sum = BaseElement::from(values.iter().sum::<u32>());
+        (proof, PublicSequenceInputs { sequence, sum })
+    };
+
+    // the witness extracted from a valid proof satisfies the circuit
+    let (proof, pub_inputs) = prove([1, 4, 9, 16, 25]);
+    let air = PublicSequenceAir::new(
+        proof.trace_info().clone(),
+        pub_inputs.clone(),
+        proof.options().clone(),
+    );
+    let (circuit, witness) =
+        VerificationCircuit::<E>::build::<PublicSequenceAir, Blake3, DefaultRandomCoin<Blake3>>(
+            proof, pub_inputs,
+        )
+        .unwrap();
+    assert_eq!(circuit.num_elements(), witness.elements.len());
+    circuit.check::<_, Blake3, DefaultRandomCoin<Blake3>>(&air, &witness).unwrap();
+
+    // a tampered witness does not satisfy the circuit
+    let mut tampered = witness.clone();
+    let last = tampered.elements.len() - 1;
+    tampered.elements[last] += E::ONE;
+    assert!(matches!(
+        circuit.check::<_, Blake3, DefaultRandomCoin<Blake3>>(&air, &tampered),
+        Err(CircuitError::UnsatisfiedOperation(_))
+    ));
+    let mut tampered = witness.clone();
+    tampered.integers.pop();
+    assert_eq!(
+        Err(CircuitError::WitnessSizeMismatch),
+        circuit.check::<_, Blake3, DefaultRandomCoin<Blake3>>(&air, &tampered)
+    );
+
+    // proofs of the same computation with the same options have the same circuit
+    let (proof, pub_inputs) = prove([2, 3, 5, 7, 11]);
+    let (other_circuit, other_witness) =
+        VerificationCircuit::<E>::build::<PublicSequenceAir, Blake3, DefaultRandomCoin<Blake3>>(
+            proof, pub_inputs,
+        )
+        .unwrap();
+    assert_eq!(circuit, other_circuit);
+    assert_ne!(witness, other_witness);
+    circuit
+        .check::<_, Blake3, DefaultRandomCoin<Blake3>>(&air, &other_witness)
+        .unwrap_err();
+
+    // auxiliary trace segments are covered by the circuit as well
+    let proof = PermutationProver::new().prove(build_permutation_trace(2_usize.pow(8))).unwrap();
+    let air = PermutationAir::new(proof.trace_info().clone(), (), proof.options().clone());
+    let (circuit, witness) = VerificationCircuit::<E>::build::<
+        PermutationAir,
+        Blake3,
+        DefaultRandomCoin<Blake3>,
+    >(proof, ())
+    .unwrap();
+    circuit.check::<_, Blake3, DefaultRandomCoin<Blake3>>(&air, &witness).unwrap();
+}
+
+#[test]
+fn test_prove_batch() {
+    let prover = PermutationProver::new();
+    let trace_lengths = [2_usize.pow(8), 2_usize.pow(9), 2_usize.pow(8)];
+    let traces = trace_lengths.iter().map(|&length| build_permutation_trace(length)).collect();
+    let proofs = prover.prove_batch(traces).unwrap();
+    assert_eq!(trace_lengths.len(), proofs.len());
+
+    // proofs generated in a batch are identical to the proofs generated one by one
+    for (proof, &trace_length) in proofs.into_iter().zip(trace_lengths.iter()) {
+        let expected_proof = prover.prove(build_permutation_trace(trace_length)).unwrap();
+        assert_eq!(expected_proof.to_bytes(), proof.to_bytes());
+
+        verify::<
+            PermutationAir,
+            Blake3_256<BaseElement>,
+            DefaultRandomCoin<Blake3_256<BaseElement>>,
+        >(proof, (), &AcceptableOptions::MinConjecturedSecurity(0))
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_verify_batch() {
+    let prover = PermutationProver::new();
+    let traces = vec![build_permutation_trace(2_usize.pow(8)); 3];
+    let mut proofs = prover.prove_batch(traces).unwrap();
+
+    // the second proof draws query positions from a different seed, and thus, is invalid
+    proofs[1].pow_nonce += 1;
+
+    let results = verify_batch::<
+        PermutationAir,
+        Blake3_256<BaseElement>,
+        DefaultRandomCoin<Blake3_256<BaseElement>>,
+    >(
+        proofs.into_iter().map(|proof| (proof, ())).collect(),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    );
+    assert_eq!(3, results.len());
+    assert_eq!(Ok(()), results[0]);
+    assert!(results[1].is_err());
+    assert_eq!(Ok(()), results[2]);
+}
+
+#[test]
+fn test_prove_committed_trace() {
+    let trace_length = 2_usize.pow(8);
+    let prover = PermutationProver::new();
+    let mut committed_trace = prover.commit_trace(build_permutation_trace(trace_length)).unwrap();
+
+    // proofs generated from a committed trace with different numbers of queries and grinding
+    // factors are identical to the proofs generated from scratch
+    for (num_queries, grinding_factor) in [(4, 0), (16, 0), (8, 4)] {
+        let options =
+            ProofOptions::new(num_queries, 8, grinding_factor, FieldExtension::Quadratic, 4, 7);
+        let prover = PermutationProver::with_options(options);
+        let proof = prover.prove_committed(&mut committed_trace).unwrap();
+        let expected_proof = prover.prove(build_permutation_trace(trace_length)).unwrap();
+        assert_eq!(expected_proof.to_bytes(), proof.to_bytes());
+
+        verify::<
+            PermutationAir,
+            Blake3_256<BaseElement>,
+            DefaultRandomCoin<Blake3_256<BaseElement>>,
+        >(proof, (), &AcceptableOptions::MinConjecturedSecurity(0))
+        .unwrap();
+    }
+
+    // options which affect the commitment to the main trace segment are rejected
+    let incompatible_options = [
+        ProofOptions::new(4, 16, 0, FieldExtension::Quadratic, 4, 7),
+        ProofOptions::new(4, 8, 0, FieldExtension::Cubic, 4, 7),
+        ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7).with_zk(),
+    ];
+    for options in incompatible_options {
+        let result = PermutationProver::with_options(options).prove_committed(&mut committed_trace);
+        assert!(matches!(result, Err(ProverError::IncompatibleCommittedTrace(_))));
+    }
+}
+
+#[test]
+fn test_prove_with_key() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace_length = 2_usize.pow(8);
+
+    // proofs generated with a proving key are identical to the proofs generated without it
+    let prover = CustomDivisorProver::new(options.clone());
+    let key = prover.build_proving_key(&build_custom_divisor_trace(trace_length));
+    assert_eq!(trace_length, key.trace_info().length());
+    for _ in 0..2 {
+        let proof = prover.prove_with_key(&key, build_custom_divisor_trace(trace_length)).unwrap();
+        let expected_proof = prover.prove(build_custom_divisor_trace(trace_length)).unwrap();
+        assert_eq!(expected_proof.to_bytes(), proof.to_bytes());
+    }
+
+    // periodic columns of AIR instances which differ from the ones the key was built for are
+    // evaluated from scratch
+    let pub_inputs = PeriodicOffsetInputs { cycle_length: 8, offset: 3 };
+    let key = PeriodicOffsetProver::new(options.clone(), pub_inputs.clone())
+        .build_proving_key(&build_periodic_offset_trace(trace_length, &pub_inputs));
+    for offset in [3, 4] {
+        let pub_inputs = PeriodicOffsetInputs { cycle_length: 8, offset };
+        let prover = PeriodicOffsetProver::new(options.clone(), pub_inputs.clone());
+        let trace = build_periodic_offset_trace(trace_length, &pub_inputs);
+        let proof = prover.prove_with_key(&key, trace.clone()).unwrap();
+        assert_eq!(prover.prove(trace).unwrap().to_bytes(), proof.to_bytes());
+
+        verify::<
+            PeriodicOffsetAir,
+            Blake3_256<BaseElement>,
+            DefaultRandomCoin<Blake3_256<BaseElement>>,
+        >(proof, pub_inputs, &AcceptableOptions::MinConjecturedSecurity(0))
+        .unwrap();
+    }
+
+    // keys built for a different trace length or different options are rejected
+    let prover = CustomDivisorProver::new(options.clone());
+    let result = prover.prove_with_key(&key, build_custom_divisor_trace(trace_length * 2));
+    assert!(matches!(result, Err(ProverError::IncompatibleProvingKey(_))));
+    let key = CustomDivisorProver::new(options.with_zk())
+        .build_proving_key(&build_custom_divisor_trace(trace_length));
+    let result = prover.prove_with_key(&key, build_custom_divisor_trace(trace_length));
+    assert!(matches!(result, Err(ProverError::IncompatibleProvingKey(_))));
+}
+
+#[test]
+fn test_air_digest() {
+    type Blake3 = Blake3_256<BaseElement>;
+
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace = build_custom_divisor_trace(2_usize.pow(8));
+    let proof = CustomDivisorProver::new(options.clone()).prove(trace).unwrap();
+
+    // the proof context binds the structure of the AIR the proof was generated for
+    let air = CustomDivisorAir::new(proof.trace_info().clone(), (), options.clone());
+    assert_eq!(&air::get_air_digest(&air), proof.context.air_digest());
+    let acceptable_options = AcceptableOptions::MinConjecturedSecurity(0);
+    verify::<CustomDivisorAir, Blake3, DefaultRandomCoin<Blake3>>(
+        proof.clone(),
+        (),
+        &acceptable_options,
+    )
+    .unwrap();
+
+    // a verifier for a structurally different AIR over a trace of the same shape rejects the
+    // proof before checking any of its commitments
+    let result = verify::<MultiRowAir, Blake3, DefaultRandomCoin<Blake3>>(
+        proof.clone(),
+        (),
+        &acceptable_options,
+    );
+    assert_eq!(Err(VerifierError::InconsistentAirDigest), result);
+    let result = verify_stream::<MultiRowAir, Blake3, DefaultRandomCoin<Blake3>, _>(
+        &mut SliceReader::new(&proof.to_stream_bytes()),
+        (),
+        &acceptable_options,
+    );
+    assert_eq!(Err(VerifierError::InconsistentAirDigest), result);
+
+    // the same happens when the digest in the proof is replaced
+    let mut proof = proof;
+    proof.context = Context::new::<BaseElement>(proof.trace_info().clone(), options, [0; 32]);
+    let result = verify::<CustomDivisorAir, Blake3, DefaultRandomCoin<Blake3>>(
+        proof,
+        (),
+        &acceptable_options,
+    );
+    assert_eq!(Err(VerifierError::InconsistentAirDigest), result);
+}
+
+#[test]
+fn test_runtime_hash_function() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let hash_fns = [
+        HashFunction::Blake3_192,
+        HashFunction::Blake3_256,
+        HashFunction::Sha3_256,
+        HashFunction::Rp64_256,
+        HashFunction::RpJive64_256,
+        HashFunction::Poseidon64_256,
+    ];
+
+    for hash_fn in hash_fns {
+        let trace = build_counter_trace(2_usize.pow(8));
+        let prover = DefaultProver::new(CounterProverFamily { options: options.clone() }, hash_fn);
+        let proof = prover.prove(trace).unwrap();
+
+        let verifier = DefaultVerifier::new(hash_fn);
+        verifier
+            .verify::<CounterAir>(proof.clone(), (), &AcceptableOptions::MinConjecturedSecurity(0))
+            .unwrap();
+
+        // the proof must not verify with a different hash function
+        let other_hash_fn = if hash_fn == HashFunction::Blake3_256 {
+            HashFunction::Sha3_256
+        } else {
+            HashFunction::Blake3_256
+        };
+        assert!(DefaultVerifier::new(other_hash_fn)
+            .verify::<CounterAir>(proof, (), &AcceptableOptions::MinConjecturedSecurity(0))
+            .is_err());
+    }
+
+    // proofs generated via runtime dispatch are identical to proofs generated directly
+    let trace = build_counter_trace(2_usize.pow(8));
+    let family = CounterProverFamily { options };
+    let expected_proof = family.build_prover::<Blake3_256<BaseElement>>().prove(trace.clone());
+    let proof = DefaultProver::new(family, HashFunction::Blake3_256).prove(trace);
+    assert_eq!(expected_proof.unwrap().to_bytes(), proof.unwrap().to_bytes());
+}
+
+#[test]
+fn test_proof_serde() {
+    let proof = PermutationProver::new().prove(build_permutation_trace(2_usize.pow(8))).unwrap();
+
+    // proofs and their components survive a round trip through a text-based format
+    let json = serde_json::to_string(&proof).unwrap();
+    let decoded: Proof = serde_json::from_str(&json).unwrap();
+    assert_eq!(proof, decoded);
+    assert_eq!(proof.to_bytes(), decoded.to_bytes());
+
+    let options = proof.options().clone().with_zk();
+    let json = serde_json::to_string(&options).unwrap();
+    assert_eq!(options, serde_json::from_str::<ProofOptions>(&json).unwrap());
+
+    verify::<PermutationAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        decoded,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_stone_parameters_serde() {
+    // the `stark` section of a Stone proof parameters file
+    let json = r#"{
+        "fri": {
+            "fri_step_list": [0, 4, 4, 4, 4],
+            "last_layer_degree_bound": 16,
+            "n_queries": 18,
+            "proof_of_work_bits": 24
+        },
+        "log_n_cosets": 4
+    }"#;
+    let params: StoneProofParameters = serde_json::from_str(json).unwrap();
+    let options = ProofOptions::from_stone_parameters(&params, 1 << 20).unwrap();
+    assert_eq!(ProofOptions::new(18, 16, 24, FieldExtension::None, 16, 15), options);
+}
+
+#[test]
+fn test_proof_compression() {
+    let proof = PermutationProver::new().prove(build_permutation_trace(2_usize.pow(8))).unwrap();
+
+    // elements of the 64-bit field cannot be packed, and zstd is applied only if it helps; thus,
+    // the compressed proof is at most one byte larger than the regular one
+    let compressed = proof.to_bytes_compressed();
+    assert!(compressed.len() <= proof.to_bytes().len() + 1);
+
+    let decoded = Proof::from_bytes_compressed(&compressed).unwrap();
+    assert_eq!(proof, decoded);
+
+    verify::<PermutationAir, Blake3_256<BaseElement>, DefaultRandomCoin<Blake3_256<BaseElement>>>(
+        decoded,
+        (),
+        &AcceptableOptions::MinConjecturedSecurity(0),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_solidity_verifier() {
+    type Keccak = Keccak256<BaseElement>;
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let trace = build_counter_trace(2_usize.pow(8));
+    let proof = CounterProverFamily { options: options.clone() }
+        .build_prover::<Keccak>()
+        .prove(trace)
+        .unwrap();
+    let air = CounterAir::new(proof.trace_info().clone(), (), options.clone());
+
+    // the generated contract embeds the transition constraint, its divisor with the exemption
+    // point, and all protocol parameters
+    let constraint = Expr::col(0).next() - Expr::col(0) - BaseElement::ONE;
+    let source = solidity::ContractGenerator::new(&air, vec![constraint.clone()])
+        .with_contract_name("CounterVerifier")
+        .generate()
+        .unwrap();
+    assert!(source.contains("contract CounterVerifier {"));
+    assert!(source.contains("eMul(c.transitionCoeffs[0], eSub(eSub(frame[1], frame[0]), 1))"));
+    assert!(source.contains("eMul(c.boundaryCoeffs[0], eSub(frame[0], 0))"));
+    assert!(!source.contains("{{"));
+
+    // the calldata contains the header followed by a fixed-size entry for every drawn query
+    let query_size = source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("uint256 internal constant QUERY_SIZE = "))
+        .and_then(|value| value.trim_end_matches(';').parse::<usize>().ok())
+        .unwrap();
+    let calldata = solidity::ProofCalldata::new::<CounterAir>(proof.clone(), ()).unwrap();
+    let num_fri_layers = options.to_fri_options().num_fri_layers(air.lde_domain_size());
+    let num_ood_elements = 2 + air.context().num_constraint_composition_columns();
+    let num_remainder_elements =
+        proof.fri_proof.num_remainder_elements::<QuadExtension<BaseElement>>();
+    let header_size =
+        32 * (num_fri_layers + 3) + 64 * (num_ood_elements + num_remainder_elements) + 96;
+    assert_eq!(header_size + options.num_queries() * query_size, calldata.proof().len());
+    let (trace_roots, ..) = proof.commitments.clone().parse::<Keccak>(1, num_fri_layers).unwrap();
+    assert_eq!(trace_roots[0].as_bytes(), calldata.proof()[..32]);
+    assert_eq!(&[0, 0], calldata.public_inputs());
+
+    let abi_call = calldata.to_abi_call();
+    assert_eq!(&Keccak::hash(b"verify(uint256[],bytes)").as_bytes()[..4], &abi_call[..4]);
+    assert_eq!(0, (abi_call.len() - 4) % 32);
+
+    // constraints which do not match the AIR and unsupported proof options are rejected
+    let invalid_constraint = Expr::col(0).next() - Expr::col(0);
+    assert_eq!(
+        Err(solidity::SolidityError::ConstraintMismatch(0)),
+        solidity::ContractGenerator::new(&air, vec![invalid_constraint]).generate()
+    );
+    assert_eq!(
+        Err(solidity::SolidityError::UnknownPublicAssertion(0, 1)),
+        solidity::ContractGenerator::new(&air, vec![constraint.clone()])
+            .with_public_assertion(0, 1, 0)
+            .generate()
+    );
+    let zk_air = CounterAir::new(proof.trace_info().clone(), (), options.with_zk());
+    assert_eq!(
+        Err(solidity::SolidityError::ZeroKnowledgeNotSupported),
+        solidity::ContractGenerator::new(&zk_air, vec![constraint]).generate()
+    );
+}
+
 // LagrangeComplexTrace
 // =================================================================================================
 
-#[derive(Clone, Debug)]
-struct LagrangeComplexTrace {
-    // dummy main trace
-    main_trace: ColMatrix<BaseElement>,
-    info: TraceInfo,
+#[derive(Clone, Debug)]
+struct LagrangeComplexTrace {
+    // dummy main trace
+    main_trace: ColMatrix<BaseElement>,
+    info: TraceInfo,
+}
+
+impl LagrangeComplexTrace {
+    fn new(trace_len: usize, aux_segment_width: usize) -> Self {
+        assert!(trace_len < u32::MAX.try_into().unwrap());
+
+        let main_trace_col: Vec<BaseElement> =
+            (0..trace_len).map(|idx| BaseElement::from(idx as u32)).collect();
+
+        Self {
+            main_trace: ColMatrix::new(vec![main_trace_col]),
+            info: TraceInfo::new_multi_segment(1, aux_segment_width, 0, trace_len, vec![]),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.main_trace.num_rows()
+    }
+}
+
+impl Trace for LagrangeComplexTrace {
+    type BaseField = BaseElement;
+
+    fn info(&self) -> &TraceInfo {
+        &self.info
+    }
+
+    fn main_segment(&self) -> &ColMatrix<Self::BaseField> {
+        &self.main_trace
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = row_idx + 1;
+        assert_ne!(next_row_idx, self.len());
+
+        self.main_trace.read_row_into(row_idx, frame.current_mut());
+        self.main_trace.read_row_into(next_row_idx, frame.next_mut());
+    }
+}
+
+// AIR
+// =================================================================================================
+
+#[derive(Debug, Clone, Default)]
+struct DummyGkrVerifier;
+
+impl GkrVerifier for DummyGkrVerifier {
+    // `GkrProof` is log(trace_len) for this dummy example, so that the verifier knows how many aux
+    // random variables to generate
+    type GkrProof = usize;
+    type Error = VerifierError;
+
+    fn verify<E, Hasher>(
+        &self,
+        gkr_proof: usize,
+        public_coin: &mut impl RandomCoin<BaseField = E::BaseField, Hasher = Hasher>,
+    ) -> Result<LagrangeKernelRandElements<E>, Self::Error>
+    where
+        E: FieldElement,
+        Hasher: crypto::ElementHasher<BaseField = E::BaseField>,
+    {
+        let log_trace_len = gkr_proof;
+        let lagrange_kernel_rand_elements: LagrangeKernelRandElements<E> = {
+            let mut rand_elements = Vec::with_capacity(log_trace_len);
+            for _ in 0..log_trace_len {
+                rand_elements.push(public_coin.draw().unwrap());
+            }
+
+            LagrangeKernelRandElements::new(rand_elements)
+        };
+
+        Ok(lagrange_kernel_rand_elements)
+    }
+}
+
+struct LagrangeKernelComplexAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for LagrangeKernelComplexAir {
+    type BaseField = BaseElement;
+    // `GkrProof` is log(trace_len) for this dummy example, so that the verifier knows how many aux
+    // random variables to generate
+    type GkrProof = usize;
+    type GkrVerifier = DummyGkrVerifier;
+
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        Self {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                vec![TransitionConstraintDegree::new(1)],
+                vec![TransitionConstraintDegree::new(1)],
+                1,
+                1,
+                Some(1),
+                options,
+            ),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: math::FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current()[0];
+        let next = frame.next()[0];
+
+        // increments by 1
+        result[0] = next - current - E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        _main_frame: &EvaluationFrame<F>,
+        _aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        _aux_rand_elements: &[E],
+        _result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        // do nothing
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &[E],
+    ) -> Vec<Assertion<E>> {
+        vec![Assertion::single(0, 0, E::ZERO)]
+    }
+
+    fn get_auxiliary_proof_verifier<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+    ) -> Self::GkrVerifier {
+        DummyGkrVerifier
+    }
+}
+
+// LagrangeComplexProver
+// ================================================================================================
+
+struct LagrangeComplexProver {
+    aux_trace_width: usize,
+    options: ProofOptions,
+}
+
+impl LagrangeComplexProver {
+    fn new(aux_trace_width: usize) -> Self {
+        Self {
+            aux_trace_width,
+            options: ProofOptions::new(1, 2, 0, FieldExtension::None, 2, 1),
+        }
+    }
+}
+
+impl Prover for LagrangeComplexProver {
+    type BaseField = BaseElement;
+    type Air = LagrangeKernelComplexAir;
+    type Trace = LagrangeComplexTrace;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, LagrangeKernelComplexAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+
+    fn generate_gkr_proof<E>(
+        &self,
+        main_trace: &Self::Trace,
+        public_coin: &mut Self::RandomCoin,
+    ) -> (ProverGkrProof<Self>, LagrangeKernelRandElements<E>)
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let main_trace = main_trace.main_segment();
+        let log_trace_len = main_trace.num_rows().ilog2() as usize;
+        let lagrange_kernel_rand_elements: Vec<E> = {
+            let mut rand_elements = Vec::with_capacity(log_trace_len);
+            for _ in 0..log_trace_len {
+                rand_elements.push(public_coin.draw().unwrap());
+            }
+
+            rand_elements
+        };
+
+        (log_trace_len, LagrangeKernelRandElements::new(lagrange_kernel_rand_elements))
+    }
+
+    fn build_aux_trace<E>(
+        &self,
+        main_trace: &Self::Trace,
+        aux_rand_elements: &AuxRandElements<E>,
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let main_trace = main_trace.main_segment();
+        let lagrange_kernel_rand_elements = aux_rand_elements
+            .lagrange()
+            .expect("expected lagrange random elements to be present.");
+
+        let mut columns = Vec::new();
+
+        // First all other auxiliary columns
+        let rand_summed = lagrange_kernel_rand_elements.iter().fold(E::ZERO, |acc, &r| acc + r);
+        for _ in 1..self.aux_trace_width {
+            // building a dummy auxiliary column
+            let column = main_trace
+                .get_column(0)
+                .iter()
+                .map(|row_val| rand_summed.mul_base(*row_val))
+                .collect();
+
+            columns.push(column);
+        }
+
+        // then build the Lagrange kernel column
+        {
+            let r = &lagrange_kernel_rand_elements;
+
+            let mut lagrange_col = Vec::with_capacity(main_trace.num_rows());
+
+            for row_idx in 0..main_trace.num_rows() {
+                let mut row_value = E::ONE;
+                for (bit_idx, &r_i) in r.iter().enumerate() {
+                    if row_idx & (1 << bit_idx) == 0 {
+                        row_value *= E::ONE - r_i;
+                    } else {
+                        row_value *= r_i;
+                    }
+                }
+                lagrange_col.push(row_value);
+            }
+
+            columns.push(lagrange_col);
+        }
+
+        ColMatrix::new(columns)
+    }
+}
+
+/// Same as [LagrangeComplexProver], but stores the extended execution trace in temporary files.
+#[cfg(feature = "mmap")]
+struct MmapLagrangeComplexProver {
+    inner: LagrangeComplexProver,
+    options: ProofOptions,
+}
+
+#[cfg(feature = "mmap")]
+impl Prover for MmapLagrangeComplexProver {
+    type BaseField = BaseElement;
+    type Air = LagrangeKernelComplexAir;
+    type Trace = LagrangeComplexTrace;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = MmapTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, LagrangeKernelComplexAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        MmapTraceLde::new(trace_info, main_trace, domain, self.options.memory_budget())
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+
+    fn generate_gkr_proof<E>(
+        &self,
+        main_trace: &Self::Trace,
+        public_coin: &mut Self::RandomCoin,
+    ) -> (ProverGkrProof<Self>, LagrangeKernelRandElements<E>)
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.inner.generate_gkr_proof(main_trace, public_coin)
+    }
+
+    fn build_aux_trace<E>(
+        &self,
+        main_trace: &Self::Trace,
+        aux_rand_elements: &AuxRandElements<E>,
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.inner.build_aux_trace(main_trace, aux_rand_elements)
+    }
+}
+
+// COUNTER AIR
+// ================================================================================================
+
+fn build_counter_trace(trace_len: usize) -> TraceTable<BaseElement> {
+    let column = (0..trace_len).map(|i| BaseElement::from(i as u32)).collect();
+    TraceTable::init(vec![column])
+}
+
+/// A counter which increments by one at every step; in zero-knowledge mode, the blinded rows at
+/// the end of the trace are exempt from the transition constraint.
+struct CounterAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for CounterAir {
+    type BaseField = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let num_exemptions = options.num_blinding_rows() + 1;
+        let context =
+            AirContext::new(trace_info, vec![TransitionConstraintDegree::new(1)], 1, options)
+                .set_num_transition_exemptions(num_exemptions);
+        Self { context }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: math::FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0] - E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+}
+
+struct ZkCounterProver {
+    options: ProofOptions,
+    seed: [u8; 32],
+}
+
+impl ZkCounterProver {
+    fn new(seed: [u8; 32]) -> Self {
+        Self {
+            options: ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7).with_zk(),
+            seed,
+        }
+    }
+}
+
+impl Prover for ZkCounterProver {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, CounterAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn zk_seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+}
+
+/// Builds provers of [CounterAir] instantiated with any hash function.
+struct CounterProverFamily {
+    options: ProofOptions,
+}
+
+impl ProverFamily for CounterProverFamily {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+    type Trace = TraceTable<BaseElement>;
+    type Prover<H: ElementHasher<BaseField = BaseElement>> = CounterProver<H>;
+
+    fn build_prover<H: ElementHasher<BaseField = BaseElement>>(&self) -> CounterProver<H> {
+        CounterProver {
+            options: self.options.clone(),
+            _hasher: PhantomData,
+        }
+    }
+}
+
+struct CounterProver<H> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> Prover for CounterProver<H> {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, CounterAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+}
+
+/// Builds the trace of a counter; if `drift_step` is set, the counter is incremented by two
+/// rather than by one at that step.
+struct CounterTraceBuilder {
+    trace_len: usize,
+    drift_step: Option<usize>,
+}
+
+impl TraceBuilder for CounterTraceBuilder {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::new(1, self.trace_len)
+    }
+
+    fn init_state(&self, state: &mut [BaseElement]) {
+        state[0] = BaseElement::ZERO;
+    }
+
+    fn update_state(&self, step: usize, state: &mut [BaseElement]) {
+        state[0] += BaseElement::ONE;
+        if self.drift_step == Some(step) {
+            state[0] += BaseElement::ONE;
+        }
+    }
+
+    fn get_pub_inputs(&self, _trace: &TraceTable<BaseElement>) {}
+}
+
+// PERMUTATION AIR
+// ================================================================================================
+
+/// Builds a trace in which the first column is a counter, and the first `trace_len - 1` values of
+/// the second column are the corresponding values of the first column in reverse order.
+fn build_permutation_trace(trace_len: usize) -> TraceTable<BaseElement> {
+    let last_step = trace_len - 1;
+    let counter = (0..trace_len).map(|i| BaseElement::from(i as u32)).collect();
+    let permuted = (0..trace_len)
+        .map(|i| if i == last_step { i } else { last_step - 1 - i })
+        .map(|i| BaseElement::from(i as u32))
+        .collect();
+    TraceTable::init(vec![counter, permuted]).with_aux_segment(1, 1)
+}
+
+/// Enforces that the values in the two main trace columns are permutations of each other via a
+/// running product column in the auxiliary trace segment.
+struct PermutationAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for PermutationAir {
+    type BaseField = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        Self {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                vec![TransitionConstraintDegree::new(1)],
+                vec![TransitionConstraintDegree::new(2)],
+                1,
+                2,
+                None,
+                options,
+            ),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: math::FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0] - E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &[E],
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let alpha = aux_rand_elements[0];
+        let main_current = main_frame.current();
+
+        // p' * (b + alpha) = p * (a + alpha)
+        result[0] = aux_frame.next()[0] * (alpha + main_current[1].into())
+            - aux_frame.current()[0] * (alpha + main_current[0].into());
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &[E],
+    ) -> Vec<Assertion<E>> {
+        let last_step = self.trace_length() - 1;
+        vec![Assertion::single(0, 0, E::ONE), Assertion::single(0, last_step, E::ONE)]
+    }
+}
+
+struct PermutationProver {
+    options: ProofOptions,
+    saves_checkpoints: bool,
+    checkpoints: Mutex<Vec<Vec<u8>>>,
+    observer: Option<EventRecorder>,
+}
+
+impl PermutationProver {
+    fn new() -> Self {
+        Self {
+            options: ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7),
+            saves_checkpoints: false,
+            checkpoints: Mutex::new(Vec::new()),
+            observer: None,
+        }
+    }
+
+    fn with_options(options: ProofOptions) -> Self {
+        Self { options, ..Self::new() }
+    }
+
+    fn with_checkpoints() -> Self {
+        Self { saves_checkpoints: true, ..Self::new() }
+    }
+
+    fn with_observer() -> Self {
+        Self {
+            observer: Some(EventRecorder(Mutex::new(Vec::new()))),
+            ..Self::new()
+        }
+    }
+}
+
+impl Prover for PermutationProver {
+    type BaseField = BaseElement;
+    type Air = PermutationAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, PermutationAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn saves_checkpoints(&self) -> bool {
+        self.saves_checkpoints
+    }
+
+    fn save_checkpoint(&self, checkpoint: &ProverCheckpoint) {
+        self.checkpoints.lock().unwrap().push(checkpoint.to_bytes());
+    }
+
+    fn observer(&self) -> Option<&dyn ProverObserver> {
+        self.observer.as_ref().map(|observer| observer as &dyn ProverObserver)
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+
+    fn build_aux_trace<E>(
+        &self,
+        main_trace: &Self::Trace,
+        aux_rand_elements: &AuxRandElements<E>,
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let main_trace = main_trace.main_segment();
+        let alpha = aux_rand_elements.rand_elements()[0];
+
+        let mut running_product = Vec::with_capacity(main_trace.num_rows());
+        running_product.push(E::ONE);
+        for step in 0..main_trace.num_rows() - 1 {
+            let numerator = alpha + E::from(main_trace.get(0, step));
+            let denominator = alpha + E::from(main_trace.get(1, step));
+            running_product.push(running_product[step] * numerator / denominator);
+        }
+
+        ColMatrix::new(vec![running_product])
+    }
+}
+
+// LOOKUP AIR
+// ================================================================================================
+
+/// Builds a trace in which the first column is a table with values in the range [0, trace_len),
+/// the second column contains lookup multiplicities, and the last two columns contain values
+/// looked up in the table.
+fn build_lookup_trace(trace_len: usize) -> TraceTable<BaseElement> {
+    // the last row is exempt from transition constraints, and thus, does not take part in lookups
+    let num_steps = trace_len - 1;
+    let mut witness1 = (0..num_steps).map(|i| (3 * i) % num_steps).collect::<Vec<_>>();
+    let mut witness2 = (0..num_steps).map(|i| (i * i) % num_steps).collect::<Vec<_>>();
+    witness1.push(0);
+    witness2.push(0);
+
+    let mut multiplicities = vec![0_u32; trace_len];
+    for &value in witness1[..num_steps].iter().chain(witness2[..num_steps].iter()) {
+        multiplicities[value] += 1;
+    }
+
+    let table = (0..trace_len).collect::<Vec<_>>();
+    let to_column = |values: &[usize]| -> Vec<BaseElement> {
+        values.iter().map(|&v| BaseElement::from(v as u32)).collect()
+    };
+    TraceTable::init(vec![
+        to_column(&table),
+        multiplicities.into_iter().map(BaseElement::from).collect(),
+        to_column(&witness1),
+        to_column(&witness2),
+    ])
+    .with_aux_segment(1, LookupArgument::NUM_RAND_ELEMENTS)
+}
+
+/// Enforces that values in the last two columns of the trace are contained in the table defined
+/// by the first column.
+struct LookupAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for LookupAir {
+    type BaseField = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let lookup = LookupArgument::new(vec![0], 1, vec![vec![2], vec![3]]);
+        let context = AirContext::new_multi_segment(
+            trace_info,
+            vec![TransitionConstraintDegree::new(1)],
+            vec![],
+            1,
+            0,
+            None,
+            options,
+        )
+        .with_lookups(vec![lookup]);
+        Self { context }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: math::FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0] - E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+}
+
+struct LookupProver {
+    options: ProofOptions,
+}
+
+impl LookupProver {
+    fn new() -> Self {
+        Self {
+            options: ProofOptions::new(4, 8, 0, FieldExtension::Quadratic, 4, 7),
+        }
+    }
+}
+
+impl Prover for LookupProver {
+    type BaseField = BaseElement;
+    type Air = LookupAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, LookupAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+}
+
+// MULTI-ROW FRAME AIR
+// ================================================================================================
+
+/// Offset of the most distant row referenced by transition constraints of [MultiRowAir].
+const MULTI_ROW_OFFSET: usize = 5;
+
+/// Builds a trace in which the first column is a counter, and every value of the second column
+/// (except for the first few) is the sum of values located [MULTI_ROW_OFFSET] and
+/// `MULTI_ROW_OFFSET - 1` rows above it.
+fn build_multi_row_trace(trace_len: usize) -> TraceTable<BaseElement> {
+    let counter = (0..trace_len).map(|i| BaseElement::from(i as u32)).collect();
+    let mut values = vec![BaseElement::ONE; MULTI_ROW_OFFSET];
+    for i in MULTI_ROW_OFFSET..trace_len {
+        values.push(values[i - MULTI_ROW_OFFSET] + values[i - MULTI_ROW_OFFSET + 1]);
+    }
+    TraceTable::init(vec![counter, values])
+}
+
+/// Evaluates transition constraints over rows `i`, `i + 1`, and `i + MULTI_ROW_OFFSET`.
+struct MultiRowAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for MultiRowAir {
+    type BaseField = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
+        let context = AirContext::new(trace_info, degrees, 2, options).with_frame_offsets(vec![
+            0,
+            1,
+            MULTI_ROW_OFFSET,
+        ]);
+        assert_eq!(MULTI_ROW_OFFSET, context.num_transition_exemptions());
+
+        // in zero-knowledge mode, rows which reference blinded rows must also be exempt
+        let num_exemptions = context.num_blinding_rows() + context.max_frame_offset();
+        let context = context.set_num_transition_exemptions(num_exemptions);
+        Self { context }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: math::FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        assert_eq!(3, frame.num_rows());
+        result[0] = frame.next()[0] - frame.current()[0] - E::ONE;
+        result[1] = frame.row(2)[1] - frame.current()[1] - frame.next()[1];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, 0, BaseElement::ONE),
+        ]
+    }
+}
+
+struct MultiRowProver {
+    options: ProofOptions,
+}
+
+impl MultiRowProver {
+    fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Prover for MultiRowProver {
+    type BaseField = BaseElement;
+    type Air = MultiRowAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, MultiRowAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn zk_seed(&self) -> [u8; 32] {
+        [1; 32]
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+}
+
+// CUSTOM DIVISOR AIR
+// ================================================================================================
+
+/// Number of steps after which the second column of [CustomDivisorAir] trace is reset to zero.
+const RESET_PERIOD: usize = 8;
+
+/// Builds a trace in which the first column is a counter, and the second column accumulates
+/// values of the counter and is reset to zero every [RESET_PERIOD] steps.
+fn build_custom_divisor_trace(trace_len: usize) -> TraceTable<BaseElement> {
+    let counter = (0..trace_len).map(|i| BaseElement::from(i as u32)).collect::<Vec<_>>();
+    let mut sums = vec![BaseElement::ZERO];
+    for i in 1..trace_len {
+        if i % RESET_PERIOD == 0 {
+            sums.push(BaseElement::ZERO);
+        } else {
+            sums.push(sums[i - 1] + counter[i - 1]);
+        }
+    }
+    TraceTable::init(vec![counter, sums])
+}
+
+/// Enforces the accumulator via two constraints with custom divisors: an accumulation constraint
+/// which holds on all steps except for the last step of every cycle, and a reset constraint which
+/// holds only on the last step of every cycle.
+struct CustomDivisorAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for CustomDivisorAir {
+    type BaseField = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let trace_len = trace_info.length();
+        let degrees = vec![TransitionConstraintDegree::new(1); 3];
+        let reset_steps = (0..trace_len)
+            .filter(|i| i % RESET_PERIOD == RESET_PERIOD - 1)
+            .collect::<Vec<_>>();
+
+        // in zero-knowledge mode, the blinded rows are exempt from the default divisor
+        let context = AirContext::new(trace_info, degrees, 2, options);
+        let num_exemptions = context.num_blinding_rows() + 1;
+        let context = context
+            .set_num_transition_exemptions(num_exemptions)
+            .with_transition_divisor(
+                1,
+                ConstraintDivisor::from_transition_exemptions(trace_len, &reset_steps),
+            )
+            .with_transition_divisor(
+                2,
+                ConstraintDivisor::from_periodic_transition(
+                    trace_len,
+                    RESET_PERIOD - 1,
+                    RESET_PERIOD,
+                ),
+            );
+        Self { context }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: math::FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0] - E::ONE;
+        result[1] = frame.next()[1] - frame.current()[1] - frame.current()[0];
+        result[2] = frame.next()[1];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, 0, BaseElement::ZERO),
+        ]
+    }
+}
+
+struct CustomDivisorProver {
+    options: ProofOptions,
+}
+
+impl CustomDivisorProver {
+    fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Prover for CustomDivisorProver {
+    type BaseField = BaseElement;
+    type Air = CustomDivisorAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, CustomDivisorAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+}
+
+// PUBLIC SEQUENCE AIR
+// ================================================================================================
+
+/// Step at which the public sequence starts in the second column of [PublicSequenceAir] trace.
+const SEQUENCE_FIRST_STEP: usize = 3;
+
+/// Builds a trace in which the second column contains the public sequence (and zeros elsewhere),
+/// and the third column accumulates the values of the second column.
+fn build_public_sequence_trace(
+    trace_len: usize,
+    sequence: &PublicSequence<BaseElement>,
+) -> TraceTable<BaseElement> {
+    let counter = (0..trace_len).map(|i| BaseElement::from(i as u32)).collect();
+    let mut inputs = vec![BaseElement::ZERO; trace_len];
+    inputs[SEQUENCE_FIRST_STEP..SEQUENCE_FIRST_STEP + sequence.len()]
+        .copy_from_slice(sequence.values());
+    let mut sums = vec![BaseElement::ZERO];
+    for i in 1..trace_len {
+        sums.push(sums[i - 1] + inputs[i - 1]);
+    }
+    TraceTable::init(vec![counter, inputs, sums])
+}
+
+#[derive(Clone)]
+struct PublicSequenceInputs {
+    sequence: PublicSequence<BaseElement>,
+    sum: BaseElement,
+}
+
+impl ToElements<BaseElement> for PublicSequenceInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.sequence.to_elements();
+        result.push(self.sum);
+        result
+    }
+}
+
+impl Serializable for PublicSequenceInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.sequence);
+        target.write(self.sum);
+    }
+}
+
+/// Binds the second column of the trace to a public sequence, and enforces that the third column
+/// accumulates the values of the second column.
+struct PublicSequenceAir {
+    context: AirContext<BaseElement>,
+    sequence: PublicSequence<BaseElement>,
+    sum: BaseElement,
+}
+
+impl Air for PublicSequenceAir {
+    type BaseField = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    type PublicInputs = PublicSequenceInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
+        Self {
+            context: AirContext::new(trace_info, degrees, 4, options),
+            sequence: pub_inputs.sequence,
+            sum: pub_inputs.sum,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: math::FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0] - E::ONE;
+        result[1] = frame.next()[2] - frame.current()[2] - frame.current()[1];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            self.sequence.to_assertion(1, SEQUENCE_FIRST_STEP),
+            Assertion::single(2, 0, BaseElement::ZERO),
+            Assertion::single(2, last_step, self.sum),
+        ]
+    }
+}
+
+struct PublicSequenceProver {
+    options: ProofOptions,
+    sequence: PublicSequence<BaseElement>,
+}
+
+impl PublicSequenceProver {
+    fn new(options: ProofOptions, sequence: PublicSequence<BaseElement>) -> Self {
+        Self { options, sequence }
+    }
+}
+
+impl Prover for PublicSequenceProver {
+    type BaseField = BaseElement;
+    type Air = PublicSequenceAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, PublicSequenceAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+        PublicSequenceInputs {
+            sequence: self.sequence.clone(),
+            sum: trace.get(2, trace.length() - 1),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+}
+
+// AIR COMPOSITION
+// ================================================================================================
+
+/// Composition of [PublicSequenceAir] and [CustomDivisorAir].
+type ComposedAir = AirComposition<PublicSequenceAir, CustomDivisorAir>;
+
+struct ComposedProver {
+    options: ProofOptions,
+    sequence: PublicSequence<BaseElement>,
+}
+
+impl ComposedProver {
+    fn new(options: ProofOptions, sequence: PublicSequence<BaseElement>) -> Self {
+        Self { options, sequence }
+    }
+}
+
+impl Prover for ComposedProver {
+    type BaseField = BaseElement;
+    type Air = ComposedAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, ComposedAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+        // the accumulator of the first component is in the third column of the composed trace
+        ComposedInputs {
+            first: PublicSequenceInputs {
+                sequence: self.sequence.clone(),
+                sum: trace.get(2, trace.length() - 1),
+            },
+            second: (),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+}
+
+// PERIODIC OFFSET AIR
+// ================================================================================================
+
+/// Builds a trace with a single column which accumulates the values of a periodic column with
+/// values `[1, 2, ..., cycle_length]` whose cycle starts at step `offset`.
+fn build_periodic_offset_trace(
+    trace_len: usize,
+    pub_inputs: &PeriodicOffsetInputs,
+) -> TraceTable<BaseElement> {
+    let mut sums = vec![BaseElement::ZERO];
+    for i in 1..trace_len {
+        sums.push(sums[i - 1] + pub_inputs.periodic_value(i - 1));
+    }
+    TraceTable::init(vec![sums])
+}
+
+#[derive(Clone)]
+struct PeriodicOffsetInputs {
+    cycle_length: usize,
+    offset: usize,
+}
+
+impl PeriodicOffsetInputs {
+    /// Returns the value of the periodic column at the specified step.
+    fn periodic_value(&self, step: usize) -> BaseElement {
+        let position =
+            (step + self.cycle_length - self.offset % self.cycle_length) % self.cycle_length;
+        BaseElement::from(position as u32 + 1)
+    }
+}
+
+impl ToElements<BaseElement> for PeriodicOffsetInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![
+            BaseElement::from(self.cycle_length as u32),
+            BaseElement::from(self.offset as u32),
+        ]
+    }
+}
+
+impl Serializable for PeriodicOffsetInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.cycle_length);
+        target.write_usize(self.offset);
+    }
 }
 
-impl LagrangeComplexTrace {
-    fn new(trace_len: usize, aux_segment_width: usize) -> Self {
-        assert!(trace_len < u32::MAX.try_into().unwrap());
+/// Enforces that the trace column accumulates the values of a periodic column; both the length
+/// of the cycle and the step at which the cycle starts are defined by the public inputs.
+struct PeriodicOffsetAir {
+    context: AirContext<BaseElement>,
+    pub_inputs: PeriodicOffsetInputs,
+}
 
-        let main_trace_col: Vec<BaseElement> =
-            (0..trace_len).map(|idx| BaseElement::from(idx as u32)).collect();
+impl Air for PeriodicOffsetAir {
+    type BaseField = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
 
-        Self {
-            main_trace: ColMatrix::new(vec![main_trace_col]),
-            info: TraceInfo::new_multi_segment(1, aux_segment_width, 0, trace_len, vec![]),
-        }
+    type PublicInputs = PeriodicOffsetInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        let context = AirContext::new(trace_info, degrees, 2, options);
+        Self { context, pub_inputs }
     }
 
-    fn len(&self) -> usize {
-        self.main_trace.num_rows()
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
     }
-}
 
-impl Trace for LagrangeComplexTrace {
-    type BaseField = BaseElement;
+    fn evaluate_transition<E: math::FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0] - periodic_values[0];
+    }
 
-    fn info(&self) -> &TraceInfo {
-        &self.info
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        let sum = (0..last_step)
+            .fold(BaseElement::ZERO, |sum, i| sum + self.pub_inputs.periodic_value(i));
+        vec![Assertion::single(0, 0, BaseElement::ZERO), Assertion::single(0, last_step, sum)]
     }
 
-    fn main_segment(&self) -> &ColMatrix<Self::BaseField> {
-        &self.main_trace
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let cycle_length = self.pub_inputs.cycle_length as u32;
+        vec![(1..=cycle_length).map(BaseElement::from).collect()]
     }
 
-    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
-        let next_row_idx = row_idx + 1;
-        assert_ne!(next_row_idx, self.len());
+    fn get_periodic_column_offsets(&self) -> Vec<usize> {
+        vec![self.pub_inputs.offset]
+    }
+}
 
-        self.main_trace.read_row_into(row_idx, frame.current_mut());
-        self.main_trace.read_row_into(next_row_idx, frame.next_mut());
+struct PeriodicOffsetProver {
+    options: ProofOptions,
+    pub_inputs: PeriodicOffsetInputs,
+}
+
+impl PeriodicOffsetProver {
+    fn new(options: ProofOptions, pub_inputs: PeriodicOffsetInputs) -> Self {
+        Self { options, pub_inputs }
     }
 }
 
-// AIR
-// =================================================================================================
+impl Prover for PeriodicOffsetProver {
+    type BaseField = BaseElement;
+    type Air = PeriodicOffsetAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, PeriodicOffsetAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
 
-#[derive(Debug, Clone, Default)]
-struct DummyGkrVerifier;
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+        self.pub_inputs.clone()
+    }
 
-impl GkrVerifier for DummyGkrVerifier {
-    // `GkrProof` is log(trace_len) for this dummy example, so that the verifier knows how many aux
-    // random variables to generate
-    type GkrProof = usize;
-    type Error = VerifierError;
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
 
-    fn verify<E, Hasher>(
+    fn new_trace_lde<E>(
         &self,
-        gkr_proof: usize,
-        public_coin: &mut impl RandomCoin<BaseField = E::BaseField, Hasher = Hasher>,
-    ) -> Result<LagrangeKernelRandElements<E>, Self::Error>
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
     where
-        E: FieldElement,
-        Hasher: crypto::ElementHasher<BaseField = E::BaseField>,
+        E: math::FieldElement<BaseField = Self::BaseField>,
     {
-        let log_trace_len = gkr_proof;
-        let lagrange_kernel_rand_elements: LagrangeKernelRandElements<E> = {
-            let mut rand_elements = Vec::with_capacity(log_trace_len);
-            for _ in 0..log_trace_len {
-                rand_elements.push(public_coin.draw().unwrap());
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
+    where
+        E: math::FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintCommitment::new(composition_poly, domain)
+    }
+}
+
+// DYNAMIC WIDTH AIR
+// ================================================================================================
+
+/// Builds a trace with the specified number of registers in which register `i` is incremented
+/// by `i + 1` on every step.
+fn build_dynamic_width_trace(trace_len: usize, num_registers: usize) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::new(num_registers, trace_len);
+    trace.fill(
+        |state| state.fill(BaseElement::ZERO),
+        |_, state| {
+            for (i, value) in state.iter_mut().enumerate() {
+                *value += BaseElement::from(i as u32 + 1);
             }
+        },
+    );
+    trace
+}
 
-            LagrangeKernelRandElements::new(rand_elements)
-        };
+struct DynamicWidthInputs {
+    num_registers: usize,
+}
 
-        Ok(lagrange_kernel_rand_elements)
+impl ToElements<BaseElement> for DynamicWidthInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![BaseElement::from(self.num_registers as u32)]
     }
 }
 
-struct LagrangeKernelComplexAir {
+impl Serializable for DynamicWidthInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.num_registers);
+    }
+}
+
+/// Describes a machine whose number of registers is defined by the public inputs; thus, the
+/// width of the execution trace, as well as the number of constraints and assertions, is known
+/// only at runtime.
+struct DynamicWidthAir {
     context: AirContext<BaseElement>,
 }
 
-impl Air for LagrangeKernelComplexAir {
+impl Air for DynamicWidthAir {
     type BaseField = BaseElement;
-    // `GkrProof` is log(trace_len) for this dummy example, so that the verifier knows how many aux
-    // random variables to generate
-    type GkrProof = usize;
-    type GkrVerifier = DummyGkrVerifier;
+    type GkrProof = ();
+    type GkrVerifier = ();
 
-    type PublicInputs = ();
+    type PublicInputs = DynamicWidthInputs;
 
-    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
-        Self {
-            context: AirContext::new_multi_segment(
-                trace_info,
-                vec![TransitionConstraintDegree::new(1)],
-                vec![TransitionConstraintDegree::new(1)],
-                1,
-                1,
-                Some(1),
-                options,
-            ),
-        }
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let num_registers = pub_inputs.num_registers;
+        assert_eq!(num_registers, trace_info.main_trace_width());
+        let degrees = vec![TransitionConstraintDegree::new(1); num_registers];
+        let context = AirContext::new(trace_info, degrees, 2 * num_registers, options);
+        Self { context }
     }
 
     fn context(&self) -> &AirContext<Self::BaseField> {
@@ -152,73 +2998,48 @@ impl Air for LagrangeKernelComplexAir {
         _periodic_values: &[E],
         result: &mut [E],
     ) {
-        let current = frame.current()[0];
-        let next = frame.next()[0];
-
-        // increments by 1
-        result[0] = next - current - E::ONE;
+        debug_assert_eq!(result.len(), frame.num_columns());
+        for (i, value) in result.iter_mut().enumerate() {
+            *value = frame.next()[i] - frame.current()[i] - E::from(i as u32 + 1);
+        }
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
-        vec![Assertion::single(0, 0, BaseElement::ZERO)]
-    }
-
-    fn evaluate_aux_transition<F, E>(
-        &self,
-        _main_frame: &EvaluationFrame<F>,
-        _aux_frame: &EvaluationFrame<E>,
-        _periodic_values: &[F],
-        _aux_rand_elements: &[E],
-        _result: &mut [E],
-    ) where
-        F: FieldElement<BaseField = Self::BaseField>,
-        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
-    {
-        // do nothing
-    }
-
-    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
-        &self,
-        _aux_rand_elements: &[E],
-    ) -> Vec<Assertion<E>> {
-        vec![Assertion::single(0, 0, E::ZERO)]
-    }
-
-    fn get_auxiliary_proof_verifier<E: FieldElement<BaseField = Self::BaseField>>(
-        &self,
-    ) -> Self::GkrVerifier {
-        DummyGkrVerifier
+        let last_step = self.trace_length() - 1;
+        let mut result = Vec::new();
+        for i in 0..self.trace_info().main_trace_width() {
+            let last_value = BaseElement::from(((i + 1) * last_step) as u32);
+            result.push(Assertion::single(i, 0, BaseElement::ZERO));
+            result.push(Assertion::single(i, last_step, last_value));
+        }
+        result
     }
 }
 
-// LagrangeComplexProver
-// ================================================================================================
-
-struct LagrangeComplexProver {
-    aux_trace_width: usize,
+struct DynamicWidthProver {
     options: ProofOptions,
 }
 
-impl LagrangeComplexProver {
-    fn new(aux_trace_width: usize) -> Self {
-        Self {
-            aux_trace_width,
-            options: ProofOptions::new(1, 2, 0, FieldExtension::None, 2, 1),
-        }
+impl DynamicWidthProver {
+    fn new(options: ProofOptions) -> Self {
+        Self { options }
     }
 }
 
-impl Prover for LagrangeComplexProver {
+impl Prover for DynamicWidthProver {
     type BaseField = BaseElement;
-    type Air = LagrangeKernelComplexAir;
-    type Trace = LagrangeComplexTrace;
+    type Air = DynamicWidthAir;
+    type Trace = TraceTable<BaseElement>;
     type HashFn = Blake3_256<BaseElement>;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
     type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Self::HashFn>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
-        DefaultConstraintEvaluator<'a, LagrangeKernelComplexAir, E>;
+        DefaultConstraintEvaluator<'a, DynamicWidthAir, E>;
+    type ConstraintCommitment<E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintCommitment<E, Self::HashFn>;
 
-    fn get_pub_inputs(&self, _trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> <<Self as Prover>::Air as Air>::PublicInputs {
+        DynamicWidthInputs { num_registers: trace.main_trace_width() }
     }
 
     fn options(&self) -> &ProofOptions {
@@ -249,77 +3070,49 @@ impl Prover for LagrangeComplexProver {
         DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
     }
 
-    fn generate_gkr_proof<E>(
+    fn new_constraint_commitment<E>(
         &self,
-        main_trace: &Self::Trace,
-        public_coin: &mut Self::RandomCoin,
-    ) -> (ProverGkrProof<Self>, LagrangeKernelRandElements<E>)
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Self::ConstraintCommitment<E>
     where
-        E: FieldElement<BaseField = Self::BaseField>,
+        E: math::FieldElement<BaseField = Self::BaseField>,
     {
-        let main_trace = main_trace.main_segment();
-        let log_trace_len = main_trace.num_rows().ilog2() as usize;
-        let lagrange_kernel_rand_elements: Vec<E> = {
-            let mut rand_elements = Vec::with_capacity(log_trace_len);
-            for _ in 0..log_trace_len {
-                rand_elements.push(public_coin.draw().unwrap());
-            }
-
-            rand_elements
-        };
-
-        (log_trace_len, LagrangeKernelRandElements::new(lagrange_kernel_rand_elements))
+        DefaultConstraintCommitment::new(composition_poly, domain)
     }
+}
 
-    fn build_aux_trace<E>(
-        &self,
-        main_trace: &Self::Trace,
-        aux_rand_elements: &AuxRandElements<E>,
-    ) -> ColMatrix<E>
-    where
-        E: FieldElement<BaseField = Self::BaseField>,
-    {
-        let main_trace = main_trace.main_segment();
-        let lagrange_kernel_rand_elements = aux_rand_elements
-            .lagrange()
-            .expect("expected lagrange random elements to be present.");
-
-        let mut columns = Vec::new();
+/// Records the name of each proof generation phase together with the main size reported for it.
+struct EventRecorder(Mutex<Vec<(&'static str, usize)>>);
 
-        // First all other auxiliary columns
-        let rand_summed = lagrange_kernel_rand_elements.iter().fold(E::ZERO, |acc, &r| acc + r);
-        for _ in 1..self.aux_trace_width {
-            // building a dummy auxiliary column
-            let column = main_trace
-                .get_column(0)
-                .iter()
-                .map(|row_val| rand_summed.mul_base(*row_val))
-                .collect();
+impl EventRecorder {
+    fn record(&self, event: &'static str, size: usize) {
+        self.0.lock().unwrap().push((event, size));
+    }
+}
 
-            columns.push(column);
-        }
+impl ProverObserver for EventRecorder {
+    fn on_trace_built(&self, trace_info: &TraceInfo, _duration: Duration) {
+        self.record("trace_built", trace_info.length());
+    }
 
-        // then build the Lagrange kernel column
-        {
-            let r = &lagrange_kernel_rand_elements;
+    fn on_trace_committed(&self, _trace_info: &TraceInfo, lde_domain_size: usize, _: Duration) {
+        self.record("trace_committed", lde_domain_size);
+    }
 
-            let mut lagrange_col = Vec::with_capacity(main_trace.num_rows());
+    fn on_constraints_evaluated(&self, ce_domain_size: usize, _duration: Duration) {
+        self.record("constraints_evaluated", ce_domain_size);
+    }
 
-            for row_idx in 0..main_trace.num_rows() {
-                let mut row_value = E::ONE;
-                for (bit_idx, &r_i) in r.iter().enumerate() {
-                    if row_idx & (1 << bit_idx) == 0 {
-                        row_value *= E::ONE - r_i;
-                    } else {
-                        row_value *= r_i;
-                    }
-                }
-                lagrange_col.push(row_value);
-            }
+    fn on_constraints_committed(&self, num_columns: usize, _duration: Duration) {
+        self.record("constraints_committed", num_columns);
+    }
 
-            columns.push(lagrange_col);
-        }
+    fn on_fri_layer_committed(&self, _layer_idx: usize, domain_size: usize, _: Duration) {
+        self.record("fri_layer_committed", domain_size);
+    }
 
-        ColMatrix::new(columns)
+    fn on_queries_built(&self, _num_queries: usize, proof_size: usize, _duration: Duration) {
+        self.record("queries_built", proof_size);
     }
 }